@@ -111,4 +111,21 @@ fn main() {
         assert!((-1.0..=-0.5).contains(&value));
         Ok(())
     });
+
+    // `arbitrary_invalid()` generates values beyond the bounds along with the expected error.
+    arbtest::builder().run(|u| {
+        let (value, err) = GreaterAndLessF32::arbitrary_invalid(u)?;
+        match err {
+            GreaterAndLessF32Error::GreaterViolated => assert!(value <= -10.0),
+            GreaterAndLessF32Error::LessViolated => assert!(value >= 10.0),
+        }
+        Ok(())
+    });
+
+    arbtest::builder().run(|u| {
+        let (value, err) = FiniteF64::arbitrary_invalid(u)?;
+        assert!(!value.is_finite());
+        assert_eq!(err, FiniteF64Error::FiniteViolated);
+        Ok(())
+    });
 }
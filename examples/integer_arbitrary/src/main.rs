@@ -70,4 +70,21 @@ fn main() {
         assert!(value == 0 || value == 1 || value == 2);
         Ok(())
     });
+
+    // `arbitrary_invalid()` generates values beyond the bounds along with the expected error.
+    arbtest::builder().run(|u| {
+        let (value, err) = GermanTaxClass::arbitrary_invalid(u)?;
+        match err {
+            GermanTaxClassError::GreaterOrEqualViolated => assert!(value < 1),
+            GermanTaxClassError::LessOrEqualViolated => assert!(value > 6),
+        }
+        Ok(())
+    });
+
+    arbtest::builder().run(|u| {
+        let (value, err) = GreaterThan125::arbitrary_invalid(u)?;
+        assert!(value <= 125);
+        assert_eq!(err, GreaterThan125Error::GreaterViolated);
+        Ok(())
+    });
 }
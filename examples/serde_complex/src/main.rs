@@ -79,7 +79,7 @@ fn main() {
         let err = res.unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Price is too small. The value must be greater than 0.0. Expected valid Price at line 6 column 13"
+            "Price is too small. The value must be greater than 0.0 and less than 1000000.0. Expected valid Price at line 6 column 13"
         );
     }
 
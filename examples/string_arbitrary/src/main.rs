@@ -9,6 +9,7 @@ fn main() {
     should_respect_not_empty_validation_without_trim();
     should_respect_len_char_max();
     should_respec_both_len_boundaries();
+    should_generate_invalid_strings();
 }
 
 fn should_generate_arbitrary_string_without_validation_with_respect_to_sanitizers() {
@@ -79,3 +80,24 @@ fn should_respec_both_len_boundaries() {
         Ok(())
     });
 }
+
+fn should_generate_invalid_strings() {
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_min = 3, len_char_max = 5),
+        derive(Arbitrary, Debug)
+    )]
+    struct Text(String);
+
+    arbtest(|u| {
+        let (value, err) = Text::arbitrary_invalid(u)?;
+        let len = value.chars().count();
+        match err {
+            TextError::NotEmptyViolated => assert_eq!(len, 0),
+            TextError::LenCharMinViolated => assert!((1..3).contains(&len)),
+            TextError::LenCharMaxViolated => assert!(len > 5),
+        }
+        assert_eq!(Text::try_new(value).unwrap_err(), err);
+        Ok(())
+    });
+}
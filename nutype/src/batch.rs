@@ -0,0 +1,45 @@
+use core::fmt;
+
+/// The error of `try_from_iter()`: the index of the first invalid item and why it was rejected.
+///
+/// ```
+/// use nutype::{nutype, BatchError};
+///
+/// #[nutype(validate(less_or_equal = 150), derive(Debug))]
+/// pub struct Age(u8);
+///
+/// let ages: Result<Vec<Age>, _> = Age::try_from_iter([7, 42, 200, 255]);
+/// assert_eq!(
+///     ages.unwrap_err(),
+///     BatchError { index: 2, error: AgeError::LessOrEqualViolated }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchError<E> {
+    /// Index of the invalid item, starting from 0.
+    pub index: usize,
+
+    /// The error returned by `try_new()` for the item.
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for BatchError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Item {} is invalid: {}", self.index, self.error)
+    }
+}
+
+// NOTE: `::core::error::Error` is stable only for rust >= 1.81.0.
+#[cfg(ERROR_IN_CORE)]
+impl<E: core::error::Error + 'static> core::error::Error for BatchError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(all(not(ERROR_IN_CORE), feature = "std"))]
+impl<E: std::error::Error + 'static> std::error::Error for BatchError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
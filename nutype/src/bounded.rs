@@ -0,0 +1,135 @@
+use core::{borrow::Borrow, cmp::Ordering, fmt, ops::Deref};
+
+/// An integer that is guaranteed to be within `MIN..=MAX`.
+///
+/// It is the storage type of integer newtypes declared with the `bounded` attribute.
+/// The bounds are a part of the type, so code that is generic over bounded integers
+/// can rely on them, and the trait implementations are shared by all such newtypes
+/// instead of being generated for every one of them.
+///
+/// ```
+/// use nutype::Bounded;
+///
+/// type Percent = Bounded<0, 100, u8>;
+///
+/// assert_eq!(Percent::try_new(42).unwrap().get(), 42);
+/// assert!(Percent::try_new(101).is_err());
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<const MIN: i128, const MAX: i128, T>(T);
+
+impl<const MIN: i128, const MAX: i128, T: BoundedInteger> Bounded<MIN, MAX, T> {
+    /// Inclusive lower bound.
+    pub const MIN: i128 = MIN;
+
+    /// Inclusive upper bound.
+    pub const MAX: i128 = MAX;
+
+    /// Returns the value if it is within `MIN..=MAX`.
+    pub fn try_new(value: T) -> Result<Self, BoundedError> {
+        if value.cmp_bound(MIN) == Ordering::Less {
+            Err(BoundedError::BelowMin)
+        } else if value.cmp_bound(MAX) == Ordering::Greater {
+            Err(BoundedError::AboveMax)
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// Wraps the value without checking the bounds.
+    /// The caller is responsible to ensure that the value is within `MIN..=MAX`.
+    pub const fn new_unchecked(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the integer.
+    pub fn get(&self) -> T {
+        self.0
+    }
+}
+
+impl<const MIN: i128, const MAX: i128, T> Deref for Bounded<MIN, MAX, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<const MIN: i128, const MAX: i128, T> AsRef<T> for Bounded<MIN, MAX, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<const MIN: i128, const MAX: i128, T> Borrow<T> for Bounded<MIN, MAX, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<const MIN: i128, const MAX: i128, T: fmt::Debug> fmt::Debug for Bounded<MIN, MAX, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<const MIN: i128, const MAX: i128, T: fmt::Display> fmt::Display for Bounded<MIN, MAX, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The value is out of the bounds of [`Bounded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedError {
+    /// The value is less than `MIN`.
+    BelowMin,
+    /// The value is greater than `MAX`.
+    AboveMax,
+}
+
+impl fmt::Display for BoundedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoundedError::BelowMin => write!(f, "The value is less than the lower bound."),
+            BoundedError::AboveMax => write!(f, "The value is greater than the upper bound."),
+        }
+    }
+}
+
+// NOTE: `::core::error::Error` is stable only for rust >= 1.81.0.
+#[cfg(ERROR_IN_CORE)]
+impl core::error::Error for BoundedError {}
+
+#[cfg(all(not(ERROR_IN_CORE), feature = "std"))]
+impl std::error::Error for BoundedError {}
+
+/// Primitive integer types that can be stored in [`Bounded`].
+pub trait BoundedInteger: Copy {
+    /// Compares the value with a bound.
+    fn cmp_bound(self, bound: i128) -> Ordering;
+}
+
+macro_rules! impl_bounded_integer {
+    ($($t:ty),*) => {
+        $(
+            impl BoundedInteger for $t {
+                #[inline]
+                fn cmp_bound(self, bound: i128) -> Ordering {
+                    // Only `u128` values above `i128::MAX` fail to convert.
+                    match i128::try_from(self) {
+                        Ok(value) => value.cmp(&bound),
+                        Err(_) => Ordering::Greater,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
@@ -0,0 +1,161 @@
+//! Text encodings of fixed-size byte arrays, used by `[u8; N]` based types with
+//! `encoding = "hex"` or `encoding = "base64"`.
+
+use core::{fmt, str::FromStr};
+
+/// The error of decoding a hex or base64 string into `[u8; N]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The string does not decode into exactly `expected` bytes.
+    InvalidLength { expected: usize },
+
+    /// The character at `index` (in bytes) is not allowed at its position.
+    InvalidCharacter { index: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength { expected } => {
+                write!(f, "Expected an encoding of exactly {expected} bytes")
+            }
+            DecodeError::InvalidCharacter { index } => {
+                write!(f, "Invalid character at index {index}")
+            }
+        }
+    }
+}
+
+// NOTE: `::core::error::Error` is stable only for rust >= 1.81.0.
+#[cfg(ERROR_IN_CORE)]
+impl core::error::Error for DecodeError {}
+
+#[cfg(all(not(ERROR_IN_CORE), feature = "std"))]
+impl std::error::Error for DecodeError {}
+
+/// `[u8; N]` written as lowercase hex digits. Uppercase digits are accepted when parsed.
+///
+/// ```
+/// use nutype::Hex;
+///
+/// let Hex(bytes) = "00ff7A".parse::<Hex<3>>().unwrap();
+/// assert_eq!(bytes, [0x00, 0xff, 0x7a]);
+/// assert_eq!(Hex(bytes).to_string(), "00ff7a");
+/// ```
+#[cfg(feature = "hex")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hex<const N: usize>(pub [u8; N]);
+
+#[cfg(feature = "hex")]
+impl<const N: usize> FromStr for Hex<N> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn digit(s: &[u8], index: usize) -> Result<u8, DecodeError> {
+            match s[index] {
+                c @ b'0'..=b'9' => Ok(c - b'0'),
+                c @ b'a'..=b'f' => Ok(c - b'a' + 10),
+                c @ b'A'..=b'F' => Ok(c - b'A' + 10),
+                _ => Err(DecodeError::InvalidCharacter { index }),
+            }
+        }
+
+        let s = s.as_bytes();
+        if s.len() != N * 2 {
+            return Err(DecodeError::InvalidLength { expected: N });
+        }
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = (digit(s, 2 * i)? << 4) | digit(s, 2 * i + 1)?;
+        }
+        Ok(Hex(bytes))
+    }
+}
+
+#[cfg(feature = "hex")]
+impl<const N: usize> fmt::Display for Hex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "base64")]
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// `[u8; N]` written in the standard base64 alphabet with `=` padding.
+///
+/// ```
+/// use nutype::Base64;
+///
+/// let Base64(bytes) = "aGV5".parse::<Base64<3>>().unwrap();
+/// assert_eq!(&bytes, b"hey");
+/// assert_eq!(Base64(*b"hi").to_string(), "aGk=");
+/// ```
+#[cfg(feature = "base64")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Base64<const N: usize>(pub [u8; N]);
+
+#[cfg(feature = "base64")]
+impl<const N: usize> FromStr for Base64<N> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        if s.len() != N.div_ceil(3) * 4 {
+            return Err(DecodeError::InvalidLength { expected: N });
+        }
+        // Number of `=` at the end, that is determined by the number of bytes.
+        let padding = (3 - N % 3) % 3;
+        let mut bytes = [0; N];
+        let mut len = 0;
+        let mut buffer: u32 = 0;
+        for (index, &c) in s.iter().enumerate() {
+            let is_padding = index >= s.len() - padding;
+            let sextet = match c {
+                b'=' if is_padding => 0,
+                _ if is_padding => return Err(DecodeError::InvalidCharacter { index }),
+                _ => BASE64_ALPHABET
+                    .iter()
+                    .position(|&a| a == c)
+                    .ok_or(DecodeError::InvalidCharacter { index })? as u32,
+            };
+            buffer = (buffer << 6) | sextet;
+            if index % 4 == 3 {
+                for byte in buffer.to_be_bytes()[1..].iter() {
+                    if len < N {
+                        bytes[len] = *byte;
+                        len += 1;
+                    }
+                }
+                buffer = 0;
+            }
+        }
+        Ok(Base64(bytes))
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<const N: usize> fmt::Display for Base64<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        for chunk in self.0.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let buffer = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let sextet = (buffer >> (18 - 6 * i)) & 0b11_1111;
+                    f.write_char(BASE64_ALPHABET[sextet as usize] as char)?;
+                } else {
+                    f.write_char('=')?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -52,6 +52,8 @@
 //! ```
 //!
 //! Haha. It's does not seem to be easy!
+//! This holds even for the code in the module where `Username` is declared, because the struct is
+//! generated inside a hidden inner module.
 //!
 //!
 //! ## A few more examples
@@ -89,7 +91,17 @@
 //!
 //! ## String
 //!
-//! At the moment the string inner type supports only `String` (owned) type.
+//! The string inner type can be `String`, or a borrowed `&'a str` and `Cow<'a, str>` for zero-allocation wrappers:
+//!
+//! ```rust
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(not_empty))]
+//! pub struct Name<'a>(&'a str);
+//! ```
+//!
+//! A `&'a str` can only be narrowed down, so `trim` is the only sanitizer it supports and `FromStr` and `Deserialize`
+//! cannot be derived for it. `Cow<'a, str>` supports all the sanitizers, but the ones other than `trim` allocate.
 //!
 //! ### String sanitizers
 //!
@@ -98,6 +110,10 @@
 //! | `trim`      | Removes leading and trailing whitespaces                                            | `trim`                                          |
 //! | `lowercase` | Converts the string to lowercase                                                    | `lowercase`                                     |
 //! | `uppercase` | Converts the string to uppercase                                                    | `uppercase`                                     |
+//! | `canonicalize_number` | Brings a formatted number to a canonical form: removes thousands separators and leading zeros, unifies the decimal separator to `.`. Ambiguous numbers like `1,234` and irregular groups like `1.2.3` are only trimmed | `canonicalize_number` |
+//! | `snake_case` | Converts an identifier to `snake_case`, e.g. `createdAt` becomes `created_at`. Requires `unicode` feature. | `snake_case` |
+//! | `kebab_case` | Converts an identifier to `kebab-case`, e.g. `HTTPServer` becomes `http-server`. Requires `unicode` feature. | `kebab_case` |
+//! | `slugify` | Turns a text into a slug for URLs of `[a-z0-9-]`, splitting words like `kebab_case`, e.g. `Hello, World!` becomes `hello-world`. Letters are decomposed without diacritics and transliterated (`Café` becomes `cafe`, `ǅemal` becomes `dzemal`), other non-ASCII characters are dropped. Requires `unicode` feature. | `slugify` |
 //! | `with`      | Custom sanitizer. A function or closure that receives `String` and returns `String` | `with = \|mut s: String\| { s.truncate(5); s }` |
 //!
 //! ### String validators
@@ -106,11 +122,77 @@
 //! |----------------|---------------------------------------------------------------------------------|----------------------|----------------------------------------------|
 //! | `len_char_min` | Min length of the string (in chars, not bytes)                                  | `LenCharMinViolated` | `len_char_min = 5`                           |
 //! | `len_char_max` | Max length of the string (in chars, not bytes)                                  | `LenCharMaxViolated` | `len_char_max = 255`                         |
+//! | `len_byte_min` | Min length of the string in bytes (UTF-8 encoded)                               | `LenByteMinViolated` | `len_byte_min = 1`                           |
+//! | `len_byte_max` | Max length of the string in bytes (UTF-8 encoded)                               | `LenByteMaxViolated` | `len_byte_max = 255`                         |
+//! | `len_grapheme_min` | Min length of the string in grapheme clusters. Requires `unicode-segmentation` feature. | `LenGraphemeMinViolated` | `len_grapheme_min = 3`           |
+//! | `len_grapheme_max` | Max length of the string in grapheme clusters. Requires `unicode-segmentation` feature. | `LenGraphemeMaxViolated` | `len_grapheme_max = 20`          |
 //! | `not_empty`    | Rejects an empty string                                                         | `NotEmptyViolated`   | `not_empty`                                  |
+//! | `lowercase`    | Rejects uppercase characters                                                    | `LowercaseViolated`  | `lowercase`                                  |
+//! | `uppercase`    | Rejects lowercase characters                                                    | `UppercaseViolated`  | `uppercase`                                  |
+//! | `alphanumeric` | Every character must be alphanumeric (Unicode)                                  | `AlphanumericViolated` | `alphanumeric`                             |
+//! | `ascii`        | Every character must be ASCII                                                   | `AsciiViolated`      | `ascii`                                      |
+//! | `no_whitespace` | Rejects whitespace characters                                                  | `NoWhitespaceViolated` | `no_whitespace`                            |
+//! | `hostname`     | Value must be a valid hostname (RFC 1123)                                       | `HostnameViolated`   | `hostname`                                   |
+//! | `starts_with`  | Value must start with the fragment. `not_starts_with` rejects it instead        | `StartsWithViolated(fragment)` | `starts_with = "+"`                |
+//! | `ends_with`    | Value must end with the fragment. `not_ends_with` rejects it instead            | `EndsWithViolated(fragment)` | `ends_with = ".rs"`                  |
+//! | `contains`     | Value must contain the fragment. `not_contains` rejects it instead              | `ContainsViolated(fragment)` | `contains = "@"`                     |
+//! | `in`           | Value must be one of the listed strings                                         | `InViolated`         | `in = ["red", "green", "blue"]`              |
 //! | `regex`        | Validates format with a regex. Requires `regex` feature.                        | `RegexViolated`      | `regex = "^[0-9]{7}$"` or `regex = ID_REGEX` |
 //! | `predicate`    | Custom validator. A function or closure that receives `&str` and returns `bool` | `PredicateViolated`  | `predicate = \|s: &str\| s.contains('@')`    |
 //! | `with`         | Custom validator with a custom error                                            | N/A                  | (see example below)                          |
 //!
+//! #### Length in chars, bytes or graphemes
+//!
+//! `len_char_min` and `len_char_max` count Unicode scalar values (`char`s), which is the default choice for limits shown to humans.
+//! When the limit comes from storage (e.g. a column measured in bytes), use `len_byte_min` and `len_byte_max`.
+//! A single user-perceived character, like `👍🏽`, may consist of several `char`s.
+//! To count such characters as one, enable the `unicode-segmentation` feature, add `unicode-segmentation` crate to the dependencies
+//! and use `len_grapheme_min` and `len_grapheme_max`.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(len_char_max = 3, len_byte_max = 8), derive(Debug))]
+//! pub struct Tag(String);
+//!
+//! // 2 chars, 8 bytes
+//! assert!(Tag::try_new("👍🏽").is_ok());
+//! // 3 chars, 9 bytes
+//! assert_eq!(Tag::try_new("€€€").unwrap_err(), TagError::LenByteMaxViolated);
+//! // 4 chars, 4 bytes
+//! assert_eq!(Tag::try_new("abcd").unwrap_err(), TagError::LenCharMaxViolated);
+//! ```
+//!
+//! #### Content validators
+//!
+//! `lowercase`, `uppercase`, `alphanumeric`, `ascii` and `no_whitespace` enforce the form of a value instead of coercing it
+//! like the `lowercase` and `uppercase` sanitizers do. Characters without case (e.g. digits or `-`) pass both `lowercase` and `uppercase`.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(len_char_min = 2, len_char_max = 2, uppercase, ascii), derive(Debug))]
+//! pub struct CountryCode(String);
+//!
+//! assert!(CountryCode::try_new("UA").is_ok());
+//! assert_eq!(CountryCode::try_new("ua").unwrap_err(), CountryCodeError::UppercaseViolated);
+//! ```
+//!
+//! `starts_with`, `ends_with` and `contains` check a fixed fragment, e.g. a country code or a file extension.
+//! Their negated forms are `not_starts_with`, `not_ends_with` and `not_contains`. The fragment is a string literal
+//! or a `&'static str` constant, and the error variant carries it, e.g. `StartsWithViolated("+49")`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(starts_with = "+49", not_contains = " "), derive(Debug, PartialEq))]
+//! pub struct GermanPhone(String);
+//!
+//! assert!(GermanPhone::try_new("+49301234567").is_ok());
+//! assert_eq!(GermanPhone::try_new("+33123456789"), Err(GermanPhoneError::StartsWithViolated("+49")));
+//! assert_eq!(GermanPhone::try_new("+49 30 1234567"), Err(GermanPhoneError::NotContainsViolated(" ")));
+//! ```
+//!
 //! #### Regex validation
 //!
 //! Requirements:
@@ -200,9 +282,10 @@
 //!
 //! ### Integer sanitizers
 //!
-//! | Sanitizer | Description       | Example                            |
-//! |-----------|-------------------|------------------------------------|
-//! | `with`    | Custom sanitizer. | `with = \|raw\| raw.clamp(0, 100)` |
+//! | Sanitizer | Description                         | Example                            |
+//! |-----------|-------------------------------------|------------------------------------|
+//! | `clamp`   | Clamps the value into `[min, max]`. | `clamp(0, 100)`                    |
+//! | `with`    | Custom sanitizer.                   | `with = \|raw\| raw.clamp(0, 100)` |
 //!
 //! ### Integer validators
 //!
@@ -212,7 +295,11 @@
 //! | `less_or_equal`     | Inclusive upper bound                 | `LessOrEqualViolated`     | `less_or_equal = 99`                 |
 //! | `greater`           | Exclusive lower bound                 | `GreaterViolated`         | `greater = 17`                       |
 //! | `greater_or_equal`  | Inclusive lower bound                 | `GreaterOrEqualViolated`  | `greater_or_equal = 18`              |
+//! | `in`                | Must be one of the listed values      | `InViolated`              | `in = [1, 2, 4, 8]`                  |
 //! | `predicate`         | Custom predicate                      | `PredicateViolated`       | `predicate = \|num\| num % 2 == 0`   |
+//! | `percentage`        | Preset for `0..=100`                  | `GreaterOrEqualViolated`, `LessOrEqualViolated` | `percentage` |
+//! | `port`              | Preset for `1..=65535`                | `GreaterOrEqualViolated`, `LessOrEqualViolated` | `port` |
+//! | `not_reserved`      | Preset for ports `1024..=65535`       | `GreaterOrEqualViolated`, `LessOrEqualViolated` | `not_reserved` |
 //! | `with`              | Custom validator with a custom error  | N/A                       | (see example below)                  |
 //!
 //! ### Integer derivable traits
@@ -231,6 +318,7 @@
 //! | Sanitizer | Description       | Example                                |
 //! |-----------|-------------------|----------------------------------------|
 //! | `with`    | Custom sanitizer. | `with = \|val\| val.clamp(0.0, 100.0)` |
+//! | `round_dp` | Round half away from zero to N decimal places. | `round_dp = 2` |
 //!
 //! ### Float validators
 //!
@@ -240,18 +328,31 @@
 //! | `less_or_equal`    | Inclusive upper bound                | `LessOrEqualViolated`    | `less_or_equal = 100.0`             |
 //! | `greater`          | Exclusive lower bound                | `GreaterViolated`        | `greater = 0.0`                     |
 //! | `greater_or_equal` | Inclusive lower bound                | `GreaterOrEqualViolated` | `greater_or_equal = 0.0`            |
+//! | `in`               | Must be one of the listed values     | `InViolated`             | `in = [0.25, 0.5, 1.0]`             |
 //! | `finite`           | Check against NaN and infinity       | `FiniteViolated`         | `finite`                            |
+//! | `not_nan`          | Check against NaN                    | `NotNanViolated`         | `not_nan`                           |
+//! | `max_dp`           | At most N decimal places             | `MaxDpViolated`          | `max_dp = 2`                        |
+//! | `unit_interval`    | Preset for `not_nan` and `0.0..=1.0` | `NotNanViolated`, `GreaterOrEqualViolated`, `LessOrEqualViolated` | `unit_interval` |
+//! | `percentage`       | Preset for `not_nan` and `0.0..=100.0` | `NotNanViolated`, `GreaterOrEqualViolated`, `LessOrEqualViolated` | `percentage` |
 //! | `predicate`        | Custom predicate                     | `PredicateViolated`      | `predicate = \|val\| val != 50.0`   |
 //! | `with`             | Custom validator with a custom error | N/A                      | (see example below)                 |
 //!
+//! Bounds are number literals written as in Rust (e.g. `greater_or_equal = -273.15` or `less_or_equal = 1.5e3`)
+//! or const expressions. Integer types reject float literals at compile time.
+//!
+//! `round_dp` and `max_dp` accept up to 15 decimal places. Floats are binary, so `1.005_f64` is stored as `1.00499999...`.
+//! Both of them work with the shortest decimal representation of the value (the one printed by `{}`), not with the binary one:
+//! `round_dp = 2` rounds `1.005`, `2.675` and `-1.555` to `1.01`, `2.68` and `-1.56`, and `max_dp = 2` accepts `2.68`.
+//! Values with a large magnitude (e.g. `1e300`), NaN and infinities are kept by `round_dp` as they are.
+//!
 //! ### Float derivable traits
 //!
 //! The following traits can be derived for a float-based type:
 //! `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, `FromStr`, `AsRef`, `Deref`,
 //! `Into`, `From`, `TryFrom`, `Hash`, `Borrow`, `Display`, `Default`, `Serialize`, `Deserialize`.
 //!
-//! It's also possible to derive `Eq` and `Ord` if the validation rules guarantee that `NaN` is excluded.
-//! This can be done by applying `finite` validation. For example:
+//! It's also possible to derive `Eq`, `Ord` and `Hash` with numeric comparison if the validation rules guarantee that `NaN` is excluded.
+//! This can be done by applying `finite` or `not_nan` validation. For example:
 //!
 //! ```rust
 //! use nutype::nutype;
@@ -301,6 +402,36 @@
 //! assert_eq!(numbers.len(), 4);
 //! ```
 //!
+//! The bounds can also be declared in a `where` clause. The error type is not generic,
+//! so `SortedNotEmptyVecError` is the same for every `T`.
+//! `where` clauses may bound only the generic parameters of the struct (e.g. `where T: Ord`).
+//!
+//! Collection based types can derive `IntoIterator` and `Extend`, so they stay convenient without a mutable access
+//! to the inner value, that could break the invariants. `IntoIterator` is implemented for the type and for a reference to it,
+//! and collections of the standard library (`Vec`, `HashSet`, `BTreeMap`, etc.) also get `len()` and `is_empty()`.
+//! `Extend` passes the extended collection through the sanitizers again. `extend()` cannot report an error,
+//! so types with validation get `try_extend()` instead of `Extend`: it returns the validation error
+//! and leaves the value unchanged (none of the items is added) if the extended collection is invalid:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(with = |mut v| { v.sort(); v }),
+//!     validate(predicate = |v| v.len() <= 3),
+//!     derive(Debug, IntoIterator, Extend),
+//! )]
+//! struct Podium(Vec<u32>);
+//!
+//! let mut podium = Podium::try_new(vec![30]).unwrap();
+//! podium.try_extend([10, 20]).unwrap();
+//! assert_eq!(podium.len(), 3);
+//! assert!(podium.try_extend([40]).is_err());
+//!
+//! let places: Vec<u32> = podium.into_iter().collect();
+//! assert_eq!(places, vec![10, 20, 30]);
+//! ```
+//!
 //! ## Custom sanitizers
 //!
 //! You can set custom sanitizers using the `with` option.
@@ -336,6 +467,59 @@
 //! assert_eq!(city.into_inner(), "Old York");
 //! ```
 //!
+//! `with` can be repeated and mixed with the other sanitizers, they are applied in the order they are declared.
+//! Commas, turbofish and nested calls in closures are fine:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(
+//!     with = |s: String| s.replace(",", ""),
+//!     trim,
+//!     with = |s: String| s.split(' ').map(str::to_string).collect::<Vec<_>>().join("-"),
+//! ))]
+//! pub struct Slug(String);
+//!
+//! assert_eq!(Slug::new(" a,b c ").into_inner(), "ab-c");
+//! ```
+//!
+//! A sanitizer, that can fail (e.g. a conversion of a domain name to ASCII), is set with `try_with(func, error = ErrorType)`.
+//! The function returns `Result<Inner, ErrorType>`, and its error is returned by `try_new()` as the `TryWithViolated` variant of the generated error.
+//! With a custom validation error it's converted with `From<ErrorType>` instead.
+//! `try_with` requires `validate(...)`, and all `try_with` sanitizers of a type must have the same error type:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[derive(Debug, Clone, PartialEq, Eq)]
+//! pub struct NonAsciiError;
+//!
+//! impl core::fmt::Display for NonAsciiError {
+//!     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//!         f.write_str("non-ASCII character")
+//!     }
+//! }
+//!
+//! fn to_ascii_lowercase(s: String) -> Result<String, NonAsciiError> {
+//!     if s.is_ascii() { Ok(s.to_ascii_lowercase()) } else { Err(NonAsciiError) }
+//! }
+//!
+//! #[nutype(
+//!     sanitize(trim, try_with(to_ascii_lowercase, error = NonAsciiError)),
+//!     validate(not_empty),
+//!     derive(Debug, PartialEq),
+//! )]
+//! pub struct Hostname(String);
+//!
+//! fn main() {
+//!     assert_eq!(Hostname::try_new(" Example.COM ").unwrap().into_inner(), "example.com");
+//!     assert_eq!(
+//!         Hostname::try_new("bücher.de"),
+//!         Err(HostnameError::TryWithViolated(NonAsciiError)),
+//!     );
+//! }
+//! ```
+//!
 //! ## Custom validation with predicate
 //!
 //! In similar fashion it's possible to define custom validators, but a validation function receives a reference and returns `bool`.
@@ -410,71 +594,1806 @@
 //! pub struct Name(String);
 //! ```
 //!
-//! ### Derive `Eq` and `Ord` on float types
+//! The default value goes through the sanitizers and validators as any other value.
+//! A literal default (e.g. `"guest"` or `-5`) is checked by the macro, so a value that violates
+//! the length, bound, `not_empty` or `in` validators is a compile error.
+//! Other expressions are checked by a generated test, and `Default::default()` panics if the value is invalid:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(len_char_min = 3),
+//!     derive(Default),
+//!     default = "guest",
+//! )]
+//! pub struct Login(String);
+//! ```
+//!
+//! ### Derive `Eq`, `Ord` and `Hash` on float types
 //!
-//! With nutype it's possible to derive `Eq` and `Ord` if there is `finite` validation set.
-//! The `finite` validation ensures that the valid value excludes `NaN`.
+//! With nutype it's possible to derive `Eq`, `Ord` and `Hash` if there is `finite` or `not_nan` validation set.
+//! Both ensure that the valid value excludes `NaN`, `not_nan` still accepts infinity.
 //!
 //! ```
 //! use nutype::nutype;
 //!
 //! #[nutype(
 //!     validate(finite),
-//!     derive(PartialEq, Eq, PartialOrd, Ord),
+//!     derive(PartialEq, Eq, PartialOrd, Ord, Hash),
 //! )]
 //! pub struct Weight(f64);
 //! ```
 //!
-//! ## How to break the constraints?
+//! The values are ordered numerically, `-Infinity` and `Infinity` (when allowed) go at the ends.
+//! `-0.0` and `0.0` are equal and have the same hash, so such types can be used as keys
+//! in `BTreeMap` and `HashMap`. `cmp()` may panic only if NaN was smuggled in with `new_unchecked`.
 //!
-//! It's discouraged, but it's possible to bypass the constraints by enabling `new_unchecked` crate feature and marking a type with `new_unchecked`:
+//! Without `finite` or `not_nan` validation, `Eq` and `Ord` can be derived only with the `total_order` attribute.
+//! It makes `PartialEq`, `Eq`, `PartialOrd` and `Ord` follow the total order of `total_cmp()`, which differs from
+//! the numeric comparison of floats: `-0.0` is not equal to `0.0` (it's less), and NaN is equal to itself (with the same bits)
+//! and greater than any other value (negative NaN is less than `-Infinity`). `total_order` also applies if NaN is excluded by validation.
+//! `Hash` always requires validation against NaN.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(total_order, derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+//! pub struct Reading(f64);
+//!
+//! assert_ne!(Reading::new(-0.0), Reading::new(0.0));
+//! assert_eq!(Reading::new(f64::NAN), Reading::new(f64::NAN));
+//! ```
+//!
+//! ### Parse amounts of money
+//!
+//! With `parse_money` attribute float based types (and custom types that implement `FromStr`, e.g. `Decimal`)
+//! get `::parse_money()` constructor. It removes currency symbols and codes, understands thousands separators,
+//! `.` or `,` as a decimal separator and negative amounts written as `-$5` or `(5.00)`.
+//! Any other use of `-` or parentheses (e.g. `"--5"` or `"5 USD-"`) is rejected with `InvalidAmount`.
+//! An optional `scale` limits the number of digits after the decimal separator.
+//! An amount like `"1,234"` can be read both as 1234 and 1.234, so it's rejected with `AmbiguousSeparator`,
+//! unless the separator is set explicitly with `decimal_separator = '.'` or `decimal_separator = ','`.
 //!
 //! ```
 //! use nutype::nutype;
 //!
 //! #[nutype(
-//!     new_unchecked,
-//!     sanitize(trim),
-//!     validate(len_char_min = 8),
+//!     parse_money(scale = 2),
+//!     validate(greater_or_equal = 0.0),
 //! )]
-//! pub struct Name(String);
+//! pub struct Price(f64);
 //!
-//! // Yes, you're forced to use `unsafe` here, so everyone will point fingers at YOU.
-//! let name = unsafe { Name::new_unchecked(" boo ".to_string()) };
+//! let price = Price::parse_money("$1,234.50").unwrap();
+//! assert_eq!(price.into_inner(), 1234.5);
+//! ```
+//!
+//! ### Custom `Display` format
+//!
+//! By default derived `Display` forwards to the inner value. Use `display` attribute to provide a format string,
+//! where the inner value is passed as the only positional argument:
 //!
-//! // `name` violates the sanitization and validation rules!!!
-//! assert_eq!(name.into_inner(), " boo ");
 //! ```
+//! use nutype::nutype;
 //!
-//! ## Feature flags
+//! #[nutype(
+//!     derive(Display),
+//!     display = "Order #{:06}",
+//! )]
+//! pub struct OrderId(u32);
 //!
-//! * `arbitrary` - enables derive of [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html).
-//! * `new_unchecked` - enables generation of unsafe `::new_unchecked()` function.
-//! * `regex` - allows to use `regex = ` validation on string-based types. Note: your crate also has to explicitly have `regex` within its dependencies.
-//! * `serde` - integrations with [`serde`](https://crates.io/crates/serde) crate. Allows to derive `Serialize` and `Deserialize` traits.
-//! * `schemars08` - allows to derive [`JsonSchema`](https://docs.rs/schemars/0.8.12/schemars/trait.JsonSchema.html) trait of [schemars](https://crates.io/crates/schemars) crate. Note that at the moment validation rules are not respected.
-//! * `std` - enabled by default. Use `default-features = false` to disable.
+//! assert_eq!(OrderId::new(42).to_string(), "Order #000042");
+//! ```
 //!
-//! ## Support Ukrainian military forces 🇺🇦
+//! ### Choose `TryFrom` source types
 //!
-//! Today I live in Berlin, I have the luxury to live a physically safe life.
-//! But I am Ukrainian. The first 25 years of my life I spent in [Kharkiv](https://en.wikipedia.org/wiki/Kharkiv),
-//! the second-largest city in Ukraine, 60km away from the border with russia. Today about [a third of my home city is destroyed](https://www.youtube.com/watch?v=ihoufBFSZds) by russians.
-//! My parents, my relatives and my friends had to survive the artillery and air attack, living for over a month in basements.
+//! By default derived `TryFrom` is implemented for the inner type (for `String` also for `&str`).
+//! Use `try_from(...)` to list the source types explicitly. A source value is converted into the inner type with `Into`,
+//! so only the conversions you need are generated and conflicts with blanket implementations in other crates can be avoided:
 //!
-//! Some of them have managed to evacuate to EU. Some others are trying to live "normal lives" in Kharkiv, doing there daily duties.
-//! And some are at the front line right now, risking their lives every second to protect the rest.
+//! ```
+//! use nutype::nutype;
 //!
-//! I encourage you to donate to [Charity foundation of Serhiy Prytula](https://prytulafoundation.org/en).
-//! Just pick the project you like and donate. This is one of the best-known foundations, you can watch a [little documentary](https://www.youtube.com/watch?v=VlmWqoeub1Q) about it.
-//! Your contribution to the Ukrainian military force is a contribution to my calmness, so I can spend more time developing the project.
+//! #[nutype(
+//!     validate(less_or_equal = 1000),
+//!     derive(Debug, TryFrom),
+//!     try_from(u8, u16, i32),
+//! )]
+//! pub struct Amount(i64);
 //!
-//! Thank you.
-
-// Set `no_std` flag if `std` feature is disabled.
-#![cfg_attr(not(feature = "std"), no_std)]
-
-pub use nutype_macros::nutype;
+//! assert_eq!(Amount::try_from(200u8).unwrap().into_inner(), 200);
+//! assert_eq!(Amount::try_from(1001i32).unwrap_err(), AmountError::LessOrEqualViolated);
+//! ```
+//!
+//! Integer types that may not fit into the inner integer type (e.g. `i64` or `usize` for `u16`) are converted with `TryFrom` instead.
+//! Their `TryFrom` returns `{Type}TryFromError`, which has an `OutOfRange` variant for failed conversions and a `Validate` variant for the validation errors:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(greater_or_equal = 1),
+//!     derive(Debug, TryFrom),
+//!     try_from(i64, usize),
+//! )]
+//! pub struct Quantity(u16);
+//!
+//! assert_eq!(Quantity::try_from(500usize).unwrap().into_inner(), 500);
+//! assert!(matches!(Quantity::try_from(-1i64), Err(QuantityTryFromError::OutOfRange(_))));
+//! assert!(matches!(Quantity::try_from(0i64), Err(QuantityTryFromError::Validate(_))));
+//! ```
+//!
+//! Integer and float types parse `&str` listed in `try_from(...)`: the string is trimmed, parsed into the inner type and validated in one step.
+//! Their `TryFrom<&str>` returns `{Type}ParseError` (the same error as derived `FromStr`), which has a `Parse` variant for failed parsing and a `Validate` variant for the validation errors:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(greater_or_equal = 1, less_or_equal = 65535),
+//!     derive(Debug, TryFrom),
+//!     try_from(&str),
+//! )]
+//! pub struct Port(u32);
+//!
+//! assert_eq!(Port::try_from(" 8080\n").unwrap().into_inner(), 8080);
+//! assert!(matches!(Port::try_from("http"), Err(PortParseError::Parse(_))));
+//! assert!(matches!(Port::try_from("70000"), Err(PortParseError::Validate(_))));
+//! ```
+//!
+//! ### Choose `Into` target types
+//!
+//! Similarly, `into(...)` lists the types that derived `Into` converts to.
+//! The inner value is converted with `Into`, so a newtype can be passed to APIs that expect a wider primitive type:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     derive(Into),
+//!     into(u16, i64, f64),
+//! )]
+//! pub struct Age(u8);
+//!
+//! let age: i64 = Age::new(32).into();
+//! assert_eq!(age, 32);
+//! ```
+//!
+//! ### Infallible `new()` with `guarantee`
+//!
+//! When the last sanitizer is `clamp(min, max)` and the range lies within the bounds of the validators, every sanitized value is valid.
+//! `guarantee` checks that at compile time and generates an infallible `new()` next to `try_new()`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(clamp(0, 100)),
+//!     validate(greater_or_equal = 0, less_or_equal = 100),
+//!     guarantee,
+//!     derive(Debug, PartialEq)
+//! )]
+//! pub struct Percent(u8);
+//!
+//! assert_eq!(Percent::new(150).into_inner(), 100);
+//! assert_eq!(Percent::try_new(42).unwrap().into_inner(), 42);
+//! ```
+//!
+//! If the proof fails, e.g. `clamp(0, 200)` with `less_or_equal = 100`, the macro reports which validator can be violated.
+//! `guarantee` is supported only by integer types: floats can be `NaN`, which `clamp` lets through.
+//!
+//! ### Rename the constructor and the getter
+//!
+//! `constructor = ...` and `getter = ...` give `try_new()` (or `new()` without validation) and `into_inner()` the names of the domain conventions.
+//! The default methods become private, while the derived traits keep working:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     constructor = parse,
+//!     getter = value,
+//!     sanitize(trim, lowercase),
+//!     validate(not_empty),
+//!     derive(Debug, PartialEq)
+//! )]
+//! pub struct Email(String);
+//!
+//! let email = Email::parse(" Bob@Example.com ").unwrap();
+//! assert_eq!(email.value(), "bob@example.com");
+//! ```
+//!
+//! ### Restrict the constructors
+//!
+//! `constructor_visibility = ...` restricts the visibility of the constructors (`try_new()` or `new()`, and the other ones like
+//! `try_from_iter()`, `try_new_into()` or `try_new_at()`), while the type and its getters keep the visibility of the type.
+//! E.g. with `pub(crate)` other crates can read the values, but only this crate can create them.
+//! Relative visibilities like `pub(self)` or `pub(super)` refer to the module of the type:
+//!
+//! ```
+//! mod repository {
+//!     use nutype::nutype;
+//!
+//!     #[nutype(
+//!         constructor_visibility = pub(self),
+//!         validate(greater = 0),
+//!         derive(Debug, Clone, Copy, PartialEq),
+//!     )]
+//!     pub struct UserId(u64);
+//!
+//!     pub fn insert_user() -> UserId {
+//!         UserId::try_new(42).unwrap()
+//!     }
+//! }
+//!
+//! let id = repository::insert_user();
+//! assert_eq!(id.into_inner(), 42);
+//! // repository::UserId::try_new(7); // error: associated function `try_new` is private
+//! ```
+//!
+//! The derived traits, that build values (e.g. `TryFrom`, `FromStr` or `Deserialize`), remain public, so derive them only if it's intended.
+//! `nutype::TryNewtype` is not implemented for such types.
+//!
+//! ### Structs with a named field
+//!
+//! A struct with a single named field works like a tuple struct, and gets an accessor named after the field,
+//! that returns a reference to the inner value:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(not_empty), derive(Debug))]
+//! pub struct Email {
+//!     value: String,
+//! }
+//!
+//! let email = Email::try_new(" bob@example.com ").unwrap();
+//! assert_eq!(email.value(), "bob@example.com");
+//! ```
+//!
+//! ### Pass newtypes across C boundaries with `ffi`
+//!
+//! With `ffi` the newtype is `#[repr(transparent)]`, so it has the same layout as the inner type, and gets `extern "C"` functions:
+//!
+//! * integer and float based types get `ffi_get()`, and `ffi_try_new(value, out)` or `ffi_new(value)` if there is no validation;
+//! * `String` based types get `ffi_as_ptr()` and `ffi_len()` and implement `TryFrom<*const c_char>`, that returns `<Type>FfiError` for a null pointer, invalid UTF-8 or an invalid value.
+//!
+//! The pointer given to `TryFrom<*const c_char>` must be null or point to a NUL-terminated string.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(ffi, validate(less_or_equal = 150), derive(Debug))]
+//! pub struct Age(u8);
+//!
+//! #[nutype(ffi, sanitize(trim), validate(not_empty), derive(Debug))]
+//! pub struct Username(String);
+//!
+//! let mut age = core::mem::MaybeUninit::<Age>::uninit();
+//! assert!(unsafe { Age::ffi_try_new(42, age.as_mut_ptr()) });
+//! assert_eq!(unsafe { age.assume_init() }.ffi_get(), 42);
+//!
+//! let username = Username::try_from(c" alice ".as_ptr()).unwrap();
+//! assert_eq!(username.ffi_len(), 5);
+//! ```
+//!
+//! ### Zero-copy slices with `repr_transparent`
+//!
+//! `repr_transparent` makes the newtype `#[repr(transparent)]` (as `ffi` does) and generates `as_inner_slice()`,
+//! that views a slice of values as a slice of the inner type without copying, e.g. to pass it to a C library or a SIMD routine:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(repr_transparent, validate(less_or_equal = 150), derive(Debug))]
+//! pub struct Age(u8);
+//!
+//! let ages = vec![Age::try_new(7).unwrap(), Age::try_new(42).unwrap()];
+//! assert_eq!(Age::as_inner_slice(&ages), &[7, 42]);
+//! ```
+//!
+//! The other direction skips the sanitizers and validators, so it's generated only together with `new_unchecked`
+//! as `unsafe fn from_inner_slice_unchecked(&[Inner]) -> &[Self]`, with the same safety contract as `::new_unchecked()`.
+//! `repr_transparent` cannot be used together with `store_as` or `storage`.
+//!
+//! ### Validate bytes before allocating with `bytes`
+//!
+//! With `bytes` a `String` based type gets `validate_bytes(&[u8])`, that checks the bytes without building the value,
+//! and `try_from_utf8(Vec<u8>)`, that reuses the allocation of the bytes for the `String`.
+//! Both return `<Type>BytesError`, which is either `Utf8(Utf8Error)` or `Validate(<Type>Error)`.
+//! With no sanitizers or only `trim`, `validate_bytes()` borrows the string from the bytes, so a rejected input is never copied:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(bytes, sanitize(trim), validate(not_empty, len_char_max = 8), derive(Debug))]
+//! pub struct Token(String);
+//!
+//! assert!(Token::validate_bytes(b" abc ").is_ok());
+//! assert!(matches!(
+//!     Token::validate_bytes(b"too long value"),
+//!     Err(TokenBytesError::Validate(TokenError::LenCharMaxViolated)),
+//! ));
+//! assert!(matches!(Token::validate_bytes(&[0xff]), Err(TokenBytesError::Utf8(_))));
+//!
+//! let token = Token::try_from_utf8(b" abc ".to_vec()).unwrap();
+//! assert_eq!(token.into_inner(), "abc");
+//! ```
+//!
+//! ### Export newtypes to JS with `wasm`
+//!
+//! With the `wasm-bindgen` feature, `wasm` exports a string, integer or float based type with [`#[wasm_bindgen]`](https://crates.io/crates/wasm-bindgen),
+//! so the same validated types can be used by a Rust core and a JS front-end.
+//! The type gets `js_new(value)`, that is the JS constructor, and `js_value()`, that is the `value` property in JS.
+//! If the value is invalid, the constructor returns `Err(JsValue)` with the `Display` text of the error, which is thrown in JS:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(wasm, sanitize(trim), validate(not_empty, len_char_max = 20), derive(Debug))]
+//! pub struct Username(String);
+//! ```
+//!
+//! ```js
+//! const username = new Username("  alice ");
+//! console.log(username.value); // "alice"
+//! new Username(" "); // throws "Username is empty."
+//! ```
+//!
+//! ### Refined types with `refines`
+//!
+//! `refines = Username` declares that a type narrows down another newtype with the same inner type.
+//! It implements `TryFrom<Username>`, that takes the value, which is already sanitized and validated by `Username`,
+//! and applies only the validators of the refined type (or `From<Username>` if it has no validation):
+//!
+//! ```
+//! # mod wrapper_module {
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim, lowercase), validate(len_char_min = 3, len_char_max = 20), derive(Debug, AsRef))]
+//! pub struct Username(String);
+//!
+//! #[nutype(refines = Username, validate(len_char_max = 8), derive(Debug, AsRef))]
+//! pub struct PremiumUsername(String);
+//!
+//! # pub fn example() {
+//! let name = Username::try_new("  Alice ").unwrap();
+//! let premium = PremiumUsername::try_from(name).unwrap();
+//! assert_eq!(premium.as_ref(), "alice");
+//! # }
+//! # }
+//! # wrapper_module::example();
+//! ```
+//!
+//! A refined type cannot have sanitizers, because the value is not sanitized again.
+//!
+//! ### Rules in the API documentation
+//!
+//! The sanitizers and validators are listed in the documentation generated for the newtype, its constructor and its error type,
+//! so the rules can be browsed with `cargo doc`. For example, the following type
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! /// Name of a user.
+//! #[nutype(
+//!     sanitize(trim, lowercase),
+//!     validate(len_char_min = 3, len_char_max = 20),
+//! )]
+//! pub struct Username(String);
+//! ```
+//!
+//! is documented as "Name of a user. Guaranteed: trimmed, lowercase, at least 3 characters, at most 20 characters."
+//!
+//! ### Examples as unit tests with `test_examples`
+//!
+//! `test_examples(valid = [...], invalid = [...])` documents the rules with examples next to the declaration.
+//! The examples are checked with `try_new()` by `#[cfg(test)]` unit tests, that are generated together with the type:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, predicate = |s| s.contains('@')),
+//!     test_examples(valid = ["a@b.c", " x@y "], invalid = ["", "x"]),
+//! )]
+//! pub struct Email(String);
+//! ```
+//!
+//! ### Constants for declared bounds
+//!
+//! Bounds declared with validators are exposed as associated constants, so UI code and tests can reuse the same limits:
+//! `MIN_LEN` and `MAX_LEN` for `len_char_min` and `len_char_max`, `MIN` and `MAX` for `greater_or_equal` and `less_or_equal`,
+//! `MIN_EXCLUSIVE` and `MAX_EXCLUSIVE` for `greater` and `less`.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(len_char_min = 3, len_char_max = 16))]
+//! pub struct Username(String);
+//!
+//! #[nutype(validate(greater_or_equal = -273.15, less_or_equal = 1000.0))]
+//! pub struct Temperature(f64);
+//!
+//! assert_eq!(Username::MAX_LEN, 16);
+//! assert_eq!(Temperature::MIN, -273.15);
+//! ```
+//!
+//! Bounds can also be set with constants or const expressions, which are checked by the compiler instead of the macro:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! const MAX_USERNAME_LEN: usize = 32;
+//!
+//! #[nutype(validate(len_char_max = MAX_USERNAME_LEN - 1))]
+//! pub struct Username(String);
+//!
+//! #[nutype(validate(greater_or_equal = -(u16::MAX as i64), less_or_equal = u16::MAX as i64))]
+//! pub struct Offset(i64);
+//!
+//! # fn main() {
+//! assert_eq!(Username::MAX_LEN, 31);
+//! assert_eq!(Offset::MIN, -65535);
+//! # }
+//! ```
+//!
+//! String based types with `len_char_max` or `len_byte_max` also get `MAX_ACCEPT_BYTES`, the maximal length of a raw input in bytes,
+//! that can be valid, and `exceeds_limit(len_hint)`, so a body size limit of an HTTP server can be derived from the type
+//! and too long payloads are rejected before they are buffered. A character takes at most 4 bytes, so `len_char_max = 16` accepts up to 64 bytes.
+//! They are not generated with `trim`, `canonicalize_number`, `snake_case`, `kebab_case`, `slugify` or `with` sanitizers, because those may shorten the input:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(not_empty, len_char_max = 16))]
+//! pub struct Username(String);
+//!
+//! assert_eq!(Username::MAX_ACCEPT_BYTES, 64);
+//! assert!(Username::exceeds_limit(65));
+//! ```
+//!
+//! ### Serde representation
+//!
+//! With `Serialize` and `Deserialize` a newtype is represented transparently as its inner value (`serde(transparent)`, the default).
+//! Use `serde(field = "...")` when consumers expect a struct with a single field instead:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     derive(Serialize, Deserialize),
+//!     serde(field = "value"),
+//! )]
+//! pub struct Amount(u32);
+//!
+//! let json = serde_json::to_string(&Amount::new(42)).unwrap();
+//! assert_eq!(json, r#"{"value":42}"#);
+//! ```
+//!
+//! To deserialize `&'a str` and `Cow<'a, str>` based types without allocation, add `borrow`: the string is borrowed from the input.
+//! With `&'a str` the input must not contain escape sequences, while `Cow<'a, str>` falls back to an owned string:
+//!
+//! ```ignore
+//! use std::borrow::Cow;
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     derive(Deserialize),
+//!     serde(borrow),
+//! )]
+//! pub struct Tag<'a>(Cow<'a, str>);
+//!
+//! let tag: Tag = serde_json::from_str(r#"" rust ""#).unwrap();
+//! assert!(matches!(tag.into_inner(), Cow::Borrowed("rust")));
+//! ```
+//!
+//! ### Migrate values validated by older rules
+//!
+//! When constraints tighten, keep the previous rule set in `legacy(validate(...))`.
+//! It generates `try_migrate()`, which accepts only values that were valid under the legacy rules and then
+//! passes them through `try_new()`, so they are sanitized and checked against the current rules:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(len_char_max = 5),
+//!     legacy(validate(len_char_max = 10)),
+//!     derive(Debug),
+//! )]
+//! pub struct Code(String);
+//!
+//! assert_eq!(Code::try_migrate(" abc ").unwrap().into_inner(), "abc");
+//! assert_eq!(
+//!     Code::try_migrate("abcdefgh").unwrap_err(),
+//!     CodeMigrateError::Current(CodeError::LenCharMaxViolated),
+//! );
+//! assert_eq!(
+//!     Code::try_migrate("abcdefghijkl").unwrap_err(),
+//!     CodeMigrateError::Legacy(CodeLegacyError::LenCharMaxViolated),
+//! );
+//! ```
+//!
+//! ### Rename types with `renamed_from`
+//!
+//! A type can be renamed without updating every use at once. `renamed_from = "..."` keeps the previous name
+//! of the type and of its error types (e.g. `NicknameError`) as `#[deprecated]` aliases, so the old names,
+//! including their constructors, keep compiling with a warning. `since = "..."` sets the semver version of the rename:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, len_char_max = 20),
+//!     renamed_from = "Nickname",
+//!     since = "2.0.0",
+//!     derive(Debug, PartialEq),
+//! )]
+//! pub struct Username(String);
+//!
+//! #[allow(deprecated)]
+//! let nickname = Nickname::try_new("alice").unwrap();
+//! assert_eq!(nickname, Username::try_new("alice").unwrap());
+//! ```
+//!
+//! ### Use newtypes in `validator` structs
+//!
+//! With the `validator` feature a newtype can derive `Validate` and be used as a `#[validate(nested)]` field
+//! of a struct validated by the [validator](https://crates.io/crates/validator) crate.
+//! The newtype is always valid, because it can be obtained only through its constructors.
+//! Checks from the validator crate can be reused with `predicate`:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//! use validator::{Validate, ValidateEmail};
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(predicate = |email| email.validate_email()),
+//!     derive(Debug, Validate),
+//! )]
+//! pub struct Email(String);
+//!
+//! #[derive(Validate)]
+//! struct SignUpForm {
+//!     #[validate(nested)]
+//!     email: Email,
+//!
+//!     #[validate(length(min = 8))]
+//!     password: String,
+//! }
+//! ```
+//!
+//! ### Binary formats: `borsh` and `rkyv`
+//!
+//! With the `borsh` feature a newtype can derive `BorshSerialize` and `BorshDeserialize` of
+//! [borsh](https://crates.io/crates/borsh). The value is written as the inner value, and a deserialized
+//! value goes through sanitization and validation. A rejected value is reported as `ErrorKind::InvalidData`.
+//!
+//! With the `rkyv` feature `derive(Archive)` implements `Archive`, `Serialize` and `Deserialize` of
+//! [rkyv](https://crates.io/crates/rkyv). The archived type `Archived{Type}` wraps the archived inner value
+//! and dereferences to it, so it can be read in place. Deserialization goes through the constructor too,
+//! so an invalid archive can not produce an invalid value:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//! use rkyv::rancor::Error;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, len_char_max = 20),
+//!     derive(Debug, PartialEq, BorshSerialize, BorshDeserialize, Archive),
+//! )]
+//! pub struct Name(String);
+//!
+//! let name = Name::try_new(" Anna ").unwrap();
+//!
+//! let bytes = borsh::to_vec(&name).unwrap();
+//! assert_eq!(borsh::from_slice::<Name>(&bytes).unwrap(), name);
+//!
+//! let bytes = rkyv::to_bytes::<Error>(&name).unwrap();
+//! let archived = rkyv::access::<ArchivedName, Error>(&bytes).unwrap();
+//! assert_eq!(archived.as_str(), "Anna");
+//!
+//! let bytes = rkyv::to_bytes::<Error>(&String::new()).unwrap();
+//! assert!(rkyv::from_bytes::<Name, Error>(&bytes).is_err());
+//! ```
+//!
+//! Your crate also has to explicitly have `borsh` or `rkyv` within its dependencies.
+//! `Archive` can not be derived for generic types and together with `store_as`.
+//!
+//! ### Command line arguments with `clap`
+//!
+//! With the `clap` feature a newtype can derive `ValueParserFactory` of [clap](https://crates.io/crates/clap),
+//! so it can be used as a type of command line arguments. An argument is parsed into the inner type
+//! (strings are taken as they are) and goes through sanitization and validation.
+//! A rejected argument is reported by clap with the message of the validation error:
+//!
+//! ```ignore
+//! use clap::Parser;
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(greater_or_equal = 1, less_or_equal = 65535),
+//!     derive(Debug, Clone, Copy, ValueParserFactory),
+//! )]
+//! pub struct Port(u32);
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[arg(long)]
+//!     port: Port,
+//! }
+//!
+//! let cli = Cli::parse_from(["app", "--port", "8080"]);
+//! assert_eq!(cli.port.into_inner(), 8080);
+//!
+//! // error: invalid value '70000' for '--port <PORT>': Port is too big. The value must be less or equal to 65535.
+//! assert!(Cli::try_parse_from(["app", "--port", "70000"]).is_err());
+//! ```
+//!
+//! `ValueParserFactory` requires `Clone`. Your crate also has to explicitly have `clap` within its dependencies.
+//!
+//! ### Compact storage with `store_as`
+//!
+//! A `String` newtype that takes only a few known values can keep a compact representation in memory.
+//! `store_as(...)` sets the storage type and a pair of functions: `encode` turns the sanitized and valid
+//! value into the storage type, `decode` restores it from a reference to the stored value.
+//! The public API stays string-like: `into_inner()`, `Debug`, `Display` and `Serialize` use the decoded value.
+//! `AsRef`, `Deref`, `Borrow`, `PartialOrd`, `Ord` and `JsonSchema` cannot be derived, because they would
+//! expose the storage type:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! fn encode_color(color: String) -> u8 {
+//!     match color.as_str() {
+//!         "red" => 0,
+//!         "green" => 1,
+//!         _ => 2,
+//!     }
+//! }
+//!
+//! fn decode_color(code: &u8) -> String {
+//!     ["red", "green", "blue"][*code as usize].to_string()
+//! }
+//!
+//! #[nutype(
+//!     sanitize(trim, lowercase),
+//!     validate(in = ["red", "green", "blue"]),
+//!     store_as(u8, encode = encode_color, decode = decode_color),
+//!     derive(Debug, Display, Clone, PartialEq, Eq, Hash),
+//! )]
+//! pub struct Color(String);
+//!
+//! # fn main() {
+//! let color = Color::try_new(" Green ").unwrap();
+//! assert_eq!(color.to_string(), "green");
+//! assert_eq!(core::mem::size_of::<Color>(), 1);
+//! # }
+//! ```
+//!
+//! The functions must be a bijection between valid values and stored values:
+//! `PartialEq`, `Eq` and `Hash` are derived on the stored value.
+//!
+//! ### Immutable storage with `storage = Box<str>`
+//!
+//! Long-lived `String` values carry unused capacity left by the sanitizers. With `storage = Box<str>`
+//! (or `storage = Arc<str>` to share the value between clones cheaply) the sanitized and valid `String`
+//! is converted into the storage type, while the constructors still take `String`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty),
+//!     storage = Box<str>,
+//!     derive(Debug, Clone, PartialEq, AsRef, Deref),
+//! )]
+//! pub struct Username(String);
+//!
+//! let username = Username::try_new("  alice ").unwrap();
+//! assert_eq!(username.as_ref(), "alice");
+//! assert_eq!(username.len(), 5);
+//! assert_eq!(username.into_inner(), "alice".to_string());
+//! ```
+//!
+//! Unlike with `store_as`, the value can still be borrowed as `str`: `AsRef<str>`, `Deref<Target = str>`
+//! and `Borrow<str>` are available, as well as `PartialOrd`, `Ord` and `JsonSchema`.
+//!
+//! Values with a declared maximal length can be kept inline, without a heap allocation per value, with
+//! `storage = heapless::String` or `storage = arrayvec::ArrayString` (features `heapless` and `arrayvec`).
+//! The capacity is derived from `len_byte_max`, or from `len_char_max` as 4 bytes per char,
+//! so a valid value always fits:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, len_byte_max = 16),
+//!     storage = heapless::String,
+//!     derive(Debug, Clone, PartialEq, AsRef),
+//! )]
+//! pub struct DeviceName(String);
+//!
+//! let name = DeviceName::try_new(" sensor-1 ").unwrap();
+//! assert_eq!(name.as_ref(), "sensor-1");
+//! assert_eq!(DeviceName::MAX_LEN_BYTES, 16);
+//! assert_eq!(core::mem::size_of::<DeviceName>(), core::mem::size_of::<heapless::String<16>>());
+//! ```
+//!
+//! Only the stored value is inline. The constructors still take a `String` (or anything that converts into it),
+//! the sanitizers and validators run on that `String`, and `into_inner()` allocates a new one.
+//! Read the value with `AsRef<str>` or `Deref` to avoid an allocation.
+//!
+//! ### Cheap hashing of IDs with `hash_with` and `nohash`
+//!
+//! `hash_with = ...` takes a function `fn(&Inner) -> u64` and implements `Hash`, that writes only its result into the hasher.
+//! It's useful to hash big values with a faster algorithm (e.g. `hash_with = fxhash::hash64`) or just a part of them:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(hash_with = |id| *id as u64, derive(Debug, PartialEq, Eq, Hash))]
+//! pub struct TraceId(u128);
+//! ```
+//!
+//! With the `nohash-hasher` feature, `nohash` implements `nohash_hasher::IsEnabled`, so integer IDs can be keys of `IntMap` and skip hashing completely.
+//! It's supported by integer types up to 64 bits, and by any type together with `hash_with`:
+//!
+//! ```ignore
+//! use nohash_hasher::IntMap;
+//! use nutype::nutype;
+//!
+//! #[nutype(nohash, derive(Debug, PartialEq, Eq, Hash))]
+//! pub struct OrderId(u64);
+//!
+//! let mut orders: IntMap<OrderId, &str> = IntMap::default();
+//! orders.insert(OrderId::new(1), "pending");
+//! ```
+//!
+//! ### Compare normalized values with `normalize_for_eq`
+//!
+//! Some values are equal in a canonical form, but should be displayed as they were entered, e.g. emails that differ only in case.
+//! `normalize_for_eq = ...` takes a function or a closure, that receives a reference to the inner value and returns the form
+//! used by `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`. The normalized value is computed on every comparison:
+//!
+//! ```
+//! use nutype::nutype;
+//! use std::collections::HashSet;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty),
+//!     normalize_for_eq = |email| email.to_lowercase(),
+//!     derive(Debug, Display, PartialEq, Eq, Hash),
+//! )]
+//! pub struct Email(String);
+//!
+//! let alice = Email::try_new("Alice@Example.com").unwrap();
+//! assert_eq!(alice, Email::try_new("alice@example.com").unwrap());
+//! assert_eq!(alice.to_string(), "Alice@Example.com");
+//!
+//! let emails: HashSet<Email> = [alice, Email::try_new("ALICE@EXAMPLE.COM").unwrap()].into();
+//! assert_eq!(emails.len(), 1);
+//! ```
+//!
+//! `Borrow` cannot be derived together with `normalize_for_eq`, because `Eq` and `Hash` of the borrowed value would not match.
+//!
+//! ### Invalid inputs with `arbitrary_invalid()`
+//!
+//! Along with the derived `Arbitrary`, string, integer and float types with validation get `arbitrary_invalid()`.
+//! It generates a raw value that breaks one of the declared rules (a length or a bound, `not_empty`, `finite`, `not_nan`),
+//! along with the error that `try_new()` returns for it, so rejection paths can be tested as easily as the happy ones:
+//!
+//! ```ignore
+//! use arbitrary::Unstructured;
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(greater_or_equal = 1, less_or_equal = 6), derive(Debug, Arbitrary))]
+//! pub struct TaxClass(u8);
+//!
+//! let mut u = Unstructured::new(&[3, 250, 7, 1]);
+//! let (value, err) = TaxClass::arbitrary_invalid(&mut u).unwrap();
+//! assert_eq!(TaxClass::try_new(value), Err(err));
+//! ```
+//!
+//! It returns `Err(arbitrary::Error::IncorrectFormat)` if the picked rule can not be broken, e.g. `greater_or_equal = 0` for `u8`.
+//!
+//! ### Match on allowed values with `as_variants`
+//!
+//! For a newtype validated with `in = [...]`, the `as_variants` attribute generates a companion enum `{Type}Variant`
+//! with a variant per allowed value and the `as_variant()` method, so the code can `match` exhaustively on the known values.
+//! Variant names are derived from string literals (`"dark-blue"` -> `DarkBlue`), integer literals (`-1` -> `ValueMinus1`)
+//! or names of constants (`DARK_BLUE` -> `DarkBlue`):
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim, lowercase),
+//!     validate(in = ["red", "green", "dark-blue"]),
+//!     as_variants,
+//! )]
+//! pub struct Color(String);
+//!
+//! let color = Color::try_new("Dark-Blue").unwrap();
+//! let temperature = match color.as_variant() {
+//!     ColorVariant::Red => "warm",
+//!     ColorVariant::Green | ColorVariant::DarkBlue => "cold",
+//! };
+//! assert_eq!(temperature, "cold");
+//! ```
+//!
+//! The companion enum implements `Display` and `FromStr`, that round-trip the values exactly as they are listed in `in`,
+//! so it can serve as the domain model, while the newtype guards the wire format.
+//! Parsing does not apply the sanitizers and fails with `{Type}VariantParseError` on any other input.
+//! `{Type}Variant` also implements `From<&Type>`:
+//!
+//! ```
+//! # use nutype::nutype;
+//! # #[nutype(sanitize(trim, lowercase), validate(in = ["red", "green", "dark-blue"]), as_variants)]
+//! # pub struct Color(String);
+//! let variant: ColorVariant = "dark-blue".parse().unwrap();
+//! assert_eq!(variant, ColorVariant::DarkBlue);
+//!
+//! let color = Color::try_new(variant.to_string()).unwrap();
+//! assert_eq!(ColorVariant::from(&color), variant);
+//! assert!("Dark-Blue".parse::<ColorVariant>().is_err());
+//! ```
+//!
+//! ### Compare with raw input using `eq_raw`
+//!
+//! To check whether an external input matches a stored value, there is no need to construct a throwaway instance:
+//! `eq_raw()` sanitizes the raw value and compares it with the inner one.
+//! String based types accept `&str`, other types accept the inner type.
+//! It is not generated for `nutype` on arbitrary types, since their inner type may not implement `PartialEq`.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim, lowercase), validate(not_empty))]
+//! pub struct Username(String);
+//!
+//! let username = Username::try_new("bob").unwrap();
+//! assert!(username.eq_raw("  Bob "));
+//! assert!(!username.eq_raw("alice"));
+//! ```
+//!
+//! ### Look up string keys with `Borrow` and `eq_str`
+//!
+//! Derived `Borrow` implements `Borrow<str>` for string based types, so a `HashMap` keyed by a newtype can be queried with a plain `&str`,
+//! without constructing and validating a key first.
+//! `eq_str` additionally implements `PartialEq<str>` and `PartialEq<&str>` in both directions:
+//!
+//! ```
+//! use nutype::nutype;
+//! use std::collections::HashMap;
+//!
+//! #[nutype(
+//!     eq_str,
+//!     sanitize(trim),
+//!     validate(not_empty),
+//!     derive(Debug, PartialEq, Eq, Hash, Borrow)
+//! )]
+//! pub struct Username(String);
+//!
+//! let alice = Username::try_new("alice").unwrap();
+//! assert!(alice == "alice");
+//! assert!("alice" == alice);
+//!
+//! let mut scores: HashMap<Username, u32> = HashMap::new();
+//! scores.insert(alice, 42);
+//! assert_eq!(scores.get("alice"), Some(&42));
+//! ```
+//!
+//! Unlike `eq_raw()`, `eq_str` does not sanitize the string slice: `alice == " alice "` is `false`.
+//! `eq_str` cannot be used together with `normalize_for_eq`.
+//!
+//! ### Share bounded integers with `bounded`
+//!
+//! An integer newtype declared with `bounded` stores its value as `nutype::Bounded<MIN, MAX, T>`.
+//! The bounds come from `greater`, `greater_or_equal`, `less` and `less_or_equal` validators
+//! (a missing bound is unlimited) and become a part of the storage type.
+//! `Bounded` implements `Debug`, `Display`, comparison, hashing, `AsRef` and `Deref` once for all
+//! such newtypes, which keeps the generated code smaller when a project declares dozens of ranged integers:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     bounded,
+//!     validate(greater_or_equal = 1, less_or_equal = 12),
+//!     derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash),
+//! )]
+//! pub struct Month(u8);
+//!
+//! let month = Month::try_new(7).unwrap();
+//! assert_eq!(month.to_string(), "7");
+//! assert_eq!(core::mem::size_of::<Month>(), 1);
+//! ```
+//!
+//! `Bounded` can also be used on its own: `Bounded::<0, 100, u8>::try_new(42)`.
+//! `bounded` cannot be combined with `in` or `predicate` validators.
+//! The bounds must fit into `i128` once exclusive bounds are made inclusive (e.g. `greater = 0` becomes `MIN = 1`), so a `u128` bound above `i128::MAX` is rejected at compile time.
+//!
+//! ### Optional values with `Option<T>`
+//!
+//! A string, integer or float newtype can wrap `Option<T>`.
+//! Sanitizers and validators are applied to the `Some` value, `None` is always valid.
+//! Add `required` to reject `None` with a `RequiredViolated` error variant:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, len_char_max = 20),
+//!     derive(Debug, Clone, PartialEq),
+//! )]
+//! pub struct MiddleName(Option<String>);
+//!
+//! assert_eq!(MiddleName::try_new(None).unwrap().into_inner(), None);
+//! assert_eq!(
+//!     MiddleName::try_new(Some(" Lee ".to_string())).unwrap().as_inner(),
+//!     Some(&"Lee".to_string()),
+//! );
+//! assert_eq!(
+//!     MiddleName::try_new(Some("  ".to_string())),
+//!     Err(MiddleNameError::NotEmptyViolated),
+//! );
+//!
+//! #[nutype(required, validate(greater = 0), derive(Debug))]
+//! pub struct Quantity(Option<u32>);
+//!
+//! assert_eq!(Quantity::try_new(None).unwrap_err(), QuantityError::RequiredViolated);
+//! ```
+//!
+//! `Option` based types can derive `Debug`, `Clone`, `Copy`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, `Hash`,
+//! `Into`, `TryFrom`, `Default`, `Serialize` and `Deserialize` (`None` is (de)serialized as `null`).
+//! `From` is available only without validation and without `required`.
+//!
+//! ### Validate dates and times
+//!
+//! Inner types that implement `nutype::Temporal` support temporal validators.
+//! `Temporal` is implemented for `std::time::SystemTime`, and with the `chrono` and `time` features
+//! for `chrono::{NaiveDate, NaiveDateTime, DateTime}` and `time::{Date, PrimitiveDateTime, OffsetDateTime}`.
+//! A date or time type of another crate needs only `unix_timestamp_nanos()`, values without a time zone are treated as UTC. Types with an offset should also implement `local_timestamp_nanos()`.
+//!
+//! | Validator               | Description                                     | Error variant          | Example                           |
+//! |-------------------------|-------------------------------------------------|------------------------|-----------------------------------|
+//! | `after`                 | Later than the timestamp                        | `AfterViolated`        | `after = "2000-01-01"`            |
+//! | `before`                | Earlier than the timestamp                      | `BeforeViolated`       | `before = "2100-01-01T00:00:00Z"` |
+//! | `not_before`            | Not earlier than the timestamp                  | `NotBeforeViolated`    | `not_before = "1970-01-01"`       |
+//! | `not_after`             | Not later than the timestamp                    | `NotAfterViolated`     | `not_after = "2038-01-19"`        |
+//! | `within_last`           | Not later than now and not earlier than the span before now | `WithinLastViolated` | `within_last = "30d"`     |
+//! | `within_next`           | Not earlier than now and not later than the span after now  | `WithinNextViolated` | `within_next = "1y"`      |
+//! | `not_in_future`         | Not later than now                              | `NotInFutureViolated`  | `not_in_future`                   |
+//! | `not_in_past`           | Not earlier than now                            | `NotInPastViolated`    | `not_in_past`                     |
+//! | `weekday_only`          | Falls on Monday to Friday                       | `WeekdayOnlyViolated`  | `weekday_only`                    |
+//! | `granularity`           | Whole `seconds`, `minutes`, `hours` or `days`   | `GranularityViolated`  | `granularity = minutes`           |
+//!
+//! Timestamps are checked at compile time. Spans of `within_last` and `within_next` use units `ms`, `s`, `m`, `h`, `d`, `w` and `y` (365 days).
+//! The current time is taken from `nutype::SystemClock`; to pin it in tests, set a type that implements `nutype::Clock` with `clock = MyClock`.
+//! Such types also get `try_new_at(value, now)`, that validates relative to the given time instead of the clock.
+//! `weekday_only` and `granularity` check the local time of the value (`Temporal::local_timestamp_nanos`), so `2024-01-15T08:00:00+10:00` is a Monday at a whole hour.
+//!
+//! ```
+//! use nutype::nutype;
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! #[nutype(
+//!     validate(not_before = "2024-01-01", weekday_only, granularity = minutes),
+//!     derive(Debug),
+//! )]
+//! pub struct MeetingStart(std::time::SystemTime);
+//!
+//! // 2024-01-15T09:30:00Z, Monday
+//! let monday = UNIX_EPOCH + Duration::from_secs(1_705_311_000);
+//! assert!(MeetingStart::try_new(monday).is_ok());
+//! assert_eq!(
+//!     MeetingStart::try_new(monday + Duration::from_secs(5 * 86_400)).unwrap_err(),
+//!     MeetingStartError::WeekdayOnlyViolated,
+//! );
+//! assert_eq!(
+//!     MeetingStart::try_new(monday + Duration::from_secs(1)).unwrap_err(),
+//!     MeetingStartError::GranularityViolated,
+//! );
+//! ```
+//!
+//! ### Durations with `min` and `max`
+//!
+//! Inner types that implement `nutype::TimeSpan` can be bounded with `min` (`MinViolated`) and `max` (`MaxViolated`).
+//! `TimeSpan` is implemented for `std::time::Duration`, and with the `chrono` and `time` features for `chrono::TimeDelta` and `time::Duration`.
+//! The bounds use the same units as `within_last`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! use std::time::Duration;
+//!
+//! #[nutype(validate(min = "100ms", max = "30s"), derive(Debug))]
+//! pub struct Timeout(std::time::Duration);
+//!
+//! assert!(Timeout::try_new(Duration::from_secs(5)).is_ok());
+//! assert_eq!(
+//!     Timeout::try_new(Duration::from_millis(10)).unwrap_err(),
+//!     TimeoutError::MinViolated,
+//! );
+//! ```
+//!
+//! ### Typed IDs with `Uuid`
+//!
+//! With the `uuid` feature, [`uuid::Uuid`](https://docs.rs/uuid/latest/uuid/struct.Uuid.html) based types
+//! can be validated with `uuid_version = N` (`UuidVersionViolated`) and `not_nil` (`NotNilViolated`).
+//! `FromStr`, `Display` and serde derives pass through to `Uuid`:
+//!
+//! ```
+//! use nutype::nutype;
+//! use uuid::Uuid;
+//!
+//! # #[cfg(feature = "uuid")]
+//! # {
+//! #[nutype(
+//!     validate(uuid_version = 4, not_nil),
+//!     derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromStr, Display),
+//! )]
+//! pub struct UserId(uuid::Uuid);
+//!
+//! let id: UserId = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap();
+//! assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+//! assert_eq!(UserId::try_new(Uuid::nil()).unwrap_err(), UserIdError::UuidVersionViolated);
+//! # }
+//! ```
+//!
+//! ### Network addresses
+//!
+//! [`IpAddr`](https://doc.rust-lang.org/std/net/enum.IpAddr.html), `Ipv4Addr` and `Ipv6Addr` based types
+//! can be validated with `not_loopback` (`NotLoopbackViolated`), `not_unspecified` (`NotUnspecifiedViolated`)
+//! and `not_multicast` (`NotMulticastViolated`).
+//! Ports and hostnames are covered by the `port` and `not_reserved` integer presets and the `hostname` string validator:
+//!
+//! ```
+//! use nutype::nutype;
+//! #[nutype(
+//!     validate(not_loopback, not_unspecified),
+//!     derive(Debug, Clone, Copy, PartialEq, FromStr, Display),
+//! )]
+//! pub struct ServerIp(std::net::IpAddr);
+//!
+//! #[nutype(validate(not_reserved), derive(Debug, Clone, Copy, PartialEq))]
+//! pub struct ListenPort(u16);
+//!
+//! #[nutype(sanitize(trim, lowercase), validate(hostname), derive(Debug, Clone, PartialEq))]
+//! pub struct Host(String);
+//!
+//! let ip: ServerIp = "10.0.0.7".parse().unwrap();
+//! assert_eq!(ip.to_string(), "10.0.0.7");
+//! assert_eq!(
+//!     ServerIp::try_new("127.0.0.1".parse().unwrap()).unwrap_err(),
+//!     ServerIpError::NotLoopbackViolated,
+//! );
+//! assert_eq!(ListenPort::try_new(80).unwrap_err(), ListenPortError::GreaterOrEqualViolated);
+//! assert_eq!(Host::try_new(" API.Example.com ").unwrap().into_inner(), "api.example.com");
+//! assert_eq!(Host::try_new("api_example.com").unwrap_err(), HostError::HostnameViolated);
+//! ```
+//!
+//! ### Hashes and keys with `[u8; N]`
+//!
+//! Fixed-size byte arrays, e.g. hashes and keys, are supported like any other type with `with` and `predicate`.
+//! With the `hex` or `base64` feature, `encoding = "hex"` or `encoding = "base64"` makes the derived `FromStr`
+//! decode the string into the bytes before they are sanitized and validated, and `Display` write them encoded:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(predicate = |hash| hash != &[0; 4]),
+//!     encoding = "hex",
+//!     derive(Debug, FromStr, Display),
+//! )]
+//! pub struct Checksum([u8; 4]);
+//!
+//! let checksum: Checksum = "DEADbeef".parse().unwrap();
+//! assert_eq!(checksum.to_string(), "deadbeef");
+//! assert!("00000000".parse::<Checksum>().is_err());
+//! ```
+//!
+//! ### Generic code with `Newtype` and `TryNewtype`
+//!
+//! String, integer and float based newtypes implement `nutype::Newtype` (`get()` and `into_inner()`)
+//! and `nutype::TryNewtype` (`try_new()`), so code can be written once for all of them,
+//! e.g. a form field binder:
+//!
+//! ```
+//! use nutype::{nutype, TryNewtype};
+//!
+//! #[nutype(sanitize(trim), validate(not_empty), derive(Debug))]
+//! pub struct Username(String);
+//!
+//! #[nutype(validate(less_or_equal = 150), derive(Debug))]
+//! pub struct Age(u8);
+//!
+//! fn bind<T: TryNewtype>(raw: &str) -> Option<T>
+//! where
+//!     T::Inner: core::str::FromStr,
+//! {
+//!     T::try_new(raw.parse().ok()?).ok()
+//! }
+//!
+//! assert_eq!(bind::<Username>(" bob ").unwrap().into_inner(), "bob");
+//! assert_eq!(bind::<Age>("42").unwrap().into_inner(), 42);
+//! assert!(bind::<Age>("420").is_none());
+//! ```
+//!
+//! Types without validation use `core::convert::Infallible` as the error.
+//! Types with an arbitrary inner type and types with `store_as` do not implement the traits,
+//! types with a custom error type implement only `Newtype`.
+//!
+//! ### Adopt newtypes gradually with `NutypeWrap`
+//!
+//! `#[derive(NutypeWrap)]` helps to introduce newtypes into existing structs with raw fields.
+//! It generates a copy of the struct, where the fields marked with `#[wrap(Newtype)]` have the newtype types,
+//! along with `TryFrom` from the raw struct and `From` back into it:
+//!
+//! ```
+//! use nutype::{nutype, NutypeWrap};
+//!
+//! #[nutype(sanitize(trim), validate(not_empty), derive(Debug, AsRef))]
+//! pub struct UserName(String);
+//!
+//! #[nutype(validate(less_or_equal = 150), derive(Debug))]
+//! pub struct Age(u8);
+//!
+//! #[derive(NutypeWrap)]
+//! #[wrap(name = ValidUser, derive(Debug))]
+//! pub struct User {
+//!     #[wrap(UserName)]
+//!     pub name: String,
+//!     #[wrap(Age)]
+//!     pub age: u8,
+//!     pub is_admin: bool,
+//! }
+//!
+//! let raw = User { name: " Alice ".to_string(), age: 30, is_admin: false };
+//! let user = ValidUser::try_from(raw).unwrap();
+//! assert_eq!(user.name.as_ref(), "Alice");
+//!
+//! let raw = User { name: "Bob".to_string(), age: 200, is_admin: false };
+//! assert_eq!(ValidUser::try_from(raw).unwrap_err(), ValidUserError::Age(AgeError::LessOrEqualViolated));
+//! ```
+//!
+//! Without `name = ...` the struct is called `{Struct}Wrapped`. The error has a variant per wrapped field.
+//! The newtypes must implement `TryNewtype`, so types with a custom error type cannot be wrapped.
+//!
+//! ### Keep secrets out of logs with `secret`
+//!
+//! `secret` makes derived `Debug` and `Display` print the type name instead of the value,
+//! so passwords and tokens do not leak into logs and error reports:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(secret, sanitize(trim), validate(not_empty), derive(Debug, Clone, AsRef))]
+//! pub struct ApiKey(String);
+//!
+//! let key = ApiKey::try_new(" sk-12345 ").unwrap();
+//! assert_eq!(format!("{key:?}"), "ApiKey(***)");
+//! assert_eq!(key.as_ref(), "sk-12345");
+//! ```
+//!
+//! Deriving `Serialize` for a secret type is rejected, unless it's allowed explicitly with `secret(serialize)`.
+//! With the `zeroize` feature, `secret(zeroize)` wipes the value from memory when it's dropped
+//! (`Copy` cannot be derived then).
+//!
+//! ### Move-only values with `no_clone`
+//!
+//! `#[nutype]` implements only the traits listed in `derive(...)`, so a newtype without `Clone` and `Copy`
+//! can only be moved. `no_clone` turns this into a guarantee for handle-like types (one-time tokens, nonces):
+//! deriving `Clone` or `Copy` is rejected, and the build fails if `Clone` is implemented manually elsewhere.
+//! Code that consumes such a value can rely on it being used only once:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(len_char_min = 16), no_clone, derive(Debug, AsRef))]
+//! pub struct ResetToken(String);
+//!
+//! fn redeem(token: ResetToken) -> String {
+//!     token.into_inner()
+//! }
+//!
+//! let token = ResetToken::try_new("0123456789abcdef").unwrap();
+//! assert_eq!(redeem(token), "0123456789abcdef");
+//! // `token` is moved and can not be redeemed again.
+//! ```
+//!
+//! ### Detect modified inputs with `lossy`
+//!
+//! Sanitizers fix the inputs silently. With `lossy` a type gets `try_from_lossy()` (`from_lossy()`
+//! if there is no validation), that works like `try_new()` (`new()`) and also returns
+//! a `nutype::SanitizeWarning` for every sanitizer, that changed the value.
+//! Data ingestion pipelines can use it to log or count the modified records:
+//!
+//! ```
+//! use nutype::{nutype, SanitizeWarning};
+//!
+//!
+//! #[nutype(
+//!     lossy,
+//!     sanitize(trim, lowercase),
+//!     validate(not_empty),
+//!     derive(Debug),
+//! )]
+//! pub struct Tag(String);
+//!
+//! let (tag, warnings) = Tag::try_from_lossy(" Rust").unwrap();
+//! assert_eq!(tag.into_inner(), "rust");
+//! assert_eq!(
+//!     warnings,
+//!     vec![
+//!         SanitizeWarning { sanitizer: "trim", index: 0 },
+//!         SanitizeWarning { sanitizer: "lowercase", index: 1 },
+//!     ]
+//! );
+//! ```
+//!
+//! A change is detected by comparing the value before and after every sanitizer, so the inner type
+//! must implement `Clone` and `PartialEq`. `lossy` requires the `std` feature.
+//!
+//! ### Report rejected values with `on_invalid`
+//!
+//! `on_invalid = ...` calls a function every time validation rejects a value, before the error is returned,
+//! so invalid input can be counted or logged in one place instead of at every construction site.
+//! The function receives the error and the rejected (already sanitized) value:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! fn report(err: &UsernameError, value: &str) {
+//!     eprintln!("rejected username {value:?}: {err}");
+//! }
+//!
+//! #[nutype(sanitize(trim), validate(not_empty, len_char_max = 20), on_invalid = report)]
+//! pub struct Username(String);
+//!
+//! fn main() {
+//!     assert!(Username::try_new("   ").is_err()); // prints `rejected username "": ...`
+//! }
+//! ```
+//!
+//! The value is `&str` for string types and `&Option<T>` for `Option<T>` types.
+//! For `secret` types the function receives only the error, e.g. `fn(&PasswordError)`, so the rejected value
+//! does not leak into logs. A non-capturing closure works too.
+//!
+//! ### Stricter defaults with `profile = "strict"`
+//!
+//! A profile toggles a group of optional behaviors at once. `profile = "strict"`:
+//!
+//! * makes derived `Debug` print `Username(***)` instead of the value (`Display` is kept as it is);
+//! * rejects unknown keys when `Deserialize` is derived with `serde(field = "...")`;
+//! * generates `validate_all()`, that reports every violated validator instead of the first one.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     profile = "strict",
+//!     validate(len_char_max = 5, predicate = |s| s.chars().all(char::is_lowercase)),
+//!     derive(Debug)
+//! )]
+//! pub struct Username(String);
+//!
+//! let errors: Vec<UsernameError> = Username::validate_all("ALICE BOB").collect();
+//! assert_eq!(errors, vec![UsernameError::LenCharMaxViolated, UsernameError::PredicateViolated]);
+//! assert_eq!(format!("{:?}", Username::try_new("alice").unwrap()), "Username(***)");
+//! ```
+//!
+//! To apply a profile to all the newtypes of a workspace, set `NUTYPE_PROFILE` environment variable,
+//! e.g. in `.cargo/config.toml`:
+//!
+//! ```toml
+//! [env]
+//! NUTYPE_PROFILE = "strict"
+//! ```
+//!
+//! `profile = "default"` opts a single type out of the workspace profile.
+//! `validate_all()` is not generated for generic types.
+//!
+//! ### Repeated rules with `duplicates = "last_wins"`
+//!
+//! A sanitizer or a validator specified more than once is a compile error.
+//! This can get in the way when the attributes are generated, e.g. by a `macro_rules!` that appends overrides to shared defaults.
+//! With `duplicates = "last_wins"` only the last occurrence of every sanitizer and validator is kept:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     duplicates = "last_wins",
+//!     sanitize(trim, lowercase, trim),
+//!     validate(not_empty, len_char_max = 10, len_char_max = 20),
+//! )]
+//! pub struct Nickname(String);
+//!
+//! // Same as `sanitize(lowercase, trim), validate(not_empty, len_char_max = 20)`
+//! assert_eq!(Nickname::MAX_LEN, 20);
+//! ```
+//!
+//! Sanitizers keep the position of their last occurrence.
+//! Custom `with = ...` sanitizers are never dropped, since all of them are applied one after another.
+//! `duplicates = "error"` is the default.
+//!
+//! ### Describe the rules with `METADATA`
+//!
+//! Every type generated with `#[nutype]` has a `METADATA` constant of type `nutype::TypeMetadata`.
+//! Its `rules` list every sanitizer and validator (including combinators like `any_of`) as `nutype::RuleMetadata`
+//! with the name, the bound and what the rule guarantees, so frameworks can derive OpenAPI schemas,
+//! form constraints or admin UIs from the declaration. `sanitizers()`, `validators()` and `guarantees()` are views of the same list:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(not_empty, len_char_max = 64))]
+//! pub struct Email(String);
+//!
+//! let max_length = Email::METADATA
+//!     .validators()
+//!     .find(|validator| validator.name == "len_char_max")
+//!     .and_then(|validator| validator.bound);
+//! assert_eq!(max_length, Some(64.0));
+//! ```
+//!
+//! The bounds are converted to `f64`. Unlike `inventory` it does not need a feature
+//! and works with a concrete type, so it's available in `const` context.
+//!
+//! ### List all newtypes with `inventory`
+//!
+//! With the `inventory` feature every type generated with `#[nutype]` registers a `NewtypeInfo` with its module and `metadata`
+//! (the same `TypeMetadata` as its `METADATA` constant),
+//! so an application can list its domain types at runtime, e.g. to render an admin dashboard or describe an API:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim, lowercase), validate(not_empty, len_char_max = 64))]
+//! pub struct Email(String);
+//!
+//! for info in nutype::registered_newtypes() {
+//!     let metadata = info.metadata;
+//!     let guarantees: Vec<&str> = metadata.guarantees().collect();
+//!     println!("{}::{}({}): {:?}", info.module_path, metadata.name, metadata.inner_type, guarantees);
+//! }
+//! ```
+//!
+//! The registration relies on [`inventory`](https://crates.io/crates/inventory), so it works only on the platforms supported by that crate.
+//!
+//! ### Reuse allocations with `try_new_into`
+//!
+//! `String` and `Vec<T>` based types get `try_new_into(raw, &mut buffer)` (`new_into` without validation),
+//! that builds the value in the allocation of an existing buffer. It helps parsers that construct many values:
+//! a rejected value is returned into the buffer, so the next attempt does not allocate.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(not_empty), derive(Debug))]
+//! pub struct Word(String);
+//!
+//! let mut buffer = String::with_capacity(64);
+//! let words: Vec<Word> = "  alpha,  , beta"
+//!     .split(',')
+//!     .filter_map(|raw| Word::try_new_into(raw, &mut buffer).ok())
+//!     .collect();
+//! assert_eq!(words.len(), 2);
+//! ```
+//!
+//! ### Transform values with `map` and `try_map`
+//!
+//! `try_map(f)` transforms the inner value and passes the result through the sanitizers and validators again,
+//! so a value can be changed without unwrapping it and calling `try_new()` manually.
+//! Types without validation get the infallible `map(f)`.
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(len_char_max = 5), derive(Debug, AsRef))]
+//! pub struct Code(String);
+//!
+//! let code = Code::try_new("ab").unwrap();
+//! let code = code.try_map(|s| format!("{s}cd")).unwrap();
+//! assert_eq!(code.as_ref(), "abcd");
+//! assert!(code.try_map(|s| s.repeat(2)).is_err());
+//! ```
+//!
+//! ### Validate many values with `try_from_iter`
+//!
+//! Types with validation get `try_from_iter(raw_values)`, that validates every raw value and collects
+//! the newtypes into any collection implementing `FromIterator` (e.g. `Vec`).
+//! It stops at the first invalid value and returns `nutype::BatchError` with its index and the error.
+//! It is not generated for types with `with_context`.
+//!
+//! ```
+//! use nutype::nutype;
+//! use nutype::BatchError;
+//!
+//! #[nutype(validate(less_or_equal = 150), derive(Debug, PartialEq))]
+//! pub struct Age(u8);
+//!
+//! let ages: Vec<Age> = Age::try_from_iter([7, 42]).unwrap();
+//! assert_eq!(ages.len(), 2);
+//!
+//! let result: Result<Vec<Age>, _> = Age::try_from_iter([7, 42, 200]);
+//! assert_eq!(
+//!     result.unwrap_err(),
+//!     BatchError { index: 2, error: AgeError::LessOrEqualViolated },
+//! );
+//! ```
+//!
+//! ### Allocate in an arena with `arena`
+//!
+//! With the `bumpalo` feature, `&'a str` based types marked with `arena` get `try_new_in(raw, &bump)`
+//! (`new_in` without validation), that copies the raw string into a [`bumpalo`](https://crates.io/crates/bumpalo) arena,
+//! so parsers can avoid a heap allocation per value:
+//!
+//! ```ignore
+//! use bumpalo::Bump;
+//! use nutype::nutype;
+//!
+//! #[nutype(arena, sanitize(trim), validate(not_empty), derive(Debug, AsRef))]
+//! pub struct Ident<'a>(&'a str);
+//!
+//! let bump = Bump::new();
+//! let ident = Ident::try_new_in("  main ", &bump).unwrap();
+//! assert_eq!(ident.as_ref(), "main");
+//! ```
+//!
+//! The string is copied before it's validated, so a rejected value stays in the arena until it's reset.
+//!
+//! ### Validate against runtime settings with `with_context`
+//!
+//! A validator declared with `with_context` receives the sanitized value along with a context of the type set by `context`,
+//! so the rules can depend on runtime configuration. Such types get `try_from_with(&ctx, raw)` instead of `try_new`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     sanitize(trim),
+//!     validate(not_empty, with_context = |name: &str, max_len: &usize| name.len() <= *max_len, context = usize),
+//!     derive(Debug, AsRef)
+//! )]
+//! pub struct Username(String);
+//!
+//! let max_len = 8; // e.g. loaded from the settings
+//! let username = Username::try_from_with(&max_len, " alice ").unwrap();
+//! assert_eq!(username.as_ref(), "alice");
+//! assert_eq!(
+//!     Username::try_from_with(&max_len, "bartholomew").unwrap_err(),
+//!     UsernameError::WithContextViolated,
+//! );
+//! ```
+//!
+//! The context validator runs after the standard ones. Since a value can be built only with the context,
+//! the traits that build values without it (`TryFrom`, `FromStr`, `Default`, `Deserialize`, `Arbitrary`) cannot be derived.
+//!
+//! ### Map errors to localized messages with `code()`
+//!
+//! Generated error types have `code()`, that returns the name of the violated validator (e.g. `"len_char_max"`),
+//! and string, integer and float errors also have `bound()`, that returns its bound (e.g. `Some(20)`).
+//! They do not depend on the wording of the `Display` messages, so web backends can map them to translations:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(sanitize(trim), validate(not_empty, len_char_max = 20), derive(Debug))]
+//! pub struct Username(String);
+//!
+//! let err = Username::try_new("x".repeat(21)).unwrap_err();
+//! assert_eq!(err.code(), "len_char_max");
+//! assert_eq!(err.bound(), Some(20));
+//! ```
+//!
+//! Bounds of string validators are lengths (`usize`), while bounds of integer and float validators have the inner type.
+//! `None` rejected with `required` is reported as `"required"` and the `with_context` validator as `"with_context"`.
+//!
+//! ### Error messages with `messages(...)`
+//!
+//! The `Display` messages of generated errors mention the configured limits, and a violated bound also mentions
+//! the other bound of the same value (e.g. `Username is too short. The value length must be between 3 and 50 characters.`).
+//! The messages can be replaced per validator with `messages(...)`, where `{type}` is the name of the type
+//! and `{limit}` is the bound of the validator:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(
+//!         not_empty,
+//!         len_char_max = 20,
+//!         messages(
+//!             not_empty = "Please enter a {type}.",
+//!             len_char_max = "{type} can have at most {limit} characters.",
+//!         ),
+//!     ),
+//!     derive(Debug),
+//! )]
+//! pub struct Title(String);
+//!
+//! assert_eq!(Title::try_new("").unwrap_err().to_string(), "Please enter a Title.");
+//! assert_eq!(
+//!     Title::try_new("x".repeat(21)).unwrap_err().to_string(),
+//!     "Title can have at most 20 characters.",
+//! );
+//! ```
+//!
+//! The keys must be validators declared on the type. `{limit}` can be used only with validators that have a bound.
+//!
+//! ### Combine validators with `any_of`, `all`, `not` and `when`
+//!
+//! String, integer and float types accept `any_of(...)`, `all(...)`, `not(...)` and `when(...)` within `validate(...)`.
+//! `any_of` (or its shorter alias `any`) passes when at least one of its validators passes, `all` passes when all of its
+//! validators pass, and `not` passes when its validator fails.
+//! They can be nested, so alternatives don't have to be moved into a custom predicate:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(
+//!         not_empty,
+//!         any_of(len_char_max = 3, len_char_min = 8),
+//!         not(predicate = |s| s.starts_with("admin")),
+//!     ),
+//!     derive(Debug, PartialEq),
+//! )]
+//! pub struct Code(String);
+//!
+//! assert!(Code::try_new("abc").is_ok());
+//! assert_eq!(Code::try_new("abcde"), Err(CodeError::AnyOfViolated));
+//! assert_eq!(Code::try_new("administrator"), Err(CodeError::NotViolated));
+//! ```
+//!
+//! Rules that depend on the shape of the value, like international and local phone numbers, are declared
+//! with `when(<condition>, then(<validators>))`. The validators of `then(...)` are checked only when the
+//! condition passes:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(
+//!         predicate = |s| s.trim_start_matches('+').chars().all(|c| c.is_ascii_digit()),
+//!         when(predicate = |s| s.starts_with('+'), then(len_char_min = 9, len_char_max = 16)),
+//!         when(not(predicate = |s| s.starts_with('+')), then(len_char_max = 10)),
+//!     ),
+//!     derive(Debug, PartialEq),
+//! )]
+//! pub struct Phone(String);
+//!
+//! assert!(Phone::try_new("+49301234567").is_ok());
+//! assert!(Phone::try_new("030123456").is_ok());
+//! assert_eq!(Phone::try_new("+4930"), Err(PhoneError::WhenViolated));
+//! ```
+//!
+//! `all` groups validators within the other combinators, e.g. to accept international numbers written with `+` or `00`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(
+//!         not(predicate = |s| s.contains(' ')),
+//!         any(
+//!             all(predicate = |s| s.starts_with('+'), len_char_min = 9),
+//!             all(predicate = |s| s.starts_with("00"), len_char_min = 10),
+//!         ),
+//!     ),
+//!     derive(Debug, PartialEq),
+//! )]
+//! pub struct IntlPhone(String);
+//!
+//! assert!(IntlPhone::try_new("+49301234").is_ok());
+//! assert_eq!(IntlPhone::try_new("030123456"), Err(IntlPhoneError::AnyOfViolated));
+//! ```
+//!
+//! A violation is reported as `AnyOfViolated`, `AllViolated`, `NotViolated` or `WhenViolated`, after the errors of the regular validators.
+//! Combinators are not supported by `Option` based types, `guarantee` and the derived `Arbitrary`.
+//!
+//! ### Rules from a JSON Schema with `nutype_from_schema`
+//!
+//! With the `from_schema` feature, `#[nutype_from_schema(...)]` reads a JSON Schema snippet and adds the matching validators
+//! to the `#[nutype(...)]` attribute below it, so schema-first projects do not translate the constraints by hand:
+//!
+//! ```ignore
+//! use nutype::{nutype, nutype_from_schema};
+//!
+//! #[nutype_from_schema(r#"{ "type": "string", "minLength": 3, "maxLength": 20, "pattern": "^[a-z_]+$" }"#)]
+//! #[nutype(sanitize(trim), derive(Debug, AsRef))]
+//! pub struct Username(String);
+//! ```
+//!
+//! | JSON Schema                                     | nutype                                   |
+//! |-------------------------------------------------|------------------------------------------|
+//! | `minLength`, `maxLength`                        | `len_char_min`, `len_char_max`           |
+//! | `pattern`                                       | `regex` (requires the `regex` feature)   |
+//! | `minimum`, `maximum`                            | `greater_or_equal`, `less_or_equal`      |
+//! | `exclusiveMinimum`, `exclusiveMaximum`          | `greater`, `less`                        |
+//! | `enum`, `const`                                 | `in`                                     |
+//!
+//! Annotations (`title`, `description`, `examples`, etc.) are ignored, while other keywords (e.g. `format`) are rejected,
+//! because they cannot be enforced. `nutype_from_schema` must be placed above `#[nutype(...)]`.
+//!
+//! ### JSON Schema metadata with `schema(...)`
+//!
+//! A derived `JsonSchema` describes only the inner type. Use `schema(...)` to add `format`, `description` and `examples`,
+//! so the generated OpenAPI documents are complete:
+//!
+//! ```ignore
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     validate(not_empty),
+//!     derive(JsonSchema),
+//!     schema(format = "email", example = "alice@example.com", description = "Email of a customer"),
+//! )]
+//! pub struct Email(String);
+//! ```
+//!
+//! `example = ...` can be repeated and accepts any value that `serde_json::json!` accepts.
+//! If `description` is not set, the doc comments of the type are used, like with the derived `JsonSchema`.
+//!
+//! ### Platform specific types with `cfg`
+//!
+//! `#[cfg(...)]` and `#[cfg_attr(...)]` on the struct are evaluated by the compiler before `#[nutype]` is expanded.
+//! A disabled type generates nothing at all (no error type, no impls), so the same type can be defined differently per platform:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(validate(not_empty))]
+//! #[cfg(unix)]
+//! pub struct Shell(String);
+//!
+//! #[nutype(validate(predicate = |s| s.ends_with(".exe")))]
+//! #[cfg(windows)]
+//! pub struct Shell(String);
+//! ```
+//!
+//! `cfg_attr` may expand only into attributes, that `#[nutype]` accepts on the struct, i.e. doc comments.
+//!
+//! ## How to break the constraints?
+//!
+//! It's discouraged, but it's possible to bypass the constraints by enabling `new_unchecked` crate feature and marking a type with `new_unchecked`:
+//!
+//! ```
+//! use nutype::nutype;
+//!
+//! #[nutype(
+//!     new_unchecked,
+//!     sanitize(trim),
+//!     validate(len_char_min = 8),
+//! )]
+//! pub struct Name(String);
+//!
+//! // Yes, you're forced to use `unsafe` here, so everyone will point fingers at YOU.
+//! let name = unsafe { Name::new_unchecked(" boo ".to_string()) };
+//!
+//! // `name` violates the sanitization and validation rules!!!
+//! assert_eq!(name.into_inner(), " boo ");
+//! ```
+//!
+//! ## Feature flags
+//!
+//! * `arbitrary` - enables derive of [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html).
+//! * `arrayvec` - allows to use `storage = arrayvec::ArrayString` on `String` based types with a declared maximal length. Note: your crate also has to explicitly have `arrayvec` within its dependencies.
+//! * `borsh` - allows to derive `BorshSerialize` and `BorshDeserialize` of [borsh](https://crates.io/crates/borsh). Note: your crate also has to explicitly have `borsh` within its dependencies.
+//! * `bumpalo` - allows to use `arena` on `&'a str` based types to generate `try_new_in()`, that allocates the value in a [`bumpalo`](https://crates.io/crates/bumpalo) arena. Note: your crate also has to explicitly have `bumpalo` within its dependencies.
+//! * `clap` - allows to derive `ValueParserFactory` of [clap](https://crates.io/crates/clap), so newtypes can be used as command line arguments. Note: your crate also has to explicitly have `clap` within its dependencies.
+//! * `heapless` - allows to use `storage = heapless::String` on `String` based types with a declared maximal length. Note: your crate also has to explicitly have `heapless` within its dependencies.
+//! * `inventory` - registers every newtype with [`inventory`](https://crates.io/crates/inventory), so they can be listed at runtime with `nutype::registered_newtypes()`.
+//! * `nohash-hasher` - allows to use `nohash` to implement `nohash_hasher::IsEnabled`, so integer IDs can be keys of `nohash_hasher::IntMap`.
+//! * `chrono` - implements `Temporal` and `TimeSpan` for date, time and duration types of [chrono](https://crates.io/crates/chrono).
+//! * `time` - implements `Temporal` and `TimeSpan` for date, time and duration types of [time](https://crates.io/crates/time).
+//! * `hex` - allows to use `encoding = "hex"` on `[u8; N]` based types, that derive `FromStr` and `Display` with hex digits.
+//! * `base64` - allows to use `encoding = "base64"` on `[u8; N]` based types, that derive `FromStr` and `Display` with base64.
+//! * `from_schema` - enables `#[nutype_from_schema(...)]`, that translates the constraints of a JSON Schema snippet into `validate(...)` rules.
+//! * `metrics` - increments the [`metrics`](https://crates.io/crates/metrics) counter `nutype_validation_failures_total`, labeled by `type` and `rule` (the `code()` of the error, e.g. `len_char_max`, or `with` for a custom error type), whenever `try_new` rejects a value. Implies `std`.
+//! * `new_unchecked` - enables generation of unsafe `::new_unchecked()` function.
+//! * `regex` - allows to use `regex = ` validation on string-based types. Note: your crate also has to explicitly have `regex` within its dependencies.
+//! * `uuid` - allows to use `uuid_version = ` and `not_nil` validators on [`uuid::Uuid`](https://docs.rs/uuid/latest/uuid/struct.Uuid.html) based types. Note: your crate also has to explicitly have `uuid` within its dependencies.
+//! * `unicode` - allows to use `snake_case`, `kebab_case` and `slugify` sanitizers on string-based types, that decompose letters with [`unicode-normalization`](https://crates.io/crates/unicode-normalization).
+//! * `unicode-segmentation` - allows to use `len_grapheme_min` and `len_grapheme_max` validators on string-based types. Note: your crate also has to explicitly have `unicode-segmentation` within its dependencies.
+//! * `serde` - integrations with [`serde`](https://crates.io/crates/serde) crate. Allows to derive `Serialize` and `Deserialize` traits.
+//! * `rkyv` - allows to derive `Archive` of [rkyv](https://crates.io/crates/rkyv), that implements `Archive`, `Serialize` and `Deserialize`. Note: your crate also has to explicitly have `rkyv` within its dependencies.
+//! * `schemars08` - allows to derive [`JsonSchema`](https://docs.rs/schemars/0.8.12/schemars/trait.JsonSchema.html) trait of [schemars](https://crates.io/crates/schemars) crate. Note that at the moment validation rules are not respected.
+//! * `tracing` - emits a [`tracing`](https://crates.io/crates/tracing) event (target `nutype`, level `DEBUG`) with the type name and the code of the failed rule (e.g. `"len_char_max"`, never the rejected value) whenever `try_new` rejects a value. With `#[nutype(trace)]` every sanitizer output (the inner type must implement `Debug`) and validator verdict is reported as well.
+//! * `std` - enabled by default. Use `default-features = false` to disable.
+//! * `wasm-bindgen` - allows to use `wasm` to export newtypes to JS with [`wasm-bindgen`](https://crates.io/crates/wasm-bindgen). Implies `std`. Note: your crate also has to explicitly have `wasm-bindgen` within its dependencies.
+//! * `validator` - allows to derive [`Validate`](https://docs.rs/validator/latest/validator/trait.Validate.html) trait of [validator](https://crates.io/crates/validator) crate, so newtypes can be used as `#[validate(nested)]` fields. Note: your crate also has to explicitly have `validator` within its dependencies.
+//! * `zeroize` - allows to use `secret(zeroize)`, that wipes the value of a secret type from memory on drop with [`zeroize`](https://crates.io/crates/zeroize).
+//!
+//! ## Support Ukrainian military forces 🇺🇦
+//!
+//! Today I live in Berlin, I have the luxury to live a physically safe life.
+//! But I am Ukrainian. The first 25 years of my life I spent in [Kharkiv](https://en.wikipedia.org/wiki/Kharkiv),
+//! the second-largest city in Ukraine, 60km away from the border with russia. Today about [a third of my home city is destroyed](https://www.youtube.com/watch?v=ihoufBFSZds) by russians.
+//! My parents, my relatives and my friends had to survive the artillery and air attack, living for over a month in basements.
+//!
+//! Some of them have managed to evacuate to EU. Some others are trying to live "normal lives" in Kharkiv, doing there daily duties.
+//! And some are at the front line right now, risking their lives every second to protect the rest.
+//!
+//! I encourage you to donate to [Charity foundation of Serhiy Prytula](https://prytulafoundation.org/en).
+//! Just pick the project you like and donate. This is one of the best-known foundations, you can watch a [little documentary](https://www.youtube.com/watch?v=VlmWqoeub1Q) about it.
+//! Your contribution to the Ukrainian military force is a contribution to my calmness, so I can spend more time developing the project.
+//!
+//! Thank you.
+
+// Set `no_std` flag if `std` feature is disabled.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod batch;
+mod bounded;
+#[cfg(any(feature = "hex", feature = "base64"))]
+mod encoding;
+mod lossy;
+mod metadata;
+mod newtype;
+#[cfg(feature = "inventory")]
+mod registry;
+mod round;
+mod temporal;
+#[cfg(feature = "unicode")]
+mod transliterate;
+
+pub use batch::BatchError;
+pub use bounded::{Bounded, BoundedError, BoundedInteger};
+#[cfg(feature = "base64")]
+pub use encoding::Base64;
+#[cfg(any(feature = "hex", feature = "base64"))]
+pub use encoding::DecodeError;
+#[cfg(feature = "hex")]
+pub use encoding::Hex;
+pub use lossy::SanitizeWarning;
+pub use metadata::{RuleMetadata, RuleStage, TypeMetadata};
+pub use newtype::{Newtype, TryNewtype};
+#[cfg(feature = "from_schema")]
+pub use nutype_macros::nutype_from_schema;
+pub use nutype_macros::{nutype, NutypeWrap};
+#[cfg(feature = "inventory")]
+pub use registry::{registered_newtypes, NewtypeInfo};
+#[cfg(feature = "std")]
+pub use temporal::SystemClock;
+pub use temporal::{Clock, Temporal, TimeSpan};
+
+// Allow the generated code to refer to `::nutype` within this crate.
+extern crate self as nutype;
+
+/// Helpers and dependencies used by the generated code. Not a public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::round::RoundDp;
+    #[cfg(feature = "unicode")]
+    pub use crate::transliterate::{is_combining_mark, to_ascii};
+    #[cfg(feature = "inventory")]
+    pub use inventory;
+    #[cfg(feature = "metrics")]
+    pub use metrics;
+    #[cfg(feature = "nohash-hasher")]
+    pub use nohash_hasher;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+    #[cfg(feature = "zeroize")]
+    pub use zeroize;
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,28 @@
+use core::fmt;
+
+/// A sanitizer, that changed the value in `try_from_lossy()` or `from_lossy()`.
+///
+/// ```
+/// use nutype::{nutype, SanitizeWarning};
+///
+/// #[nutype(lossy, sanitize(trim, lowercase), validate(not_empty), derive(Debug))]
+/// pub struct Tag(String);
+///
+/// let (tag, warnings) = Tag::try_from_lossy("  rust").unwrap();
+/// assert_eq!(tag.into_inner(), "rust");
+/// assert_eq!(warnings, vec![SanitizeWarning { sanitizer: "trim", index: 0 }]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SanitizeWarning {
+    /// Name of the sanitizer as it's written in the attributes, e.g. `trim` or `clamp`.
+    pub sanitizer: &'static str,
+
+    /// Position of the sanitizer in `sanitize(...)`, starting from 0.
+    pub index: usize,
+}
+
+impl fmt::Display for SanitizeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sanitizer `{}` changed the value", self.sanitizer)
+    }
+}
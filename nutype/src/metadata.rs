@@ -0,0 +1,114 @@
+/// Description of the rules of a type generated with `#[nutype]`, available as its `METADATA`
+/// constant. It allows to derive API documentation, form constraints, etc. from the declaration.
+///
+/// The rules are listed once in [`TypeMetadata::rules`]; `sanitizers()`, `validators()` and
+/// `guarantees()` are views of that list.
+///
+/// ```
+/// use nutype::nutype;
+///
+/// #[nutype(sanitize(trim), validate(not_empty, len_char_max = 64))]
+/// pub struct Email(String);
+///
+/// assert_eq!(Email::METADATA.name, "Email");
+/// assert_eq!(Email::METADATA.inner_type, "String");
+/// assert!(Email::METADATA.sanitizers().map(|rule| rule.name).eq(["trim"]));
+/// assert!(Email::METADATA
+///     .validators()
+///     .map(|rule| (rule.name, rule.bound))
+///     .eq([("not_empty", None), ("len_char_max", Some(64.0))]));
+/// assert!(Email::METADATA
+///     .guarantees()
+///     .eq(["trimmed", "not empty", "at most 64 characters"]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeMetadata {
+    /// Name of the type, e.g. `"Email"`.
+    pub name: &'static str,
+
+    /// The inner type as it's written in the declaration, e.g. `"String"`.
+    pub inner_type: &'static str,
+
+    /// All the sanitizers (in the order of application) followed by all the validators
+    /// (in the order of declaration).
+    pub rules: &'static [RuleMetadata],
+}
+
+impl TypeMetadata {
+    /// The sanitizers in the order of application.
+    pub fn sanitizers(&self) -> impl Iterator<Item = &'static RuleMetadata> {
+        self.rules_of(RuleStage::Sanitizer)
+    }
+
+    /// The validators in the order of declaration, including combinators like `any_of`.
+    pub fn validators(&self) -> impl Iterator<Item = &'static RuleMetadata> {
+        self.rules_of(RuleStage::Validator)
+    }
+
+    /// What the rules guarantee, as listed in the generated documentation,
+    /// e.g. `["trimmed", "at most 64 characters"]`.
+    pub fn guarantees(&self) -> impl Iterator<Item = &'static str> {
+        let rules = self.rules;
+        rules.iter().enumerate().filter_map(move |(index, rule)| {
+            let description = rule.description?;
+            // Repeated `with` sanitizers have the same description.
+            let is_repeated = rules[..index]
+                .iter()
+                .any(|previous| previous.description == Some(description));
+            (!is_repeated).then_some(description)
+        })
+    }
+
+    fn rules_of(&self, stage: RuleStage) -> impl Iterator<Item = &'static RuleMetadata> {
+        self.rules.iter().filter(move |rule| rule.stage == stage)
+    }
+}
+
+/// Whether a rule transforms (`sanitize(...)`) or checks (`validate(...)`) the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleStage {
+    Sanitizer,
+    Validator,
+}
+
+/// A sanitizer or a validator of a type generated with `#[nutype]`, in the same representation
+/// for all the kinds of inner types.
+///
+/// ```
+/// use nutype::{nutype, RuleMetadata, RuleStage};
+///
+/// #[nutype(sanitize(trim), validate(len_char_max = 64))]
+/// pub struct Email(String);
+///
+/// assert_eq!(
+///     Email::METADATA.rules,
+///     [
+///         RuleMetadata {
+///             stage: RuleStage::Sanitizer,
+///             name: "trim",
+///             bound: None,
+///             description: Some("trimmed"),
+///         },
+///         RuleMetadata {
+///             stage: RuleStage::Validator,
+///             name: "len_char_max",
+///             bound: Some(64.0),
+///             description: Some("at most 64 characters"),
+///         },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleMetadata {
+    /// Whether it's a sanitizer or a validator.
+    pub stage: RuleStage,
+
+    /// Name of the rule as it's written in the attributes, e.g. `"trim"` or `"any_of"`.
+    pub name: &'static str,
+
+    /// The bound of a validator, converted to `f64`, e.g. `64.0` for `len_char_max = 64`.
+    pub bound: Option<f64>,
+
+    /// What the rule guarantees, e.g. `"at most 64 characters"`.
+    pub description: Option<&'static str>,
+}
@@ -0,0 +1,63 @@
+/// Implemented by every type generated with `#[nutype]`, so generic code can work
+/// with any of them, e.g. to render or bind form fields.
+///
+/// It is not implemented by types with an arbitrary inner type, because the inner type may be
+/// private, and by types that keep their value in a different representation with `store_as`.
+///
+/// ```
+/// use nutype::{nutype, Newtype};
+///
+/// #[nutype(sanitize(trim), validate(not_empty))]
+/// pub struct Name(String);
+///
+/// fn describe<T: Newtype>(value: &T) -> String
+/// where
+///     T::Inner: core::fmt::Debug,
+/// {
+///     format!("{:?}", value.get())
+/// }
+///
+/// let name = Name::try_new(" Bob ").unwrap();
+/// assert_eq!(describe(&name), r#""Bob""#);
+/// ```
+pub trait Newtype {
+    /// The inner type, e.g. `String` for `struct Name(String)`.
+    type Inner;
+
+    /// Returns a reference to the inner value.
+    fn get(&self) -> &Self::Inner;
+
+    /// Returns the inner value.
+    fn into_inner(self) -> Self::Inner;
+}
+
+/// The constructor of a type generated with `#[nutype]`.
+///
+/// For types without validation the error is [`core::convert::Infallible`].
+/// It is not implemented by types with a custom error type set with `error = ...`,
+/// because the error type may be private, and by types with `constructor_visibility = ...`,
+/// because it would make the restricted constructor public.
+///
+/// ```
+/// use nutype::{nutype, TryNewtype};
+///
+/// #[nutype(validate(less_or_equal = 100), derive(Debug))]
+/// pub struct Percentage(u8);
+///
+/// fn parse_all<T: TryNewtype>(values: Vec<T::Inner>) -> Result<Vec<T>, T::Error> {
+///     values.into_iter().map(T::try_new).collect()
+/// }
+///
+/// assert!(parse_all::<Percentage>(vec![5, 50, 100]).is_ok());
+/// assert_eq!(
+///     parse_all::<Percentage>(vec![5, 200]).unwrap_err(),
+///     PercentageError::LessOrEqualViolated,
+/// );
+/// ```
+pub trait TryNewtype: Newtype + Sized {
+    /// The error returned when the value violates the validation rules.
+    type Error;
+
+    /// Sanitizes and validates the value.
+    fn try_new(inner: Self::Inner) -> Result<Self, Self::Error>;
+}
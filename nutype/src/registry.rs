@@ -0,0 +1,34 @@
+use crate::TypeMetadata;
+
+/// Description of a type generated with `#[nutype]`, registered with the `inventory` feature.
+///
+/// ```
+/// use nutype::nutype;
+///
+/// #[nutype(sanitize(trim, lowercase), validate(not_empty, len_char_max = 64))]
+/// pub struct Email(String);
+///
+/// let email = nutype::registered_newtypes()
+///     .find(|info| info.metadata.name == "Email")
+///     .unwrap();
+/// assert_eq!(email.metadata, Email::METADATA);
+/// assert!(email
+///     .metadata
+///     .guarantees()
+///     .eq(["trimmed", "lowercase", "not empty", "at most 64 characters"]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtypeInfo {
+    /// Module where the type is declared, as returned by `module_path!()`.
+    pub module_path: &'static str,
+
+    /// The name, the inner type and the rules, the same as the `METADATA` constant of the type.
+    pub metadata: TypeMetadata,
+}
+
+inventory::collect!(NewtypeInfo);
+
+/// Returns all the types generated with `#[nutype]` within the final binary, in no particular order.
+pub fn registered_newtypes() -> impl Iterator<Item = &'static NewtypeInfo> {
+    inventory::iter::<NewtypeInfo>.into_iter()
+}
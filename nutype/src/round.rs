@@ -0,0 +1,111 @@
+//! Decimal aware rounding of floats, used by `round_dp` sanitizer and `max_dp` validator.
+//!
+//! A float like `2.675_f64` is stored as `2.67499999999999982236431605997495353221893310546875`.
+//! Rounding of the binary value (e.g. `(value * 100.0).round() / 100.0`) gives results that
+//! depend on the representation error and on the error of the multiplication: `1.005` becomes `1.0`,
+//! while `2.675` becomes `2.68`. Instead, the rounding is applied to the shortest decimal
+//! representation of the value, which is the number as it's written in the source code or parsed
+//! from a string. So `1.005`, `2.675` and `-1.555` are rounded to `1.01`, `2.68` and `-1.56`.
+//!
+//! It does not rely on `f64::round()`, which is not available in `no_std`.
+
+use core::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+/// Rounding to a given number of decimal places.
+pub trait RoundDp: Sized {
+    /// Rounds the shortest decimal representation of the value half away from zero
+    /// to `decimal_places`. NaN and infinities are returned as they are.
+    fn round_dp(self, decimal_places: u32) -> Self;
+}
+
+macro_rules! impl_round_dp {
+    ($($float:ty),*) => {
+        $(
+            impl RoundDp for $float {
+                fn round_dp(self, decimal_places: u32) -> Self {
+                    if !self.is_finite() {
+                        return self;
+                    }
+                    let mut repr = Buffer::new();
+                    // E.g. `-1.555e0` or `1e300`. It does not fail for finite values.
+                    if write!(repr, "{self:e}").is_err() {
+                        return self;
+                    }
+                    round_decimal(repr.as_str(), decimal_places)
+                        .and_then(|rounded| rounded.as_str().parse().ok())
+                        .unwrap_or(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_round_dp!(f32, f64);
+
+/// Rounds a number in scientific notation (as it's formatted with `{:e}`) to `decimal_places`.
+/// Returns `None` if the number already has at most `decimal_places`.
+fn round_decimal(repr: &str, decimal_places: u32) -> Option<Buffer> {
+    let (sign, unsigned) = match repr.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", repr),
+    };
+    let (mantissa, exponent) = unsigned.split_once('e')?;
+    let exponent = i64::from_str(exponent).ok()?;
+    let digits = mantissa.bytes().filter(u8::is_ascii_digit);
+
+    // The mantissa has a single digit before the point, so the number of digits to keep is
+    // the number of digits before the decimal point of the value plus `decimal_places`.
+    let keep = exponent + 1 + i64::from(decimal_places);
+    let digits_count = digits.clone().count() as i64;
+    if keep >= digits_count {
+        return None;
+    }
+
+    let mut rounded: u64 = 0;
+    let mut rest = digits.map(|digit| u64::from(digit - b'0'));
+    for _ in 0..keep.max(0) {
+        rounded = rounded * 10 + rest.next()?;
+    }
+    // Half away from zero: a tie (exactly `5` as the last digit) is rounded up as well.
+    let first_dropped = if keep < 0 { 0 } else { rest.next()? };
+    if first_dropped >= 5 {
+        rounded += 1;
+    }
+
+    let mut result = Buffer::new();
+    write!(result, "{sign}{rounded}e-{decimal_places}").ok()?;
+    Some(result)
+}
+
+/// A fixed size buffer to format a float without allocation.
+struct Buffer {
+    bytes: [u8; 40],
+    len: usize,
+}
+
+impl Buffer {
+    fn new() -> Self {
+        Self {
+            bytes: [0; 40],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only `&str` is ever written into the buffer.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let target = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+        target.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+/// A point in time, that can be checked by the temporal validators:
+/// `after`, `before`, `not_before`, `not_after`, `within_last`, `within_next`, `not_in_future`,
+/// `not_in_past`, `weekday_only` and `granularity`.
+///
+/// It is implemented for [`std::time::SystemTime`], for the date and time types of `chrono`
+/// with the `chrono` feature and of `time` with the `time` feature. Other types can implement it
+/// in a couple of lines:
+///
+/// ```ignore
+/// impl nutype::Temporal for Timestamp {
+///     fn unix_timestamp_nanos(&self) -> i128 {
+///         self.0.timestamp_nanos_opt().unwrap_or_default().into()
+///     }
+/// }
+/// ```
+pub trait Temporal {
+    /// Returns the number of nanoseconds since the Unix epoch (`1970-01-01T00:00:00Z`).
+    /// Values without a time zone are treated as UTC.
+    fn unix_timestamp_nanos(&self) -> i128;
+
+    /// Returns the number of nanoseconds since `1970-01-01T00:00:00` in the value's own offset,
+    /// i.e. the wall clock time. `weekday_only` and `granularity` check it, so
+    /// `2024-01-15T08:00:00+10:00` is a Monday at a whole hour.
+    ///
+    /// Defaults to [`Temporal::unix_timestamp_nanos`], which is right for values without an offset.
+    fn local_timestamp_nanos(&self) -> i128 {
+        self.unix_timestamp_nanos()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Temporal for std::time::SystemTime {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        match self.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => duration.as_nanos() as i128,
+            Err(err) => -(err.duration().as_nanos() as i128),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Temporal for chrono::NaiveDateTime {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        self.and_utc().unix_timestamp_nanos()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Temporal for chrono::NaiveDate {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        self.and_time(chrono::NaiveTime::MIN).unix_timestamp_nanos()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Temporal for chrono::DateTime<Tz> {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        i128::from(self.timestamp()) * 1_000_000_000 + i128::from(self.timestamp_subsec_nanos())
+    }
+
+    fn local_timestamp_nanos(&self) -> i128 {
+        self.naive_local().unix_timestamp_nanos()
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::OffsetDateTime {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        time::OffsetDateTime::unix_timestamp_nanos(*self)
+    }
+
+    fn local_timestamp_nanos(&self) -> i128 {
+        self.unix_timestamp_nanos() + i128::from(self.offset().whole_seconds()) * 1_000_000_000
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::PrimitiveDateTime {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        self.assume_utc().unix_timestamp_nanos()
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::Date {
+    fn unix_timestamp_nanos(&self) -> i128 {
+        self.midnight().assume_utc().unix_timestamp_nanos()
+    }
+}
+
+/// A length of time, that can be checked by `min` and `max` validators, e.g. a timeout.
+///
+/// It is implemented for [`core::time::Duration`], for `chrono::TimeDelta` with the `chrono`
+/// feature and for `time::Duration` with the `time` feature.
+///
+/// ```
+/// use core::time::Duration;
+/// use nutype::nutype;
+///
+/// #[nutype(validate(min = "100ms", max = "30s"), derive(Debug))]
+/// pub struct Timeout(std::time::Duration);
+///
+/// assert!(Timeout::try_new(Duration::from_secs(5)).is_ok());
+/// assert_eq!(
+///     Timeout::try_new(Duration::from_millis(10)).unwrap_err(),
+///     TimeoutError::MinViolated,
+/// );
+/// ```
+pub trait TimeSpan {
+    /// Returns the length in nanoseconds. It's negative for spans going back in time.
+    fn span_nanos(&self) -> i128;
+}
+
+impl TimeSpan for core::time::Duration {
+    fn span_nanos(&self) -> i128 {
+        self.as_nanos() as i128
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeSpan for chrono::TimeDelta {
+    fn span_nanos(&self) -> i128 {
+        i128::from(self.num_seconds()) * 1_000_000_000 + i128::from(self.subsec_nanos())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TimeSpan for time::Duration {
+    fn span_nanos(&self) -> i128 {
+        self.whole_nanoseconds()
+    }
+}
+
+/// The source of the current time for the validators relative to the current time:
+/// `within_last`, `within_next`, `not_in_future` and `not_in_past`.
+///
+/// The validators use [`SystemClock`] unless a clock is set with `clock = ...`, e.g. to pin
+/// the time in tests:
+///
+/// ```
+/// # mod wrapper_module {
+/// use nutype::{nutype, Clock};
+///
+/// struct NewYear2024;
+///
+/// impl Clock for NewYear2024 {
+///     fn now_unix_nanos() -> i128 {
+///         1_704_067_200 * 1_000_000_000
+///     }
+/// }
+///
+/// #[nutype(validate(within_last = "7d", clock = NewYear2024), derive(Debug))]
+/// pub struct RecentEvent(std::time::SystemTime);
+/// # }
+/// ```
+pub trait Clock {
+    /// Returns the number of nanoseconds since the Unix epoch.
+    fn now_unix_nanos() -> i128;
+}
+
+/// The clock of the operating system.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_unix_nanos() -> i128 {
+        std::time::SystemTime::now().unix_timestamp_nanos()
+    }
+}
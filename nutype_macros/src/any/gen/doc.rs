@@ -0,0 +1,63 @@
+use crate::{
+    any::models::{AnySanitizer, AnyValidator, Granularity},
+    common::models::Rule,
+};
+
+impl Rule for AnySanitizer {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        match self {
+            Self::With(_) => Some("sanitized with a custom function".to_string()),
+            Self::TryWith(_) => Some("sanitized with a fallible custom function".to_string()),
+        }
+    }
+
+    fn try_with_error_type(&self) -> Option<&syn::Type> {
+        match self {
+            Self::TryWith(try_sanitizer) => Some(&try_sanitizer.error_type),
+            _ => None,
+        }
+    }
+}
+
+impl Rule for AnyValidator {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let description = match self {
+            Self::Predicate(_) => "satisfies the predicate".to_string(),
+            Self::After(timestamp) => format!("after {}", timestamp.raw),
+            Self::Before(timestamp) => format!("before {}", timestamp.raw),
+            Self::NotBefore(timestamp) => format!("not before {}", timestamp.raw),
+            Self::NotAfter(timestamp) => format!("not after {}", timestamp.raw),
+            Self::WithinLast(duration) => format!("within the last {}", duration.raw),
+            Self::WithinNext(duration) => format!("within the next {}", duration.raw),
+            Self::NotInFuture => "not in the future".to_string(),
+            Self::NotInPast => "not in the past".to_string(),
+            Self::Clock(_) => return None,
+            Self::Min(duration) => format!("at least {}", duration.raw),
+            Self::Max(duration) => format!("at most {}", duration.raw),
+            Self::WeekdayOnly => "on a weekday".to_string(),
+            Self::Granularity(granularity) => {
+                let unit = match granularity {
+                    Granularity::Seconds => "seconds",
+                    Granularity::Minutes => "minutes",
+                    Granularity::Hours => "hours",
+                    Granularity::Days => "days",
+                };
+                format!("in whole {unit}")
+            }
+            Self::UuidVersion(version) => format!("a UUID of version {version}"),
+            Self::NotNil => "not the nil UUID".to_string(),
+            Self::NotLoopback => "not a loopback address".to_string(),
+            Self::NotUnspecified => "not the unspecified address".to_string(),
+            Self::NotMulticast => "not a multicast address".to_string(),
+        };
+        Some(description)
+    }
+}
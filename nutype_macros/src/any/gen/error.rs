@@ -4,8 +4,11 @@ use quote::quote;
 use crate::{
     any::models::AnyValidator,
     common::{
-        gen::error::gen_impl_error_trait,
-        models::{ErrorTypePath, TypeName},
+        gen::error::{
+            describe_range, gen_custom_display_arms, gen_extra_display_arms, gen_extra_variants,
+            gen_impl_error_code, gen_impl_error_trait, ErrorVariantCode, ExtraVariants, RangeBound,
+        },
+        models::{ErrorMessages, ErrorTypePath, Rule, TypeName},
     },
 };
 
@@ -13,10 +16,24 @@ pub fn gen_validation_error_type(
     type_name: &TypeName,
     error_type_path: &ErrorTypePath,
     validators: &[AnyValidator],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let definition = gen_definition(error_type_path, validators);
-    let impl_display_trait = gen_impl_display_trait(type_name, error_type_path, validators);
+    let definition = gen_definition(error_type_path, validators, extra_variants);
+    let impl_display_trait = gen_impl_display_trait(
+        type_name,
+        error_type_path,
+        validators,
+        extra_variants,
+        messages,
+    );
     let impl_error_trait = gen_impl_error_trait(error_type_path);
+    let impl_error_code = gen_impl_error_code(
+        error_type_path,
+        gen_variant_codes(validators),
+        None,
+        extra_variants,
+    );
 
     quote! {
         #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,23 +41,49 @@ pub fn gen_validation_error_type(
 
         #impl_display_trait
         #impl_error_trait
+        #impl_error_code
     }
 }
 
-fn gen_definition(error_type_path: &ErrorTypePath, validators: &[AnyValidator]) -> TokenStream {
+fn gen_definition(
+    error_type_path: &ErrorTypePath,
+    validators: &[AnyValidator],
+    extra_variants: ExtraVariants,
+) -> TokenStream {
     let error_variants: TokenStream = validators
         .iter()
         .map(|validator| match validator {
             AnyValidator::Predicate(_) => {
                 quote!(PredicateViolated,)
             }
+            AnyValidator::After(_) => quote!(AfterViolated,),
+            AnyValidator::Before(_) => quote!(BeforeViolated,),
+            AnyValidator::NotBefore(_) => quote!(NotBeforeViolated,),
+            AnyValidator::NotAfter(_) => quote!(NotAfterViolated,),
+            AnyValidator::WithinLast(_) => quote!(WithinLastViolated,),
+            AnyValidator::WithinNext(_) => quote!(WithinNextViolated,),
+            AnyValidator::NotInFuture => quote!(NotInFutureViolated,),
+            AnyValidator::NotInPast => quote!(NotInPastViolated,),
+            AnyValidator::Clock(_) => quote!(),
+            AnyValidator::Min(_) => quote!(MinViolated,),
+            AnyValidator::Max(_) => quote!(MaxViolated,),
+            AnyValidator::WeekdayOnly => quote!(WeekdayOnlyViolated,),
+            AnyValidator::Granularity(_) => quote!(GranularityViolated,),
+            AnyValidator::UuidVersion(_) => quote!(UuidVersionViolated,),
+            AnyValidator::NotNil => quote!(NotNilViolated,),
+            AnyValidator::NotLoopback => quote!(NotLoopbackViolated,),
+            AnyValidator::NotUnspecified => quote!(NotUnspecifiedViolated,),
+            AnyValidator::NotMulticast => quote!(NotMulticastViolated,),
         })
         .collect();
 
+    let extra_variants_tokens = gen_extra_variants(extra_variants);
+
     quote! {
         #[allow(clippy::enum_variant_names)]
         pub enum #error_type_path {
             #error_variants
+            #extra_variants_tokens
         }
     }
 }
@@ -49,20 +92,165 @@ fn gen_impl_display_trait(
     type_name: &TypeName,
     error_type_path: &ErrorTypePath,
     validators: &[AnyValidator],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let match_arms = validators.iter().map(|validator| match validator {
+    let match_arms = validators
+        .iter()
+        .filter(|validator| messages.get(&validator.name()).is_none())
+        .filter_map(|validator| Some(match validator {
         AnyValidator::Predicate(_) => quote! {
              #error_type_path::PredicateViolated => write!(f, "{} failed the predicate test.", stringify!(#type_name))
         },
-    });
+        AnyValidator::After(timestamp) => {
+            let raw = &timestamp.raw;
+            quote! {
+                #error_type_path::AfterViolated => write!(f, "{} is too early. The value must be after {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::Before(timestamp) => {
+            let raw = &timestamp.raw;
+            quote! {
+                #error_type_path::BeforeViolated => write!(f, "{} is too late. The value must be before {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::NotBefore(timestamp) => {
+            let raw = &timestamp.raw;
+            quote! {
+                #error_type_path::NotBeforeViolated => write!(f, "{} is too early. The value must not be before {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::NotAfter(timestamp) => {
+            let raw = &timestamp.raw;
+            quote! {
+                #error_type_path::NotAfterViolated => write!(f, "{} is too late. The value must not be after {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::WithinLast(duration) => {
+            let raw = &duration.raw;
+            quote! {
+                #error_type_path::WithinLastViolated => write!(f, "{} must be within the last {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::WithinNext(duration) => {
+            let raw = &duration.raw;
+            quote! {
+                #error_type_path::WithinNextViolated => write!(f, "{} must be within the next {}.", stringify!(#type_name), #raw)
+            }
+        }
+        AnyValidator::NotInFuture => quote! {
+             #error_type_path::NotInFutureViolated => write!(f, "{} must not be in the future.", stringify!(#type_name))
+        },
+        AnyValidator::NotInPast => quote! {
+             #error_type_path::NotInPastViolated => write!(f, "{} must not be in the past.", stringify!(#type_name))
+        },
+        AnyValidator::Clock(_) => return None,
+        AnyValidator::Min(_) => {
+            let message = gen_span_message(type_name, "short", validators);
+            quote!(#error_type_path::MinViolated => #message)
+        }
+        AnyValidator::Max(_) => {
+            let message = gen_span_message(type_name, "long", validators);
+            quote!(#error_type_path::MaxViolated => #message)
+        }
+        AnyValidator::WeekdayOnly => quote! {
+             #error_type_path::WeekdayOnlyViolated => write!(f, "{} falls on a weekend.", stringify!(#type_name))
+        },
+        AnyValidator::Granularity(granularity) => {
+            let name = granularity.name();
+            quote! {
+                #error_type_path::GranularityViolated => write!(f, "{} is too precise. The value must be in whole {}.", stringify!(#type_name), #name)
+            }
+        }
+        AnyValidator::UuidVersion(version) => quote! {
+             #error_type_path::UuidVersionViolated => write!(f, "{} must be a UUID of version {}.", stringify!(#type_name), #version)
+        },
+        AnyValidator::NotNil => quote! {
+             #error_type_path::NotNilViolated => write!(f, "{} must not be the nil UUID.", stringify!(#type_name))
+        },
+        AnyValidator::NotLoopback => quote! {
+             #error_type_path::NotLoopbackViolated => write!(f, "{} must not be a loopback address.", stringify!(#type_name))
+        },
+        AnyValidator::NotUnspecified => quote! {
+             #error_type_path::NotUnspecifiedViolated => write!(f, "{} must not be the unspecified address.", stringify!(#type_name))
+        },
+        AnyValidator::NotMulticast => quote! {
+             #error_type_path::NotMulticastViolated => write!(f, "{} must not be a multicast address.", stringify!(#type_name))
+        },
+    }));
+
+    let custom_arms = gen_custom_display_arms(type_name, gen_variant_codes(validators), messages);
+
+    let extra_arms = gen_extra_display_arms(type_name, error_type_path, extra_variants);
 
     quote! {
         impl ::core::fmt::Display for #error_type_path {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 match self {
                     #(#match_arms,)*
+                    #(#custom_arms,)*
+                    #extra_arms
                 }
             }
         }
     }
 }
+
+/// Message of a violated `min` or `max`, that mentions both bounds when they are set,
+/// e.g. `Timeout is too long. The value must be between 1s and 1h.`
+fn gen_span_message(type_name: &TypeName, too: &str, validators: &[AnyValidator]) -> TokenStream {
+    let mut lower = None;
+    let mut upper = None;
+    for validator in validators {
+        match validator {
+            AnyValidator::Min(duration) => {
+                let raw = &duration.raw;
+                lower = Some(RangeBound {
+                    value: quote!(#raw),
+                    inclusive: true,
+                })
+            }
+            AnyValidator::Max(duration) => {
+                let raw = &duration.raw;
+                upper = Some(RangeBound {
+                    value: quote!(#raw),
+                    inclusive: true,
+                })
+            }
+            _ => {}
+        }
+    }
+    let (range, values) = describe_range(lower, upper, "{}", "");
+    let format = format!("{{}} is too {too}. The value must be {range}.");
+    quote!(write!(f, #format, stringify!(#type_name), #(#values),*))
+}
+
+fn gen_variant_codes(validators: &[AnyValidator]) -> Vec<ErrorVariantCode> {
+    validators
+        .iter()
+        .filter_map(|validator| {
+            let pattern = match validator {
+                AnyValidator::Predicate(_) => quote!(Self::PredicateViolated),
+                AnyValidator::After(_) => quote!(Self::AfterViolated),
+                AnyValidator::Before(_) => quote!(Self::BeforeViolated),
+                AnyValidator::NotBefore(_) => quote!(Self::NotBeforeViolated),
+                AnyValidator::NotAfter(_) => quote!(Self::NotAfterViolated),
+                AnyValidator::WithinLast(_) => quote!(Self::WithinLastViolated),
+                AnyValidator::WithinNext(_) => quote!(Self::WithinNextViolated),
+                AnyValidator::NotInFuture => quote!(Self::NotInFutureViolated),
+                AnyValidator::NotInPast => quote!(Self::NotInPastViolated),
+                AnyValidator::Clock(_) => return None,
+                AnyValidator::Min(_) => quote!(Self::MinViolated),
+                AnyValidator::Max(_) => quote!(Self::MaxViolated),
+                AnyValidator::WeekdayOnly => quote!(Self::WeekdayOnlyViolated),
+                AnyValidator::Granularity(_) => quote!(Self::GranularityViolated),
+                AnyValidator::UuidVersion(_) => quote!(Self::UuidVersionViolated),
+                AnyValidator::NotNil => quote!(Self::NotNilViolated),
+                AnyValidator::NotLoopback => quote!(Self::NotLoopbackViolated),
+                AnyValidator::NotUnspecified => quote!(Self::NotUnspecifiedViolated),
+                AnyValidator::NotMulticast => quote!(Self::NotMulticastViolated),
+            };
+            Some(ErrorVariantCode::new(pattern, validator))
+        })
+        .collect()
+}
@@ -1,17 +1,27 @@
+mod doc;
 mod error;
 mod traits;
 
 use std::collections::HashSet;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 use syn::{parse_quote, Generics};
 
 use crate::common::{
     gen::{
-        tests::gen_test_should_have_valid_default_value, traits::GeneratedTraits, GenerateNewtype,
+        error::ExtraVariants,
+        new_into::gen_new_into,
+        tests::gen_test_should_have_valid_default_value,
+        trace::{gen_sanitizations, gen_validations},
+        traits::GeneratedTraits,
+        try_with::{gen_fn_sanitize_of, gen_try_with_step},
+        GenerateNewtype,
+    },
+    models::{
+        DeriveOptions, ErrorMessages, ErrorTypePath, Guard, OnInvalid, RuleNode, StoreAs, TypeName,
+        TypedCustomFunction,
     },
-    models::{ErrorTypePath, Guard, TypeName, TypedCustomFunction},
 };
 
 use self::error::gen_validation_error_type;
@@ -29,13 +39,20 @@ impl GenerateNewtype for AnyNewtype {
     type InnerType = AnyInnerType;
     type TypedTrait = AnyDeriveTrait;
 
+    // The inner type may be private.
+    const IMPLEMENTS_NEWTYPE_TRAITS: bool = false;
+
     fn gen_fn_sanitize(
         inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
+        trace: bool,
+        lossy: bool,
     ) -> TokenStream {
-        let transformations: TokenStream = sanitizers
-            .iter()
-            .map(|san| match san {
+        let transformations = gen_sanitizations(
+            &RuleNode::sanitizers(sanitizers),
+            trace,
+            lossy,
+            |san| match san {
                 AnySanitizer::With(custom_sanitizer) => {
                     let inner_type_ref: syn::Type = parse_quote!(
                         #inner_type
@@ -48,25 +65,74 @@ impl GenerateNewtype for AnyNewtype {
                         value = (#typed_sanitizer)(value);
                     )
                 }
-            })
-            .collect();
+                AnySanitizer::TryWith(try_sanitizer) => {
+                    let step = gen_try_with_step(try_sanitizer, inner_type);
+                    quote!(
+                        value = #step;
+                    )
+                }
+            },
+        );
 
-        quote!(
-            fn __sanitize__(mut value: #inner_type) -> #inner_type {
-                #transformations
-                value
-            }
+        gen_fn_sanitize_of(
+            quote!(mut value: #inner_type),
+            inner_type,
+            sanitizers,
+            transformations,
+            lossy,
+        )
+    }
+
+    // The inner type is not required to implement `PartialEq`, so `eq_raw()` is not generated.
+    fn gen_fn_new_into(
+        type_name: &TypeName,
+        generics: &Generics,
+        inner_type: &Self::InnerType,
+        guard: &AnyGuard,
+        _arena: bool,
+        maybe_on_invalid: Option<&OnInvalid>,
+        constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        let Some(element_type) = inner_type.vec_element_type() else {
+            return quote!();
+        };
+        gen_new_into(
+            type_name,
+            generics,
+            inner_type,
+            quote!(&[#element_type]),
+            quote!(value.extend_from_slice(raw);),
+            Some(quote!(where #element_type: ::core::clone::Clone)),
+            guard.maybe_error_type_path(),
+            maybe_on_invalid,
+            constructor_vis,
         )
     }
 
+    fn gen_fn_eq_raw(
+        _inner_type: &Self::InnerType,
+        _sanitizers: &[Self::Sanitizer],
+        _maybe_store_as: Option<&StoreAs>,
+    ) -> TokenStream {
+        quote!()
+    }
+
     fn gen_fn_validate(
         inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        trace: bool,
     ) -> TokenStream {
-        let validations: TokenStream = validators
+        let clock = validators
             .iter()
-            .map(|validator| match validator {
+            .find_map(|validator| match validator {
+                AnyValidator::Clock(clock) => Some(quote!(#clock)),
+                _ => None,
+            })
+            .unwrap_or_else(|| quote!(::nutype::SystemClock));
+
+        let validations = gen_validations(&RuleNode::validators(validators), trace, |validator| {
+            match validator {
                 AnyValidator::Predicate(predicate) => {
                     let inner_type_ref: syn::Type = parse_quote!(
                         &'nutype_a #inner_type
@@ -81,36 +147,216 @@ impl GenerateNewtype for AnyNewtype {
                         }
                     )
                 }
-            })
-            .collect();
+                AnyValidator::After(timestamp) => {
+                    let bound = timestamp.unix_nanos;
+                    quote!(
+                        if !(::nutype::Temporal::unix_timestamp_nanos(val) > #bound) {
+                            return Err(#error_type_path::AfterViolated);
+                        }
+                    )
+                }
+                AnyValidator::Before(timestamp) => {
+                    let bound = timestamp.unix_nanos;
+                    quote!(
+                        if !(::nutype::Temporal::unix_timestamp_nanos(val) < #bound) {
+                            return Err(#error_type_path::BeforeViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotBefore(timestamp) => {
+                    let bound = timestamp.unix_nanos;
+                    quote!(
+                        if ::nutype::Temporal::unix_timestamp_nanos(val) < #bound {
+                            return Err(#error_type_path::NotBeforeViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotAfter(timestamp) => {
+                    let bound = timestamp.unix_nanos;
+                    quote!(
+                        if ::nutype::Temporal::unix_timestamp_nanos(val) > #bound {
+                            return Err(#error_type_path::NotAfterViolated);
+                        }
+                    )
+                }
+                AnyValidator::WithinLast(duration) => {
+                    let window = duration.nanos;
+                    quote!(
+                        let value = ::nutype::Temporal::unix_timestamp_nanos(val);
+                        if value > now || value < now - #window {
+                            return Err(#error_type_path::WithinLastViolated);
+                        }
+                    )
+                }
+                AnyValidator::WithinNext(duration) => {
+                    let window = duration.nanos;
+                    quote!(
+                        let value = ::nutype::Temporal::unix_timestamp_nanos(val);
+                        if value < now || value > now + #window {
+                            return Err(#error_type_path::WithinNextViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotInFuture => quote!(
+                    if ::nutype::Temporal::unix_timestamp_nanos(val) > now {
+                        return Err(#error_type_path::NotInFutureViolated);
+                    }
+                ),
+                AnyValidator::NotInPast => quote!(
+                    if ::nutype::Temporal::unix_timestamp_nanos(val) < now {
+                        return Err(#error_type_path::NotInPastViolated);
+                    }
+                ),
+                AnyValidator::Clock(_) => quote!(),
+                AnyValidator::Min(duration) => {
+                    let bound = duration.nanos;
+                    quote!(
+                        if ::nutype::TimeSpan::span_nanos(val) < #bound {
+                            return Err(#error_type_path::MinViolated);
+                        }
+                    )
+                }
+                AnyValidator::Max(duration) => {
+                    let bound = duration.nanos;
+                    quote!(
+                        if ::nutype::TimeSpan::span_nanos(val) > #bound {
+                            return Err(#error_type_path::MaxViolated);
+                        }
+                    )
+                }
+                AnyValidator::WeekdayOnly => {
+                    quote!(
+                        // 1970-01-01 was Thursday, so Monday gets 0 and Sunday gets 6.
+                        let days = ::nutype::Temporal::local_timestamp_nanos(val).div_euclid(86_400_000_000_000);
+                        if (days + 3).rem_euclid(7) >= 5 {
+                            return Err(#error_type_path::WeekdayOnlyViolated);
+                        }
+                    )
+                }
+                AnyValidator::Granularity(granularity) => {
+                    let nanos = granularity.nanos();
+                    quote!(
+                        if ::nutype::Temporal::local_timestamp_nanos(val).rem_euclid(#nanos) != 0 {
+                            return Err(#error_type_path::GranularityViolated);
+                        }
+                    )
+                }
+                // UUID validators rely on the API of `uuid::Uuid`.
+                AnyValidator::UuidVersion(version) => {
+                    let version = Literal::usize_unsuffixed(usize::from(*version));
+                    quote!(
+                        if val.get_version_num() != #version {
+                            return Err(#error_type_path::UuidVersionViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotNil => {
+                    quote!(
+                        if val.is_nil() {
+                            return Err(#error_type_path::NotNilViolated);
+                        }
+                    )
+                }
+                // IP validators rely on the API of `IpAddr`, `Ipv4Addr` and `Ipv6Addr`.
+                AnyValidator::NotLoopback => {
+                    quote!(
+                        if val.is_loopback() {
+                            return Err(#error_type_path::NotLoopbackViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotUnspecified => {
+                    quote!(
+                        if val.is_unspecified() {
+                            return Err(#error_type_path::NotUnspecifiedViolated);
+                        }
+                    )
+                }
+                AnyValidator::NotMulticast => {
+                    quote!(
+                        if val.is_multicast() {
+                            return Err(#error_type_path::NotMulticastViolated);
+                        }
+                    )
+                }
+            }
+        });
+
+        let has_relative_validator = validators.iter().any(AnyValidator::is_relative_to_now);
+        if !has_relative_validator {
+            return quote!(
+                // NOTE 1: we're using a unique lifetime name `nutype_a` in a hope that it will not clash
+                // with any other lifetimes in the user's code.
+                //
+                // NOTE 2:
+                // When inner type is Cow<'a, str>, the generated code will look like this (with 2
+                // lifetimes):
+                //
+                //     fn __validate__<'nutype_a>(val: &'nutype_a Cow<'a, str>)
+                //
+                // Clippy does not like passing a reference to a Cow. So we need to ignore the `clippy::ptr_arg` warning.
+                // Since this code is generic which is used for different inner types (not only Cow), we cannot easily fix it to make
+                // clippy happy.
+                #[allow(clippy::ptr_arg)]
+                fn __validate__<'nutype_a>(val: &'nutype_a #inner_type) -> ::core::result::Result<(), #error_type_path> {
+                    #validations
+                    Ok(())
+                }
+            );
+        }
 
+        // `within_last`, `within_next`, `not_in_future` and `not_in_past` depend on the current time, so `try_new_at()` is
+        // generated to validate against a given time instead of the clock.
         quote!(
-            // NOTE 1: we're using a unique lifetime name `nutype_a` in a hope that it will not clash
-            // with any other lifetimes in the user's code.
-            //
-            // NOTE 2:
-            // When inner type is Cow<'a, str>, the generated code will look like this (with 2
-            // lifetimes):
-            //
-            //     fn __validate__<'nutype_a>(val: &'nutype_a Cow<'a, str>)
-            //
-            // Clippy does not like passing a reference to a Cow. So we need to ignore the `clippy::ptr_arg` warning.
-            // Since this code is generic which is used for different inner types (not only Cow), we cannot easily fix it to make
-            // clippy happy.
             #[allow(clippy::ptr_arg)]
             fn __validate__<'nutype_a>(val: &'nutype_a #inner_type) -> ::core::result::Result<(), #error_type_path> {
+                Self::__validate_at__(val, <#clock as ::nutype::Clock>::now_unix_nanos())
+            }
+
+            #[allow(clippy::ptr_arg)]
+            fn __validate_at__<'nutype_a>(val: &'nutype_a #inner_type, now: i128) -> ::core::result::Result<(), #error_type_path> {
                 #validations
                 Ok(())
             }
         )
     }
 
+    fn gen_extra_constructors(
+        inner_type: &Self::InnerType,
+        validators: &[Self::Validator],
+        error_type_path: &ErrorTypePath,
+        constructor_vis: &TokenStream,
+        report_validation_failure: &TokenStream,
+    ) -> TokenStream {
+        if !validators.iter().any(AnyValidator::is_relative_to_now) {
+            return quote!();
+        }
+        quote!(
+            /// Same as `try_new()`, but the validators relative to the current time are validated
+            /// relative to `now` instead of the current time of the clock.
+            #constructor_vis fn try_new_at(raw_value: #inner_type, now: impl ::nutype::Temporal) -> ::core::result::Result<Self, #error_type_path> {
+                let sanitized_value: #inner_type = Self::__sanitize__(raw_value);
+                Self::__validate_at__(&sanitized_value, ::nutype::Temporal::unix_timestamp_nanos(&now))#report_validation_failure?;
+                Ok(Self(sanitized_value))
+            }
+        )
+    }
+
     fn gen_validation_error_type(
         type_name: &TypeName,
+        _inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        extra_variants: ExtraVariants,
+        messages: &ErrorMessages,
     ) -> TokenStream {
-        gen_validation_error_type(type_name, error_type_path, validators)
+        gen_validation_error_type(
+            type_name,
+            error_type_path,
+            validators,
+            extra_variants,
+            messages,
+        )
     }
 
     fn gen_traits(
@@ -119,7 +365,9 @@ impl GenerateNewtype for AnyNewtype {
         inner_type: &Self::InnerType,
         traits: HashSet<Self::TypedTrait>,
         maybe_default_value: Option<syn::Expr>,
+        derive_options: &DeriveOptions,
         guard: &AnyGuard,
+        _maybe_store_as: Option<&StoreAs>,
     ) -> Result<GeneratedTraits, syn::Error> {
         gen_traits(
             type_name,
@@ -127,6 +375,7 @@ impl GenerateNewtype for AnyNewtype {
             inner_type,
             traits,
             maybe_default_value,
+            derive_options,
             guard,
         )
     }
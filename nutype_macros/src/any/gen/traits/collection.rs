@@ -0,0 +1,122 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Generics};
+
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::strip_trait_bounds_on_generics,
+        models::{ErrorTypePath, TypeName},
+    },
+};
+
+/// Implement `IntoIterator` for the type and for a reference to it, so a wrapped collection can
+/// be iterated without access to the inner value. Collections of the standard library also get
+/// `len()` and `is_empty()`.
+pub fn gen_impl_trait_into_iterator(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &AnyInnerType,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let mut generics_with_lifetime = generics.clone();
+    generics_with_lifetime
+        .params
+        .insert(0, parse_quote!('__nutype_a));
+
+    let impl_len = inner_type.is_std_collection().then(|| {
+        quote! {
+            impl #generics #type_name #generics_without_bounds {
+                /// Returns the number of elements of the inner collection.
+                #[inline]
+                pub fn len(&self) -> usize {
+                    self.0.len()
+                }
+
+                /// Returns `true` if the inner collection contains no elements.
+                #[inline]
+                pub fn is_empty(&self) -> bool {
+                    self.0.is_empty()
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #generics ::core::iter::IntoIterator for #type_name #generics_without_bounds {
+            type Item = <#inner_type as ::core::iter::IntoIterator>::Item;
+            type IntoIter = <#inner_type as ::core::iter::IntoIterator>::IntoIter;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                ::core::iter::IntoIterator::into_iter(self.into_inner())
+            }
+        }
+
+        impl #generics_with_lifetime ::core::iter::IntoIterator for &'__nutype_a #type_name #generics_without_bounds
+        where
+            &'__nutype_a #inner_type: ::core::iter::IntoIterator,
+        {
+            type Item = <&'__nutype_a #inner_type as ::core::iter::IntoIterator>::Item;
+            type IntoIter = <&'__nutype_a #inner_type as ::core::iter::IntoIterator>::IntoIter;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                ::core::iter::IntoIterator::into_iter(&self.0)
+            }
+        }
+
+        #impl_len
+    }
+}
+
+/// Implement `Extend`, that extends the inner collection and passes the result through the
+/// sanitizers again. `extend()` cannot report an error, so types with validation get
+/// `try_extend()` instead of `Extend`: it returns the validation error and leaves the value
+/// unchanged, because the extended collection is validated before it replaces the old one.
+pub fn gen_impl_trait_extend(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &AnyInnerType,
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+
+    if let Some(error_type_name) = maybe_error_type_name {
+        return quote! {
+            impl #generics #type_name #generics_without_bounds {
+                /// Extends the inner collection with the items of the iterator and passes the
+                /// result through the sanitizers and validators again.
+                /// If the extended collection is invalid, the error is returned and the value stays unchanged.
+                pub fn try_extend<__NutypeItem, I: ::core::iter::IntoIterator<Item = __NutypeItem>>(
+                    &mut self,
+                    iter: I,
+                ) -> ::core::result::Result<(), #error_type_name>
+                where
+                    #inner_type: ::core::iter::Extend<__NutypeItem> + ::core::clone::Clone,
+                {
+                    let mut value = ::core::clone::Clone::clone(&self.0);
+                    ::core::iter::Extend::extend(&mut value, iter);
+                    *self = Self::try_new(value)?;
+                    ::core::result::Result::Ok(())
+                }
+            }
+        };
+    }
+
+    let mut generics_with_item = generics.clone();
+    generics_with_item.params.push(parse_quote!(__NutypeItem));
+
+    quote! {
+        impl #generics_with_item ::core::iter::Extend<__NutypeItem> for #type_name #generics_without_bounds
+        where
+            #inner_type: ::core::iter::Extend<__NutypeItem> + ::core::default::Default,
+        {
+            fn extend<I: ::core::iter::IntoIterator<Item = __NutypeItem>>(&mut self, iter: I) {
+                let mut value = ::core::mem::take(&mut self.0);
+                ::core::iter::Extend::extend(&mut value, iter);
+                *self = Self::new(value);
+            }
+        }
+    }
+}
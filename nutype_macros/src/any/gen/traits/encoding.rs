@@ -0,0 +1,97 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::{
+            parse_error::{gen_def_parse_error, gen_parse_error_name},
+            strip_trait_bounds_on_generics,
+        },
+        models::{Encoding, ErrorTypePath, TypeName},
+    },
+};
+
+/// `nutype::Hex` or `nutype::Base64`, that parses and displays `[u8; N]`.
+fn gen_encoded_type_path(encoding: Encoding) -> TokenStream {
+    match encoding {
+        Encoding::Hex => quote!(::nutype::Hex),
+        Encoding::Base64 => quote!(::nutype::Base64),
+    }
+}
+
+/// `FromStr` of a `[u8; N]` based type with `encoding = "..."`, that decodes the string
+/// into the bytes and then sanitizes and validates them.
+pub fn gen_impl_trait_from_str_encoded(
+    type_name: &TypeName,
+    generics: &Generics,
+    encoding: Encoding,
+    len: &syn::Expr,
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let encoded_type_path = gen_encoded_type_path(encoding);
+    let encoded_type: syn::Type = syn::parse_quote!(#encoded_type_path<#len>);
+    let parse_error_type_name = gen_parse_error_name(type_name);
+    let def_parse_error = gen_def_parse_error(
+        type_name,
+        generics,
+        AnyInnerType::new(syn::Field {
+            attrs: vec![],
+            vis: syn::Visibility::Inherited,
+            mutability: syn::FieldMutability::None,
+            ident: None,
+            colon_token: None,
+            ty: encoded_type.clone(),
+        }),
+        maybe_error_type_name,
+        &parse_error_type_name,
+    );
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+
+    let construct = if maybe_error_type_name.is_some() {
+        quote!(Self::try_new(bytes).map_err(#parse_error_type_name::Validate))
+    } else {
+        quote!(Ok(Self::new(bytes)))
+    };
+
+    quote! {
+        #def_parse_error
+
+        impl #generics ::core::str::FromStr for #type_name #generics_without_bounds {
+            type Err = #parse_error_type_name #generics_without_bounds;
+
+            fn from_str(raw_string: &str) -> ::core::result::Result<Self, Self::Err> {
+                let decoded: #encoded_type = raw_string.parse().map_err(#parse_error_type_name::Parse)?;
+                let bytes = decoded.0;
+                #construct
+            }
+        }
+    }
+}
+
+/// `Display` of a `[u8; N]` based type with `encoding = "..."`, that writes the encoded bytes.
+pub fn gen_impl_trait_display_encoded(
+    type_name: &TypeName,
+    generics: &Generics,
+    encoding: Encoding,
+    len: &syn::Expr,
+    maybe_format: Option<&syn::LitStr>,
+) -> TokenStream {
+    let encoded_type_path = gen_encoded_type_path(encoding);
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let encoded_value = quote!(#encoded_type_path::<#len>(self.0));
+    let body = match maybe_format {
+        Some(format) => quote!(::core::write!(f, #format, #encoded_value)),
+        None => quote!(::core::fmt::Display::fmt(&#encoded_value, f)),
+    };
+
+    quote! {
+        impl #generics ::core::fmt::Display for #type_name #generics_without_bounds {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    }
+}
@@ -1,21 +1,34 @@
 pub mod arbitrary;
+pub mod collection;
+pub mod encoding;
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use std::collections::HashSet;
 
+use self::{
+    collection::{gen_impl_trait_extend, gen_impl_trait_into_iterator},
+    encoding::{gen_impl_trait_display_encoded, gen_impl_trait_from_str_encoded},
+};
 use crate::{
     any::models::AnyDeriveTrait,
     any::models::{AnyGuard, AnyInnerType},
     common::{
-        gen::traits::{
-            gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
-            gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
-            gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_serde_deserialize,
-            gen_impl_trait_serde_serialize, gen_impl_trait_try_from, split_into_generatable_traits,
-            GeneratableTrait, GeneratableTraits, GeneratedTraits,
+        gen::{
+            borsh::{gen_impl_trait_borsh_deserialize, gen_impl_trait_borsh_serialize},
+            clap::gen_impl_trait_clap_value_parser_factory,
+            rkyv::gen_impl_trait_rkyv,
+            traits::{
+                gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
+                gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
+                gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_into_types,
+                gen_impl_trait_serde_deserialize, gen_impl_trait_serde_serialize,
+                gen_impl_trait_try_from, gen_impl_trait_try_from_types,
+                gen_impl_trait_validator_validate, split_into_generatable_traits, GeneratableTrait,
+                GeneratableTraits, GeneratedTraits,
+            },
         },
-        models::TypeName,
+        models::{DeriveOptions, TypeName},
     },
 };
 
@@ -45,6 +58,10 @@ impl From<AnyDeriveTrait> for AnyGeneratableTrait {
             AnyDeriveTrait::FromStr => AnyGeneratableTrait::Irregular(AnyIrregularTrait::FromStr),
             AnyDeriveTrait::TryFrom => AnyGeneratableTrait::Irregular(AnyIrregularTrait::TryFrom),
             AnyDeriveTrait::Default => AnyGeneratableTrait::Irregular(AnyIrregularTrait::Default),
+            AnyDeriveTrait::IntoIterator => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::IntoIterator)
+            }
+            AnyDeriveTrait::Extend => AnyGeneratableTrait::Irregular(AnyIrregularTrait::Extend),
             AnyDeriveTrait::SerdeSerialize => {
                 AnyGeneratableTrait::Irregular(AnyIrregularTrait::SerdeSerialize)
             }
@@ -54,6 +71,21 @@ impl From<AnyDeriveTrait> for AnyGeneratableTrait {
             AnyDeriveTrait::ArbitraryArbitrary => {
                 AnyGeneratableTrait::Irregular(AnyIrregularTrait::ArbitraryArbitrary)
             }
+            AnyDeriveTrait::ValidatorValidate => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::ValidatorValidate)
+            }
+            AnyDeriveTrait::BorshSerialize => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::BorshSerialize)
+            }
+            AnyDeriveTrait::BorshDeserialize => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::BorshDeserialize)
+            }
+            AnyDeriveTrait::RkyvArchive => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::RkyvArchive)
+            }
+            AnyDeriveTrait::ClapValueParserFactory => {
+                AnyGeneratableTrait::Irregular(AnyIrregularTrait::ClapValueParserFactory)
+            }
         }
     }
 }
@@ -100,9 +132,16 @@ enum AnyIrregularTrait {
     FromStr,
     TryFrom,
     Default,
+    IntoIterator,
+    Extend,
     SerdeSerialize,
     SerdeDeserialize,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 pub fn gen_traits(
@@ -111,6 +150,7 @@ pub fn gen_traits(
     inner_type: &AnyInnerType,
     traits: HashSet<AnyDeriveTrait>,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     guard: &AnyGuard,
 ) -> Result<GeneratedTraits, syn::Error> {
     let GeneratableTraits {
@@ -130,6 +170,7 @@ pub fn gen_traits(
         inner_type,
         irregular_traits,
         maybe_default_value,
+        derive_options,
         guard,
     )?;
 
@@ -145,24 +186,45 @@ fn gen_implemented_traits(
     inner_type: &AnyInnerType,
     impl_traits: Vec<AnyIrregularTrait>,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     guard: &AnyGuard,
 ) -> Result<TokenStream, syn::Error> {
     let maybe_error_type_name = guard.maybe_error_type_path();
+    // `[u8; N]` with `encoding = "..."` is parsed from and displayed as the encoded string.
+    let encoded_bytes = derive_options
+        .encoding
+        .as_ref()
+        .zip(inner_type.byte_array_len())
+        .map(|(encoding, len)| (encoding.item, len));
     impl_traits
         .iter()
         .map(|t| match t {
             AnyIrregularTrait::AsRef => Ok(gen_impl_trait_as_ref(type_name, generics, inner_type)),
             AnyIrregularTrait::From => Ok(gen_impl_trait_from(type_name, generics, inner_type)),
-            AnyIrregularTrait::Into => Ok(gen_impl_trait_into(type_name, generics, inner_type.clone())),
-            AnyIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics)),
+            AnyIrregularTrait::Into => match derive_options.into {
+                Some(ref target_types) => Ok(gen_impl_trait_into_types(type_name, generics, target_types)),
+                None => Ok(gen_impl_trait_into(type_name, generics, inner_type.clone())),
+            },
+            AnyIrregularTrait::Display => match encoded_bytes {
+                Some((encoding, len)) => Ok(gen_impl_trait_display_encoded(type_name, generics, encoding, len, derive_options.display.as_ref())),
+                None => Ok(gen_impl_trait_display(type_name, generics, derive_options.display.as_ref(), None)),
+            },
             AnyIrregularTrait::Deref => Ok(gen_impl_trait_deref(type_name, generics, inner_type)),
             AnyIrregularTrait::Borrow => Ok(gen_impl_trait_borrow(type_name, generics, inner_type)),
-            AnyIrregularTrait::FromStr => Ok(
-                gen_impl_trait_from_str(type_name, generics, inner_type, maybe_error_type_name)
-            ),
-            AnyIrregularTrait::TryFrom => Ok(
-                gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name)
-            ),
+            AnyIrregularTrait::FromStr => match encoded_bytes {
+                Some((encoding, len)) => Ok(gen_impl_trait_from_str_encoded(type_name, generics, encoding, len, maybe_error_type_name)),
+                None => Ok(gen_impl_trait_from_str(type_name, generics, inner_type, maybe_error_type_name)),
+            },
+            AnyIrregularTrait::TryFrom => match derive_options.try_from {
+                Some(ref source_types) => Ok(gen_impl_trait_try_from_types(
+                    type_name,
+                    generics,
+                    inner_type,
+                    source_types,
+                    maybe_error_type_name,
+                )),
+                None => Ok(gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name)),
+            },
             AnyIrregularTrait::Default => match maybe_default_value {
                 Some(ref default_value) => {
                     let has_validation = maybe_error_type_name.is_some();
@@ -174,13 +236,40 @@ fn gen_implemented_traits(
                     Err(syn::Error::new(span, msg))
                 }
             },
+            AnyIrregularTrait::IntoIterator => Ok(gen_impl_trait_into_iterator(type_name, generics, inner_type)),
+            AnyIrregularTrait::Extend => {
+                Ok(gen_impl_trait_extend(type_name, generics, inner_type, maybe_error_type_name))
+            }
             AnyIrregularTrait::SerdeSerialize => Ok(
-                gen_impl_trait_serde_serialize(type_name, generics)
+                gen_impl_trait_serde_serialize(type_name, generics, &derive_options.serde, None)
             ),
             AnyIrregularTrait::SerdeDeserialize => Ok(
-                gen_impl_trait_serde_deserialize(type_name, generics, inner_type, maybe_error_type_name)
+                gen_impl_trait_serde_deserialize(type_name, generics, inner_type, maybe_error_type_name, &derive_options.serde, None, derive_options.profile.denies_unknown_fields())
             ),
             AnyIrregularTrait::ArbitraryArbitrary => arbitrary::gen_impl_trait_arbitrary(type_name, generics, inner_type, guard),
+            AnyIrregularTrait::ValidatorValidate => {
+                Ok(gen_impl_trait_validator_validate(type_name, generics))
+            }
+            AnyIrregularTrait::BorshSerialize => {
+                Ok(gen_impl_trait_borsh_serialize(type_name, generics, None))
+            }
+            AnyIrregularTrait::BorshDeserialize => Ok(gen_impl_trait_borsh_deserialize(
+                type_name,
+                generics,
+                inner_type,
+                maybe_error_type_name,
+            )),
+            AnyIrregularTrait::RkyvArchive => {
+                gen_impl_trait_rkyv(type_name, generics, inner_type, maybe_error_type_name)
+            }
+            AnyIrregularTrait::ClapValueParserFactory => {
+                Ok(gen_impl_trait_clap_value_parser_factory(
+                    type_name,
+                    generics,
+                    inner_type,
+                    maybe_error_type_name,
+                ))
+            }
         })
         .collect()
 }
@@ -10,8 +10,11 @@ use self::models::{AnyDeriveTrait, AnyGuard, AnyInnerType, AnySanitizer, AnyVali
 use crate::common::gen::GenerateNewtype;
 use crate::common::models::TypeName;
 use crate::{
-    any::validate::validate_any_derive_traits,
-    common::models::{Attributes, GenerateParams, Newtype, SpannedDeriveTrait},
+    any::validate::{validate_any_derive_traits, validate_encoding},
+    common::{
+        models::{Attributes, DeriveOptions, GenerateParams, Newtype, SpannedDeriveTrait},
+        validate::validate_serde_borrow,
+    },
 };
 
 pub struct AnyNewtype;
@@ -25,6 +28,7 @@ impl Newtype for AnyNewtype {
     fn parse_attributes(
         attrs: TokenStream,
         type_name: &TypeName,
+        _inner_type: &Self::InnerType,
     ) -> Result<Attributes<AnyGuard, SpannedDeriveTrait>, syn::Error> {
         parse::parse_attributes(attrs, type_name)
     }
@@ -32,7 +36,11 @@ impl Newtype for AnyNewtype {
     fn validate(
         guard: &AnyGuard,
         derive_traits: Vec<SpannedDeriveTrait>,
+        inner_type: &Self::InnerType,
+        derive_options: &DeriveOptions,
     ) -> Result<HashSet<Self::TypedTrait>, syn::Error> {
+        validate_serde_borrow(derive_options, false)?;
+        validate_encoding(derive_options, inner_type)?;
         validate_any_derive_traits(guard, derive_traits)
     }
 
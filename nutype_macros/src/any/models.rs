@@ -4,13 +4,17 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Field;
 
-use crate::common::models::{CustomFunction, Guard, RawGuard, SpannedItem, TypeTrait};
+use crate::common::models::{
+    CustomFunction, Guard, RawGuard, SpannedItem, TrySanitizer, TypeTrait,
+};
 
 /// Sanitizer for "any" type.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Kinded)]
 #[kinded(display = "snake_case")]
 pub enum AnySanitizer {
     With(CustomFunction),
+    TryWith(TrySanitizer),
 }
 
 pub type SpannedAnySanitizer = SpannedItem<AnySanitizer>;
@@ -20,6 +24,85 @@ pub type SpannedAnySanitizer = SpannedItem<AnySanitizer>;
 #[kinded(display = "snake_case")]
 pub enum AnyValidator {
     Predicate(CustomFunction),
+    After(Timestamp),
+    Before(Timestamp),
+    NotBefore(Timestamp),
+    NotAfter(Timestamp),
+    WithinLast(RelativeDuration),
+    WithinNext(RelativeDuration),
+    NotInFuture,
+    NotInPast,
+    /// The clock for the validators relative to the current time. It does not validate anything by itself.
+    Clock(syn::Path),
+    /// Lower bound of a time span, e.g. `min = "100ms"` for `Duration`.
+    Min(RelativeDuration),
+    /// Upper bound of a time span, e.g. `max = "30s"` for `Duration`.
+    Max(RelativeDuration),
+    WeekdayOnly,
+    Granularity(Granularity),
+    #[cfg_attr(not(feature = "uuid"), allow(dead_code))]
+    UuidVersion(u8),
+    #[cfg_attr(not(feature = "uuid"), allow(dead_code))]
+    NotNil,
+    NotLoopback,
+    NotUnspecified,
+    NotMulticast,
+}
+
+impl AnyValidator {
+    /// Validators that depend on the current time, so they are validated with the clock.
+    pub fn is_relative_to_now(&self) -> bool {
+        matches!(
+            self,
+            Self::WithinLast(_) | Self::WithinNext(_) | Self::NotInFuture | Self::NotInPast
+        )
+    }
+}
+
+/// A point in time set as a bound of a temporal validator, e.g. `after = "2000-01-01"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timestamp {
+    /// The value as it was written by the user, used in error messages.
+    pub raw: String,
+    pub unix_nanos: i128,
+}
+
+/// A time span, e.g. `within_last = "30d"` or `max = "30s"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativeDuration {
+    /// The value as it was written by the user, used in error messages.
+    pub raw: String,
+    pub nanos: i128,
+}
+
+/// Precision of a temporal value set with `granularity = ...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl Granularity {
+    pub fn nanos(self) -> i128 {
+        const NANOS_IN_SECOND: i128 = 1_000_000_000;
+        match self {
+            Self::Seconds => NANOS_IN_SECOND,
+            Self::Minutes => 60 * NANOS_IN_SECOND,
+            Self::Hours => 60 * 60 * NANOS_IN_SECOND,
+            Self::Days => 24 * 60 * 60 * NANOS_IN_SECOND,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Seconds => "seconds",
+            Self::Minutes => "minutes",
+            Self::Hours => "hours",
+            Self::Days => "days",
+        }
+    }
 }
 
 pub type SpannedAnyValidator = SpannedItem<AnyValidator>;
@@ -44,17 +127,41 @@ pub enum AnyDeriveTrait {
     TryFrom,
     Default,
     Hash,
+    IntoIterator,
+    Extend,
 
     // External crates
     SerdeSerialize,
     SerdeDeserialize,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl TypeTrait for AnyDeriveTrait {
     fn is_from_str(&self) -> bool {
         self == &AnyDeriveTrait::FromStr
     }
+
+    fn is_debug(&self) -> bool {
+        self == &AnyDeriveTrait::Debug
+    }
+
+    fn is_display(&self) -> bool {
+        self == &AnyDeriveTrait::Display
+    }
+
+    // `JsonSchema` is not supported by `any` types.
+    fn is_json_schema(&self) -> bool {
+        false
+    }
+
+    fn is_rkyv_archive(&self) -> bool {
+        self == &AnyDeriveTrait::RkyvArchive
+    }
 }
 
 pub type AnyRawGuard = RawGuard<SpannedAnySanitizer, SpannedAnyValidator>;
@@ -67,6 +174,58 @@ impl AnyInnerType {
     pub fn new(field: Field) -> Self {
         Self(field)
     }
+
+    /// Returns `N` if the inner type is `[u8; N]`.
+    pub fn byte_array_len(&self) -> Option<&syn::Expr> {
+        let syn::Type::Array(array) = &self.0.ty else {
+            return None;
+        };
+        let is_u8 = matches!(&*array.elem, syn::Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident("u8"));
+        is_u8.then_some(&array.len)
+    }
+
+    /// Whether the inner type is a collection of the standard library, e.g. `Vec<T>` or
+    /// `BTreeMap<K, V>`, that has `len()` and `is_empty()`.
+    pub fn is_std_collection(&self) -> bool {
+        const COLLECTIONS: [&str; 8] = [
+            "Vec",
+            "VecDeque",
+            "LinkedList",
+            "HashMap",
+            "BTreeMap",
+            "HashSet",
+            "BTreeSet",
+            "BinaryHeap",
+        ];
+        let syn::Type::Path(type_path) = &self.0.ty else {
+            return false;
+        };
+        type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| COLLECTIONS.iter().any(|name| segment.ident == name))
+    }
+
+    /// Returns `T` if the inner type is `Vec<T>`.
+    pub fn vec_element_type(&self) -> Option<&syn::Type> {
+        let syn::Type::Path(type_path) = &self.0.ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "Vec" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        match args.args.first() {
+            Some(syn::GenericArgument::Type(element_type)) if args.args.len() == 1 => {
+                Some(element_type)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl ToTokens for AnyInnerType {
@@ -1,17 +1,22 @@
 use crate::common::{
     models::{Attributes, CustomFunction, SpannedDeriveTrait, TypeName},
-    parse::{parse_sanitizer_kind, parse_validator_kind, ParseableAttributes},
+    parse::{
+        parse_sanitizer_kind, parse_try_sanitizer, parse_validator_kind, ParseableAttributes,
+        RawValidation, ValidatorPresets,
+    },
+    validate::validate_nohash,
 };
-use proc_macro2::TokenStream;
+use cfg_if::cfg_if;
+use proc_macro2::{Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream},
-    Token,
+    Ident, LitStr, Token,
 };
 
 use super::{
     models::{
         AnyGuard, AnyRawGuard, AnySanitizer, AnySanitizerKind, AnyValidator, AnyValidatorKind,
-        SpannedAnySanitizer, SpannedAnyValidator,
+        Granularity, RelativeDuration, SpannedAnySanitizer, SpannedAnyValidator, Timestamp,
     },
     validate::validate_any_guard,
 };
@@ -25,13 +30,80 @@ pub fn parse_attributes(
     let ParseableAttributes {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
         new_unchecked,
         default,
         derive_traits,
+        parse_money,
+        store_as,
+        storage,
+        as_variants,
+        bounded,
+        required,
+        trace,
+        secret,
+        arena,
+        guarantee,
+        derive_options,
     } = attrs;
+    validate_nohash(&derive_options, false)?;
+    if let Some(guarantee) = guarantee {
+        let msg = "`guarantee` is supported only by integer based types.";
+        return Err(syn::Error::new(guarantee, msg));
+    }
+    if let Some(RawValidation::Standard { combinators, .. }) = &validation {
+        if let Some(combinator) = combinators.first() {
+            let msg =
+                "`any_of`, `all`, `not` and `when` are supported only by string, integer and float based types.";
+            return Err(syn::Error::new(combinator.span(), msg));
+        }
+    }
+    if let Some(bytes) = derive_options.bytes {
+        let msg = "`bytes` is supported only by `String` based types.";
+        return Err(syn::Error::new(bytes, msg));
+    }
+    if let Some(eq_str) = derive_options.eq_str {
+        let msg = "`eq_str` is supported only by string based types.";
+        return Err(syn::Error::new(eq_str, msg));
+    }
+    if let Some(total_order) = derive_options.total_order {
+        let msg = "`total_order` is supported only by float based types.";
+        return Err(syn::Error::new(total_order, msg));
+    }
+    if let Some(ffi) = derive_options.ffi {
+        let msg = "`ffi` is supported only by string, integer and float based types.";
+        return Err(syn::Error::new(ffi, msg));
+    }
+    if let Some(wasm) = derive_options.wasm {
+        let msg = "`wasm` is supported only by string, integer and float based types.";
+        return Err(syn::Error::new(wasm, msg));
+    }
+    if let Some(bounded) = bounded {
+        let msg = "`bounded` is supported only by integer based types.";
+        return Err(syn::Error::new(bounded, msg));
+    }
+    if let Some(arena) = arena {
+        let msg = "`arena` is supported only by `&'a str` based types.";
+        return Err(syn::Error::new(arena, msg));
+    }
+    if let Some(as_variants_span) = as_variants {
+        let msg = "`as_variants` is supported only by string and integer based types.";
+        return Err(syn::Error::new(as_variants_span, msg));
+    }
+    if let Some(store_as) = store_as {
+        let msg = "`store_as` is supported only by `String` based types.";
+        return Err(syn::Error::new(store_as.span(), msg));
+    }
+    if let Some(storage) = storage {
+        let msg = "`storage` is supported only by `String` based types.";
+        return Err(syn::Error::new(storage.span(), msg));
+    }
     let raw_guard = AnyRawGuard {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
     };
     let guard = validate_any_guard(raw_guard, type_name)?;
     Ok(Attributes {
@@ -39,6 +111,15 @@ pub fn parse_attributes(
         guard,
         default,
         derive_traits,
+        parse_money: parse_money.map(|parse_money| parse_money.item),
+        store_as: None,
+        as_variants: None,
+        required,
+        trace: trace.is_some(),
+        secret,
+        arena: false,
+        guarantee: false,
+        derive_options,
     })
 }
 
@@ -56,13 +137,22 @@ impl Parse for SpannedAnySanitizer {
                     span,
                 })
             }
+            AnySanitizerKind::TryWith => {
+                let (try_sanitizer, span) = parse_try_sanitizer(input)?;
+                Ok(SpannedAnySanitizer {
+                    item: AnySanitizer::TryWith(try_sanitizer),
+                    span,
+                })
+            }
         }
     }
 }
 
+impl ValidatorPresets for SpannedAnyValidator {}
+
 impl Parse for SpannedAnyValidator {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let (kind, _ident) = parse_validator_kind(input)?;
+        let (kind, ident) = parse_validator_kind(input)?;
 
         match kind {
             AnyValidatorKind::Predicate => {
@@ -74,6 +164,252 @@ impl Parse for SpannedAnyValidator {
                     span,
                 })
             }
+            AnyValidatorKind::After => {
+                let (timestamp, span) = parse_timestamp(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::After(timestamp),
+                    span,
+                })
+            }
+            AnyValidatorKind::Before => {
+                let (timestamp, span) = parse_timestamp(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::Before(timestamp),
+                    span,
+                })
+            }
+            AnyValidatorKind::NotBefore => {
+                let (timestamp, span) = parse_timestamp(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::NotBefore(timestamp),
+                    span,
+                })
+            }
+            AnyValidatorKind::NotAfter => {
+                let (timestamp, span) = parse_timestamp(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::NotAfter(timestamp),
+                    span,
+                })
+            }
+            AnyValidatorKind::WithinLast => {
+                let (duration, span) = parse_relative_duration(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::WithinLast(duration),
+                    span,
+                })
+            }
+            AnyValidatorKind::WithinNext => {
+                let (duration, span) = parse_relative_duration(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::WithinNext(duration),
+                    span,
+                })
+            }
+            AnyValidatorKind::NotInFuture => Ok(SpannedAnyValidator {
+                item: AnyValidator::NotInFuture,
+                span: ident.span(),
+            }),
+            AnyValidatorKind::NotInPast => Ok(SpannedAnyValidator {
+                item: AnyValidator::NotInPast,
+                span: ident.span(),
+            }),
+            AnyValidatorKind::Min => {
+                let (duration, span) = parse_relative_duration(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::Min(duration),
+                    span,
+                })
+            }
+            AnyValidatorKind::Max => {
+                let (duration, span) = parse_relative_duration(input)?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::Max(duration),
+                    span,
+                })
+            }
+            AnyValidatorKind::Clock => {
+                let _eq: Token![=] = input.parse()?;
+                let span = input.span();
+                let clock: syn::Path = input.parse()?;
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::Clock(clock),
+                    span,
+                })
+            }
+            AnyValidatorKind::WeekdayOnly => Ok(SpannedAnyValidator {
+                item: AnyValidator::WeekdayOnly,
+                span: ident.span(),
+            }),
+            AnyValidatorKind::Granularity => {
+                let _eq: Token![=] = input.parse()?;
+                let ident: Ident = input.parse()?;
+                let granularity = match ident.to_string().as_str() {
+                    "seconds" => Granularity::Seconds,
+                    "minutes" => Granularity::Minutes,
+                    "hours" => Granularity::Hours,
+                    "days" => Granularity::Days,
+                    _ => {
+                        let msg =
+                            "Unknown granularity. Expected one of: seconds, minutes, hours, days.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                };
+                Ok(SpannedAnyValidator {
+                    item: AnyValidator::Granularity(granularity),
+                    span: ident.span(),
+                })
+            }
+            AnyValidatorKind::UuidVersion => {
+                cfg_if! {
+                    if #[cfg(feature = "uuid")] {
+                        let _eq: Token![=] = input.parse()?;
+                        let (version, span) = crate::common::parse::parse_number::<u8>(input)?;
+                        if !(1..=8).contains(&version) {
+                            let msg = "UUID version must be in range 1..=8.";
+                            return Err(syn::Error::new(span, msg));
+                        }
+                        Ok(SpannedAnyValidator {
+                            item: AnyValidator::UuidVersion(version),
+                            span,
+                        })
+                    } else {
+                        Err(uuid_feature_error(&ident))
+                    }
+                }
+            }
+            AnyValidatorKind::NotNil => {
+                cfg_if! {
+                    if #[cfg(feature = "uuid")] {
+                        Ok(SpannedAnyValidator {
+                            item: AnyValidator::NotNil,
+                            span: ident.span(),
+                        })
+                    } else {
+                        Err(uuid_feature_error(&ident))
+                    }
+                }
+            }
+            AnyValidatorKind::NotLoopback => Ok(SpannedAnyValidator {
+                item: AnyValidator::NotLoopback,
+                span: ident.span(),
+            }),
+            AnyValidatorKind::NotUnspecified => Ok(SpannedAnyValidator {
+                item: AnyValidator::NotUnspecified,
+                span: ident.span(),
+            }),
+            AnyValidatorKind::NotMulticast => Ok(SpannedAnyValidator {
+                item: AnyValidator::NotMulticast,
+                span: ident.span(),
+            }),
         }
     }
 }
+
+/// Error for UUID validators used without the `uuid` feature.
+#[cfg(not(feature = "uuid"))]
+fn uuid_feature_error(ident: &Ident) -> syn::Error {
+    let msg = concat!(
+        "To validate UUIDs, the feature `uuid` of the crate `nutype` must be enabled.\n",
+        "IMPORTANT: Make sure that your crate EXPLICITLY depends on the `uuid` crate.",
+    );
+    syn::Error::new(ident.span(), msg)
+}
+
+/// Parse `= "2000-01-01"` or `= "2000-01-01T12:30:00Z"` into a timestamp.
+fn parse_timestamp(input: ParseStream) -> syn::Result<(Timestamp, Span)> {
+    let _eq: Token![=] = input.parse()?;
+    let lit: LitStr = input.parse()?;
+    let raw = lit.value();
+    match parse_unix_nanos(&raw) {
+        Some(unix_nanos) => Ok((Timestamp { raw, unix_nanos }, lit.span())),
+        None => {
+            let msg = format!("Invalid timestamp `{raw}`.\nExpected a UTC date `YYYY-MM-DD` or a date with time `YYYY-MM-DDTHH:MM:SS[Z]`.");
+            Err(syn::Error::new(lit.span(), msg))
+        }
+    }
+}
+
+/// Parse `= "30d"` into a duration. The supported units are `ms`, `s`, `m`, `h`, `d`, `w` and `y` (365 days).
+fn parse_relative_duration(input: ParseStream) -> syn::Result<(RelativeDuration, Span)> {
+    let _eq: Token![=] = input.parse()?;
+    let lit: LitStr = input.parse()?;
+    let raw = lit.value();
+    let split_at = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (amount, unit) = raw.split_at(split_at);
+    const NANOS_IN_SECOND: i128 = 1_000_000_000;
+    let unit_nanos: i128 = match unit {
+        "ms" => NANOS_IN_SECOND / 1_000,
+        "s" => NANOS_IN_SECOND,
+        "m" => 60 * NANOS_IN_SECOND,
+        "h" => 60 * 60 * NANOS_IN_SECOND,
+        "d" => 24 * 60 * 60 * NANOS_IN_SECOND,
+        "w" => 7 * 24 * 60 * 60 * NANOS_IN_SECOND,
+        "y" => 365 * 24 * 60 * 60 * NANOS_IN_SECOND,
+        _ => 0,
+    };
+    match amount.parse::<u32>() {
+        Ok(amount) if unit_nanos > 0 => {
+            let nanos = i128::from(amount) * unit_nanos;
+            Ok((RelativeDuration { raw, nanos }, lit.span()))
+        }
+        _ => {
+            let msg = format!("Invalid duration `{raw}`.\nExpected a number followed by a unit: `ms`, `s`, `m`, `h`, `d`, `w` or `y`, e.g. \"30d\".");
+            Err(syn::Error::new(lit.span(), msg))
+        }
+    }
+}
+
+fn parse_unix_nanos(value: &str) -> Option<i128> {
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time.strip_suffix('Z').unwrap_or(time))),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let seconds_of_day = match time {
+        Some(time) => {
+            let mut time_parts = time.splitn(3, ':');
+            let hours: i64 = time_parts.next()?.parse().ok()?;
+            let minutes: i64 = time_parts.next()?.parse().ok()?;
+            let seconds: i64 = time_parts.next()?.parse().ok()?;
+            if hours > 23 || minutes > 59 || seconds > 59 {
+                return None;
+            }
+            hours * 3600 + minutes * 60 + seconds
+        }
+        None => 0,
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + seconds_of_day;
+    Some(i128::from(seconds) * 1_000_000_000)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01 in the proleptic Gregorian calendar.
+/// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
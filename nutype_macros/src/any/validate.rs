@@ -1,27 +1,69 @@
 use std::collections::HashSet;
 
 use proc_macro2::Span;
+use syn::spanned::Spanned;
 
 use crate::common::{
-    models::{DeriveTrait, SpannedDeriveTrait, TypeName},
-    validate::{validate_duplicates, validate_guard, validate_traits_from_xor_try_from},
+    gen::try_with::try_with_error_type,
+    models::{DeriveOptions, DeriveTrait, SpannedDeriveTrait, TypeName, Validation},
+    validate::{
+        validate_duplicates, validate_guard, validate_sanitizer_duplicates,
+        validate_traits_from_xor_try_from,
+    },
 };
 
 use super::models::{
-    AnyDeriveTrait, AnyGuard, AnyRawGuard, AnySanitizer, AnyValidator, SpannedAnySanitizer,
-    SpannedAnyValidator,
+    AnyDeriveTrait, AnyGuard, AnyInnerType, AnyRawGuard, AnySanitizer, AnyValidator,
+    SpannedAnySanitizer, SpannedAnyValidator,
 };
 
 pub fn validate_any_guard(
     raw_guard: AnyRawGuard,
     type_name: &TypeName,
 ) -> Result<AnyGuard, syn::Error> {
-    validate_guard(
+    let guard = validate_guard(
         raw_guard,
         type_name,
         validate_validators,
         validate_sanitizers,
-    )
+    )?;
+
+    // `try_new_at()` is generated only for the current validation rules.
+    if let AnyGuard::WithValidation {
+        legacy: Some(Validation::Standard { validators, .. }),
+        ..
+    } = &guard
+    {
+        let has_relative_validator = validators.iter().any(AnyValidator::is_relative_to_now);
+        if has_relative_validator {
+            let msg = "`within_last`, `within_next`, `not_in_future` and `not_in_past` are not supported by `legacy` validation.";
+            return Err(syn::Error::new(Span::call_site(), msg));
+        }
+    }
+
+    // `try_new_at()` would build a value without the context.
+    if let Some(with_context) = guard.with_context() {
+        let has_relative_validator = guard
+            .standard_validators()
+            .is_some_and(|validators| validators.iter().any(AnyValidator::is_relative_to_now));
+        if has_relative_validator {
+            let msg = "`within_last`, `within_next`, `not_in_future` and `not_in_past` cannot be combined with `with_context`.";
+            return Err(syn::Error::new(with_context.with.span(), msg));
+        }
+    }
+
+    // `try_new_at()` sanitizes the value without reporting the error of `try_with`.
+    if let Some(error_type) = try_with_error_type(guard.sanitizers()) {
+        let has_relative_validator = guard
+            .standard_validators()
+            .is_some_and(|validators| validators.iter().any(AnyValidator::is_relative_to_now));
+        if has_relative_validator {
+            let msg = "`within_last`, `within_next`, `not_in_future` and `not_in_past` cannot be combined with `try_with`.";
+            return Err(syn::Error::new(error_type.span(), msg));
+        }
+    }
+
+    Ok(guard)
 }
 
 // > Quis custodiet ipsos custodes? :D
@@ -33,6 +75,50 @@ fn validate_validators(
         format!("Duplicated validators `{kind}`.\nOh, maybe it's a time to take a break?")
     })?;
 
+    let has_relative_validator = validators.iter().any(|v| v.item.is_relative_to_now());
+    if let Some(clock) = validators
+        .iter()
+        .find(|v| matches!(v.item, AnyValidator::Clock(_)))
+    {
+        if !has_relative_validator {
+            let msg = "`clock` is used only by `within_last`, `within_next`, `not_in_future` and `not_in_past` validators.";
+            return Err(syn::Error::new(clock.span, msg));
+        }
+    }
+
+    // Validate that the lower bound is not greater than the upper bound
+    let lower = validators.iter().find_map(|v| match &v.item {
+        AnyValidator::After(timestamp) | AnyValidator::NotBefore(timestamp) => Some(timestamp),
+        _ => None,
+    });
+    let upper = validators.iter().find_map(|v| match &v.item {
+        AnyValidator::Before(timestamp) | AnyValidator::NotAfter(timestamp) => {
+            Some((timestamp, v.span))
+        }
+        _ => None,
+    });
+    if let (Some(lower), Some((upper, span))) = (lower, upper) {
+        if lower.unix_nanos > upper.unix_nanos {
+            let msg = "The lower bound (`after` or `not_before`) cannot be later than the upper bound (`before` or `not_after`).\nTime flies, but not backwards.";
+            return Err(syn::Error::new(span, msg));
+        }
+    }
+
+    let min = validators.iter().find_map(|v| match &v.item {
+        AnyValidator::Min(duration) => Some(duration),
+        _ => None,
+    });
+    let max = validators.iter().find_map(|v| match &v.item {
+        AnyValidator::Max(duration) => Some((duration, v.span)),
+        _ => None,
+    });
+    if let (Some(min), Some((max, span))) = (min, max) {
+        if min.nanos > max.nanos {
+            let msg = "`min` cannot be greater than `max`.";
+            return Err(syn::Error::new(span, msg));
+        }
+    }
+
     let validators: Vec<AnyValidator> = validators.into_iter().map(|v| v.item).collect();
     Ok(validators)
 }
@@ -40,7 +126,7 @@ fn validate_validators(
 fn validate_sanitizers(
     sanitizers: Vec<SpannedAnySanitizer>,
 ) -> Result<Vec<AnySanitizer>, syn::Error> {
-    validate_duplicates(&sanitizers, |kind| {
+    validate_sanitizer_duplicates(&sanitizers, |kind| {
         format!("Duplicated sanitizer `{kind}`.\nYou never know, what kind of error will be next!")
     })?;
 
@@ -48,6 +134,20 @@ fn validate_sanitizers(
     Ok(sanitizers)
 }
 
+/// `encoding = "..."` converts strings only from and into `[u8; N]`.
+pub fn validate_encoding(
+    derive_options: &DeriveOptions,
+    inner_type: &AnyInnerType,
+) -> Result<(), syn::Error> {
+    if let Some(encoding) = &derive_options.encoding {
+        if inner_type.byte_array_len().is_none() {
+            let msg = "`encoding` is supported only by `[u8; N]` based types.";
+            return Err(syn::Error::new(encoding.span(), msg));
+        }
+    }
+    Ok(())
+}
+
 pub fn validate_any_derive_traits(
     guard: &AnyGuard,
     spanned_derive_traits: Vec<SpannedDeriveTrait>,
@@ -85,6 +185,8 @@ fn to_any_derive_trait(
         DeriveTrait::From => Ok(AnyDeriveTrait::From),
         DeriveTrait::Deref => Ok(AnyDeriveTrait::Deref),
         DeriveTrait::Borrow => Ok(AnyDeriveTrait::Borrow),
+        DeriveTrait::IntoIterator => Ok(AnyDeriveTrait::IntoIterator),
+        DeriveTrait::Extend => Ok(AnyDeriveTrait::Extend),
         DeriveTrait::FromStr => Ok(AnyDeriveTrait::FromStr),
         DeriveTrait::TryFrom => Ok(AnyDeriveTrait::TryFrom),
         DeriveTrait::Default => Ok(AnyDeriveTrait::Default),
@@ -92,6 +194,11 @@ fn to_any_derive_trait(
         DeriveTrait::SerdeDeserialize => Ok(AnyDeriveTrait::SerdeDeserialize),
         DeriveTrait::Hash => Ok(AnyDeriveTrait::Hash),
         DeriveTrait::ArbitraryArbitrary => Ok(AnyDeriveTrait::ArbitraryArbitrary),
+        DeriveTrait::ValidatorValidate => Ok(AnyDeriveTrait::ValidatorValidate),
+        DeriveTrait::BorshSerialize => Ok(AnyDeriveTrait::BorshSerialize),
+        DeriveTrait::BorshDeserialize => Ok(AnyDeriveTrait::BorshDeserialize),
+        DeriveTrait::RkyvArchive => Ok(AnyDeriveTrait::RkyvArchive),
+        DeriveTrait::ClapValueParserFactory => Ok(AnyDeriveTrait::ClapValueParserFactory),
         DeriveTrait::SchemarsJsonSchema => {
             let msg =
                 format!("Deriving of trait `{tr:?}` is not (yet) supported for an arbitrary type");
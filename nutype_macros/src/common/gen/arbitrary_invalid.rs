@@ -0,0 +1,43 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{ErrorTypePath, TypeName};
+
+/// Generate `arbitrary_invalid()` next to the derived `Arbitrary`.
+/// Every violation is a block that evaluates to `::arbitrary::Result<Inner>`: a raw value that
+/// breaks one of the validators, or `Err(IncorrectFormat)` if the validator can not be broken
+/// (e.g. `greater_or_equal = i32::MIN`).
+pub fn gen_fn_arbitrary_invalid(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    error_type_path: &ErrorTypePath,
+    violations: Vec<TokenStream>,
+) -> TokenStream {
+    if violations.is_empty() {
+        return quote!();
+    }
+    let count = violations.len();
+    let arms = violations
+        .iter()
+        .enumerate()
+        .map(|(index, violation)| quote!(#index => { #violation }?,));
+
+    quote! {
+        impl #type_name {
+            /// Generates a raw value, that violates one of the declared validators, along with the
+            /// error returned by `try_new()` for it.
+            pub fn arbitrary_invalid(
+                u: &mut ::arbitrary::Unstructured<'_>,
+            ) -> ::arbitrary::Result<(#inner_type, #error_type_path)> {
+                let raw_value: #inner_type = match u.choose_index(#count)? {
+                    #(#arms)*
+                    _ => unreachable!("choose_index() returns an index below the count"),
+                };
+                match Self::try_new(::core::clone::Clone::clone(&raw_value)) {
+                    Ok(_) => Err(::arbitrary::Error::IncorrectFormat),
+                    Err(err) => Ok((raw_value, err)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{
+        error::{
+            allowed_values_to_string, gen_impl_error_trait, gen_variant_parse_error_type_name,
+        },
+        store_as::gen_decode,
+        strip_trait_bounds_on_generics,
+    },
+    models::{AsVariants, StoreAs, TypeName},
+};
+
+/// Generate a name for the companion enum, e.g. `ColorVariant` for `Color`.
+pub fn gen_variant_enum_name(type_name: &TypeName) -> Ident {
+    format_ident!("{type_name}Variant")
+}
+
+/// Generate the companion enum and `::as_variant()` that maps a value to its variant.
+/// The enum implements `Display` and `FromStr`, that round-trip the values listed in `in`.
+///
+/// String values are compared with the input as they are, other inner types are parsed first.
+pub fn gen_as_variants(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    is_string: bool,
+    maybe_store_as: Option<&StoreAs>,
+    as_variants: &AsVariants,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let enum_name = gen_variant_enum_name(type_name);
+    let inner_value = gen_decode(maybe_store_as);
+
+    let variant_defs = as_variants.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let doc = format!("`{}`", variant.value.to_token_stream());
+        quote! {
+            #[doc = #doc]
+            #ident
+        }
+    });
+    let checks = as_variants.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let value = &variant.value;
+        quote! {
+            if *inner == #value {
+                return #enum_name::#ident;
+            }
+        }
+    });
+
+    let display_arms = as_variants.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let value = &variant.value;
+        quote! {
+            #enum_name::#ident => ::core::fmt::Display::fmt(&#value, f)
+        }
+    });
+
+    let parse_error_name = gen_variant_parse_error_type_name(type_name);
+    let values: Vec<_> = as_variants
+        .variants
+        .iter()
+        .map(|variant| &variant.value)
+        .collect();
+    let parse_error_msg = format!(
+        "Failed to parse {enum_name}: expected one of [{}].",
+        allowed_values_to_string(&values)
+    );
+    let impl_error_trait = gen_impl_error_trait(&parse_error_name);
+    let parse_input = if is_string {
+        quote!(let input = s;)
+    } else {
+        quote! {
+            let input: #inner_type = s.parse().map_err(|_| #parse_error_name)?;
+        }
+    };
+    let parse_checks = as_variants.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let value = &variant.value;
+        quote! {
+            if input == #value {
+                return Ok(#enum_name::#ident);
+            }
+        }
+    });
+
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #enum_name {
+            #(#variant_defs,)*
+        }
+
+        impl #generics #type_name #generics_without_bounds {
+            /// Returns the variant of the companion enum, that corresponds to the value.
+            pub fn as_variant(&self) -> #enum_name {
+                let inner = &#inner_value;
+                #(#checks)*
+                unreachable!("the value is validated to be one of the values allowed by `in`")
+            }
+        }
+
+        impl #generics ::core::convert::From<&#type_name #generics_without_bounds> for #enum_name {
+            fn from(value: &#type_name #generics_without_bounds) -> Self {
+                value.as_variant()
+            }
+        }
+
+        impl ::core::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#display_arms,)*
+                }
+            }
+        }
+
+        /// The string is not one of the values allowed by `in`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct #parse_error_name;
+
+        impl ::core::fmt::Display for #parse_error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, #parse_error_msg)
+            }
+        }
+
+        #impl_error_trait
+
+        impl ::core::str::FromStr for #enum_name {
+            type Err = #parse_error_name;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                #parse_input
+                #(#parse_checks)*
+                Err(#parse_error_name)
+            }
+        }
+    }
+}
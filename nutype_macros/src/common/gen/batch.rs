@@ -0,0 +1,45 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::ErrorTypePath;
+
+/// Generate `try_from_iter()`, that builds a collection of values and reports the position of
+/// the first invalid item with `nutype::BatchError`.
+///
+/// The collection is generic (`FromIterator<Self>`), so no allocation is required in `no_std`.
+pub fn gen_fn_try_from_iter(
+    inner_type: &impl ToTokens,
+    error_type_path: &ErrorTypePath,
+    converts_into_inner_type: bool,
+    vis: &TokenStream,
+) -> TokenStream {
+    let (generic_item, item_type, maybe_item_bound) = if converts_into_inner_type {
+        (
+            quote!(, Item),
+            quote!(Item),
+            quote!(Item: ::core::convert::Into<#inner_type>,),
+        )
+    } else {
+        (quote!(), quote!(#inner_type), quote!())
+    };
+
+    quote! {
+        /// Sanitizes and validates every item and collects the values, e.g. into a `Vec`.
+        /// Stops at the first invalid item and returns its index together with the error.
+        #vis fn try_from_iter<Collection #generic_item>(
+            raw_values: impl ::core::iter::IntoIterator<Item = #item_type>,
+        ) -> ::core::result::Result<Collection, ::nutype::BatchError<#error_type_path>>
+        where
+            Collection: ::core::iter::FromIterator<Self>,
+            #maybe_item_bound
+        {
+            raw_values
+                .into_iter()
+                .enumerate()
+                .map(|(index, raw_value)| {
+                    Self::try_new(raw_value).map_err(|error| ::nutype::BatchError { index, error })
+                })
+                .collect()
+        }
+    }
+}
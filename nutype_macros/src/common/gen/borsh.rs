@@ -0,0 +1,63 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{
+    gen::{add_bound_to_all_type_params, store_as::gen_decode, strip_trait_bounds_on_generics},
+    models::{ErrorTypePath, InnerType, StoreAs, TypeName},
+};
+
+/// Implement `BorshSerialize`, that writes the inner value as it is.
+pub fn gen_impl_trait_borsh_serialize(
+    type_name: &TypeName,
+    generics: &Generics,
+    maybe_store_as: Option<&StoreAs>,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let all_generics_with_bound =
+        add_bound_to_all_type_params(generics, syn::parse_quote!(::borsh::BorshSerialize));
+    let inner_value = gen_decode(maybe_store_as);
+
+    quote! {
+        impl #all_generics_with_bound ::borsh::BorshSerialize for #type_name #generics_without_bounds {
+            fn serialize<W: ::borsh::io::Write>(&self, writer: &mut W) -> ::borsh::io::Result<()> {
+                ::borsh::BorshSerialize::serialize(&#inner_value, writer)
+            }
+        }
+    }
+}
+
+/// Implement `BorshDeserialize`, that reads the inner value and passes it through the constructor.
+/// A value rejected by the validation is reported as `ErrorKind::InvalidData`.
+pub fn gen_impl_trait_borsh_deserialize(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl Into<InnerType>,
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let inner_type: InnerType = inner_type.into();
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let all_generics_with_bound =
+        add_bound_to_all_type_params(generics, syn::parse_quote!(::borsh::BorshDeserialize));
+
+    let raw_value_to_result = if maybe_error_type_name.is_some() {
+        let type_name_str = type_name.to_string();
+        quote! {
+            #type_name::try_new(raw_value).map_err(|validation_error| {
+                let err_msg = format!("{validation_error} Expected valid {}", #type_name_str);
+                ::borsh::io::Error::new(::borsh::io::ErrorKind::InvalidData, err_msg)
+            })
+        }
+    } else {
+        quote!(Ok(#type_name::new(raw_value)))
+    };
+
+    quote! {
+        impl #all_generics_with_bound ::borsh::BorshDeserialize for #type_name #generics_without_bounds {
+            fn deserialize_reader<R: ::borsh::io::Read>(reader: &mut R) -> ::borsh::io::Result<Self> {
+                let raw_value = <#inner_type as ::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+                #raw_value_to_result
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::common::{
+    gen::error::{gen_bytes_error_type_name, gen_impl_error_trait},
+    models::{ErrorTypePath, TypeName},
+};
+
+/// Implement the functions of the `bytes` attribute for `String` based types, so the values
+/// received as bytes (e.g. from the network) can be checked before a `String` is allocated.
+///
+/// `sanitize_raw` sanitizes `raw: &str` into `value`, which is borrowed when the sanitizers
+/// do not require an allocation.
+pub fn gen_impl_bytes(
+    type_name: &TypeName,
+    sanitize_raw: TokenStream,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    constructor_vis: &TokenStream,
+) -> TokenStream {
+    let type_name_str = type_name.to_string();
+    let bytes_error_type_path = gen_bytes_error_type_name(type_name);
+    let impl_error_trait = gen_impl_error_trait(&bytes_error_type_path);
+
+    let (maybe_validate_variant, maybe_validate_display, maybe_fn_validate_bytes, build_newtype) =
+        match maybe_error_type_path {
+            Some(error_type_path) => (
+                quote!(Validate(#error_type_path),),
+                quote!(#bytes_error_type_path::Validate(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),),
+                quote! {
+                    /// Checks, that the bytes are valid UTF-8 and the sanitized string passes
+                    /// the validation, without building the value.
+                    /// The string is borrowed from `bytes`, unless a sanitizer needs an owned `String`.
+                    pub fn validate_bytes(bytes: &[u8]) -> ::core::result::Result<(), #bytes_error_type_path> {
+                        let raw: &str = ::core::str::from_utf8(bytes).map_err(#bytes_error_type_path::Utf8)?;
+                        #sanitize_raw
+                        Self::__validate__(&value).map_err(#bytes_error_type_path::Validate)
+                    }
+                },
+                quote!(Self::try_new(value).map_err(#bytes_error_type_path::Validate)),
+            ),
+            None => (quote!(), quote!(), quote!(), quote!(Ok(Self::new(value)))),
+        };
+
+    quote! {
+        impl #type_name {
+            #maybe_fn_validate_bytes
+
+            /// Builds the value from UTF-8 bytes, reusing their allocation for the `String`.
+            #constructor_vis fn try_from_utf8(bytes: Vec<u8>) -> ::core::result::Result<Self, #bytes_error_type_path> {
+                let value = String::from_utf8(bytes)
+                    .map_err(|err| #bytes_error_type_path::Utf8(err.utf8_error()))?;
+                #build_newtype
+            }
+        }
+
+        #[derive(Debug)]
+        pub enum #bytes_error_type_path {
+            Utf8(::core::str::Utf8Error),
+            #maybe_validate_variant
+        }
+
+        impl ::core::fmt::Display for #bytes_error_type_path {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #bytes_error_type_path::Utf8(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),
+                    #maybe_validate_display
+                }
+            }
+        }
+
+        #impl_error_trait
+    }
+}
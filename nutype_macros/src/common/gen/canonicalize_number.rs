@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::common::gen::gen_string_path;
+
+/// Generates a transformation that brings a human formatted number (e.g. `"1 234,50"`,
+/// `"1,234.50"` or `"+0042"`) into a canonical form (`"1234.50"`, `"1234.50"`, `"42"`):
+///
+/// * Leading and trailing whitespaces and a leading `+` are removed.
+/// * Whitespaces, `_` and `'` are treated as thousands separators and removed.
+/// * If both `.` and `,` are present, the rightmost one is the decimal separator and
+///   the other one is a thousands separator.
+/// * If only one of `.` or `,` is present, it is a decimal separator when it occurs exactly once,
+///   and a thousands separator otherwise.
+/// * `.` and `,` are removed as thousands separators only if they split the integer part into
+///   groups of three digits, so e.g. `"1.2.3"` is only trimmed and validation decides about it.
+/// * A single separator followed by exactly three digits (e.g. `"1,234"`) can be read both ways,
+///   so such a value is only trimmed and validation decides about it.
+/// * The decimal separator is unified to `.`, leading zeros of the integer part and
+///   a trailing decimal separator (e.g. `"12."`) are removed.
+///
+/// If `decimal_separator` is given, it is the only decimal separator and nothing is guessed.
+///
+/// Characters that are neither digits nor separators are kept as they are, so validation
+/// can still reject the value.
+pub fn gen_canonicalize_number(decimal_separator: Option<char>) -> TokenStream {
+    let string = gen_string_path();
+
+    let detect_decimal_position = match decimal_separator {
+        Some(decimal_separator) => quote!(
+            let decimal_position: Option<usize> = match unsigned.matches(#decimal_separator).count() {
+                0 => None,
+                1 => unsigned.find(#decimal_separator),
+                _ => break 'canonical #string::from(trimmed),
+            };
+        ),
+        None => quote!(
+            let last_dot = unsigned.rfind('.');
+            let last_comma = unsigned.rfind(',');
+            let decimal_position: Option<usize> = match (last_dot, last_comma) {
+                (Some(dot), Some(comma)) => Some(dot.max(comma)),
+                (Some(dot), None) if unsigned.matches('.').count() == 1 => Some(dot),
+                (None, Some(comma)) if unsigned.matches(',').count() == 1 => Some(comma),
+                _ => None,
+            };
+            // `"1,234"` is either 1234 or 1.234, so we refuse to guess.
+            if let (Some(position), Some(_)) = (decimal_position, last_dot.xor(last_comma)) {
+                let integer_digits = unsigned[..position].chars().filter(|c| c.is_ascii_digit());
+                let fraction_len = unsigned[position + 1..]
+                    .chars()
+                    .filter(|c| c.is_ascii_digit())
+                    .count();
+                let integer_len = integer_digits.clone().count();
+                let starts_with_zero = integer_digits.clone().next() == Some('0');
+                if fraction_len == 3 && (1..=3).contains(&integer_len) && !starts_with_zero {
+                    break 'canonical #string::from(trimmed);
+                }
+            }
+        ),
+    };
+
+    quote!(
+        let value: #string = 'canonical: {
+            let trimmed = value.trim();
+            let (sign, unsigned) = if let Some(rest) = trimmed.strip_prefix('-') {
+                ("-", rest)
+            } else if let Some(rest) = trimmed.strip_prefix('+') {
+                ("", rest)
+            } else {
+                ("", trimmed)
+            };
+            #detect_decimal_position
+
+            // `"1.2.3"` must not silently become 123.
+            let integer_end = decimal_position.unwrap_or(unsigned.len());
+            let mut groups = unsigned[..integer_end].split(['.', ',']);
+            groups.next();
+            if groups.any(|group| group.chars().filter(|c| c.is_ascii_digit()).count() != 3) {
+                break 'canonical #string::from(trimmed);
+            }
+
+            let mut integer_part = #string::with_capacity(unsigned.len());
+            let mut fraction_part = #string::new();
+            for (index, ch) in unsigned.char_indices() {
+                if Some(index) == decimal_position {
+                    continue;
+                }
+                if ch.is_whitespace() || matches!(ch, '_' | '\'' | '.' | ',') {
+                    continue;
+                }
+                match decimal_position {
+                    Some(position) if index > position => fraction_part.push(ch),
+                    _ => integer_part.push(ch),
+                }
+            }
+
+            if integer_part.is_empty() && fraction_part.is_empty() {
+                // Nothing that looks like a number: leave it up to validation.
+                #string::from(trimmed)
+            } else {
+                let integer_part = match integer_part.trim_start_matches('0') {
+                    "" => "0",
+                    stripped => stripped,
+                };
+                let mut canonical = #string::with_capacity(
+                    sign.len() + integer_part.len() + fraction_part.len() + 1,
+                );
+                canonical.push_str(sign);
+                canonical.push_str(integer_part);
+                if !fraction_part.is_empty() {
+                    canonical.push('.');
+                    canonical.push_str(&fraction_part);
+                }
+                canonical
+            }
+        };
+    )
+}
@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{
+    gen::{add_bound_to_all_type_params, strip_trait_bounds_on_generics},
+    models::{ErrorTypePath, InnerType, TypeName},
+};
+
+/// Implement `ValueParserFactory`, so the newtype can be used as a type of CLI arguments
+/// (`value_parser!(T)`, `#[derive(clap::Parser)]`).
+/// The argument is parsed into the inner type (unless it's a string) and passed through the
+/// constructor. A rejected value is reported with the message of the validation error.
+pub fn gen_impl_trait_clap_value_parser_factory(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl Into<InnerType>,
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let inner_type: InnerType = inner_type.into();
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+
+    // Clap keeps the parsed arguments as `Clone + Send + Sync + 'static` values.
+    let mut bounds = vec![
+        quote!(::core::clone::Clone),
+        quote!(::core::marker::Send),
+        quote!(::core::marker::Sync),
+        quote!('static),
+    ];
+    // Strings are taken as they are, other inner types are parsed with `FromStr`.
+    let parse_raw_value = match inner_type {
+        InnerType::String(_) => quote!(let raw_value = raw_string;),
+        _ => {
+            bounds.push(quote!(::core::str::FromStr<Err: ::core::fmt::Display>));
+            quote! {
+                let raw_value: #inner_type = raw_string
+                    .parse()
+                    .map_err(|err| ::std::string::ToString::to_string(&err))?;
+            }
+        }
+    };
+    let all_generics_with_bound = bounds
+        .into_iter()
+        .fold(generics.clone(), |generics, bound| {
+            add_bound_to_all_type_params(&generics, bound)
+        });
+
+    let raw_value_to_result = if maybe_error_type_name.is_some() {
+        quote! {
+            #type_name::try_new(raw_value)
+                .map_err(|validation_error| ::std::string::ToString::to_string(&validation_error))
+        }
+    } else {
+        quote!(Ok(#type_name::new(raw_value)))
+    };
+
+    quote! {
+        impl #all_generics_with_bound ::clap::builder::ValueParserFactory for #type_name #generics_without_bounds {
+            type Parser = ::clap::builder::ValueParser;
+
+            fn value_parser() -> Self::Parser {
+                ::clap::builder::ValueParser::new(
+                    |raw_string: &str| -> ::core::result::Result<Self, ::std::string::String> {
+                        #parse_raw_value
+                        #raw_value_to_result
+                    },
+                )
+            }
+        }
+    }
+}
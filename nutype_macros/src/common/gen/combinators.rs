@@ -0,0 +1,152 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::common::{
+    gen::{error::ExtraVariants, migrate::rename_ident, GenerateNewtype},
+    models::{ErrorMessages, ErrorTypePath, TypeName, ValidatorCombinator},
+};
+
+/// Extend `__validate__()` with the checks of `any_of(...)`, `all(...)`, `not(...)` and `when(...)`.
+/// The regular validators are checked first by `__validate_validators__()`.
+pub fn gen_fn_validate_with_combinators<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    inner_type: &G::InnerType,
+    error_type_path: &ErrorTypePath,
+    fn_validate: TokenStream,
+    combinators: &[ValidatorCombinator<G::Validator>],
+) -> TokenStream {
+    if combinators.is_empty() {
+        return fn_validate;
+    }
+    let fn_validate_validators = rename_ident(
+        fn_validate,
+        "__validate__",
+        &format_ident!("__validate_validators__"),
+    );
+    let checks = combinators.iter().map(|combinator| {
+        gen_combinator_check::<G>(type_name, inner_type, error_type_path, combinator)
+    });
+    let validated_type = validated_type::<G>(inner_type);
+
+    quote! {
+        #fn_validate_validators
+
+        // The errors of the combined validators are not reported, so their methods are unused.
+        #[allow(dead_code)]
+        fn __validate__(val: &#validated_type) -> ::core::result::Result<(), #error_type_path> {
+            Self::__validate_validators__(val)?;
+            #(#checks)*
+            Ok(())
+        }
+    }
+}
+
+/// A function that checks a single combinator, so `validate_all()` can report it on its own.
+pub fn gen_fn_check_combinator<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    inner_type: &G::InnerType,
+    error_type_path: &ErrorTypePath,
+    combinator: &ValidatorCombinator<G::Validator>,
+) -> TokenStream {
+    let check = gen_combinator_check::<G>(type_name, inner_type, error_type_path, combinator);
+    let validated_type = validated_type::<G>(inner_type);
+    quote! {
+        #[allow(dead_code)]
+        fn __check__(val: &#validated_type) -> ::core::result::Result<(), #error_type_path> {
+            #check
+            Ok(())
+        }
+    }
+}
+
+fn validated_type<G: GenerateNewtype + ?Sized>(inner_type: &G::InnerType) -> TokenStream {
+    // Validators of string types receive `&str` instead of a reference to the inner type.
+    if G::VALIDATES_STR {
+        quote!(str)
+    } else {
+        quote!(#inner_type)
+    }
+}
+
+fn gen_combinator_check<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    inner_type: &G::InnerType,
+    error_type_path: &ErrorTypePath,
+    combinator: &ValidatorCombinator<G::Validator>,
+) -> TokenStream {
+    let passes = gen_passes::<G>(type_name, inner_type, combinator);
+    let variant = match combinator {
+        ValidatorCombinator::AnyOf { .. } => quote!(AnyOfViolated),
+        ValidatorCombinator::All { .. } => quote!(AllViolated),
+        ValidatorCombinator::Not { .. } => quote!(NotViolated),
+        ValidatorCombinator::When { .. } => quote!(WhenViolated),
+        // The parser puts only the combinators at the top level.
+        ValidatorCombinator::Validator(_) => unreachable!("combinators: a plain validator"),
+    };
+    quote! {
+        if !#passes {
+            return Err(#error_type_path::#variant);
+        }
+    }
+}
+
+/// An expression, that is `true` when the value passes the combinator.
+///
+/// Every validator gets its own `__validate__()` and error type within a local type, so the code
+/// of the regular validation is reused.
+fn gen_passes<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    inner_type: &G::InnerType,
+    combinator: &ValidatorCombinator<G::Validator>,
+) -> TokenStream {
+    match combinator {
+        ValidatorCombinator::AnyOf { alternatives, .. } => {
+            let alternatives = alternatives
+                .iter()
+                .map(|alternative| gen_passes::<G>(type_name, inner_type, alternative));
+            quote!((#(#alternatives)||*))
+        }
+        ValidatorCombinator::All { validators, .. } => {
+            let validators = validators
+                .iter()
+                .map(|validator| gen_passes::<G>(type_name, inner_type, validator));
+            quote!((#(#validators)&&*))
+        }
+        ValidatorCombinator::Not { validator, .. } => {
+            let passes = gen_passes::<G>(type_name, inner_type, validator);
+            quote!((!#passes))
+        }
+        ValidatorCombinator::When {
+            condition, then, ..
+        } => {
+            let condition = gen_passes::<G>(type_name, inner_type, condition);
+            let then = then
+                .iter()
+                .map(|validator| gen_passes::<G>(type_name, inner_type, validator));
+            quote!((!#condition || (#(#then)&&*)))
+        }
+        ValidatorCombinator::Validator(validator) => {
+            let error_type_path = ErrorTypePath::new(format_ident!("__CheckError"));
+            let validators = core::slice::from_ref(validator);
+            let error_type = G::gen_validation_error_type(
+                type_name,
+                inner_type,
+                &error_type_path,
+                validators,
+                ExtraVariants::default(),
+                &ErrorMessages::default(),
+            );
+            let fn_validate = G::gen_fn_validate(inner_type, &error_type_path, validators, false);
+            quote! {
+                {
+                    #error_type
+                    struct __Check;
+                    impl __Check {
+                        #fn_validate
+                    }
+                    __Check::__validate__(val).is_ok()
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{TypeName, ValueOrExpr};
+
+/// Render a bound as it's written, e.g. `20` for `20usize` or `` `MAX_LEN` `` for a constant.
+pub fn describe_value<T: ToTokens>(value: &ValueOrExpr<T>) -> String {
+    let tokens = value.to_token_stream();
+    match syn::parse2::<syn::Expr>(tokens.clone()) {
+        Ok(syn::Expr::Lit(expr_lit)) => describe_lit(&expr_lit.lit),
+        Ok(syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        })) => match *expr {
+            syn::Expr::Lit(expr_lit) => format!("-{}", describe_lit(&expr_lit.lit)),
+            _ => format!("`{tokens}`"),
+        },
+        _ => format!("`{tokens}`"),
+    }
+}
+
+fn describe_lit(lit: &syn::Lit) -> String {
+    match lit {
+        syn::Lit::Int(lit_int) => lit_int.base10_digits().to_string(),
+        syn::Lit::Float(lit_float) => lit_float.base10_digits().to_string(),
+        lit => lit.to_token_stream().to_string(),
+    }
+}
+
+/// Summarize already described rules in one sentence.
+pub fn summarize_guarantees(rules: Vec<String>) -> Option<String> {
+    if rules.is_empty() {
+        None
+    } else {
+        Some(format!("Guaranteed: {}.", rules.join(", ")))
+    }
+}
+
+/// Doc comment of a constructor: what it does, followed by the guarantees.
+pub fn gen_constructor_doc(summary: &str, maybe_guarantees: Option<&String>) -> TokenStream {
+    match maybe_guarantees {
+        Some(guarantees) => quote! {
+            #[doc = #summary]
+            #[doc = ""]
+            #[doc = #guarantees]
+        },
+        None => quote!(#[doc = #summary]),
+    }
+}
+
+/// Doc comment appended to the doc comments of the newtype, so the rules can be browsed in
+/// the API documentation.
+pub fn gen_type_doc(has_doc_attrs: bool, maybe_guarantees: Option<&String>) -> TokenStream {
+    match maybe_guarantees {
+        Some(guarantees) if has_doc_attrs => quote! {
+            #[doc = ""]
+            #[doc = #guarantees]
+        },
+        Some(guarantees) => quote!(#[doc = #guarantees]),
+        None => quote!(),
+    }
+}
+
+/// Doc comment of the generated validation error, that lists the validators.
+pub fn gen_error_doc(type_name: &TypeName, validator_descriptions: &[String]) -> TokenStream {
+    let doc = format!(
+        "An error returned when a value does not meet the rules of [`{type_name}`]: {}.",
+        validator_descriptions.join(", ")
+    );
+    quote!(#[doc = #doc])
+}
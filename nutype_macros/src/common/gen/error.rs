@@ -1,8 +1,11 @@
 use cfg_if::cfg_if;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::LitStr;
 
-use crate::common::models::{ErrorTypePath, TypeName};
+use crate::common::models::{
+    ErrorMessages, ErrorTypePath, Rule, RuleNode, RuleStage, TypeName, ValidatorCombinator,
+};
 
 /// Generate a default error type name if the error name is not specified explicitly by
 /// the user in the attributes.
@@ -11,6 +14,364 @@ pub fn gen_error_type_name(type_name: &TypeName) -> ErrorTypePath {
     ErrorTypePath::new(ident)
 }
 
+/// Render the values of an `in` validator as they were written, e.g. `"red", "green"`,
+/// so they can be listed in the error message.
+pub fn allowed_values_to_string<T: ToTokens>(allowed_values: &[T]) -> String {
+    allowed_values
+        .iter()
+        .map(|value| value.to_token_stream().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Name of the error type generated for the rules declared with `legacy(validate(...))`.
+pub fn gen_legacy_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}LegacyError");
+    ErrorTypePath::new(ident)
+}
+
+/// Name of the error type returned by `try_migrate()`.
+pub fn gen_migrate_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}MigrateError");
+    ErrorTypePath::new(ident)
+}
+
+/// Name of the error type returned by `TryFrom` for integer types, that may not fit into the inner type.
+pub fn gen_try_from_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}TryFromError");
+    ErrorTypePath::new(ident)
+}
+
+/// Name of the error type returned by `TryFrom<*const c_char>` of string based types with `ffi`.
+pub fn gen_ffi_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}FfiError");
+    ErrorTypePath::new(ident)
+}
+
+/// Name of the error type returned by `validate_bytes()` and `try_from_utf8()` of string based
+/// types with `bytes`.
+pub fn gen_bytes_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}BytesError");
+    ErrorTypePath::new(ident)
+}
+
+/// Name of the error type returned by `FromStr` of the companion enum generated with `as_variants`.
+pub fn gen_variant_parse_error_type_name(type_name: &TypeName) -> ErrorTypePath {
+    let ident = format_ident!("{type_name}VariantParseError");
+    ErrorTypePath::new(ident)
+}
+
+/// Variants of a generated validation error, that do not come from the validators of the inner type.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtraVariants<'a> {
+    /// `RequiredViolated`, returned for `None` by `Option` based types declared with `required`.
+    pub required: bool,
+
+    /// `WithContextViolated`, returned when the `with_context` validator fails.
+    pub with_context: bool,
+
+    /// `AnyOfViolated`, returned when none of the alternatives of `any_of(...)` passes.
+    pub any_of: bool,
+
+    /// `AllViolated`, returned when one of the validators of `all(...)` fails.
+    pub all: bool,
+
+    /// `NotViolated`, returned when the validator of `not(...)` passes.
+    pub not: bool,
+
+    /// `WhenViolated`, returned when the condition of `when(...)` passes, but `then(...)` fails.
+    pub when: bool,
+
+    /// `TryWithViolated(Error)`, returned when a `try_with(...)` sanitizer fails with `Error`.
+    pub try_with: Option<&'a syn::Type>,
+}
+
+impl ExtraVariants<'_> {
+    /// The variants of the combinators declared with `any_of(...)`, `all(...)`, `not(...)` and `when(...)`.
+    pub fn with_combinators<V>(self, combinators: &[ValidatorCombinator<V>]) -> Self {
+        Self {
+            any_of: combinators
+                .iter()
+                .any(|combinator| matches!(combinator, ValidatorCombinator::AnyOf { .. })),
+            all: combinators
+                .iter()
+                .any(|combinator| matches!(combinator, ValidatorCombinator::All { .. })),
+            not: combinators
+                .iter()
+                .any(|combinator| matches!(combinator, ValidatorCombinator::Not { .. })),
+            when: combinators
+                .iter()
+                .any(|combinator| matches!(combinator, ValidatorCombinator::When { .. })),
+            ..self
+        }
+    }
+}
+
+pub fn gen_extra_variants(extra_variants: ExtraVariants) -> TokenStream {
+    let required = extra_variants.required.then(|| quote!(RequiredViolated,));
+    let with_context = extra_variants
+        .with_context
+        .then(|| quote!(WithContextViolated,));
+    let any_of = extra_variants.any_of.then(|| quote!(AnyOfViolated,));
+    let all = extra_variants.all.then(|| quote!(AllViolated,));
+    let not = extra_variants.not.then(|| quote!(NotViolated,));
+    let when = extra_variants.when.then(|| quote!(WhenViolated,));
+    let try_with = extra_variants
+        .try_with
+        .map(|error_type| quote!(TryWithViolated(#error_type),));
+    quote! {
+        #required
+        #with_context
+        #any_of
+        #all
+        #not
+        #when
+        #try_with
+    }
+}
+
+pub fn gen_extra_display_arms(
+    type_name: &TypeName,
+    error_type_path: &ErrorTypePath,
+    extra_variants: ExtraVariants,
+) -> TokenStream {
+    let required = extra_variants.required.then(|| {
+        quote! {
+            #error_type_path::RequiredViolated => write!(f, "{} is required.", stringify!(#type_name)),
+        }
+    });
+    let with_context = extra_variants.with_context.then(|| {
+        quote! {
+            #error_type_path::WithContextViolated => write!(f, "{} failed the context validation.", stringify!(#type_name)),
+        }
+    });
+    let any_of = extra_variants.any_of.then(|| {
+        quote! {
+            #error_type_path::AnyOfViolated => write!(f, "{} does not match any of the alternatives.", stringify!(#type_name)),
+        }
+    });
+    let all = extra_variants.all.then(|| {
+        quote! {
+            #error_type_path::AllViolated => write!(f, "{} does not meet all the rules.", stringify!(#type_name)),
+        }
+    });
+    let not = extra_variants.not.then(|| {
+        quote! {
+            #error_type_path::NotViolated => write!(f, "{} matches a rule, that it must not match.", stringify!(#type_name)),
+        }
+    });
+    let when = extra_variants.when.then(|| {
+        quote! {
+            #error_type_path::WhenViolated => write!(f, "{} does not meet the rules required by its format.", stringify!(#type_name)),
+        }
+    });
+    let try_with = extra_variants.try_with.map(|_| {
+        quote! {
+            #error_type_path::TryWithViolated(err) => write!(f, "{} could not be sanitized: {err}", stringify!(#type_name)),
+        }
+    });
+    quote! {
+        #required
+        #with_context
+        #any_of
+        #all
+        #not
+        #when
+        #try_with
+    }
+}
+
+/// A variant of a generated validation error, as it's reported by `code()` and `bound()`.
+pub struct ErrorVariantCode {
+    /// Pattern that matches the variant, e.g. `Self::InViolated(_)`.
+    pub pattern: TokenStream,
+
+    /// Name of the violated validator, e.g. `"len_char_max"`.
+    pub code: String,
+
+    /// Bound of the violated validator, e.g. `100` for `len_char_max = 100`.
+    pub bound: Option<TokenStream>,
+}
+
+impl ErrorVariantCode {
+    /// A variant of a kind specific validator with the code and the bound of its [`RuleNode`].
+    pub fn new<V: Rule>(pattern: TokenStream, validator: &V) -> Self {
+        let RuleNode { name, bound, .. } = RuleNode::new(RuleStage::Validator, validator);
+        Self {
+            pattern,
+            code: name,
+            bound,
+        }
+    }
+}
+
+/// Display arms of the variants, which messages are set with `messages(...)`.
+/// `{type}` in a message is replaced with the type name and `{limit}` refers to the bound of
+/// the validator, e.g. `messages(len_char_max = "{type} can have at most {limit} characters.")`.
+pub fn gen_custom_display_arms(
+    type_name: &TypeName,
+    variants: Vec<ErrorVariantCode>,
+    messages: &ErrorMessages,
+) -> Vec<TokenStream> {
+    variants
+        .into_iter()
+        .filter_map(|variant| {
+            let message = messages.get(&variant.code)?;
+            let ErrorVariantCode {
+                pattern,
+                code,
+                bound,
+            } = variant;
+            let text = message.value().replace("{type}", &type_name.to_string());
+            let format = LitStr::new(&text, message.span());
+            let arm = if !text.contains("{limit") {
+                quote!(#pattern => write!(f, #format))
+            } else if let Some(bound) = bound {
+                quote!(#pattern => write!(f, #format, limit = #bound))
+            } else {
+                let msg =
+                    format!("`{code}` has no limit, so `{{limit}}` cannot be used in its message.");
+                quote_spanned!(message.span()=> #pattern => ::core::compile_error!(#msg))
+            };
+            Some(arm)
+        })
+        .collect()
+}
+
+/// A lower or an upper bound of the accepted values, as it's rendered in the default messages.
+pub struct RangeBound {
+    pub value: TokenStream,
+    pub inclusive: bool,
+}
+
+/// Describe the accepted values, so the message of a violated bound also mentions the other
+/// bound. Returns a format string, e.g. `between {:#?} and {:#?} characters`, and its arguments.
+pub fn describe_range(
+    lower: Option<RangeBound>,
+    upper: Option<RangeBound>,
+    placeholder: &str,
+    unit: &str,
+) -> (String, Vec<TokenStream>) {
+    let lower_phrase = |bound: &RangeBound| {
+        if bound.inclusive {
+            "at least"
+        } else {
+            "greater than"
+        }
+    };
+    let upper_phrase = |bound: &RangeBound| {
+        if bound.inclusive {
+            "at most"
+        } else {
+            "less than"
+        }
+    };
+    let (text, values) = match (lower, upper) {
+        (Some(lower), Some(upper)) if lower.inclusive && upper.inclusive => (
+            format!("between {placeholder} and {placeholder}"),
+            vec![lower.value, upper.value],
+        ),
+        (Some(lower), Some(upper)) => (
+            format!(
+                "{} {placeholder} and {} {placeholder}",
+                lower_phrase(&lower),
+                upper_phrase(&upper)
+            ),
+            vec![lower.value, upper.value],
+        ),
+        (Some(lower), None) => (
+            format!("{} {placeholder}", lower_phrase(&lower)),
+            vec![lower.value],
+        ),
+        (None, Some(upper)) => (
+            format!("{} {placeholder}", upper_phrase(&upper)),
+            vec![upper.value],
+        ),
+        (None, None) => (String::new(), vec![]),
+    };
+    (format!("{text}{unit}"), values)
+}
+
+/// Generate `code()`, that returns the name of the violated validator, and `bound()` (if
+/// `maybe_bound_type` is set), that returns its bound. Unlike `Display` they do not change
+/// with the wording of the messages, so the errors can be mapped to localized messages.
+pub fn gen_impl_error_code(
+    error_type_path: &ErrorTypePath,
+    variants: Vec<ErrorVariantCode>,
+    maybe_bound_type: Option<TokenStream>,
+    extra_variants: ExtraVariants,
+) -> TokenStream {
+    let extra_codes = [
+        (
+            extra_variants.required,
+            quote!(Self::RequiredViolated),
+            "required",
+        ),
+        (
+            extra_variants.with_context,
+            quote!(Self::WithContextViolated),
+            "with_context",
+        ),
+        (extra_variants.any_of, quote!(Self::AnyOfViolated), "any_of"),
+        (extra_variants.all, quote!(Self::AllViolated), "all"),
+        (extra_variants.not, quote!(Self::NotViolated), "not"),
+        (extra_variants.when, quote!(Self::WhenViolated), "when"),
+        (
+            extra_variants.try_with.is_some(),
+            quote!(Self::TryWithViolated(_)),
+            "try_with",
+        ),
+    ];
+    let variants: Vec<ErrorVariantCode> = variants
+        .into_iter()
+        .chain(
+            extra_codes
+                .into_iter()
+                .filter(|(is_present, _, _)| *is_present)
+                .map(|(_, pattern, code)| ErrorVariantCode {
+                    pattern,
+                    code: code.to_string(),
+                    bound: None,
+                }),
+        )
+        .collect();
+
+    let code_arms = variants.iter().map(|variant| {
+        let ErrorVariantCode { pattern, code, .. } = variant;
+        quote!(#pattern => #code,)
+    });
+    let fn_bound = maybe_bound_type.map(|bound_type| {
+        let bound_arms = variants.iter().map(|variant| {
+            let pattern = &variant.pattern;
+            match &variant.bound {
+                Some(bound) => quote!(#pattern => ::core::option::Option::Some(#bound),),
+                None => quote!(#pattern => ::core::option::Option::None,),
+            }
+        });
+        quote! {
+            /// Returns the bound of the violated validator, e.g. `100` for `len_char_max = 100`.
+            pub fn bound(&self) -> ::core::option::Option<#bound_type> {
+                match self {
+                    #(#bound_arms)*
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #error_type_path {
+            /// Returns the name of the violated validator, e.g. `"len_char_max"`.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    #(#code_arms)*
+                }
+            }
+
+            #fn_bound
+        }
+    }
+}
+
 // NOTE: `::core::error::Error` is stable only for rust >= 1.81.0.
 #[allow(unused_variables)]
 pub fn gen_impl_error_trait(error_type_path: &ErrorTypePath) -> TokenStream {
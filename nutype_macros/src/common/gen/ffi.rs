@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::{
+    gen::error::{gen_ffi_error_type_name, gen_impl_error_trait},
+    models::{ErrorTypePath, TypeName},
+};
+
+/// Implement the functions of the `ffi` attribute, that let the value cross C boundaries.
+/// The newtype itself is `#[repr(transparent)]`, so it has the layout of the inner type.
+///
+/// Integers and floats are passed by value: `ffi_get()` returns the inner value and
+/// `ffi_new()` or `ffi_try_new()` build the newtype from it.
+/// Strings are not FFI-safe, so they expose the bytes with `ffi_as_ptr()` and `ffi_len()`
+/// and are built from a C string with `TryFrom<*const c_char>`.
+pub fn gen_impl_ffi(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    is_string: bool,
+) -> TokenStream {
+    if is_string {
+        gen_impl_ffi_for_string(type_name, maybe_error_type_path)
+    } else {
+        gen_impl_ffi_for_number(type_name, inner_type, maybe_error_type_path)
+    }
+}
+
+fn gen_impl_ffi_for_number(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let constructor = match maybe_error_type_path {
+        Some(_) => quote! {
+            /// Sanitizes and validates the value and writes the newtype into `out`.
+            /// Returns `false` and leaves `out` untouched, if the value is invalid.
+            ///
+            /// # Safety
+            ///
+            /// `out` must be valid for writes and properly aligned.
+            pub unsafe extern "C" fn ffi_try_new(value: #inner_type, out: *mut Self) -> bool {
+                match Self::try_new(value) {
+                    Ok(newtype) => {
+                        unsafe { out.write(newtype) };
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+        },
+        None => quote! {
+            /// Sanitizes the value and returns the newtype.
+            pub extern "C" fn ffi_new(value: #inner_type) -> Self {
+                Self::new(value)
+            }
+        },
+    };
+
+    quote! {
+        impl #type_name {
+            /// Returns the inner value.
+            #[inline]
+            pub extern "C" fn ffi_get(&self) -> #inner_type {
+                self.0
+            }
+
+            #constructor
+        }
+    }
+}
+
+fn gen_impl_ffi_for_string(
+    type_name: &TypeName,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let type_name_str = type_name.to_string();
+    let ffi_error_type_path = gen_ffi_error_type_name(type_name);
+    let impl_error_trait = gen_impl_error_trait(&ffi_error_type_path);
+
+    let (maybe_validate_variant, maybe_validate_display, build_newtype) =
+        match maybe_error_type_path {
+            Some(error_type_path) => (
+                quote!(Validate(#error_type_path),),
+                quote!(#ffi_error_type_path::Validate(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),),
+                quote!(Self::try_new(value).map_err(#ffi_error_type_path::Validate)),
+            ),
+            None => (quote!(), quote!(), quote!(Ok(Self::new(value)))),
+        };
+
+    quote! {
+        impl #type_name {
+            /// Returns a pointer to the UTF-8 bytes of the value.
+            /// The bytes are not NUL-terminated, their number is returned by `ffi_len()`.
+            #[inline]
+            pub extern "C" fn ffi_as_ptr(&self) -> *const u8 {
+                self.0.as_ptr()
+            }
+
+            /// Returns the length of the value in bytes.
+            #[inline]
+            pub extern "C" fn ffi_len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        #[derive(Debug)]
+        pub enum #ffi_error_type_path {
+            NullPointer,
+            Utf8(::core::str::Utf8Error),
+            #maybe_validate_variant
+        }
+
+        impl ::core::fmt::Display for #ffi_error_type_path {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #ffi_error_type_path::NullPointer => write!(f, "Failed to convert into {}: the pointer is null", #type_name_str),
+                    #ffi_error_type_path::Utf8(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),
+                    #maybe_validate_display
+                }
+            }
+        }
+
+        #impl_error_trait
+
+        /// Builds the newtype from a NUL-terminated C string.
+        /// The pointer must be null or point to a NUL-terminated string, that is valid for reads.
+        impl ::core::convert::TryFrom<*const ::core::ffi::c_char> for #type_name {
+            type Error = #ffi_error_type_path;
+
+            fn try_from(ptr: *const ::core::ffi::c_char) -> ::core::result::Result<Self, Self::Error> {
+                if ptr.is_null() {
+                    return Err(#ffi_error_type_path::NullPointer);
+                }
+                // SAFETY: the pointer is not null, and the caller guarantees that it points to
+                // a NUL-terminated string as it's documented above.
+                let c_str = unsafe { ::core::ffi::CStr::from_ptr(ptr) };
+                let value = c_str.to_str().map_err(#ffi_error_type_path::Utf8)?;
+                #build_newtype
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{store_as::gen_decode_of, strip_trait_bounds_on_generics, type_custom_closure},
+    models::{CustomFunction, StoreAs, TypeName},
+};
+
+/// Implement `Hash`, that writes only the `u64` computed by `hash_with = ...` into the hasher,
+/// e.g. with a faster algorithm than the one of the hasher.
+pub fn gen_impl_hash_with(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    maybe_store_as: Option<&StoreAs>,
+    hash_with: &CustomFunction,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let hash_with = type_custom_closure(&hash_with.to_token_stream(), quote!(&#inner_type));
+    let inner_value = gen_decode_of(quote!(self), maybe_store_as);
+    quote! {
+        impl #generics ::core::hash::Hash for #type_name #generics_without_bounds {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                let hash: u64 = (#hash_with)(&#inner_value);
+                state.write_u64(hash);
+            }
+        }
+    }
+}
+
+/// Mark the type as hashed into a single integer, so it can be a key of `nohash_hasher::IntMap`.
+pub fn gen_impl_nohash(type_name: &TypeName, generics: &Generics) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    quote! {
+        impl #generics ::nutype::__private::nohash_hasher::IsEnabled for #type_name #generics_without_bounds {}
+    }
+}
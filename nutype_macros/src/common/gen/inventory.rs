@@ -0,0 +1,31 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{Rules, TypeName};
+
+/// Register the newtype with `inventory`, so the application can list all the newtypes
+/// and their rules at runtime with `nutype::registered_newtypes()`.
+///
+/// Without the `inventory` feature nothing is generated.
+#[allow(unused_variables)]
+pub fn gen_inventory_submit<S, V>(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    rules: &Rules<S, V>,
+) -> TokenStream {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "inventory")] {
+            let type_metadata = super::metadata::gen_type_metadata(type_name, inner_type, rules);
+            quote! {
+                ::nutype::__private::inventory::submit! {
+                    ::nutype::NewtypeInfo {
+                        module_path: ::core::module_path!(),
+                        metadata: #type_metadata,
+                    }
+                }
+            }
+        } else {
+            quote!()
+        }
+    }
+}
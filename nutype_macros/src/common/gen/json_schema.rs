@@ -0,0 +1,81 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Attribute, Expr, ExprLit, Generics, Lit, Meta};
+
+use crate::common::{
+    gen::strip_trait_bounds_on_generics,
+    models::{SchemaMetadata, TypeName},
+};
+
+/// Implement `JsonSchema` of a type with `schema(...)` metadata: the schema of the inner type
+/// extended with `format`, `description` and `examples`.
+pub fn gen_impl_json_schema(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    doc_attrs: &[Attribute],
+    schema: &SchemaMetadata,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let schema_name = type_name.to_string();
+    let SchemaMetadata {
+        format,
+        examples,
+        description,
+    } = schema;
+
+    let set_format = format.as_ref().map(|format| {
+        quote! {
+            schema.format = ::core::option::Option::Some(::std::string::String::from(#format));
+        }
+    });
+    // Like the derived `JsonSchema`, fall back to the doc comments of the type.
+    let description = description
+        .as_ref()
+        .map(|description| description.value())
+        .or_else(|| doc_comment(doc_attrs));
+    let set_description = description.map(|description| {
+        quote! {
+            schema.metadata().description = ::core::option::Option::Some(::std::string::String::from(#description));
+        }
+    });
+
+    quote! {
+        impl #generics ::schemars::JsonSchema for #type_name #generics_without_bounds {
+            fn schema_name() -> ::std::string::String {
+                ::std::string::String::from(#schema_name)
+            }
+
+            fn json_schema(generator: &mut ::schemars::gen::SchemaGenerator) -> ::schemars::schema::Schema {
+                let mut schema = generator.subschema_for::<#inner_type>().into_object();
+                #set_format
+                #set_description
+                #(
+                    schema.metadata().examples.push(::schemars::_serde_json::json!(#examples));
+                )*
+                ::schemars::schema::Schema::Object(schema)
+            }
+        }
+    }
+}
+
+/// Join the lines of `///` comments, or return `None` if there are none.
+fn doc_comment(doc_attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = doc_attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("doc") => {
+                match &name_value.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }) => Some(line.value().trim().to_string()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
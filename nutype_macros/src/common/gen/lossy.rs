@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{
+    gen::{
+        gen_report_validation_failure,
+        store_as::gen_encode,
+        strip_trait_bounds_on_generics,
+        try_with::{gen_wrap_try_with_error, try_with_error_type},
+        GenerateNewtype,
+    },
+    models::{Guard, OnInvalid, StoreAs, TypeName},
+};
+
+/// Generate `try_from_lossy()` (`from_lossy()` for types without validation), that builds
+/// the value like `try_new()` (`new()`) and also returns `nutype::SanitizeWarning` for every
+/// sanitizer, that changed the value.
+#[allow(clippy::too_many_arguments)]
+pub fn gen_impl_lossy<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &G::InnerType,
+    guard: &Guard<G::Sanitizer, G::Validator>,
+    maybe_store_as: Option<&StoreAs>,
+    trace: bool,
+    maybe_on_invalid: Option<&OnInvalid>,
+    constructor_vis: &TokenStream,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let sanitizers = guard.sanitizers();
+    let fn_sanitize_lossy = G::gen_fn_sanitize(inner_type, sanitizers, trace, true);
+    let field_value = gen_encode(quote!(sanitized_value), inner_type, maybe_store_as);
+
+    let (input_type, convert_raw_value_if_necessary) = if G::NEW_CONVERT_INTO_INNER_TYPE {
+        (
+            quote!(impl Into<#inner_type>),
+            quote!(let raw_value = raw_value.into();),
+        )
+    } else {
+        (quote!(#inner_type), quote!())
+    };
+    let warnings_type = quote!(::std::vec::Vec<::nutype::SanitizeWarning>);
+
+    let constructor = match guard {
+        Guard::WithoutValidation { .. } => quote!(
+            /// Sanitizes the value like `new()` and reports the sanitizers, that changed it.
+            #constructor_vis fn from_lossy(raw_value: #input_type) -> (Self, #warnings_type) {
+                #convert_raw_value_if_necessary
+
+                let mut warnings = ::std::vec::Vec::new();
+                let sanitized_value: #inner_type = Self::__sanitize_lossy__(raw_value, &mut warnings);
+                (#type_name(#field_value), warnings)
+            }
+        ),
+        Guard::WithValidation { validation, .. } => {
+            let error_type_path = validation.error_type_path();
+            let sanitize_raw_value = if try_with_error_type(sanitizers).is_some() {
+                let wrap_error = gen_wrap_try_with_error(validation);
+                quote!(Self::__sanitize_lossy__(raw_value, &mut warnings).map_err(|err| #wrap_error)?)
+            } else {
+                quote!(Self::__sanitize_lossy__(raw_value, &mut warnings))
+            };
+            let report_validation_failure =
+                gen_report_validation_failure(type_name, maybe_on_invalid, quote!(sanitized_value));
+            quote!(
+                /// Sanitizes and validates the value like `try_new()` and reports the sanitizers,
+                /// that changed it.
+                #constructor_vis fn try_from_lossy(
+                    raw_value: #input_type,
+                ) -> ::core::result::Result<(Self, #warnings_type), #error_type_path> {
+                    #convert_raw_value_if_necessary
+
+                    let mut warnings = ::std::vec::Vec::new();
+                    let sanitized_value: #inner_type = #sanitize_raw_value;
+                    Self::__validate__(&sanitized_value)#report_validation_failure?;
+                    Ok((#type_name(#field_value), warnings))
+                }
+            )
+        }
+    };
+
+    quote!(
+        impl #generics #type_name #generics_without_bounds {
+            #constructor
+
+            #fn_sanitize_lossy
+        }
+    )
+}
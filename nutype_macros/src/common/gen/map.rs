@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::ErrorTypePath;
+
+/// Generate `try_map()`, that transforms the inner value and passes the result through
+/// the sanitizers and validators again.
+pub fn gen_fn_try_map(
+    inner_type: &impl ToTokens,
+    error_type_path: &ErrorTypePath,
+    vis: &TokenStream,
+) -> TokenStream {
+    quote! {
+        /// Transforms the inner value with `f`, then sanitizes and validates the result.
+        #[inline]
+        #vis fn try_map(
+            self,
+            f: impl ::core::ops::FnOnce(#inner_type) -> #inner_type,
+        ) -> ::core::result::Result<Self, #error_type_path> {
+            Self::try_new(f(self.into_inner()))
+        }
+    }
+}
+
+/// Generate `map()` for types without validation, that transforms the inner value and
+/// sanitizes the result again.
+pub fn gen_fn_map(inner_type: &impl ToTokens, vis: &TokenStream) -> TokenStream {
+    quote! {
+        /// Transforms the inner value with `f`, then sanitizes the result.
+        #[inline]
+        #vis fn map(self, f: impl ::core::ops::FnOnce(#inner_type) -> #inner_type) -> Self {
+            Self::new(f(self.into_inner()))
+        }
+    }
+}
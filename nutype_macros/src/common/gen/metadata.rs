@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::strip_trait_bounds_on_generics,
+    models::{RuleNode, RuleStage, Rules, TypeName},
+};
+
+/// Generate `METADATA` constant, that describes the rules of the type with
+/// `nutype::TypeMetadata`.
+pub fn gen_impl_metadata<S, V>(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    rules: &Rules<S, V>,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let type_metadata = gen_type_metadata(type_name, inner_type, rules);
+
+    quote! {
+        impl #generics #type_name #generics_without_bounds {
+            /// Description of the sanitizers and validators of the type.
+            // The bounds of float types are already `f64`.
+            #[allow(clippy::unnecessary_cast)]
+            pub const METADATA: ::nutype::TypeMetadata = #type_metadata;
+        }
+    }
+}
+
+/// `nutype::TypeMetadata` of the type, shared by `METADATA` and `inventory`.
+pub fn gen_type_metadata<S, V>(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    rules: &Rules<S, V>,
+) -> TokenStream {
+    let name = type_name.to_string();
+    let inner_type_name = inner_type.to_token_stream().to_string();
+    let rule_metadata = rules
+        .sanitizers
+        .iter()
+        .map(gen_rule_metadata)
+        .chain(rules.validators.iter().map(gen_rule_metadata));
+
+    quote! {
+        ::nutype::TypeMetadata {
+            name: #name,
+            inner_type: #inner_type_name,
+            rules: &[#(#rule_metadata),*],
+        }
+    }
+}
+
+fn gen_rule_metadata<K>(node: &RuleNode<K>) -> TokenStream {
+    let stage = match node.stage {
+        RuleStage::Sanitizer => quote!(::nutype::RuleStage::Sanitizer),
+        RuleStage::Validator => quote!(::nutype::RuleStage::Validator),
+    };
+    let name = &node.name;
+    let bound = match &node.bound {
+        Some(bound) => quote!(::core::option::Option::Some((#bound) as f64)),
+        None => quote!(::core::option::Option::None),
+    };
+    let description = match &node.description {
+        Some(description) => quote!(::core::option::Option::Some(#description)),
+        None => quote!(::core::option::Option::None),
+    };
+    quote! {
+        ::nutype::RuleMetadata {
+            stage: #stage,
+            name: #name,
+            bound: #bound,
+            description: #description,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::quote;
+
+use crate::common::{
+    gen::error::gen_impl_error_trait,
+    models::{ErrorTypePath, TypeName},
+};
+
+/// Generate the error type returned by `try_migrate()`.
+/// It tells whether the value violates the legacy rules or the current ones.
+///
+/// Custom error types provided by the user are not required to implement anything but `Debug`,
+/// so the extra traits are implemented only if the current error type is generated by nutype.
+pub fn gen_migrate_error(
+    type_name: &TypeName,
+    migrate_error_type_path: &ErrorTypePath,
+    legacy_error_type_path: &ErrorTypePath,
+    error_type_path: &ErrorTypePath,
+    is_error_generated: bool,
+) -> TokenStream {
+    let definition = quote! {
+        pub enum #migrate_error_type_path {
+            /// The value violates the rules declared with `legacy(validate(...))`.
+            Legacy(#legacy_error_type_path),
+            /// The value is valid under the legacy rules, but violates the current ones.
+            Current(#error_type_path),
+        }
+    };
+
+    if is_error_generated {
+        let impl_error_trait = gen_impl_error_trait(migrate_error_type_path);
+        quote! {
+            #[derive(Debug, Clone, PartialEq)]
+            #definition
+
+            impl ::core::fmt::Display for #migrate_error_type_path {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #migrate_error_type_path::Legacy(err) => write!(f, "{} cannot be migrated: {}", stringify!(#type_name), err),
+                        #migrate_error_type_path::Current(err) => write!(f, "{}", err),
+                    }
+                }
+            }
+
+            #impl_error_trait
+        }
+    } else {
+        quote! {
+            #[derive(Debug)]
+            #definition
+        }
+    }
+}
+
+/// Replace every occurrence of the identifier `from` with `to`.
+/// It is used to obtain the legacy validation function out of the regular `__validate__()`.
+pub fn rename_ident(tokens: TokenStream, from: &str, to: &Ident) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ref ident) if ident == from => TokenTree::Ident(to.clone()),
+            TokenTree::Group(group) => {
+                let stream = rename_ident(group.stream(), from, to);
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            other => other,
+        })
+        .collect()
+}
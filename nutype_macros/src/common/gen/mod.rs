@@ -1,23 +1,97 @@
+pub mod arbitrary_invalid;
+pub mod as_variants;
+pub mod batch;
+pub mod borsh;
+pub mod bytes;
+pub mod canonicalize_number;
+pub mod clap;
+pub mod combinators;
+pub mod doc;
 pub mod error;
+pub mod ffi;
+pub mod hash;
+pub mod inventory;
+pub mod json_schema;
+pub mod lossy;
+pub mod map;
+pub mod metadata;
+pub mod migrate;
+pub mod new_into;
 pub mod new_unchecked;
+pub mod newtype;
+pub mod no_clone;
+pub mod normalize_for_eq;
+pub mod on_invalid;
+pub mod optional;
 pub mod parse_error;
+pub mod parse_money;
+pub mod refines;
+pub mod renamed_from;
+pub mod repr_transparent;
+pub mod rkyv;
+pub mod secret;
+pub mod store_as;
 pub mod tests;
+pub mod trace;
 pub mod traits;
+pub mod try_from_int;
+pub mod try_with;
+pub mod validate_all;
+pub mod wasm;
 
 use core::hash::Hash;
 use std::collections::HashSet;
 
 use self::traits::GeneratedTraits;
 
+use self::error::ExtraVariants;
 use super::models::{
-    CustomFunction, ErrorTypePath, GenerateParams, Guard, NewUnchecked, ParseErrorTypeName,
-    TypeName, TypeTrait,
+    CustomFunction, DeriveOptions, ErrorMessages, ErrorTypePath, GenerateParams, Guard,
+    MethodNames, NewUnchecked, OnInvalid, ParseErrorTypeName, Rule, Secret, StoreAs, TypeName,
+    TypeTrait,
 };
 use crate::common::{
-    gen::{new_unchecked::gen_new_unchecked, parse_error::gen_parse_error_name},
-    models::{ModuleName, Validation},
+    gen::{
+        as_variants::{gen_as_variants, gen_variant_enum_name},
+        batch::gen_fn_try_from_iter,
+        combinators::gen_fn_validate_with_combinators,
+        doc::{gen_constructor_doc, gen_error_doc, gen_type_doc, summarize_guarantees},
+        error::{
+            gen_bytes_error_type_name, gen_ffi_error_type_name, gen_migrate_error_type_name,
+            gen_try_from_error_type_name, gen_variant_parse_error_type_name,
+        },
+        ffi::gen_impl_ffi,
+        hash::{gen_impl_hash_with, gen_impl_nohash},
+        inventory::gen_inventory_submit,
+        json_schema::gen_impl_json_schema,
+        lossy::gen_impl_lossy,
+        map::{gen_fn_map, gen_fn_try_map},
+        metadata::gen_impl_metadata,
+        migrate::{gen_migrate_error, rename_ident},
+        new_unchecked::gen_new_unchecked,
+        newtype::gen_impl_newtype_traits,
+        no_clone::gen_assert_not_clone,
+        normalize_for_eq::gen_impl_normalized_traits,
+        on_invalid::gen_fn_on_invalid,
+        parse_error::gen_parse_error_name,
+        parse_money::{gen_parse_money, gen_parse_money_error_name},
+        refines::gen_impl_trait_try_from_refined,
+        renamed_from::gen_deprecated_aliases,
+        repr_transparent::gen_impl_repr_transparent,
+        rkyv::gen_archived_type_name,
+        secret::{gen_impl_redacted_traits, gen_impl_zeroize_on_drop},
+        store_as::{gen_decode, gen_encode, gen_field_type},
+        tests::gen_tests_examples,
+        traits::is_str_reference,
+        try_from_int::has_narrowing_int_source,
+        try_with::{gen_sanitize_or_return, try_with_error_type},
+        validate_all::gen_impl_validate_all,
+        wasm::{gen_impl_wasm, gen_wasm_bindgen_attr},
+    },
+    models::{validation_nodes, ModuleName, RuleNode, Rules, Validation, WithContext},
 };
-use proc_macro2::{Punct, Spacing, TokenStream, TokenTree};
+use cfg_if::cfg_if;
+use proc_macro2::{Ident, Punct, Spacing, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
 use syn::{Generics, Visibility};
 
@@ -102,6 +176,8 @@ pub fn gen_reimports(
     module_name: &ModuleName,
     maybe_error_type_path: Option<&ErrorTypePath>,
     maybe_parse_error_type_name: Option<&ParseErrorTypeName>,
+    maybe_parse_money_error_type_name: Option<&ParseErrorTypeName>,
+    companion_type_names: &[&dyn ToTokens],
 ) -> TokenStream {
     let reimport_main_type = quote! {
         #vis use #module_name::#type_name;
@@ -125,41 +201,181 @@ pub fn gen_reimports(
         }
     };
 
+    let reimport_parse_money_error_type_if_needed = match maybe_parse_money_error_type_name {
+        None => quote!(),
+        Some(ref parse_money_error_type_name) => {
+            quote! (
+                #vis use #module_name::#parse_money_error_type_name;
+            )
+        }
+    };
+
+    let reimport_companion_types = companion_type_names.iter().map(|companion_type_name| {
+        quote! (
+            #vis use #module_name::#companion_type_name;
+        )
+    });
+
     quote! {
         #reimport_main_type
         #reimport_error_type_if_needed
         #reimport_parse_error_type_if_needed
+        #reimport_parse_money_error_type_if_needed
+        #(#reimport_companion_types)*
     }
 }
 
+/// Visibility of the constructors, set with `constructor_visibility = ...`, or `pub` by default.
+/// The constructors are generated in a nested module, so a visibility relative to the module
+/// of the type (`pub(self)`, `pub(super)`, `pub(in super::...)`) gets one more `super`.
+pub fn gen_constructor_vis(method_names: &MethodNames) -> TokenStream {
+    let restricted = match &method_names.constructor_visibility {
+        None | Some(Visibility::Public(_)) => return quote!(pub),
+        // Rejected by the parser.
+        Some(Visibility::Inherited) => quote!(pub(super)),
+        Some(Visibility::Restricted(restricted)) => {
+            let path = &restricted.path;
+            let mut segments = path.segments.iter();
+            match segments.next() {
+                Some(first) if path.leading_colon.is_none() && first.ident == "self" => {
+                    let rest = segments.map(|segment| &segment.ident);
+                    quote!(pub(in super #(::#rest)*))
+                }
+                Some(first) if path.leading_colon.is_none() && first.ident == "super" => {
+                    quote!(pub(in super::#path))
+                }
+                _ => quote!(#restricted),
+            }
+        }
+    };
+    // Restricted constructors are not a part of the public API, so they may stay unused.
+    quote!(#[allow(dead_code)] #restricted)
+}
+
 pub fn gen_impl_into_inner(
     type_name: &TypeName,
     generics: &Generics,
     inner_type: impl ToTokens,
+    maybe_store_as: Option<&StoreAs>,
+    zeroize: bool,
+    method_names: &MethodNames,
 ) -> TokenStream {
+    let maybe_getter = method_names.getter.as_ref();
     let generics_without_bounds = strip_trait_bounds_on_generics(generics);
-    quote! {
-        impl #generics #type_name #generics_without_bounds {
+    let maybe_getter = maybe_getter.filter(|getter| *getter != "into_inner");
+    // With `getter = ...` the getter is public under that name. `into_inner()` is still used
+    // by the generated code, so it's kept private.
+    let (into_inner_vis, renamed_getter) = match maybe_getter {
+        Some(getter) => (
+            quote!(#[allow(dead_code)] pub(self)),
+            quote!(
+                /// Returns the inner value.
+                #[inline]
+                pub fn #getter(self) -> #inner_type {
+                    self.into_inner()
+                }
+            ),
+        ),
+        None => (quote!(pub), quote!()),
+    };
+    let fn_into_inner = if zeroize {
+        // The value is wiped on drop, so it can not be moved out of the field.
+        quote! {
+            #[inline]
+            #into_inner_vis fn into_inner(mut self) -> #inner_type {
+                ::core::mem::take(&mut self.0)
+            }
+        }
+    } else {
+        let inner_value = gen_decode(maybe_store_as);
+        quote! {
             #[inline]
-            pub fn into_inner(self) -> #inner_type {
-                self.0
+            #into_inner_vis fn into_inner(self) -> #inner_type {
+                #inner_value
             }
         }
+    };
+    let fn_field = method_names
+        .field
+        .as_ref()
+        .map(|field| gen_fn_field(field, &inner_type));
+    quote! {
+        impl #generics #type_name #generics_without_bounds {
+            #fn_into_inner
+            #renamed_getter
+            #fn_field
+        }
+    }
+}
+
+/// Accessor of a struct with a single named field, named after the field,
+/// e.g. `email.value()` for `struct Email { value: String }`.
+pub fn gen_fn_field(field: &Ident, inner_type: impl ToTokens) -> TokenStream {
+    quote! {
+        /// Returns a reference to the inner value.
+        #[inline]
+        pub fn #field(&self) -> &#inner_type {
+            &self.0
+        }
     }
 }
 
-/// Remove trait bounds from generics.
+/// Take `Debug` and `Display` out of the derived traits of a `secret` type and implement them
+/// with the redacted output instead.
+fn gen_secret<T: TypeTrait + Eq + Hash>(
+    type_name: &TypeName,
+    generics: &Generics,
+    traits: HashSet<T>,
+    secret: Secret,
+) -> (HashSet<T>, TokenStream) {
+    let debug = traits.iter().any(TypeTrait::is_debug);
+    let display = traits.iter().any(TypeTrait::is_display);
+    let traits = traits
+        .into_iter()
+        .filter(|t| !t.is_debug() && !t.is_display())
+        .collect();
+    let impl_redacted_traits = gen_impl_redacted_traits(type_name, generics, debug, display);
+    let impl_zeroize_on_drop = secret
+        .zeroize
+        .then(|| gen_impl_zeroize_on_drop(type_name, generics));
+    let impl_secret = quote! {
+        #impl_redacted_traits
+        #impl_zeroize_on_drop
+    };
+    (traits, impl_secret)
+}
+
+/// Implement `Debug` that prints `Name(***)` instead of the value, as the `strict` profile
+/// requires. Unlike `secret`, `Display` is kept as it is.
+fn gen_redacted_debug<T: TypeTrait + Eq + Hash>(
+    type_name: &TypeName,
+    generics: &Generics,
+    traits: HashSet<T>,
+) -> (HashSet<T>, TokenStream) {
+    let debug = traits.iter().any(TypeTrait::is_debug);
+    let traits = traits.into_iter().filter(|t| !t.is_debug()).collect();
+    let impl_redacted_debug = gen_impl_redacted_traits(type_name, generics, debug, false);
+    (traits, impl_redacted_debug)
+}
+
+/// Remove trait and lifetime bounds from generics.
 ///
 /// Input:
-///    <T: Display + Debug, U: Clone>
+///    <'a, 'b: 'a, T: Display + Debug, U: Clone>
 ///
 /// Output:
-///   <T, U>
+///   <'a, 'b, T, U>
 pub fn strip_trait_bounds_on_generics(original: &Generics) -> Generics {
     let mut generics = original.clone();
     for param in &mut generics.params {
-        if let syn::GenericParam::Type(syn::TypeParam { bounds, .. }) = param {
-            *bounds = syn::punctuated::Punctuated::new();
+        match param {
+            syn::GenericParam::Type(syn::TypeParam { bounds, .. }) => {
+                *bounds = syn::punctuated::Punctuated::new();
+            }
+            syn::GenericParam::Lifetime(syn::LifetimeParam { bounds, .. }) => {
+                *bounds = syn::punctuated::Punctuated::new();
+            }
+            syn::GenericParam::Const(_) => {}
         }
     }
     generics
@@ -201,11 +417,23 @@ pub fn add_param(generics: &Generics, param: TokenStream) -> Generics {
     generics
 }
 
+/// Path to `String` in the generated code. Without `std` it's taken from `alloc`,
+/// so the code also compiles in `no_std` crates that declare `extern crate alloc`.
+pub fn gen_string_path() -> TokenStream {
+    cfg_if! {
+        if #[cfg(feature = "std")] {
+            quote!(::std::string::String)
+        } else {
+            quote!(::alloc::string::String)
+        }
+    }
+}
+
 pub trait GenerateNewtype {
-    type Sanitizer;
-    type Validator;
+    type Sanitizer: Rule;
+    type Validator: Rule;
     type InnerType: ToTokens;
-    type TypedTrait: Hash + TypeTrait;
+    type TypedTrait: Hash + Eq + TypeTrait;
 
     /// If the type has dedicated parse error. This error is used within `FromStr` trait.
     /// For most of the types it's different from the regular validation error, because parsing
@@ -220,57 +448,221 @@ pub trait GenerateNewtype {
     /// If it's true, then `::new()` function receives `impl Into<T>` instead of `T`.
     const NEW_CONVERT_INTO_INNER_TYPE: bool = false;
 
-    fn gen_fn_sanitize(inner_type: &Self::InnerType, sanitizers: &[Self::Sanitizer])
-        -> TokenStream;
+    /// If it's true, then `nutype::Newtype` and `nutype::TryNewtype` are implemented.
+    /// The inner type is used as an associated type, so it must not be private.
+    const IMPLEMENTS_NEWTYPE_TRAITS: bool = true;
+
+    /// If it's true, then `&str` listed in `try_from(...)` is trimmed and parsed into the inner
+    /// type, and the failures are reported with the dedicated parse error.
+    const PARSES_STR_SOURCE: bool = false;
+
+    /// If it's true, then the validators receive `&str` instead of a reference to the inner type.
+    const VALIDATES_STR: bool = false;
+
+    /// With `trace` the output of every sanitizer is reported with a `tracing` event.
+    /// With `lossy` it generates `__sanitize_lossy__()`, that reports the sanitizers, that
+    /// changed the value.
+    fn gen_fn_sanitize(
+        inner_type: &Self::InnerType,
+        sanitizers: &[Self::Sanitizer],
+        trace: bool,
+        lossy: bool,
+    ) -> TokenStream;
+
+    /// Generate `try_new_into()` (or `new_into()`) that reuses the allocation of a buffer.
+    /// Only the inner types, that own a growable buffer (e.g. `String`), have something to reuse.
+    /// With `arena` borrowed inner types get `try_new_in()` that allocates in a `bumpalo` arena.
+    fn gen_fn_new_into(
+        _type_name: &TypeName,
+        _generics: &Generics,
+        _inner_type: &Self::InnerType,
+        _guard: &Guard<Self::Sanitizer, Self::Validator>,
+        _arena: bool,
+        _maybe_on_invalid: Option<&OnInvalid>,
+        _constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        quote!()
+    }
+
+    /// Generate `validate_bytes()` and `try_from_utf8()` of the `bytes` attribute.
+    /// Only `String` based types can be built from UTF-8 bytes.
+    fn gen_impl_bytes(
+        _type_name: &TypeName,
+        _guard: &Guard<Self::Sanitizer, Self::Validator>,
+        _constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        quote!()
+    }
 
+    /// Generate `eq_raw()` that sanitizes a raw value and compares it with the inner one.
+    /// It relies on `__sanitize__()`, which is defined along with `new()` or `try_new()`.
+    fn gen_fn_eq_raw(
+        inner_type: &Self::InnerType,
+        sanitizers: &[Self::Sanitizer],
+        maybe_store_as: Option<&StoreAs>,
+    ) -> TokenStream {
+        let inner_value = gen_decode(maybe_store_as);
+        // A value rejected by a `try_with(...)` sanitizer is not equal to any value.
+        let is_equal = if try_with_error_type(sanitizers).is_some() {
+            quote!(Self::__sanitize__(raw).is_ok_and(|value| value == #inner_value))
+        } else {
+            quote!(Self::__sanitize__(raw) == #inner_value)
+        };
+        quote! {
+            /// Returns `true` if the value, after being sanitized, is equal to the inner value.
+            pub fn eq_raw(&self, raw: #inner_type) -> bool {
+                #is_equal
+            }
+        }
+    }
+
+    /// With `trace` the verdict of every validator is reported with a `tracing` event.
     fn gen_fn_validate(
         inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        trace: bool,
     ) -> TokenStream;
 
     fn gen_validation_error_type(
         type_name: &TypeName,
+        inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        extra_variants: ExtraVariants,
+        messages: &ErrorMessages,
     ) -> TokenStream;
 
+    /// Associated constants for the bounds declared with validators, e.g. `MIN_LEN` or `MAX`.
+    fn gen_bound_consts(
+        _inner_type: &Self::InnerType,
+        _validators: &[Self::Validator],
+    ) -> TokenStream {
+        quote!()
+    }
+
+    /// `MAX_ACCEPT_BYTES` and `exceeds_limit()`, that let reject a too long raw input
+    /// before it's buffered completely. Only string based types have a length limit.
+    fn gen_input_limit(
+        _sanitizers: &[Self::Sanitizer],
+        _validators: &[Self::Validator],
+    ) -> TokenStream {
+        quote!()
+    }
+
+    /// Constructors that only some kinds have, generated next to `try_new()`,
+    /// e.g. `try_new_at()` of temporal types. They have the visibility of `try_new()`
+    /// and report a rejected value with `report_validation_failure` the same way.
+    fn gen_extra_constructors(
+        _inner_type: &Self::InnerType,
+        _validators: &[Self::Validator],
+        _error_type_path: &ErrorTypePath,
+        _constructor_vis: &TokenStream,
+        _report_validation_failure: &TokenStream,
+    ) -> TokenStream {
+        quote!()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn gen_traits(
         type_name: &TypeName,
         generics: &Generics,
         inner_type: &Self::InnerType,
         traits: HashSet<Self::TypedTrait>,
         maybe_default_value: Option<syn::Expr>,
+        derive_options: &DeriveOptions,
         guard: &Guard<Self::Sanitizer, Self::Validator>,
+        maybe_store_as: Option<&StoreAs>,
     ) -> Result<GeneratedTraits, syn::Error>;
 
+    #[allow(clippy::too_many_arguments)]
     fn gen_try_new(
         type_name: &TypeName,
         generics: &Generics,
         inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
         validation: &Validation<Self::Validator>,
+        maybe_store_as: Option<&StoreAs>,
+        trace: bool,
+        guarantee: bool,
+        method_names: &MethodNames,
+        maybe_on_invalid: Option<&OnInvalid>,
     ) -> TokenStream {
         let generics_without_bounds = strip_trait_bounds_on_generics(generics);
-        let fn_sanitize = Self::gen_fn_sanitize(inner_type, sanitizers);
+        let fn_sanitize = Self::gen_fn_sanitize(inner_type, sanitizers, trace, false);
+        let field_value = gen_encode(quote!(sanitized_value), inner_type, maybe_store_as);
+
+        let rules = Rules {
+            sanitizers: RuleNode::sanitizers(sanitizers),
+            validators: validation_nodes(validation),
+        };
+        let validator_descriptions: Vec<String> = rules
+            .validators
+            .iter()
+            .filter_map(|node| node.description.clone())
+            .collect();
+        let error_doc = gen_error_doc(type_name, &validator_descriptions);
+        let maybe_guarantees = summarize_guarantees(rules.guarantees());
+        let try_new_doc = gen_constructor_doc(
+            "Sanitizes and validates the value.",
+            maybe_guarantees.as_ref(),
+        );
 
         let maybe_generated_validation_error = match validation {
             Validation::Standard {
                 validators,
                 error_type_path,
+                with_context,
+                messages,
+                combinators,
             } => {
-                let validation_error =
-                    Self::gen_validation_error_type(type_name, error_type_path, validators);
-                Some(validation_error)
+                let extra_variants = ExtraVariants {
+                    with_context: with_context.is_some(),
+                    try_with: try_with_error_type(sanitizers),
+                    ..ExtraVariants::default()
+                }
+                .with_combinators(combinators);
+                let validation_error = Self::gen_validation_error_type(
+                    type_name,
+                    inner_type,
+                    error_type_path,
+                    validators,
+                    extra_variants,
+                    messages,
+                );
+                Some(quote! {
+                    #error_doc
+                    #validation_error
+                })
             }
             Validation::Custom { .. } => None,
         };
 
+        let bound_consts = match validation {
+            Validation::Standard { validators, .. } => {
+                let bound_consts = Self::gen_bound_consts(inner_type, validators);
+                let input_limit = Self::gen_input_limit(sanitizers, validators);
+                quote!(
+                    #bound_consts
+                    #input_limit
+                )
+            }
+            Validation::Custom { .. } => quote!(),
+        };
+
         let fn_validate = match validation {
             Validation::Standard {
                 validators,
                 error_type_path,
-            } => Self::gen_fn_validate(inner_type, error_type_path, validators),
+                combinators,
+                ..
+            } => gen_fn_validate_with_combinators::<Self>(
+                type_name,
+                inner_type,
+                error_type_path,
+                Self::gen_fn_validate(inner_type, error_type_path, validators, trace),
+                combinators,
+            ),
             Validation::Custom {
                 with,
                 error_type_path,
@@ -287,29 +679,241 @@ pub trait GenerateNewtype {
         };
 
         let error_type_path = validation.error_type_path();
+        let report_validation_failure =
+            gen_report_validation_failure(type_name, maybe_on_invalid, quote!(sanitized_value));
+        let sanitize_raw_value = gen_sanitize_or_return(quote!(raw_value), sanitizers, validation);
+        // Validators of string types receive `&str` instead of a reference to the inner type.
+        let validated_type = if Self::VALIDATES_STR {
+            quote!(str)
+        } else {
+            quote!(#inner_type)
+        };
+        let fn_on_invalid = maybe_on_invalid
+            .map(|on_invalid| gen_fn_on_invalid(on_invalid, error_type_path, &validated_type));
+        let fn_validation_failure_code = gen_fn_validation_failure_code(
+            error_type_path,
+            matches!(validation, Validation::Standard { .. }),
+        );
 
-        quote!(
-            #maybe_generated_validation_error
+        let constructor_vis = gen_constructor_vis(method_names);
+        let maybe_constructor = method_names
+            .constructor
+            .as_ref()
+            .filter(|constructor| *constructor != "try_new");
+        // With `constructor = ...` the constructor is available under that name. `try_new()` is
+        // still used by the generated code, so it's kept private.
+        let (try_new_vis, renamed_constructor) = match maybe_constructor {
+            Some(constructor) => (
+                quote!(#[allow(dead_code)] pub(self)),
+                quote!(
+                    #try_new_doc
+                    #[inline]
+                    #constructor_vis fn #constructor(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+                        Self::try_new(raw_value)
+                    }
+                ),
+            ),
+            None => (constructor_vis.clone(), quote!()),
+        };
 
-            impl #generics #type_name #generics_without_bounds {
-                pub fn try_new(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+        // TODO: Remove in 0.5.0
+        let deprecated_new = maybe_constructor.is_none().then(|| {
+            quote!(
+                #[deprecated(since="0.4.3", note="\nUse `try_new` instead.")]
+                #[inline]
+                #constructor_vis fn new(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+                    Self::try_new(raw_value)
+                }
+            )
+        });
+
+        let constructor = match validation {
+            Validation::Standard {
+                with_context: Some(WithContext { with, context_type }),
+                ..
+            } => {
+                // `try_new()` is not generated, so a value can not be created without the context.
+                let try_from_with_doc = gen_constructor_doc(
+                    "Sanitizes and validates the value, including the `with_context` validator, that checks the value against `ctx`.",
+                    maybe_guarantees.as_ref(),
+                );
+                quote!(
+                    #try_from_with_doc
+                    #[inline]
+                    #constructor_vis fn try_from_with(ctx: &#context_type, raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+                        #convert_raw_value_if_necessary
+
+                        let sanitized_value: #inner_type = #sanitize_raw_value;
+                        Self::__validate__(&sanitized_value)#report_validation_failure?;
+                        Self::__validate_with_context__(&sanitized_value, ctx)#report_validation_failure?;
+                        Ok(#type_name(#field_value))
+                    }
+
+                    fn __validate_with_context__(val: &#validated_type, ctx: &#context_type) -> ::core::result::Result<(), #error_type_path> {
+                        let with_context: fn(&#validated_type, &#context_type) -> bool = #with;
+                        if with_context(val, ctx) {
+                            Ok(())
+                        } else {
+                            Err(#error_type_path::WithContextViolated)
+                        }
+                    }
+                )
+            }
+            // The sanitizers are verified to produce only valid values, so the deprecated
+            // fallible `new()` is replaced with the infallible one.
+            _ if guarantee => quote!(
+                #try_new_doc
+                #[inline]
+                #try_new_vis fn try_new(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+                    #convert_raw_value_if_necessary
+
+                    let sanitized_value: #inner_type = #sanitize_raw_value;
+                    Self::__validate__(&sanitized_value)#report_validation_failure?;
+                    Ok(#type_name(#field_value))
+                }
+
+                /// Sanitizes the value, that is guaranteed to be valid after the sanitizers.
+                #[inline]
+                #constructor_vis fn new(raw_value: #input_type) -> Self {
                     #convert_raw_value_if_necessary
 
                     let sanitized_value: #inner_type = Self::__sanitize__(raw_value);
-                    Self::__validate__(&sanitized_value)?;
-                    Ok(#type_name(sanitized_value))
+                    debug_assert!(Self::__validate__(&sanitized_value).is_ok());
+                    #type_name(#field_value)
                 }
+            ),
+            _ => quote!(
+                #try_new_doc
+                #[inline]
+                #try_new_vis fn try_new(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
+                    #convert_raw_value_if_necessary
+
+                    let sanitized_value: #inner_type = #sanitize_raw_value;
+                    Self::__validate__(&sanitized_value)#report_validation_failure?;
+                    Ok(#type_name(#field_value))
+                }
+
+                #deprecated_new
+            ),
+        };
+
+        let extra_constructors = match validation {
+            Validation::Standard { validators, .. } => Self::gen_extra_constructors(
+                inner_type,
+                validators,
+                error_type_path,
+                &constructor_vis,
+                &report_validation_failure,
+            ),
+            Validation::Custom { .. } => quote!(),
+        };
+
+        // Values of `with_context` types are built only with `try_from_with()`.
+        let (fn_try_from_iter, fn_try_map) = match validation {
+            Validation::Standard {
+                with_context: Some(_),
+                ..
+            } => (quote!(), quote!()),
+            _ => (
+                gen_fn_try_from_iter(
+                    inner_type,
+                    error_type_path,
+                    Self::NEW_CONVERT_INTO_INNER_TYPE,
+                    &constructor_vis,
+                ),
+                gen_fn_try_map(inner_type, error_type_path, &constructor_vis),
+            ),
+        };
+
+        quote!(
+            #maybe_generated_validation_error
+
+            impl #generics #type_name #generics_without_bounds {
+                #bound_consts
+
+                #constructor
+                #renamed_constructor
+                #extra_constructors
+                #fn_try_from_iter
+                #fn_try_map
 
                 // Definite associated private functions __sanitize__() and __validate__() with underscores so they do not overlap with outer
                 // scope imported with `use super::*`.
                 #fn_sanitize
                 #fn_validate
+                #fn_on_invalid
+                #fn_validation_failure_code
+            }
+        )
+    }
 
-                // TODO: Remove in 0.5.0
-                #[deprecated(since="0.4.3", note="\nUse `try_new` instead.")]
-                pub fn new(raw_value: #input_type) -> ::core::result::Result<Self, #error_type_path> {
-                    Self::try_new(raw_value)
+    /// Generate `try_migrate()` that accepts values valid under the legacy rules and brings
+    /// them to the current rules with `try_new()`.
+    fn gen_try_migrate(
+        type_name: &TypeName,
+        generics: &Generics,
+        inner_type: &Self::InnerType,
+        validation: &Validation<Self::Validator>,
+        legacy: &Validation<Self::Validator>,
+        constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        let Validation::Standard {
+            validators: legacy_validators,
+            error_type_path: legacy_error_type_path,
+            messages: legacy_messages,
+            ..
+        } = legacy
+        else {
+            // Custom legacy validation is rejected by the parser.
+            return quote!();
+        };
+        let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+        let error_type_path = validation.error_type_path();
+        let migrate_error_type_path = gen_migrate_error_type_name(type_name);
+
+        let legacy_error = Self::gen_validation_error_type(
+            type_name,
+            inner_type,
+            legacy_error_type_path,
+            legacy_validators,
+            ExtraVariants::default(),
+            legacy_messages,
+        );
+        let migrate_error = gen_migrate_error(
+            type_name,
+            &migrate_error_type_path,
+            legacy_error_type_path,
+            error_type_path,
+            matches!(validation, Validation::Standard { .. }),
+        );
+        let fn_validate_legacy = rename_ident(
+            Self::gen_fn_validate(inner_type, legacy_error_type_path, legacy_validators, false),
+            "__validate__",
+            &format_ident!("__validate_legacy__"),
+        );
+
+        let (input_type, convert_raw_value_if_necessary) = if Self::NEW_CONVERT_INTO_INNER_TYPE {
+            (
+                quote!(impl Into<#inner_type>),
+                quote!(let raw_value: #inner_type = raw_value.into();),
+            )
+        } else {
+            (quote!(#inner_type), quote!())
+        };
+
+        quote!(
+            #legacy_error
+            #migrate_error
+
+            impl #generics #type_name #generics_without_bounds {
+                #constructor_vis fn try_migrate(raw_value: #input_type) -> ::core::result::Result<Self, #migrate_error_type_path> {
+                    #convert_raw_value_if_necessary
+
+                    Self::__validate_legacy__(&raw_value).map_err(#migrate_error_type_path::Legacy)?;
+                    Self::try_new(raw_value).map_err(#migrate_error_type_path::Current)
                 }
+
+                #fn_validate_legacy
             }
         )
     }
@@ -319,9 +923,17 @@ pub trait GenerateNewtype {
         generics: &Generics,
         inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
+        maybe_store_as: Option<&StoreAs>,
+        trace: bool,
+        method_names: &MethodNames,
     ) -> TokenStream {
         let generics_without_bounds = strip_trait_bounds_on_generics(generics);
-        let fn_sanitize = Self::gen_fn_sanitize(inner_type, sanitizers);
+        let fn_sanitize = Self::gen_fn_sanitize(inner_type, sanitizers, trace, false);
+        let field_value = gen_encode(
+            quote!(Self::__sanitize__(raw_value)),
+            inner_type,
+            maybe_store_as,
+        );
 
         let (input_type, convert_raw_value_if_necessary) = if Self::NEW_CONVERT_INTO_INNER_TYPE {
             (
@@ -332,12 +944,50 @@ pub trait GenerateNewtype {
             (quote!(#inner_type), quote!())
         };
 
+        let new_doc = gen_constructor_doc(
+            "Sanitizes the value.",
+            summarize_guarantees(
+                Rules::<_, Self::Validator> {
+                    sanitizers: RuleNode::sanitizers(sanitizers),
+                    validators: vec![],
+                }
+                .guarantees(),
+            )
+            .as_ref(),
+        );
+
+        let constructor_vis = gen_constructor_vis(method_names);
+        let maybe_constructor = method_names
+            .constructor
+            .as_ref()
+            .filter(|constructor| *constructor != "new");
+        // With `constructor = ...` the constructor is available under that name. `new()` is still
+        // used by the generated code, so it's kept private.
+        let (new_vis, renamed_constructor) = match maybe_constructor {
+            Some(constructor) => (
+                quote!(#[allow(dead_code)] pub(self)),
+                quote!(
+                    #new_doc
+                    #[inline]
+                    #constructor_vis fn #constructor(raw_value: #input_type) -> Self {
+                        Self::new(raw_value)
+                    }
+                ),
+            ),
+            None => (constructor_vis.clone(), quote!()),
+        };
+        let fn_map = gen_fn_map(inner_type, &constructor_vis);
+
         quote!(
             impl #generics #type_name #generics_without_bounds {
-                pub fn new(raw_value: #input_type) -> Self {
+                #new_doc
+                #[inline]
+                #new_vis fn new(raw_value: #input_type) -> Self {
                     #convert_raw_value_if_necessary
-                    Self(Self::__sanitize__(raw_value))
+                    Self(#field_value)
                 }
+                #renamed_constructor
+                #fn_map
                 // Definite associated private function __sanitize__() with underscores so they do not overlap with outer
                 // scope imported with `use super::*`.
                 #fn_sanitize
@@ -345,29 +995,153 @@ pub trait GenerateNewtype {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn gen_implementation(
         type_name: &TypeName,
         generics: &Generics,
         inner_type: &Self::InnerType,
         guard: &Guard<Self::Sanitizer, Self::Validator>,
         new_unchecked: NewUnchecked,
+        maybe_store_as: Option<&StoreAs>,
+        trace: bool,
+        zeroize: bool,
+        arena: bool,
+        guarantee: bool,
+        method_names: &MethodNames,
+        maybe_on_invalid: Option<&OnInvalid>,
+        lossy: bool,
     ) -> TokenStream {
+        let constructor_vis = gen_constructor_vis(method_names);
         let impl_new = match guard {
-            Guard::WithoutValidation { sanitizers } => {
-                Self::gen_new(type_name, generics, inner_type, sanitizers)
-            }
+            Guard::WithoutValidation { sanitizers } => Self::gen_new(
+                type_name,
+                generics,
+                inner_type,
+                sanitizers,
+                maybe_store_as,
+                trace,
+                method_names,
+            ),
             Guard::WithValidation {
                 sanitizers,
                 validation,
-            } => Self::gen_try_new(type_name, generics, inner_type, sanitizers, validation),
+                legacy,
+            } => {
+                let impl_try_new = Self::gen_try_new(
+                    type_name,
+                    generics,
+                    inner_type,
+                    sanitizers,
+                    validation,
+                    maybe_store_as,
+                    trace,
+                    guarantee,
+                    method_names,
+                    maybe_on_invalid,
+                );
+                let impl_try_migrate = legacy.as_ref().map(|legacy| {
+                    Self::gen_try_migrate(
+                        type_name,
+                        generics,
+                        inner_type,
+                        validation,
+                        legacy,
+                        &constructor_vis,
+                    )
+                });
+                quote! {
+                    #impl_try_new
+                    #impl_try_migrate
+                }
+            }
+        };
+        let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+        let fn_eq_raw = Self::gen_fn_eq_raw(inner_type, guard.sanitizers(), maybe_store_as);
+        let impl_eq_raw = quote! {
+            impl #generics #type_name #generics_without_bounds {
+                #fn_eq_raw
+            }
+        };
+        // The buffer can not be reused when the value is stored in a different representation.
+        // Values of `with_context` types are built only with `try_from_with()`.
+        // A `try_with(...)` sanitizer consumes the buffer, so it can not be given back on failure.
+        let impl_new_into = if maybe_store_as.is_none()
+            && guard.with_context().is_none()
+            && try_with_error_type(guard.sanitizers()).is_none()
+        {
+            Self::gen_fn_new_into(
+                type_name,
+                generics,
+                inner_type,
+                guard,
+                arena,
+                maybe_on_invalid,
+                &constructor_vis,
+            )
+        } else {
+            quote!()
+        };
+        let impl_lossy = lossy.then(|| {
+            gen_impl_lossy::<Self>(
+                type_name,
+                generics,
+                inner_type,
+                guard,
+                maybe_store_as,
+                trace,
+                maybe_on_invalid,
+                &constructor_vis,
+            )
+        });
+        let impl_into_inner = gen_impl_into_inner(
+            type_name,
+            generics,
+            inner_type,
+            maybe_store_as,
+            zeroize,
+            method_names,
+        );
+        let impl_new_unchecked = gen_new_unchecked(
+            type_name,
+            generics,
+            inner_type,
+            new_unchecked,
+            maybe_store_as,
+        );
+
+        // A reference to the inner value can not be obtained when it's stored in a different
+        // representation.
+        let impl_newtype_traits = if Self::IMPLEMENTS_NEWTYPE_TRAITS
+            && maybe_store_as.is_none()
+            && guard.with_context().is_none()
+        {
+            let is_custom_error = matches!(
+                guard,
+                Guard::WithValidation {
+                    validation: Validation::Custom { .. },
+                    ..
+                }
+            );
+            let has_restricted_constructor = method_names.constructor_visibility.is_some();
+            gen_impl_newtype_traits(
+                type_name,
+                generics,
+                inner_type,
+                guard.maybe_error_type_path(),
+                !is_custom_error && !has_restricted_constructor,
+            )
+        } else {
+            quote!()
         };
-        let impl_into_inner = gen_impl_into_inner(type_name, generics, inner_type);
-        let impl_new_unchecked = gen_new_unchecked(type_name, inner_type, new_unchecked);
 
         quote! {
             #impl_new
+            #impl_new_into
+            #impl_lossy
+            #impl_eq_raw
             #impl_into_inner
             #impl_new_unchecked
+            #impl_newtype_traits
         }
     }
 
@@ -389,18 +1163,53 @@ pub trait GenerateNewtype {
             maybe_default_value,
             inner_type,
             generics,
+            parse_money,
+            store_as,
+            as_variants,
+            derive_options,
+            trace,
+            secret,
+            arena,
+            guarantee,
         } = params;
 
+        let rules = Rules::of_guard(&guard);
+        let inventory_submit = gen_inventory_submit(&type_name, &inner_type, &rules);
+        let impl_metadata = gen_impl_metadata(&type_name, &generics, &inner_type, &rules);
+        let type_doc = {
+            let maybe_guarantees = summarize_guarantees(rules.guarantees());
+            gen_type_doc(!doc_attrs.is_empty(), maybe_guarantees.as_ref())
+        };
         let module_name = gen_module_name_for_type(&type_name);
-        let implementation =
-            Self::gen_implementation(&type_name, &generics, &inner_type, &guard, new_unchecked);
+        let implementation = Self::gen_implementation(
+            &type_name,
+            &generics,
+            &inner_type,
+            &guard,
+            new_unchecked,
+            store_as.as_ref(),
+            trace,
+            secret.is_some_and(|secret| secret.zeroize),
+            arena,
+            guarantee,
+            &derive_options.method_names,
+            derive_options.on_invalid.as_ref(),
+            derive_options.lossy.is_some(),
+        );
+        let field_type = gen_field_type(&inner_type, store_as.as_ref());
 
         let has_from_str_trait = traits.iter().any(|t| t.is_from_str());
-        let maybe_parse_error_type_path = if has_from_str_trait && Self::HAS_DEDICATED_PARSE_ERROR {
-            Some(gen_parse_error_name(&type_name))
-        } else {
-            None
-        };
+        let has_str_source = Self::PARSES_STR_SOURCE
+            && derive_options
+                .try_from
+                .as_ref()
+                .is_some_and(|source_types| source_types.iter().any(is_str_reference));
+        let maybe_parse_error_type_path =
+            if (has_from_str_trait || has_str_source) && Self::HAS_DEDICATED_PARSE_ERROR {
+                Some(gen_parse_error_name(&type_name))
+            } else {
+                None
+            };
 
         let tests = Self::gen_tests(
             &type_name,
@@ -422,14 +1231,198 @@ pub trait GenerateNewtype {
             },
         };
 
+        let (maybe_migrate_error_type_path, maybe_legacy_error_type_path) = match &guard {
+            Guard::WithValidation {
+                legacy: Some(legacy),
+                ..
+            } => (
+                Some(gen_migrate_error_type_name(&type_name)),
+                Some(legacy.error_type_path()),
+            ),
+            _ => (None, None),
+        };
+        let maybe_variant_enum_name = as_variants
+            .as_ref()
+            .map(|_| gen_variant_enum_name(&type_name));
+        let maybe_variant_parse_error_type_path = as_variants
+            .as_ref()
+            .map(|_| gen_variant_parse_error_type_name(&type_name));
+        let maybe_try_from_error_type_path = derive_options
+            .try_from
+            .as_ref()
+            .filter(|source_types| has_narrowing_int_source(&inner_type, source_types))
+            .map(|_| gen_try_from_error_type_name(&type_name));
+        // String based types are built from a C string, that may fail to convert.
+        let is_string = !Self::HAS_DEDICATED_PARSE_ERROR;
+        let maybe_ffi_error_type_path = derive_options
+            .ffi
+            .filter(|_| is_string)
+            .map(|_| gen_ffi_error_type_name(&type_name));
+        let maybe_bytes_error_type_path = derive_options
+            .bytes
+            .map(|_| gen_bytes_error_type_name(&type_name));
+        let maybe_archived_type_name = traits
+            .iter()
+            .any(|t| t.is_rkyv_archive())
+            .then(|| gen_archived_type_name(&type_name));
+        let companion_type_names: Vec<&dyn ToTokens> = maybe_migrate_error_type_path
+            .iter()
+            .chain(maybe_legacy_error_type_path)
+            .chain(maybe_try_from_error_type_path.iter())
+            .chain(maybe_ffi_error_type_path.iter())
+            .chain(maybe_bytes_error_type_path.iter())
+            .chain(maybe_variant_parse_error_type_path.iter())
+            .map(|error_type_path| error_type_path as &dyn ToTokens)
+            .chain(
+                maybe_variant_enum_name
+                    .iter()
+                    .chain(maybe_archived_type_name.iter())
+                    .map(|name| name as &dyn ToTokens),
+            )
+            .collect();
+        let maybe_as_variants = as_variants.as_ref().map(|as_variants| {
+            gen_as_variants(
+                &type_name,
+                &generics,
+                &inner_type,
+                is_string,
+                store_as.as_ref(),
+                as_variants,
+            )
+        });
+
+        let constructor_vis = gen_constructor_vis(&derive_options.method_names);
+        let maybe_parse_money = parse_money.as_ref().map(|parse_money| {
+            gen_parse_money(
+                &type_name,
+                &generics,
+                &inner_type,
+                guard.maybe_error_type_path(),
+                parse_money,
+                &constructor_vis,
+            )
+        });
+        let maybe_parse_money_error_type_name = parse_money
+            .as_ref()
+            .map(|_| gen_parse_money_error_name(&type_name));
+
+        let maybe_deprecated_aliases = derive_options.renamed_from.as_ref().map(|renamed_from| {
+            let companion_names: Vec<String> = maybe_reimported_error_type_path
+                .iter()
+                .map(ToString::to_string)
+                .chain(maybe_parse_error_type_path.iter().map(ToString::to_string))
+                .chain(
+                    maybe_parse_money_error_type_name
+                        .iter()
+                        .map(ToString::to_string),
+                )
+                .chain(
+                    companion_type_names
+                        .iter()
+                        .map(|name| name.to_token_stream().to_string()),
+                )
+                .collect();
+            gen_deprecated_aliases(&vis, &type_name, &generics, renamed_from, &companion_names)
+        });
         let reimports = gen_reimports(
             vis,
             &type_name,
             &module_name,
             maybe_reimported_error_type_path,
             maybe_parse_error_type_path.as_ref(),
+            maybe_parse_money_error_type_name.as_ref(),
+            &companion_type_names,
         );
 
+        let (traits, impl_secret) = match secret {
+            Some(secret) => gen_secret(&type_name, &generics, traits, secret),
+            None if derive_options.profile.redacts_debug() => {
+                gen_redacted_debug(&type_name, &generics, traits)
+            }
+            None => (traits, quote!()),
+        };
+        let impl_try_from_refined = derive_options.refines.as_ref().map(|base_type| {
+            gen_impl_trait_try_from_refined(
+                &type_name,
+                &generics,
+                &inner_type,
+                base_type,
+                guard.maybe_error_type_path(),
+                store_as.as_ref(),
+                derive_options.on_invalid.as_ref(),
+            )
+        });
+        let impl_validate_all = derive_options
+            .profile
+            .validates_all()
+            .then(|| gen_impl_validate_all::<Self>(&type_name, &generics, &inner_type, &guard));
+        // `JsonSchema` with `schema(...)` metadata is implemented instead of derived.
+        let (traits, impl_json_schema) = match &derive_options.schema {
+            Some(schema) => {
+                let traits = traits.into_iter().filter(|t| !t.is_json_schema()).collect();
+                let impl_json_schema =
+                    gen_impl_json_schema(&type_name, &generics, &inner_type, &doc_attrs, schema);
+                (traits, impl_json_schema)
+            }
+            None => (traits, quote!()),
+        };
+        let impl_normalized_traits =
+            derive_options
+                .normalize_for_eq
+                .as_ref()
+                .map(|normalize_for_eq| {
+                    gen_impl_normalized_traits(
+                        &type_name,
+                        &generics,
+                        &inner_type,
+                        store_as.as_ref(),
+                        normalize_for_eq,
+                    )
+                });
+
+        let impl_hash_with = derive_options.hash_with.as_ref().map(|hash_with| {
+            gen_impl_hash_with(
+                &type_name,
+                &generics,
+                &inner_type,
+                store_as.as_ref(),
+                hash_with,
+            )
+        });
+        let impl_nohash = derive_options
+            .nohash
+            .map(|_| gen_impl_nohash(&type_name, &generics));
+        let assert_not_clone = derive_options
+            .no_clone
+            .map(|_| gen_assert_not_clone(&type_name, &generics));
+        let impl_ffi = derive_options.ffi.map(|_| {
+            gen_impl_ffi(
+                &type_name,
+                &inner_type,
+                guard.maybe_error_type_path(),
+                is_string,
+            )
+        });
+        let maybe_repr_transparent = derive_options
+            .ffi
+            .or(derive_options.repr_transparent)
+            .map(|_| quote!(#[repr(transparent)]));
+        let impl_repr_transparent = derive_options
+            .repr_transparent
+            .map(|_| gen_impl_repr_transparent(&type_name, &generics, &inner_type, new_unchecked));
+        let impl_wasm = derive_options.wasm.map(|_| {
+            gen_impl_wasm(
+                &type_name,
+                &inner_type,
+                guard.maybe_error_type_path(),
+                is_string,
+            )
+        });
+        let maybe_wasm_bindgen_attr = derive_options.wasm.map(|_| gen_wasm_bindgen_attr());
+        let impl_bytes = derive_options
+            .bytes
+            .map(|_| Self::gen_impl_bytes(&type_name, &guard, &constructor_vis));
+
         let GeneratedTraits {
             derive_transparent_traits,
             implement_traits,
@@ -439,9 +1432,16 @@ pub trait GenerateNewtype {
             &inner_type,
             traits,
             maybe_default_value,
+            &derive_options,
             &guard,
+            store_as.as_ref(),
         )?;
 
+        let tests_examples = derive_options
+            .test_examples
+            .as_ref()
+            .map(|test_examples| gen_tests_examples(&type_name, test_examples));
+
         Ok(quote!(
             #[doc(hidden)]
             #[allow(non_snake_case, reason = "we keep original structure name which is probably CamelCase")]
@@ -449,19 +1449,40 @@ pub trait GenerateNewtype {
                 use super::*;
 
                 #(#doc_attrs)*
+                #type_doc
+                #maybe_wasm_bindgen_attr
                 #derive_transparent_traits
-                pub struct #type_name #generics(#inner_type);
+                #maybe_repr_transparent
+                pub struct #type_name #generics(#field_type);
 
                 #implementation
+                #impl_metadata
                 #implement_traits
+                #impl_secret
+                #impl_json_schema
+                #impl_normalized_traits
+                #impl_hash_with
+                #impl_nohash
+                #assert_not_clone
+                #impl_ffi
+                #impl_repr_transparent
+                #impl_bytes
+                #impl_wasm
+                #impl_validate_all
+                #impl_try_from_refined
+                #maybe_parse_money
+                #maybe_as_variants
 
                 #[cfg(test)]
                 mod tests {
                     use super::*;
                     #tests
+                    #tests_examples
                 }
             }
             #reimports
+            #maybe_deprecated_aliases
+            #inventory_submit
         ))
     }
 
@@ -475,6 +1496,95 @@ pub trait GenerateNewtype {
     ) -> TokenStream;
 }
 
+/// Report a rejected value, so it's possible to monitor which constraints reject real traffic:
+/// emit a `tracing` event and/or increment a `metrics` counter, depending on the enabled features,
+/// and call the callback of `on_invalid = ...` with the error and the rejected `value`.
+fn gen_report_validation_failure(
+    type_name: &TypeName,
+    maybe_on_invalid: Option<&OnInvalid>,
+    value: TokenStream,
+) -> TokenStream {
+    let trace_event = gen_trace_validation_failure(type_name);
+    let increment_counter = gen_count_validation_failure(type_name);
+    let call_on_invalid = maybe_on_invalid.map(|_| quote!(Self::__on_invalid__(err, &#value);));
+    if trace_event.is_empty() && increment_counter.is_empty() && call_on_invalid.is_none() {
+        quote!()
+    } else {
+        quote! {
+            .inspect_err(|err| {
+                #trace_event
+                #increment_counter
+                #call_on_invalid
+            })
+        }
+    }
+}
+
+#[allow(unused_variables)]
+fn gen_trace_validation_failure(type_name: &TypeName) -> TokenStream {
+    cfg_if! {
+        if #[cfg(feature = "tracing")] {
+            quote! {
+                ::nutype::__private::tracing::event!(
+                    target: "nutype",
+                    ::nutype::__private::tracing::Level::DEBUG,
+                    type_name = stringify!(#type_name),
+                    rule = Self::__validation_failure_code__(err),
+                    "validation failed"
+                );
+            }
+        } else {
+            quote!()
+        }
+    }
+}
+
+#[allow(unused_variables)]
+fn gen_count_validation_failure(type_name: &TypeName) -> TokenStream {
+    cfg_if! {
+        if #[cfg(feature = "metrics")] {
+            quote! {
+                ::nutype::__private::metrics::counter!(
+                    "nutype_validation_failures_total",
+                    "type" => stringify!(#type_name),
+                    "rule" => Self::__validation_failure_code__(err)
+                )
+                .increment(1);
+            }
+        } else {
+            quote!()
+        }
+    }
+}
+
+/// Generate `__validation_failure_code__()`, that names the violated rule in the `rule` field
+/// of the `tracing` event and the `rule` label of the `metrics` counter. It must not carry
+/// the rejected value (which may be a secret) and must have a small set of values, so it's
+/// the `code()` of the generated error, or `"with"` for a custom error type.
+#[allow(unused_variables)]
+fn gen_fn_validation_failure_code(
+    error_type_path: &ErrorTypePath,
+    is_generated_error: bool,
+) -> TokenStream {
+    cfg_if! {
+        if #[cfg(any(feature = "tracing", feature = "metrics"))] {
+            let code = if is_generated_error {
+                quote!(err.code())
+            } else {
+                quote!("with")
+            };
+            quote! {
+                #[allow(unused_variables)]
+                fn __validation_failure_code__(err: &#error_type_path) -> &'static str {
+                    #code
+                }
+            }
+        } else {
+            quote!()
+        }
+    }
+}
+
 fn gen_fn_validate_custom<InnerType: ToTokens>(
     inner_type: &InnerType,
     with: &CustomFunction,
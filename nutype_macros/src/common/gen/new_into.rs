@@ -0,0 +1,109 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{gen_report_validation_failure, strip_trait_bounds_on_generics},
+    models::{ErrorTypePath, OnInvalid, TypeName},
+};
+
+/// Generate `try_new_into()` (or `new_into()` for types without validation), that builds the
+/// value in the allocation of an existing buffer, so it can be reused across constructions.
+///
+/// The buffer is cleared and filled with the raw value. On success its allocation is moved into
+/// the value and the buffer is left empty. A rejected value is returned into the buffer.
+#[allow(clippy::too_many_arguments)]
+pub fn gen_new_into(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    raw_type: TokenStream,
+    fill_buffer: TokenStream,
+    maybe_where_clause: Option<TokenStream>,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    maybe_on_invalid: Option<&OnInvalid>,
+    constructor_vis: &TokenStream,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let take_buffer = quote! {
+        let mut value: #inner_type = ::core::mem::take(buffer);
+        value.clear();
+        #fill_buffer
+    };
+
+    let constructor = match maybe_error_type_path {
+        Some(error_type_path) => {
+            let report_validation_failure =
+                gen_report_validation_failure(type_name, maybe_on_invalid, quote!(sanitized_value));
+            quote! {
+                /// Same as `try_new()`, but reuses the allocation of `buffer`.
+                /// On success the allocation is moved into the value and `buffer` is left empty,
+                /// otherwise `buffer` gets it back.
+                #constructor_vis fn try_new_into(raw: #raw_type, buffer: &mut #inner_type) -> ::core::result::Result<Self, #error_type_path>
+                #maybe_where_clause
+                {
+                    #take_buffer
+                    let sanitized_value: #inner_type = Self::__sanitize__(value);
+                    match Self::__validate__(&sanitized_value)#report_validation_failure {
+                        Ok(()) => Ok(#type_name(sanitized_value)),
+                        Err(err) => {
+                            *buffer = sanitized_value;
+                            Err(err)
+                        }
+                    }
+                }
+            }
+        }
+        None => quote! {
+            /// Same as `new()`, but reuses the allocation of `buffer`, which is left empty.
+            #constructor_vis fn new_into(raw: #raw_type, buffer: &mut #inner_type) -> Self
+            #maybe_where_clause
+            {
+                #take_buffer
+                #type_name(Self::__sanitize__(value))
+            }
+        },
+    };
+
+    quote! {
+        impl #generics #type_name #generics_without_bounds {
+            #constructor
+        }
+    }
+}
+
+/// Generate `try_new_in()` (or `new_in()` for types without validation) for `&'a str` based
+/// types, that copies the raw string into a `bumpalo` arena, so the value borrows from it.
+/// The string is copied before it's sanitized and validated, so a rejected value stays in the
+/// arena until it's reset.
+pub fn gen_new_in_arena(
+    type_name: &TypeName,
+    generics: &Generics,
+    lifetime: &syn::Lifetime,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    constructor_vis: &TokenStream,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let constructor = match maybe_error_type_path {
+        Some(error_type_path) => quote! {
+            /// Same as `try_new()`, but copies `raw` into the arena first.
+            #constructor_vis fn try_new_in(raw: &str, bump: &#lifetime ::bumpalo::Bump) -> ::core::result::Result<Self, #error_type_path> {
+                let value: &#lifetime str = bump.alloc_str(raw);
+                Self::try_new(value)
+            }
+        },
+        None => quote! {
+            /// Same as `new()`, but copies `raw` into the arena first.
+            #constructor_vis fn new_in(raw: &str, bump: &#lifetime ::bumpalo::Bump) -> Self {
+                let value: &#lifetime str = bump.alloc_str(raw);
+                Self::new(value)
+            }
+        },
+    };
+
+    quote! {
+        impl #generics #type_name #generics_without_bounds {
+            #constructor
+        }
+    }
+}
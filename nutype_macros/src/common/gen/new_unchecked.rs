@@ -1,21 +1,30 @@
-use crate::common::models::{NewUnchecked, TypeName};
+use crate::common::{
+    gen::{store_as::gen_encode, strip_trait_bounds_on_generics},
+    models::{NewUnchecked, StoreAs, TypeName},
+};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
+use syn::Generics;
 
 pub fn gen_new_unchecked(
     type_name: &TypeName,
+    generics: &Generics,
     inner_type: impl ToTokens,
     new_unchecked: NewUnchecked,
+    maybe_store_as: Option<&StoreAs>,
 ) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let field_value = gen_encode(quote!(inner_value), &inner_type, maybe_store_as);
+
     match new_unchecked {
         NewUnchecked::Off => quote! {},
         NewUnchecked::On => quote! {
-            impl #type_name {
+            impl #generics #type_name #generics_without_bounds {
                 /// Creates a value of type skipping the sanitization and validation
                 /// rules. Generally, you should avoid using `::new_unchecked()` without a real need.
                 /// Use `::new()` instead when it's possible.
-                pub unsafe fn new_unchecked(inner_value: #inner_type) -> #type_name {
-                    #type_name(inner_value)
+                pub unsafe fn new_unchecked(inner_value: #inner_type) -> Self {
+                    Self(#field_value)
                 }
             }
         },
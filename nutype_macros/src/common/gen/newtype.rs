@@ -0,0 +1,63 @@
+use crate::common::{
+    gen::strip_trait_bounds_on_generics,
+    models::{ErrorTypePath, TypeName},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+/// Implement `nutype::Newtype` and `nutype::TryNewtype`, so generic code can be written over
+/// all generated types.
+/// The implementations delegate to the inherent `into_inner()` and `try_new()` (or `new()`).
+///
+/// `TryNewtype` is not implemented for a custom error type: it may be private, and a private type
+/// can not be used as an associated type of a public type. Neither is it implemented with
+/// `constructor_visibility = ...`, otherwise the trait would make the constructor public again.
+pub fn gen_impl_newtype_traits(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    with_try_newtype: bool,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let impl_newtype = quote! {
+        impl #generics ::nutype::Newtype for #type_name #generics_without_bounds {
+            type Inner = #inner_type;
+
+            #[inline]
+            fn get(&self) -> &Self::Inner {
+                &self.0
+            }
+
+            #[inline]
+            fn into_inner(self) -> Self::Inner {
+                #type_name::into_inner(self)
+            }
+        }
+    };
+
+    if !with_try_newtype {
+        return impl_newtype;
+    }
+    let (error_type, try_new) = match maybe_error_type_path {
+        Some(error_type_path) => (quote!(#error_type_path), quote!(Self::try_new(inner))),
+        None => (
+            quote!(::core::convert::Infallible),
+            quote!(Ok(Self::new(inner))),
+        ),
+    };
+
+    quote! {
+        #impl_newtype
+
+        impl #generics ::nutype::TryNewtype for #type_name #generics_without_bounds {
+            type Error = #error_type;
+
+            #[inline]
+            fn try_new(inner: Self::Inner) -> ::core::result::Result<Self, Self::Error> {
+                #try_new
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{gen::strip_trait_bounds_on_generics, models::TypeName};
+
+/// Assert at compile time, that the type of `no_clone` does not implement `Clone` (and so `Copy`),
+/// even if it's implemented manually outside of `#[nutype]`.
+///
+/// If the type implements `Clone`, both impls of `AmbiguousIfClone` apply to it, so the type
+/// argument can not be inferred and the compilation fails.
+pub fn gen_assert_not_clone(type_name: &TypeName, generics: &Generics) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    quote! {
+        const _: () = {
+            trait AmbiguousIfClone<A> {
+                fn some_item() {}
+            }
+            impl<T: ?Sized> AmbiguousIfClone<()> for T {}
+            impl<T: ?Sized + ::core::clone::Clone> AmbiguousIfClone<u8> for T {}
+
+            #[allow(dead_code)]
+            fn assert_not_clone #generics () {
+                let _ = <#type_name #generics_without_bounds as AmbiguousIfClone<_>>::some_item;
+            }
+        };
+    }
+}
@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{store_as::gen_decode_of, strip_trait_bounds_on_generics, type_custom_closure},
+    models::{DeriveTrait, NormalizeForEq, StoreAs, TypeName},
+};
+
+/// Implement the comparison traits listed in `normalize_for_eq` with the normalized values,
+/// e.g. two emails are equal if they are equal in lower case. The original value is kept as it is.
+pub fn gen_impl_normalized_traits(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    maybe_store_as: Option<&StoreAs>,
+    normalize_for_eq: &NormalizeForEq,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let NormalizeForEq { with, traits } = normalize_for_eq;
+    let normalize = type_custom_closure(&with.to_token_stream(), quote!(&#inner_type));
+    let inner_value = gen_decode_of(quote!(self), maybe_store_as);
+    let other_inner_value = gen_decode_of(quote!(other), maybe_store_as);
+    let normalized = quote!((#normalize)(&#inner_value));
+    let other_normalized = quote!((#normalize)(&#other_inner_value));
+
+    traits
+        .iter()
+        .map(|derive_trait| match derive_trait {
+            DeriveTrait::PartialEq => quote! {
+                impl #generics ::core::cmp::PartialEq for #type_name #generics_without_bounds {
+                    fn eq(&self, other: &Self) -> bool {
+                        #normalized == #other_normalized
+                    }
+                }
+            },
+            DeriveTrait::Eq => quote! {
+                impl #generics ::core::cmp::Eq for #type_name #generics_without_bounds {}
+            },
+            DeriveTrait::Hash => quote! {
+                impl #generics ::core::hash::Hash for #type_name #generics_without_bounds {
+                    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                        ::core::hash::Hash::hash(&#normalized, state)
+                    }
+                }
+            },
+            DeriveTrait::PartialOrd => quote! {
+                impl #generics ::core::cmp::PartialOrd for #type_name #generics_without_bounds {
+                    fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                        ::core::cmp::PartialOrd::partial_cmp(&#normalized, &#other_normalized)
+                    }
+                }
+            },
+            DeriveTrait::Ord => quote! {
+                impl #generics ::core::cmp::Ord for #type_name #generics_without_bounds {
+                    fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                        ::core::cmp::Ord::cmp(&#normalized, &#other_normalized)
+                    }
+                }
+            },
+            _ => quote!(),
+        })
+        .collect()
+}
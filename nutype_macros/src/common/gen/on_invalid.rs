@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{ErrorTypePath, OnInvalid};
+
+/// Generate `__on_invalid__()`, that passes the error of a rejected value to the callback of
+/// `on_invalid = ...`. The callback is `fn(&Error, &Value)`, or `fn(&Error)` for `secret` types,
+/// so the rejected value does not leak into logs.
+pub fn gen_fn_on_invalid(
+    on_invalid: &OnInvalid,
+    error_type_path: &ErrorTypePath,
+    value_type: impl ToTokens,
+) -> TokenStream {
+    let OnInvalid {
+        callback,
+        with_value,
+    } = on_invalid;
+    let call_callback = if *with_value {
+        quote! {
+            let on_invalid: fn(&#error_type_path, &#value_type) = #callback;
+            on_invalid(err, value);
+        }
+    } else {
+        quote! {
+            let on_invalid: fn(&#error_type_path) = #callback;
+            on_invalid(err);
+        }
+    };
+    quote! {
+        #[allow(unused_variables)]
+        fn __on_invalid__(err: &#error_type_path, value: &#value_type) {
+            #call_callback
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::{
+            error::{gen_error_type_name, ExtraVariants},
+            gen_fn_field, gen_fn_validate_custom, gen_fn_validation_failure_code,
+            gen_module_name_for_type, gen_reimports, gen_report_validation_failure,
+            inventory::gen_inventory_submit,
+            metadata::gen_impl_metadata,
+            new_unchecked::gen_new_unchecked,
+            newtype::gen_impl_newtype_traits,
+            no_clone::gen_assert_not_clone,
+            on_invalid::gen_fn_on_invalid,
+            renamed_from::gen_deprecated_aliases,
+            secret::{gen_impl_redacted_traits, gen_impl_zeroize_on_drop},
+            strip_trait_bounds_on_generics,
+            traits::{
+                gen_impl_trait_default, gen_impl_trait_from, gen_impl_trait_into,
+                gen_impl_trait_serde_deserialize, gen_impl_trait_serde_serialize,
+                gen_impl_trait_try_from,
+            },
+            try_with::{gen_wrap_try_with_error, try_with_error_type},
+            GenerateNewtype,
+        },
+        models::{
+            DeriveOptions, DeriveTrait, ErrorMessages, ErrorTypePath, GenerateParams, Guard,
+            InnerType, Rules, TypeName, Validation,
+        },
+    },
+};
+
+/// Generate a newtype around `Option<T>`.
+///
+/// The sanitizers and validators of `T` are generated by the inner type and applied only to
+/// the `Some` value. `None` is always valid, unless it's rejected with `required`.
+#[allow(clippy::type_complexity)]
+pub fn gen_optional_nutype<G: GenerateNewtype>(
+    params: GenerateParams<G::InnerType, DeriveTrait, Guard<G::Sanitizer, G::Validator>>,
+    optional_type: &AnyInnerType,
+    is_required: bool,
+) -> Result<TokenStream, syn::Error> {
+    let GenerateParams {
+        doc_attrs,
+        traits,
+        vis,
+        type_name,
+        generics,
+        guard,
+        new_unchecked,
+        maybe_default_value,
+        inner_type,
+        derive_options,
+        trace,
+        secret,
+        ..
+    } = params;
+
+    let rules = Rules::of_guard(&guard);
+    let inventory_submit = gen_inventory_submit(&type_name, optional_type, &rules);
+    let impl_metadata = gen_impl_metadata(&type_name, &generics, optional_type, &rules);
+    let module_name = gen_module_name_for_type(&type_name);
+    let generics_without_bounds = strip_trait_bounds_on_generics(&generics);
+    let fn_sanitize = G::gen_fn_sanitize(&inner_type, guard.sanitizers(), trace, false);
+
+    // `None` rejected with `required` is reported with the validation error, so the error type
+    // is needed even if there are no validators.
+    let (maybe_error_type_path, maybe_generated_error, fn_validate, bound_consts) = match &guard {
+        Guard::WithValidation { validation, .. } => match validation {
+            Validation::Standard {
+                validators,
+                error_type_path,
+                messages,
+                ..
+            } => (
+                Some(error_type_path.clone()),
+                Some(G::gen_validation_error_type(
+                    &type_name,
+                    &inner_type,
+                    error_type_path,
+                    validators,
+                    ExtraVariants {
+                        required: is_required,
+                        try_with: try_with_error_type(guard.sanitizers()),
+                        ..ExtraVariants::default()
+                    },
+                    messages,
+                )),
+                G::gen_fn_validate(&inner_type, error_type_path, validators, trace),
+                G::gen_bound_consts(&inner_type, validators),
+            ),
+            Validation::Custom {
+                with,
+                error_type_path,
+            } => (
+                Some(error_type_path.clone()),
+                None,
+                gen_fn_validate_custom(&inner_type, with, error_type_path),
+                quote!(),
+            ),
+        },
+        Guard::WithoutValidation { .. } if is_required => {
+            let error_type_path = gen_error_type_name(&type_name);
+            let extra_variants = ExtraVariants {
+                required: true,
+                ..ExtraVariants::default()
+            };
+            let error = G::gen_validation_error_type(
+                &type_name,
+                &inner_type,
+                &error_type_path,
+                &[],
+                extra_variants,
+                &ErrorMessages::default(),
+            );
+            (Some(error_type_path), Some(error), quote!(), quote!())
+        }
+        Guard::WithoutValidation { .. } => (None, None, quote!(), quote!()),
+    };
+
+    let constructor = match maybe_error_type_path {
+        Some(ref error_type_path) => {
+            let validate_some = if guard.has_validation() {
+                quote!(Self::__validate__(value))
+            } else {
+                quote!(Ok(()))
+            };
+            let validate_none = if is_required {
+                quote!(Err(#error_type_path::RequiredViolated))
+            } else {
+                quote!(Ok(()))
+            };
+            let report_validation_failure = gen_report_validation_failure(
+                &type_name,
+                derive_options.on_invalid.as_ref(),
+                quote!(sanitized_value),
+            );
+            let sanitize_raw_value = match &guard {
+                Guard::WithValidation { validation, .. }
+                    if try_with_error_type(guard.sanitizers()).is_some() =>
+                {
+                    let wrap_error = gen_wrap_try_with_error(validation);
+                    quote!(raw_value.map(Self::__sanitize__).transpose().map_err(|err| #wrap_error)?)
+                }
+                _ => quote!(raw_value.map(Self::__sanitize__)),
+            };
+            let fn_on_invalid = derive_options.on_invalid.as_ref().map(|on_invalid| {
+                gen_fn_on_invalid(
+                    on_invalid,
+                    error_type_path,
+                    quote!(::core::option::Option<#inner_type>),
+                )
+            });
+            let fn_validation_failure_code =
+                gen_fn_validation_failure_code(error_type_path, maybe_generated_error.is_some());
+            quote! {
+                #[inline]
+                pub fn try_new(raw_value: ::core::option::Option<#inner_type>) -> ::core::result::Result<Self, #error_type_path> {
+                    let sanitized_value: ::core::option::Option<#inner_type> = #sanitize_raw_value;
+                    let validation_result: ::core::result::Result<(), #error_type_path> = match sanitized_value {
+                        Some(ref value) => #validate_some,
+                        None => #validate_none,
+                    };
+                    validation_result #report_validation_failure?;
+                    Ok(#type_name(sanitized_value))
+                }
+
+                #fn_on_invalid
+                #fn_validation_failure_code
+            }
+        }
+        None => quote! {
+            #[inline]
+            pub fn new(raw_value: ::core::option::Option<#inner_type>) -> Self {
+                Self(raw_value.map(Self::__sanitize__))
+            }
+        },
+    };
+
+    let impl_newtype_traits = gen_impl_newtype_traits(
+        &type_name,
+        &generics,
+        optional_type,
+        maybe_error_type_path.as_ref(),
+        maybe_generated_error.is_some(),
+    );
+    let impl_new_unchecked =
+        gen_new_unchecked(&type_name, &generics, optional_type, new_unchecked, None);
+
+    let redacts_debug = secret.is_some() || derive_options.profile.redacts_debug();
+    let derive_transparent_traits = traits.iter().filter_map(|derive_trait| match derive_trait {
+        DeriveTrait::Debug if !redacts_debug => Some(quote!(Debug)),
+        DeriveTrait::Clone => Some(quote!(Clone)),
+        DeriveTrait::Copy => Some(quote!(Copy)),
+        DeriveTrait::PartialEq => Some(quote!(PartialEq)),
+        DeriveTrait::Eq => Some(quote!(Eq)),
+        DeriveTrait::PartialOrd => Some(quote!(PartialOrd)),
+        DeriveTrait::Ord => Some(quote!(Ord)),
+        DeriveTrait::Hash => Some(quote!(Hash)),
+        _ => None,
+    });
+    let impl_secret = redacts_debug.then(|| {
+        let debug = traits.contains(&DeriveTrait::Debug);
+        let impl_redacted_traits = gen_impl_redacted_traits(&type_name, &generics, debug, false);
+        let impl_zeroize_on_drop = secret
+            .is_some_and(|secret| secret.zeroize)
+            .then(|| gen_impl_zeroize_on_drop(&type_name, &generics));
+        quote! {
+            #impl_redacted_traits
+            #impl_zeroize_on_drop
+        }
+    });
+    // The value of a `secret(zeroize)` type is wiped on drop, so it can not be moved out of the field.
+    let fn_into_inner = if secret.is_some_and(|secret| secret.zeroize) {
+        quote! {
+            #[inline]
+            pub fn into_inner(mut self) -> #optional_type {
+                ::core::mem::take(&mut self.0)
+            }
+        }
+    } else {
+        quote! {
+            #[inline]
+            pub fn into_inner(self) -> #optional_type {
+                self.0
+            }
+        }
+    };
+    let fn_field = derive_options
+        .method_names
+        .field
+        .as_ref()
+        .map(|field| gen_fn_field(field, optional_type));
+    let implement_traits = gen_implemented_traits(
+        &traits,
+        &type_name,
+        &generics,
+        optional_type,
+        maybe_default_value,
+        &derive_options,
+        maybe_error_type_path.as_ref(),
+    );
+
+    let maybe_deprecated_aliases = derive_options.renamed_from.as_ref().map(|renamed_from| {
+        let companion_names: Vec<String> = maybe_error_type_path
+            .iter()
+            .filter(|_| maybe_generated_error.is_some())
+            .map(ToString::to_string)
+            .collect();
+        gen_deprecated_aliases(&vis, &type_name, &generics, renamed_from, &companion_names)
+    });
+    let assert_not_clone = derive_options
+        .no_clone
+        .map(|_| gen_assert_not_clone(&type_name, &generics));
+    let reimports = gen_reimports(
+        vis,
+        &type_name,
+        &module_name,
+        // A custom error type is provided by the user, so it's not reimported.
+        maybe_error_type_path
+            .as_ref()
+            .filter(|_| maybe_generated_error.is_some()),
+        None,
+        None,
+        &[],
+    );
+
+    Ok(quote!(
+        #[doc(hidden)]
+        #[allow(non_snake_case, reason = "we keep original structure name which is probably CamelCase")]
+        mod #module_name {
+            use super::*;
+
+            #(#doc_attrs)*
+            #[derive(#(#derive_transparent_traits,)*)]
+            pub struct #type_name #generics(#optional_type);
+
+            #maybe_generated_error
+
+            impl #generics #type_name #generics_without_bounds {
+                #bound_consts
+
+                #constructor
+
+                // Definite associated private functions __sanitize__() and __validate__() with underscores so they do not overlap with outer
+                // scope imported with `use super::*`.
+                #fn_sanitize
+                #fn_validate
+
+                #fn_into_inner
+                #fn_field
+
+                /// Returns a reference to the `Some` value.
+                #[inline]
+                pub fn as_inner(&self) -> ::core::option::Option<&#inner_type> {
+                    self.0.as_ref()
+                }
+            }
+
+            #impl_metadata
+            #impl_new_unchecked
+            #impl_newtype_traits
+            #implement_traits
+            #impl_secret
+            #assert_not_clone
+        }
+        #reimports
+        #maybe_deprecated_aliases
+        #inventory_submit
+    ))
+}
+
+fn gen_implemented_traits(
+    traits: &HashSet<DeriveTrait>,
+    type_name: &TypeName,
+    generics: &Generics,
+    optional_type: &AnyInnerType,
+    maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let has_validation = maybe_error_type_path.is_some();
+    traits
+        .iter()
+        .map(|derive_trait| match derive_trait {
+            DeriveTrait::From => gen_impl_trait_from(type_name, generics, optional_type),
+            DeriveTrait::Into => {
+                gen_impl_trait_into(type_name, generics, InnerType::Any(optional_type.clone()))
+            }
+            DeriveTrait::TryFrom => {
+                gen_impl_trait_try_from(type_name, generics, optional_type, maybe_error_type_path)
+            }
+            DeriveTrait::Default => match maybe_default_value {
+                Some(ref default_value) => {
+                    gen_impl_trait_default(type_name, generics, default_value, has_validation)
+                }
+                // The parser requires `default = ` to be set along with `Default`.
+                None => quote!(),
+            },
+            DeriveTrait::SerdeSerialize => {
+                gen_impl_trait_serde_serialize(type_name, generics, &derive_options.serde, None)
+            }
+            DeriveTrait::SerdeDeserialize => gen_impl_trait_serde_deserialize(
+                type_name,
+                generics,
+                InnerType::Any(optional_type.clone()),
+                maybe_error_type_path,
+                &derive_options.serde,
+                None,
+                derive_options.profile.denies_unknown_fields(),
+            ),
+            _ => quote!(),
+        })
+        .collect()
+}
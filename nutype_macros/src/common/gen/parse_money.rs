@@ -0,0 +1,237 @@
+use cfg_if::cfg_if;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Generics};
+
+use crate::common::{
+    gen::{
+        canonicalize_number::gen_canonicalize_number, gen_string_path,
+        strip_trait_bounds_on_generics,
+    },
+    models::{ErrorTypePath, ParseErrorTypeName, ParseMoney, TypeName},
+};
+
+/// Generate a name for the error which is returned by `::parse_money()`.
+pub fn gen_parse_money_error_name(type_name: &TypeName) -> ParseErrorTypeName {
+    let ident = format_ident!("{type_name}ParseMoneyError");
+    ParseErrorTypeName::new(ident)
+}
+
+/// Generate `::parse_money()` constructor and its error type.
+///
+/// The constructor removes currency symbols or codes around the amount, detects the sign
+/// (a single `-` in front of the amount or accounting style parentheses around it), canonicalizes thousands and decimal separators,
+/// checks the scale (if configured) and parses the amount with `FromStr` of the inner type.
+/// Finally the amount goes through the regular sanitization and validation.
+///
+/// Unless `decimal_separator` is set, an amount with a single `.` or `,` followed by exactly
+/// three digits (e.g. `"1,234"`) is rejected as ambiguous.
+pub fn gen_parse_money(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    parse_money: &ParseMoney,
+    constructor_vis: &TokenStream,
+) -> TokenStream {
+    let error_type_name = gen_parse_money_error_name(type_name);
+    let def_error = gen_def_parse_money_error(type_name, &error_type_name, maybe_error_type_path);
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    // Once `decimal_separator` is resolved, `.` is the only possible decimal separator.
+    let canonicalize_number = gen_canonicalize_number(parse_money.decimal_separator.map(|_| '.'));
+    let string = gen_string_path();
+
+    let resolve_separators = match parse_money.decimal_separator {
+        Some(decimal_separator) => {
+            let grouping_separator = if decimal_separator == '.' { ',' } else { '.' };
+            quote!(
+                if amount.matches(#decimal_separator).count() > 1 {
+                    return Err(#error_type_name::InvalidAmount);
+                }
+                let value: #string = amount
+                    .chars()
+                    .filter(|&c| c != #grouping_separator)
+                    .map(|c| if c == #decimal_separator { '.' } else { c })
+                    .collect();
+            )
+        }
+        None => quote!(
+            // A single separator followed by exactly three digits can be read both ways:
+            // `"1,234"` is either 1234 or 1.234, so we refuse to guess.
+            let mut separators = amount.match_indices(|c: char| c == '.' || c == ',');
+            if let (Some((position, _)), None) = (separators.next(), separators.next()) {
+                let integer_digits = amount[..position].chars().filter(|c| c.is_ascii_digit());
+                let fraction_digits = amount[position + 1..].chars().filter(|c| c.is_ascii_digit());
+                let integer_len = integer_digits.clone().count();
+                let starts_with_zero = integer_digits.clone().next() == Some('0');
+                if fraction_digits.count() == 3 && (1..=3).contains(&integer_len) && !starts_with_zero {
+                    return Err(#error_type_name::AmbiguousSeparator);
+                }
+            }
+            let value: #string = #string::from(amount);
+        ),
+    };
+
+    let check_scale = match parse_money.scale {
+        Some(scale) => {
+            let scale = scale as usize;
+            quote!(
+                let scale = value.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+                if scale > #scale {
+                    return Err(#error_type_name::ScaleViolated);
+                }
+            )
+        }
+        None => quote!(),
+    };
+
+    let construct = if maybe_error_type_path.is_some() {
+        quote!(Self::try_new(amount).map_err(#error_type_name::Validate))
+    } else {
+        quote!(Ok(Self::new(amount)))
+    };
+
+    let mut generics_with_from_str = generics.clone();
+    generics_with_from_str
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#inner_type: ::core::str::FromStr));
+    let where_clause = &generics_with_from_str.where_clause;
+
+    quote! {
+        #def_error
+
+        impl #generics #type_name #generics_without_bounds
+        #where_clause
+        {
+            /// Parses a human formatted amount of money, e.g. `"$1,234.50"`, `"-1 234,50 EUR"`
+            /// or `"(12.00)"`.
+            #constructor_vis fn parse_money(input: &str) -> ::core::result::Result<Self, #error_type_name> {
+                let input = input.trim();
+                let (Some(start), Some(end)) = (
+                    input.find(|c: char| c.is_ascii_digit() || c == '.' || c == ','),
+                    input.rfind(|c: char| c.is_ascii_digit()),
+                ) else {
+                    return Err(#error_type_name::InvalidAmount);
+                };
+                if start > end {
+                    return Err(#error_type_name::InvalidAmount);
+                }
+
+                // Everything around the amount is considered to be a currency symbol or code.
+                let (prefix, amount, suffix) = (&input[..start], &input[start..=end], &input[end + 1..]);
+                let is_amount_char = |c: char| {
+                    c.is_ascii_digit() || c.is_whitespace() || matches!(c, '.' | ',' | '_' | '\'')
+                };
+                if !amount.chars().all(is_amount_char) {
+                    return Err(#error_type_name::InvalidAmount);
+                }
+                // The amount is negative with a single `-` in front of it (`"-$5"` or `"$-5"`)
+                // or within a pair of parentheses around the whole input (`"($5)"`).
+                let count_in_affixes = |pattern: fn(char) -> bool| {
+                    prefix.matches(pattern).count() + suffix.matches(pattern).count()
+                };
+                let minuses = count_in_affixes(|c| c == '-');
+                let parentheses = count_in_affixes(|c| c == '(' || c == ')');
+                let is_negative = if minuses == 0 && parentheses == 0 {
+                    false
+                } else if minuses == 1
+                    && parentheses == 0
+                    && (prefix.starts_with('-') || prefix.trim_end().ends_with('-'))
+                {
+                    true
+                } else if minuses == 0
+                    && parentheses == 2
+                    && prefix.starts_with('(')
+                    && suffix.ends_with(')')
+                {
+                    true
+                } else {
+                    return Err(#error_type_name::InvalidAmount);
+                };
+
+                #resolve_separators
+                #canonicalize_number
+                #check_scale
+
+                let value = if is_negative {
+                    let mut negative = #string::with_capacity(value.len() + 1);
+                    negative.push('-');
+                    negative.push_str(&value);
+                    negative
+                } else {
+                    value
+                };
+                let amount: #inner_type = value
+                    .parse()
+                    .map_err(|_| #error_type_name::InvalidAmount)?;
+                #construct
+            }
+        }
+    }
+}
+
+fn gen_def_parse_money_error(
+    type_name: &TypeName,
+    error_type_name: &ParseErrorTypeName,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let type_name_str = type_name.to_string();
+
+    let (validate_variant, display_validate) = match maybe_error_type_path {
+        Some(error_type_path) => (
+            quote!(Validate(#error_type_path),),
+            quote!(#error_type_name::Validate(err) => write!(f, "Failed to parse {}: {}", #type_name_str, err),),
+        ),
+        None => (quote!(), quote!()),
+    };
+
+    cfg_if! {
+        if #[cfg(any(ERROR_IN_CORE, feature = "std"))] {
+            cfg_if! {
+                if #[cfg(ERROR_IN_CORE)] {
+                    let error = quote! { ::core::error::Error };
+                } else {
+                    let error = quote! { ::std::error::Error };
+                }
+            };
+            let impl_error = quote! {
+                impl #error for #error_type_name {
+                    fn source(&self) -> Option<&(dyn #error + 'static)> {
+                        None
+                    }
+                }
+            };
+        } else {
+            // NOTE: `::core::error::Error` is stable only for rust >= 1.81.0.
+            let impl_error = quote! {};
+        }
+    };
+
+    quote! {
+        #[derive(Debug)]
+        pub enum #error_type_name {
+            /// The input does not contain a valid amount.
+            InvalidAmount,
+            /// The amount has more digits after the decimal separator than allowed by `scale`.
+            ScaleViolated,
+            /// It's unclear whether the only separator is a decimal or a thousands one (e.g. `"1,234"`).
+            /// Set `decimal_separator` to make it explicit.
+            AmbiguousSeparator,
+            #validate_variant
+        }
+
+        impl ::core::fmt::Display for #error_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #error_type_name::InvalidAmount => write!(f, "Failed to parse {}: invalid amount", #type_name_str),
+                    #error_type_name::ScaleViolated => write!(f, "Failed to parse {}: too many digits after the decimal separator", #type_name_str),
+                    #error_type_name::AmbiguousSeparator => write!(f, "Failed to parse {}: ambiguous decimal separator", #type_name_str),
+                    #display_validate
+                }
+            }
+        }
+
+        #impl_error
+    }
+}
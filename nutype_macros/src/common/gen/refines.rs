@@ -0,0 +1,53 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{gen_report_validation_failure, store_as::gen_encode, strip_trait_bounds_on_generics},
+    models::{ErrorTypePath, OnInvalid, StoreAs, TypeName},
+};
+
+/// Implement conversion of the refined newtype (`refines = Username`) into this one.
+///
+/// The inner value of the refined newtype is already sanitized and validated by its own rules,
+/// so only the validators of this newtype are applied. Without validation it's `From`.
+pub fn gen_impl_trait_try_from_refined(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    base_type: &syn::Type,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    maybe_store_as: Option<&StoreAs>,
+    maybe_on_invalid: Option<&OnInvalid>,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let field_value = gen_encode(quote!(inner_value), inner_type, maybe_store_as);
+
+    match maybe_error_type_path {
+        Some(error_type_path) => {
+            let report_validation_failure =
+                gen_report_validation_failure(type_name, maybe_on_invalid, quote!(inner_value));
+            quote! {
+                impl #generics ::core::convert::TryFrom<#base_type> for #type_name #generics_without_bounds {
+                    type Error = #error_type_path;
+
+                    #[inline]
+                    fn try_from(base: #base_type) -> ::core::result::Result<Self, Self::Error> {
+                        let inner_value: #inner_type = base.into_inner();
+                        Self::__validate__(&inner_value)#report_validation_failure?;
+                        Ok(#type_name(#field_value))
+                    }
+                }
+            }
+        }
+        None => quote! {
+            impl #generics ::core::convert::From<#base_type> for #type_name #generics_without_bounds {
+                #[inline]
+                fn from(base: #base_type) -> Self {
+                    let inner_value: #inner_type = base.into_inner();
+                    #type_name(#field_value)
+                }
+            }
+        },
+    }
+}
@@ -0,0 +1,55 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Visibility};
+
+use crate::common::{
+    gen::strip_trait_bounds_on_generics,
+    models::{RenamedFrom, TypeName},
+};
+
+/// Keep the previous names of a renamed type and its companion types (e.g. `UsernameError`) as
+/// deprecated aliases, so the code that uses them keeps compiling with a warning.
+/// Only the companion types, that are named after the type, are aliased.
+pub fn gen_deprecated_aliases(
+    vis: &Visibility,
+    type_name: &TypeName,
+    generics: &Generics,
+    renamed_from: &RenamedFrom,
+    companion_type_names: &[String],
+) -> TokenStream {
+    let RenamedFrom {
+        name: old_name,
+        since,
+    } = renamed_from;
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let deprecated = |new_name: &dyn ToString| {
+        let note = format!("Renamed to `{}`", new_name.to_string());
+        match since {
+            Some(since) => quote!(#[deprecated(since = #since, note = #note)]),
+            None => quote!(#[deprecated(note = #note)]),
+        }
+    };
+
+    let type_deprecated = deprecated(type_name);
+    let type_alias = quote! {
+        #type_deprecated
+        #vis type #old_name #generics_without_bounds = #type_name #generics_without_bounds;
+    };
+
+    let type_name_str = type_name.to_string();
+    let companion_aliases = companion_type_names.iter().filter_map(|companion_name| {
+        let suffix = companion_name.strip_prefix(&type_name_str)?;
+        let old_companion_name = format_ident!("{old_name}{suffix}");
+        let companion_name = format_ident!("{companion_name}");
+        let companion_deprecated = deprecated(&companion_name);
+        Some(quote! {
+            #companion_deprecated
+            #vis type #old_companion_name = #companion_name;
+        })
+    });
+
+    quote! {
+        #type_alias
+        #(#companion_aliases)*
+    }
+}
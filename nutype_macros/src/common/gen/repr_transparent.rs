@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::strip_trait_bounds_on_generics,
+    models::{NewUnchecked, TypeName},
+};
+
+/// Implement the zero-copy slice views of the `repr_transparent` attribute.
+///
+/// Every value is a valid inner value, so a slice of values can always be viewed as a slice of
+/// the inner type. The other way around the rules are skipped, so it's available only together
+/// with `new_unchecked` and is as unsafe as `::new_unchecked()`.
+pub fn gen_impl_repr_transparent(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &impl ToTokens,
+    new_unchecked: NewUnchecked,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let maybe_from_inner_slice_unchecked = match new_unchecked {
+        NewUnchecked::Off => quote!(),
+        NewUnchecked::On => quote! {
+            /// Views a slice of inner values as a slice of values without copying, skipping
+            /// the sanitization and validation rules.
+            ///
+            /// # Safety
+            ///
+            /// The same as for `::new_unchecked()`: every element must already be sanitized
+            /// and valid.
+            pub unsafe fn from_inner_slice_unchecked(values: &[#inner_type]) -> &[Self] {
+                // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                unsafe {
+                    ::core::slice::from_raw_parts(values.as_ptr().cast::<Self>(), values.len())
+                }
+            }
+        },
+    };
+
+    quote! {
+        impl #generics #type_name #generics_without_bounds {
+            /// Views a slice of values as a slice of inner values without copying.
+            pub fn as_inner_slice(values: &[Self]) -> &[#inner_type] {
+                // SAFETY: `Self` is `#[repr(transparent)]` over the inner type.
+                unsafe {
+                    ::core::slice::from_raw_parts(values.as_ptr().cast::<#inner_type>(), values.len())
+                }
+            }
+
+            #maybe_from_inner_slice_unchecked
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::Generics;
+
+use crate::common::models::{ErrorTypePath, InnerType, TypeName};
+
+/// Name of the archived type generated with `derive(Archive)`, e.g. `ArchivedEmail`.
+pub fn gen_archived_type_name(type_name: &TypeName) -> Ident {
+    format_ident!("Archived{type_name}")
+}
+
+/// Implement rkyv's `Archive`, `Serialize` and `Deserialize`, requested with `derive(Archive)`.
+///
+/// The archived type wraps the archived inner value, so it has the same layout and is checked
+/// the same way. `Deserialize` passes the deserialized inner value through the constructor, so
+/// a value rejected by the validation is reported with `rancor::Source`.
+pub fn gen_impl_trait_rkyv(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl Into<InnerType>,
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> Result<TokenStream, syn::Error> {
+    if !generics.params.is_empty() {
+        let msg = "#[nutype] cannot derive `Archive` for a generic type.\nImplement `Archive`, `Serialize` and `Deserialize` on your own.";
+        return Err(syn::Error::new(Span::call_site(), msg));
+    }
+    let inner_type: InnerType = inner_type.into();
+    let archived_type_name = gen_archived_type_name(type_name);
+    let doc = format!("An archived [`{type_name}`].");
+
+    let raw_value_to_result = if maybe_error_type_name.is_some() {
+        quote! {
+            #type_name::try_new(raw_value).map_err(<DE::Error as ::rkyv::rancor::Source>::new)
+        }
+    } else {
+        quote!(Ok(#type_name::new(raw_value)))
+    };
+    let maybe_source_bound =
+        maybe_error_type_name.map(|_| quote!(DE::Error: ::rkyv::rancor::Source,));
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[repr(transparent)]
+        pub struct #archived_type_name(::rkyv::Archived<#inner_type>);
+
+        // SAFETY: the only field is an archived value, that is portable.
+        unsafe impl ::rkyv::Portable for #archived_type_name {}
+
+        // SAFETY: the type is `repr(transparent)`, so the bytes are checked as the archived inner value.
+        unsafe impl<C> ::rkyv::bytecheck::CheckBytes<C> for #archived_type_name
+        where
+            C: ::rkyv::rancor::Fallible + ?Sized,
+            ::rkyv::Archived<#inner_type>: ::rkyv::bytecheck::CheckBytes<C>,
+        {
+            unsafe fn check_bytes(value: *const Self, context: &mut C) -> ::core::result::Result<(), C::Error> {
+                <::rkyv::Archived<#inner_type> as ::rkyv::bytecheck::CheckBytes<C>>::check_bytes(value.cast(), context)
+            }
+        }
+
+        impl ::core::ops::Deref for #archived_type_name {
+            type Target = ::rkyv::Archived<#inner_type>;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::rkyv::Archive for #type_name {
+            type Archived = #archived_type_name;
+            type Resolver = ::rkyv::Resolver<#inner_type>;
+
+            fn resolve(&self, resolver: Self::Resolver, out: ::rkyv::Place<Self::Archived>) {
+                // SAFETY: the archived type is `repr(transparent)` over the archived inner value.
+                let out = unsafe { out.cast_unchecked::<::rkyv::Archived<#inner_type>>() };
+                ::rkyv::Archive::resolve(&self.0, resolver, out)
+            }
+        }
+
+        impl<S> ::rkyv::Serialize<S> for #type_name
+        where
+            S: ::rkyv::rancor::Fallible + ?Sized,
+            #inner_type: ::rkyv::Serialize<S>,
+        {
+            fn serialize(&self, serializer: &mut S) -> ::core::result::Result<Self::Resolver, S::Error> {
+                ::rkyv::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<DE> ::rkyv::Deserialize<#type_name, DE> for #archived_type_name
+        where
+            DE: ::rkyv::rancor::Fallible + ?Sized,
+            #maybe_source_bound
+            ::rkyv::Archived<#inner_type>: ::rkyv::Deserialize<#inner_type, DE>,
+        {
+            fn deserialize(&self, deserializer: &mut DE) -> ::core::result::Result<#type_name, DE::Error> {
+                let raw_value: #inner_type = ::rkyv::Deserialize::deserialize(&self.0, deserializer)?;
+                #raw_value_to_result
+            }
+        }
+    })
+}
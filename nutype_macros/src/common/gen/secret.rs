@@ -0,0 +1,51 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{gen::strip_trait_bounds_on_generics, models::TypeName};
+
+/// Implement `Debug` and `Display` of a `secret` type, that print `ApiKey(***)` instead of
+/// the value.
+pub fn gen_impl_redacted_traits(
+    type_name: &TypeName,
+    generics: &Generics,
+    debug: bool,
+    display: bool,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let redacted = format!("{type_name}(***)");
+    let impl_debug = debug.then(|| {
+        quote! {
+            impl #generics ::core::fmt::Debug for #type_name #generics_without_bounds {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#redacted)
+                }
+            }
+        }
+    });
+    let impl_display = display.then(|| {
+        quote! {
+            impl #generics ::core::fmt::Display for #type_name #generics_without_bounds {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#redacted)
+                }
+            }
+        }
+    });
+    quote! {
+        #impl_debug
+        #impl_display
+    }
+}
+
+/// Wipe the value of a `secret(zeroize)` type on drop.
+pub fn gen_impl_zeroize_on_drop(type_name: &TypeName, generics: &Generics) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    quote! {
+        impl #generics ::core::ops::Drop for #type_name #generics_without_bounds {
+            fn drop(&mut self) {
+                ::nutype::__private::zeroize::Zeroize::zeroize(&mut self.0);
+            }
+        }
+    }
+}
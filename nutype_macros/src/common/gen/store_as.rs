@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Generics;
+
+use crate::common::{
+    gen::{strip_trait_bounds_on_generics, type_custom_closure},
+    models::{StoreAs, TypeName},
+};
+
+/// Generate the type of the field that holds the value: the storage type set with `store_as(...)`
+/// or the inner type itself.
+pub fn gen_field_type(inner_type: impl ToTokens, maybe_store_as: Option<&StoreAs>) -> TokenStream {
+    match maybe_store_as {
+        Some(store_as) => store_as.storage_type.to_token_stream(),
+        None => inner_type.to_token_stream(),
+    }
+}
+
+/// Generate an expression that turns the inner value into the value of the field.
+pub fn gen_encode(
+    inner_value: TokenStream,
+    inner_type: impl ToTokens,
+    maybe_store_as: Option<&StoreAs>,
+) -> TokenStream {
+    match maybe_store_as {
+        Some(StoreAs { encode, .. }) => {
+            let encode = type_custom_closure(&encode.to_token_stream(), inner_type);
+            quote!((#encode)(#inner_value))
+        }
+        None => inner_value,
+    }
+}
+
+/// Generate an expression that obtains the inner value out of `self.0`.
+pub fn gen_decode(maybe_store_as: Option<&StoreAs>) -> TokenStream {
+    gen_decode_of(quote!(self), maybe_store_as)
+}
+
+/// Generate an expression that obtains the inner value out of `#value.0`.
+pub fn gen_decode_of(value: TokenStream, maybe_store_as: Option<&StoreAs>) -> TokenStream {
+    match maybe_store_as {
+        Some(StoreAs {
+            decode,
+            storage_type,
+            ..
+        }) => {
+            let decode = type_custom_closure(&decode.to_token_stream(), quote!(&#storage_type));
+            quote!((#decode)(&#value.0))
+        }
+        None => quote!(#value.0),
+    }
+}
+
+/// `Debug` can not be derived when the value is stored in a different representation,
+/// because it would print the stored value instead of the inner one.
+pub fn gen_impl_trait_debug_decoded(
+    type_name: &TypeName,
+    generics: &Generics,
+    store_as: &StoreAs,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let inner_value = gen_decode(Some(store_as));
+
+    quote! {
+        impl #generics ::core::fmt::Debug for #type_name #generics_without_bounds {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(stringify!(#type_name)).field(&#inner_value).finish()
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::Generics;
 
-use crate::common::models::{NumericBound, TypeName};
+use crate::common::models::{NumericBound, TestExamples, TypeName};
 
 pub fn gen_test_should_have_consistent_lower_and_upper_boundaries<Validator>(
     type_name: &TypeName,
@@ -73,3 +73,52 @@ Note: the test is generated automatically by #[nutype] macro
         }
     ))
 }
+
+/// Check the examples of `test_examples(valid = [...], invalid = [...])` against `try_new()`.
+pub fn gen_tests_examples(type_name: &TypeName, test_examples: &TestExamples) -> TokenStream {
+    let check_valid = test_examples.valid.iter().map(|example| {
+        let msg = format!(
+            "
+Example `{}` must be valid for type `{type_name}`
+Note: the test is generated automatically by #[nutype] macro
+",
+            example.to_token_stream()
+        );
+        quote!(
+            assert!(#type_name::try_new(#example).is_ok(), #msg);
+        )
+    });
+    let check_invalid = test_examples.invalid.iter().map(|example| {
+        let msg = format!(
+            "
+Example `{}` must be invalid for type `{type_name}`
+Note: the test is generated automatically by #[nutype] macro
+",
+            example.to_token_stream()
+        );
+        quote!(
+            assert!(#type_name::try_new(#example).is_err(), #msg);
+        )
+    });
+
+    let test_valid = (!test_examples.valid.is_empty()).then(|| {
+        quote!(
+            #[test]
+            fn should_accept_valid_examples() {
+                #(#check_valid)*
+            }
+        )
+    });
+    let test_invalid = (!test_examples.invalid.is_empty()).then(|| {
+        quote!(
+            #[test]
+            fn should_reject_invalid_examples() {
+                #(#check_invalid)*
+            }
+        )
+    });
+    quote!(
+        #test_valid
+        #test_invalid
+    )
+}
@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::common::models::RuleNode;
+
+/// Generate the sanitization steps of the kind specific sanitizers with `gen_step`.
+/// With `trace` every step is followed by a `tracing` event with the output of the sanitizer.
+/// With `report_changes` every step, that changed the value, is recorded in `warnings`.
+pub fn gen_sanitizations<S>(
+    sanitizers: &[RuleNode<'_, S>],
+    trace: bool,
+    report_changes: bool,
+    mut gen_step: impl FnMut(&S) -> TokenStream,
+) -> TokenStream {
+    sanitizers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, node)| node.rule.map(|sanitizer| (index, node, sanitizer)))
+        .map(|(index, node, sanitizer)| {
+            let mut step = gen_step(sanitizer);
+            let kind = &node.name;
+            if report_changes {
+                step = quote! {
+                    let before = ::core::clone::Clone::clone(&value);
+                    #step
+                    if value != before {
+                        warnings.push(::nutype::SanitizeWarning {
+                            sanitizer: #kind,
+                            index: #index,
+                        });
+                    }
+                };
+            }
+            if !trace {
+                return step;
+            }
+            quote! {
+                #step
+                ::nutype::__private::tracing::event!(
+                    target: "nutype",
+                    ::nutype::__private::tracing::Level::DEBUG,
+                    type_name = ::core::any::type_name::<Self>(),
+                    sanitizer = #kind,
+                    output = ?value,
+                    "sanitizer applied"
+                );
+            }
+        })
+        .collect()
+}
+
+/// Generate the checks of the kind specific validators with `gen_check`. With `trace` the verdict
+/// of every validator is reported with a `tracing` event.
+///
+/// A check returns the error on failure, so with `trace` it's wrapped into a closure to obtain
+/// the verdict before returning.
+pub fn gen_validations<V>(
+    validators: &[RuleNode<'_, V>],
+    trace: bool,
+    mut gen_check: impl FnMut(&V) -> TokenStream,
+) -> TokenStream {
+    validators
+        .iter()
+        .filter_map(|node| node.rule.map(|validator| (node, validator)))
+        .map(|(node, validator)| {
+            let check = gen_check(validator);
+            if !trace {
+                return check;
+            }
+            let kind = &node.name;
+            quote! {
+                #[allow(clippy::redundant_closure_call)]
+                let verdict = (|| {
+                    #check
+                    Ok(())
+                })();
+                ::nutype::__private::tracing::event!(
+                    target: "nutype",
+                    ::nutype::__private::tracing::Level::DEBUG,
+                    type_name = ::core::any::type_name::<Self>(),
+                    validator = #kind,
+                    passed = verdict.is_ok(),
+                    "validator checked"
+                );
+                verdict?;
+            }
+        })
+        .collect()
+}
@@ -5,11 +5,15 @@ use quote::{quote, ToTokens};
 use syn::Generics;
 
 use crate::common::{
-    gen::{add_bound_to_all_type_params, strip_trait_bounds_on_generics},
-    models::{ErrorTypePath, InnerType, TypeName},
+    gen::{add_bound_to_all_type_params, store_as::gen_decode, strip_trait_bounds_on_generics},
+    models::{ErrorTypePath, InnerType, SerdeMode, StoreAs, TypeName},
 };
 
-use super::parse_error::{gen_def_parse_error, gen_parse_error_name};
+use super::{
+    error::gen_try_from_error_type_name,
+    parse_error::{gen_def_parse_error, gen_parse_error_name},
+    try_from_int::{gen_try_from_error, has_narrowing_int_source, is_narrowing_int_conversion},
+};
 
 /// Generated implementation of traits.
 pub struct GeneratedTraits {
@@ -76,6 +80,28 @@ pub fn gen_impl_trait_into(
     }
 }
 
+/// Generates `From<Type> for Target` implementations for the target types listed with `into(...)`.
+/// The inner value is converted into a target type with `Into`.
+pub fn gen_impl_trait_into_types(
+    type_name: &TypeName,
+    generics: &Generics,
+    target_types: &[syn::Type],
+) -> TokenStream {
+    target_types
+        .iter()
+        .map(|target_type| {
+            quote! {
+                impl #generics ::core::convert::From<#type_name #generics> for #target_type {
+                    #[inline]
+                    fn from(value: #type_name #generics) -> Self {
+                        ::core::convert::Into::<#target_type>::into(value.into_inner())
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
 pub fn gen_impl_trait_as_ref(
     type_name: &TypeName,
     generics: &Generics,
@@ -112,23 +138,41 @@ pub fn gen_impl_trait_deref(
     }
 }
 
-pub fn gen_impl_trait_display(type_name: &TypeName, generics: &Generics) -> TokenStream {
+pub fn gen_impl_trait_display(
+    type_name: &TypeName,
+    generics: &Generics,
+    maybe_format: Option<&syn::LitStr>,
+    maybe_store_as: Option<&StoreAs>,
+) -> TokenStream {
     let generics_without_bounds = strip_trait_bounds_on_generics(generics);
     let generics_with_display_bound =
         add_bound_to_all_type_params(generics, syn::parse_quote!(::core::fmt::Display));
+    let inner_value = gen_decode(maybe_store_as);
+
+    // With a custom format string the inner value is passed as the only positional argument,
+    // e.g. `display = "Order #{}"`.
+    let body = match maybe_format {
+        Some(format) => quote! {
+            ::core::write!(f, #format, &#inner_value)
+        },
+        None => quote! {
+            // A tiny wrapper function with trait boundary that improves error reporting.
+            // It makes it clear for the end-user that the inner type has to implement Display
+            // in order to derive display for the newtype.
+            #[inline]
+            fn display<T: ::core::fmt::Display>(f: &mut ::core::fmt::Formatter<'_>, val: &T) -> ::core::fmt::Result {
+                use ::core::fmt::Display;
+                val.fmt(f)
+            }
+            display(f, &#inner_value)
+        },
+    };
+
     quote! {
         impl #generics_with_display_bound ::core::fmt::Display for #type_name #generics_without_bounds {
             #[inline]
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                // A tiny wrapper function with trait boundary that improves error reporting.
-                // It makes it clear for the end-user that the inner type has to implement Display
-                // in order to derive display for the newtype.
-                #[inline]
-                fn display<T: ::core::fmt::Display>(f: &mut ::core::fmt::Formatter<'_>, val: &T) -> ::core::fmt::Result {
-                    use ::core::fmt::Display;
-                    val.fmt(f)
-                }
-                display(f, &self.0)
+                #body
             }
         }
     }
@@ -151,6 +195,21 @@ pub fn gen_impl_trait_borrow(
     }
 }
 
+/// Implement `validator::Validate`, so a newtype can be used as a field of a struct that derives
+/// `Validate` (e.g. with `#[validate(nested)]`). The value is always valid, since it can be
+/// obtained only through the generated constructors.
+pub fn gen_impl_trait_validator_validate(type_name: &TypeName, generics: &Generics) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+
+    quote! {
+        impl #generics ::validator::Validate for #type_name #generics_without_bounds {
+            fn validate(&self) -> ::core::result::Result<(), ::validator::ValidationErrors> {
+                Ok(())
+            }
+        }
+    }
+}
+
 pub fn gen_impl_trait_from(
     type_name: &TypeName,
     generics: &Generics,
@@ -173,6 +232,175 @@ pub fn gen_impl_trait_try_from(
     generics: &Generics,
     inner_type: impl ToTokens,
     maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    gen_impl_trait_try_from_source(
+        type_name,
+        generics,
+        inner_type,
+        quote!(raw_value),
+        maybe_error_type_name,
+    )
+}
+
+/// Generates `TryFrom` implementations for the source types listed with `try_from(...)`.
+/// A source value is converted into the inner type with `Into` before it is validated.
+/// Integers that may not fit into the inner integer type (e.g. `i64` for `i32`) are converted
+/// with `TryFrom` and reported with the dedicated `{Type}TryFromError`.
+pub fn gen_impl_trait_try_from_types(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    source_types: &[syn::Type],
+    maybe_error_type_name: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let try_from_error_type_path = gen_try_from_error_type_name(type_name);
+    let maybe_try_from_error = has_narrowing_int_source(&inner_type, source_types)
+        .then(|| gen_try_from_error(type_name, &try_from_error_type_path, maybe_error_type_name));
+
+    let impls: TokenStream = source_types
+        .iter()
+        .map(|source_type| {
+            if is_narrowing_int_conversion(source_type, &inner_type) {
+                gen_impl_trait_try_from_int(
+                    type_name,
+                    generics,
+                    &inner_type,
+                    source_type,
+                    &try_from_error_type_path,
+                    maybe_error_type_name.is_some(),
+                )
+            } else {
+                let conversion = quote!(::core::convert::Into::<#inner_type>::into(raw_value));
+                gen_impl_trait_try_from_source(
+                    type_name,
+                    generics,
+                    source_type,
+                    conversion,
+                    maybe_error_type_name,
+                )
+            }
+        })
+        .collect();
+
+    quote! {
+        #maybe_try_from_error
+        #impls
+    }
+}
+
+/// Check if the type is `&str`.
+pub fn is_str_reference(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => {
+            reference.mutability.is_none()
+                && matches!(&*reference.elem, syn::Type::Path(path) if path.path.is_ident("str"))
+        }
+        _ => false,
+    }
+}
+
+/// Generates `TryFrom` implementations for the source types listed with `try_from(...)` of
+/// numeric types.
+/// Unlike the other source types, `&str` is trimmed and parsed into the inner type before it is
+/// validated. The failures are reported with `{Type}ParseError`, that tells whether the parsing
+/// or the validation failed. The error is defined along with `FromStr`, if it's derived.
+pub fn gen_impl_trait_try_from_numeric_types(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl Into<InnerType>,
+    source_types: &[syn::Type],
+    maybe_error_type_name: Option<&ErrorTypePath>,
+    has_from_str_trait: bool,
+) -> TokenStream {
+    let inner_type: InnerType = inner_type.into();
+    let (str_source_types, other_source_types): (Vec<syn::Type>, Vec<syn::Type>) =
+        source_types.iter().cloned().partition(is_str_reference);
+
+    let impl_other_source_types = (!other_source_types.is_empty()).then(|| {
+        gen_impl_trait_try_from_types(
+            type_name,
+            generics,
+            &inner_type,
+            &other_source_types,
+            maybe_error_type_name,
+        )
+    });
+    if str_source_types.is_empty() {
+        return quote!(#impl_other_source_types);
+    }
+
+    let parse_error_type_name = gen_parse_error_name(type_name);
+    let maybe_def_parse_error = (!has_from_str_trait).then(|| {
+        gen_def_parse_error(
+            type_name,
+            generics,
+            inner_type.clone(),
+            maybe_error_type_name,
+            &parse_error_type_name,
+        )
+    });
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let construct = if maybe_error_type_name.is_some() {
+        quote!(Self::try_new(raw_value).map_err(#parse_error_type_name::Validate))
+    } else {
+        quote!(Ok(Self::new(raw_value)))
+    };
+    let impl_str_source_types = str_source_types.iter().map(|source_type| {
+        quote! {
+            impl #generics ::core::convert::TryFrom<#source_type> for #type_name #generics_without_bounds {
+                type Error = #parse_error_type_name #generics_without_bounds;
+
+                #[inline]
+                fn try_from(raw_string: #source_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
+                    let raw_value: #inner_type = raw_string.trim().parse().map_err(#parse_error_type_name::Parse)?;
+                    #construct
+                }
+            }
+        }
+    });
+
+    quote! {
+        #maybe_def_parse_error
+        #impl_other_source_types
+        #(#impl_str_source_types)*
+    }
+}
+
+fn gen_impl_trait_try_from_int(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: impl ToTokens,
+    source_type: &syn::Type,
+    try_from_error_type_path: &ErrorTypePath,
+    has_validation: bool,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let construct = if has_validation {
+        quote!(Self::try_new(inner_value).map_err(#try_from_error_type_path::Validate))
+    } else {
+        quote!(Ok(Self::new(inner_value)))
+    };
+
+    quote! {
+        impl #generics ::core::convert::TryFrom<#source_type> for #type_name #generics_without_bounds {
+            type Error = #try_from_error_type_path;
+
+            #[inline]
+            fn try_from(raw_value: #source_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
+                let inner_value = <#inner_type as ::core::convert::TryFrom<#source_type>>::try_from(raw_value)
+                    .map_err(#try_from_error_type_path::OutOfRange)?;
+                #construct
+            }
+        }
+    }
+}
+
+fn gen_impl_trait_try_from_source(
+    type_name: &TypeName,
+    generics: &Generics,
+    source_type: impl ToTokens,
+    conversion: TokenStream,
+    maybe_error_type_name: Option<&ErrorTypePath>,
 ) -> TokenStream {
     let generics_without_bounds = strip_trait_bounds_on_generics(generics);
 
@@ -181,12 +409,12 @@ pub fn gen_impl_trait_try_from(
             // The case when there are validation
             //
             quote! {
-                impl #generics ::core::convert::TryFrom<#inner_type> for #type_name #generics_without_bounds {
+                impl #generics ::core::convert::TryFrom<#source_type> for #type_name #generics_without_bounds {
                     type Error = #error_type_name;
 
                     #[inline]
-                    fn try_from(raw_value: #inner_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
-                        Self::try_new(raw_value)
+                    fn try_from(raw_value: #source_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
+                        Self::try_new(#conversion)
                     }
                 }
             }
@@ -194,13 +422,15 @@ pub fn gen_impl_trait_try_from(
         None => {
             // The case when there are no validation
             //
+            // The user explicitly asked for `TryFrom`, so silence clippy suggesting `From` instead.
             quote! {
-                impl #generics ::core::convert::TryFrom<#inner_type> for #type_name #generics_without_bounds {
+                #[allow(unknown_lints, clippy::infallible_try_from)]
+                impl #generics ::core::convert::TryFrom<#source_type> for #type_name #generics_without_bounds {
                     type Error = ::core::convert::Infallible;
 
                     #[inline]
-                    fn try_from(raw_value: #inner_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
-                        Ok(Self::new(raw_value))
+                    fn try_from(raw_value: #source_type) -> ::core::result::Result<#type_name #generics_without_bounds, Self::Error> {
+                        Ok(Self::new(#conversion))
                     }
                 }
             }
@@ -262,33 +492,82 @@ pub fn gen_impl_trait_from_str(
     }
 }
 
-pub fn gen_impl_trait_serde_serialize(type_name: &TypeName, generics: &Generics) -> TokenStream {
+pub fn gen_impl_trait_serde_serialize(
+    type_name: &TypeName,
+    generics: &Generics,
+    serde_mode: &SerdeMode,
+    maybe_store_as: Option<&StoreAs>,
+) -> TokenStream {
     let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let inner_value = gen_decode(maybe_store_as);
 
     // Turn `<T>` into `<T: Serialize>`
     let all_generics_with_serialize_bound =
         add_bound_to_all_type_params(generics, syn::parse_quote!(::serde::Serialize));
 
     let type_name_str = type_name.to_string();
+    let body = match serde_mode {
+        SerdeMode::Transparent => quote! {
+            serializer.serialize_newtype_struct(#type_name_str, &#inner_value)
+        },
+        SerdeMode::Field(field_name) => quote! {
+            use ::serde::ser::SerializeStruct;
+            let mut state = serializer.serialize_struct(#type_name_str, 1)?;
+            state.serialize_field(#field_name, &#inner_value)?;
+            state.end()
+        },
+    };
+
     quote! {
         impl #all_generics_with_serialize_bound ::serde::Serialize for #type_name #generics_without_bounds {
             fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
             where
                 S: ::serde::Serializer
             {
-                serializer.serialize_newtype_struct(#type_name_str, &self.0)
+                #body
             }
         }
     }
 }
 
+/// Deserialization that borrows the inner value from the input, requested with `serde(borrow)`.
+pub struct SerdeBorrow {
+    /// Lifetime of the inner type. The input must outlive it: `'de: 'a`.
+    pub lifetime: syn::Lifetime,
+
+    /// Type that is deserialized in place of the inner type.
+    pub raw_type: TokenStream,
+
+    /// Definitions of the helper items that `raw_type` may refer to.
+    pub helpers: TokenStream,
+
+    /// Expression that turns `raw_value` of `raw_type` into the inner type.
+    pub into_inner: TokenStream,
+}
+
 pub fn gen_impl_trait_serde_deserialize(
     type_name: &TypeName,
     type_generics: &Generics,
     inner_type: impl Into<InnerType>,
     maybe_error_type_name: Option<&ErrorTypePath>,
+    serde_mode: &SerdeMode,
+    maybe_serde_borrow: Option<&SerdeBorrow>,
+    deny_unknown_fields: bool,
 ) -> TokenStream {
     let inner_type: InnerType = inner_type.into();
+    let (raw_type, raw_value_into_inner, helpers) = match maybe_serde_borrow {
+        Some(SerdeBorrow {
+            raw_type,
+            helpers,
+            into_inner,
+            ..
+        }) => (
+            raw_type.clone(),
+            quote!(let raw_value = #into_inner;),
+            helpers.clone(),
+        ),
+        None => (inner_type.to_token_stream(), quote!(), quote!()),
+    };
     let raw_value_to_result: TokenStream = if maybe_error_type_name.is_some() {
         let type_name_str = type_name.to_string();
         quote! {
@@ -303,14 +582,74 @@ pub fn gen_impl_trait_serde_deserialize(
             Ok(#type_name::new(raw_value))
         }
     };
+    let raw_value_to_result = quote! {
+        #raw_value_into_inner
+        #raw_value_to_result
+    };
 
-    let expecting_str = format!("tuple struct {type_name}");
     let type_name_str = type_name.to_string();
+    let (expecting_str, visit_methods, deserialize_call) = match serde_mode {
+        SerdeMode::Transparent => {
+            let visit_methods = quote! {
+                fn visit_newtype_struct<DE>(self, deserializer: DE) -> ::core::result::Result<Self::Value, DE::Error>
+                where
+                    DE: ::serde::Deserializer<'de>
+                {
+                    let raw_value: #raw_type = match <#raw_type as ::serde::Deserialize>::deserialize(deserializer) {
+                        Ok(val) => val,
+                        Err(err) => return Err(err)
+                    };
+                    #raw_value_to_result
+                }
+            };
+            let deserialize_call = quote! {
+                ::serde::de::Deserializer::deserialize_newtype_struct(
+                    deserializer,
+                    #type_name_str,
+                    __Visitor {
+                        marker: Default::default(),
+                        lifetime: Default::default(),
+                    }
+                )
+            };
+            (
+                format!("tuple struct {type_name}"),
+                visit_methods,
+                deserialize_call,
+            )
+        }
+        SerdeMode::Field(field_name) => {
+            let visit_methods = gen_serde_visit_single_field(
+                &raw_type,
+                field_name,
+                &raw_value_to_result,
+                deny_unknown_fields,
+            );
+            let deserialize_call = quote! {
+                ::serde::de::Deserializer::deserialize_struct(
+                    deserializer,
+                    #type_name_str,
+                    &[#field_name],
+                    __Visitor {
+                        marker: Default::default(),
+                        lifetime: Default::default(),
+                    }
+                )
+            };
+            let expecting_str = format!("struct {type_name} with field `{}`", field_name.value());
+            (expecting_str, visit_methods, deserialize_call)
+        }
+    };
 
-    // type generics + 'de lifetime for Deserialize
+    // type generics + 'de lifetime for Deserialize, which outlives the borrowed inner value
     let all_generics = {
         let mut all_generics = type_generics.clone();
-        all_generics.params.push(syn::parse_quote!('de));
+        match maybe_serde_borrow {
+            Some(SerdeBorrow { lifetime, .. }) => {
+                all_generics.params.push(syn::parse_quote!('de: #lifetime))
+            }
+            None => all_generics.params.push(syn::parse_quote!('de)),
+        }
         all_generics
     };
     let all_generics_without_bounds = strip_trait_bounds_on_generics(&all_generics);
@@ -323,6 +662,8 @@ pub fn gen_impl_trait_serde_deserialize(
     quote! {
         impl #all_generics_with_deserialize_bound ::serde::Deserialize<'de> for #type_name #type_generics_without_bounds {
             fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                #helpers
+
                 struct __Visitor #all_generics {
                     marker: ::core::marker::PhantomData<#type_name #type_generics_without_bounds>,
                     lifetime: ::core::marker::PhantomData<&'de ()>,
@@ -335,27 +676,86 @@ pub fn gen_impl_trait_serde_deserialize(
                         write!(formatter, #expecting_str)
                     }
 
-                    fn visit_newtype_struct<DE>(self, deserializer: DE) -> ::core::result::Result<Self::Value, DE::Error>
-                    where
-                        DE: ::serde::Deserializer<'de>
-                    {
-                        let raw_value: #inner_type = match <#inner_type as ::serde::Deserialize>::deserialize(deserializer) {
-                            Ok(val) => val,
-                            Err(err) => return Err(err)
-                        };
-                        #raw_value_to_result
+                    #visit_methods
+                }
+
+                #deserialize_call
+            }
+        }
+    }
+}
+
+/// Generates `visit_map` and `visit_seq` for a struct with a single field, e.g. `{"value": 42}`.
+/// Other keys are ignored, unless `deny_unknown_fields` is set.
+fn gen_serde_visit_single_field(
+    inner_type: &TokenStream,
+    field_name: &syn::LitStr,
+    raw_value_to_result: &TokenStream,
+    deny_unknown_fields: bool,
+) -> TokenStream {
+    let check_unknown_field = deny_unknown_fields.then(|| {
+        quote! {
+            if key != #field_name {
+                return Err(E::unknown_field(key, &[#field_name]));
+            }
+        }
+    });
+    quote! {
+        fn visit_map<DE>(self, mut map: DE) -> ::core::result::Result<Self::Value, DE::Error>
+        where
+            DE: ::serde::de::MapAccess<'de>
+        {
+            // Keys are matched against the field name.
+            struct __Key(bool);
+
+            impl<'de> ::serde::Deserialize<'de> for __Key {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                    struct __KeyVisitor;
+
+                    impl<'de> ::serde::de::Visitor<'de> for __KeyVisitor {
+                        type Value = __Key;
+
+                        fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                            formatter.write_str("field identifier")
+                        }
+
+                        fn visit_str<E: ::serde::de::Error>(self, key: &str) -> ::core::result::Result<__Key, E> {
+                            #check_unknown_field
+                            Ok(__Key(key == #field_name))
+                        }
                     }
+
+                    deserializer.deserialize_identifier(__KeyVisitor)
                 }
+            }
 
-                ::serde::de::Deserializer::deserialize_newtype_struct(
-                    deserializer,
-                    #type_name_str,
-                    __Visitor {
-                        marker: Default::default(),
-                        lifetime: Default::default(),
+            let mut maybe_raw_value: ::core::option::Option<#inner_type> = None;
+            while let Some(__Key(is_field)) = map.next_key::<__Key>()? {
+                if is_field {
+                    if maybe_raw_value.is_some() {
+                        return Err(<DE::Error as ::serde::de::Error>::duplicate_field(#field_name));
                     }
-                )
+                    maybe_raw_value = Some(map.next_value::<#inner_type>()?);
+                } else {
+                    map.next_value::<::serde::de::IgnoredAny>()?;
+                }
             }
+            let raw_value: #inner_type = match maybe_raw_value {
+                Some(val) => val,
+                None => return Err(<DE::Error as ::serde::de::Error>::missing_field(#field_name)),
+            };
+            #raw_value_to_result
+        }
+
+        fn visit_seq<DE>(self, mut seq: DE) -> ::core::result::Result<Self::Value, DE::Error>
+        where
+            DE: ::serde::de::SeqAccess<'de>
+        {
+            let raw_value: #inner_type = match seq.next_element::<#inner_type>()? {
+                Some(val) => val,
+                None => return Err(<DE::Error as ::serde::de::Error>::invalid_length(0, &self)),
+            };
+            #raw_value_to_result
         }
     }
 }
@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::{
+    gen::error::gen_impl_error_trait,
+    models::{ErrorTypePath, TypeName},
+};
+
+const INTEGER_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Check if both types are integer primitives and not every value of the source type fits
+/// into the target type, so the conversion has to be done with `TryFrom` rather than `Into`.
+/// The lossless conversions are the ones that `core` implements `From` for, e.g. `u8 -> i32`.
+pub fn is_narrowing_int_conversion(source_type: impl ToTokens, target_type: impl ToTokens) -> bool {
+    let source = source_type.to_token_stream().to_string();
+    let target = target_type.to_token_stream().to_string();
+    if source == target
+        || !INTEGER_TYPES.contains(&source.as_str())
+        || !INTEGER_TYPES.contains(&target.as_str())
+    {
+        return false;
+    }
+
+    let lossless_targets: &[&str] = match source.as_str() {
+        "u8" => &[
+            "u16", "u32", "u64", "u128", "usize", "i16", "i32", "i64", "i128", "isize",
+        ],
+        "u16" => &["u32", "u64", "u128", "usize", "i32", "i64", "i128"],
+        "u32" => &["u64", "u128", "i64", "i128"],
+        "u64" => &["u128", "i128"],
+        "i8" => &["i16", "i32", "i64", "i128", "isize"],
+        "i16" => &["i32", "i64", "i128", "isize"],
+        "i32" => &["i64", "i128"],
+        "i64" => &["i128"],
+        _ => &[],
+    };
+    !lossless_targets.contains(&target.as_str())
+}
+
+/// Check if any of the source types listed with `try_from(...)` requires a narrowing conversion.
+pub fn has_narrowing_int_source(inner_type: impl ToTokens, source_types: &[syn::Type]) -> bool {
+    source_types
+        .iter()
+        .any(|source_type| is_narrowing_int_conversion(source_type, &inner_type))
+}
+
+/// Generate the error type returned by `TryFrom` for narrowing conversions.
+/// Similar to the parse error it tells whether the conversion or the validation failed.
+pub fn gen_try_from_error(
+    type_name: &TypeName,
+    try_from_error_type_path: &ErrorTypePath,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+) -> TokenStream {
+    let type_name_str = type_name.to_string();
+    let impl_error_trait = gen_impl_error_trait(try_from_error_type_path);
+
+    let (maybe_validate_variant, maybe_validate_display) = match maybe_error_type_path {
+        Some(error_type_path) => (
+            quote!(Validate(#error_type_path),),
+            quote!(#try_from_error_type_path::Validate(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),),
+        ),
+        None => (quote!(), quote!()),
+    };
+
+    quote! {
+        #[derive(Debug)]
+        pub enum #try_from_error_type_path {
+            OutOfRange(::core::num::TryFromIntError),
+            #maybe_validate_variant
+        }
+
+        impl ::core::fmt::Display for #try_from_error_type_path {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #try_from_error_type_path::OutOfRange(err) => write!(f, "Failed to convert into {}: {}", #type_name_str, err),
+                    #maybe_validate_display
+                }
+            }
+        }
+
+        #impl_error_trait
+    }
+}
@@ -0,0 +1,80 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{Rule, TrySanitizer, Validation};
+
+/// The error type of the `try_with(...)` sanitizers. All of them return the same error type,
+/// so `__sanitize__()` can return it.
+pub fn try_with_error_type<S: Rule>(sanitizers: &[S]) -> Option<&syn::Type> {
+    sanitizers.iter().find_map(Rule::try_with_error_type)
+}
+
+/// Generate `__sanitize__()` with the given transformations of `value`.
+/// With `try_with(...)` sanitizers it's fallible and returns their error.
+/// With `report_changes` it's `__sanitize_lossy__()`, that records the changes in `warnings`.
+pub fn gen_fn_sanitize_of<S: Rule>(
+    param: TokenStream,
+    inner_type: &impl ToTokens,
+    sanitizers: &[S],
+    transformations: TokenStream,
+    report_changes: bool,
+) -> TokenStream {
+    let (fn_name, params) = if report_changes {
+        (
+            quote!(__sanitize_lossy__),
+            quote!(#param, warnings: &mut ::std::vec::Vec<::nutype::SanitizeWarning>),
+        )
+    } else {
+        (quote!(__sanitize__), param)
+    };
+    match try_with_error_type(sanitizers) {
+        Some(error_type) => quote!(
+            fn #fn_name(#params) -> ::core::result::Result<#inner_type, #error_type> {
+                #transformations
+                Ok(value)
+            }
+        ),
+        None => quote!(
+            fn #fn_name(#params) -> #inner_type {
+                #transformations
+                value
+            }
+        ),
+    }
+}
+
+/// An expression, that applies the `try_with(...)` sanitizer to `value` of `value_type`
+/// and returns its error from `__sanitize__()`.
+pub fn gen_try_with_step(try_sanitizer: &TrySanitizer, value_type: impl ToTokens) -> TokenStream {
+    let TrySanitizer { with, error_type } = try_sanitizer;
+    quote!({
+        let try_with: fn(#value_type) -> ::core::result::Result<#value_type, #error_type> = #with;
+        try_with(value)?
+    })
+}
+
+/// Convert `err` of `try_with(...)` into the validation error: it's wrapped into `TryWithViolated`
+/// of the generated error, or converted with `From` into the error of the custom validation.
+pub fn gen_wrap_try_with_error<V>(validation: &Validation<V>) -> TokenStream {
+    match validation {
+        Validation::Standard {
+            error_type_path, ..
+        } => quote!(#error_type_path::TryWithViolated(err)),
+        Validation::Custom {
+            error_type_path, ..
+        } => quote!(<#error_type_path as ::core::convert::From<_>>::from(err)),
+    }
+}
+
+/// Sanitize `raw` in a function, that returns the validation error.
+pub fn gen_sanitize_or_return<S: Rule, V>(
+    raw: TokenStream,
+    sanitizers: &[S],
+    validation: &Validation<V>,
+) -> TokenStream {
+    if try_with_error_type(sanitizers).is_none() {
+        return quote!(Self::__sanitize__(#raw));
+    }
+    let wrap_error = gen_wrap_try_with_error(validation);
+    quote!(Self::__sanitize__(#raw).map_err(|err| #wrap_error)?)
+}
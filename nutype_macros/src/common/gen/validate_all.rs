@@ -0,0 +1,119 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Generics;
+
+use crate::common::{
+    gen::{
+        combinators::gen_fn_check_combinator,
+        try_with::{gen_wrap_try_with_error, try_with_error_type},
+        GenerateNewtype,
+    },
+    models::{Guard, TypeName, Validation},
+};
+
+/// Implement `validate_all()` of the `strict` profile, that checks the validators one by one
+/// and returns every violation, instead of stopping at the first one.
+///
+/// Every validator gets its own `__validate__()` within a local type, so the code of the regular
+/// validation is reused. Local types can not refer to the generic parameters of the newtype,
+/// so generic newtypes do not get `validate_all()`.
+pub fn gen_impl_validate_all<G: GenerateNewtype + ?Sized>(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &G::InnerType,
+    guard: &Guard<G::Sanitizer, G::Validator>,
+) -> TokenStream {
+    let Guard::WithValidation { validation, .. } = guard else {
+        return quote!();
+    };
+    if !generics.params.is_empty() {
+        return quote!();
+    }
+    let error_type_path = validation.error_type_path();
+    let checks: Vec<TokenStream> = match validation {
+        Validation::Standard {
+            validators,
+            combinators,
+            ..
+        } => validators
+            .iter()
+            .map(|validator| {
+                let fn_validate = G::gen_fn_validate(
+                    inner_type,
+                    error_type_path,
+                    core::slice::from_ref(validator),
+                    false,
+                );
+                quote! {
+                    {
+                        struct __Check;
+                        impl __Check {
+                            #fn_validate
+                        }
+                        __Check::__validate__(&sanitized_value).err()
+                    }
+                }
+            })
+            .chain(combinators.iter().map(|combinator| {
+                let fn_check = gen_fn_check_combinator::<G>(
+                    type_name,
+                    inner_type,
+                    error_type_path,
+                    combinator,
+                );
+                quote! {
+                    {
+                        struct __Check;
+                        impl __Check {
+                            #fn_check
+                        }
+                        __Check::__check__(&sanitized_value).err()
+                    }
+                }
+            }))
+            .collect(),
+        // A custom validation function is a single check.
+        Validation::Custom { .. } => vec![quote!(Self::__validate__(&sanitized_value).err())],
+    };
+
+    // A value rejected by `try_with(...)` can not be validated, so the error of the sanitizer
+    // is the only violation.
+    let sanitize_raw_value = if try_with_error_type(guard.sanitizers()).is_some() {
+        let wrap_error = gen_wrap_try_with_error(validation);
+        let nones = checks.iter().skip(1).map(|_| quote!(None));
+        quote! {
+            match Self::__sanitize__(raw_value) {
+                Ok(value) => value,
+                Err(err) => {
+                    let violations = [Some(#wrap_error), #(#nones),*];
+                    return ::core::iter::IntoIterator::into_iter(violations).flatten();
+                }
+            }
+        }
+    } else {
+        quote!(Self::__sanitize__(raw_value))
+    };
+
+    let (input_type, convert_raw_value_if_necessary) = if G::NEW_CONVERT_INTO_INNER_TYPE {
+        (
+            quote!(impl Into<#inner_type>),
+            quote!(let raw_value = raw_value.into();),
+        )
+    } else {
+        (quote!(#inner_type), quote!())
+    };
+
+    quote! {
+        impl #type_name {
+            /// Sanitizes the value and checks it with all the validators.
+            /// Unlike `try_new()`, it does not stop at the first violation, but returns all of them.
+            pub fn validate_all(raw_value: #input_type) -> impl ::core::iter::Iterator<Item = #error_type_path> {
+                #convert_raw_value_if_necessary
+
+                let sanitized_value: #inner_type = #sanitize_raw_value;
+                let violations = [#(#checks),*];
+                ::core::iter::IntoIterator::into_iter(violations).flatten()
+            }
+        }
+    }
+}
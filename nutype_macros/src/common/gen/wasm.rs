@@ -0,0 +1,62 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::common::models::{ErrorTypePath, TypeName};
+
+/// Implement the functions of the `wasm` attribute, that export the newtype to JS with
+/// `#[wasm_bindgen]`. The struct itself gets the attribute from `gen_wasm_bindgen_attr()`.
+///
+/// The constructor is exposed as `new Type(value)` in JS. If the value is invalid, it throws
+/// the `Display` text of the validation error.
+pub fn gen_impl_wasm(
+    type_name: &TypeName,
+    inner_type: &impl ToTokens,
+    maybe_error_type_path: Option<&ErrorTypePath>,
+    is_string: bool,
+) -> TokenStream {
+    let wasm_bindgen_attr = gen_wasm_bindgen_attr();
+    let (param_type, get_value) = if is_string {
+        (quote!(String), quote!(self.0.clone()))
+    } else {
+        (quote!(#inner_type), quote!(self.0))
+    };
+
+    let constructor = match maybe_error_type_path {
+        Some(_) => quote! {
+            /// Sanitizes and validates the value.
+            /// Returns the `Display` text of the validation error, if the value is invalid.
+            #[wasm_bindgen(constructor)]
+            pub fn js_new(value: #param_type) -> ::core::result::Result<#type_name, ::wasm_bindgen::JsValue> {
+                Self::try_new(value).map_err(|err| {
+                    ::wasm_bindgen::JsValue::from_str(&err.to_string())
+                })
+            }
+        },
+        None => quote! {
+            /// Sanitizes the value.
+            #[wasm_bindgen(constructor)]
+            pub fn js_new(value: #param_type) -> #type_name {
+                Self::new(value)
+            }
+        },
+    };
+
+    quote! {
+        #wasm_bindgen_attr
+        impl #type_name {
+            #constructor
+
+            /// Returns the inner value. Available in JS as the `value` property.
+            #[wasm_bindgen(getter = value)]
+            pub fn js_value(&self) -> #param_type {
+                #get_value
+            }
+        }
+    }
+}
+
+/// The code generated by `#[wasm_bindgen]` refers to `::wasm_bindgen`, so the crate, that uses
+/// `wasm`, must depend on `wasm-bindgen` directly.
+pub fn gen_wasm_bindgen_attr() -> TokenStream {
+    quote!(#[::wasm_bindgen::prelude::wasm_bindgen])
+}
@@ -1,11 +1,12 @@
 mod error_type_path;
+mod rules;
 
 use core::{fmt::Debug, ops::Add};
 use kinded::Kinded;
 use std::collections::HashSet;
 use syn::Generics;
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
@@ -19,8 +20,12 @@ use crate::{
 
 use super::gen::type_custom_closure;
 use super::parse::RawValidation;
+use super::validate::{
+    validate_combinators, validate_field_name, validate_optional, validate_with_context,
+};
 
 pub use error_type_path::ErrorTypePath;
+pub use rules::{validation_nodes, Rule, RuleNode, RuleStage, Rules};
 
 /// A spanned item. An item can be anything that cares a domain value.
 /// Keeping a span allows to throw good precise error messages at the validation stage.
@@ -50,6 +55,7 @@ impl<T: Kinded> Kinded for SpannedItem<T> {
 
 /// Represents the inner type of a newtype.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
 pub enum InnerType {
     String(StringInnerType),
     Integer(IntegerInnerType),
@@ -161,6 +167,13 @@ pub struct Meta {
     pub vis: syn::Visibility,
     pub doc_attrs: Vec<Attribute>,
     pub generics: Generics,
+
+    /// Name of the field if the struct has a single named field, e.g. `value` in
+    /// `struct Email { value: String }`.
+    pub field_name: Option<Ident>,
+
+    /// The whole field type if it's `Option<T>`, while `inner_type` is `T`.
+    pub optional: Option<AnyInnerType>,
 }
 
 impl Meta {
@@ -171,6 +184,8 @@ impl Meta {
             inner_type,
             vis,
             generics,
+            field_name,
+            optional,
         } = self;
         let typed_meta = TypedMeta {
             doc_attrs,
@@ -178,6 +193,8 @@ impl Meta {
             generics,
             attrs,
             vis,
+            field_name,
+            optional,
         };
         (typed_meta, inner_type)
     }
@@ -195,10 +212,17 @@ pub struct TypedMeta {
     pub vis: syn::Visibility,
     pub doc_attrs: Vec<Attribute>,
     pub generics: Generics,
+
+    /// Name of the field if the struct has a single named field.
+    pub field_name: Option<Ident>,
+
+    /// The whole field type if it's `Option<T>`, while the inner type is `T`.
+    pub optional: Option<AnyInnerType>,
 }
 
 /// Validated model, that represents precisely what needs to be generated.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Guard<Sanitizer, Validator> {
     WithoutValidation {
         sanitizers: Vec<Sanitizer>,
@@ -206,10 +230,13 @@ pub enum Guard<Sanitizer, Validator> {
     WithValidation {
         sanitizers: Vec<Sanitizer>,
         validation: Validation<Validator>,
+        /// Previous rule set declared with `legacy(validate(...))`, used by `try_migrate()`.
+        legacy: Option<Validation<Validator>>,
     },
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Validation<Validator> {
     Custom {
         /// Custom validation function that should return `Result<(), ErrorType>`
@@ -225,7 +252,146 @@ pub enum Validation<Validator> {
 
         /// Name of the error type. The #[nutype] macro must generate definition of this type.
         error_type_path: ErrorTypePath,
+
+        /// Validator that also receives a context, declared with `with_context` and `context`.
+        with_context: Option<WithContext>,
+
+        /// Messages that replace the default `Display` of the error variants.
+        messages: ErrorMessages,
+
+        /// Validators combined with `any_of(...)`, `all(...)`, `not(...)` and `when(...)`.
+        combinators: Vec<ValidatorCombinator<Validator>>,
+    },
+}
+
+/// Validators combined with `any_of(...)` (or its alias `any(...)`), `all(...)`, `not(...)` and
+/// `when(...)`, e.g. `any_of(len_char_min = 64, not_empty)`.
+#[derive(Debug)]
+pub enum ValidatorCombinator<Validator> {
+    /// Passes when at least one of the alternatives passes.
+    AnyOf {
+        span: Span,
+        alternatives: Vec<ValidatorCombinator<Validator>>,
+    },
+    /// Passes when all the validators pass.
+    All {
+        span: Span,
+        validators: Vec<ValidatorCombinator<Validator>>,
     },
+    /// Passes when the validator fails.
+    Not {
+        span: Span,
+        validator: Box<ValidatorCombinator<Validator>>,
+    },
+    /// Passes when the condition fails or all the validators of `then(...)` pass.
+    When {
+        span: Span,
+        condition: Box<ValidatorCombinator<Validator>>,
+        then: Vec<ValidatorCombinator<Validator>>,
+    },
+    Validator(Validator),
+}
+
+impl<Validator> ValidatorCombinator<Validator> {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::AnyOf { span, .. }
+            | Self::All { span, .. }
+            | Self::Not { span, .. }
+            | Self::When { span, .. } => *span,
+            Self::Validator(_) => Span::call_site(),
+        }
+    }
+
+    /// Convert the validators, keeping the structure of the combinators.
+    pub fn try_map<Other>(
+        self,
+        f: &impl Fn(Validator) -> syn::Result<Other>,
+    ) -> syn::Result<ValidatorCombinator<Other>> {
+        match self {
+            Self::AnyOf { span, alternatives } => Ok(ValidatorCombinator::AnyOf {
+                span,
+                alternatives: alternatives
+                    .into_iter()
+                    .map(|alternative| alternative.try_map(f))
+                    .collect::<syn::Result<_>>()?,
+            }),
+            Self::All { span, validators } => Ok(ValidatorCombinator::All {
+                span,
+                validators: validators
+                    .into_iter()
+                    .map(|validator| validator.try_map(f))
+                    .collect::<syn::Result<_>>()?,
+            }),
+            Self::Not { span, validator } => Ok(ValidatorCombinator::Not {
+                span,
+                validator: Box::new(validator.try_map(f)?),
+            }),
+            Self::When {
+                span,
+                condition,
+                then,
+            } => Ok(ValidatorCombinator::When {
+                span,
+                condition: Box::new(condition.try_map(f)?),
+                then: then
+                    .into_iter()
+                    .map(|validator| validator.try_map(f))
+                    .collect::<syn::Result<_>>()?,
+            }),
+            Self::Validator(validator) => Ok(ValidatorCombinator::Validator(f(validator)?)),
+        }
+    }
+}
+
+/// Messages of the validation error set with `messages(len_char_max = "...")`, that replace
+/// the default ones. `{type}` is replaced with the type name and `{limit}` with the bound of
+/// the validator.
+#[derive(Debug, Default)]
+pub struct ErrorMessages {
+    pub messages: Vec<(Ident, syn::LitStr)>,
+}
+
+impl ErrorMessages {
+    /// The message set for the validator, e.g. `len_char_max`.
+    pub fn get(&self, validator_name: &str) -> Option<&syn::LitStr> {
+        self.messages
+            .iter()
+            .find(|(name, _)| name == validator_name)
+            .map(|(_, message)| message)
+    }
+}
+
+impl Parse for ErrorMessages {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut messages: Vec<(Ident, syn::LitStr)> = Vec::new();
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            let _eq: syn::Token![=] = input.parse()?;
+            let message: syn::LitStr = input.parse()?;
+            if messages.iter().any(|(other_name, _)| *other_name == name) {
+                let msg = format!("Duplicate message for `{name}`.");
+                return Err(syn::Error::new(name.span(), msg));
+            }
+            messages.push((name, message));
+            if input.is_empty() {
+                break;
+            }
+            let _comma: syn::Token![,] = input.parse()?;
+        }
+        Ok(Self { messages })
+    }
+}
+
+/// Validator declared with `with_context = ...` and `context = ...`.
+/// It checks the sanitized value against a context passed to `try_from_with()`.
+#[derive(Debug)]
+pub struct WithContext {
+    /// Function or closure `fn(&Inner, &Context) -> bool`.
+    pub with: CustomFunction,
+
+    /// Type of the context.
+    pub context_type: syn::Type,
 }
 
 impl<V> Validation<V> {
@@ -263,6 +429,34 @@ pub struct Attributes<G, DT> {
     pub default: Option<syn::Expr>,
 
     pub derive_traits: Vec<DT>,
+
+    /// `parse_money` attribute
+    pub parse_money: Option<ParseMoney>,
+
+    /// `store_as` or `storage = ...` attribute
+    pub store_as: Option<StoreAs>,
+
+    /// `as_variants` attribute
+    pub as_variants: Option<AsVariants>,
+
+    /// `required` attribute: `None` is rejected by an `Option` based type
+    pub required: Option<Span>,
+
+    /// `trace` attribute: report every sanitizer and validator with `tracing`
+    pub trace: bool,
+
+    /// `secret` attribute: the value is redacted by `Debug` and `Display`
+    pub secret: Option<Secret>,
+
+    /// `arena` attribute: generate `try_new_in()` that copies the value into a `bumpalo` arena
+    pub arena: bool,
+
+    /// `guarantee` attribute: the sanitizers always produce a valid value, so infallible `new()`
+    /// is generated along with `try_new()`
+    pub guarantee: bool,
+
+    /// Settings for derived traits, e.g. `display = "..."`
+    pub derive_options: DeriveOptions,
 }
 
 /// Represents a value known at compile time or an expression.
@@ -322,6 +516,23 @@ impl<Sanitizer, Validator> Guard<Sanitizer, Validator> {
             Self::WithoutValidation { .. } => None,
         }
     }
+
+    pub fn with_context(&self) -> Option<&WithContext> {
+        match self {
+            Self::WithValidation {
+                validation: Validation::Standard { with_context, .. },
+                ..
+            } => with_context.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn sanitizers(&self) -> &[Sanitizer] {
+        match self {
+            Self::WithValidation { sanitizers, .. } => sanitizers,
+            Self::WithoutValidation { sanitizers } => sanitizers,
+        }
+    }
 }
 
 /// Parsed by not yet validated
@@ -329,9 +540,11 @@ impl<Sanitizer, Validator> Guard<Sanitizer, Validator> {
 pub struct RawGuard<Sanitizer, Validator> {
     pub sanitizers: Vec<Sanitizer>,
     pub validation: Option<RawValidation<Validator>>,
+    pub legacy: Option<RawValidation<Validator>>,
+    pub duplicates: DuplicatePolicy,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DeriveTrait {
     // Standard library
     Debug,
@@ -351,6 +564,8 @@ pub enum DeriveTrait {
     Display,
     Default,
     Deref,
+    IntoIterator,
+    Extend,
 
     // External crates
     //
@@ -364,6 +579,20 @@ pub enum DeriveTrait {
 
     #[cfg_attr(not(feature = "arbitrary"), allow(dead_code))]
     ArbitraryArbitrary,
+
+    #[cfg_attr(not(feature = "validator"), allow(dead_code))]
+    ValidatorValidate,
+
+    #[cfg_attr(not(feature = "borsh"), allow(dead_code))]
+    BorshSerialize,
+    #[cfg_attr(not(feature = "borsh"), allow(dead_code))]
+    BorshDeserialize,
+
+    #[cfg_attr(not(feature = "rkyv"), allow(dead_code))]
+    RkyvArchive,
+
+    #[cfg_attr(not(feature = "clap"), allow(dead_code))]
+    ClapValueParserFactory,
 }
 
 pub type SpannedDeriveTrait = SpannedItem<DeriveTrait>;
@@ -371,12 +600,24 @@ pub type SpannedDeriveTrait = SpannedItem<DeriveTrait>;
 pub trait TypeTrait {
     // If this is FromStr variant?
     fn is_from_str(&self) -> bool;
+
+    // If this is Debug variant?
+    fn is_debug(&self) -> bool;
+
+    // If this is Display variant?
+    fn is_display(&self) -> bool;
+
+    // If this is JsonSchema variant?
+    fn is_json_schema(&self) -> bool;
+
+    // If this is Archive (rkyv) variant?
+    fn is_rkyv_archive(&self) -> bool;
 }
 
 /// The flag the indicates that a newtype will be generated with extra constructor,
 /// `::new_unchecked()` constructor which allows to avoid the guards.
 /// Generally, usage of `new_unchecked` is discouraged.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub enum NewUnchecked {
     #[default]
     Off,
@@ -386,6 +627,343 @@ pub enum NewUnchecked {
     On,
 }
 
+/// Requests generation of `::parse_money()` constructor, that parses human formatted amounts
+/// of money, e.g. `"$1,234.50"` or `"1 234,50 EUR"`.
+#[derive(Debug, Clone, Default)]
+pub struct ParseMoney {
+    /// Max number of digits after the decimal separator. Set with `parse_money(scale = 2)`.
+    pub scale: Option<u32>,
+
+    /// The decimal separator (`'.'` or `','`). The other one is then a thousands separator.
+    /// Set with `parse_money(decimal_separator = ',')`. If not set, the separator is detected,
+    /// and amounts like `"1,234"` which can be read both ways are rejected.
+    pub decimal_separator: Option<char>,
+}
+
+pub type SpannedParseMoney = SpannedItem<ParseMoney>;
+
+/// Compact in-memory representation of a newtype.
+/// Set with `store_as(u8, encode = path::to::encode, decode = path::to::decode)`.
+#[derive(Debug, Clone)]
+pub struct StoreAs {
+    /// The type of the field, which holds the value.
+    pub storage_type: syn::Type,
+
+    /// Converts the sanitized and validated inner value into the storage type.
+    pub encode: CustomFunction,
+
+    /// Converts a reference to the stored value back into the inner value.
+    pub decode: CustomFunction,
+
+    /// The storage type dereferences to `str` (`storage = Box<str>` or `storage = Arc<str>`),
+    /// so `AsRef<str>`, `Deref<Target = str>` and `Borrow<str>` can still be implemented.
+    pub derefs_to_str: bool,
+}
+
+pub type SpannedStoreAs = SpannedItem<StoreAs>;
+
+/// Settings of a newtype that holds sensitive data, e.g. a password or a token.
+/// Set with `secret` or `secret(serialize, zeroize)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Secret {
+    /// Allows to derive `Serialize`. Set with `secret(serialize)`
+    pub serialize: bool,
+
+    /// Wipe the value with `zeroize` on drop. Set with `secret(zeroize)`
+    pub zeroize: bool,
+}
+
+/// Companion enum with a variant per value allowed by `in = [...]` validator.
+/// Requested with `as_variants` attribute.
+#[derive(Debug, Clone)]
+pub struct AsVariants {
+    pub variants: Vec<AsVariant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsVariant {
+    /// Name of the enum variant, e.g. `DarkBlue` for `"dark-blue"`.
+    pub ident: proc_macro2::Ident,
+
+    /// The allowed value as it's written in `in = [...]`.
+    pub value: syn::Expr,
+}
+
+/// Settings for derived traits that are provided with top-level attributes.
+#[derive(Debug, Default)]
+pub struct DeriveOptions {
+    /// Custom format string for `Display`. Provide with `display = "..."`
+    pub display: Option<syn::LitStr>,
+
+    /// Source types for `TryFrom`. Provide with `try_from(...)`
+    pub try_from: Option<Vec<syn::Type>>,
+
+    /// Target types for `Into`. Provide with `into(...)`
+    pub into: Option<Vec<syn::Type>>,
+
+    /// Representation used by `Serialize` and `Deserialize`. Provide with `serde(...)`
+    pub serde: SerdeMode,
+
+    /// Borrow the inner string from the input on deserialization. Set with `serde(borrow)`
+    pub serde_borrow: Option<Span>,
+
+    /// Metadata of the generated `JsonSchema`. Provide with `schema(...)`
+    pub schema: Option<SchemaMetadata>,
+
+    /// Raw values checked by the generated unit tests. Provide with `test_examples(...)`
+    pub test_examples: Option<TestExamples>,
+
+    /// Text encoding of `[u8; N]` based types, that `FromStr` parses and `Display` writes.
+    /// Set with `encoding = "..."`
+    pub encoding: Option<SpannedItem<Encoding>>,
+
+    /// Compare and hash a normalized form of the value. Provide with `normalize_for_eq = ...`
+    pub normalize_for_eq: Option<NormalizeForEq>,
+
+    /// The newtype refined by this one, that gets `TryFrom` into this one. Provide with `refines = ...`
+    pub refines: Option<syn::Type>,
+
+    /// Function `fn(&Inner) -> u64`, that `Hash` writes into the hasher instead of the value.
+    /// Provide with `hash_with = ...`
+    pub hash_with: Option<CustomFunction>,
+
+    /// Implement `nohash_hasher::IsEnabled`, so the type can be a key of `IntMap`. Set with `nohash`
+    pub nohash: Option<Span>,
+
+    /// Forbid `Clone` and `Copy`, so values of move-only types (one-time tokens, nonces) can not
+    /// be duplicated. Set with `no_clone`
+    pub no_clone: Option<Span>,
+
+    /// Generate `try_from_lossy()` (`from_lossy()` without validation), that also reports
+    /// the sanitizers, that changed the value. Set with `lossy`
+    pub lossy: Option<Span>,
+
+    /// Names of the public constructor and getter, and visibility of the constructors.
+    /// Provide with `constructor = ...`, `getter = ...` and `constructor_visibility = ...`
+    pub method_names: MethodNames,
+
+    /// Make the type `#[repr(transparent)]` and generate `extern "C"` functions, so it can cross
+    /// C boundaries. Set with `ffi`
+    pub ffi: Option<Span>,
+
+    /// Make the type `#[repr(transparent)]` and generate zero-copy slice views.
+    /// Set with `repr_transparent`
+    pub repr_transparent: Option<Span>,
+
+    /// Generate `validate_bytes()` and `try_from_utf8()`, that validate UTF-8 bytes before
+    /// a `String` is allocated. Set with `bytes`
+    pub bytes: Option<Span>,
+
+    /// Export the type with `#[wasm_bindgen]` together with a JS constructor and a `value` getter.
+    /// Set with `wasm`
+    pub wasm: Option<Span>,
+
+    /// Implement `PartialEq<str>` and `PartialEq<&str>` for string based types. Set with `eq_str`
+    pub eq_str: Option<Span>,
+
+    /// Compare float based types with `total_cmp()`, so `Eq` and `Ord` can be derived without
+    /// validation against NaN. Set with `total_order`
+    pub total_order: Option<Span>,
+
+    /// Group of optional behaviors. Set with `profile = "..."` or `NUTYPE_PROFILE` env variable
+    pub profile: Profile,
+
+    /// The previous name of the type, kept as a deprecated alias. Set with `renamed_from = "..."`
+    pub renamed_from: Option<RenamedFrom>,
+
+    /// Function called when validation rejects a value. Provide with `on_invalid = ...`
+    pub on_invalid: Option<OnInvalid>,
+}
+
+/// Callback of `on_invalid = ...`, e.g. to report rejected values to metrics or logs from
+/// a single place. It's called with the error before it's returned.
+#[derive(Debug)]
+pub struct OnInvalid {
+    pub callback: CustomFunction,
+
+    /// Pass the rejected value as the second argument. It's not passed for `secret` types.
+    pub with_value: bool,
+}
+
+/// The previous name of a renamed type, e.g. `renamed_from = "Nickname", since = "2.0.0"`.
+/// The old names of the type and its error types remain available as deprecated aliases.
+#[derive(Debug)]
+pub struct RenamedFrom {
+    pub name: Ident,
+
+    /// The version, in which the type was renamed. Set with `since = "..."`
+    pub since: Option<syn::LitStr>,
+}
+
+/// Names of the generated methods, that follow the conventions of the domain,
+/// e.g. `Email::parse(raw)` and `email.value()`.
+#[derive(Debug, Default)]
+pub struct MethodNames {
+    /// Replaces `try_new()`, or `new()` if there is no validation.
+    pub constructor: Option<Ident>,
+
+    /// Replaces `into_inner()`.
+    pub getter: Option<Ident>,
+
+    /// Name of the field of a struct with a single named field, that is kept as the name of
+    /// the `&self` accessor, e.g. `email.value()`.
+    pub field: Option<Ident>,
+
+    /// Visibility of the constructors, if it's narrower than the visibility of the type,
+    /// e.g. `constructor_visibility = pub(crate)`.
+    pub constructor_visibility: Option<syn::Visibility>,
+}
+
+/// How sanitizers or validators of the same kind, that are specified more than once, are treated.
+/// Set with `duplicates = "..."`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Duplicates are a compile error.
+    #[default]
+    Error,
+
+    /// Only the last one is kept, e.g. `validate(len_char_max = 10, len_char_max = 20)` is
+    /// the same as `validate(len_char_max = 20)`.
+    LastWins,
+}
+
+impl DuplicatePolicy {
+    pub const NAMES: &'static [&'static str] = &["error", "last_wins"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(Self::Error),
+            "last_wins" => Some(Self::LastWins),
+            _ => None,
+        }
+    }
+}
+
+/// Text encoding of `[u8; N]` based types. Set with `encoding = "..."`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Hex digits, e.g. `"00ff"`. Requires feature `hex`.
+    Hex,
+
+    /// Standard base64 alphabet with padding, e.g. `"AP8="`. Requires feature `base64`.
+    Base64,
+}
+
+impl Encoding {
+    pub const NAMES: &'static [&'static str] = &["hex", "base64"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hex" => Some(Self::Hex),
+            "base64" => Some(Self::Base64),
+            _ => None,
+        }
+    }
+
+    /// The feature of `nutype`, that provides the encoding.
+    pub fn feature(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Self::Hex => cfg!(feature = "hex"),
+            Self::Base64 => cfg!(feature = "base64"),
+        }
+    }
+}
+
+/// A group of optional behaviors, that can be enabled for all the newtypes of a build at once.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// No optional behaviors.
+    #[default]
+    Default,
+
+    /// Redacts `Debug`, denies unknown fields in `serde(field = "...")` and generates
+    /// `validate_all()`.
+    Strict,
+}
+
+impl Profile {
+    pub const NAMES: &'static [&'static str] = &["default", "strict"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::Default),
+            "strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+
+    /// Derived `Debug` prints `Name(***)` instead of the value.
+    pub fn redacts_debug(self) -> bool {
+        self == Self::Strict
+    }
+
+    /// `Deserialize` with `serde(field = "...")` rejects unknown keys.
+    pub fn denies_unknown_fields(self) -> bool {
+        self == Self::Strict
+    }
+
+    /// `validate_all()` reports all the violated validators, not only the first one.
+    pub fn validates_all(self) -> bool {
+        self == Self::Strict
+    }
+}
+
+/// `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord` that compare the values normalized by
+/// a function, e.g. `normalize_for_eq = |email| email.to_lowercase()`.
+#[derive(Debug)]
+pub struct NormalizeForEq {
+    /// Function or closure `fn(&Inner) -> Key`.
+    pub with: CustomFunction,
+
+    /// The derived traits, that are implemented with the normalized value instead.
+    pub traits: Vec<DeriveTrait>,
+}
+
+/// Metadata added to the JSON Schema of a newtype on top of the schema of the inner type,
+/// e.g. `schema(format = "email", example = "alice@example.com")`.
+#[derive(Debug, Default)]
+pub struct SchemaMetadata {
+    /// The `format` keyword, e.g. `"email"` or `"uuid"`.
+    pub format: Option<syn::LitStr>,
+
+    /// Values listed in `examples`. Every `example = ...` adds one.
+    pub examples: Vec<syn::Expr>,
+
+    /// The `description` keyword. If it's not set, the doc comments of the type are used.
+    pub description: Option<syn::LitStr>,
+}
+
+/// Raw values, that the generated unit tests pass to `try_new()`,
+/// e.g. `test_examples(valid = ["a@b.c"], invalid = ["", "x"])`.
+#[derive(Debug)]
+pub struct TestExamples {
+    /// Values that must be accepted.
+    pub valid: Vec<syn::Expr>,
+
+    /// Values that must be rejected.
+    pub invalid: Vec<syn::Expr>,
+
+    pub span: Span,
+}
+
+/// How `Serialize` and `Deserialize` represent a newtype.
+#[derive(Debug, Default)]
+pub enum SerdeMode {
+    /// As the inner value, e.g. `42`. Set with `serde(transparent)`
+    #[default]
+    Transparent,
+
+    /// As a struct with a single field, e.g. `{"value": 42}`. Set with `serde(field = "value")`
+    Field(syn::LitStr),
+}
+
 pub struct GenerateParams<IT, Trait, Guard> {
     pub inner_type: IT,
     pub doc_attrs: Vec<Attribute>,
@@ -396,6 +974,14 @@ pub struct GenerateParams<IT, Trait, Guard> {
     pub guard: Guard,
     pub new_unchecked: NewUnchecked,
     pub maybe_default_value: Option<syn::Expr>,
+    pub parse_money: Option<ParseMoney>,
+    pub store_as: Option<StoreAs>,
+    pub as_variants: Option<AsVariants>,
+    pub derive_options: DeriveOptions,
+    pub trace: bool,
+    pub secret: Option<Secret>,
+    pub arena: bool,
+    pub guarantee: bool,
 }
 
 pub trait Newtype {
@@ -408,11 +994,14 @@ pub trait Newtype {
     fn parse_attributes(
         attrs: TokenStream,
         type_name: &TypeName,
+        inner_type: &Self::InnerType,
     ) -> Result<Attributes<Guard<Self::Sanitizer, Self::Validator>, SpannedDeriveTrait>, syn::Error>;
 
     fn validate(
         guard: &Guard<Self::Sanitizer, Self::Validator>,
         derive_traits: Vec<SpannedDeriveTrait>,
+        inner_type: &Self::InnerType,
+        derive_options: &DeriveOptions,
     ) -> Result<HashSet<Self::TypedTrait>, syn::Error>;
 
     #[allow(clippy::type_complexity)]
@@ -424,6 +1013,21 @@ pub trait Newtype {
         >,
     ) -> Result<TokenStream, syn::Error>;
 
+    /// Generate a newtype around `Option<T>`, where `T` is the inner type.
+    /// Only string, integer and float inner types can be optional.
+    #[allow(clippy::type_complexity)]
+    fn generate_optional(
+        _params: GenerateParams<
+            Self::InnerType,
+            DeriveTrait,
+            Guard<Self::Sanitizer, Self::Validator>,
+        >,
+        _optional_type: &AnyInnerType,
+        _is_required: bool,
+    ) -> Result<TokenStream, syn::Error> {
+        unreachable!("Only string, integer and float inner types can be optional")
+    }
+
     fn expand(
         typed_meta: TypedMeta,
         inner_type: Self::InnerType,
@@ -434,14 +1038,77 @@ pub trait Newtype {
             attrs,
             vis,
             generics,
+            field_name,
+            optional,
         } = typed_meta;
+        let mut attributes = Self::parse_attributes(attrs, &type_name, &inner_type)?;
+        if let Some(field_name) = field_name {
+            validate_field_name(&attributes, &field_name)?;
+            attributes.derive_options.method_names.field = Some(field_name);
+        }
+        validate_with_context(&attributes, optional.is_some())?;
+        validate_combinators(&attributes, optional.is_some())?;
+
+        if let Some(optional_type) = optional {
+            let traits = validate_optional(&attributes)?;
+            let Attributes {
+                guard,
+                new_unchecked,
+                default: maybe_default_value,
+                derive_traits,
+                required,
+                derive_options,
+                trace,
+                secret,
+                ..
+            } = attributes;
+            // The rules of the inner type (e.g. `Eq` on floats requires `finite`) apply to the `Some` value.
+            Self::validate(&guard, derive_traits, &inner_type, &derive_options)?;
+            return Self::generate_optional(
+                GenerateParams {
+                    doc_attrs,
+                    traits,
+                    vis,
+                    type_name,
+                    generics,
+                    guard,
+                    new_unchecked,
+                    maybe_default_value,
+                    inner_type,
+                    parse_money: None,
+                    store_as: None,
+                    as_variants: None,
+                    derive_options,
+                    trace,
+                    secret,
+                    arena: false,
+                    guarantee: false,
+                },
+                &optional_type,
+                required.is_some(),
+            );
+        }
+
         let Attributes {
             guard,
             new_unchecked,
             default: maybe_default_value,
             derive_traits,
-        } = Self::parse_attributes(attrs, &type_name)?;
-        let traits = Self::validate(&guard, derive_traits)?;
+            parse_money,
+            store_as,
+            as_variants,
+            required,
+            derive_options,
+            trace,
+            secret,
+            arena,
+            guarantee,
+        } = attributes;
+        if let Some(required_span) = required {
+            let msg = "`required` is supported only by `Option` based types.";
+            return Err(syn::Error::new(required_span, msg));
+        }
+        let traits = Self::validate(&guard, derive_traits, &inner_type, &derive_options)?;
         let generated_output = Self::generate(GenerateParams {
             doc_attrs,
             traits,
@@ -452,6 +1119,14 @@ pub trait Newtype {
             new_unchecked,
             maybe_default_value,
             inner_type,
+            parse_money,
+            store_as,
+            as_variants,
+            derive_options,
+            trace,
+            secret,
+            arena,
+            guarantee,
         })?;
         Ok(generated_output)
     }
@@ -468,7 +1143,14 @@ pub enum CustomFunction {
 
 impl Parse for CustomFunction {
     fn parse(input: ParseStream) -> syn::Result<CustomFunction> {
-        if let Ok(path) = input.parse::<Path>() {
+        // A closure is parsed as a whole expression, so commas, turbofish and nested calls in its
+        // body do not end the attribute. Its errors are reported as they are.
+        let is_closure = input.peek(syn::Token![|])
+            || input.peek(syn::Token![||])
+            || input.peek(syn::Token![move]);
+        if is_closure {
+            Ok(Self::Closure(input.parse()?))
+        } else if let Ok(path) = input.parse::<Path>() {
             Ok(Self::Path(path))
         } else if let Ok(closure) = input.parse::<ExprClosure>() {
             Ok(Self::Closure(closure))
@@ -507,6 +1189,15 @@ impl ToTokens for CustomFunction {
     }
 }
 
+/// A fallible sanitizer `try_with(func, error = Error)`, where `func` is
+/// `fn(Inner) -> Result<Inner, Error>`. Its error is reported with `TryWithViolated(Error)`
+/// variant of the generated error.
+#[derive(Debug, Clone)]
+pub struct TrySanitizer {
+    pub with: CustomFunction,
+    pub error_type: syn::Type,
+}
+
 /// Represents a function that is used for custom sanitizers and validators specified
 /// with `with =`.
 /// It's almost the same as CustomFunction with one important difference:
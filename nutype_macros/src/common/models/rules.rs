@@ -0,0 +1,208 @@
+use proc_macro2::TokenStream;
+
+use super::{Guard, Validation, ValidatorCombinator};
+
+/// Adapter of the per-kind sanitizers and validators (e.g. `StringSanitizer`, `FloatValidator`)
+/// to the kind independent [`RuleNode`].
+pub trait Rule {
+    /// Name of the rule as it's written in the attributes, e.g. `trim` or `len_char_max`.
+    fn name(&self) -> String;
+
+    /// A short description of what the rule guarantees, e.g. `trimmed` or `at most 20 characters`.
+    /// Returns `None` for the rules that do not constrain the value by themselves.
+    fn describe(&self) -> Option<String>;
+
+    /// The error type of a fallible sanitizer, declared with `try_with(...)`.
+    fn try_with_error_type(&self) -> Option<&syn::Type> {
+        None
+    }
+
+    /// The bound of a validator, e.g. `100` for `len_char_max = 100`.
+    fn bound(&self) -> Option<TokenStream> {
+        None
+    }
+}
+
+/// Whether a rule transforms or checks the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleStage {
+    Sanitizer,
+    Validator,
+}
+
+/// A sanitizer or a validator in the representation shared by all the kinds of inner types.
+///
+/// The cross-cutting features (documentation, tracing, error codes, `METADATA` and `inventory`)
+/// only use the common part. The check or the transformation itself is generated by the kind
+/// from its extension `rule`.
+#[derive(Debug)]
+pub struct RuleNode<'a, K> {
+    pub stage: RuleStage,
+
+    /// Name of the rule as it's written in the attributes, e.g. `len_char_max` or `any_of`.
+    pub name: String,
+
+    /// What the rule guarantees, e.g. `at most 20 characters`.
+    pub description: Option<String>,
+
+    /// The bound of a validator, e.g. `20` for `len_char_max = 20`.
+    pub bound: Option<TokenStream>,
+
+    /// The kind specific rule. It's `None` for the rules, that are the same for all the kinds:
+    /// combinators, `with_context` and custom validation with `with`.
+    pub rule: Option<&'a K>,
+}
+
+impl<'a, K: Rule> RuleNode<'a, K> {
+    pub fn new(stage: RuleStage, rule: &'a K) -> Self {
+        Self {
+            stage,
+            name: rule.name(),
+            description: rule.describe(),
+            bound: rule.bound(),
+            rule: Some(rule),
+        }
+    }
+
+    pub fn sanitizers(sanitizers: &'a [K]) -> Vec<Self> {
+        sanitizers
+            .iter()
+            .map(|sanitizer| Self::new(RuleStage::Sanitizer, sanitizer))
+            .collect()
+    }
+
+    pub fn validators(validators: &'a [K]) -> Vec<Self> {
+        validators
+            .iter()
+            .map(|validator| Self::new(RuleStage::Validator, validator))
+            .collect()
+    }
+
+    /// A validator, that exists for all the kinds.
+    fn common_validator(name: &str, description: String) -> Self {
+        Self {
+            stage: RuleStage::Validator,
+            name: name.to_string(),
+            description: Some(description),
+            bound: None,
+            rule: None,
+        }
+    }
+
+    fn combinator(combinator: &'a ValidatorCombinator<K>) -> Self {
+        match combinator {
+            ValidatorCombinator::Validator(validator) => Self::new(RuleStage::Validator, validator),
+            ValidatorCombinator::AnyOf { .. } => {
+                Self::common_validator("any_of", describe_combinator(combinator))
+            }
+            ValidatorCombinator::All { .. } => {
+                Self::common_validator("all", describe_combinator(combinator))
+            }
+            ValidatorCombinator::Not { .. } => {
+                Self::common_validator("not", describe_combinator(combinator))
+            }
+            ValidatorCombinator::When { .. } => {
+                Self::common_validator("when", describe_combinator(combinator))
+            }
+        }
+    }
+}
+
+/// All the rules of a newtype: the sanitizers in the order of application, followed by
+/// the validators.
+#[derive(Debug)]
+pub struct Rules<'a, S, V> {
+    pub sanitizers: Vec<RuleNode<'a, S>>,
+    pub validators: Vec<RuleNode<'a, V>>,
+}
+
+impl<'a, S: Rule, V: Rule> Rules<'a, S, V> {
+    pub fn of_guard(guard: &'a Guard<S, V>) -> Self {
+        let validators = match guard {
+            Guard::WithoutValidation { .. } => vec![],
+            Guard::WithValidation { validation, .. } => validation_nodes(validation),
+        };
+        Self {
+            sanitizers: RuleNode::sanitizers(guard.sanitizers()),
+            validators,
+        }
+    }
+}
+
+impl<S, V> Rules<'_, S, V> {
+    /// What the rules guarantee, e.g. `["trimmed", "at most 20 characters"]`.
+    pub fn guarantees(&self) -> Vec<String> {
+        let descriptions = self
+            .sanitizers
+            .iter()
+            .filter_map(|node| node.description.clone())
+            .chain(
+                self.validators
+                    .iter()
+                    .filter_map(|node| node.description.clone()),
+            );
+        let mut guarantees: Vec<String> = Vec::new();
+        // Repeated `with` sanitizers have the same description.
+        for description in descriptions {
+            if !guarantees.contains(&description) {
+                guarantees.push(description);
+            }
+        }
+        guarantees
+    }
+}
+
+/// The validators of a validation, including the combinators and `with_context`.
+pub fn validation_nodes<V: Rule>(validation: &Validation<V>) -> Vec<RuleNode<'_, V>> {
+    match validation {
+        Validation::Standard {
+            validators,
+            combinators,
+            with_context,
+            ..
+        } => RuleNode::validators(validators)
+            .into_iter()
+            .chain(combinators.iter().map(RuleNode::combinator))
+            .chain(with_context.as_ref().map(|_| {
+                RuleNode::common_validator(
+                    "with_context",
+                    "valid in the context given to `try_from_with()`".to_string(),
+                )
+            }))
+            .collect(),
+        Validation::Custom { .. } => vec![RuleNode::common_validator(
+            "with",
+            "checked by a custom validation function".to_string(),
+        )],
+    }
+}
+
+/// Describe `any_of(...)`, `all(...)`, `not(...)` or `when(...)`, e.g. `any of: at most 5 characters or at least 10 characters`.
+fn describe_combinator<V: Rule>(combinator: &ValidatorCombinator<V>) -> String {
+    match combinator {
+        ValidatorCombinator::AnyOf { alternatives, .. } => {
+            let alternatives: Vec<String> = alternatives.iter().map(describe_combinator).collect();
+            format!("any of: {}", alternatives.join(" or "))
+        }
+        ValidatorCombinator::All { validators, .. } => {
+            let validators: Vec<String> = validators.iter().map(describe_combinator).collect();
+            format!("all of: {}", validators.join(" and "))
+        }
+        ValidatorCombinator::Not { validator, .. } => {
+            format!("not {}", describe_combinator(validator))
+        }
+        ValidatorCombinator::When {
+            condition, then, ..
+        } => {
+            let then: Vec<String> = then.iter().map(describe_combinator).collect();
+            format!(
+                "when {}: {}",
+                describe_combinator(condition),
+                then.join(" and ")
+            )
+        }
+        ValidatorCombinator::Validator(validator) => validator
+            .describe()
+            .unwrap_or_else(|| "a custom rule".to_string()),
+    }
+}
@@ -26,6 +26,8 @@ impl Parse for SpannedDeriveTrait {
             "Hash" => DeriveTrait::Hash,
             "Borrow" => DeriveTrait::Borrow,
             "Default" => DeriveTrait::Default,
+            "IntoIterator" => DeriveTrait::IntoIterator,
+            "Extend" => DeriveTrait::Extend,
             "Serialize" => {
                 cfg_if! {
                     if #[cfg(feature = "serde")] {
@@ -62,6 +64,51 @@ impl Parse for SpannedDeriveTrait {
                     }
                 }
             }
+            "Validate" => {
+                cfg_if! {
+                    if #[cfg(feature = "validator")] {
+                        DeriveTrait::ValidatorValidate
+                    } else {
+                        return Err(syn::Error::new(ident.span(), "To derive Validate, the feature `validator` of the crate `nutype` needs to be enabled."));
+                    }
+                }
+            }
+            "BorshSerialize" => {
+                cfg_if! {
+                    if #[cfg(feature = "borsh")] {
+                        DeriveTrait::BorshSerialize
+                    } else {
+                        return Err(syn::Error::new(ident.span(), "To derive BorshSerialize, the feature `borsh` of the crate `nutype` needs to be enabled."));
+                    }
+                }
+            }
+            "BorshDeserialize" => {
+                cfg_if! {
+                    if #[cfg(feature = "borsh")] {
+                        DeriveTrait::BorshDeserialize
+                    } else {
+                        return Err(syn::Error::new(ident.span(), "To derive BorshDeserialize, the feature `borsh` of the crate `nutype` needs to be enabled."));
+                    }
+                }
+            }
+            "Archive" => {
+                cfg_if! {
+                    if #[cfg(feature = "rkyv")] {
+                        DeriveTrait::RkyvArchive
+                    } else {
+                        return Err(syn::Error::new(ident.span(), "To derive Archive, the feature `rkyv` of the crate `nutype` needs to be enabled."));
+                    }
+                }
+            }
+            "ValueParserFactory" => {
+                cfg_if! {
+                    if #[cfg(feature = "clap")] {
+                        DeriveTrait::ClapValueParserFactory
+                    } else {
+                        return Err(syn::Error::new(ident.span(), "To derive ValueParserFactory, the feature `clap` of the crate `nutype` needs to be enabled."));
+                    }
+                }
+            }
             _ => {
                 return Err(syn::Error::new(
                     ident.span(),
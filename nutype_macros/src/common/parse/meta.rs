@@ -1,6 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{spanned::Spanned, Attribute, DeriveInput, Visibility};
+use syn::{
+    spanned::Spanned, Attribute, DeriveInput, Generics, PredicateLifetime, PredicateType,
+    Visibility, WherePredicate,
+};
 
 use crate::{
     any::models::AnyInnerType,
@@ -26,6 +29,7 @@ pub fn parse_meta(token_stream: TokenStream) -> Result<Meta, syn::Error> {
     } = input;
 
     let type_name = TypeName::new(type_name);
+    let generics = move_where_clause_into_bounds(generics)?;
 
     validate_supported_attrs(&attrs)?;
 
@@ -35,22 +39,22 @@ pub fn parse_meta(token_stream: TokenStream) -> Result<Meta, syn::Error> {
     let data_struct = match &data {
         syn::Data::Struct(v) => v.clone(),
         _ => {
-            let error =
-                syn::Error::new(input_span, "#[nutype] can be used only with tuple structs.");
-            return Err(error);
+            let msg = "#[nutype] can be used only with tuple structs or structs with a single named field.";
+            return Err(syn::Error::new(input_span, msg));
         }
     };
 
-    let fields_unnamed = match data_struct.fields {
-        syn::Fields::Unnamed(fu) => fu,
-        _ => {
-            let error =
-                syn::Error::new(input_span, "#[nutype] can be used only with tuple structs.");
-            return Err(error);
+    let fields_span = data_struct.fields.span();
+    let fields = match data_struct.fields {
+        syn::Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed,
+        syn::Fields::Named(fields_named) => fields_named.named,
+        syn::Fields::Unit => {
+            let msg = "#[nutype] can be used only with tuple structs or structs with a single named field.";
+            return Err(syn::Error::new(input_span, msg));
         }
     };
 
-    let seg = fields_unnamed.unnamed.iter().next().ok_or_else(|| {
+    let seg = fields.iter().next().ok_or_else(|| {
         let suggested_struct = quote::quote!(
             #vis struct #type_name(i32)
         )
@@ -61,12 +65,47 @@ pub fn parse_meta(token_stream: TokenStream) -> Result<Meta, syn::Error> {
              I bet you'll be luckier trying out something like this:\n\n\
              {suggested_struct};\n\n"
         );
-        syn::Error::new(fields_unnamed.span(), msg)
+        syn::Error::new(fields_span, msg)
     })?;
+    if let Some(extra_field) = fields.iter().nth(1) {
+        let msg = "#[nutype] requires exactly one field, that holds the inner value.";
+        return Err(syn::Error::new(extra_field.span(), msg));
+    }
+    let field_name = seg.ident.clone();
+    // The inner type is emitted as the field of the generated tuple struct.
+    let seg = syn::Field {
+        ident: None,
+        colon_token: None,
+        ..seg.clone()
+    };
     validate_inner_field_visibility(&seg.vis)?;
 
     let type_path = match seg.ty.clone() {
         syn::Type::Path(tp) => tp,
+        syn::Type::Reference(type_ref) => {
+            let inner_type = InnerType::String(parse_borrowed_str(&type_ref)?);
+            return Ok(Meta {
+                doc_attrs,
+                type_name,
+                generics,
+                inner_type,
+                vis,
+                field_name,
+                optional: None,
+            });
+        }
+        // Arrays, e.g. `[u8; 32]`, are treated as any other type.
+        syn::Type::Array(_) => {
+            return Ok(Meta {
+                doc_attrs,
+                type_name,
+                generics,
+                inner_type: InnerType::Any(AnyInnerType::new(seg)),
+                vis,
+                field_name,
+                optional: None,
+            });
+        }
         _ => {
             let error = syn::Error::new(
                 seg.span(),
@@ -76,10 +115,93 @@ pub fn parse_meta(token_stream: TokenStream) -> Result<Meta, syn::Error> {
         }
     };
 
-    let type_path_str = type_path.into_token_stream().to_string();
+    // `Option<T>` where `T` is a string, integer or float: the guards are applied to the `Some` value.
+    if let Some(inner_type) = parse_option_of_primitive(&type_path) {
+        return Ok(Meta {
+            doc_attrs,
+            type_name,
+            generics,
+            inner_type,
+            vis,
+            field_name,
+            optional: Some(AnyInnerType::new(seg.clone())),
+        });
+    }
+
+    let inner_type = match parse_primitive(&type_path) {
+        Some(inner_type) => inner_type,
+        None => match parse_cow_str(&type_path) {
+            Some(cow_inner_type) => InnerType::String(cow_inner_type),
+            None => InnerType::Any(AnyInnerType::new(seg.clone())),
+        },
+    };
+
+    Ok(Meta {
+        doc_attrs,
+        type_name,
+        generics,
+        inner_type,
+        vis,
+        field_name,
+        optional: None,
+    })
+}
+
+/// The generated code carries the bounds of generic parameters, but not a `where` clause,
+/// so `struct Sorted<T>(Vec<T>) where T: Ord;` is turned into `struct Sorted<T: Ord>(Vec<T>);`.
+/// Only the bounds of the type parameters and lifetimes of the struct can be moved.
+fn move_where_clause_into_bounds(mut generics: Generics) -> Result<Generics, syn::Error> {
+    let Some(where_clause) = generics.where_clause.take() else {
+        return Ok(generics);
+    };
+    for predicate in where_clause.predicates {
+        match predicate {
+            WherePredicate::Type(PredicateType {
+                lifetimes: None,
+                bounded_ty: syn::Type::Path(ref type_path),
+                ref bounds,
+                ..
+            }) if type_path.qself.is_none() => {
+                let param = type_path.path.get_ident().and_then(|ident| {
+                    generics
+                        .type_params_mut()
+                        .find(|param| param.ident == *ident)
+                });
+                match param {
+                    Some(param) => param.bounds.extend(bounds.iter().cloned()),
+                    None => return Err(unsupported_where_predicate(&predicate)),
+                }
+            }
+            WherePredicate::Lifetime(PredicateLifetime {
+                ref lifetime,
+                ref bounds,
+                ..
+            }) => {
+                let param = generics
+                    .lifetimes_mut()
+                    .find(|param| param.lifetime == *lifetime);
+                match param {
+                    Some(param) => param.bounds.extend(bounds.iter().cloned()),
+                    None => return Err(unsupported_where_predicate(&predicate)),
+                }
+            }
+            _ => return Err(unsupported_where_predicate(&predicate)),
+        }
+    }
+    Ok(generics)
+}
+
+fn unsupported_where_predicate(predicate: &WherePredicate) -> syn::Error {
+    let msg = "#[nutype] supports only bounds of the generic parameters in `where` clause, e.g. `where T: Ord`.";
+    syn::Error::new(predicate.span(), msg)
+}
+
+/// Recognizes `String`, integer and float types.
+fn parse_primitive(type_path: &syn::TypePath) -> Option<InnerType> {
+    let type_path_str = type_path.to_token_stream().to_string();
 
     let inner_type = match type_path_str.as_ref() {
-        "String" => InnerType::String(StringInnerType),
+        "String" => InnerType::String(StringInnerType::String),
         "u8" => InnerType::Integer(IntegerInnerType::U8),
         "u16" => InnerType::Integer(IntegerInnerType::U16),
         "u32" => InnerType::Integer(IntegerInnerType::U32),
@@ -94,15 +216,81 @@ pub fn parse_meta(token_stream: TokenStream) -> Result<Meta, syn::Error> {
         "isize" => InnerType::Integer(IntegerInnerType::Isize),
         "f32" => InnerType::Float(FloatInnerType::F32),
         "f64" => InnerType::Float(FloatInnerType::F64),
-        _ => InnerType::Any(AnyInnerType::new(seg.clone())),
+        _ => return None,
     };
+    Some(inner_type)
+}
 
-    Ok(Meta {
-        doc_attrs,
-        type_name,
-        generics,
-        inner_type,
-        vis,
+/// Recognizes `Option<T>` (possibly with a path prefix like `std::option::Option<T>`),
+/// where `T` is `String`, an integer or a float. Returns the inner type `T`.
+/// Options of other types are treated as any other type.
+fn parse_option_of_primitive(type_path: &syn::TypePath) -> Option<InnerType> {
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(ref args) = last_segment.arguments else {
+        return None;
+    };
+    let mut args = args.args.iter();
+    let (Some(syn::GenericArgument::Type(syn::Type::Path(some_type))), None) =
+        (args.next(), args.next())
+    else {
+        return None;
+    };
+    parse_primitive(some_type)
+}
+
+/// Parses `&'a str` inner type.
+fn parse_borrowed_str(type_ref: &syn::TypeReference) -> Result<StringInnerType, syn::Error> {
+    let is_str = matches!(&*type_ref.elem, syn::Type::Path(tp) if tp.qself.is_none() && tp.path.is_ident("str"));
+    if !is_str || type_ref.mutability.is_some() {
+        let msg = "#[nutype] supports only `&'a str` as a borrowed inner type.";
+        return Err(syn::Error::new(type_ref.span(), msg));
+    }
+    let Some(lifetime) = type_ref.lifetime.clone() else {
+        let msg = "A borrowed inner type requires an explicit lifetime, e.g. `&'a str`.";
+        return Err(syn::Error::new(type_ref.span(), msg));
+    };
+    Ok(StringInnerType::Str { lifetime })
+}
+
+/// Recognizes `Cow<'a, str>` (possibly with a path prefix like `std::borrow::Cow<'a, str>`).
+/// Returns `None` if the type is something else, so it can be treated as any other type.
+fn parse_cow_str(type_path: &syn::TypePath) -> Option<StringInnerType> {
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Cow" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(ref args) = last_segment.arguments else {
+        return None;
+    };
+    let mut args = args.args.iter();
+    let (
+        Some(syn::GenericArgument::Lifetime(lifetime)),
+        Some(syn::GenericArgument::Type(syn::Type::Path(str_path))),
+        None,
+    ) = (args.next(), args.next(), args.next())
+    else {
+        return None;
+    };
+    if !str_path.path.is_ident("str") {
+        return None;
+    }
+
+    let mut path = type_path.path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.arguments = syn::PathArguments::None;
+    }
+    Some(StringInnerType::Cow {
+        path,
+        lifetime: lifetime.clone(),
     })
 }
 
@@ -10,18 +10,23 @@ use core::{
 use cfg_if::cfg_if;
 use kinded::{Kind, Kinded};
 use proc_macro2::{Ident, Span};
+use quote::{quote, ToTokens};
 use syn::{
+    ext::IdentExt,
     parenthesized,
-    parse::{Parse, ParseStream},
+    parse::{Parse, ParseStream, Parser},
     spanned::Spanned,
     token::Paren,
     Expr, Lit, Token,
 };
 
-use crate::common::models::SpannedDeriveTrait;
+use crate::common::models::{SpannedDeriveTrait, SpannedItem};
 
 use super::models::{
-    CustomFunction, ErrorTypePath, NewUnchecked, TypedCustomFunction, ValueOrExpr,
+    CustomFunction, DeriveOptions, DeriveTrait, DuplicatePolicy, Encoding, ErrorMessages,
+    ErrorTypePath, NewUnchecked, NormalizeForEq, OnInvalid, ParseMoney, Profile, RenamedFrom,
+    SchemaMetadata, Secret, SerdeMode, SpannedParseMoney, SpannedStoreAs, StoreAs, TestExamples,
+    TrySanitizer, TypedCustomFunction, ValidatorCombinator, ValueOrExpr, WithContext,
 };
 
 pub fn is_doc_attribute(attribute: &syn::Attribute) -> bool {
@@ -63,6 +68,9 @@ pub struct ParseableAttributes<Sanitizer, Validator> {
     /// Parsed from `validate(...)` attribute
     pub validation: Option<RawValidation<Validator>>,
 
+    /// Parsed from `legacy(validate(...))` attribute
+    pub legacy: Option<RawValidation<Validator>>,
+
     /// Parsed from `new_unchecked` attribute
     pub new_unchecked: NewUnchecked,
 
@@ -71,20 +79,174 @@ pub struct ParseableAttributes<Sanitizer, Validator> {
 
     /// Parsed from `derive(...)` attribute
     pub derive_traits: Vec<SpannedDeriveTrait>,
+
+    /// Parsed from `parse_money` or `parse_money(...)` attribute
+    pub parse_money: Option<SpannedParseMoney>,
+
+    /// Parsed from `store_as(...)` attribute
+    pub store_as: Option<SpannedStoreAs>,
+
+    /// Parsed from `storage = ...` attribute
+    pub storage: Option<SpannedItem<syn::Type>>,
+
+    /// Parsed from `as_variants` attribute
+    pub as_variants: Option<Span>,
+
+    /// Parsed from `bounded` attribute
+    pub bounded: Option<Span>,
+
+    /// Parsed from `required` attribute
+    pub required: Option<Span>,
+
+    /// Parsed from `trace` attribute
+    pub trace: Option<Span>,
+
+    /// Parsed from `secret` or `secret(...)` attribute
+    pub secret: Option<Secret>,
+
+    /// Parsed from `arena` attribute
+    pub arena: Option<Span>,
+
+    /// Parsed from `guarantee` attribute
+    pub guarantee: Option<Span>,
+
+    /// Parsed from `duplicates = "..."` attribute
+    pub duplicates: DuplicatePolicy,
+
+    /// Parsed from `display = "..."`, `try_from(...)`, `into(...)` and `serde(...)` attributes
+    pub derive_options: DeriveOptions,
+}
+
+/// Validator presets (e.g. `percentage`) that expand into a set of standard validators.
+pub trait ValidatorPresets: Sized {
+    /// Names of supported presets. Used to render the list of possible validation attributes.
+    const PRESETS: &'static [&'static str] = &[];
+
+    /// Expand the preset with the given name. Returns `None` if there is no such preset.
+    fn expand_preset(_ident: &Ident) -> Option<Vec<Self>> {
+        None
+    }
 }
 
 enum ValidateAttr<Validator: Parse + Kinded> {
     Standard(Validator),
+    Preset(Vec<Validator>),
+    Combinator(ValidatorCombinator<Validator>),
     Extra(ExtraValidateAttr),
 }
 
+/// Names of the combinators. `any` is an alias of `any_of`.
+const COMBINATORS: [&str; 5] = ["any_of", "any", "all", "not", "when"];
+
+/// Whether the input starts with `any_of(...)`, `any(...)`, `all(...)`, `not(...)` or `when(...)`.
+fn peek_combinator(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Ident>()
+        .is_ok_and(|ident| COMBINATORS.iter().any(|combinator| ident == combinator))
+        && fork.peek(Paren)
+}
+
+/// Parse `any_of(...)`, `any(...)`, `all(...)`, `not(...)` or `when(...)`.
+/// They can be nested, e.g. `not(any_of(...))` or `any(all(...), ...)`.
+fn parse_combinator<Validator: Parse>(
+    input: ParseStream,
+) -> syn::Result<ValidatorCombinator<Validator>> {
+    let ident: Ident = input.parse()?;
+    let content;
+    parenthesized!(content in input);
+    if ident == "when" {
+        return parse_when(ident, &content);
+    }
+    let items = content.parse_terminated(parse_combinator_item::<Validator>, Token![,])?;
+    let mut items: Vec<ValidatorCombinator<Validator>> = items.into_iter().collect();
+    if ident == "any_of" || ident == "any" {
+        if items.len() < 2 {
+            let msg = format!("`{ident}` requires at least two alternatives, e.g. `{ident}(len_char_max = 10, len_char_min = 20)`.");
+            return Err(syn::Error::new(ident.span(), msg));
+        }
+        Ok(ValidatorCombinator::AnyOf {
+            span: ident.span(),
+            alternatives: items,
+        })
+    } else if ident == "all" {
+        if items.len() < 2 {
+            let msg = "`all` requires at least two validators, e.g. `all(len_char_min = 3, len_char_max = 20)`.";
+            return Err(syn::Error::new(ident.span(), msg));
+        }
+        Ok(ValidatorCombinator::All {
+            span: ident.span(),
+            validators: items,
+        })
+    } else {
+        match (items.pop(), items.is_empty()) {
+            (Some(validator), true) => Ok(ValidatorCombinator::Not {
+                span: ident.span(),
+                validator: Box::new(validator),
+            }),
+            _ => {
+                let msg = "`not` requires exactly one validator, e.g. `not(regex = \"^admin\")`.\nUse `not(any_of(...))` to reject several alternatives.";
+                Err(syn::Error::new(ident.span(), msg))
+            }
+        }
+    }
+}
+
+/// Parse the content of `when(<condition>, then(<validators>))`.
+fn parse_when<Validator: Parse>(
+    ident: Ident,
+    content: ParseStream,
+) -> syn::Result<ValidatorCombinator<Validator>> {
+    const USAGE: &str = "`when` expects a condition followed by `then(...)`, e.g. `when(predicate = |s| s.starts_with('+'), then(len_char_max = 16))`.";
+
+    if content.is_empty() {
+        return Err(syn::Error::new(ident.span(), USAGE));
+    }
+    let condition = parse_combinator_item::<Validator>(content)?;
+    content.parse::<Token![,]>()?;
+    let then_ident: Ident = content.parse()?;
+    if then_ident != "then" {
+        return Err(syn::Error::new(then_ident.span(), USAGE));
+    }
+    let then_content;
+    parenthesized!(then_content in content);
+    let then = then_content.parse_terminated(parse_combinator_item::<Validator>, Token![,])?;
+    if then.is_empty() {
+        let msg = "`then(...)` requires at least one validator.";
+        return Err(syn::Error::new(then_ident.span(), msg));
+    }
+    if !content.is_empty() {
+        content.parse::<Token![,]>()?;
+    }
+    if !content.is_empty() {
+        return Err(content.error(USAGE));
+    }
+    Ok(ValidatorCombinator::When {
+        span: ident.span(),
+        condition: Box::new(condition),
+        then: then.into_iter().collect(),
+    })
+}
+
+fn parse_combinator_item<Validator: Parse>(
+    input: ParseStream,
+) -> syn::Result<ValidatorCombinator<Validator>> {
+    if peek_combinator(input) {
+        parse_combinator(input)
+    } else {
+        Ok(ValidatorCombinator::Validator(input.parse()?))
+    }
+}
+
 /// Non standard (custom) validation attributes.
-/// Responsible for parsing `error` and `with` attributes.
+/// Responsible for parsing `error`, `with`, `with_context`, `context` and `messages` attributes.
 #[derive(Debug, Kinded)]
 #[kinded(display = "snake_case")]
 enum ExtraValidateAttr {
     Error(ErrorTypePath),
     With(CustomFunction),
+    WithContext(CustomFunction),
+    Context(syn::Type),
+    Messages(ErrorMessages),
 }
 
 impl Parse for ExtraValidateAttr {
@@ -101,20 +263,46 @@ impl Parse for ExtraValidateAttr {
                 let custom_function: CustomFunction = input.parse()?;
                 Ok(ExtraValidateAttr::With(custom_function))
             }
+            ExtraValidateAttrKind::WithContext => {
+                let _eq: Token![=] = input.parse()?;
+                let custom_function: CustomFunction = input.parse()?;
+                Ok(ExtraValidateAttr::WithContext(custom_function))
+            }
+            ExtraValidateAttrKind::Context => {
+                let _eq: Token![=] = input.parse()?;
+                let context_type: syn::Type = input.parse()?;
+                Ok(ExtraValidateAttr::Context(context_type))
+            }
+            ExtraValidateAttrKind::Messages => {
+                let content;
+                parenthesized!(content in input);
+                let messages: ErrorMessages = content.parse()?;
+                Ok(ExtraValidateAttr::Messages(messages))
+            }
         }
     }
 }
 
 impl<Validator> Parse for ValidateAttr<Validator>
 where
-    Validator: Parse + Kinded,
+    Validator: Parse + Kinded + ValidatorPresets,
     <Validator as Kinded>::Kind: Kind + Display + 'static,
 {
     /// Try to parse either standard validation attributes or combination of `error` and `with` attributes.
     fn parse(input: ParseStream) -> syn::Result<Self> {
         // NOTE: ParseStream has interior mutability, so we want to try to parse validator,
         // but we don't want to advance the input if it fails.
-        if input.fork().parse::<Validator>().is_ok() {
+        let maybe_preset = input
+            .fork()
+            .parse::<Ident>()
+            .ok()
+            .and_then(|ident| Validator::expand_preset(&ident));
+        if let Some(validators) = maybe_preset {
+            let _preset: Ident = input.parse()?;
+            Ok(ValidateAttr::Preset(validators))
+        } else if peek_combinator(input) {
+            Ok(ValidateAttr::Combinator(parse_combinator(input)?))
+        } else if input.fork().parse::<Validator>().is_ok() {
             let validator: Validator = input.parse()?;
             Ok(ValidateAttr::Standard(validator))
         } else if input.fork().parse::<ExtraValidateAttr>().is_ok() {
@@ -123,21 +311,48 @@ where
         } else {
             // HACK: we want to prapagate the original error in case if it was `regex` attribute.
             // Most likely it was not parsed, because `regex` feature was not enabled.
-            if let Ok(ident) = input.fork().parse::<Ident>() {
-                if ident == "regex" {
+            // The same applies to known validators with invalid arguments, e.g. `in = []`.
+            if let Ok(ident) = input.fork().call(Ident::parse_any) {
+                let is_known_validator = <Validator as Kinded>::Kind::all()
+                    .iter()
+                    .any(|kind| ident == kind.to_string());
+                if ident == "regex" || is_known_validator {
                     // Parse again and return the original error
                     input.fork().parse::<Validator>()?;
                 }
+                if ident == "messages" {
+                    input.fork().parse::<ExtraValidateAttr>()?;
+                }
             }
 
             let possible_values: String = <Validator as Kinded>::Kind::all()
                 .iter()
                 .map(|k| format!("`{k}`"))
                 .filter(|s| s != "`phantom`") // filter out _Phantom variant
-                .chain(["`with`", "`error`"].iter().map(|s| s.to_string()))
+                .chain(
+                    Validator::PRESETS
+                        .iter()
+                        .map(|preset| format!("`{preset}`")),
+                )
+                .chain(
+                    [
+                        "`any_of`",
+                        "`any`",
+                        "`all`",
+                        "`not`",
+                        "`when`",
+                        "`with`",
+                        "`error`",
+                        "`with_context`",
+                        "`context`",
+                        "`messages`",
+                    ]
+                    .iter()
+                    .map(|s| s.to_string()),
+                )
                 .collect::<Vec<_>>()
                 .join(", ");
-            let ident: Ident = input.parse()?;
+            let ident: Ident = Ident::parse_any(input)?;
             let msg = format!("Unknown validation attribute: `{ident}`.\nPossible attributes are {possible_values}.");
             Err(syn::Error::new(ident.span(), msg))
         }
@@ -145,6 +360,7 @@ where
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum RawValidation<Validator> {
     Custom {
         with: CustomFunction,
@@ -152,12 +368,15 @@ pub enum RawValidation<Validator> {
     },
     Standard {
         validators: Vec<Validator>,
+        with_context: Option<WithContext>,
+        messages: ErrorMessages,
+        combinators: Vec<ValidatorCombinator<Validator>>,
     },
 }
 
 impl<Validator> Parse for RawValidation<Validator>
 where
-    Validator: Parse + Kinded,
+    Validator: Parse + Kinded + ValidatorPresets,
     <Validator as Kinded>::Kind: Kind + Display + 'static,
 {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -165,14 +384,24 @@ where
         let attrs: Vec<ValidateAttr<Validator>> = items.into_iter().collect();
 
         let mut validators: Vec<Validator> = Vec::new();
+        let mut combinators: Vec<ValidatorCombinator<Validator>> = Vec::new();
         let mut maybe_with: Option<CustomFunction> = None;
         let mut maybe_error: Option<ErrorTypePath> = None;
+        let mut maybe_with_context: Option<CustomFunction> = None;
+        let mut maybe_context: Option<syn::Type> = None;
+        let mut maybe_messages: Option<(Span, ErrorMessages)> = None;
 
         for attr in attrs {
             match attr {
                 ValidateAttr::Standard(validator) => {
                     validators.push(validator);
                 }
+                ValidateAttr::Preset(preset_validators) => {
+                    validators.extend(preset_validators);
+                }
+                ValidateAttr::Combinator(combinator) => {
+                    combinators.push(combinator);
+                }
                 ValidateAttr::Extra(extra_attr) => match extra_attr {
                     ExtraValidateAttr::Error(error) => {
                         if maybe_error.is_some() {
@@ -188,29 +417,118 @@ where
                         }
                         maybe_with = Some(with);
                     }
+                    ExtraValidateAttr::WithContext(with_context) => {
+                        if maybe_with_context.is_some() {
+                            let msg = "Duplicate `with_context` attribute";
+                            return Err(syn::Error::new(with_context.span(), msg));
+                        }
+                        maybe_with_context = Some(with_context);
+                    }
+                    ExtraValidateAttr::Context(context_type) => {
+                        if maybe_context.is_some() {
+                            let msg = "Duplicate `context` attribute";
+                            return Err(syn::Error::new(context_type.span(), msg));
+                        }
+                        maybe_context = Some(context_type);
+                    }
+                    ExtraValidateAttr::Messages(messages) => {
+                        let span = messages
+                            .messages
+                            .first()
+                            .map(|(name, _)| name.span())
+                            .unwrap_or_else(|| input.span());
+                        if maybe_messages.is_some() {
+                            let msg = "Duplicate `messages` attribute";
+                            return Err(syn::Error::new(span, msg));
+                        }
+                        maybe_messages = Some((span, messages));
+                    }
                 },
             }
         }
 
-        match (validators.len(), maybe_with, maybe_error) {
+        let with_context = match (maybe_with_context, maybe_context) {
+            (Some(with), Some(context_type)) => Some(WithContext { with, context_type }),
+            (Some(with), None) => {
+                let msg = "The `with_context` attribute requires an accompanying `context` attribute.\nPlease provide the type of the context, e.g. `context = Settings`.";
+                return Err(syn::Error::new(with.span(), msg));
+            }
+            (None, Some(context_type)) => {
+                let msg = "The `context` attribute requires an accompanying `with_context` attribute.\nPlease provide the function that validates the value against the context.";
+                return Err(syn::Error::new(context_type.span(), msg));
+            }
+            (None, None) => None,
+        };
+        if let Some(with_context) = &with_context {
+            if maybe_with.is_some() || maybe_error.is_some() {
+                let msg = "`with_context` cannot be combined with `with` and `error` attributes.";
+                return Err(syn::Error::new(with_context.with.span(), msg));
+            }
+        }
+        let messages = match maybe_messages {
+            Some((span, messages)) => {
+                if validators.is_empty() && combinators.is_empty() {
+                    let msg = "`messages` can be set only for standard validators, e.g. `messages(len_char_max = \"...\")`.";
+                    return Err(syn::Error::new(span, msg));
+                }
+                for (name, _) in &messages.messages {
+                    let is_declared = validators
+                        .iter()
+                        .any(|validator| *name == validator.kind().to_string());
+                    if !is_declared {
+                        let declared: Vec<String> = validators
+                            .iter()
+                            .map(|validator| format!("`{}`", validator.kind()))
+                            .collect();
+                        let msg = format!("`{name}` is not one of the validators of the type.\nMessages can be set for {}.", declared.join(", "));
+                        return Err(syn::Error::new(name.span(), msg));
+                    }
+                }
+                messages
+            }
+            None => ErrorMessages::default(),
+        };
+        if validators.is_empty() && combinators.is_empty() && with_context.is_some() {
+            return Ok(RawValidation::Standard {
+                validators,
+                with_context,
+                messages,
+                combinators,
+            });
+        }
+
+        match (
+            validators.len() + combinators.len(),
+            maybe_with,
+            maybe_error,
+        ) {
             (0, Some(with), Some(error)) => Ok(RawValidation::Custom { with, error }),
-            (0, Some(_), None) => {
+            (0, Some(with), None) => {
                 let msg = "The `with` attribute requires an accompanying `error` attribute.\nPlease provide the error type that the `with` validation function returns.";
-                Err(syn::Error::new(input.span(), msg))
+                Err(syn::Error::new(with.span(), msg))
             }
             (0, None, Some(error_type)) => {
                 let msg = format!("The `error` attribute requires an accompanying `with` attribute.\nPlease provide the validation function that returns Result<(), {error_type}>.");
-                Err(syn::Error::new(input.span(), msg))
+                Err(syn::Error::new(error_type.span(), msg))
             }
             (0, None, None) => {
                 let msg = "At least one validator must be specified";
                 Err(syn::Error::new(input.span(), msg))
             }
-            (_, None, None) => Ok(RawValidation::Standard { validators }),
-            (_, _, _) => {
+            (_, None, None) => Ok(RawValidation::Standard {
+                validators,
+                with_context,
+                messages,
+                combinators,
+            }),
+            (_, maybe_with, maybe_error) => {
                 let msg =
                     "`with` and `error` attributes cannot be used mixed with other validators.";
-                Err(syn::Error::new(input.span(), msg))
+                let span = maybe_with
+                    .map(|with| with.span())
+                    .or_else(|| maybe_error.map(|error| error.span()))
+                    .unwrap_or_else(|| input.span());
+                Err(syn::Error::new(span, msg))
             }
         }
     }
@@ -222,21 +540,147 @@ impl<Sanitizer, Validator> Default for ParseableAttributes<Sanitizer, Validator>
         Self {
             sanitizers: vec![],
             validation: None,
+            legacy: None,
             new_unchecked: NewUnchecked::Off,
             default: None,
             derive_traits: vec![],
+            parse_money: None,
+            store_as: None,
+            storage: None,
+            as_variants: None,
+            bounded: None,
+            required: None,
+            trace: None,
+            secret: None,
+            arena: None,
+            guarantee: None,
+            duplicates: DuplicatePolicy::default(),
+            derive_options: DeriveOptions::default(),
         }
     }
 }
 
+/// Top level attributes accepted by `#[nutype(...)]`.
+const ATTRIBUTES: &[&str] = &[
+    "sanitize",
+    "validate",
+    "derive",
+    "default",
+    "new_unchecked",
+    "parse_money",
+    "store_as",
+    "guarantee",
+    "as_variants",
+    "bounded",
+    "required",
+    "trace",
+    "arena",
+    "secret",
+    "schema",
+    "normalize_for_eq",
+    "hash_with",
+    "nohash",
+    "profile",
+    "duplicates",
+    "ffi",
+    "wasm",
+    "repr_transparent",
+    "bytes",
+    "eq_str",
+    "total_order",
+    "constructor",
+    "constructor_visibility",
+    "getter",
+    "refines",
+    "storage",
+    "display",
+    "try_from",
+    "serde",
+    "legacy",
+    "into",
+    "test_examples",
+    "encoding",
+    "renamed_from",
+    "since",
+    "on_invalid",
+    "no_clone",
+    "lossy",
+];
+
+/// Is it a version like `2.0.0` or `2.0.0-rc.1`? `#[deprecated(since = ...)]` with other values is
+/// rejected by clippy.
+fn is_semver(version: &str) -> bool {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn unknown_attribute_error(ident: &Ident) -> syn::Error {
+    let name = ident.to_string();
+    let possible_values = ATTRIBUTES
+        .iter()
+        .map(|attr| format!("`{attr}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let msg = match suggest(&name, ATTRIBUTES) {
+        Some(suggestion) => format!(
+            "Unknown attribute `{name}`. Did you mean `{suggestion}`?\nPossible attributes are {possible_values}."
+        ),
+        None => format!("Unknown attribute `{name}`.\nPossible attributes are {possible_values}."),
+    };
+    syn::Error::new(ident.span(), msg)
+}
+
+/// Find the candidate closest to a misspelled name, if it is close enough to be a typo.
+fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 impl<Sanitizer, Validator> Parse for ParseableAttributes<Sanitizer, Validator>
 where
     Sanitizer: Parse,
-    Validator: Parse + Kinded,
+    Validator: Parse + Kinded + ValidatorPresets,
     <Validator as Kinded>::Kind: Kind + Display + 'static,
 {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut attrs = ParseableAttributes::default();
+        let mut maybe_try_from_span: Option<Span> = None;
+        let mut maybe_into_span: Option<Span> = None;
+        let mut maybe_serde_span: Option<Span> = None;
+        let mut maybe_legacy_span: Option<Span> = None;
+        let mut maybe_secret_span: Option<Span> = None;
+        let mut maybe_schema_span: Option<Span> = None;
+        let mut maybe_normalize_for_eq: Option<(Span, CustomFunction)> = None;
+        let mut maybe_hash_with: Option<(Span, CustomFunction)> = None;
+        let mut maybe_on_invalid: Option<(Span, CustomFunction)> = None;
+        let mut maybe_profile: Option<Profile> = None;
+        let mut maybe_renamed_from: Option<Ident> = None;
+        let mut maybe_since: Option<syn::LitStr> = None;
 
         while !input.is_empty() {
             let ident: Ident = input.parse()?;
@@ -258,6 +702,10 @@ where
                 if input.peek(Paren) {
                     let content;
                     parenthesized!(content in input);
+                    if content.is_empty() {
+                        let msg = "At least one validator must be specified";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
                     let validation: RawValidation<Validator> = content.parse()?;
                     attrs.validation = Some(validation);
                 } else {
@@ -299,9 +747,214 @@ where
                         return Err(syn::Error::new(ident.span(), msg));
                     }
                 }
+            } else if ident == "parse_money" {
+                let parse_money = if input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    content.parse()?
+                } else {
+                    ParseMoney::default()
+                };
+                attrs.parse_money = Some(SpannedParseMoney::new(parse_money, ident.span()));
+            } else if ident == "store_as" {
+                if input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    let store_as: StoreAs = content.parse()?;
+                    attrs.store_as = Some(SpannedStoreAs::new(store_as, ident.span()));
+                } else {
+                    let msg = concat!(
+                        "`store_as` must be used with parenthesis.\n",
+                        "For example:\n\n",
+                        "    store_as(u8, encode = encode_color, decode = decode_color)\n\n"
+                    );
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+            } else if ident == "guarantee" {
+                attrs.guarantee = Some(ident.span());
+            } else if ident == "as_variants" {
+                attrs.as_variants = Some(ident.span());
+            } else if ident == "bounded" {
+                attrs.bounded = Some(ident.span());
+            } else if ident == "required" {
+                attrs.required = Some(ident.span());
+            } else if ident == "trace" {
+                cfg_if! {
+                    if #[cfg(feature = "tracing")] {
+                        attrs.trace = Some(ident.span());
+                    } else {
+                        let msg = "To use `trace`, the feature `tracing` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else if ident == "arena" {
+                cfg_if! {
+                    if #[cfg(feature = "bumpalo")] {
+                        attrs.arena = Some(ident.span());
+                    } else {
+                        let msg = "To use `arena`, the feature `bumpalo` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else if ident == "secret" {
+                let secret = if input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    content.parse()?
+                } else {
+                    Secret::default()
+                };
+                attrs.secret = Some(secret);
+                maybe_secret_span = Some(ident.span());
+            } else if ident == "schema" {
+                if input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    attrs.derive_options.schema = Some(content.parse()?);
+                    maybe_schema_span = Some(ident.span());
+                } else {
+                    let msg = concat!(
+                        "`schema` must be used with parenthesis.\n",
+                        "For example:\n\n",
+                        "    schema(format = \"email\", example = \"alice@example.com\")\n\n"
+                    );
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+            } else if ident == "normalize_for_eq" {
+                let _eq: Token![=] = input.parse()?;
+                let with: CustomFunction = input.parse()?;
+                maybe_normalize_for_eq = Some((ident.span(), with));
+            } else if ident == "hash_with" {
+                let _eq: Token![=] = input.parse()?;
+                let with: CustomFunction = input.parse()?;
+                maybe_hash_with = Some((ident.span(), with));
+            } else if ident == "on_invalid" {
+                let _eq: Token![=] = input.parse()?;
+                let callback: CustomFunction = input.parse()?;
+                maybe_on_invalid = Some((ident.span(), callback));
+            } else if ident == "nohash" {
+                cfg_if! {
+                    if #[cfg(feature = "nohash-hasher")] {
+                        attrs.derive_options.nohash = Some(ident.span());
+                    } else {
+                        let msg = "To use `nohash`, the feature `nohash-hasher` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else if ident == "profile" {
+                let _eq: Token![=] = input.parse()?;
+                let name: syn::LitStr = input.parse()?;
+                maybe_profile = Some(parse_profile_name(&name.value(), name.span())?);
+            } else if ident == "duplicates" {
+                let _eq: Token![=] = input.parse()?;
+                let name: syn::LitStr = input.parse()?;
+                attrs.duplicates = parse_duplicate_policy_name(&name.value(), name.span())?;
+            } else if ident == "encoding" {
+                let _eq: Token![=] = input.parse()?;
+                let name: syn::LitStr = input.parse()?;
+                let encoding = parse_encoding_name(&name.value(), name.span())?;
+                attrs.derive_options.encoding = Some(SpannedItem::new(encoding, name.span()));
+            } else if ident == "ffi" {
+                attrs.derive_options.ffi = Some(ident.span());
+            } else if ident == "wasm" {
+                cfg_if! {
+                    if #[cfg(feature = "wasm-bindgen")] {
+                        attrs.derive_options.wasm = Some(ident.span());
+                    } else {
+                        let msg = "To use `wasm`, the feature `wasm-bindgen` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else if ident == "repr_transparent" {
+                attrs.derive_options.repr_transparent = Some(ident.span());
+            } else if ident == "no_clone" {
+                attrs.derive_options.no_clone = Some(ident.span());
+            } else if ident == "lossy" {
+                cfg_if! {
+                    if #[cfg(feature = "std")] {
+                        attrs.derive_options.lossy = Some(ident.span());
+                    } else {
+                        let msg = "To use `lossy`, the feature `std` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else if ident == "bytes" {
+                attrs.derive_options.bytes = Some(ident.span());
+            } else if ident == "eq_str" {
+                attrs.derive_options.eq_str = Some(ident.span());
+            } else if ident == "total_order" {
+                attrs.derive_options.total_order = Some(ident.span());
+            } else if ident == "constructor" {
+                let _eq: Token![=] = input.parse()?;
+                attrs.derive_options.method_names.constructor = Some(input.parse()?);
+            } else if ident == "constructor_visibility" {
+                let _eq: Token![=] = input.parse()?;
+                let visibility: syn::Visibility = input.parse()?;
+                if matches!(visibility, syn::Visibility::Inherited) {
+                    let msg = "`constructor_visibility` expects a visibility, e.g. `constructor_visibility = pub(crate)`.\nUse `pub(self)` to keep the constructors private to the module of the type.";
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+                attrs.derive_options.method_names.constructor_visibility = Some(visibility);
+            } else if ident == "getter" {
+                let _eq: Token![=] = input.parse()?;
+                attrs.derive_options.method_names.getter = Some(input.parse()?);
+            } else if ident == "refines" {
+                let _eq: Token![=] = input.parse()?;
+                attrs.derive_options.refines = Some(input.parse()?);
+            } else if ident == "storage" {
+                let _eq: Token![=] = input.parse()?;
+                let storage_type: syn::Type = input.parse()?;
+                let span = storage_type.span();
+                attrs.storage = Some(SpannedItem::new(storage_type, span));
+            } else if ident == "display" {
+                let _eq: Token![=] = input.parse()?;
+                let format: syn::LitStr = input.parse()?;
+                attrs.derive_options.display = Some(format);
+            } else if ident == "try_from" {
+                let source_types = parse_type_list(&ident, input, "try_from(String, &str)")?;
+                attrs.derive_options.try_from = Some(source_types);
+                maybe_try_from_span = Some(ident.span());
+            } else if ident == "serde" {
+                if input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    parse_serde_options(&content, &mut attrs.derive_options)?;
+                    maybe_serde_span = Some(ident.span());
+                } else {
+                    let msg = concat!(
+                        "`serde` must be used with parenthesis.\n",
+                        "For example:\n\n",
+                        "    serde(field = \"value\")\n\n"
+                    );
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+            } else if ident == "legacy" {
+                attrs.legacy = Some(parse_legacy(&ident, input)?);
+                maybe_legacy_span = Some(ident.span());
+            } else if ident == "test_examples" {
+                attrs.derive_options.test_examples = Some(parse_test_examples(&ident, input)?);
+            } else if ident == "renamed_from" {
+                let _eq: Token![=] = input.parse()?;
+                let name: syn::LitStr = input.parse()?;
+                let name = name.parse::<Ident>().map_err(|_| {
+                    let msg = "`renamed_from` expects the previous name of the type, e.g. `renamed_from = \"Nickname\"`.";
+                    syn::Error::new(name.span(), msg)
+                })?;
+                maybe_renamed_from = Some(name);
+            } else if ident == "since" {
+                let _eq: Token![=] = input.parse()?;
+                let since: syn::LitStr = input.parse()?;
+                if !is_semver(&since.value()) {
+                    let msg = "`since` expects a semver version, e.g. `since = \"2.0.0\"`.";
+                    return Err(syn::Error::new(since.span(), msg));
+                }
+                maybe_since = Some(since);
+            } else if ident == "into" {
+                let target_types = parse_type_list(&ident, input, "into(String, i64)")?;
+                attrs.derive_options.into = Some(target_types);
+                maybe_into_span = Some(ident.span());
             } else {
-                let msg = format!("Unknown attribute `{ident}`");
-                return Err(syn::Error::new(ident.span(), msg));
+                return Err(unknown_attribute_error(&ident));
             }
 
             // Parse `,` unless it's the end of the stream
@@ -310,55 +963,782 @@ where
             }
         }
 
+        match (maybe_renamed_from, maybe_since) {
+            (Some(name), since) => {
+                attrs.derive_options.renamed_from = Some(RenamedFrom { name, since });
+            }
+            (None, Some(since)) => {
+                let msg =
+                    "`since` requires `renamed_from`, that sets the previous name of the type.";
+                return Err(syn::Error::new(since.span(), msg));
+            }
+            (None, None) => {}
+        }
+
+        let is_derived = |derive_trait: DeriveTrait| {
+            attrs
+                .derive_traits
+                .iter()
+                .any(|spanned| spanned.item == derive_trait)
+        };
+        if let Some(default_trait) = attrs
+            .derive_traits
+            .iter()
+            .find(|spanned| spanned.item == DeriveTrait::Default)
+        {
+            if attrs.default.is_none() {
+                let msg = "Trait `Default` is derived, but `default = ` parameter is missing in #[nutype] macro";
+                return Err(syn::Error::new(default_trait.span, msg));
+            }
+        }
+        if let Some(ref format) = attrs.derive_options.display {
+            if !is_derived(DeriveTrait::Display) {
+                let msg = "`display` format requires `Display` to be derived.\nAdd `Display` to `derive(...)`.";
+                return Err(syn::Error::new(format.span(), msg));
+            }
+        }
+        if let Some(try_from_span) = maybe_try_from_span {
+            if !is_derived(DeriveTrait::TryFrom) {
+                let msg = "`try_from(...)` requires `TryFrom` to be derived.\nAdd `TryFrom` to `derive(...)`.";
+                return Err(syn::Error::new(try_from_span, msg));
+            }
+        }
+        if let Some(into_span) = maybe_into_span {
+            if !is_derived(DeriveTrait::Into) {
+                let msg =
+                    "`into(...)` requires `Into` to be derived.\nAdd `Into` to `derive(...)`.";
+                return Err(syn::Error::new(into_span, msg));
+            }
+        }
+        if let Some(schema_span) = maybe_schema_span {
+            if !is_derived(DeriveTrait::SchemarsJsonSchema) {
+                let msg = "`schema(...)` requires `JsonSchema` to be derived.\nAdd `JsonSchema` to `derive(...)`.";
+                return Err(syn::Error::new(schema_span, msg));
+            }
+        }
+        if let Some(test_examples) = &attrs.derive_options.test_examples {
+            if attrs.validation.is_none() {
+                let msg = "`test_examples(...)` requires `validate(...)`, otherwise every value is valid.";
+                return Err(syn::Error::new(test_examples.span, msg));
+            }
+        }
+        if let Some((on_invalid_span, callback)) = maybe_on_invalid {
+            if attrs.validation.is_none() && attrs.required.is_none() {
+                let msg = "`on_invalid` requires `validate(...)`, otherwise no value is rejected.";
+                return Err(syn::Error::new(on_invalid_span, msg));
+            }
+            // The rejected value of a `secret` type is not exposed to the callback.
+            attrs.derive_options.on_invalid = Some(OnInvalid {
+                callback,
+                with_value: attrs.secret.is_none(),
+            });
+        }
+        if let Some(legacy_span) = maybe_legacy_span {
+            if attrs.validation.is_none() {
+                let msg = "`legacy(...)` requires `validate(...)` with the current rules.";
+                return Err(syn::Error::new(legacy_span, msg));
+            }
+        }
+        if let Some(serde_span) = maybe_serde_span {
+            if !is_derived(DeriveTrait::SerdeSerialize)
+                && !is_derived(DeriveTrait::SerdeDeserialize)
+            {
+                let msg = "`serde(...)` requires `Serialize` or `Deserialize` to be derived.";
+                return Err(syn::Error::new(serde_span, msg));
+            }
+        }
+        if let Some(borrow_span) = attrs.derive_options.serde_borrow {
+            if !is_derived(DeriveTrait::SerdeDeserialize) {
+                let msg = "`serde(borrow)` requires `Deserialize` to be derived.";
+                return Err(syn::Error::new(borrow_span, msg));
+            }
+        }
+        if let (Some(secret), Some(secret_span)) = (attrs.secret, maybe_secret_span) {
+            if let Some(serialize_trait) = attrs
+                .derive_traits
+                .iter()
+                .find(|spanned| {
+                    matches!(
+                        spanned.item,
+                        DeriveTrait::SerdeSerialize
+                            | DeriveTrait::BorshSerialize
+                            | DeriveTrait::RkyvArchive
+                    )
+                })
+                .filter(|_| !secret.serialize)
+            {
+                let trait_name = match serialize_trait.item {
+                    DeriveTrait::BorshSerialize => "BorshSerialize",
+                    DeriveTrait::RkyvArchive => "Archive",
+                    _ => "Serialize",
+                };
+                let msg = format!(
+                    "Deriving `{trait_name}` for a `secret` type would expose the value.\nUse `secret(serialize)` to allow it explicitly."
+                );
+                return Err(syn::Error::new(serialize_trait.span, msg));
+            }
+            if let Some(ref format) = attrs.derive_options.display {
+                let msg = "`display` format cannot be used with `secret`, because `Display` of a secret type is redacted.";
+                return Err(syn::Error::new(format.span(), msg));
+            }
+            if let Some(trace_span) = attrs.trace {
+                let msg =
+                    "`trace` cannot be used with `secret`, because it would log the secret value.";
+                return Err(syn::Error::new(trace_span, msg));
+            }
+            if let Some(copy_trait) = attrs
+                .derive_traits
+                .iter()
+                .find(|spanned| spanned.item == DeriveTrait::Copy)
+                .filter(|_| secret.zeroize)
+            {
+                let msg = "`Copy` cannot be derived with `secret(zeroize)`, because the value is wiped on drop.";
+                return Err(syn::Error::new(copy_trait.span, msg));
+            }
+            if secret.zeroize && (attrs.store_as.is_some() || attrs.storage.is_some()) {
+                let msg = "`secret(zeroize)` cannot be used together with `store_as` or `storage`.";
+                return Err(syn::Error::new(secret_span, msg));
+            }
+        }
+        if attrs.store_as.is_some() {
+            // These traits expose the stored value directly, but it is not the inner value anymore.
+            let incompatible_trait = attrs.derive_traits.iter().find(|spanned| {
+                matches!(
+                    spanned.item,
+                    DeriveTrait::AsRef
+                        | DeriveTrait::Deref
+                        | DeriveTrait::Borrow
+                        | DeriveTrait::PartialOrd
+                        | DeriveTrait::Ord
+                        | DeriveTrait::SchemarsJsonSchema
+                )
+            });
+            if let Some(incompatible_trait) = incompatible_trait {
+                let msg = concat!(
+                    "`AsRef`, `Deref`, `Borrow`, `PartialOrd`, `Ord` and `JsonSchema` cannot be derived together with `store_as`, ",
+                    "because the inner value is not kept in memory as it is."
+                );
+                return Err(syn::Error::new(incompatible_trait.span, msg));
+            }
+        }
+        if let Some(archive_trait) = attrs
+            .derive_traits
+            .iter()
+            .find(|spanned| spanned.item == DeriveTrait::RkyvArchive)
+        {
+            if attrs.store_as.is_some() || attrs.storage.is_some() {
+                let msg = "`Archive` cannot be derived together with `store_as` or `storage`, because the value is archived as the inner type.";
+                return Err(syn::Error::new(archive_trait.span, msg));
+            }
+        }
+        if attrs.derive_options.no_clone.is_some() {
+            if let Some(clone_trait) = attrs
+                .derive_traits
+                .iter()
+                .find(|spanned| matches!(spanned.item, DeriveTrait::Clone | DeriveTrait::Copy))
+            {
+                let msg = format!(
+                    "`{:?}` cannot be derived for a `no_clone` type, because its values must not be duplicated.",
+                    clone_trait.item
+                );
+                return Err(syn::Error::new(clone_trait.span, msg));
+            }
+        }
+        if let Some(value_parser_trait) = attrs
+            .derive_traits
+            .iter()
+            .find(|spanned| spanned.item == DeriveTrait::ClapValueParserFactory)
+        {
+            let has_clone = attrs
+                .derive_traits
+                .iter()
+                .any(|spanned| spanned.item == DeriveTrait::Clone);
+            if !has_clone {
+                let msg = "`ValueParserFactory` requires `Clone`, because clap stores the parsed arguments as cloneable values.\nAdd `Clone` to `derive(...)`.";
+                return Err(syn::Error::new(value_parser_trait.span, msg));
+            }
+        }
+        if let Some(ffi_span) = attrs.derive_options.ffi {
+            if attrs.store_as.is_some() || attrs.storage.is_some() {
+                let msg = "`ffi` cannot be used together with `store_as` or `storage`, because the value must have the layout of the inner type.";
+                return Err(syn::Error::new(ffi_span, msg));
+            }
+        }
+        if let Some(repr_transparent_span) = attrs.derive_options.repr_transparent {
+            if attrs.store_as.is_some() || attrs.storage.is_some() {
+                let msg = "`repr_transparent` cannot be used together with `store_as` or `storage`, because the value must have the layout of the inner type.";
+                return Err(syn::Error::new(repr_transparent_span, msg));
+            }
+        }
+        if let Some(wasm_span) = attrs.derive_options.wasm {
+            if attrs.store_as.is_some() || attrs.storage.is_some() {
+                let msg = "`wasm` cannot be used together with `store_as` or `storage`, because the value is passed to JS as the inner type.";
+                return Err(syn::Error::new(wasm_span, msg));
+            }
+        }
+        if let Some((hash_with_span, with)) = maybe_hash_with {
+            if maybe_normalize_for_eq.is_some() {
+                let msg = "`hash_with` cannot be used together with `normalize_for_eq`, because both define `Hash`.";
+                return Err(syn::Error::new(hash_with_span, msg));
+            }
+            if let Some(borrow_trait) = attrs
+                .derive_traits
+                .iter()
+                .find(|spanned| spanned.item == DeriveTrait::Borrow)
+            {
+                let msg = "`Borrow` cannot be derived with `hash_with`, because `Hash` of the borrowed value would not match.";
+                return Err(syn::Error::new(borrow_trait.span, msg));
+            }
+            // `Hash` is implemented with the function, so it's not derived by the inner type.
+            let derive_traits_len = attrs.derive_traits.len();
+            attrs
+                .derive_traits
+                .retain(|spanned| spanned.item != DeriveTrait::Hash);
+            if attrs.derive_traits.len() == derive_traits_len {
+                let msg = "`hash_with` requires `Hash` to be derived.";
+                return Err(syn::Error::new(hash_with_span, msg));
+            }
+            attrs.derive_options.hash_with = Some(with);
+        }
+        if let Some(nohash_span) = attrs.derive_options.nohash {
+            let has_hash = attrs.derive_options.hash_with.is_some()
+                || attrs
+                    .derive_traits
+                    .iter()
+                    .any(|spanned| spanned.item == DeriveTrait::Hash);
+            if !has_hash {
+                let msg = "`nohash` requires `Hash` to be derived.";
+                return Err(syn::Error::new(nohash_span, msg));
+            }
+        }
+        if let Some((normalize_span, with)) = maybe_normalize_for_eq {
+            if let Some(borrow_trait) = attrs
+                .derive_traits
+                .iter()
+                .find(|spanned| spanned.item == DeriveTrait::Borrow)
+            {
+                let msg = "`Borrow` cannot be derived with `normalize_for_eq`, because `Eq` and `Hash` of the borrowed value would not match.";
+                return Err(syn::Error::new(borrow_trait.span, msg));
+            }
+            if let Some(eq_str_span) = attrs.derive_options.eq_str {
+                let msg = "`eq_str` cannot be used together with `normalize_for_eq`, because `str` is compared with the value as it is.";
+                return Err(syn::Error::new(eq_str_span, msg));
+            }
+            // The comparison traits are implemented with the normalized value, so they are not
+            // derived by the inner type.
+            let (normalized_traits, derive_traits): (Vec<_>, Vec<_>) =
+                attrs.derive_traits.into_iter().partition(|spanned| {
+                    matches!(
+                        spanned.item,
+                        DeriveTrait::PartialEq
+                            | DeriveTrait::Eq
+                            | DeriveTrait::Hash
+                            | DeriveTrait::PartialOrd
+                            | DeriveTrait::Ord
+                    )
+                });
+            if normalized_traits.is_empty() {
+                let msg = "`normalize_for_eq` requires `PartialEq`, `Eq`, `Hash`, `PartialOrd` or `Ord` to be derived.";
+                return Err(syn::Error::new(normalize_span, msg));
+            }
+            attrs.derive_traits = derive_traits;
+            attrs.derive_options.normalize_for_eq = Some(NormalizeForEq {
+                with,
+                traits: normalized_traits
+                    .into_iter()
+                    .map(|spanned| spanned.item)
+                    .collect(),
+            });
+        }
+        if let Some(ref base_type) = attrs.derive_options.refines {
+            if !attrs.sanitizers.is_empty() {
+                let msg = "`refines` cannot be used together with `sanitize(...)`, because the value taken from the refined type is not sanitized again.";
+                return Err(syn::Error::new_spanned(base_type, msg));
+            }
+        }
+        attrs.derive_options.profile = match maybe_profile {
+            Some(profile) => profile,
+            None => parse_crate_profile()?,
+        };
+
         Ok(attrs)
     }
 }
 
+/// The profile of the types without `profile = "..."`. It's set for the whole build with
+/// `NUTYPE_PROFILE` environment variable, e.g. in `[env]` section of `.cargo/config.toml`.
+fn parse_crate_profile() -> syn::Result<Profile> {
+    match option_env!("NUTYPE_PROFILE") {
+        Some(name) => parse_profile_name(name, Span::call_site()).map_err(|err| {
+            let msg =
+                format!("{err}\nThe profile is set with `NUTYPE_PROFILE` environment variable.");
+            syn::Error::new(err.span(), msg)
+        }),
+        None => Ok(Profile::Default),
+    }
+}
+
+fn parse_profile_name(name: &str, span: Span) -> syn::Result<Profile> {
+    Profile::from_name(name).ok_or_else(|| {
+        let possible = Profile::NAMES
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!("Unknown profile `{name}`.\nPossible profiles are {possible}.");
+        syn::Error::new(span, msg)
+    })
+}
+
+fn parse_duplicate_policy_name(name: &str, span: Span) -> syn::Result<DuplicatePolicy> {
+    DuplicatePolicy::from_name(name).ok_or_else(|| {
+        let possible = DuplicatePolicy::NAMES
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!("Unknown duplicates policy `{name}`.\nPossible policies are {possible}.");
+        syn::Error::new(span, msg)
+    })
+}
+
+fn parse_encoding_name(name: &str, span: Span) -> syn::Result<Encoding> {
+    let encoding = Encoding::from_name(name).ok_or_else(|| {
+        let possible = Encoding::NAMES
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!("Unknown encoding `{name}`.\nPossible encodings are {possible}.");
+        syn::Error::new(span, msg)
+    })?;
+    if !encoding.is_enabled() {
+        let feature = encoding.feature();
+        let msg = format!("To use `encoding = \"{name}\"`, the feature `{feature}` of the crate `nutype` must be enabled.");
+        return Err(syn::Error::new(span, msg));
+    }
+    Ok(encoding)
+}
+
+/// Parse a non-empty list of types in parenthesis, e.g. `try_from(String, &str)`.
+fn parse_type_list(
+    ident: &Ident,
+    input: ParseStream,
+    example: &str,
+) -> syn::Result<Vec<syn::Type>> {
+    if input.peek(Paren) {
+        let content;
+        parenthesized!(content in input);
+        let items = content.parse_terminated(syn::Type::parse, Token![,])?;
+        let types: Vec<syn::Type> = items.into_iter().collect();
+        if types.is_empty() {
+            let msg = format!("`{ident}` requires at least one type.");
+            return Err(syn::Error::new(ident.span(), msg));
+        }
+        Ok(types)
+    } else {
+        let msg =
+            format!("`{ident}` must be used with parenthesis.\nFor example:\n\n    {example}\n\n");
+        Err(syn::Error::new(ident.span(), msg))
+    }
+}
+
+/// Parse the previous rule set, e.g. `legacy(validate(len_char_max = 100))`.
+fn parse_test_examples(ident: &Ident, input: ParseStream) -> syn::Result<TestExamples> {
+    if !input.peek(Paren) {
+        let msg = concat!(
+            "`test_examples` must be used with parenthesis.\n",
+            "For example:\n\n",
+            "    test_examples(valid = [\"a@b.c\"], invalid = [\"\", \"x\"])\n\n"
+        );
+        return Err(syn::Error::new(ident.span(), msg));
+    }
+    let content;
+    parenthesized!(content in input);
+    let mut test_examples = TestExamples {
+        valid: Vec::new(),
+        invalid: Vec::new(),
+        span: ident.span(),
+    };
+    while !content.is_empty() {
+        let key: Ident = content.parse()?;
+        let examples = if key == "valid" {
+            &mut test_examples.valid
+        } else if key == "invalid" {
+            &mut test_examples.invalid
+        } else {
+            let msg = format!("Unknown `test_examples` attribute `{key}`.\nPossible attributes are `valid` and `invalid`.");
+            return Err(syn::Error::new(key.span(), msg));
+        };
+        if !examples.is_empty() {
+            let msg = format!("Duplicated `{key}` in `test_examples(...)`.");
+            return Err(syn::Error::new(key.span(), msg));
+        }
+        let _eq: Token![=] = content.parse()?;
+        let array: syn::ExprArray = content.parse()?;
+        examples.extend(array.elems);
+
+        if !content.is_empty() {
+            let _comma: Token![,] = content.parse()?;
+        }
+    }
+    Ok(test_examples)
+}
+
+fn parse_legacy<Validator>(
+    ident: &Ident,
+    input: ParseStream,
+) -> syn::Result<RawValidation<Validator>>
+where
+    Validator: Parse + Kinded + ValidatorPresets,
+    <Validator as Kinded>::Kind: Kind + Display + 'static,
+{
+    let example = concat!(
+        "`legacy` must be used with `validate(...)` in parenthesis.\n",
+        "For example:\n\n",
+        "    legacy(validate(len_char_max = 100))\n\n"
+    );
+    if !input.peek(Paren) {
+        return Err(syn::Error::new(ident.span(), example));
+    }
+    let content;
+    parenthesized!(content in input);
+    let validate_ident: Ident = match content.parse() {
+        Ok(validate_ident) if validate_ident == "validate" && content.peek(Paren) => validate_ident,
+        _ => return Err(syn::Error::new(ident.span(), example)),
+    };
+    let validators_content;
+    parenthesized!(validators_content in content);
+    if validators_content.is_empty() {
+        let msg = "At least one validator must be specified";
+        return Err(syn::Error::new(validate_ident.span(), msg));
+    }
+    let legacy: RawValidation<Validator> = validators_content.parse()?;
+    if let RawValidation::Custom { with, .. } = &legacy {
+        let msg =
+            "`legacy(validate(...))` supports only standard validators, not `with` and `error`.";
+        return Err(syn::Error::new(with.span(), msg));
+    }
+    if let RawValidation::Standard {
+        with_context: Some(with_context),
+        ..
+    } = &legacy
+    {
+        let msg = "`legacy(validate(...))` does not support `with_context`.";
+        return Err(syn::Error::new(with_context.with.span(), msg));
+    }
+    if let RawValidation::Standard { combinators, .. } = &legacy {
+        if let Some(combinator) = combinators.first() {
+            let msg = "`legacy(validate(...))` does not support `any_of`, `not` and `when`.";
+            return Err(syn::Error::new(combinator.span(), msg));
+        }
+    }
+    if !content.is_empty() {
+        return Err(syn::Error::new(content.span(), example));
+    }
+    Ok(legacy)
+}
+
+/// Parse the content of `serde(...)`: the representation (`transparent` or `field = "..."`)
+/// optionally combined with `borrow`.
+fn parse_serde_options(input: ParseStream, derive_options: &mut DeriveOptions) -> syn::Result<()> {
+    let mut has_mode = false;
+    loop {
+        let ident: Ident = input.parse()?;
+        if ident == "borrow" {
+            if derive_options.serde_borrow.is_some() {
+                let msg = "Duplicated `borrow` in `serde(...)`.";
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+            derive_options.serde_borrow = Some(ident.span());
+        } else {
+            let serde_mode = if ident == "transparent" {
+                SerdeMode::Transparent
+            } else if ident == "field" {
+                let _eq: Token![=] = input.parse()?;
+                let field_name: syn::LitStr = input.parse()?;
+                SerdeMode::Field(field_name)
+            } else {
+                let msg = format!("Unknown `serde` attribute `{ident}`.\nPossible attributes are `transparent`, `field`, `borrow`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            };
+            if has_mode {
+                let msg = "`serde` accepts only one of `transparent` or `field = \"...\"`.";
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+            derive_options.serde = serde_mode;
+            has_mode = true;
+        }
+        if input.is_empty() {
+            return Ok(());
+        }
+        let _comma: Token![,] = input.parse()?;
+    }
+}
+
+impl Parse for StoreAs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let storage_type: syn::Type = input.parse()?;
+        let mut maybe_encode: Option<CustomFunction> = None;
+        let mut maybe_decode: Option<CustomFunction> = None;
+        while !input.is_empty() {
+            let _comma: Token![,] = input.parse()?;
+            if input.is_empty() {
+                break;
+            }
+            let ident: Ident = input.parse()?;
+            let _eq: Token![=] = input.parse()?;
+            if ident == "encode" {
+                maybe_encode = Some(input.parse()?);
+            } else if ident == "decode" {
+                maybe_decode = Some(input.parse()?);
+            } else {
+                let msg = format!("Unknown `store_as` attribute `{ident}`.\nPossible attributes are `encode` and `decode`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+        }
+        match (maybe_encode, maybe_decode) {
+            (Some(encode), Some(decode)) => Ok(StoreAs {
+                storage_type,
+                encode,
+                decode,
+                derefs_to_str: false,
+            }),
+            _ => {
+                let msg = concat!(
+                    "`store_as` requires both `encode` and `decode` functions.\n",
+                    "For example:\n\n",
+                    "    store_as(u8, encode = encode_color, decode = decode_color)\n\n"
+                );
+                Err(syn::Error::new(storage_type.span(), msg))
+            }
+        }
+    }
+}
+
+impl Parse for Secret {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut secret = Secret::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "serialize" {
+                secret.serialize = true;
+            } else if ident == "zeroize" {
+                cfg_if! {
+                    if #[cfg(feature = "zeroize")] {
+                        secret.zeroize = true;
+                    } else {
+                        let msg = "To use `secret(zeroize)`, the feature `zeroize` of the crate `nutype` must be enabled.";
+                        return Err(syn::Error::new(ident.span(), msg));
+                    }
+                }
+            } else {
+                let msg = format!("Unknown `secret` attribute `{ident}`.\nPossible attributes are `serialize` and `zeroize`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
+        }
+        Ok(secret)
+    }
+}
+
+impl Parse for SchemaMetadata {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut schema = SchemaMetadata::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if !["format", "example", "description"]
+                .iter()
+                .any(|name| ident == name)
+            {
+                let msg = format!("Unknown `schema` attribute `{ident}`.\nPossible attributes are `format`, `example` and `description`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+            let _eq: Token![=] = input.parse()?;
+            if ident == "format" {
+                if schema.format.is_some() {
+                    let msg = "Duplicated `format` in `schema(...)`.";
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+                schema.format = Some(input.parse()?);
+            } else if ident == "example" {
+                schema.examples.push(input.parse()?);
+            } else {
+                if schema.description.is_some() {
+                    let msg = "Duplicated `description` in `schema(...)`.";
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+                schema.description = Some(input.parse()?);
+            }
+
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
+        }
+        Ok(schema)
+    }
+}
+
+impl Parse for ParseMoney {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut parse_money = ParseMoney::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "scale" {
+                let _eq: Token![=] = input.parse()?;
+                let (scale, _span) = parse_number::<u32>(input)?;
+                parse_money.scale = Some(scale);
+            } else if ident == "decimal_separator" {
+                let _eq: Token![=] = input.parse()?;
+                let separator: syn::LitChar = input.parse()?;
+                if !matches!(separator.value(), '.' | ',') {
+                    let msg = "`decimal_separator` must be either '.' or ','.";
+                    return Err(syn::Error::new(separator.span(), msg));
+                }
+                parse_money.decimal_separator = Some(separator.value());
+            } else {
+                let msg = format!("Unknown `parse_money` attribute `{ident}`.\nPossible attributes are `scale` and `decimal_separator`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
+        }
+        Ok(parse_money)
+    }
+}
+
+/// Parse a non-empty list of allowed values, e.g. `["red", "green"]` of `in = ["red", "green"]`.
+pub fn parse_allowed_values(input: ParseStream) -> syn::Result<(Vec<Expr>, Span)> {
+    let array: syn::ExprArray = input.parse()?;
+    let span = array.span();
+    if array.elems.is_empty() {
+        let msg = "`in` requires at least one allowed value.";
+        return Err(syn::Error::new(span, msg));
+    }
+    Ok((array.elems.into_iter().collect(), span))
+}
+
+/// Parse a number literal, that can be negative, as type T, e.g. `-5`, `10_000`, `0xFF` or `8u8`.
 pub fn parse_number<T>(input: ParseStream) -> syn::Result<(T, Span)>
 where
     T: FromStr,
 {
-    let mut number_str = String::with_capacity(16);
-    if input.peek(Token![-]) {
-        let _: Token![-] = input.parse()?;
-        number_str.push('-');
-    }
+    let subject = format!("`{}` value", type_name::<T>());
+    parse_number_literal::<T>(input, &subject)
+}
 
+/// Parse a length of a length validator, e.g. `len_char_max = 10_000`.
+pub fn parse_len_or_expr(input: ParseStream) -> syn::Result<(ValueOrExpr<usize>, Span)> {
+    parse_number_or_expr_with::<usize>(input, |input| {
+        parse_number_literal::<usize>(input, "Length")
+    })
+}
+
+/// Parse a number literal as type T. Literals are written as in Rust: with `_` separators,
+/// hexadecimal, octal or binary, and with a suffix, that must match type T.
+/// `subject` names the value in the error messages, e.g. "Length must be a non-negative integer".
+fn parse_number_literal<T>(input: ParseStream, subject: &str) -> syn::Result<(T, Span)>
+where
+    T: FromStr,
+{
+    let maybe_minus: Option<Token![-]> = input.parse()?;
     let lit: Lit = input.parse()?;
-    let lit_str = match &lit {
-        Lit::Float(lf) => lf.to_string(),
-        Lit::Int(li) => li.to_string(),
+    // Point at the whole number including the sign, e.g. `-1`
+    let error = |msg: String| syn::Error::new_spanned(quote!(#maybe_minus #lit), msg);
+
+    let (digits, suffix) = match &lit {
+        Lit::Int(li) => (li.base10_digits(), li.suffix()),
+        Lit::Float(lf) => (lf.base10_digits(), lf.suffix()),
         _ => {
             let msg = "Expected number literal";
             return Err(syn::Error::new(lit.span(), msg));
         }
     };
+    let literal = format!(
+        "{}{}",
+        if maybe_minus.is_some() { "-" } else { "" },
+        lit.to_token_stream()
+    );
 
-    number_str.push_str(&lit_str.replace('_', ""));
+    let type_name = type_name::<T>();
+    if !suffix.is_empty() && suffix != type_name {
+        let msg =
+            format!("The suffix of `{literal}` does not match the expected type `{type_name}`.");
+        return Err(error(msg));
+    }
+
+    let is_float_type = "0.5".parse::<T>().is_ok();
+    let is_unsigned_type = !is_float_type && "-1".parse::<T>().is_err();
+    if is_unsigned_type && maybe_minus.is_some() {
+        let msg = format!("{subject} must be a non-negative integer, got `{literal}`.");
+        return Err(error(msg));
+    }
+    if !is_float_type && matches!(lit, Lit::Float(_)) {
+        let msg = format!("{subject} must be an integer, got `{literal}`.");
+        return Err(error(msg));
+    }
 
+    let number_str = match maybe_minus {
+        Some(_) => format!("-{digits}"),
+        None => digits.to_string(),
+    };
     let number: T = number_str.parse::<T>().map_err(|_err| {
-        let msg = format!("Expected {}, got `{}`", type_name::<T>(), number_str);
-        syn::Error::new(lit.span(), msg)
+        let msg = format!("`{literal}` is out of range of `{type_name}`.");
+        error(msg)
     })?;
 
     Ok((number, lit.span()))
 }
 
-/// Try to parse input as a number of type T (if the value specified directly)
-/// If that fails then try to parse it as an expression (if the value is specified as an expression, a constant, etc.)
+/// Parse input as an expression. If it's a number literal (e.g. `5` or `-1.5`), the number is
+/// parsed as type T, so it's known at compile time. Any other expression (a constant or a const
+/// expression, e.g. `MAX_LEN - 1` or `u16::MAX as i64`) is kept as it is and emitted into the
+/// generated code.
 pub fn parse_number_or_expr<T>(input: ParseStream) -> syn::Result<(ValueOrExpr<T>, Span)>
 where
     T: FromStr,
 {
-    if let Ok((number, span)) = parse_number::<T>(input) {
+    parse_number_or_expr_with(input, parse_number::<T>)
+}
+
+fn parse_number_or_expr_with<T>(
+    input: ParseStream,
+    parse_literal: impl FnOnce(ParseStream) -> syn::Result<(T, Span)>,
+) -> syn::Result<(ValueOrExpr<T>, Span)> {
+    let expr: Expr = input.parse()?;
+    if is_number_literal(&expr) {
+        let (number, span) = parse_literal.parse2(expr.to_token_stream())?;
         Ok((ValueOrExpr::Value(number), span))
     } else {
-        let expr: Expr = input.parse()?;
         let span = expr.span();
         Ok((ValueOrExpr::Expr(expr), span))
     }
 }
 
+/// Check if the expression is a number literal, that can be negative, e.g. `-5`.
+fn is_number_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(expr_lit) => matches!(expr_lit.lit, Lit::Int(_) | Lit::Float(_)),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => matches!(
+            expr.as_ref(),
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Int(_) | Lit::Float(_),
+                ..
+            })
+        ),
+        _ => false,
+    }
+}
+
 // NOTE: This is a quite hacky way to obtain a syn::Type from `T`.
 // Is there a better way?
 pub fn parse_typed_custom_function<T>(
@@ -379,6 +1759,32 @@ pub fn parse_typed_custom_function_raw(
     Ok((typed_custom_function, span))
 }
 
+/// Parse the arguments of a fallible sanitizer: `try_with(func, error = Error)`.
+pub fn parse_try_sanitizer(input: ParseStream) -> syn::Result<(TrySanitizer, Span)> {
+    let content;
+    parenthesized!(content in input);
+    let with: CustomFunction = content.parse()?;
+    let span = with.span();
+    let missing_error = || {
+        let msg = "`try_with` expects the function and its error type, e.g. `try_with(to_ascii, error = IdnaError)`.";
+        syn::Error::new(span, msg)
+    };
+    if content.is_empty() {
+        return Err(missing_error());
+    }
+    let _comma: Token![,] = content.parse()?;
+    let ident: Ident = content.parse()?;
+    if ident != "error" {
+        return Err(missing_error());
+    }
+    let _eq: Token![=] = content.parse()?;
+    let error_type: syn::Type = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("Unexpected tokens after the error type of `try_with`."));
+    }
+    Ok((TrySanitizer { with, error_type }, span))
+}
+
 pub fn parse_sanitizer_kind<K>(input: ParseStream) -> syn::Result<(K, Ident)>
 where
     K: core::str::FromStr + kinded::Kind + core::fmt::Display + 'static,
@@ -399,7 +1805,8 @@ fn parse_kind<K>(attr_type: &str, input: ParseStream) -> syn::Result<(K, Ident)>
 where
     K: core::str::FromStr + kinded::Kind + core::fmt::Display + 'static,
 {
-    let ident: Ident = input.parse()?;
+    // Use `parse_any` to accept keywords, e.g. `in` validator.
+    let ident: Ident = Ident::parse_any(input)?;
     let attr_name = ident.to_string();
 
     if let Ok(kind) = attr_name.parse::<K>() {
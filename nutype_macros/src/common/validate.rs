@@ -1,40 +1,87 @@
+use core::{fmt::Display, str::FromStr};
+use std::collections::HashSet;
+
 use kinded::Kinded;
-use proc_macro2::Span;
+use proc_macro2::{Ident, Span};
+use quote::ToTokens;
+use syn::spanned::Spanned;
 
 use super::{
     models::{
-        DeriveTrait, Guard, NumericBoundValidator, RawGuard, SpannedDeriveTrait, SpannedItem,
-        TypeName, Validation,
+        AsVariant, AsVariants, Attributes, DeriveOptions, DeriveTrait, DuplicatePolicy, Guard,
+        NumericBoundValidator, RawGuard, Rule, SpannedDeriveTrait, SpannedItem, TypeName,
+        Validation,
     },
     parse::RawValidation,
-    r#gen::error::gen_error_type_name,
+    r#gen::error::{gen_error_type_name, gen_legacy_error_type_name},
 };
 
 /// Some kind of template method to validate a guard of all types.
 pub fn validate_guard<RawSanitizer, RawValidator, Sanitizer, Validator>(
     raw_guard: RawGuard<RawSanitizer, RawValidator>,
     type_name: &TypeName,
-    validate_validators: impl FnOnce(Vec<RawValidator>) -> Result<Vec<Validator>, syn::Error>,
+    validate_validators: impl Fn(Vec<RawValidator>) -> Result<Vec<Validator>, syn::Error>,
     validate_sanitizers: impl FnOnce(Vec<RawSanitizer>) -> Result<Vec<Sanitizer>, syn::Error>,
-) -> Result<Guard<Sanitizer, Validator>, syn::Error> {
+) -> Result<Guard<Sanitizer, Validator>, syn::Error>
+where
+    RawSanitizer: Kinded,
+    <RawSanitizer as Kinded>::Kind: Display,
+    RawValidator: Kinded,
+    Sanitizer: Rule,
+{
     let RawGuard {
         sanitizers: raw_sanitizers,
         validation: maybe_raw_validation,
+        legacy: maybe_raw_legacy,
+        duplicates,
     } = raw_guard;
 
+    let validate_validators = |validators: Vec<RawValidator>| match duplicates {
+        DuplicatePolicy::Error => validate_validators(validators),
+        DuplicatePolicy::LastWins => validate_validators(keep_last_of_kind(validators, |_| false)),
+    };
+    let raw_sanitizers = match duplicates {
+        DuplicatePolicy::Error => raw_sanitizers,
+        // Every `with` and `try_with` sanitizer is applied, so they are never overridden.
+        DuplicatePolicy::LastWins => keep_last_of_kind(raw_sanitizers, |sanitizer| {
+            is_repeatable_sanitizer(&sanitizer.kind().to_string())
+        }),
+    };
+
     let sanitizers = validate_sanitizers(raw_sanitizers)?;
+    validate_try_with_error_types(&sanitizers, maybe_raw_validation.is_some())?;
 
     let Some(raw_validation) = maybe_raw_validation else {
         return Ok(Guard::WithoutValidation { sanitizers });
     };
 
     let validation = match raw_validation {
-        RawValidation::Standard { validators } => {
+        RawValidation::Standard {
+            validators,
+            with_context,
+            messages,
+            combinators,
+        } => {
             let error_type_path = gen_error_type_name(type_name);
             let validators = validate_validators(validators)?;
+            // Every validator of a combinator is checked on its own, so e.g. the bounds of
+            // alternatives do not have to be consistent with each other.
+            let validate_combined = |validator: RawValidator| {
+                let span = proc_macro2::Span::call_site();
+                validate_validators(vec![validator])?
+                    .pop()
+                    .ok_or_else(|| syn::Error::new(span, "The validator cannot be combined."))
+            };
+            let combinators = combinators
+                .into_iter()
+                .map(|combinator| combinator.try_map(&validate_combined))
+                .collect::<Result<_, syn::Error>>()?;
             Validation::Standard {
                 validators,
                 error_type_path,
+                with_context,
+                messages,
+                combinators,
             }
         }
         RawValidation::Custom { with, error } => {
@@ -45,9 +92,25 @@ pub fn validate_guard<RawSanitizer, RawValidator, Sanitizer, Validator>(
             }
         }
     };
+    let legacy = match maybe_raw_legacy {
+        // Custom legacy validation and `with_context` are rejected by the parser.
+        Some(RawValidation::Standard {
+            validators,
+            messages,
+            ..
+        }) => Some(Validation::Standard {
+            validators: validate_validators(validators)?,
+            error_type_path: gen_legacy_error_type_name(type_name),
+            with_context: None,
+            messages,
+            combinators: Vec::new(),
+        }),
+        Some(RawValidation::Custom { .. }) | None => None,
+    };
     Ok(Guard::WithValidation {
         sanitizers,
         validation,
+        legacy,
     })
 }
 
@@ -58,7 +121,8 @@ pub fn validate_duplicates<T>(
 where
     T: Kinded,
 {
-    if let Some((item1, item2)) = detect_items_of_same_kind(items) {
+    let items: Vec<&SpannedItem<T>> = items.iter().collect();
+    if let Some((item1, item2)) = detect_items_of_same_kind(&items) {
         assert_eq!(item1.kind(), item2.kind());
         let kind = item1.kind();
         let msg = build_error_msg(kind);
@@ -69,12 +133,78 @@ where
     Ok(())
 }
 
-fn detect_items_of_same_kind<T: Kinded>(items: &[T]) -> Option<(&T, &T)> {
+/// The errors of `try_with(...)` sanitizers are returned by `try_new()`, so there must be
+/// validation, and all of them must have the same error type.
+fn validate_try_with_error_types<S: Rule>(
+    sanitizers: &[S],
+    has_validation: bool,
+) -> Result<(), syn::Error> {
+    let mut error_types = sanitizers.iter().filter_map(Rule::try_with_error_type);
+    let Some(first_error_type) = error_types.next() else {
+        return Ok(());
+    };
+    if !has_validation {
+        let msg =
+            "`try_with` requires `validate(...)`, because its error is returned by `try_new()`.";
+        return Err(syn::Error::new_spanned(first_error_type, msg));
+    }
+    let first = first_error_type.to_token_stream().to_string();
+    if let Some(other_error_type) =
+        error_types.find(|error_type| error_type.to_token_stream().to_string() != first)
+    {
+        let msg = "All `try_with` sanitizers must have the same error type.";
+        return Err(syn::Error::new_spanned(other_error_type, msg));
+    }
+    Ok(())
+}
+
+/// Custom sanitizers (`with` and `try_with`) may be repeated: they are applied in the order they
+/// are declared, like the other sanitizers.
+fn is_repeatable_sanitizer(kind: &str) -> bool {
+    kind == "with" || kind == "try_with"
+}
+
+/// Like `validate_duplicates`, but `with` and `try_with` sanitizers may be repeated.
+pub fn validate_sanitizer_duplicates<T>(
+    items: &[SpannedItem<T>],
+    build_error_msg: impl Fn(<T as Kinded>::Kind) -> String,
+) -> Result<(), syn::Error>
+where
+    T: Kinded,
+    <T as Kinded>::Kind: Display,
+{
+    let items: Vec<&SpannedItem<T>> = items
+        .iter()
+        .filter(|item| !is_repeatable_sanitizer(&item.kind().to_string()))
+        .collect();
+    if let Some((item1, item2)) = detect_items_of_same_kind(&items) {
+        let kind = item1.kind();
+        let msg = build_error_msg(kind);
+        let span = join_spans_or_last(item1.span(), item2.span());
+        return Err(syn::Error::new(span, msg));
+    }
+    Ok(())
+}
+
+/// Removes items, that are overridden by a later item of the same kind.
+/// The remaining items keep their relative order.
+fn keep_last_of_kind<T: Kinded>(items: Vec<T>, is_repeatable: impl Fn(&T) -> bool) -> Vec<T> {
+    let mut kept: Vec<T> = Vec::with_capacity(items.len());
+    for item in items.into_iter().rev() {
+        if is_repeatable(&item) || !kept.iter().any(|other| other.kind() == item.kind()) {
+            kept.push(item);
+        }
+    }
+    kept.reverse();
+    kept
+}
+
+fn detect_items_of_same_kind<'a, T: Kinded>(items: &[&'a T]) -> Option<(&'a T, &'a T)> {
     // Note: this has O(n^2) complexity, but it's not a problem, because size of collection is < 10.
     for (i1, item1) in items.iter().enumerate() {
         for (i2, item2) in items.iter().enumerate() {
             if i1 != i2 && item1.kind() == item2.kind() {
-                return Some((item1, item2));
+                return Some((*item1, *item2));
             }
         }
     }
@@ -151,6 +281,179 @@ where
     Ok(())
 }
 
+/// Check a literal default value (e.g. `default = 5`) against the numeric bounds, so an invalid
+/// default is reported at compile time rather than by a panic in `Default::default()`.
+/// Other expressions (e.g. constants) are still checked by the generated test.
+pub fn validate_numeric_default_value<V, T>(
+    default_value: &syn::Expr,
+    validators: &[V],
+) -> Result<(), syn::Error>
+where
+    V: NumericBoundValidator<T>,
+    T: Clone + PartialOrd + FromStr,
+{
+    let Some(literal) = literal_number(default_value) else {
+        return Ok(());
+    };
+    let Ok(value) = literal.parse::<T>() else {
+        return Ok(());
+    };
+
+    for validator in validators {
+        let violated_validator = if validator.greater().is_some_and(|bound| value <= bound) {
+            "greater"
+        } else if validator
+            .greater_or_equal()
+            .is_some_and(|bound| value < bound)
+        {
+            "greater_or_equal"
+        } else if validator.less().is_some_and(|bound| value >= bound) {
+            "less"
+        } else if validator.less_or_equal().is_some_and(|bound| value > bound) {
+            "less_or_equal"
+        } else {
+            continue;
+        };
+        return Err(invalid_default_value_error(
+            default_value,
+            &literal,
+            violated_validator,
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn invalid_default_value_error(
+    default_value: &syn::Expr,
+    displayed_value: &str,
+    validator: &str,
+) -> syn::Error {
+    let msg = format!("Default value `{displayed_value}` violates `{validator}` validator.");
+    syn::Error::new_spanned(default_value, msg)
+}
+
+/// Get a numeric literal, that can be negative, e.g. `-5` or `0.5`, without a type suffix.
+/// Returns `None` for any other expression.
+fn literal_number(expr: &syn::Expr) -> Option<String> {
+    let (is_negative, lit) = match expr {
+        syn::Expr::Lit(expr_lit) => (false, &expr_lit.lit),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            syn::Expr::Lit(expr_lit) => (true, &expr_lit.lit),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let digits = match lit {
+        syn::Lit::Int(lit_int) => lit_int.base10_digits(),
+        syn::Lit::Float(lit_float) => lit_float.base10_digits(),
+        _ => return None,
+    };
+    let sign = if is_negative { "-" } else { "" };
+    Some(format!("{sign}{digits}"))
+}
+
+/// Build variants of the companion enum requested with `as_variants` out of the values
+/// allowed by `in = [...]` validator.
+pub fn validate_as_variants(
+    as_variants_span: Span,
+    maybe_allowed_values: Option<&[syn::Expr]>,
+) -> Result<AsVariants, syn::Error> {
+    let Some(allowed_values) = maybe_allowed_values else {
+        let msg = "`as_variants` requires `in = [...]` validator.";
+        return Err(syn::Error::new(as_variants_span, msg));
+    };
+
+    let mut variants: Vec<AsVariant> = Vec::with_capacity(allowed_values.len());
+    for value in allowed_values {
+        let ident = gen_variant_ident(value)?;
+        if variants.iter().any(|variant| variant.ident == ident) {
+            let msg = format!("`as_variants` generates variant `{ident}` for more than one value.");
+            return Err(syn::Error::new_spanned(value, msg));
+        }
+        variants.push(AsVariant {
+            ident,
+            value: value.clone(),
+        });
+    }
+    Ok(AsVariants { variants })
+}
+
+/// Derive a variant name from an allowed value:
+/// * `"dark-blue"` -> `DarkBlue`
+/// * `42` -> `Value42`, `-1` -> `ValueMinus1`
+/// * `DARK_BLUE` (constant) -> `DarkBlue`
+fn gen_variant_ident(value: &syn::Expr) -> Result<proc_macro2::Ident, syn::Error> {
+    let name = match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => to_upper_camel_case(&lit_str.value()),
+        syn::Expr::Path(expr_path) => expr_path
+            .path
+            .segments
+            .last()
+            .map(|segment| to_upper_camel_case(&segment.ident.to_string()))
+            .unwrap_or_default(),
+        _ => match literal_number(value) {
+            Some(number) => format!("Value{}", number.replace('-', "Minus")),
+            None => String::new(),
+        },
+    };
+
+    // Names like `1st` are not valid identifiers, so they get a prefix.
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Value{name}")
+    } else {
+        name
+    };
+
+    syn::parse_str::<proc_macro2::Ident>(&name).map_err(|_| {
+        let msg = "`as_variants` cannot derive a variant name from this value.\nUse string or integer literals, or constants.";
+        syn::Error::new_spanned(value, msg)
+    })
+}
+
+/// Convert words separated by non alphanumeric characters into `UpperCamelCase`,
+/// e.g. `dark-blue` or `DARK_BLUE` into `DarkBlue`.
+pub fn to_upper_camel_case(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_uppercase().to_string());
+            let rest = chars.as_str();
+            // Keep `camelCase` words as they are, but lowercase `SCREAMING` ones.
+            let rest = if word.chars().all(|c| !c.is_lowercase()) {
+                rest.to_lowercase()
+            } else {
+                rest.to_string()
+            };
+            first.unwrap_or_default() + &rest
+        })
+        .collect()
+}
+
+/// `serde(borrow)` makes sense only for inner types that can borrow a string from the input.
+pub fn validate_serde_borrow(
+    derive_options: &DeriveOptions,
+    can_borrow: bool,
+) -> Result<(), syn::Error> {
+    match derive_options.serde_borrow {
+        Some(span) if !can_borrow => {
+            let msg =
+                "`serde(borrow)` is supported only by `&'a str` and `Cow<'a, str>` inner types.";
+            Err(syn::Error::new(span, msg))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn validate_traits_from_xor_try_from(
     spanned_derive_traits: &[SpannedDeriveTrait],
 ) -> Result<(), syn::Error> {
@@ -170,3 +473,311 @@ pub fn validate_traits_from_xor_try_from(
         _ => Ok(()),
     }
 }
+
+/// `nohash_hasher` expects `Hash` to write a single integer into the hasher. `Hash` of the inner
+/// types other than integers up to 64 bits does not, so they need `hash_with`.
+pub fn validate_nohash(
+    derive_options: &DeriveOptions,
+    hashes_into_single_integer: bool,
+) -> Result<(), syn::Error> {
+    match derive_options.nohash {
+        Some(nohash_span) if !hashes_into_single_integer && derive_options.hash_with.is_none() => {
+            let msg = "`nohash` is supported only by integer based types up to 64 bits.\nFor other types use it together with `hash_with`, that hashes the value into `u64`.";
+            Err(syn::Error::new(nohash_span, msg))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `Option<T>` based types support only the attributes and traits, that do not need to expose
+/// the `Some` value. Returns the traits to derive.
+pub fn validate_optional<Sanitizer, Validator>(
+    attributes: &Attributes<Guard<Sanitizer, Validator>, SpannedDeriveTrait>,
+) -> Result<HashSet<DeriveTrait>, syn::Error> {
+    let Attributes {
+        guard,
+        derive_traits,
+        parse_money,
+        store_as,
+        as_variants,
+        required,
+        derive_options,
+        guarantee,
+        ..
+    } = attributes;
+
+    let unsupported_attr = if parse_money.is_some() {
+        Some("parse_money")
+    } else if store_as.is_some() {
+        Some("store_as`, `storage` or `bounded")
+    } else if as_variants.is_some() {
+        Some("as_variants")
+    } else if derive_options.try_from.is_some() {
+        Some("try_from(...)")
+    } else if derive_options.into.is_some() {
+        Some("into(...)")
+    } else if derive_options.normalize_for_eq.is_some() {
+        Some("normalize_for_eq")
+    } else if derive_options.refines.is_some() {
+        Some("refines")
+    } else if derive_options.method_names.constructor.is_some() {
+        Some("constructor")
+    } else if derive_options.method_names.constructor_visibility.is_some() {
+        Some("constructor_visibility")
+    } else if derive_options.method_names.getter.is_some() {
+        Some("getter")
+    } else if derive_options.hash_with.is_some() {
+        Some("hash_with")
+    } else if derive_options.nohash.is_some() {
+        Some("nohash")
+    } else if derive_options.ffi.is_some() {
+        Some("ffi")
+    } else if derive_options.repr_transparent.is_some() {
+        Some("repr_transparent")
+    } else if derive_options.bytes.is_some() {
+        Some("bytes")
+    } else if derive_options.wasm.is_some() {
+        Some("wasm")
+    } else if derive_options.eq_str.is_some() {
+        Some("eq_str")
+    } else if derive_options.total_order.is_some() {
+        Some("total_order")
+    } else if derive_options.test_examples.is_some() {
+        Some("test_examples(...)")
+    } else if derive_options.encoding.is_some() {
+        Some("encoding")
+    } else if derive_options.lossy.is_some() {
+        Some("lossy")
+    } else if *guarantee {
+        Some("guarantee")
+    } else if matches!(
+        guard,
+        Guard::WithValidation {
+            legacy: Some(_),
+            ..
+        }
+    ) {
+        Some("legacy")
+    } else {
+        None
+    };
+    if let Some(attr) = unsupported_attr {
+        let msg = format!("`{attr}` is not supported by `Option` based types.");
+        return Err(syn::Error::new(Span::call_site(), msg));
+    }
+
+    if let (
+        Some(required_span),
+        Guard::WithValidation {
+            validation: Validation::Custom { .. },
+            ..
+        },
+    ) = (required, guard)
+    {
+        let msg = "`required` cannot be combined with a custom error type.\nReturn an error for `None` from the custom validation function instead.";
+        return Err(syn::Error::new(*required_span, msg));
+    }
+
+    let mut traits = HashSet::with_capacity(derive_traits.len());
+    for spanned_trait in derive_traits {
+        let derive_trait = spanned_trait.item;
+        match derive_trait {
+            DeriveTrait::Debug
+            | DeriveTrait::Clone
+            | DeriveTrait::Copy
+            | DeriveTrait::PartialEq
+            | DeriveTrait::Eq
+            | DeriveTrait::PartialOrd
+            | DeriveTrait::Ord
+            | DeriveTrait::Hash
+            | DeriveTrait::Into
+            | DeriveTrait::TryFrom
+            | DeriveTrait::Default
+            | DeriveTrait::SerdeSerialize
+            | DeriveTrait::SerdeDeserialize => {}
+            DeriveTrait::From => {
+                if required.is_some() {
+                    let msg = "#[nutype] cannot derive `From` trait, because `None` is rejected with `required`. Use `TryFrom` instead.";
+                    return Err(syn::Error::new(spanned_trait.span, msg));
+                }
+            }
+            DeriveTrait::FromStr
+            | DeriveTrait::AsRef
+            | DeriveTrait::Deref
+            | DeriveTrait::Borrow
+            | DeriveTrait::IntoIterator
+            | DeriveTrait::Extend
+            | DeriveTrait::Display => {
+                let msg = format!(
+                    "#[nutype] cannot derive `{derive_trait:?}` trait for `Option` based types."
+                );
+                return Err(syn::Error::new(spanned_trait.span, msg));
+            }
+            DeriveTrait::SchemarsJsonSchema
+            | DeriveTrait::ArbitraryArbitrary
+            | DeriveTrait::ValidatorValidate
+            | DeriveTrait::BorshSerialize
+            | DeriveTrait::BorshDeserialize
+            | DeriveTrait::RkyvArchive
+            | DeriveTrait::ClapValueParserFactory => {
+                let msg = "#[nutype] cannot derive traits of external crates (except `serde`) for `Option` based types.";
+                return Err(syn::Error::new(spanned_trait.span, msg));
+            }
+        }
+        traits.insert(derive_trait);
+    }
+    Ok(traits)
+}
+
+/// `IntoIterator` and `Extend` are supported only by types with a collection as the inner type.
+pub fn unsupported_collection_trait(derive_trait: DeriveTrait, span: Span) -> syn::Error {
+    let msg = format!("#[nutype] cannot derive `{derive_trait:?}` trait, because the inner type is not a collection.\nIt can be derived for types like `Vec<T>`, `HashSet<T>` or `BTreeMap<K, V>`.");
+    syn::Error::new(span, msg)
+}
+
+/// Values of a `with_context` type are built only with `try_from_with()`, so everything that
+/// builds a value without a context is rejected.
+/// `any_of(...)`, `all(...)`, `not(...)` and `when(...)` are checked only at runtime by `try_new()`.
+pub fn validate_combinators<Sanitizer, Validator>(
+    attributes: &Attributes<Guard<Sanitizer, Validator>, SpannedDeriveTrait>,
+    is_optional: bool,
+) -> Result<(), syn::Error> {
+    let Attributes {
+        guard,
+        derive_traits,
+        guarantee,
+        ..
+    } = attributes;
+    let Guard::WithValidation {
+        validation: Validation::Standard { combinators, .. },
+        ..
+    } = guard
+    else {
+        return Ok(());
+    };
+    let Some(combinator) = combinators.first() else {
+        return Ok(());
+    };
+    let span = combinator.span();
+
+    if is_optional {
+        let msg = "`any_of`, `all`, `not` and `when` are not supported by `Option` based types.";
+        return Err(syn::Error::new(span, msg));
+    }
+    if *guarantee {
+        let msg = "`any_of`, `all`, `not` and `when` can not be combined with `guarantee`, because they can not be checked at compile time.";
+        return Err(syn::Error::new(span, msg));
+    }
+    if let Some(spanned_trait) = derive_traits
+        .iter()
+        .find(|spanned_trait| spanned_trait.item == DeriveTrait::ArbitraryArbitrary)
+    {
+        let msg = "#[nutype] cannot derive `Arbitrary` trait for a type with `any_of`, `all`, `not` or `when`.\nYou have to implement `Arbitrary` trait on you own.";
+        return Err(syn::Error::new(spanned_trait.span, msg));
+    }
+    Ok(())
+}
+
+/// The field of a struct with a single named field gets a `&self` accessor, so the value must be
+/// stored as it is, and the accessor must not clash with the renamed getter.
+pub fn validate_field_name<Sanitizer, Validator>(
+    attributes: &Attributes<Guard<Sanitizer, Validator>, SpannedDeriveTrait>,
+    field_name: &Ident,
+) -> Result<(), syn::Error> {
+    if attributes.store_as.is_some() {
+        let msg = "`store_as`, `storage` and `bounded` are not supported by structs with a named field, because a reference to the inner value can not be obtained.\nUse a tuple struct instead, e.g. `struct Email(String)`.";
+        return Err(syn::Error::new(field_name.span(), msg));
+    }
+    if let Some(getter) = &attributes.derive_options.method_names.getter {
+        if getter == field_name {
+            let msg = format!("`getter = {getter}` clashes with the accessor of the field `{field_name}`.\nRemove `getter` or rename the field.");
+            return Err(syn::Error::new(getter.span(), msg));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_with_context<Sanitizer, Validator>(
+    attributes: &Attributes<Guard<Sanitizer, Validator>, SpannedDeriveTrait>,
+    is_optional: bool,
+) -> Result<(), syn::Error> {
+    let Attributes {
+        guard,
+        derive_traits,
+        default,
+        parse_money,
+        as_variants,
+        arena,
+        derive_options,
+        ..
+    } = attributes;
+    let Some(with_context) = guard.with_context() else {
+        return Ok(());
+    };
+    let span = with_context.with.span();
+
+    if is_optional {
+        let msg = "`with_context` is not supported by `Option` based types.";
+        return Err(syn::Error::new(span, msg));
+    }
+
+    let unsupported_attr = if parse_money.is_some() {
+        Some("parse_money")
+    } else if as_variants.is_some() {
+        Some("as_variants")
+    } else if *arena {
+        Some("arena")
+    } else if default.is_some() {
+        Some("default")
+    } else if derive_options.try_from.is_some() {
+        Some("try_from(...)")
+    } else if derive_options.refines.is_some() {
+        Some("refines")
+    } else if derive_options.method_names.constructor.is_some() {
+        Some("constructor")
+    } else if derive_options.ffi.is_some() {
+        Some("ffi")
+    } else if derive_options.bytes.is_some() {
+        Some("bytes")
+    } else if derive_options.wasm.is_some() {
+        Some("wasm")
+    } else if derive_options.test_examples.is_some() {
+        Some("test_examples(...)")
+    } else if derive_options.lossy.is_some() {
+        Some("lossy")
+    } else if matches!(
+        guard,
+        Guard::WithValidation {
+            legacy: Some(_),
+            ..
+        }
+    ) {
+        Some("legacy")
+    } else {
+        None
+    };
+    if let Some(attr) = unsupported_attr {
+        let msg = format!("`with_context` is not supported by `{attr}`.");
+        return Err(syn::Error::new(span, msg));
+    }
+
+    for spanned_trait in derive_traits {
+        let derive_trait = &spanned_trait.item;
+        if matches!(
+            derive_trait,
+            DeriveTrait::TryFrom
+                | DeriveTrait::FromStr
+                | DeriveTrait::Default
+                | DeriveTrait::Extend
+                | DeriveTrait::SerdeDeserialize
+                | DeriveTrait::BorshDeserialize
+                | DeriveTrait::RkyvArchive
+                | DeriveTrait::ClapValueParserFactory
+                | DeriveTrait::ArbitraryArbitrary
+        ) {
+            let msg = format!("#[nutype] cannot derive `{derive_trait:?}` trait, because `with_context` requires a context to validate the value.\nUse `try_from_with()` instead.");
+            return Err(syn::Error::new(spanned_trait.span, msg));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{
+    common::{
+        gen::{doc::describe_value, error::allowed_values_to_string},
+        models::Rule,
+    },
+    float::models::{FloatSanitizer, FloatValidator},
+};
+
+impl<T> Rule for FloatSanitizer<T> {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        match self {
+            Self::With(_) => Some("sanitized with a custom function".to_string()),
+            Self::TryWith(_) => Some("sanitized with a fallible custom function".to_string()),
+            Self::RoundDp(decimal_places) => {
+                Some(format!("rounded to {decimal_places} decimal places"))
+            }
+            Self::_Phantom(_) => None,
+        }
+    }
+
+    fn try_with_error_type(&self) -> Option<&syn::Type> {
+        match self {
+            Self::TryWith(try_sanitizer) => Some(&try_sanitizer.error_type),
+            _ => None,
+        }
+    }
+}
+
+impl<T: ToTokens> Rule for FloatValidator<T> {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let description = match self {
+            Self::Greater(value) => format!("greater than {}", describe_value(value)),
+            Self::GreaterOrEqual(value) => format!("at least {}", describe_value(value)),
+            Self::Less(value) => format!("less than {}", describe_value(value)),
+            Self::LessOrEqual(value) => format!("at most {}", describe_value(value)),
+            Self::In(allowed_values) => {
+                format!("one of [{}]", allowed_values_to_string(allowed_values))
+            }
+            Self::Predicate(_) => "satisfies the predicate".to_string(),
+            Self::Finite => "finite".to_string(),
+            Self::NotNan => "not NaN".to_string(),
+            Self::MaxDp(decimal_places) => format!("at most {decimal_places} decimal places"),
+        };
+        Some(description)
+    }
+    fn bound(&self) -> Option<TokenStream> {
+        match self {
+            Self::Greater(value)
+            | Self::GreaterOrEqual(value)
+            | Self::Less(value)
+            | Self::LessOrEqual(value) => Some(quote!(#value)),
+            Self::In(_) | Self::Predicate(_) | Self::Finite | Self::NotNan | Self::MaxDp(_) => None,
+        }
+    }
+}
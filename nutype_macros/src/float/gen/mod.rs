@@ -1,3 +1,4 @@
+pub mod doc;
 pub mod error;
 pub mod traits;
 
@@ -15,19 +16,30 @@ use super::{
 use crate::{
     common::{
         gen::{
+            error::ExtraVariants,
             tests::{
                 gen_test_should_have_consistent_lower_and_upper_boundaries,
                 gen_test_should_have_valid_default_value,
             },
+            trace::{gen_sanitizations, gen_validations},
             traits::GeneratedTraits,
+            try_with::{gen_fn_sanitize_of, gen_try_with_step},
             GenerateNewtype,
         },
-        models::{ErrorTypePath, Guard, TypeName},
+        models::{DeriveOptions, ErrorMessages, ErrorTypePath, Guard, RuleNode, StoreAs, TypeName},
     },
     float::models::FloatInnerType,
 };
 use traits::gen_traits;
 
+/// An expression, that rounds `value` to `decimal_places` half away from zero.
+///
+/// The rounding is applied to the shortest decimal representation of the value, so `1.005`
+/// becomes `1.01` even though it's stored as `1.00499999...`. NaN and infinities are kept as they are.
+fn gen_round_dp(value: TokenStream, decimal_places: u32) -> TokenStream {
+    quote!(::nutype::__private::RoundDp::round_dp(#value, #decimal_places))
+}
+
 impl<T> GenerateNewtype for FloatNewtype<T>
 where
     T: FloatType + ToTokens + PartialOrd,
@@ -37,29 +49,49 @@ where
     type InnerType = FloatInnerType;
     type TypedTrait = FloatDeriveTrait;
 
+    // Numbers are parsed from `&str` listed in `try_from(...)`.
+    const PARSES_STR_SOURCE: bool = true;
+
     fn gen_fn_sanitize(
         inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
+        trace: bool,
+        lossy: bool,
     ) -> TokenStream {
-        let transformations: TokenStream = sanitizers
-            .iter()
-            .map(|san| match san {
+        let transformations = gen_sanitizations(
+            &RuleNode::sanitizers(sanitizers),
+            trace,
+            lossy,
+            |san| match san {
                 FloatSanitizer::With(custom_sanitizer) => {
                     quote!(
                         value = (#custom_sanitizer)(value);
                     )
                 }
+                FloatSanitizer::TryWith(try_sanitizer) => {
+                    let step = gen_try_with_step(try_sanitizer, inner_type);
+                    quote!(
+                        value = #step;
+                    )
+                }
+                FloatSanitizer::RoundDp(decimal_places) => {
+                    let round = gen_round_dp(quote!(value), *decimal_places);
+                    quote!(
+                        value = #round;
+                    )
+                }
                 FloatSanitizer::_Phantom(_) => {
                     unreachable!("float::gen FloatSanitizer::_Phantom must not be used")
                 }
-            })
-            .collect();
+            },
+        );
 
-        quote!(
-            fn __sanitize__(mut value: #inner_type) -> #inner_type {
-                #transformations
-                value
-            }
+        gen_fn_sanitize_of(
+            quote!(mut value: #inner_type),
+            inner_type,
+            sanitizers,
+            transformations,
+            lossy,
         )
     }
 
@@ -67,54 +99,82 @@ where
         inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        trace: bool,
     ) -> TokenStream {
-        let validations: TokenStream = validators
-            .iter()
-            .map(|validator| match validator {
-                FloatValidator::Less(exclusive_upper_bound) => {
-                    quote!(
-                        if val >= #exclusive_upper_bound {
-                            return Err(#error_type_path::LessViolated);
-                        }
-                    )
-                }
-                FloatValidator::LessOrEqual(max) => {
-                    quote!(
-                        if val > #max {
-                            return Err(#error_type_path::LessOrEqualViolated);
-                        }
-                    )
-                }
-                FloatValidator::Greater(exclusive_lower_bound) => {
-                    quote!(
-                        if val <= #exclusive_lower_bound {
-                            return Err(#error_type_path::GreaterViolated);
-                        }
-                    )
-                }
-                FloatValidator::GreaterOrEqual(min) => {
-                    quote!(
-                        if val < #min {
-                            return Err(#error_type_path::GreaterOrEqualViolated);
-                        }
-                    )
-                }
-                FloatValidator::Predicate(custom_is_valid_fn) => {
-                    quote!(
-                        if !(#custom_is_valid_fn)(&val) {
-                            return Err(#error_type_path::PredicateViolated);
-                        }
-                    )
-                }
-                FloatValidator::Finite => {
-                    quote!(
-                        if !val.is_finite() {
-                            return Err(#error_type_path::FiniteViolated);
-                        }
-                    )
-                }
-            })
-            .collect();
+        let validations =
+            gen_validations(
+                &RuleNode::validators(validators),
+                trace,
+                |validator| match validator {
+                    FloatValidator::Less(exclusive_upper_bound) => {
+                        quote!(
+                            if val >= #exclusive_upper_bound {
+                                return Err(#error_type_path::LessViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::LessOrEqual(max) => {
+                        quote!(
+                            if val > #max {
+                                return Err(#error_type_path::LessOrEqualViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::Greater(exclusive_lower_bound) => {
+                        quote!(
+                            if val <= #exclusive_lower_bound {
+                                return Err(#error_type_path::GreaterViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::GreaterOrEqual(min) => {
+                        quote!(
+                            if val < #min {
+                                return Err(#error_type_path::GreaterOrEqualViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::In(allowed_values) => {
+                        quote!(
+                            if ![#(#allowed_values),*].contains(&val) {
+                                return Err(#error_type_path::InViolated(val));
+                            }
+                        )
+                    }
+                    FloatValidator::Predicate(custom_is_valid_fn) => {
+                        quote!(
+                            if !(#custom_is_valid_fn)(&val) {
+                                return Err(#error_type_path::PredicateViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::Finite => {
+                        quote!(
+                            if !val.is_finite() {
+                                return Err(#error_type_path::FiniteViolated);
+                            }
+                        )
+                    }
+                    FloatValidator::NotNan => {
+                        quote!(
+                            if val.is_nan() {
+                                return Err(#error_type_path::NotNanViolated);
+                            }
+                        )
+                    }
+                    // The value has at most N decimal places, if rounding to N decimal places keeps it
+                    // as it is. NaN is rejected, since it's not equal to itself.
+                    FloatValidator::MaxDp(decimal_places) => {
+                        let round = gen_round_dp(quote!(val), *decimal_places);
+                        quote!(
+                            #[allow(clippy::float_cmp)]
+                            if #round != val {
+                                return Err(#error_type_path::MaxDpViolated);
+                            }
+                        )
+                    }
+                },
+            );
 
         quote!(
             fn __validate__(val: &#inner_type) -> core::result::Result<(), #error_type_path> {
@@ -127,10 +187,52 @@ where
 
     fn gen_validation_error_type(
         type_name: &TypeName,
+        inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        extra_variants: ExtraVariants,
+        messages: &ErrorMessages,
+    ) -> TokenStream {
+        gen_validation_error_type(
+            type_name,
+            inner_type,
+            error_type_path,
+            validators,
+            extra_variants,
+            messages,
+        )
+    }
+
+    fn gen_bound_consts(
+        inner_type: &Self::InnerType,
+        validators: &[Self::Validator],
     ) -> TokenStream {
-        gen_validation_error_type(type_name, error_type_path, validators)
+        validators
+            .iter()
+            .map(|validator| match validator {
+                FloatValidator::GreaterOrEqual(min) => quote!(
+                    /// Inclusive lower bound, declared with `greater_or_equal`.
+                    pub const MIN: #inner_type = #min;
+                ),
+                FloatValidator::LessOrEqual(max) => quote!(
+                    /// Inclusive upper bound, declared with `less_or_equal`.
+                    pub const MAX: #inner_type = #max;
+                ),
+                FloatValidator::Greater(exclusive_lower_bound) => quote!(
+                    /// Exclusive lower bound, declared with `greater`.
+                    pub const MIN_EXCLUSIVE: #inner_type = #exclusive_lower_bound;
+                ),
+                FloatValidator::Less(exclusive_upper_bound) => quote!(
+                    /// Exclusive upper bound, declared with `less`.
+                    pub const MAX_EXCLUSIVE: #inner_type = #exclusive_upper_bound;
+                ),
+                FloatValidator::In(_)
+                | FloatValidator::Predicate(_)
+                | FloatValidator::Finite
+                | FloatValidator::NotNan
+                | FloatValidator::MaxDp(_) => quote!(),
+            })
+            .collect()
     }
 
     fn gen_traits(
@@ -139,13 +241,16 @@ where
         inner_type: &Self::InnerType,
         traits: HashSet<Self::TypedTrait>,
         maybe_default_value: Option<syn::Expr>,
+        derive_options: &DeriveOptions,
         guard: &FloatGuard<T>,
+        _maybe_store_as: Option<&StoreAs>,
     ) -> Result<GeneratedTraits, syn::Error> {
         gen_traits(
             type_name,
             generics,
             inner_type,
             maybe_default_value,
+            derive_options,
             traits,
             guard,
         )
@@ -2,7 +2,10 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 
 use crate::{
-    common::models::{TypeName, Validation},
+    common::{
+        gen::arbitrary_invalid::gen_fn_arbitrary_invalid,
+        models::{TypeName, Validation},
+    },
     float::models::{
         FloatGuard, FloatInnerType, FloatSanitizer, FloatSanitizerKind, FloatValidator,
         FloatValidatorKind,
@@ -33,6 +36,10 @@ pub fn gen_impl_trait_arbitrary<T: ToTokens>(
     };
 
     let generate_inner_value = gen_generate_valid_inner_value(inner_type, guard)?;
+    let maybe_fn_arbitrary_invalid = guard.maybe_error_type_path().map(|error_type_path| {
+        let violations = gen_violations(inner_type, guard);
+        gen_fn_arbitrary_invalid(type_name, inner_type, error_type_path, violations)
+    });
 
     Ok(quote!(
         impl ::arbitrary::Arbitrary<'_> for #type_name {
@@ -49,9 +56,58 @@ pub fn gen_impl_trait_arbitrary<T: ToTokens>(
                 (n, Some(n))
             }
         }
+
+        #maybe_fn_arbitrary_invalid
     ))
 }
 
+/// Values beyond the declared bounds, infinities and NaN.
+fn gen_violations<T: ToTokens>(
+    inner_type: &FloatInnerType,
+    guard: &FloatGuard<T>,
+) -> Vec<TokenStream> {
+    let validators = guard
+        .standard_validators()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    // The distance from the bound is at least 1 and scales with the bound, so it is not lost
+    // to rounding.
+    let gen_distance = |bound: TokenStream| {
+        quote!(
+            let bound: #inner_type = #bound;
+            let steps: #inner_type = u.int_in_range(1u8..=100)?.into();
+            let distance = (bound.abs() + 1.0) * steps;
+        )
+    };
+
+    validators
+        .iter()
+        .filter_map(|validator| match validator {
+            FloatValidator::Greater(min) | FloatValidator::GreaterOrEqual(min) => {
+                let distance = gen_distance(quote!(#min));
+                Some(quote!(
+                    #distance
+                    Ok(bound - distance)
+                ))
+            }
+            FloatValidator::Less(max) | FloatValidator::LessOrEqual(max) => {
+                let distance = gen_distance(quote!(#max));
+                Some(quote!(
+                    #distance
+                    Ok(bound + distance)
+                ))
+            }
+            FloatValidator::Finite => Some(quote!(
+                Ok(*u.choose(&[#inner_type::INFINITY, #inner_type::NEG_INFINITY, #inner_type::NAN])?)
+            )),
+            FloatValidator::NotNan => Some(quote!(Ok(#inner_type::NAN))),
+            FloatValidator::In(_) | FloatValidator::Predicate(_) | FloatValidator::MaxDp(_) => {
+                None
+            }
+        })
+        .collect()
+}
+
 /// Generates a code that generates a valid inner value.
 fn gen_generate_valid_inner_value<T: ToTokens>(
     inner_type: &FloatInnerType,
@@ -66,6 +122,7 @@ fn gen_generate_valid_inner_value<T: ToTokens>(
         FloatGuard::WithValidation {
             sanitizers,
             validation,
+            ..
         } => {
             match validation {
                 Validation::Standard { validators, .. } => {
@@ -97,11 +154,31 @@ fn gen_generate_valid_inner_value_with_validators<T: ToTokens>(
         let msg = "It's not possible to derive `Arbitrary` trait for a type with `predicate` validator.\nYou have to implement `Arbitrary` trait on you own.";
         return Err(syn::Error::new(span, msg));
     }
+    if validator_kinds.contains(&FloatValidatorKind::In) {
+        let span = Span::call_site();
+        let msg = "It's not possible to derive `Arbitrary` trait for a type with `in` validator.\nYou have to implement `Arbitrary` trait on you own.";
+        return Err(syn::Error::new(span, msg));
+    }
     if sanitizer_kinds.contains(&FloatSanitizerKind::With) {
         let span = Span::call_site();
         let msg = "It's not possible to derive `Arbitrary` trait for a type with `with` sanitizer and validations.\nYou have to implement `Arbitrary` trait on you own.";
         return Err(syn::Error::new(span, msg));
     }
+    if validator_kinds.contains(&FloatValidatorKind::MaxDp) {
+        let span = Span::call_site();
+        let msg = "It's not possible to derive `Arbitrary` trait for a type with `max_dp` validator.\nYou have to implement `Arbitrary` trait on you own.";
+        return Err(syn::Error::new(span, msg));
+    }
+    if sanitizer_kinds.contains(&FloatSanitizerKind::RoundDp) {
+        let span = Span::call_site();
+        let msg = "It's not possible to derive `Arbitrary` trait for a type with `round_dp` sanitizer and validations.\nYou have to implement `Arbitrary` trait on you own.";
+        return Err(syn::Error::new(span, msg));
+    }
+    if sanitizer_kinds.contains(&FloatSanitizerKind::TryWith) {
+        let span = Span::call_site();
+        let msg = "It's not possible to derive `Arbitrary` trait for a type with `try_with` sanitizer.\nYou have to implement `Arbitrary` trait on you own.";
+        return Err(syn::Error::new(span, msg));
+    }
 
     let basic_value_kind = compute_basic_value_kind(&validator_kinds);
     let basic_value = generate_basic_value(inner_type, basic_value_kind);
@@ -272,7 +349,7 @@ fn compute_basic_value_kind(validators: &[FloatValidatorKind]) -> BasicValueKind
 
     if validators.contains(&FloatValidatorKind::Finite) {
         BasicValueKind::Finite
-    } else if has_boundaries() {
+    } else if validators.contains(&FloatValidatorKind::NotNan) || has_boundaries() {
         BasicValueKind::NotNaN
     } else {
         BasicValueKind::All
@@ -320,7 +397,11 @@ fn compute_boundaries<T: ToTokens>(validators: &[FloatValidator<T>]) -> Boundari
                     is_inclusive,
                 });
             }
-            FloatValidator::Finite | FloatValidator::Predicate(..) => {
+            FloatValidator::Finite
+            | FloatValidator::NotNan
+            | FloatValidator::In(..)
+            | FloatValidator::Predicate(..)
+            | FloatValidator::MaxDp(..) => {
                 // We don't care about these validators here.
             }
         }
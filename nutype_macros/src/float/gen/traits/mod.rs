@@ -7,14 +7,21 @@ use syn::Generics;
 
 use crate::{
     common::{
-        gen::traits::{
-            gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
-            gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
-            gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_serde_deserialize,
-            gen_impl_trait_serde_serialize, gen_impl_trait_try_from, split_into_generatable_traits,
-            GeneratableTrait, GeneratableTraits, GeneratedTraits,
+        gen::{
+            borsh::{gen_impl_trait_borsh_deserialize, gen_impl_trait_borsh_serialize},
+            clap::gen_impl_trait_clap_value_parser_factory,
+            rkyv::gen_impl_trait_rkyv,
+            traits::{
+                gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
+                gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
+                gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_into_types,
+                gen_impl_trait_serde_deserialize, gen_impl_trait_serde_serialize,
+                gen_impl_trait_try_from, gen_impl_trait_try_from_numeric_types,
+                gen_impl_trait_validator_validate, split_into_generatable_traits, GeneratableTrait,
+                GeneratableTraits, GeneratedTraits,
+            },
         },
-        models::TypeName,
+        models::{DeriveOptions, TypeName},
     },
     float::models::{FloatDeriveTrait, FloatGuard, FloatInnerType},
 };
@@ -43,6 +50,10 @@ enum FloatIrregularTrait {
     From,
     Eq,
     Ord,
+    // `PartialEq` and `PartialOrd` consistent with the total order of `total_cmp()`.
+    TotalPartialEq,
+    TotalPartialOrd,
+    Hash,
     TryFrom,
     Borrow,
     Display,
@@ -50,6 +61,11 @@ enum FloatIrregularTrait {
     SerdeSerialize,
     SerdeDeserialize,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl From<FloatDeriveTrait> for FloatGeneratableTrait {
@@ -72,6 +88,7 @@ impl From<FloatDeriveTrait> for FloatGeneratableTrait {
                 FloatGeneratableTrait::Transparent(FloatTransparentTrait::PartialOrd)
             }
             FloatDeriveTrait::Ord => FloatGeneratableTrait::Irregular(FloatIrregularTrait::Ord),
+            FloatDeriveTrait::Hash => FloatGeneratableTrait::Irregular(FloatIrregularTrait::Hash),
             FloatDeriveTrait::FromStr => {
                 FloatGeneratableTrait::Irregular(FloatIrregularTrait::FromStr)
             }
@@ -100,6 +117,21 @@ impl From<FloatDeriveTrait> for FloatGeneratableTrait {
             FloatDeriveTrait::ArbitraryArbitrary => {
                 FloatGeneratableTrait::Irregular(FloatIrregularTrait::ArbitraryArbitrary)
             }
+            FloatDeriveTrait::ValidatorValidate => {
+                FloatGeneratableTrait::Irregular(FloatIrregularTrait::ValidatorValidate)
+            }
+            FloatDeriveTrait::BorshSerialize => {
+                FloatGeneratableTrait::Irregular(FloatIrregularTrait::BorshSerialize)
+            }
+            FloatDeriveTrait::BorshDeserialize => {
+                FloatGeneratableTrait::Irregular(FloatIrregularTrait::BorshDeserialize)
+            }
+            FloatDeriveTrait::RkyvArchive => {
+                FloatGeneratableTrait::Irregular(FloatIrregularTrait::RkyvArchive)
+            }
+            FloatDeriveTrait::ClapValueParserFactory => {
+                FloatGeneratableTrait::Irregular(FloatIrregularTrait::ClapValueParserFactory)
+            }
             FloatDeriveTrait::SchemarsJsonSchema => {
                 FloatGeneratableTrait::Transparent(FloatTransparentTrait::SchemarsJsonSchema)
             }
@@ -126,14 +158,33 @@ pub fn gen_traits<T: ToTokens>(
     generics: &Generics,
     inner_type: &FloatInnerType,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     traits: HashSet<FloatDeriveTrait>,
     guard: &FloatGuard<T>,
 ) -> Result<GeneratedTraits, syn::Error> {
+    // With `total_order` all the comparison traits are implemented with `total_cmp()`,
+    // so they are consistent with each other even if NaN is not excluded by validation.
+    let total_order = derive_options.total_order.is_some();
+
     let GeneratableTraits {
-        transparent_traits,
-        irregular_traits,
+        mut transparent_traits,
+        mut irregular_traits,
     } = split_into_generatable_traits(traits);
 
+    if total_order {
+        transparent_traits.retain(|tr| match tr {
+            FloatTransparentTrait::PartialEq => {
+                irregular_traits.push(FloatIrregularTrait::TotalPartialEq);
+                false
+            }
+            FloatTransparentTrait::PartialOrd => {
+                irregular_traits.push(FloatIrregularTrait::TotalPartialOrd);
+                false
+            }
+            _ => true,
+        });
+    }
+
     let derive_transparent_traits = quote! {
         #[derive(
             #(#transparent_traits,)*
@@ -145,6 +196,7 @@ pub fn gen_traits<T: ToTokens>(
         generics,
         inner_type,
         maybe_default_value,
+        derive_options,
         irregular_traits,
         guard,
     )?;
@@ -160,6 +212,7 @@ fn gen_implemented_traits<T: ToTokens>(
     generics: &Generics,
     inner_type: &FloatInnerType,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     impl_traits: Vec<FloatIrregularTrait>,
     guard: &FloatGuard<T>,
 ) -> Result<TokenStream, syn::Error> {
@@ -173,12 +226,23 @@ fn gen_implemented_traits<T: ToTokens>(
                 Ok(gen_impl_trait_from_str(type_name, generics, inner_type, maybe_error_type_name))
             }
             FloatIrregularTrait::From => Ok(gen_impl_trait_from(type_name, generics, inner_type)),
-            FloatIrregularTrait::Into => Ok(gen_impl_trait_into(type_name, generics, inner_type)),
-            FloatIrregularTrait::TryFrom => {
-                Ok(gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name))
-            }
+            FloatIrregularTrait::Into => match derive_options.into {
+                Some(ref target_types) => Ok(gen_impl_trait_into_types(type_name, generics, target_types)),
+                None => Ok(gen_impl_trait_into(type_name, generics, inner_type)),
+            },
+            FloatIrregularTrait::TryFrom => match derive_options.try_from {
+                Some(ref source_types) => Ok(gen_impl_trait_try_from_numeric_types(
+                    type_name,
+                    generics,
+                    inner_type,
+                    source_types,
+                    maybe_error_type_name,
+                    impl_traits.contains(&FloatIrregularTrait::FromStr),
+                )),
+                None => Ok(gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name)),
+            },
             FloatIrregularTrait::Borrow => Ok(gen_impl_trait_borrow(type_name, generics, inner_type)),
-            FloatIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics)),
+            FloatIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics, derive_options.display.as_ref(), None)),
             FloatIrregularTrait::Default => match maybe_default_value {
                 Some(ref default_value) => {
                     let has_validation = maybe_error_type_name.is_some();
@@ -190,18 +254,52 @@ fn gen_implemented_traits<T: ToTokens>(
                     Err(syn::Error::new(span, msg))
                 }
             },
-            FloatIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics)),
+            FloatIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics, &derive_options.serde, None)),
             FloatIrregularTrait::SerdeDeserialize => Ok(gen_impl_trait_serde_deserialize(
                 type_name,
                 generics,
                 inner_type,
-                maybe_error_type_name,
+                maybe_error_type_name, &derive_options.serde,
+                None,
+                derive_options.profile.denies_unknown_fields(),
             )),
             FloatIrregularTrait::Eq => Ok(gen_impl_trait_eq(type_name)),
-            FloatIrregularTrait::Ord => Ok(gen_impl_trait_ord(type_name)),
+            FloatIrregularTrait::Ord => {
+                if impl_traits.contains(&FloatIrregularTrait::TotalPartialOrd) {
+                    Ok(gen_impl_trait_total_ord(type_name))
+                } else {
+                    Ok(gen_impl_trait_ord(type_name))
+                }
+            }
+            FloatIrregularTrait::TotalPartialEq => Ok(gen_impl_trait_total_partial_eq(type_name)),
+            FloatIrregularTrait::TotalPartialOrd => Ok(gen_impl_trait_total_partial_ord(type_name)),
+            FloatIrregularTrait::Hash => Ok(gen_impl_trait_hash(type_name)),
             FloatIrregularTrait::ArbitraryArbitrary => {
                 arbitrary::gen_impl_trait_arbitrary(type_name, inner_type, guard)
             }
+            FloatIrregularTrait::ValidatorValidate => {
+                Ok(gen_impl_trait_validator_validate(type_name, generics))
+            }
+            FloatIrregularTrait::BorshSerialize => {
+                Ok(gen_impl_trait_borsh_serialize(type_name, generics, None))
+            }
+            FloatIrregularTrait::BorshDeserialize => Ok(gen_impl_trait_borsh_deserialize(
+                type_name,
+                generics,
+                inner_type,
+                maybe_error_type_name,
+            )),
+            FloatIrregularTrait::RkyvArchive => {
+                gen_impl_trait_rkyv(type_name, generics, inner_type, maybe_error_type_name)
+            }
+            FloatIrregularTrait::ClapValueParserFactory => {
+                Ok(gen_impl_trait_clap_value_parser_factory(
+                    type_name,
+                    generics,
+                    inner_type,
+                    maybe_error_type_name,
+                ))
+            }
         })
         .collect()
 }
@@ -212,10 +310,26 @@ fn gen_impl_trait_eq(type_name: &TypeName) -> TokenStream {
     }
 }
 
+// Hash is computed from the bits of the value, so it is consistent with `PartialEq`
+// only when NaN is excluded by validation. `-0.0` and `0.0` are equal, so they must be
+// hashed identically.
+fn gen_impl_trait_hash(type_name: &TypeName) -> TokenStream {
+    quote! {
+        // Make clippy ignore this manual implementation of Hash even when PartialEq is derived.
+        #[allow(clippy::derived_hash_with_manual_eq)]
+        impl ::core::hash::Hash for #type_name {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                let value = if self.0 == 0.0 { 0.0 } else { self.0 };
+                value.to_bits().hash(state);
+            }
+        }
+    }
+}
+
 // The implementation below may panic.
 // Function `partial_cmp` returns `None` only for `NaN` values, but
 // `NaN` values are supposed to be excluded by `finite` validation rule.
-// Without `finite` validation deriving `Ord` is not allowed.
+// With `total_order` `Ord` is implemented with `total_cmp` instead.
 fn gen_impl_trait_ord(type_name: &TypeName) -> TokenStream {
     let tp = type_name.to_string();
     quote! {
@@ -232,3 +346,38 @@ fn gen_impl_trait_ord(type_name: &TypeName) -> TokenStream {
         }
     }
 }
+
+// Implementations of the comparison traits with `total_order`, so values may be NaN.
+// They follow the total order of `total_cmp()`: `-0.0` is less than `0.0` and
+// NaN is equal to itself, so `Eq` and `Ord` hold for every value.
+fn gen_impl_trait_total_partial_eq(type_name: &TypeName) -> TokenStream {
+    quote! {
+        impl ::core::cmp::PartialEq for #type_name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.total_cmp(&other.0).is_eq()
+            }
+        }
+    }
+}
+
+fn gen_impl_trait_total_partial_ord(type_name: &TypeName) -> TokenStream {
+    quote! {
+        // `Ord` may be not derived, so `partial_cmp()` can not always call `cmp()`.
+        #[allow(clippy::non_canonical_partial_ord_impl)]
+        impl ::core::cmp::PartialOrd for #type_name {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(self.0.total_cmp(&other.0))
+            }
+        }
+    }
+}
+
+fn gen_impl_trait_total_ord(type_name: &TypeName) -> TokenStream {
+    quote! {
+        impl ::core::cmp::Ord for #type_name {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+    }
+}
@@ -8,9 +8,16 @@ use std::collections::HashSet;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-use crate::common::{
-    gen::GenerateNewtype,
-    models::{Attributes, GenerateParams, Guard, Newtype, SpannedDeriveTrait, TypeName},
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::{optional::gen_optional_nutype, GenerateNewtype},
+        models::{
+            Attributes, DeriveOptions, DeriveTrait, GenerateParams, Guard, Newtype,
+            SpannedDeriveTrait, TypeName,
+        },
+        validate::validate_serde_borrow,
+    },
 };
 
 use self::{
@@ -40,6 +47,7 @@ where
     fn parse_attributes(
         attrs: TokenStream,
         type_name: &TypeName,
+        _inner_type: &Self::InnerType,
     ) -> Result<Attributes<FloatGuard<T>, SpannedDeriveTrait>, syn::Error> {
         parse::parse_attributes::<T>(attrs, type_name)
     }
@@ -47,8 +55,11 @@ where
     fn validate(
         guard: &Guard<Self::Sanitizer, Self::Validator>,
         derive_traits: Vec<SpannedDeriveTrait>,
+        _inner_type: &Self::InnerType,
+        derive_options: &DeriveOptions,
     ) -> Result<HashSet<Self::TypedTrait>, syn::Error> {
-        validate_float_derive_traits(derive_traits, guard)
+        validate_serde_borrow(derive_options, false)?;
+        validate_float_derive_traits(derive_traits, guard, derive_options)
     }
 
     fn generate(
@@ -60,4 +71,16 @@ where
     ) -> Result<TokenStream, syn::Error> {
         FloatNewtype::gen_nutype(params)
     }
+
+    fn generate_optional(
+        params: GenerateParams<
+            FloatInnerType,
+            DeriveTrait,
+            Guard<Self::Sanitizer, Self::Validator>,
+        >,
+        optional_type: &AnyInnerType,
+        is_required: bool,
+    ) -> Result<TokenStream, syn::Error> {
+        gen_optional_nutype::<FloatNewtype<T>>(params, optional_type, is_required)
+    }
 }
@@ -3,16 +3,20 @@ use proc_macro2::TokenStream;
 
 use crate::common::models::{
     impl_numeric_bound_on_vec_of, impl_numeric_bound_validator, Guard, RawGuard, SpannedItem,
-    TypeTrait, TypedCustomFunction, ValueOrExpr,
+    TrySanitizer, TypeTrait, TypedCustomFunction, ValueOrExpr,
 };
 
 // Sanitizer
 //
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Kinded)]
 #[kinded(display = "snake_case", derive(Hash))]
 pub enum FloatSanitizer<T> {
     With(TypedCustomFunction),
+    TryWith(TrySanitizer),
+    /// Round to the given number of decimal places.
+    RoundDp(u32),
     _Phantom(core::marker::PhantomData<T>),
 }
 
@@ -28,8 +32,12 @@ pub enum FloatValidator<T> {
     GreaterOrEqual(ValueOrExpr<T>),
     Less(ValueOrExpr<T>),
     LessOrEqual(ValueOrExpr<T>),
+    In(Vec<syn::Expr>),
     Predicate(TypedCustomFunction),
     Finite,
+    NotNan,
+    /// At most the given number of decimal places.
+    MaxDp(u32),
 }
 
 impl_numeric_bound_validator!(FloatValidator);
@@ -49,6 +57,7 @@ pub enum FloatDeriveTrait {
     Eq,
     PartialOrd,
     Ord,
+    Hash,
     FromStr,
     AsRef,
     Into,
@@ -64,12 +73,33 @@ pub enum FloatDeriveTrait {
     SerdeDeserialize,
     SchemarsJsonSchema,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl TypeTrait for FloatDeriveTrait {
     fn is_from_str(&self) -> bool {
         self == &FloatDeriveTrait::FromStr
     }
+
+    fn is_debug(&self) -> bool {
+        self == &FloatDeriveTrait::Debug
+    }
+
+    fn is_display(&self) -> bool {
+        self == &FloatDeriveTrait::Display
+    }
+
+    fn is_json_schema(&self) -> bool {
+        self == &FloatDeriveTrait::SchemarsJsonSchema
+    }
+
+    fn is_rkyv_archive(&self) -> bool {
+        self == &FloatDeriveTrait::RkyvArchive
+    }
 }
 
 pub type FloatRawGuard<T> = RawGuard<SpannedFloatSanitizer<T>, SpannedFloatValidator<T>>;
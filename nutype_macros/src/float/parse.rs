@@ -4,13 +4,15 @@ use core::{
 };
 
 use crate::common::{
-    models::{Attributes, SpannedDeriveTrait, TypeName},
+    models::{Attributes, SpannedDeriveTrait, TypeName, ValueOrExpr},
     parse::{
-        parse_number_or_expr, parse_sanitizer_kind, parse_typed_custom_function,
-        parse_validator_kind, ParseableAttributes,
+        parse_allowed_values, parse_number, parse_number_or_expr, parse_sanitizer_kind,
+        parse_try_sanitizer, parse_typed_custom_function, parse_validator_kind,
+        ParseableAttributes, ValidatorPresets,
     },
+    validate::validate_nohash,
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream},
     Token,
@@ -21,7 +23,7 @@ use super::{
         FloatGuard, FloatRawGuard, FloatSanitizer, FloatSanitizerKind, FloatValidator,
         FloatValidatorKind, SpannedFloatSanitizer, SpannedFloatValidator,
     },
-    validate::validate_float_guard,
+    validate::{validate_float_default_value, validate_float_guard},
 };
 
 pub fn parse_attributes<T>(
@@ -38,20 +40,84 @@ where
     let ParseableAttributes {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
         new_unchecked,
         default,
         derive_traits,
+        parse_money,
+        store_as,
+        storage,
+        as_variants,
+        bounded,
+        required,
+        trace,
+        secret,
+        arena,
+        guarantee,
+        derive_options,
     } = attrs;
+    validate_nohash(&derive_options, false)?;
+    if let Some(guarantee) = guarantee {
+        let msg = "`guarantee` is not supported by float based types, because `NaN` cannot be clamped into a range.";
+        return Err(syn::Error::new(guarantee, msg));
+    }
+    if let Some(bounded) = bounded {
+        let msg = "`bounded` is supported only by integer based types.";
+        return Err(syn::Error::new(bounded, msg));
+    }
+    if let Some(arena) = arena {
+        let msg = "`arena` is supported only by `&'a str` based types.";
+        return Err(syn::Error::new(arena, msg));
+    }
+    if let Some(bytes) = derive_options.bytes {
+        let msg = "`bytes` is supported only by `String` based types.";
+        return Err(syn::Error::new(bytes, msg));
+    }
+    if let Some(eq_str) = derive_options.eq_str {
+        let msg = "`eq_str` is supported only by string based types.";
+        return Err(syn::Error::new(eq_str, msg));
+    }
+    if let Some(encoding) = &derive_options.encoding {
+        let msg = "`encoding` is supported only by `[u8; N]` based types.";
+        return Err(syn::Error::new(encoding.span(), msg));
+    }
+    if let Some(as_variants_span) = as_variants {
+        let msg = "`as_variants` is supported only by string and integer based types, because floats can not be compared for equality reliably.";
+        return Err(syn::Error::new(as_variants_span, msg));
+    }
+    if let Some(store_as) = store_as {
+        let msg = "`store_as` is supported only by `String` based types.";
+        return Err(syn::Error::new(store_as.span(), msg));
+    }
+    if let Some(storage) = storage {
+        let msg = "`storage` is supported only by `String` based types.";
+        return Err(syn::Error::new(storage.span(), msg));
+    }
     let raw_guard = FloatRawGuard {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
     };
     let guard = validate_float_guard(raw_guard, type_name)?;
+    if let Some(ref default_value) = default {
+        validate_float_default_value(&guard, default_value)?;
+    }
     Ok(Attributes {
         new_unchecked,
         guard,
         default,
         derive_traits,
+        parse_money: parse_money.map(|parse_money| parse_money.item),
+        store_as: None,
+        as_variants: None,
+        required,
+        trace: trace.is_some(),
+        secret,
+        arena: false,
+        guarantee: false,
+        derive_options,
     })
 }
 
@@ -96,6 +162,14 @@ where
                     span,
                 })
             }
+            FloatValidatorKind::In => {
+                let _eq: Token![=] = input.parse()?;
+                let (allowed_values, span) = parse_allowed_values(input)?;
+                Ok(SpannedFloatValidator {
+                    item: FloatValidator::In(allowed_values),
+                    span,
+                })
+            }
             FloatValidatorKind::Predicate => {
                 let _eq: Token![=] = input.parse()?;
                 let (typed_custom_function, span) = parse_typed_custom_function::<&T>(input)?;
@@ -111,10 +185,62 @@ where
                     span: ident.span(),
                 })
             }
+            FloatValidatorKind::NotNan => Ok(SpannedFloatValidator {
+                item: FloatValidator::NotNan,
+                span: ident.span(),
+            }),
+            FloatValidatorKind::MaxDp => {
+                let _eq: Token![=] = input.parse()?;
+                let (decimal_places, span) = parse_decimal_places(input)?;
+                Ok(SpannedFloatValidator {
+                    item: FloatValidator::MaxDp(decimal_places),
+                    span,
+                })
+            }
         }
     }
 }
 
+/// Number of decimal places of `round_dp` and `max_dp`. `f64` has at most 15 significant decimal
+/// digits, so more decimal places can not be distinguished.
+fn parse_decimal_places(input: ParseStream) -> syn::Result<(u32, Span)> {
+    let (decimal_places, span) = parse_number::<u32>(input)?;
+    if decimal_places > MAX_DECIMAL_PLACES {
+        let msg = format!("The number of decimal places must be at most {MAX_DECIMAL_PLACES}.");
+        return Err(syn::Error::new(span, msg));
+    }
+    Ok((decimal_places, span))
+}
+
+const MAX_DECIMAL_PLACES: u32 = 15;
+
+impl<T> ValidatorPresets for SpannedFloatValidator<T>
+where
+    T: FromStr,
+{
+    const PRESETS: &'static [&'static str] = &["unit_interval", "percentage"];
+
+    fn expand_preset(ident: &Ident) -> Option<Vec<Self>> {
+        let (min, max) = match ident.to_string().as_str() {
+            "unit_interval" => ("0.0", "1.0"),
+            "percentage" => ("0.0", "100.0"),
+            _ => return None,
+        };
+        let span = ident.span();
+        let min = min.parse::<T>().ok()?;
+        let max = max.parse::<T>().ok()?;
+        // A value in a range can not be NaN, so the presets also allow deriving `Eq` and `Ord`.
+        Some(vec![
+            SpannedFloatValidator::new(FloatValidator::NotNan, span),
+            SpannedFloatValidator::new(
+                FloatValidator::GreaterOrEqual(ValueOrExpr::Value(min)),
+                span,
+            ),
+            SpannedFloatValidator::new(FloatValidator::LessOrEqual(ValueOrExpr::Value(max)), span),
+        ])
+    }
+}
+
 impl<T> Parse for SpannedFloatSanitizer<T>
 where
     T: FromStr,
@@ -132,6 +258,21 @@ where
                     span,
                 })
             }
+            FloatSanitizerKind::TryWith => {
+                let (try_sanitizer, span) = parse_try_sanitizer(input)?;
+                Ok(SpannedFloatSanitizer {
+                    item: FloatSanitizer::TryWith(try_sanitizer),
+                    span,
+                })
+            }
+            FloatSanitizerKind::RoundDp => {
+                let _eq: Token![=] = input.parse()?;
+                let (decimal_places, span) = parse_decimal_places(input)?;
+                Ok(SpannedFloatSanitizer {
+                    item: FloatSanitizer::RoundDp(decimal_places),
+                    span,
+                })
+            }
             FloatSanitizerKind::_Phantom => {
                 let msg = format!("Unknown validator `{ident}`");
                 Err(syn::Error::new(ident.span(), msg))
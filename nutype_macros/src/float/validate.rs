@@ -1,10 +1,13 @@
+use core::str::FromStr;
+
 use proc_macro2::Span;
 use std::collections::HashSet;
 
 use crate::common::{
-    models::{DeriveTrait, SpannedDeriveTrait, TypeName, Validation},
+    models::{DeriveOptions, DeriveTrait, SpannedDeriveTrait, TypeName, Validation},
     validate::{
-        validate_duplicates, validate_guard, validate_numeric_bounds,
+        unsupported_collection_trait, validate_duplicates, validate_guard, validate_numeric_bounds,
+        validate_numeric_default_value, validate_sanitizer_duplicates,
         validate_traits_from_xor_try_from,
     },
 };
@@ -29,6 +32,23 @@ where
     )
 }
 
+/// Check a literal default value against the numeric bounds at compile time.
+/// It's skipped if the value goes through custom sanitizers first.
+pub fn validate_float_default_value<T>(
+    guard: &FloatGuard<T>,
+    default_value: &syn::Expr,
+) -> Result<(), syn::Error>
+where
+    T: PartialOrd + Clone + FromStr,
+{
+    match guard.standard_validators() {
+        Some(validators) if guard.sanitizers().is_empty() => {
+            validate_numeric_default_value(default_value, validators)
+        }
+        _ => Ok(()),
+    }
+}
+
 fn validate_validators<T>(
     validators: Vec<SpannedFloatValidator<T>>,
 ) -> Result<Vec<FloatValidator<T>>, syn::Error>
@@ -53,7 +73,7 @@ fn validate_sanitizers<T>(
 where
     T: PartialOrd + Clone,
 {
-    validate_duplicates(&sanitizers, |kind| {
+    validate_sanitizer_duplicates(&sanitizers, |kind| {
         format!("Duplicated sanitizer `{kind}`.\nIt happens, don't worry. We still love you!")
     })?;
 
@@ -66,9 +86,14 @@ fn has_validation_against_nan<T>(guard: &FloatGuard<T>) -> bool {
         FloatGuard::WithoutValidation { .. } => false,
         FloatGuard::WithValidation { validation, .. } => match validation {
             Validation::Custom { .. } => false,
-            Validation::Standard { validators, .. } => validators
-                .iter()
-                .any(|v| v.kind() == FloatValidatorKind::Finite),
+            Validation::Standard { validators, .. } => validators.iter().any(|v| {
+                matches!(
+                    v.kind(),
+                    FloatValidatorKind::Finite
+                        | FloatValidatorKind::NotNan
+                        | FloatValidatorKind::MaxDp
+                )
+            }),
         },
     }
 }
@@ -77,15 +102,17 @@ fn has_validation_against_nan<T>(guard: &FloatGuard<T>) -> bool {
 struct ValidationInfo {
     has_validation: bool,
     has_nan_validation: bool,
+    total_order: bool,
 }
 
 impl ValidationInfo {
-    fn from_guard<T>(guard: &FloatGuard<T>) -> ValidationInfo {
+    fn new<T>(guard: &FloatGuard<T>, derive_options: &DeriveOptions) -> ValidationInfo {
         let has_validation = guard.has_validation();
         let has_nan_validation = has_validation_against_nan(guard);
         ValidationInfo {
             has_validation,
             has_nan_validation,
+            total_order: derive_options.total_order.is_some(),
         }
     }
 }
@@ -93,10 +120,11 @@ impl ValidationInfo {
 pub fn validate_float_derive_traits<T>(
     spanned_derive_traits: Vec<SpannedDeriveTrait>,
     guard: &FloatGuard<T>,
+    derive_options: &DeriveOptions,
 ) -> Result<HashSet<FloatDeriveTrait>, syn::Error> {
     validate_traits_from_xor_try_from(&spanned_derive_traits)?;
 
-    let validation = ValidationInfo::from_guard(guard);
+    let validation = ValidationInfo::new(guard, derive_options);
     let mut traits = HashSet::with_capacity(24);
 
     for spanned_trait in spanned_derive_traits.iter() {
@@ -157,30 +185,37 @@ fn to_float_derive_trait(
         DeriveTrait::PartialEq => Ok(FloatDeriveTrait::PartialEq),
         DeriveTrait::Into => Ok(FloatDeriveTrait::Into),
         DeriveTrait::Eq => {
-            if validation.has_nan_validation {
+            if validation.has_nan_validation || validation.total_order {
                 Ok(FloatDeriveTrait::Eq)
             } else {
-                let msg = "To derive Eq trait on float-based type there must be validation that proves that inner value is not NaN.\nConsider adding:\n    validate(finite)";
+                let msg = "To derive Eq trait on float-based type there must be validation that proves that inner value is not NaN.\nConsider adding:\n    validate(finite)\nor:\n    validate(not_nan)\nor compare with `total_cmp()`, where `-0.0 != 0.0` and NaN is equal to itself:\n    total_order";
                 Err(syn::Error::new(span, msg))
             }
         }
         DeriveTrait::PartialOrd => Ok(FloatDeriveTrait::PartialOrd),
         DeriveTrait::Ord => {
-            if validation.has_nan_validation {
+            if validation.has_nan_validation || validation.total_order {
                 Ok(FloatDeriveTrait::Ord)
             } else {
-                let msg = "To derive Ord trait on float-based type there must be validation that proves that inner value is not NaN.\nConsider adding:\n    validate(finite)";
+                let msg = "To derive Ord trait on float-based type there must be validation that proves that inner value is not NaN.\nConsider adding:\n    validate(finite)\nor:\n    validate(not_nan)\nor compare with `total_cmp()`, where `-0.0 < 0.0` and NaN is greater than any other value:\n    total_order";
                 Err(syn::Error::new(span, msg))
             }
         }
         DeriveTrait::FromStr => Ok(FloatDeriveTrait::FromStr),
         DeriveTrait::AsRef => Ok(FloatDeriveTrait::AsRef),
         DeriveTrait::Deref => Ok(FloatDeriveTrait::Deref),
-        DeriveTrait::Hash => Err(syn::Error::new(
-            span,
-            "#[nutype] cannot derive `Hash` trait for float types.",
-        )),
+        DeriveTrait::Hash => {
+            if validation.has_nan_validation {
+                Ok(FloatDeriveTrait::Hash)
+            } else {
+                let msg = "To derive Hash trait on float-based type there must be validation that proves that inner value is not NaN.\nConsider adding:\n    validate(finite)\nor:\n    validate(not_nan)";
+                Err(syn::Error::new(span, msg))
+            }
+        }
         DeriveTrait::Borrow => Ok(FloatDeriveTrait::Borrow),
+        DeriveTrait::IntoIterator | DeriveTrait::Extend => {
+            Err(unsupported_collection_trait(tr, span))
+        }
         DeriveTrait::Copy => Ok(FloatDeriveTrait::Copy),
         DeriveTrait::From => {
             if validation.has_validation {
@@ -197,5 +232,10 @@ fn to_float_derive_trait(
         DeriveTrait::SerdeDeserialize => Ok(FloatDeriveTrait::SerdeDeserialize),
         DeriveTrait::SchemarsJsonSchema => Ok(FloatDeriveTrait::SchemarsJsonSchema),
         DeriveTrait::ArbitraryArbitrary => Ok(FloatDeriveTrait::ArbitraryArbitrary),
+        DeriveTrait::ValidatorValidate => Ok(FloatDeriveTrait::ValidatorValidate),
+        DeriveTrait::BorshSerialize => Ok(FloatDeriveTrait::BorshSerialize),
+        DeriveTrait::BorshDeserialize => Ok(FloatDeriveTrait::BorshDeserialize),
+        DeriveTrait::RkyvArchive => Ok(FloatDeriveTrait::RkyvArchive),
+        DeriveTrait::ClapValueParserFactory => Ok(FloatDeriveTrait::ClapValueParserFactory),
     }
 }
@@ -0,0 +1,250 @@
+//! `#[nutype_from_schema(...)]` translates the constraints of a JSON Schema snippet into
+//! `validate(...)` rules of `#[nutype(...)]`.
+
+use proc_macro2::{Delimiter, Group, Literal, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use serde_json::{Number, Value};
+use syn::{Attribute, ItemStruct, LitStr, Meta};
+
+/// Keywords that only describe the value, so they do not produce validators.
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "examples",
+    "default",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Keywords that are translated into validators, in the order of the generated validators.
+const SUPPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "enum",
+    "const",
+];
+
+pub fn expand_from_schema(
+    attrs: TokenStream,
+    type_definition: TokenStream,
+) -> Result<TokenStream, syn::Error> {
+    let schema_lit: LitStr = syn::parse2(attrs).map_err(|err| {
+        let msg = "`nutype_from_schema` expects a JSON Schema as a string literal, e.g.:\n\n    #[nutype_from_schema(r#\"{ \"type\": \"string\", \"maxLength\": 20 }\"#)]\n";
+        syn::Error::new(err.span(), msg)
+    })?;
+    let validators = schema_to_validators(&schema_lit)?;
+
+    let mut item: ItemStruct = syn::parse2(type_definition)?;
+    match item.attrs.iter_mut().find(|attr| is_nutype_attribute(attr)) {
+        Some(attr) => {
+            let path = attr.path().clone();
+            let args = match &attr.meta {
+                Meta::List(list) => list.tokens.clone(),
+                _ => TokenStream::new(),
+            };
+            let args = merge_validators(args, validators);
+            attr.meta = syn::parse_quote!(#path(#args));
+        }
+        None if validators.is_empty() => {}
+        None => {
+            let attr: Attribute = syn::parse_quote!(#[::nutype::nutype(validate(#validators))]);
+            item.attrs.insert(0, attr);
+        }
+    }
+    Ok(item.into_token_stream())
+}
+
+fn is_nutype_attribute(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "nutype")
+}
+
+/// Add the validators to `validate(...)` of the `#[nutype(...)]` arguments, or append
+/// `validate(...)` if there is none yet.
+fn merge_validators(args: TokenStream, validators: TokenStream) -> TokenStream {
+    if validators.is_empty() {
+        return args;
+    }
+    let mut tokens: Vec<TokenTree> = args.into_iter().collect();
+    let validate_pos = tokens.windows(2).position(|pair| match pair {
+        [TokenTree::Ident(ident), TokenTree::Group(group)] => {
+            ident == "validate" && group.delimiter() == Delimiter::Parenthesis
+        }
+        _ => false,
+    });
+    match validate_pos {
+        Some(pos) => {
+            let TokenTree::Group(group) = &tokens[pos + 1] else {
+                unreachable!("`validate` is followed by a group");
+            };
+            let existing = group.stream();
+            let mut merged = Group::new(Delimiter::Parenthesis, quote!(#existing, #validators));
+            merged.set_span(group.span());
+            tokens[pos + 1] = TokenTree::Group(merged);
+            tokens.into_iter().collect()
+        }
+        None => {
+            let args: TokenStream = tokens.into_iter().collect();
+            if args.is_empty() {
+                quote!(validate(#validators))
+            } else {
+                quote!(#args, validate(#validators))
+            }
+        }
+    }
+}
+
+fn schema_to_validators(schema_lit: &LitStr) -> Result<TokenStream, syn::Error> {
+    let span = schema_lit.span();
+    let schema: Value = serde_json::from_str(&schema_lit.value()).map_err(|err| {
+        let msg = format!("Invalid JSON Schema: {err}");
+        syn::Error::new(span, msg)
+    })?;
+    let Value::Object(schema) = schema else {
+        let msg = "JSON Schema must be an object.";
+        return Err(syn::Error::new(span, msg));
+    };
+
+    let schema_type = match schema.get("type") {
+        None => None,
+        Some(Value::String(tp)) if ["string", "integer", "number"].contains(&tp.as_str()) => {
+            Some(tp.as_str())
+        }
+        Some(tp) => {
+            let msg = format!("Unsupported JSON Schema type {tp}.\nSupported types are \"string\", \"integer\" and \"number\".");
+            return Err(syn::Error::new(span, msg));
+        }
+    };
+
+    if let Some(keyword) = schema.keys().find(|keyword| {
+        !SUPPORTED_KEYWORDS.contains(&keyword.as_str())
+            && !ANNOTATION_KEYWORDS.contains(&keyword.as_str())
+    }) {
+        let supported = SUPPORTED_KEYWORDS
+            .iter()
+            .map(|keyword| format!("`{keyword}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!(
+            "Unsupported JSON Schema keyword `{keyword}`.\nSupported keywords are {supported}."
+        );
+        return Err(syn::Error::new(span, msg));
+    }
+
+    let mut validators: Vec<TokenStream> = Vec::new();
+    for &keyword in SUPPORTED_KEYWORDS {
+        let Some(value) = schema.get(keyword) else {
+            continue;
+        };
+        let validator = match keyword {
+            "minLength" => {
+                let len = get_length(keyword, value, span)?;
+                quote!(len_char_min = #len)
+            }
+            "maxLength" => {
+                let len = get_length(keyword, value, span)?;
+                quote!(len_char_max = #len)
+            }
+            "pattern" => {
+                let Value::String(pattern) = value else {
+                    return Err(invalid_value_error(keyword, "a string", span));
+                };
+                let pattern = LitStr::new(pattern, span);
+                quote!(regex = #pattern)
+            }
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                let Value::Number(number) = value else {
+                    return Err(invalid_value_error(keyword, "a number", span));
+                };
+                let number = number_to_tokens(number, schema_type);
+                match keyword {
+                    "minimum" => quote!(greater_or_equal = #number),
+                    "maximum" => quote!(less_or_equal = #number),
+                    "exclusiveMinimum" => quote!(greater = #number),
+                    _ => quote!(less = #number),
+                }
+            }
+            "enum" => {
+                let Value::Array(values) = value else {
+                    return Err(invalid_value_error(keyword, "an array", span));
+                };
+                let values = values
+                    .iter()
+                    .map(|value| value_to_tokens(keyword, value, schema_type, span))
+                    .collect::<Result<Vec<_>, _>>()?;
+                quote!(in = [#(#values),*])
+            }
+            "const" => {
+                let value = value_to_tokens(keyword, value, schema_type, span)?;
+                quote!(in = [#value])
+            }
+            // `type` is used only to write the numbers.
+            _ => continue,
+        };
+        validators.push(validator);
+    }
+
+    Ok(quote!(#(#validators),*))
+}
+
+fn get_length(keyword: &str, value: &Value, span: Span) -> Result<Literal, syn::Error> {
+    value
+        .as_u64()
+        .map(|len| Literal::usize_unsuffixed(len as usize))
+        .ok_or_else(|| invalid_value_error(keyword, "a non-negative integer", span))
+}
+
+fn value_to_tokens(
+    keyword: &str,
+    value: &Value,
+    schema_type: Option<&str>,
+    span: Span,
+) -> Result<TokenStream, syn::Error> {
+    match value {
+        Value::String(string) => Ok(LitStr::new(string, span).into_token_stream()),
+        Value::Number(number) => Ok(number_to_tokens(number, schema_type)),
+        _ => Err(invalid_value_error(
+            keyword,
+            "a string or a number (or an array of them)",
+            span,
+        )),
+    }
+}
+
+/// Numbers of `"type": "number"` are written as float literals, so `0` becomes `0.0`.
+fn number_to_tokens(number: &Number, schema_type: Option<&str>) -> TokenStream {
+    let is_float = schema_type == Some("number") || number.is_f64();
+    if is_float {
+        let value = number.as_f64().unwrap_or_default();
+        let literal = Literal::f64_unsuffixed(value.abs());
+        if value.is_sign_negative() {
+            quote!(-#literal)
+        } else {
+            literal.into_token_stream()
+        }
+    } else if let Some(value) = number.as_u64() {
+        Literal::u64_unsuffixed(value).into_token_stream()
+    } else {
+        // Only negative integers do not fit into `u64`.
+        let value = number.as_i64().unwrap_or_default();
+        let literal = Literal::u64_unsuffixed(value.unsigned_abs());
+        quote!(-#literal)
+    }
+}
+
+fn invalid_value_error(keyword: &str, expected: &str, span: Span) -> syn::Error {
+    let msg = format!("The value of JSON Schema keyword `{keyword}` must be {expected}.");
+    syn::Error::new(span, msg)
+}
@@ -3,18 +3,37 @@ use quote::{quote, ToTokens};
 
 use super::super::models::IntegerValidator;
 use crate::common::{
-    gen::error::gen_impl_error_trait,
-    models::{ErrorTypePath, TypeName},
+    gen::error::{
+        allowed_values_to_string, describe_range, gen_custom_display_arms, gen_extra_display_arms,
+        gen_extra_variants, gen_impl_error_code, gen_impl_error_trait, ErrorVariantCode,
+        ExtraVariants, RangeBound,
+    },
+    models::{ErrorMessages, ErrorTypePath, Rule, TypeName},
 };
 
 pub fn gen_validation_error_type<T: ToTokens>(
     type_name: &TypeName,
+    inner_type: &impl ToTokens,
     error_type_path: &ErrorTypePath,
     validators: &[IntegerValidator<T>],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let definition = gen_definition(error_type_path, validators);
-    let impl_display_trait = gen_impl_display_trait(type_name, error_type_path, validators);
+    let definition = gen_definition(inner_type, error_type_path, validators, extra_variants);
+    let impl_display_trait = gen_impl_display_trait(
+        type_name,
+        error_type_path,
+        validators,
+        extra_variants,
+        messages,
+    );
     let impl_error_trait = gen_impl_error_trait(error_type_path);
+    let impl_error_code = gen_impl_error_code(
+        error_type_path,
+        gen_variant_codes(validators),
+        Some(quote!(#inner_type)),
+        extra_variants,
+    );
 
     quote! {
         #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,12 +41,15 @@ pub fn gen_validation_error_type<T: ToTokens>(
 
         #impl_display_trait
         #impl_error_trait
+        #impl_error_code
     }
 }
 
 fn gen_definition<T>(
+    inner_type: &impl ToTokens,
     error_type_path: &ErrorTypePath,
     validators: &[IntegerValidator<T>],
+    extra_variants: ExtraVariants,
 ) -> TokenStream {
     let error_variants: TokenStream = validators
         .iter()
@@ -44,16 +66,22 @@ fn gen_definition<T>(
             IntegerValidator::LessOrEqual(_) => {
                 quote!(LessOrEqualViolated,)
             }
+            IntegerValidator::In(_) => {
+                quote!(InViolated(#inner_type),)
+            }
             IntegerValidator::Predicate(_) => {
                 quote!(PredicateViolated,)
             }
         })
         .collect();
 
+    let extra_variants_tokens = gen_extra_variants(extra_variants);
+
     quote! {
         #[allow(clippy::enum_variant_names)]
         pub enum #error_type_path {
             #error_variants
+            #extra_variants_tokens
         }
     }
 }
@@ -62,32 +90,113 @@ fn gen_impl_display_trait<T: ToTokens>(
     type_name: &TypeName,
     error_type_path: &ErrorTypePath,
     validators: &[IntegerValidator<T>],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let match_arms = validators.iter().map(|validator| match validator {
-        IntegerValidator::Greater(val) => quote! {
-             #error_type_path::GreaterViolated => write!(f, "{} is too small. The value must be greater than {:#?}.", stringify!(#type_name), #val)
-        },
-        IntegerValidator::GreaterOrEqual(val) => quote! {
-             #error_type_path::GreaterOrEqualViolated => write!(f, "{} is too small. The value must be greater or equal to {:#?}.", stringify!(#type_name), #val)
-        },
-        IntegerValidator::Less(val) => quote! {
-             #error_type_path::LessViolated=> write!(f, "{} is too big. The value must be less than {:#?}.", stringify!(#type_name), #val)
-        },
-        IntegerValidator::LessOrEqual(val) => quote! {
-             #error_type_path::LessOrEqualViolated=> write!(f, "{} is too big. The value must be less or equal to {:#?}.", stringify!(#type_name), #val)
-        },
+    let match_arms = validators
+        .iter()
+        .filter(|validator| messages.get(&validator.name()).is_none())
+        .map(|validator| match validator {
+        IntegerValidator::Greater(_) => {
+            let message = gen_bound_message(type_name, "small", validators);
+            quote!(#error_type_path::GreaterViolated => #message)
+        }
+        IntegerValidator::GreaterOrEqual(_) => {
+            let message = gen_bound_message(type_name, "small", validators);
+            quote!(#error_type_path::GreaterOrEqualViolated => #message)
+        }
+        IntegerValidator::Less(_) => {
+            let message = gen_bound_message(type_name, "big", validators);
+            quote!(#error_type_path::LessViolated => #message)
+        }
+        IntegerValidator::LessOrEqual(_) => {
+            let message = gen_bound_message(type_name, "big", validators);
+            quote!(#error_type_path::LessOrEqualViolated => #message)
+        }
+        IntegerValidator::In(allowed_values) => {
+            let allowed_values = allowed_values_to_string(allowed_values);
+            quote! {
+                #error_type_path::InViolated(val) => write!(f, "{} is not allowed. The value must be one of [{}], got {:?}.", stringify!(#type_name), #allowed_values, val)
+            }
+        }
         IntegerValidator::Predicate(_) => quote! {
              #error_type_path::PredicateViolated => write!(f, "{} failed the predicate test.", stringify!(#type_name))
         },
     });
 
+    let custom_arms = gen_custom_display_arms(type_name, gen_variant_codes(validators), messages);
+
+    let extra_arms = gen_extra_display_arms(type_name, error_type_path, extra_variants);
+
     quote! {
         impl ::core::fmt::Display for #error_type_path {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 match self {
                     #(#match_arms,)*
+                    #(#custom_arms,)*
+                    #extra_arms
                 }
             }
         }
     }
 }
+
+/// Message of a violated bound, that mentions both bounds when they are set,
+/// e.g. `Age is too big. The value must be between 18 and 99.`
+fn gen_bound_message<T: ToTokens>(
+    type_name: &TypeName,
+    too: &str,
+    validators: &[IntegerValidator<T>],
+) -> TokenStream {
+    let mut lower = None;
+    let mut upper = None;
+    for validator in validators {
+        match validator {
+            IntegerValidator::Greater(val) => {
+                lower = Some(RangeBound {
+                    value: quote!(#val),
+                    inclusive: false,
+                })
+            }
+            IntegerValidator::GreaterOrEqual(val) => {
+                lower = Some(RangeBound {
+                    value: quote!(#val),
+                    inclusive: true,
+                })
+            }
+            IntegerValidator::Less(val) => {
+                upper = Some(RangeBound {
+                    value: quote!(#val),
+                    inclusive: false,
+                })
+            }
+            IntegerValidator::LessOrEqual(val) => {
+                upper = Some(RangeBound {
+                    value: quote!(#val),
+                    inclusive: true,
+                })
+            }
+            _ => {}
+        }
+    }
+    let (range, values) = describe_range(lower, upper, "{:#?}", "");
+    let format = format!("{{}} is too {too}. The value must be {range}.");
+    quote!(write!(f, #format, stringify!(#type_name), #(#values),*))
+}
+
+fn gen_variant_codes<T: ToTokens>(validators: &[IntegerValidator<T>]) -> Vec<ErrorVariantCode> {
+    validators
+        .iter()
+        .map(|validator| {
+            let pattern = match validator {
+                IntegerValidator::Greater(_) => quote!(Self::GreaterViolated),
+                IntegerValidator::GreaterOrEqual(_) => quote!(Self::GreaterOrEqualViolated),
+                IntegerValidator::Less(_) => quote!(Self::LessViolated),
+                IntegerValidator::LessOrEqual(_) => quote!(Self::LessOrEqualViolated),
+                IntegerValidator::In(_) => quote!(Self::InViolated(_)),
+                IntegerValidator::Predicate(_) => quote!(Self::PredicateViolated),
+            };
+            ErrorVariantCode::new(pattern, validator)
+        })
+        .collect()
+}
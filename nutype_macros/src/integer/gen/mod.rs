@@ -1,3 +1,4 @@
+pub mod doc;
 pub mod error;
 pub mod traits;
 
@@ -17,14 +18,17 @@ use super::{
 };
 use crate::common::{
     gen::{
+        error::ExtraVariants,
         tests::{
             gen_test_should_have_consistent_lower_and_upper_boundaries,
             gen_test_should_have_valid_default_value,
         },
+        trace::{gen_sanitizations, gen_validations},
         traits::GeneratedTraits,
+        try_with::{gen_fn_sanitize_of, gen_try_with_step},
         GenerateNewtype,
     },
-    models::{ErrorTypePath, Guard, TypeName},
+    models::{DeriveOptions, ErrorMessages, ErrorTypePath, Guard, RuleNode, StoreAs, TypeName},
 };
 
 impl<T> GenerateNewtype for IntegerNewtype<T>
@@ -36,29 +40,48 @@ where
     type InnerType = IntegerInnerType;
     type TypedTrait = IntegerDeriveTrait;
 
+    // Numbers are parsed from `&str` listed in `try_from(...)`.
+    const PARSES_STR_SOURCE: bool = true;
+
     fn gen_fn_sanitize(
         inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
+        trace: bool,
+        lossy: bool,
     ) -> TokenStream {
-        let transformations: TokenStream = sanitizers
-            .iter()
-            .map(|san| match san {
+        let transformations = gen_sanitizations(
+            &RuleNode::sanitizers(sanitizers),
+            trace,
+            lossy,
+            |san| match san {
                 IntegerSanitizer::With(custom_sanitizer) => {
                     quote!(
                         value = (#custom_sanitizer)(value);
                     )
                 }
+                IntegerSanitizer::TryWith(try_sanitizer) => {
+                    let step = gen_try_with_step(try_sanitizer, inner_type);
+                    quote!(
+                        value = #step;
+                    )
+                }
+                IntegerSanitizer::Clamp { min, max } => {
+                    quote!(
+                        value = ::core::cmp::Ord::clamp(value, #min, #max);
+                    )
+                }
                 IntegerSanitizer::_Phantom(_) => {
                     unreachable!("integer::gen: IntegerSanitizer::_Phantom must not be used")
                 }
-            })
-            .collect();
+            },
+        );
 
-        quote!(
-            fn __sanitize__(mut value: #inner_type) -> #inner_type {
-                #transformations
-                value
-            }
+        gen_fn_sanitize_of(
+            quote!(mut value: #inner_type),
+            inner_type,
+            sanitizers,
+            transformations,
+            lossy,
         )
     }
 
@@ -66,47 +89,57 @@ where
         inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        trace: bool,
     ) -> TokenStream {
-        let validations: TokenStream = validators
-            .iter()
-            .map(|validator| match validator {
-                IntegerValidator::Less(exclusive_upper_bound) => {
-                    quote!(
-                        if val >= #exclusive_upper_bound {
-                            return Err(#error_type_path::LessViolated);
-                        }
-                    )
-                }
-                IntegerValidator::LessOrEqual(max) => {
-                    quote!(
-                        if val > #max {
-                            return Err(#error_type_path::LessOrEqualViolated);
-                        }
-                    )
-                }
-                IntegerValidator::Greater(exclusive_lower_bound) => {
-                    quote!(
-                        if val <= #exclusive_lower_bound {
-                            return Err(#error_type_path::GreaterViolated);
-                        }
-                    )
-                }
-                IntegerValidator::GreaterOrEqual(min) => {
-                    quote!(
-                        if val < #min {
-                            return Err(#error_type_path::GreaterOrEqualViolated);
-                        }
-                    )
-                }
-                IntegerValidator::Predicate(custom_is_valid_fn) => {
-                    quote!(
-                        if !(#custom_is_valid_fn)(&val) {
-                            return Err(#error_type_path::PredicateViolated);
-                        }
-                    )
-                }
-            })
-            .collect();
+        let validations =
+            gen_validations(
+                &RuleNode::validators(validators),
+                trace,
+                |validator| match validator {
+                    IntegerValidator::Less(exclusive_upper_bound) => {
+                        quote!(
+                            if val >= #exclusive_upper_bound {
+                                return Err(#error_type_path::LessViolated);
+                            }
+                        )
+                    }
+                    IntegerValidator::LessOrEqual(max) => {
+                        quote!(
+                            if val > #max {
+                                return Err(#error_type_path::LessOrEqualViolated);
+                            }
+                        )
+                    }
+                    IntegerValidator::Greater(exclusive_lower_bound) => {
+                        quote!(
+                            if val <= #exclusive_lower_bound {
+                                return Err(#error_type_path::GreaterViolated);
+                            }
+                        )
+                    }
+                    IntegerValidator::GreaterOrEqual(min) => {
+                        quote!(
+                            if val < #min {
+                                return Err(#error_type_path::GreaterOrEqualViolated);
+                            }
+                        )
+                    }
+                    IntegerValidator::In(allowed_values) => {
+                        quote!(
+                            if ![#(#allowed_values),*].contains(&val) {
+                                return Err(#error_type_path::InViolated(val));
+                            }
+                        )
+                    }
+                    IntegerValidator::Predicate(custom_is_valid_fn) => {
+                        quote!(
+                            if !(#custom_is_valid_fn)(&val) {
+                                return Err(#error_type_path::PredicateViolated);
+                            }
+                        )
+                    }
+                },
+            );
 
         quote!(
             fn __validate__(val: &#inner_type) -> ::core::result::Result<(), #error_type_path> {
@@ -119,10 +152,48 @@ where
 
     fn gen_validation_error_type(
         type_name: &TypeName,
+        inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        extra_variants: ExtraVariants,
+        messages: &ErrorMessages,
     ) -> TokenStream {
-        gen_validation_error_type(type_name, error_type_path, validators)
+        gen_validation_error_type(
+            type_name,
+            inner_type,
+            error_type_path,
+            validators,
+            extra_variants,
+            messages,
+        )
+    }
+
+    fn gen_bound_consts(
+        inner_type: &Self::InnerType,
+        validators: &[Self::Validator],
+    ) -> TokenStream {
+        validators
+            .iter()
+            .map(|validator| match validator {
+                IntegerValidator::GreaterOrEqual(min) => quote!(
+                    /// Inclusive lower bound, declared with `greater_or_equal`.
+                    pub const MIN: #inner_type = #min;
+                ),
+                IntegerValidator::LessOrEqual(max) => quote!(
+                    /// Inclusive upper bound, declared with `less_or_equal`.
+                    pub const MAX: #inner_type = #max;
+                ),
+                IntegerValidator::Greater(exclusive_lower_bound) => quote!(
+                    /// Exclusive lower bound, declared with `greater`.
+                    pub const MIN_EXCLUSIVE: #inner_type = #exclusive_lower_bound;
+                ),
+                IntegerValidator::Less(exclusive_upper_bound) => quote!(
+                    /// Exclusive upper bound, declared with `less`.
+                    pub const MAX_EXCLUSIVE: #inner_type = #exclusive_upper_bound;
+                ),
+                IntegerValidator::In(_) | IntegerValidator::Predicate(_) => quote!(),
+            })
+            .collect()
     }
 
     fn gen_traits(
@@ -131,7 +202,9 @@ where
         inner_type: &Self::InnerType,
         traits: HashSet<Self::TypedTrait>,
         maybe_default_value: Option<syn::Expr>,
+        derive_options: &DeriveOptions,
         guard: &IntegerGuard<T>,
+        maybe_store_as: Option<&StoreAs>,
     ) -> Result<GeneratedTraits, syn::Error> {
         gen_traits(
             type_name,
@@ -139,7 +212,9 @@ where
             inner_type,
             traits,
             maybe_default_value,
+            derive_options,
             guard,
+            maybe_store_as,
         )
     }
 
@@ -2,8 +2,11 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use crate::{
-    common::models::{TypeName, Validation},
-    integer::models::{IntegerGuard, IntegerInnerType, IntegerValidator},
+    common::{
+        gen::arbitrary_invalid::gen_fn_arbitrary_invalid,
+        models::{TypeName, Validation},
+    },
+    integer::models::{IntegerGuard, IntegerInnerType, IntegerSanitizer, IntegerValidator},
     utils::issue_reporter::{build_github_link_with_issue, Issue},
 };
 
@@ -13,6 +16,10 @@ pub fn gen_impl_trait_arbitrary<T: ToTokens>(
     guard: &IntegerGuard<T>,
 ) -> Result<TokenStream, syn::Error> {
     let Boundary { min, max } = guard_to_boundary(inner_type, guard)?;
+    let maybe_fn_arbitrary_invalid = guard.maybe_error_type_path().map(|error_type_path| {
+        let violations = gen_violations(inner_type, guard, &min, &max);
+        gen_fn_arbitrary_invalid(type_name, inner_type, error_type_path, violations)
+    });
 
     let construct_value = if guard.has_validation() {
         // If by some reason we generate an invalid value, make it very easy for the user to report
@@ -42,9 +49,58 @@ pub fn gen_impl_trait_arbitrary<T: ToTokens>(
             let n = ::core::mem::size_of::<#inner_type>();
             (n, Some(n))
         }
+
+        #maybe_fn_arbitrary_invalid
     ))
 }
 
+/// Values below the lower bound and above the upper bound, if the bounds are declared.
+fn gen_violations<T>(
+    inner_type: &IntegerInnerType,
+    guard: &IntegerGuard<T>,
+    min: &TokenStream,
+    max: &TokenStream,
+) -> Vec<TokenStream> {
+    let validators = guard
+        .standard_validators()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has_min = validators.iter().any(|validator| {
+        matches!(
+            validator,
+            IntegerValidator::Greater(_) | IntegerValidator::GreaterOrEqual(_)
+        )
+    });
+    let has_max = validators.iter().any(|validator| {
+        matches!(
+            validator,
+            IntegerValidator::Less(_) | IntegerValidator::LessOrEqual(_)
+        )
+    });
+
+    let below_min = has_min.then(|| {
+        quote!(
+            let min: #inner_type = #min;
+            if min == #inner_type::MIN {
+                Err(::arbitrary::Error::IncorrectFormat)
+            } else {
+                u.int_in_range(#inner_type::MIN..=(min - 1))
+            }
+        )
+    });
+    let above_max = has_max.then(|| {
+        quote!(
+            let max: #inner_type = #max;
+            if max == #inner_type::MAX {
+                Err(::arbitrary::Error::IncorrectFormat)
+            } else {
+                u.int_in_range((max + 1)..=#inner_type::MAX)
+            }
+        )
+    });
+    below_min.into_iter().chain(above_max).collect()
+}
+
 #[derive(Debug)]
 struct Boundary {
     min: TokenStream,
@@ -65,9 +121,19 @@ fn guard_to_boundary<T: ToTokens>(
             // Nothing to validate, so every possible value for the inner type is valid.
         }
         IntegerGuard::WithValidation {
-            sanitizers: _,
+            sanitizers,
             validation,
+            ..
         } => {
+            if sanitizers
+                .iter()
+                .any(|sanitizer| matches!(sanitizer, IntegerSanitizer::TryWith(_)))
+            {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "Cannot derive trait `Arbitrary` for a type with `try_with` sanitizer.",
+                ));
+            }
             match validation {
                 Validation::Custom { .. } => {
                     return Err(syn::Error::new(
@@ -78,6 +144,9 @@ fn guard_to_boundary<T: ToTokens>(
                 Validation::Standard {
                     validators,
                     error_type_path: _,
+                    with_context: _,
+                    messages: _,
+                    combinators: _,
                 } => {
                     // Apply the validators to the boundaries.
                     // Since the validators were already validated, it's guaranteed that they're not
@@ -96,6 +165,12 @@ fn guard_to_boundary<T: ToTokens>(
                             IntegerValidator::LessOrEqual(lte) => {
                                 boundary.max = quote!(#lte);
                             }
+                            IntegerValidator::In(_) => {
+                                return Err(syn::Error::new(
+                                    proc_macro2::Span::call_site(),
+                                    "Cannot derive trait `Arbitrary` for a type with `in` validator",
+                                ));
+                            }
                             IntegerValidator::Predicate(_) => {
                                 return Err(syn::Error::new(
                                     proc_macro2::Span::call_site(),
@@ -8,27 +8,37 @@ use syn::Generics;
 
 use crate::{
     common::{
-        gen::traits::{
-            gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
-            gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
-            gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_serde_deserialize,
-            gen_impl_trait_serde_serialize, gen_impl_trait_try_from, split_into_generatable_traits,
-            GeneratableTrait, GeneratableTraits, GeneratedTraits,
+        gen::{
+            borsh::{gen_impl_trait_borsh_deserialize, gen_impl_trait_borsh_serialize},
+            clap::gen_impl_trait_clap_value_parser_factory,
+            rkyv::gen_impl_trait_rkyv,
+            traits::{
+                gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
+                gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from,
+                gen_impl_trait_from_str, gen_impl_trait_into, gen_impl_trait_into_types,
+                gen_impl_trait_serde_deserialize, gen_impl_trait_serde_serialize,
+                gen_impl_trait_try_from, gen_impl_trait_try_from_numeric_types,
+                gen_impl_trait_validator_validate, split_into_generatable_traits, GeneratableTrait,
+                GeneratableTraits, GeneratedTraits,
+            },
         },
-        models::TypeName,
+        models::{DeriveOptions, StoreAs, TypeName},
     },
     integer::models::{IntegerDeriveTrait, IntegerGuard, IntegerInnerType},
 };
 
 type IntegerGeneratableTrait = GeneratableTrait<IntegerTransparentTrait, IntegerIrregularTrait>;
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_traits<T: ToTokens>(
     type_name: &TypeName,
     generics: &Generics,
     inner_type: &IntegerInnerType,
     traits: HashSet<IntegerDeriveTrait>,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     guard: &IntegerGuard<T>,
+    maybe_store_as: Option<&StoreAs>,
 ) -> Result<GeneratedTraits, syn::Error> {
     let GeneratableTraits {
         transparent_traits,
@@ -47,7 +57,9 @@ pub fn gen_traits<T: ToTokens>(
         inner_type,
         irregular_traits,
         maybe_default_value,
+        derive_options,
         guard,
+        maybe_store_as,
     )?;
 
     Ok(GeneratedTraits {
@@ -122,6 +134,21 @@ impl From<IntegerDeriveTrait> for IntegerGeneratableTrait {
             IntegerDeriveTrait::ArbitraryArbitrary => {
                 IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::ArbitraryArbitrary)
             }
+            IntegerDeriveTrait::ValidatorValidate => {
+                IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::ValidatorValidate)
+            }
+            IntegerDeriveTrait::BorshSerialize => {
+                IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::BorshSerialize)
+            }
+            IntegerDeriveTrait::BorshDeserialize => {
+                IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::BorshDeserialize)
+            }
+            IntegerDeriveTrait::RkyvArchive => {
+                IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::RkyvArchive)
+            }
+            IntegerDeriveTrait::ClapValueParserFactory => {
+                IntegerGeneratableTrait::Irregular(IntegerIrregularTrait::ClapValueParserFactory)
+            }
         }
     }
 }
@@ -156,6 +183,11 @@ enum IntegerIrregularTrait {
     SerdeSerialize,
     SerdeDeserialize,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl ToTokens for IntegerTransparentTrait {
@@ -175,13 +207,16 @@ impl ToTokens for IntegerTransparentTrait {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn gen_implemented_traits<T: ToTokens>(
     type_name: &TypeName,
     generics: &Generics,
     inner_type: &IntegerInnerType,
     impl_traits: Vec<IntegerIrregularTrait>,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     guard: &IntegerGuard<T>,
+    maybe_store_as: Option<&StoreAs>,
 ) -> Result<TokenStream, syn::Error> {
     let maybe_error_type_name = guard.maybe_error_type_path();
     impl_traits
@@ -193,12 +228,23 @@ fn gen_implemented_traits<T: ToTokens>(
                 Ok(gen_impl_trait_from_str(type_name, generics, inner_type, maybe_error_type_name))
             }
             IntegerIrregularTrait::From => Ok(gen_impl_trait_from(type_name, generics, inner_type)),
-            IntegerIrregularTrait::Into => Ok(gen_impl_trait_into(type_name, generics, inner_type)),
-            IntegerIrregularTrait::TryFrom => {
-                Ok(gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name))
-            }
+            IntegerIrregularTrait::Into => match derive_options.into {
+                Some(ref target_types) => Ok(gen_impl_trait_into_types(type_name, generics, target_types)),
+                None => Ok(gen_impl_trait_into(type_name, generics, inner_type)),
+            },
+            IntegerIrregularTrait::TryFrom => match derive_options.try_from {
+                Some(ref source_types) => Ok(gen_impl_trait_try_from_numeric_types(
+                    type_name,
+                    generics,
+                    inner_type,
+                    source_types,
+                    maybe_error_type_name,
+                    impl_traits.contains(&IntegerIrregularTrait::FromStr),
+                )),
+                None => Ok(gen_impl_trait_try_from(type_name, generics, inner_type, maybe_error_type_name)),
+            },
             IntegerIrregularTrait::Borrow => Ok(gen_impl_trait_borrow(type_name, generics, inner_type)),
-            IntegerIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics)),
+            IntegerIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics, derive_options.display.as_ref(), maybe_store_as)),
             IntegerIrregularTrait::Default => {
                 match maybe_default_value {
                     Some(ref default_value) => {
@@ -212,16 +258,41 @@ fn gen_implemented_traits<T: ToTokens>(
                     }
                 }
             }
-            IntegerIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics)),
+            IntegerIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics, &derive_options.serde, maybe_store_as)),
             IntegerIrregularTrait::SerdeDeserialize => Ok(gen_impl_trait_serde_deserialize(
                 type_name,
                 generics,
                 inner_type,
-                maybe_error_type_name,
+                maybe_error_type_name, &derive_options.serde,
+                None,
+                derive_options.profile.denies_unknown_fields(),
             )),
             IntegerIrregularTrait::ArbitraryArbitrary => {
                 arbitrary::gen_impl_trait_arbitrary(type_name, inner_type, guard)
             }
+            IntegerIrregularTrait::ValidatorValidate => {
+                Ok(gen_impl_trait_validator_validate(type_name, generics))
+            }
+            IntegerIrregularTrait::BorshSerialize => {
+                Ok(gen_impl_trait_borsh_serialize(type_name, generics, maybe_store_as))
+            }
+            IntegerIrregularTrait::BorshDeserialize => Ok(gen_impl_trait_borsh_deserialize(
+                type_name,
+                generics,
+                inner_type,
+                maybe_error_type_name,
+            )),
+            IntegerIrregularTrait::RkyvArchive => {
+                gen_impl_trait_rkyv(type_name, generics, inner_type, maybe_error_type_name)
+            }
+            IntegerIrregularTrait::ClapValueParserFactory => {
+                Ok(gen_impl_trait_clap_value_parser_factory(
+                    type_name,
+                    generics,
+                    inner_type,
+                    maybe_error_type_name,
+                ))
+            }
         })
         .collect()
 }
@@ -8,9 +8,16 @@ use std::collections::HashSet;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-use crate::common::{
-    gen::GenerateNewtype,
-    models::{Attributes, GenerateParams, Guard, Newtype, SpannedDeriveTrait, TypeName},
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::{optional::gen_optional_nutype, GenerateNewtype},
+        models::{
+            Attributes, DeriveOptions, DeriveTrait, GenerateParams, Guard, Newtype,
+            SpannedDeriveTrait, TypeName,
+        },
+        validate::validate_serde_borrow,
+    },
 };
 
 use self::{
@@ -41,14 +48,18 @@ where
     fn parse_attributes(
         attrs: TokenStream,
         type_name: &TypeName,
+        inner_type: &Self::InnerType,
     ) -> Result<Attributes<IntegerGuard<T>, SpannedDeriveTrait>, syn::Error> {
-        parse::parse_attributes::<T>(attrs, type_name)
+        parse::parse_attributes::<T>(attrs, type_name, inner_type)
     }
 
     fn validate(
         guard: &Guard<Self::Sanitizer, Self::Validator>,
         derive_traits: Vec<SpannedDeriveTrait>,
+        _inner_type: &Self::InnerType,
+        derive_options: &DeriveOptions,
     ) -> Result<HashSet<Self::TypedTrait>, syn::Error> {
+        validate_serde_borrow(derive_options, false)?;
         let has_validation = guard.has_validation();
         validate_integer_derive_traits(derive_traits, has_validation)
     }
@@ -62,4 +73,16 @@ where
     ) -> Result<TokenStream, syn::Error> {
         IntegerNewtype::gen_nutype(params)
     }
+
+    fn generate_optional(
+        params: GenerateParams<
+            IntegerInnerType,
+            DeriveTrait,
+            Guard<Self::Sanitizer, Self::Validator>,
+        >,
+        optional_type: &AnyInnerType,
+        is_required: bool,
+    ) -> Result<TokenStream, syn::Error> {
+        gen_optional_nutype::<IntegerNewtype<T>>(params, optional_type, is_required)
+    }
 }
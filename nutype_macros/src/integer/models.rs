@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 
 use crate::common::models::{
     impl_numeric_bound_on_vec_of, impl_numeric_bound_validator, Guard, RawGuard, SpannedItem,
-    TypeTrait, TypedCustomFunction, ValueOrExpr,
+    TrySanitizer, TypeTrait, TypedCustomFunction, ValueOrExpr,
 };
 
 // Sanitizer
@@ -13,6 +13,11 @@ use crate::common::models::{
 #[kinded(display = "snake_case")]
 pub enum IntegerSanitizer<T> {
     With(TypedCustomFunction),
+    TryWith(TrySanitizer),
+    Clamp {
+        min: ValueOrExpr<T>,
+        max: ValueOrExpr<T>,
+    },
     _Phantom(core::marker::PhantomData<T>),
 }
 
@@ -28,6 +33,7 @@ pub enum IntegerValidator<T> {
     GreaterOrEqual(ValueOrExpr<T>),
     Less(ValueOrExpr<T>),
     LessOrEqual(ValueOrExpr<T>),
+    In(Vec<syn::Expr>),
     Predicate(TypedCustomFunction),
 }
 
@@ -64,18 +70,42 @@ pub enum IntegerDeriveTrait {
     SerdeDeserialize,
     SchemarsJsonSchema,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl TypeTrait for IntegerDeriveTrait {
     fn is_from_str(&self) -> bool {
         self == &IntegerDeriveTrait::FromStr
     }
+
+    fn is_debug(&self) -> bool {
+        self == &IntegerDeriveTrait::Debug
+    }
+
+    fn is_display(&self) -> bool {
+        self == &IntegerDeriveTrait::Display
+    }
+
+    fn is_json_schema(&self) -> bool {
+        self == &IntegerDeriveTrait::SchemarsJsonSchema
+    }
+
+    fn is_rkyv_archive(&self) -> bool {
+        self == &IntegerDeriveTrait::RkyvArchive
+    }
 }
 
 pub type IntegerRawGuard<T> = RawGuard<SpannedIntegerSanitizer<T>, SpannedIntegerValidator<T>>;
 pub type IntegerGuard<T> = Guard<IntegerSanitizer<T>, IntegerValidator<T>>;
 
-pub trait IntegerType {}
+pub trait IntegerType {
+    /// Returns the value as `i128`, or `None` if it does not fit (only possible for `u128`).
+    fn to_i128(&self) -> Option<i128>;
+}
 
 macro_rules! define_integer_inner_type {
     ($($tp:ty => $variant:ident),*) => {
@@ -86,6 +116,9 @@ macro_rules! define_integer_inner_type {
 
         $(
             impl IntegerType for $tp {
+                fn to_i128(&self) -> Option<i128> {
+                    i128::try_from(*self).ok()
+                }
             }
         )*
 
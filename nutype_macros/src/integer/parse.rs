@@ -4,32 +4,40 @@ use core::{
 };
 
 use crate::common::{
-    models::{Attributes, SpannedDeriveTrait, TypeName},
+    models::{
+        Attributes, CustomFunction, DeriveTrait, SpannedDeriveTrait, StoreAs, TypeName, ValueOrExpr,
+    },
     parse::{
-        parse_number_or_expr, parse_sanitizer_kind, parse_typed_custom_function,
-        parse_validator_kind, ParseableAttributes,
+        parse_allowed_values, parse_number_or_expr, parse_sanitizer_kind, parse_try_sanitizer,
+        parse_typed_custom_function, parse_validator_kind, ParseableAttributes, ValidatorPresets,
     },
+    validate::{validate_as_variants, validate_nohash},
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Token,
+    parse_quote, Token,
 };
 
 use super::{
     models::{
-        IntegerGuard, IntegerRawGuard, IntegerSanitizer, IntegerSanitizerKind, IntegerValidator,
-        IntegerValidatorKind, SpannedIntegerSanitizer, SpannedIntegerValidator,
+        IntegerGuard, IntegerInnerType, IntegerRawGuard, IntegerSanitizer, IntegerSanitizerKind,
+        IntegerType, IntegerValidator, IntegerValidatorKind, SpannedIntegerSanitizer,
+        SpannedIntegerValidator,
+    },
+    validate::{
+        validate_integer_default_value, validate_integer_guarantee, validate_integer_guard,
     },
-    validate::validate_integer_guard,
 };
 
 pub fn parse_attributes<T>(
     input: TokenStream,
     type_name: &TypeName,
+    inner_type: &IntegerInnerType,
 ) -> Result<Attributes<IntegerGuard<T>, SpannedDeriveTrait>, syn::Error>
 where
-    T: FromStr + PartialOrd + Clone,
+    T: IntegerType + FromStr + PartialOrd + Clone + ToTokens,
     <T as FromStr>::Err: Debug + Display,
 {
     let attrs: ParseableAttributes<SpannedIntegerSanitizer<T>, SpannedIntegerValidator<T>> =
@@ -38,23 +46,190 @@ where
     let ParseableAttributes {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
         new_unchecked,
         default,
         derive_traits,
+        parse_money,
+        store_as,
+        storage,
+        as_variants,
+        bounded,
+        required,
+        trace,
+        secret,
+        arena,
+        guarantee,
+        derive_options,
     } = attrs;
+    let hashes_into_single_integer =
+        !matches!(inner_type, IntegerInnerType::U128 | IntegerInnerType::I128);
+    validate_nohash(&derive_options, hashes_into_single_integer)?;
+    if let Some(arena) = arena {
+        let msg = "`arena` is supported only by `&'a str` based types.";
+        return Err(syn::Error::new(arena, msg));
+    }
+    if let Some(bytes) = derive_options.bytes {
+        let msg = "`bytes` is supported only by `String` based types.";
+        return Err(syn::Error::new(bytes, msg));
+    }
+    if let Some(eq_str) = derive_options.eq_str {
+        let msg = "`eq_str` is supported only by string based types.";
+        return Err(syn::Error::new(eq_str, msg));
+    }
+    if let Some(total_order) = derive_options.total_order {
+        let msg = "`total_order` is supported only by float based types.";
+        return Err(syn::Error::new(total_order, msg));
+    }
+    if let Some(encoding) = &derive_options.encoding {
+        let msg = "`encoding` is supported only by `[u8; N]` based types.";
+        return Err(syn::Error::new(encoding.span(), msg));
+    }
+    if let Some(store_as) = store_as {
+        let msg = "`store_as` is supported only by `String` based types.";
+        return Err(syn::Error::new(store_as.span(), msg));
+    }
+    if let Some(storage) = storage {
+        let msg = "`storage` is supported only by `String` based types.";
+        return Err(syn::Error::new(storage.span(), msg));
+    }
+    if let Some(parse_money) = parse_money {
+        let msg = "`parse_money` is supported only by float based types and custom types (e.g. `Decimal`) that implement `FromStr`.";
+        return Err(syn::Error::new(parse_money.span(), msg));
+    }
     let raw_guard = IntegerRawGuard {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
     };
     let guard = validate_integer_guard(raw_guard, type_name)?;
+    if let Some(span) = guarantee {
+        validate_integer_guarantee(span, &guard)?;
+    }
+    if let Some(ref default_value) = default {
+        validate_integer_default_value(&guard, default_value)?;
+    }
+    let as_variants = as_variants
+        .map(|span| {
+            let maybe_allowed_values = guard.standard_validators().and_then(|validators| {
+                validators.iter().find_map(|validator| match validator {
+                    IntegerValidator::In(allowed_values) => Some(allowed_values.as_slice()),
+                    _ => None,
+                })
+            });
+            validate_as_variants(span, maybe_allowed_values)
+        })
+        .transpose()?;
+    let store_as = bounded
+        .map(|span| gen_bounded_store_as(span, &guard, &derive_traits, inner_type))
+        .transpose()?;
     Ok(Attributes {
         new_unchecked,
         guard,
         default,
         derive_traits,
+        parse_money: None,
+        store_as,
+        as_variants,
+        required,
+        trace: trace.is_some(),
+        secret,
+        arena: false,
+        guarantee: guarantee.is_some(),
+        derive_options,
     })
 }
 
+/// With `bounded` the value is stored as `::nutype::Bounded<MIN, MAX, T>`,
+/// where the inclusive bounds are computed from the declared bound validators.
+fn gen_bounded_store_as<T: IntegerType + ToTokens>(
+    span: Span,
+    guard: &IntegerGuard<T>,
+    derive_traits: &[SpannedDeriveTrait],
+    inner_type: &IntegerInnerType,
+) -> Result<StoreAs, syn::Error> {
+    let Some(validators) = guard.standard_validators() else {
+        let msg = "`bounded` requires bounds to be declared with `validate(...)`.\nFor example:\n\n    validate(greater_or_equal = 1, less_or_equal = 100)\n";
+        return Err(syn::Error::new(span, msg));
+    };
+    let mut min = quote!(::core::primitive::i128::MIN);
+    let mut max = quote!(::core::primitive::i128::MAX);
+    for validator in validators {
+        match validator {
+            IntegerValidator::GreaterOrEqual(bound) => {
+                min = gen_bounded_limit(span, bound, 0, inner_type)?
+            }
+            IntegerValidator::Greater(bound) => {
+                min = gen_bounded_limit(span, bound, 1, inner_type)?
+            }
+            IntegerValidator::LessOrEqual(bound) => {
+                max = gen_bounded_limit(span, bound, 0, inner_type)?
+            }
+            IntegerValidator::Less(bound) => max = gen_bounded_limit(span, bound, -1, inner_type)?,
+            IntegerValidator::In(_) | IntegerValidator::Predicate(_) => {
+                let msg = "`bounded` supports only `greater`, `greater_or_equal`, `less` and `less_or_equal` validators.";
+                return Err(syn::Error::new(span, msg));
+            }
+        }
+    }
+    if let Some(derive_trait) = derive_traits
+        .iter()
+        .find(|spanned| spanned.item == DeriveTrait::SchemarsJsonSchema)
+    {
+        let msg = "`JsonSchema` cannot be derived for `bounded` types.";
+        return Err(syn::Error::new(derive_trait.span, msg));
+    }
+    Ok(StoreAs {
+        storage_type: parse_quote!(::nutype::Bounded<{ #min }, { #max }, #inner_type>),
+        encode: CustomFunction::Path(parse_quote!(::nutype::Bounded::new_unchecked)),
+        decode: CustomFunction::Path(parse_quote!(::nutype::Bounded::get)),
+        derefs_to_str: false,
+    })
+}
+
+/// Computes an inclusive limit of `Bounded` from a declared bound, where `offset` turns
+/// an exclusive bound into an inclusive one.
+///
+/// Literal bounds are checked right away. Bounds given as expressions are checked when
+/// the generated const is evaluated, so an out of range bound fails to compile instead
+/// of wrapping around.
+fn gen_bounded_limit<T: IntegerType + ToTokens>(
+    span: Span,
+    bound: &ValueOrExpr<T>,
+    offset: i128,
+    inner_type: &IntegerInnerType,
+) -> Result<TokenStream, syn::Error> {
+    let msg = "`bounded` supports only bounds that are within the range of `i128` after exclusive bounds are converted to inclusive ones.";
+    if let ValueOrExpr::Value(value) = bound {
+        if value
+            .to_i128()
+            .and_then(|v| v.checked_add(offset))
+            .is_none()
+        {
+            return Err(syn::Error::new(span, msg));
+        }
+    }
+    let check_fits_i128 = if *inner_type == IntegerInnerType::U128 {
+        quote!(
+            if bound > ::core::primitive::i128::MAX as ::core::primitive::u128 {
+                ::core::panic!(#msg);
+            }
+        )
+    } else {
+        quote!()
+    };
+    Ok(quote!({
+        let bound = #bound;
+        #check_fits_i128
+        match (bound as ::core::primitive::i128).checked_add(#offset) {
+            ::core::option::Option::Some(limit) => limit,
+            ::core::option::Option::None => ::core::panic!(#msg),
+        }
+    }))
+}
+
 impl<T> Parse for SpannedIntegerValidator<T>
 where
     T: FromStr,
@@ -96,6 +271,14 @@ where
                     span,
                 })
             }
+            IntegerValidatorKind::In => {
+                let _eq: Token![=] = input.parse()?;
+                let (allowed_values, span) = parse_allowed_values(input)?;
+                Ok(SpannedIntegerValidator {
+                    item: IntegerValidator::In(allowed_values),
+                    span,
+                })
+            }
             IntegerValidatorKind::Predicate => {
                 let _eq: Token![=] = input.parse()?;
                 let (typed_custom_function, span) = parse_typed_custom_function::<&T>(input)?;
@@ -108,6 +291,37 @@ where
     }
 }
 
+impl<T> ValidatorPresets for SpannedIntegerValidator<T>
+where
+    T: FromStr,
+{
+    const PRESETS: &'static [&'static str] = &["percentage", "port", "not_reserved"];
+
+    fn expand_preset(ident: &Ident) -> Option<Vec<Self>> {
+        let (min, max) = match ident.to_string().as_str() {
+            "percentage" => ("0", "100"),
+            // A network port. Port 0 is not a real port, it asks the OS to pick a free one.
+            "port" => ("1", "65535"),
+            // A port, that does not require privileges to bind to.
+            "not_reserved" => ("1024", "65535"),
+            _ => return None,
+        };
+        let span = ident.span();
+        let min = min.parse::<T>().ok()?;
+        let max = max.parse::<T>().ok()?;
+        Some(vec![
+            SpannedIntegerValidator::new(
+                IntegerValidator::GreaterOrEqual(ValueOrExpr::Value(min)),
+                span,
+            ),
+            SpannedIntegerValidator::new(
+                IntegerValidator::LessOrEqual(ValueOrExpr::Value(max)),
+                span,
+            ),
+        ])
+    }
+}
+
 impl<T> Parse for SpannedIntegerSanitizer<T>
 where
     T: FromStr,
@@ -125,6 +339,24 @@ where
                     span,
                 })
             }
+            IntegerSanitizerKind::TryWith => {
+                let (try_sanitizer, span) = parse_try_sanitizer(input)?;
+                Ok(SpannedIntegerSanitizer {
+                    item: IntegerSanitizer::TryWith(try_sanitizer),
+                    span,
+                })
+            }
+            IntegerSanitizerKind::Clamp => {
+                let content;
+                syn::parenthesized!(content in input);
+                let (min, _) = parse_number_or_expr::<T>(&content)?;
+                let _comma: Token![,] = content.parse()?;
+                let (max, _) = parse_number_or_expr::<T>(&content)?;
+                Ok(SpannedIntegerSanitizer {
+                    item: IntegerSanitizer::Clamp { min, max },
+                    span: ident.span(),
+                })
+            }
             IntegerSanitizerKind::_Phantom => {
                 let msg = format!("Unknown validator `{ident}`");
                 Err(syn::Error::new(ident.span(), msg))
@@ -1,11 +1,15 @@
 use std::collections::HashSet;
 
+use core::str::FromStr;
+
 use proc_macro2::Span;
+use quote::ToTokens;
 
 use crate::common::{
-    models::{DeriveTrait, SpannedDeriveTrait, TypeName},
+    models::{DeriveTrait, Guard, SpannedDeriveTrait, TypeName, Validation, ValueOrExpr},
     validate::{
-        validate_duplicates, validate_guard, validate_numeric_bounds,
+        unsupported_collection_trait, validate_duplicates, validate_guard, validate_numeric_bounds,
+        validate_numeric_default_value, validate_sanitizer_duplicates,
         validate_traits_from_xor_try_from,
     },
 };
@@ -20,7 +24,7 @@ pub fn validate_integer_guard<T>(
     type_name: &TypeName,
 ) -> Result<IntegerGuard<T>, syn::Error>
 where
-    T: PartialOrd + Clone,
+    T: PartialOrd + Clone + ToTokens,
 {
     validate_guard(
         raw_guard,
@@ -30,6 +34,23 @@ where
     )
 }
 
+/// Check a literal default value against the numeric bounds at compile time.
+/// It's skipped if the value goes through custom sanitizers first.
+pub fn validate_integer_default_value<T>(
+    guard: &IntegerGuard<T>,
+    default_value: &syn::Expr,
+) -> Result<(), syn::Error>
+where
+    T: PartialOrd + Clone + FromStr,
+{
+    match guard.standard_validators() {
+        Some(validators) if guard.sanitizers().is_empty() => {
+            validate_numeric_default_value(default_value, validators)
+        }
+        _ => Ok(()),
+    }
+}
+
 fn validate_validators<T>(
     validators: Vec<SpannedIntegerValidator<T>>,
 ) -> Result<Vec<IntegerValidator<T>>, syn::Error>
@@ -54,14 +75,97 @@ fn validate_sanitizers<T>(
 where
     T: PartialOrd + Clone,
 {
-    validate_duplicates(&sanitizers, |kind| {
+    validate_sanitizer_duplicates(&sanitizers, |kind| {
         format!("Duplicated sanitizer `{kind}`.\nIt happens, don't worry. We still love you!")
     })?;
 
+    for sanitizer in &sanitizers {
+        if let IntegerSanitizer::Clamp {
+            min: ValueOrExpr::Value(min),
+            max: ValueOrExpr::Value(max),
+        } = &sanitizer.item
+        {
+            if min > max {
+                let msg = "The lower bound of `clamp` must not be greater than the upper bound.";
+                return Err(syn::Error::new(sanitizer.span, msg));
+            }
+        }
+    }
+
     let sanitizers: Vec<_> = sanitizers.into_iter().map(|s| s.item).collect();
     Ok(sanitizers)
 }
 
+/// Check at compile time that `clamp`, the last sanitizer, brings every value within the bounds
+/// of the validators, so the value can be created with infallible `new()`.
+pub fn validate_integer_guarantee<T>(span: Span, guard: &IntegerGuard<T>) -> Result<(), syn::Error>
+where
+    T: PartialOrd,
+{
+    let fail = |reason: &str| {
+        let msg = format!("`guarantee` cannot be established: {reason}");
+        Err(syn::Error::new(span, msg))
+    };
+    let Guard::WithValidation {
+        sanitizers,
+        validation,
+        ..
+    } = guard
+    else {
+        return fail("there is no validation, so `new()` is infallible already.");
+    };
+    let validators = match validation {
+        Validation::Standard {
+            with_context: Some(_),
+            ..
+        } => return fail("`with_context` depends on a context, that is known only at runtime."),
+        Validation::Standard { validators, .. } => validators,
+        Validation::Custom { .. } => {
+            return fail("custom validation can not be checked at compile time.")
+        }
+    };
+    if sanitizers
+        .iter()
+        .any(|sanitizer| matches!(sanitizer, IntegerSanitizer::TryWith(_)))
+    {
+        return fail("`try_with` may reject a value.");
+    }
+    let (min, max) = match sanitizers.last() {
+        Some(IntegerSanitizer::Clamp {
+            min: ValueOrExpr::Value(min),
+            max: ValueOrExpr::Value(max),
+        }) => (min, max),
+        Some(IntegerSanitizer::Clamp { .. }) => {
+            return fail("the bounds of `clamp` must be number literals.")
+        }
+        _ => return fail("the last sanitizer must be `clamp(min, max)`."),
+    };
+    for validator in validators {
+        let kind = validator.kind();
+        let is_implied = match validator {
+            IntegerValidator::Greater(ValueOrExpr::Value(bound)) => min > bound,
+            IntegerValidator::GreaterOrEqual(ValueOrExpr::Value(bound)) => min >= bound,
+            IntegerValidator::Less(ValueOrExpr::Value(bound)) => max < bound,
+            IntegerValidator::LessOrEqual(ValueOrExpr::Value(bound)) => max <= bound,
+            IntegerValidator::Greater(_)
+            | IntegerValidator::GreaterOrEqual(_)
+            | IntegerValidator::Less(_)
+            | IntegerValidator::LessOrEqual(_) => {
+                return fail(&format!("the bound of `{kind}` must be a number literal."));
+            }
+            IntegerValidator::In(_) | IntegerValidator::Predicate(_) => {
+                return fail(&format!("`{kind}` is not implied by `clamp`."));
+            }
+        };
+        if !is_implied {
+            return fail(&format!(
+                "`clamp` lets through values that violate `{kind}`."
+            ));
+        }
+    }
+    Ok(())
+}
+
 pub fn validate_integer_derive_traits(
     spanned_derive_traits: Vec<SpannedDeriveTrait>,
     has_validation: bool,
@@ -99,11 +203,19 @@ fn to_integer_derive_trait(
         DeriveTrait::Deref => Ok(IntegerDeriveTrait::Deref),
         DeriveTrait::Hash => Ok(IntegerDeriveTrait::Hash),
         DeriveTrait::Borrow => Ok(IntegerDeriveTrait::Borrow),
+        DeriveTrait::IntoIterator | DeriveTrait::Extend => {
+            Err(unsupported_collection_trait(tr, span))
+        }
         DeriveTrait::Copy => Ok(IntegerDeriveTrait::Copy),
         DeriveTrait::SerdeSerialize => Ok(IntegerDeriveTrait::SerdeSerialize),
         DeriveTrait::SerdeDeserialize => Ok(IntegerDeriveTrait::SerdeDeserialize),
         DeriveTrait::SchemarsJsonSchema => Ok(IntegerDeriveTrait::SchemarsJsonSchema),
         DeriveTrait::ArbitraryArbitrary => Ok(IntegerDeriveTrait::ArbitraryArbitrary),
+        DeriveTrait::ValidatorValidate => Ok(IntegerDeriveTrait::ValidatorValidate),
+        DeriveTrait::BorshSerialize => Ok(IntegerDeriveTrait::BorshSerialize),
+        DeriveTrait::BorshDeserialize => Ok(IntegerDeriveTrait::BorshDeserialize),
+        DeriveTrait::RkyvArchive => Ok(IntegerDeriveTrait::RkyvArchive),
+        DeriveTrait::ClapValueParserFactory => Ok(IntegerDeriveTrait::ClapValueParserFactory),
         DeriveTrait::TryFrom => Ok(IntegerDeriveTrait::TryFrom),
         DeriveTrait::From => {
             if has_validation {
@@ -7,9 +7,12 @@
 mod any;
 mod common;
 mod float;
+#[cfg(feature = "from_schema")]
+mod from_schema;
 mod integer;
 mod string;
 mod utils;
+mod wrap;
 
 use any::AnyNewtype;
 use common::{
@@ -34,6 +37,29 @@ pub fn nutype(
         .into()
 }
 
+/// Translates the constraints of a JSON Schema snippet into `validate(...)` rules of the
+/// `#[nutype(...)]` attribute, that follows it.
+#[cfg(feature = "from_schema")]
+#[proc_macro_attribute]
+pub fn nutype_from_schema(
+    attrs: proc_macro::TokenStream,
+    type_definition: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    from_schema::expand_from_schema(attrs.into(), type_definition.into())
+        .unwrap_or_else(|e| syn::Error::to_compile_error(&e))
+        .into()
+}
+
+/// Generates a copy of a struct, where the fields marked with `#[wrap(Newtype)]` have newtype
+/// types, along with `TryFrom` and `From` conversions between the two structs.
+/// See the documentation for [nutype](https://docs.rs/nutype) crate for more information.
+#[proc_macro_derive(NutypeWrap, attributes(wrap))]
+pub fn derive_nutype_wrap(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    wrap::expand_nutype_wrap(input.into())
+        .unwrap_or_else(|e| syn::Error::to_compile_error(&e))
+        .into()
+}
+
 fn expand_nutype(
     attrs: TokenStream,
     type_definition: TokenStream,
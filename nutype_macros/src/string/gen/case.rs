@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generates a transformation that converts an identifier into `snake_case` (with `_` as
+/// the `separator`) or `kebab-case` (with `-`):
+///
+/// * Words are separated by characters that are neither letters nor digits (e.g. spaces,
+///   `-`, `_`, `.`) and by changes of the case: `fooBar` becomes `foo_bar` and
+///   `HTTPServer` becomes `http_server`.
+/// * Characters that are neither letters nor digits are dropped.
+/// * Letters are lowercased.
+/// * Combining marks are dropped, so they neither split words nor remain after lowercasing
+///   (`İ` is lowercased into `i` followed by a combining dot).
+pub fn gen_convert_case(separator: char) -> TokenStream {
+    quote!(
+        let value: String = {
+            let mut converted = String::with_capacity(value.len() + 4);
+            let mut pending_separator = false;
+            let mut prev: Option<char> = None;
+            let mut chars = value.chars().peekable();
+            while let Some(ch) = chars.next() {
+                if ::nutype::__private::is_combining_mark(ch) {
+                    continue;
+                }
+                if !ch.is_alphanumeric() {
+                    pending_separator = !converted.is_empty();
+                    prev = Some(ch);
+                    continue;
+                }
+                if let Some(prev) = prev.filter(|_| ch.is_uppercase() && !converted.is_empty()) {
+                    let next_is_lowercase = chars.peek().is_some_and(|next| next.is_lowercase());
+                    if prev.is_lowercase()
+                        || prev.is_numeric()
+                        || (prev.is_uppercase() && next_is_lowercase)
+                    {
+                        pending_separator = true;
+                    }
+                }
+                if pending_separator {
+                    converted.push(#separator);
+                    pending_separator = false;
+                }
+                converted.extend(
+                    ch.to_lowercase()
+                        .filter(|&lower| !::nutype::__private::is_combining_mark(lower)),
+                );
+                prev = Some(ch);
+            }
+            converted
+        };
+    )
+}
+
+/// Generates a transformation that turns a text into a slug for URLs, which consists only of
+/// `[a-z0-9-]`. Non-ASCII characters are decomposed (NFKD) without combining marks and Latin
+/// letters are transliterated into ASCII (`é` becomes `e`, `ǅ` becomes `dz`, `ß` becomes `ss`),
+/// other non-ASCII characters are treated as separators. Then words are
+/// split and joined with `-` the same way as with `kebab_case`, e.g. `" Hello, World! "`
+/// becomes `"hello-world"` and `"CaféMenu"` becomes `"cafe-menu"`.
+pub fn gen_slugify() -> TokenStream {
+    let convert_case = gen_convert_case('-');
+    quote!(
+        let value: String = {
+            let mut ascii = String::with_capacity(value.len());
+            for ch in value.chars() {
+                if ch.is_ascii() {
+                    ascii.push(ch);
+                } else {
+                    ::nutype::__private::to_ascii(ch, |part| ascii.push(part));
+                }
+            }
+            ascii
+        };
+        #convert_case
+    )
+}
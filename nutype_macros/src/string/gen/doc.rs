@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    common::{
+        gen::{doc::describe_value, error::allowed_values_to_string},
+        models::Rule,
+    },
+    string::models::{RegexDef, StringSanitizer, StringValidator},
+};
+
+impl Rule for StringSanitizer {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let description = match self {
+            Self::Trim => "trimmed".to_string(),
+            Self::Lowercase => "lowercase".to_string(),
+            Self::Uppercase => "uppercase".to_string(),
+            Self::CanonicalizeNumber => "a number in canonical form".to_string(),
+            Self::SnakeCase => "in snake_case".to_string(),
+            Self::KebabCase => "in kebab-case".to_string(),
+            Self::Slugify => "a slug".to_string(),
+            Self::With(_) => "sanitized with a custom function".to_string(),
+            Self::TryWith(_) => "sanitized with a fallible custom function".to_string(),
+        };
+        Some(description)
+    }
+
+    fn try_with_error_type(&self) -> Option<&syn::Type> {
+        match self {
+            Self::TryWith(try_sanitizer) => Some(&try_sanitizer.error_type),
+            _ => None,
+        }
+    }
+}
+
+impl Rule for StringValidator {
+    fn name(&self) -> String {
+        self.kind().to_string()
+    }
+
+    fn describe(&self) -> Option<String> {
+        let description = match self {
+            Self::LenCharMin(len) => format!("at least {} characters", describe_value(len)),
+            Self::LenCharMax(len) => format!("at most {} characters", describe_value(len)),
+            Self::LenByteMin(len) => format!("at least {} bytes", describe_value(len)),
+            Self::LenByteMax(len) => format!("at most {} bytes", describe_value(len)),
+            Self::LenGraphemeMin(len) => format!("at least {} graphemes", describe_value(len)),
+            Self::LenGraphemeMax(len) => format!("at most {} graphemes", describe_value(len)),
+            Self::NotEmpty => "not empty".to_string(),
+            Self::Lowercase => "no uppercase characters".to_string(),
+            Self::Uppercase => "no lowercase characters".to_string(),
+            Self::Alphanumeric => "alphanumeric".to_string(),
+            Self::Ascii => "ASCII".to_string(),
+            Self::NoWhitespace => "no whitespace".to_string(),
+            Self::Hostname => "a valid hostname".to_string(),
+            Self::StartsWith(fragment) => format!("starts with {}", describe_fragment(fragment)),
+            Self::NotStartsWith(fragment) => {
+                format!("does not start with {}", describe_fragment(fragment))
+            }
+            Self::EndsWith(fragment) => format!("ends with {}", describe_fragment(fragment)),
+            Self::NotEndsWith(fragment) => {
+                format!("does not end with {}", describe_fragment(fragment))
+            }
+            Self::Contains(fragment) => format!("contains {}", describe_fragment(fragment)),
+            Self::NotContains(fragment) => {
+                format!("does not contain {}", describe_fragment(fragment))
+            }
+            Self::In(allowed_values) => {
+                format!("one of [{}]", allowed_values_to_string(allowed_values))
+            }
+            Self::Predicate(_) => "satisfies the predicate".to_string(),
+            Self::Regex(RegexDef::StringLiteral(regex)) => {
+                format!("matches the regex `{}`", regex.value())
+            }
+            Self::Regex(RegexDef::Path(path)) => {
+                let path = quote::ToTokens::to_token_stream(path);
+                format!("matches the regex `{path}`")
+            }
+        };
+        Some(description)
+    }
+
+    fn bound(&self) -> Option<TokenStream> {
+        match self {
+            Self::LenCharMin(len)
+            | Self::LenCharMax(len)
+            | Self::LenByteMin(len)
+            | Self::LenByteMax(len)
+            | Self::LenGraphemeMin(len)
+            | Self::LenGraphemeMax(len) => Some(quote!(#len)),
+            _ => None,
+        }
+    }
+}
+
+/// Render a fragment of `starts_with`, `ends_with` or `contains` as it's written, e.g. `"+"`.
+fn describe_fragment(fragment: &syn::Expr) -> String {
+    format!("`{}`", quote::ToTokens::to_token_stream(fragment))
+}
@@ -3,8 +3,12 @@ use quote::quote;
 
 use crate::{
     common::{
-        gen::error::gen_impl_error_trait,
-        models::{ErrorTypePath, TypeName},
+        gen::error::{
+            allowed_values_to_string, describe_range, gen_custom_display_arms,
+            gen_extra_display_arms, gen_extra_variants, gen_impl_error_code, gen_impl_error_trait,
+            ErrorVariantCode, ExtraVariants, RangeBound,
+        },
+        models::{ErrorMessages, ErrorTypePath, Rule, TypeName, ValueOrExpr},
     },
     string::models::StringValidator,
 };
@@ -13,10 +17,24 @@ pub fn gen_validation_error_type(
     type_name: &TypeName,
     error_type_path: &ErrorTypePath,
     validators: &[StringValidator],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let definition = gen_definition(error_type_path, validators);
-    let impl_display_trait = gen_impl_display_trait(type_name, error_type_path, validators);
+    let definition = gen_definition(error_type_path, validators, extra_variants);
+    let impl_display_trait = gen_impl_display_trait(
+        type_name,
+        error_type_path,
+        validators,
+        extra_variants,
+        messages,
+    );
     let impl_error_trait = gen_impl_error_trait(error_type_path);
+    let impl_error_code = gen_impl_error_code(
+        error_type_path,
+        gen_variant_codes(validators),
+        Some(quote!(usize)),
+        extra_variants,
+    );
 
     quote! {
         #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,10 +42,15 @@ pub fn gen_validation_error_type(
 
         #impl_display_trait
         #impl_error_trait
+        #impl_error_code
     }
 }
 
-fn gen_definition(error_type_path: &ErrorTypePath, validators: &[StringValidator]) -> TokenStream {
+fn gen_definition(
+    error_type_path: &ErrorTypePath,
+    validators: &[StringValidator],
+    extra_variants: ExtraVariants,
+) -> TokenStream {
     let error_variants: TokenStream = validators
         .iter()
         .map(|validator| match validator {
@@ -37,9 +60,60 @@ fn gen_definition(error_type_path: &ErrorTypePath, validators: &[StringValidator
             StringValidator::LenCharMin(_len) => {
                 quote!(LenCharMinViolated,)
             }
+            StringValidator::LenByteMax(_len) => {
+                quote!(LenByteMaxViolated,)
+            }
+            StringValidator::LenByteMin(_len) => {
+                quote!(LenByteMinViolated,)
+            }
+            StringValidator::LenGraphemeMax(_len) => {
+                quote!(LenGraphemeMaxViolated,)
+            }
+            StringValidator::LenGraphemeMin(_len) => {
+                quote!(LenGraphemeMinViolated,)
+            }
             StringValidator::NotEmpty => {
                 quote!(NotEmptyViolated,)
             }
+            StringValidator::Lowercase => {
+                quote!(LowercaseViolated,)
+            }
+            StringValidator::Uppercase => {
+                quote!(UppercaseViolated,)
+            }
+            StringValidator::Alphanumeric => {
+                quote!(AlphanumericViolated,)
+            }
+            StringValidator::Ascii => {
+                quote!(AsciiViolated,)
+            }
+            StringValidator::NoWhitespace => {
+                quote!(NoWhitespaceViolated,)
+            }
+            StringValidator::Hostname => {
+                quote!(HostnameViolated,)
+            }
+            StringValidator::StartsWith(_) => {
+                quote!(StartsWithViolated(&'static str),)
+            }
+            StringValidator::NotStartsWith(_) => {
+                quote!(NotStartsWithViolated(&'static str),)
+            }
+            StringValidator::EndsWith(_) => {
+                quote!(EndsWithViolated(&'static str),)
+            }
+            StringValidator::NotEndsWith(_) => {
+                quote!(NotEndsWithViolated(&'static str),)
+            }
+            StringValidator::Contains(_) => {
+                quote!(ContainsViolated(&'static str),)
+            }
+            StringValidator::NotContains(_) => {
+                quote!(NotContainsViolated(&'static str),)
+            }
+            StringValidator::In(_) => {
+                quote!(InViolated(String),)
+            }
             StringValidator::Predicate(_) => {
                 quote!(PredicateViolated,)
             }
@@ -49,10 +123,13 @@ fn gen_definition(error_type_path: &ErrorTypePath, validators: &[StringValidator
         })
         .collect();
 
+    let extra_variants_tokens = gen_extra_variants(extra_variants);
+
     quote! {
         #[allow(clippy::enum_variant_names)]
         pub enum #error_type_path {
             #error_variants
+            #extra_variants_tokens
         }
     }
 }
@@ -61,32 +138,189 @@ fn gen_impl_display_trait(
     type_name: &TypeName,
     error_type_path: &ErrorTypePath,
     validators: &[StringValidator],
+    extra_variants: ExtraVariants,
+    messages: &ErrorMessages,
 ) -> TokenStream {
-    let match_arms = validators.iter().map(|validator| match validator {
-        StringValidator::LenCharMax(len_char_max) => quote! {
-             #error_type_path::LenCharMaxViolated => write!(f, "{} is too long. The value length must be less than {:#?} character(s).", stringify!(#type_name), #len_char_max)
-        },
-        StringValidator::LenCharMin(len_char_min) => quote! {
-             #error_type_path::LenCharMinViolated => write!(f, "{} is too short. The value length must be more than {:#?} character(s).", stringify!(#type_name), #len_char_min)
-        },
-        StringValidator::NotEmpty => quote! {
-             #error_type_path::NotEmptyViolated => write!(f, "{} is empty.", stringify!(#type_name))
-        },
-        StringValidator::Predicate(_) => quote! {
-             #error_type_path::PredicateViolated => write!(f, "{} failed the predicate test.", stringify!(#type_name))
-        },
-        StringValidator::Regex(_) => quote! {
-             #error_type_path::RegexViolated => write!(f, "{} violated the regular expression.", stringify!(#type_name))
-        },
-    });
+    let match_arms = validators
+        .iter()
+        .filter(|validator| messages.get(&validator.name()).is_none())
+        .map(|validator| match validator {
+            StringValidator::LenCharMax(_) => {
+                let message = gen_len_message(type_name, "long", validators, len_char_bound, " characters");
+                quote!(#error_type_path::LenCharMaxViolated => #message)
+            }
+            StringValidator::LenCharMin(_) => {
+                let message = gen_len_message(type_name, "short", validators, len_char_bound, " characters");
+                quote!(#error_type_path::LenCharMinViolated => #message)
+            }
+            StringValidator::LenByteMax(_) => {
+                let message = gen_len_message(type_name, "long", validators, len_byte_bound, " bytes");
+                quote!(#error_type_path::LenByteMaxViolated => #message)
+            }
+            StringValidator::LenByteMin(_) => {
+                let message = gen_len_message(type_name, "short", validators, len_byte_bound, " bytes");
+                quote!(#error_type_path::LenByteMinViolated => #message)
+            }
+            StringValidator::LenGraphemeMax(_) => {
+                let message = gen_len_message(type_name, "long", validators, len_grapheme_bound, " graphemes");
+                quote!(#error_type_path::LenGraphemeMaxViolated => #message)
+            }
+            StringValidator::LenGraphemeMin(_) => {
+                let message = gen_len_message(type_name, "short", validators, len_grapheme_bound, " graphemes");
+                quote!(#error_type_path::LenGraphemeMinViolated => #message)
+            }
+            StringValidator::NotEmpty => quote! {
+                 #error_type_path::NotEmptyViolated => write!(f, "{} is empty.", stringify!(#type_name))
+            },
+            StringValidator::Lowercase => quote! {
+                 #error_type_path::LowercaseViolated => write!(f, "{} contains uppercase characters.", stringify!(#type_name))
+            },
+            StringValidator::Uppercase => quote! {
+                 #error_type_path::UppercaseViolated => write!(f, "{} contains lowercase characters.", stringify!(#type_name))
+            },
+            StringValidator::Alphanumeric => quote! {
+                 #error_type_path::AlphanumericViolated => write!(f, "{} contains characters, that are not alphanumeric.", stringify!(#type_name))
+            },
+            StringValidator::Ascii => quote! {
+                 #error_type_path::AsciiViolated => write!(f, "{} contains non-ASCII characters.", stringify!(#type_name))
+            },
+            StringValidator::NoWhitespace => quote! {
+                 #error_type_path::NoWhitespaceViolated => write!(f, "{} contains whitespace.", stringify!(#type_name))
+            },
+            StringValidator::Hostname => quote! {
+                 #error_type_path::HostnameViolated => write!(f, "{} is not a valid hostname.", stringify!(#type_name))
+            },
+            StringValidator::StartsWith(_) => quote! {
+                 #error_type_path::StartsWithViolated(fragment) => write!(f, "{} must start with {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::NotStartsWith(_) => quote! {
+                 #error_type_path::NotStartsWithViolated(fragment) => write!(f, "{} must not start with {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::EndsWith(_) => quote! {
+                 #error_type_path::EndsWithViolated(fragment) => write!(f, "{} must end with {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::NotEndsWith(_) => quote! {
+                 #error_type_path::NotEndsWithViolated(fragment) => write!(f, "{} must not end with {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::Contains(_) => quote! {
+                 #error_type_path::ContainsViolated(fragment) => write!(f, "{} must contain {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::NotContains(_) => quote! {
+                 #error_type_path::NotContainsViolated(fragment) => write!(f, "{} must not contain {:?}.", stringify!(#type_name), fragment)
+            },
+            StringValidator::In(allowed_values) => {
+                let allowed_values = allowed_values_to_string(allowed_values);
+                quote! {
+                    #error_type_path::InViolated(val) => write!(f, "{} is not allowed. The value must be one of [{}], got {:?}.", stringify!(#type_name), #allowed_values, val)
+                }
+            }
+            StringValidator::Predicate(_) => quote! {
+                 #error_type_path::PredicateViolated => write!(f, "{} failed the predicate test.", stringify!(#type_name))
+            },
+            StringValidator::Regex(_) => quote! {
+                 #error_type_path::RegexViolated => write!(f, "{} violated the regular expression.", stringify!(#type_name))
+            },
+        });
+    let custom_arms = gen_custom_display_arms(type_name, gen_variant_codes(validators), messages);
+
+    let extra_arms = gen_extra_display_arms(type_name, error_type_path, extra_variants);
 
     quote! {
         impl ::core::fmt::Display for #error_type_path {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 match self {
                     #(#match_arms,)*
+                    #(#custom_arms,)*
+                    #extra_arms
                 }
             }
         }
     }
 }
+
+/// Returns `(true, len)` for the lower bound of a length and `(false, len)` for the upper one.
+type LenBound<'a> = fn(&'a StringValidator) -> Option<(bool, &'a ValueOrExpr<usize>)>;
+
+/// Message of a violated length validator, that mentions both bounds of the length when they
+/// are set, e.g. `Name is too long. The value length must be between 3 and 50 characters.`
+fn gen_len_message<'a>(
+    type_name: &TypeName,
+    too: &str,
+    validators: &'a [StringValidator],
+    len_bound: LenBound<'a>,
+    unit: &str,
+) -> TokenStream {
+    let mut lower = None;
+    let mut upper = None;
+    for (is_min, len) in validators.iter().filter_map(len_bound) {
+        let bound = RangeBound {
+            value: quote!(#len),
+            inclusive: true,
+        };
+        if is_min {
+            lower = Some(bound);
+        } else {
+            upper = Some(bound);
+        }
+    }
+    let (range, values) = describe_range(lower, upper, "{}", unit);
+    let format = format!("{{}} is too {too}. The value length must be {range}.");
+    quote!(write!(f, #format, stringify!(#type_name), #(#values),*))
+}
+
+fn len_char_bound(validator: &StringValidator) -> Option<(bool, &ValueOrExpr<usize>)> {
+    match validator {
+        StringValidator::LenCharMin(len) => Some((true, len)),
+        StringValidator::LenCharMax(len) => Some((false, len)),
+        _ => None,
+    }
+}
+
+fn len_byte_bound(validator: &StringValidator) -> Option<(bool, &ValueOrExpr<usize>)> {
+    match validator {
+        StringValidator::LenByteMin(len) => Some((true, len)),
+        StringValidator::LenByteMax(len) => Some((false, len)),
+        _ => None,
+    }
+}
+
+fn len_grapheme_bound(validator: &StringValidator) -> Option<(bool, &ValueOrExpr<usize>)> {
+    match validator {
+        StringValidator::LenGraphemeMin(len) => Some((true, len)),
+        StringValidator::LenGraphemeMax(len) => Some((false, len)),
+        _ => None,
+    }
+}
+
+fn gen_variant_codes(validators: &[StringValidator]) -> Vec<ErrorVariantCode> {
+    validators
+        .iter()
+        .map(|validator| {
+            let pattern = match validator {
+                StringValidator::LenCharMax(_) => quote!(Self::LenCharMaxViolated),
+                StringValidator::LenCharMin(_) => quote!(Self::LenCharMinViolated),
+                StringValidator::LenByteMax(_) => quote!(Self::LenByteMaxViolated),
+                StringValidator::LenByteMin(_) => quote!(Self::LenByteMinViolated),
+                StringValidator::LenGraphemeMax(_) => quote!(Self::LenGraphemeMaxViolated),
+                StringValidator::LenGraphemeMin(_) => quote!(Self::LenGraphemeMinViolated),
+                StringValidator::NotEmpty => quote!(Self::NotEmptyViolated),
+                StringValidator::Lowercase => quote!(Self::LowercaseViolated),
+                StringValidator::Uppercase => quote!(Self::UppercaseViolated),
+                StringValidator::Alphanumeric => quote!(Self::AlphanumericViolated),
+                StringValidator::Ascii => quote!(Self::AsciiViolated),
+                StringValidator::NoWhitespace => quote!(Self::NoWhitespaceViolated),
+                StringValidator::Hostname => quote!(Self::HostnameViolated),
+                StringValidator::StartsWith(_) => quote!(Self::StartsWithViolated(_)),
+                StringValidator::NotStartsWith(_) => quote!(Self::NotStartsWithViolated(_)),
+                StringValidator::EndsWith(_) => quote!(Self::EndsWithViolated(_)),
+                StringValidator::NotEndsWith(_) => quote!(Self::NotEndsWithViolated(_)),
+                StringValidator::Contains(_) => quote!(Self::ContainsViolated(_)),
+                StringValidator::NotContains(_) => quote!(Self::NotContainsViolated(_)),
+                StringValidator::In(_) => quote!(Self::InViolated(_)),
+                StringValidator::Predicate(_) => quote!(Self::PredicateViolated),
+                StringValidator::Regex(_) => quote!(Self::RegexViolated),
+            };
+            ErrorVariantCode::new(pattern, validator)
+        })
+        .collect()
+}
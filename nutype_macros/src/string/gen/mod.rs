@@ -1,3 +1,5 @@
+pub mod case;
+pub mod doc;
 pub mod error;
 pub mod tests;
 pub mod traits;
@@ -11,15 +13,32 @@ use syn::Generics;
 use crate::{
     common::{
         gen::{
-            tests::gen_test_should_have_valid_default_value, traits::GeneratedTraits,
+            bytes::gen_impl_bytes,
+            canonicalize_number::gen_canonicalize_number,
+            error::{gen_bytes_error_type_name, ExtraVariants},
+            new_into::{gen_new_in_arena, gen_new_into},
+            store_as::gen_decode,
+            tests::gen_test_should_have_valid_default_value,
+            trace::{gen_sanitizations, gen_validations},
+            traits::GeneratedTraits,
+            try_with::{
+                gen_fn_sanitize_of, gen_try_with_step, gen_wrap_try_with_error, try_with_error_type,
+            },
             GenerateNewtype,
         },
-        models::{ErrorTypePath, Guard, TypeName},
+        models::{
+            DeriveOptions, ErrorMessages, ErrorTypePath, Guard, OnInvalid, RuleNode, StoreAs,
+            TypeName, Validation,
+        },
     },
     string::models::{RegexDef, StringInnerType, StringSanitizer, StringValidator},
 };
 
-use self::{error::gen_validation_error_type, traits::gen_traits};
+use self::{
+    case::{gen_convert_case, gen_slugify},
+    error::gen_validation_error_type,
+    traits::gen_traits,
+};
 
 use super::{
     models::{StringDeriveTrait, StringGuard},
@@ -39,43 +58,182 @@ impl GenerateNewtype for StringNewtype {
     // This allows to use &str with it.
     const NEW_CONVERT_INTO_INNER_TYPE: bool = true;
 
+    // Validators of String based types receive `&str`.
+    const VALIDATES_STR: bool = true;
+
     fn gen_fn_sanitize(
+        inner_type: &Self::InnerType,
+        sanitizers: &[Self::Sanitizer],
+        trace: bool,
+        lossy: bool,
+    ) -> TokenStream {
+        match inner_type {
+            StringInnerType::String => {
+                let transformations = gen_sanitizations(
+                    &RuleNode::sanitizers(sanitizers),
+                    trace,
+                    lossy,
+                    gen_string_sanitizer,
+                );
+                gen_fn_sanitize_of(
+                    quote!(value: String),
+                    inner_type,
+                    sanitizers,
+                    transformations,
+                    lossy,
+                )
+            }
+            StringInnerType::Str { .. } => {
+                // NOTE: Validation guarantees that `trim` is the only sanitizer that can be
+                // applied to `&str`, since it does not require allocation.
+                let transformations =
+                    gen_sanitizations(&RuleNode::sanitizers(sanitizers), trace, lossy, |san| {
+                        match san {
+                            StringSanitizer::Trim => quote!(
+                                let value: #inner_type = value.trim();
+                            ),
+                            _ => unreachable!("Sanitizer {san:?} is not allowed for &str"),
+                        }
+                    });
+                gen_fn_sanitize_of(
+                    quote!(value: #inner_type),
+                    inner_type,
+                    sanitizers,
+                    transformations,
+                    lossy,
+                )
+            }
+            StringInnerType::Cow { path, .. } => {
+                let transformations =
+                    gen_sanitizations(&RuleNode::sanitizers(sanitizers), trace, lossy, |san| {
+                        match san {
+                            // Trimming a borrowed string does not require allocation
+                            StringSanitizer::Trim => quote!(
+                                let value: #inner_type = match value {
+                                    #path::Borrowed(s) => #path::Borrowed(s.trim()),
+                                    #path::Owned(s) => {
+                                        let trimmed = s.trim();
+                                        if trimmed.len() == s.len() {
+                                            #path::Owned(s)
+                                        } else {
+                                            #path::Owned(trimmed.to_string())
+                                        }
+                                    }
+                                };
+                            ),
+                            _ => {
+                                let transformation = gen_string_sanitizer(san);
+                                quote!(
+                                    let value: #inner_type = #path::Owned({
+                                        let value: String = value.into_owned();
+                                        #transformation
+                                        value
+                                    });
+                                )
+                            }
+                        }
+                    });
+                gen_fn_sanitize_of(
+                    quote!(value: #inner_type),
+                    inner_type,
+                    sanitizers,
+                    transformations,
+                    lossy,
+                )
+            }
+        }
+    }
+
+    fn gen_fn_new_into(
+        type_name: &TypeName,
+        generics: &Generics,
+        inner_type: &Self::InnerType,
+        guard: &StringGuard,
+        arena: bool,
+        maybe_on_invalid: Option<&OnInvalid>,
+        constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        match inner_type {
+            StringInnerType::String => gen_new_into(
+                type_name,
+                generics,
+                inner_type,
+                quote!(&str),
+                quote!(value.push_str(raw);),
+                None,
+                guard.maybe_error_type_path(),
+                maybe_on_invalid,
+                constructor_vis,
+            ),
+            StringInnerType::Str { lifetime } if arena => gen_new_in_arena(
+                type_name,
+                generics,
+                lifetime,
+                guard.maybe_error_type_path(),
+                constructor_vis,
+            ),
+            // Borrowed strings do not own a buffer.
+            StringInnerType::Str { .. } | StringInnerType::Cow { .. } => quote!(),
+        }
+    }
+
+    fn gen_fn_eq_raw(
         _inner_type: &Self::InnerType,
         sanitizers: &[Self::Sanitizer],
+        maybe_store_as: Option<&StoreAs>,
     ) -> TokenStream {
-        let transformations: TokenStream = sanitizers
-            .iter()
-            .map(|san| match san {
-                StringSanitizer::Trim => {
-                    // TODO: consider optimizing sequences of [trim, lowercase] and [trim, uppercase] to avoid
-                    // unnecessary allocation with `to_string()`
-                    quote!(
-                        let value: String = value.trim().to_string();
-                    )
-                }
-                StringSanitizer::Lowercase => {
-                    quote!(
-                        let value: String = value.to_lowercase();
-                    )
-                }
-                StringSanitizer::Uppercase => {
-                    quote!(
-                        let value: String = value.to_uppercase();
-                    )
-                }
-                StringSanitizer::With(typed_custom_function) => {
-                    quote!(
-                        let value: String = (#typed_custom_function)(value);
-                    )
-                }
-            })
-            .collect();
+        let inner_value = gen_decode(maybe_store_as);
+        // A fallible sanitizer can not be applied in place, so a rejected input is not equal.
+        let sanitized_raw = if try_with_error_type(sanitizers).is_some() {
+            quote!(
+                let Ok(value) = Self::__sanitize__(::core::convert::From::from(String::from(raw))) else {
+                    return false;
+                };
+            )
+        } else {
+            gen_sanitize_raw_str(sanitizers)
+        };
 
-        quote!(
-            fn __sanitize__(value: String) -> String {
-                #transformations
-                value
+        quote! {
+            /// Returns `true` if the value, after being sanitized, is equal to the inner value.
+            pub fn eq_raw(&self, raw: &str) -> bool {
+                #sanitized_raw
+                *#inner_value == *value
+            }
+        }
+    }
+
+    fn gen_impl_bytes(
+        type_name: &TypeName,
+        guard: &StringGuard,
+        constructor_vis: &TokenStream,
+    ) -> TokenStream {
+        let bytes_error_type_path = gen_bytes_error_type_name(type_name);
+        let sanitize_raw = match guard {
+            // The error of a fallible sanitizer is reported as a validation error.
+            Guard::WithValidation {
+                sanitizers,
+                validation,
+                ..
+            } if try_with_error_type(sanitizers).is_some() => {
+                let wrap_error = gen_wrap_try_with_error(validation);
+                quote!(
+                    let value: String = Self::__sanitize__(raw.into())
+                        .map_err(|err| #bytes_error_type_path::Validate(#wrap_error))?;
+                )
             }
+            // A custom validation function receives `&String`.
+            Guard::WithValidation {
+                validation: Validation::Custom { .. },
+                ..
+            } => quote!(let value: String = Self::__sanitize__(raw.into());),
+            _ => gen_sanitize_raw_str(guard.sanitizers()),
+        };
+        gen_impl_bytes(
+            type_name,
+            sanitize_raw,
+            guard.maybe_error_type_path(),
+            constructor_vis,
         )
     }
 
@@ -83,14 +241,16 @@ impl GenerateNewtype for StringNewtype {
         _inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        trace: bool,
     ) -> TokenStream {
         // Indicates that `chars_count` variable needs to be set, which is used within
         // min_len and max_len validations.
         let mut requires_chars_count = false;
+        // The same for `graphemes_count`, which is used within len_grapheme_min and len_grapheme_max.
+        let mut requires_graphemes_count = false;
 
-        let validations: TokenStream = validators
-            .iter()
-            .map(|validator| match validator {
+        let validations = gen_validations(&RuleNode::validators(validators), trace, |validator| {
+            match validator {
                 StringValidator::LenCharMax(max_len) => {
                     requires_chars_count = true;
                     quote!(
@@ -107,6 +267,36 @@ impl GenerateNewtype for StringNewtype {
                         }
                     )
                 }
+                StringValidator::LenByteMax(max_len) => {
+                    quote!(
+                        if val.len() > #max_len {
+                            return Err(#error_type_path::LenByteMaxViolated);
+                        }
+                    )
+                }
+                StringValidator::LenByteMin(min_len) => {
+                    quote!(
+                        if val.len() < #min_len {
+                            return Err(#error_type_path::LenByteMinViolated);
+                        }
+                    )
+                }
+                StringValidator::LenGraphemeMax(max_len) => {
+                    requires_graphemes_count = true;
+                    quote!(
+                        if graphemes_count > #max_len {
+                            return Err(#error_type_path::LenGraphemeMaxViolated);
+                        }
+                    )
+                }
+                StringValidator::LenGraphemeMin(min_len) => {
+                    requires_graphemes_count = true;
+                    quote!(
+                        if graphemes_count < #min_len {
+                            return Err(#error_type_path::LenGraphemeMinViolated);
+                        }
+                    )
+                }
                 StringValidator::NotEmpty => {
                     quote!(
                         if val.is_empty() {
@@ -114,6 +304,105 @@ impl GenerateNewtype for StringNewtype {
                         }
                     )
                 }
+                StringValidator::Lowercase => {
+                    quote!(
+                        if val.chars().any(char::is_uppercase) {
+                            return Err(#error_type_path::LowercaseViolated);
+                        }
+                    )
+                }
+                StringValidator::Uppercase => {
+                    quote!(
+                        if val.chars().any(char::is_lowercase) {
+                            return Err(#error_type_path::UppercaseViolated);
+                        }
+                    )
+                }
+                StringValidator::Alphanumeric => {
+                    quote!(
+                        if !val.chars().all(char::is_alphanumeric) {
+                            return Err(#error_type_path::AlphanumericViolated);
+                        }
+                    )
+                }
+                StringValidator::Ascii => {
+                    quote!(
+                        if !val.is_ascii() {
+                            return Err(#error_type_path::AsciiViolated);
+                        }
+                    )
+                }
+                StringValidator::NoWhitespace => {
+                    quote!(
+                        if val.chars().any(char::is_whitespace) {
+                            return Err(#error_type_path::NoWhitespaceViolated);
+                        }
+                    )
+                }
+                // Follows RFC 1123, see `is_hostname()` in `string::validate`.
+                StringValidator::Hostname => {
+                    quote!(
+                        let is_hostname = val.len() <= 253
+                            && val.split('.').all(|label| {
+                                (1..=63).contains(&label.len())
+                                    && !label.starts_with('-')
+                                    && !label.ends_with('-')
+                                    && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+                            });
+                        if !is_hostname {
+                            return Err(#error_type_path::HostnameViolated);
+                        }
+                    )
+                }
+                StringValidator::StartsWith(fragment) => {
+                    quote!(
+                        if !val.starts_with(#fragment) {
+                            return Err(#error_type_path::StartsWithViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::NotStartsWith(fragment) => {
+                    quote!(
+                        if val.starts_with(#fragment) {
+                            return Err(#error_type_path::NotStartsWithViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::EndsWith(fragment) => {
+                    quote!(
+                        if !val.ends_with(#fragment) {
+                            return Err(#error_type_path::EndsWithViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::NotEndsWith(fragment) => {
+                    quote!(
+                        if val.ends_with(#fragment) {
+                            return Err(#error_type_path::NotEndsWithViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::Contains(fragment) => {
+                    quote!(
+                        if !val.contains(#fragment) {
+                            return Err(#error_type_path::ContainsViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::NotContains(fragment) => {
+                    quote!(
+                        if val.contains(#fragment) {
+                            return Err(#error_type_path::NotContainsViolated(#fragment));
+                        }
+                    )
+                }
+                StringValidator::In(allowed_values) => {
+                    quote!(
+                        if ![#(#allowed_values),*].contains(&val) {
+                            return Err(#error_type_path::InViolated(val.into()));
+                        }
+                    )
+                }
                 StringValidator::Predicate(typed_custom_function) => {
                     quote!(
                         if !(#typed_custom_function)(&val) {
@@ -132,7 +421,6 @@ impl GenerateNewtype for StringNewtype {
                                     return Err(#error_type_path::RegexViolated);
                                 }
                             )
-
                         }
                         RegexDef::Path(regex_path) => {
                             quote!(
@@ -143,8 +431,8 @@ impl GenerateNewtype for StringNewtype {
                         }
                     }
                 }
-            })
-            .collect();
+            }
+        });
 
         let chars_count_if_required = if requires_chars_count {
             quote!(
@@ -154,9 +442,16 @@ impl GenerateNewtype for StringNewtype {
             quote!()
         };
 
+        let graphemes_count_if_required = requires_graphemes_count.then(|| {
+            quote!(
+                let graphemes_count = ::unicode_segmentation::UnicodeSegmentation::graphemes(val, true).count();
+            )
+        });
+
         quote!(
             fn __validate__(val: &str) -> ::core::result::Result<(), #error_type_path> {
                 #chars_count_if_required
+                #graphemes_count_if_required
                 #validations
                 Ok(())
             }
@@ -165,21 +460,139 @@ impl GenerateNewtype for StringNewtype {
 
     fn gen_validation_error_type(
         type_name: &TypeName,
+        _inner_type: &Self::InnerType,
         error_type_path: &ErrorTypePath,
         validators: &[Self::Validator],
+        extra_variants: ExtraVariants,
+        messages: &ErrorMessages,
+    ) -> TokenStream {
+        gen_validation_error_type(
+            type_name,
+            error_type_path,
+            validators,
+            extra_variants,
+            messages,
+        )
+    }
+
+    fn gen_bound_consts(
+        _inner_type: &Self::InnerType,
+        validators: &[Self::Validator],
+    ) -> TokenStream {
+        validators
+            .iter()
+            .map(|validator| match validator {
+                StringValidator::LenCharMin(min_len) => quote!(
+                    /// Minimal length in characters, declared with `len_char_min`.
+                    pub const MIN_LEN: usize = #min_len;
+                ),
+                StringValidator::LenCharMax(max_len) => quote!(
+                    /// Maximal length in characters, declared with `len_char_max`.
+                    pub const MAX_LEN: usize = #max_len;
+                ),
+                StringValidator::LenByteMin(min_len) => quote!(
+                    /// Minimal length in bytes, declared with `len_byte_min`.
+                    pub const MIN_LEN_BYTES: usize = #min_len;
+                ),
+                StringValidator::LenByteMax(max_len) => quote!(
+                    /// Maximal length in bytes, declared with `len_byte_max`.
+                    pub const MAX_LEN_BYTES: usize = #max_len;
+                ),
+                StringValidator::LenGraphemeMin(min_len) => quote!(
+                    /// Minimal length in graphemes, declared with `len_grapheme_min`.
+                    pub const MIN_LEN_GRAPHEMES: usize = #min_len;
+                ),
+                StringValidator::LenGraphemeMax(max_len) => quote!(
+                    /// Maximal length in graphemes, declared with `len_grapheme_max`.
+                    pub const MAX_LEN_GRAPHEMES: usize = #max_len;
+                ),
+                StringValidator::NotEmpty
+                | StringValidator::Lowercase
+                | StringValidator::Uppercase
+                | StringValidator::Alphanumeric
+                | StringValidator::Ascii
+                | StringValidator::NoWhitespace
+                | StringValidator::Hostname
+                | StringValidator::StartsWith(_)
+                | StringValidator::NotStartsWith(_)
+                | StringValidator::EndsWith(_)
+                | StringValidator::NotEndsWith(_)
+                | StringValidator::Contains(_)
+                | StringValidator::NotContains(_)
+                | StringValidator::In(_)
+                | StringValidator::Predicate(_)
+                | StringValidator::Regex(_) => quote!(),
+            })
+            .collect()
+    }
+
+    fn gen_input_limit(
+        sanitizers: &[Self::Sanitizer],
+        validators: &[Self::Validator],
     ) -> TokenStream {
-        gen_validation_error_type(type_name, error_type_path, validators)
+        // `trim`, `canonicalize_number`, `snake_case`, `kebab_case`, `slugify` and custom
+        // sanitizers may shorten the input, so a long raw input can still be valid. Case
+        // conversion never reduces the number of characters, but may reduce the number of bytes
+        // (e.g. the Kelvin sign `U+212A` becomes `k`).
+        let converts_case = !sanitizers.is_empty();
+        if !sanitizers.iter().all(|sanitizer| {
+            matches!(
+                sanitizer,
+                StringSanitizer::Lowercase | StringSanitizer::Uppercase
+            )
+        }) {
+            return quote!();
+        }
+
+        let limits = validators.iter().filter_map(|validator| match validator {
+            // A character takes at most 4 bytes in UTF-8.
+            StringValidator::LenCharMax(max_len) => Some(quote!((#max_len).saturating_mul(4))),
+            StringValidator::LenByteMax(max_len) if !converts_case => Some(quote!(#max_len)),
+            _ => None,
+        });
+        let Some(max_accept_bytes) = limits.reduce(|lhs, rhs| {
+            quote!({
+                let (lhs, rhs): (usize, usize) = (#lhs, #rhs);
+                if lhs < rhs { lhs } else { rhs }
+            })
+        }) else {
+            return quote!();
+        };
+
+        quote!(
+            /// Maximal length in bytes of a raw input, that can be valid.
+            /// Derived from `len_char_max` and `len_byte_max`.
+            pub const MAX_ACCEPT_BYTES: usize = #max_accept_bytes;
+
+            /// Returns `true` if an input of `len_hint` bytes (e.g. `Content-Length`) is too long
+            /// to be valid, so it can be rejected before it's buffered.
+            #[inline]
+            pub const fn exceeds_limit(len_hint: usize) -> bool {
+                len_hint > Self::MAX_ACCEPT_BYTES
+            }
+        )
     }
 
     fn gen_traits(
         type_name: &TypeName,
         generics: &Generics,
-        _inner_type: &Self::InnerType,
+        inner_type: &Self::InnerType,
         traits: HashSet<Self::TypedTrait>,
         maybe_default_value: Option<syn::Expr>,
+        derive_options: &DeriveOptions,
         guard: &StringGuard,
+        maybe_store_as: Option<&StoreAs>,
     ) -> Result<GeneratedTraits, syn::Error> {
-        gen_traits(type_name, generics, traits, maybe_default_value, guard)
+        gen_traits(
+            type_name,
+            generics,
+            inner_type,
+            traits,
+            maybe_default_value,
+            derive_options,
+            guard,
+            maybe_store_as,
+        )
     }
 
     fn gen_tests(
@@ -190,8 +603,8 @@ impl GenerateNewtype for StringNewtype {
         guard: &Guard<Self::Sanitizer, Self::Validator>,
         _traits: &HashSet<Self::TypedTrait>,
     ) -> TokenStream {
-        let test_len_char_min_vs_max = guard.standard_validators().and_then(|validators| {
-            tests::gen_test_should_have_consistent_len_char_boundaries(type_name, validators)
+        let test_len_min_vs_max = guard.standard_validators().map(|validators| {
+            tests::gen_tests_should_have_consistent_len_boundaries(type_name, validators)
         });
 
         let test_valid_default_value = gen_test_should_have_valid_default_value(
@@ -202,8 +615,88 @@ impl GenerateNewtype for StringNewtype {
         );
 
         quote! {
-            #test_len_char_min_vs_max
+            #test_len_min_vs_max
             #test_valid_default_value
         }
     }
 }
+
+/// Generates a single sanitizing transformation of `value: String`.
+fn gen_string_sanitizer(sanitizer: &StringSanitizer) -> TokenStream {
+    match sanitizer {
+        // The sanitizers below modify the string in place where it's possible, so a chain like
+        // `trim, lowercase` does not allocate for ASCII input.
+        StringSanitizer::Trim => {
+            quote!(
+                let value: String = {
+                    let mut value = value;
+                    let end = value.trim_end().len();
+                    value.truncate(end);
+                    let start = value.len() - value.trim_start().len();
+                    value.drain(..start);
+                    value
+                };
+            )
+        }
+        StringSanitizer::Lowercase => {
+            // Lowercasing of non-ASCII characters may change the length of the string.
+            quote!(
+                let value: String = if value.is_ascii() {
+                    let mut value = value;
+                    value.make_ascii_lowercase();
+                    value
+                } else {
+                    value.to_lowercase()
+                };
+            )
+        }
+        StringSanitizer::Uppercase => {
+            quote!(
+                let value: String = if value.is_ascii() {
+                    let mut value = value;
+                    value.make_ascii_uppercase();
+                    value
+                } else {
+                    value.to_uppercase()
+                };
+            )
+        }
+        StringSanitizer::CanonicalizeNumber => gen_canonicalize_number(None),
+        StringSanitizer::SnakeCase => gen_convert_case('_'),
+        StringSanitizer::KebabCase => gen_convert_case('-'),
+        StringSanitizer::Slugify => gen_slugify(),
+        StringSanitizer::With(typed_custom_function) => {
+            quote!(
+                let value: String = (#typed_custom_function)(value);
+            )
+        }
+        StringSanitizer::TryWith(try_sanitizer) => {
+            let step = gen_try_with_step(try_sanitizer, quote!(String));
+            quote!(
+                let value: String = #step;
+            )
+        }
+    }
+}
+
+/// Sanitize `raw: &str` into `value`.
+/// Trimming does not require allocation, so the raw value is sanitized in place.
+/// Other sanitizers work on an owned `String`, regardless of the inner type.
+fn gen_sanitize_raw_str(sanitizers: &[StringSanitizer]) -> TokenStream {
+    if sanitizers
+        .iter()
+        .all(|san| matches!(san, StringSanitizer::Trim))
+    {
+        let trim = (!sanitizers.is_empty()).then(|| quote!(let raw = raw.trim();));
+        quote!(
+            #trim
+            let value: &str = raw;
+        )
+    } else {
+        let transformations: TokenStream = sanitizers.iter().map(gen_string_sanitizer).collect();
+        quote!(
+            let value: String = raw.into();
+            #transformations
+        )
+    }
+}
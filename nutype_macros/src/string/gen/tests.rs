@@ -1,36 +1,88 @@
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 
-use crate::{common::models::TypeName, string::models::StringValidator};
+use crate::{
+    common::models::{TypeName, ValueOrExpr},
+    string::models::StringValidator,
+};
 
-pub fn gen_test_should_have_consistent_len_char_boundaries(
+/// Generate tests that ensure that the lower length boundaries are not greater than the upper ones,
+/// for every pair of `len_{unit}_min` and `len_{unit}_max` validators.
+pub fn gen_tests_should_have_consistent_len_boundaries(
     type_name: &TypeName,
     validators: &[StringValidator],
+) -> TokenStream {
+    let char_boundaries = gen_test_should_have_consistent_len_boundaries(
+        type_name,
+        "char",
+        validators,
+        |v| match v {
+            StringValidator::LenCharMin(len) => Some(len),
+            _ => None,
+        },
+        |v| match v {
+            StringValidator::LenCharMax(len) => Some(len),
+            _ => None,
+        },
+    );
+    let byte_boundaries = gen_test_should_have_consistent_len_boundaries(
+        type_name,
+        "byte",
+        validators,
+        |v| match v {
+            StringValidator::LenByteMin(len) => Some(len),
+            _ => None,
+        },
+        |v| match v {
+            StringValidator::LenByteMax(len) => Some(len),
+            _ => None,
+        },
+    );
+    let grapheme_boundaries = gen_test_should_have_consistent_len_boundaries(
+        type_name,
+        "grapheme",
+        validators,
+        |v| match v {
+            StringValidator::LenGraphemeMin(len) => Some(len),
+            _ => None,
+        },
+        |v| match v {
+            StringValidator::LenGraphemeMax(len) => Some(len),
+            _ => None,
+        },
+    );
+
+    quote! {
+        #char_boundaries
+        #byte_boundaries
+        #grapheme_boundaries
+    }
+}
+
+fn gen_test_should_have_consistent_len_boundaries(
+    type_name: &TypeName,
+    unit: &str,
+    validators: &[StringValidator],
+    get_min: impl Fn(&StringValidator) -> Option<&ValueOrExpr<usize>>,
+    get_max: impl Fn(&StringValidator) -> Option<&ValueOrExpr<usize>>,
 ) -> Option<TokenStream> {
-    let maybe_len_char_min: Option<TokenStream> = validators
+    let maybe_len_min: Option<TokenStream> = validators
         .iter()
-        .flat_map(|v| match v {
-            StringValidator::LenCharMin(len) => Some(len.to_token_stream()),
-            _ => None,
-        })
-        .next();
-    let maybe_len_char_max: Option<TokenStream> = validators
+        .find_map(|v| get_min(v).map(ToTokens::to_token_stream));
+    let maybe_len_max: Option<TokenStream> = validators
         .iter()
-        .flat_map(|v| match v {
-            StringValidator::LenCharMax(len) => Some(len.to_token_stream()),
-            _ => None,
-        })
-        .next();
-    let (Some(len_char_min), Some(len_char_max)) = (maybe_len_char_min, maybe_len_char_max) else {
+        .find_map(|v| get_max(v).map(ToTokens::to_token_stream));
+    let (Some(len_min), Some(len_max)) = (maybe_len_min, maybe_len_max) else {
         return None;
     };
 
-    let msg = format!("\nInconsistent lower and upper boundaries for type `{type_name}`\nThe upper boundary `{len_char_max}` must be greater than or equal to the lower boundary `{len_char_min}`\n");
+    let msg = format!("\nInconsistent lower and upper boundaries for type `{type_name}`\nThe upper boundary `{len_max}` must be greater than or equal to the lower boundary `{len_min}`\n");
+    let test_name = format_ident!("should_have_consistent_len_{unit}_boundaries");
 
     Some(quote!(
         #[test]
-        fn should_have_consistent_len_char_boundaries() {
-            assert!(#len_char_max >= #len_char_min, #msg);
+        fn #test_name() {
+            assert!(#len_max >= #len_min, #msg);
         }
     ))
 }
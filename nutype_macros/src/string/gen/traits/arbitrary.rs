@@ -3,7 +3,10 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
 use crate::{
-    common::models::{TypeName, Validation, ValueOrExpr},
+    common::{
+        gen::arbitrary_invalid::gen_fn_arbitrary_invalid,
+        models::{TypeName, Validation, ValueOrExpr},
+    },
     string::models::{StringGuard, StringSanitizer, StringValidator},
     utils::issue_reporter::{build_github_link_with_issue, Issue},
 };
@@ -32,6 +35,10 @@ pub fn gen_impl_trait_arbitrary(
     let maybe_spec = build_specification(guard)?;
     let generate_inner_value = gen_generate_valid_inner_value(&maybe_spec);
     let size_hint = gen_size_hint(&maybe_spec);
+    let maybe_fn_arbitrary_invalid = guard.maybe_error_type_path().map(|error_type_path| {
+        let violations = gen_violations(guard);
+        gen_fn_arbitrary_invalid(type_name, &quote!(String), error_type_path, violations)
+    });
 
     Ok(quote!(
         impl ::arbitrary::Arbitrary<'_> for #type_name {
@@ -47,9 +54,60 @@ pub fn gen_impl_trait_arbitrary(
                 #size_hint
             }
         }
+
+        #maybe_fn_arbitrary_invalid
     ))
 }
 
+/// Strings that are too short or too long. They consist of ASCII letters, so sanitizers do not
+/// change their length.
+fn gen_violations(guard: &StringGuard) -> Vec<TokenStream> {
+    let validators = guard
+        .standard_validators()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has_not_empty = validators
+        .iter()
+        .any(|validator| matches!(validator, StringValidator::NotEmpty));
+    let gen_letters = |len: TokenStream| {
+        quote!(
+            (0..#len)
+                .map(|_| u.int_in_range(b'a'..=b'z').map(char::from))
+                .collect::<::arbitrary::Result<String>>()
+        )
+    };
+
+    validators
+        .iter()
+        .filter_map(|validator| match validator {
+            StringValidator::NotEmpty => Some(quote!(Ok(String::new()))),
+            StringValidator::LenCharMin(min_len) => {
+                // An empty string would break `not_empty` as well.
+                let shortest = if has_not_empty { quote!(1) } else { quote!(0) };
+                let letters = gen_letters(quote!(len));
+                Some(quote!(
+                    let min_len: usize = #min_len;
+                    if min_len <= #shortest {
+                        Err(::arbitrary::Error::IncorrectFormat)
+                    } else {
+                        let len = u.int_in_range(#shortest..=(min_len - 1))?;
+                        #letters
+                    }
+                ))
+            }
+            StringValidator::LenCharMax(max_len) => {
+                let letters = gen_letters(quote!(len));
+                Some(quote!(
+                    let max_len: usize = #max_len;
+                    let len = u.int_in_range((max_len + 1)..=(max_len + #DEFAULT_LEN_OFFSET))?;
+                    #letters
+                ))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn gen_size_hint(maybe_spec: &Option<Specification>) -> TokenStream {
     match maybe_spec {
         Some(spec) => {
@@ -111,6 +169,7 @@ fn build_specification(guard: &StringGuard) -> Result<Option<Specification>, syn
         StringGuard::WithValidation {
             sanitizers,
             validation,
+            ..
         } => {
             let validators = get_validators(validation)?;
             let relevant_validators = filter_validators(validators)?;
@@ -169,6 +228,29 @@ fn filter_validators(validators: &[StringValidator]) -> Result<Vec<RelevantValid
             StringValidator::LenCharMax(value) => Ok(RelevantValidator::LenCharMax(value.clone())),
             // In context of generating an arbitrary string NotEmpty is the same as LenCharMin(1)
             StringValidator::NotEmpty => Ok(RelevantValidator::LenCharMin(ValueOrExpr::Value(1))),
+            StringValidator::LenByteMin(_)
+            | StringValidator::LenByteMax(_)
+            | StringValidator::LenGraphemeMin(_)
+            | StringValidator::LenGraphemeMax(_)
+            | StringValidator::Lowercase
+            | StringValidator::Uppercase
+            | StringValidator::Alphanumeric
+            | StringValidator::Ascii
+            | StringValidator::NoWhitespace
+            | StringValidator::Hostname
+            | StringValidator::StartsWith(_)
+            | StringValidator::NotStartsWith(_)
+            | StringValidator::EndsWith(_)
+            | StringValidator::NotEndsWith(_)
+            | StringValidator::Contains(_)
+            | StringValidator::NotContains(_) => {
+                let msg = format!("It's not possible to derive `Arbitrary` trait for a type with `{}` validator.\nYou have to implement `Arbitrary` trait on you own.", v.kind());
+                Err(syn::Error::new(Span::call_site(), msg))
+            }
+            StringValidator::In(_) => {
+                let msg = "It's not possible to derive `Arbitrary` trait for a type with `in` validator.\nYou have to implement `Arbitrary` trait on you own.";
+                Err(syn::Error::new(Span::call_site(), msg))
+            }
             StringValidator::Predicate(_) => {
                 let msg = "It's not possible to derive `Arbitrary` trait for a type with `predicate` validator.\nYou have to implement `Arbitrary` trait on you own.";
                 Err(syn::Error::new(Span::call_site(), msg))
@@ -191,10 +273,22 @@ fn filter_sanitizers(sanitizers: &[StringSanitizer]) -> Result<Vec<RelevantSanit
             // so we can ignore them
             StringSanitizer::Lowercase => None,
             StringSanitizer::Uppercase => None,
+            StringSanitizer::CanonicalizeNumber => {
+                let msg = "It's not possible to derive `Arbitrary` trait for a type with `canonicalize_number` sanitizer.\nYou have to implement `Arbitrary` trait on you own.";
+                Some(Err(syn::Error::new(Span::call_site(), msg)))
+            }
+            StringSanitizer::SnakeCase | StringSanitizer::KebabCase | StringSanitizer::Slugify => {
+                let msg = format!("It's not possible to derive `Arbitrary` trait for a type with `{}` sanitizer.\nYou have to implement `Arbitrary` trait on you own.", s.kind());
+                Some(Err(syn::Error::new(Span::call_site(), msg)))
+            }
             StringSanitizer::With(_) => {
                 let msg = "It's not possible to derive `Arbitrary` trait for a type with `with` sanitizer.\nYou have to implement `Arbitrary` trait on you own.";
                 Some(Err(syn::Error::new(Span::call_site(), msg)))
             }
+            StringSanitizer::TryWith(_) => {
+                let msg = "It's not possible to derive `Arbitrary` trait for a type with `try_with` sanitizer.\nYou have to implement `Arbitrary` trait on you own.";
+                Some(Err(syn::Error::new(Span::call_site(), msg)))
+            }
         }
     }).collect()
 }
@@ -1,4 +1,5 @@
 pub mod arbitrary;
+pub mod serde_borrow;
 
 use std::collections::HashSet;
 
@@ -8,14 +9,20 @@ use syn::Generics;
 
 use crate::{
     common::{
+        gen::borsh::{gen_impl_trait_borsh_deserialize, gen_impl_trait_borsh_serialize},
+        gen::clap::gen_impl_trait_clap_value_parser_factory,
+        gen::rkyv::gen_impl_trait_rkyv,
+        gen::store_as::{gen_decode_of, gen_impl_trait_debug_decoded},
+        gen::strip_trait_bounds_on_generics,
         gen::traits::{
             gen_impl_trait_as_ref, gen_impl_trait_borrow, gen_impl_trait_default,
             gen_impl_trait_deref, gen_impl_trait_display, gen_impl_trait_from, gen_impl_trait_into,
-            gen_impl_trait_serde_deserialize, gen_impl_trait_serde_serialize,
-            gen_impl_trait_try_from, split_into_generatable_traits, GeneratableTrait,
+            gen_impl_trait_into_types, gen_impl_trait_serde_deserialize,
+            gen_impl_trait_serde_serialize, gen_impl_trait_try_from, gen_impl_trait_try_from_types,
+            gen_impl_trait_validator_validate, split_into_generatable_traits, GeneratableTrait,
             GeneratableTraits, GeneratedTraits,
         },
-        models::{ErrorTypePath, TypeName},
+        models::{DeriveOptions, ErrorTypePath, StoreAs, TypeName},
     },
     string::models::{StringDeriveTrait, StringGuard, StringInnerType},
 };
@@ -27,6 +34,7 @@ type StringGeneratableTrait = GeneratableTrait<StringTransparentTrait, StringIrr
 enum StringTransparentTrait {
     Debug,
     Clone,
+    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -51,6 +59,11 @@ enum StringIrregularTrait {
     SerdeSerialize,
     SerdeDeserialize,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl From<StringDeriveTrait> for StringGeneratableTrait {
@@ -62,6 +75,9 @@ impl From<StringDeriveTrait> for StringGeneratableTrait {
             StringDeriveTrait::Clone => {
                 StringGeneratableTrait::Transparent(StringTransparentTrait::Clone)
             }
+            StringDeriveTrait::Copy => {
+                StringGeneratableTrait::Transparent(StringTransparentTrait::Copy)
+            }
             StringDeriveTrait::PartialEq => {
                 StringGeneratableTrait::Transparent(StringTransparentTrait::PartialEq)
             }
@@ -116,6 +132,21 @@ impl From<StringDeriveTrait> for StringGeneratableTrait {
             StringDeriveTrait::ArbitraryArbitrary => {
                 StringGeneratableTrait::Irregular(StringIrregularTrait::ArbitraryArbitrary)
             }
+            StringDeriveTrait::ValidatorValidate => {
+                StringGeneratableTrait::Irregular(StringIrregularTrait::ValidatorValidate)
+            }
+            StringDeriveTrait::BorshSerialize => {
+                StringGeneratableTrait::Irregular(StringIrregularTrait::BorshSerialize)
+            }
+            StringDeriveTrait::BorshDeserialize => {
+                StringGeneratableTrait::Irregular(StringIrregularTrait::BorshDeserialize)
+            }
+            StringDeriveTrait::RkyvArchive => {
+                StringGeneratableTrait::Irregular(StringIrregularTrait::RkyvArchive)
+            }
+            StringDeriveTrait::ClapValueParserFactory => {
+                StringGeneratableTrait::Irregular(StringIrregularTrait::ClapValueParserFactory)
+            }
         }
     }
 }
@@ -125,6 +156,7 @@ impl ToTokens for StringTransparentTrait {
         let tokens = match self {
             Self::Debug => quote!(Debug),
             Self::Clone => quote!(Clone),
+            Self::Copy => quote!(Copy),
             Self::PartialEq => quote!(PartialEq),
             Self::Eq => quote!(Eq),
             Self::PartialOrd => quote!(PartialOrd),
@@ -136,13 +168,25 @@ impl ToTokens for StringTransparentTrait {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn gen_traits(
     type_name: &TypeName,
     generics: &Generics,
-    traits: HashSet<StringDeriveTrait>,
+    inner_type: &StringInnerType,
+    mut traits: HashSet<StringDeriveTrait>,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     guard: &StringGuard,
+    maybe_store_as: Option<&StoreAs>,
 ) -> Result<GeneratedTraits, syn::Error> {
+    // Derived `Debug` would print the stored value, so it is implemented manually.
+    let maybe_impl_debug_decoded = match maybe_store_as {
+        Some(store_as) if traits.remove(&StringDeriveTrait::Debug) => {
+            Some(gen_impl_trait_debug_decoded(type_name, generics, store_as))
+        }
+        _ => None,
+    };
+
     let GeneratableTraits {
         transparent_traits,
         irregular_traits,
@@ -157,42 +201,112 @@ pub fn gen_traits(
     let implement_traits = gen_implemented_traits(
         type_name,
         generics,
+        inner_type,
         maybe_default_value,
+        derive_options,
         irregular_traits,
         guard,
+        maybe_store_as,
     )?;
 
+    let maybe_impl_eq_str = derive_options
+        .eq_str
+        .map(|_| gen_impl_eq_str(type_name, generics, maybe_store_as));
+
     Ok(GeneratedTraits {
         derive_transparent_traits,
-        implement_traits,
+        implement_traits: quote! {
+            #maybe_impl_debug_decoded
+            #implement_traits
+            #maybe_impl_eq_str
+        },
     })
 }
 
+/// Compare with string slices in both directions, e.g. `username == "alice"` and
+/// `"alice" == username`. The raw string is neither sanitized nor validated.
+fn gen_impl_eq_str(
+    type_name: &TypeName,
+    generics: &Generics,
+    maybe_store_as: Option<&StoreAs>,
+) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+    let self_value = gen_decode_of(quote!(self), maybe_store_as);
+    let other_value = gen_decode_of(quote!(other), maybe_store_as);
+
+    quote! {
+        impl #generics ::core::cmp::PartialEq<str> for #type_name #generics_without_bounds {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                ::core::convert::AsRef::<str>::as_ref(&#self_value) == other
+            }
+        }
+
+        impl #generics ::core::cmp::PartialEq<&str> for #type_name #generics_without_bounds {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                ::core::convert::AsRef::<str>::as_ref(&#self_value) == *other
+            }
+        }
+
+        impl #generics ::core::cmp::PartialEq<#type_name #generics_without_bounds> for str {
+            #[inline]
+            fn eq(&self, other: &#type_name #generics_without_bounds) -> bool {
+                self == ::core::convert::AsRef::<str>::as_ref(&#other_value)
+            }
+        }
+
+        impl #generics ::core::cmp::PartialEq<#type_name #generics_without_bounds> for &str {
+            #[inline]
+            fn eq(&self, other: &#type_name #generics_without_bounds) -> bool {
+                *self == ::core::convert::AsRef::<str>::as_ref(&#other_value)
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn gen_implemented_traits(
     type_name: &TypeName,
     generics: &Generics,
+    inner_type: &StringInnerType,
     maybe_default_value: Option<syn::Expr>,
+    derive_options: &DeriveOptions,
     impl_traits: Vec<StringIrregularTrait>,
     guard: &StringGuard,
+    maybe_store_as: Option<&StoreAs>,
 ) -> Result<TokenStream, syn::Error> {
-    let inner_type = StringInnerType;
     let maybe_error_type_name = guard.maybe_error_type_path();
+    // `Box<str>` and `Arc<str>` storage can be borrowed as `str`, but not as `String`.
+    let derefs_to_str = maybe_store_as.is_some_and(|store_as| store_as.derefs_to_str);
 
     impl_traits
         .iter()
         .map(|t| match t {
             StringIrregularTrait::AsRef => Ok(gen_impl_trait_as_ref(type_name, generics, quote!(str))),
-            StringIrregularTrait::Deref => Ok(gen_impl_trait_deref(type_name, generics, quote!(String))),
+            StringIrregularTrait::Deref if derefs_to_str => Ok(gen_impl_trait_deref(type_name, generics, quote!(str))),
+            StringIrregularTrait::Deref => Ok(gen_impl_trait_deref(type_name, generics, inner_type)),
             StringIrregularTrait::FromStr => {
-                Ok(gen_impl_from_str(type_name, maybe_error_type_name))
-            }
-            StringIrregularTrait::From => Ok(gen_impl_from_str_and_string(type_name)),
-            StringIrregularTrait::Into => Ok(gen_impl_trait_into(type_name, &Generics::default(), inner_type)),
-            StringIrregularTrait::TryFrom => {
-                Ok(gen_impl_try_from(type_name, maybe_error_type_name))
+                Ok(gen_impl_from_str(type_name, generics, inner_type, maybe_error_type_name))
             }
-            StringIrregularTrait::Borrow => Ok(gen_impl_borrow_str_and_string(type_name)),
-            StringIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, &Generics::default())),
+            StringIrregularTrait::From => Ok(gen_impl_from_str_and_string(type_name, generics, inner_type)),
+            StringIrregularTrait::Into => match derive_options.into {
+                Some(ref target_types) => Ok(gen_impl_trait_into_types(type_name, generics, target_types)),
+                None => Ok(gen_impl_trait_into(type_name, generics, inner_type.clone())),
+            },
+            StringIrregularTrait::TryFrom => match derive_options.try_from {
+                Some(ref source_types) => Ok(gen_impl_trait_try_from_types(
+                    type_name,
+                    generics,
+                    inner_type,
+                    source_types,
+                    maybe_error_type_name,
+                )),
+                None => Ok(gen_impl_try_from(type_name, generics, inner_type, maybe_error_type_name)),
+            },
+            StringIrregularTrait::Borrow if derefs_to_str => Ok(gen_impl_trait_borrow(type_name, generics, quote!(str))),
+            StringIrregularTrait::Borrow => Ok(gen_impl_borrow_str_and_string(type_name, generics, inner_type)),
+            StringIrregularTrait::Display => Ok(gen_impl_trait_display(type_name, generics, derive_options.display.as_ref(), maybe_store_as)),
             StringIrregularTrait::Default => match maybe_default_value {
                 Some(ref default_value) => {
                     let has_validation = maybe_error_type_name.is_some();
@@ -209,83 +323,143 @@ fn gen_implemented_traits(
                     Err(syn::Error::new(span, msg))
                 }
             },
-            StringIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics)),
+            StringIrregularTrait::SerdeSerialize => Ok(gen_impl_trait_serde_serialize(type_name, generics, &derive_options.serde, maybe_store_as)),
             StringIrregularTrait::SerdeDeserialize => Ok(gen_impl_trait_serde_deserialize(
                 type_name,
                 generics,
-                inner_type,
-                maybe_error_type_name,
+                inner_type.clone(),
+                maybe_error_type_name, &derive_options.serde,
+                derive_options
+                    .serde_borrow
+                    .and_then(|_| serde_borrow::gen_serde_borrow(inner_type))
+                    .as_ref(),
+                derive_options.profile.denies_unknown_fields(),
             )),
             StringIrregularTrait::ArbitraryArbitrary => {
                 arbitrary::gen_impl_trait_arbitrary(type_name, guard)
             }
+            StringIrregularTrait::ValidatorValidate => {
+                Ok(gen_impl_trait_validator_validate(type_name, generics))
+            }
+            StringIrregularTrait::BorshSerialize => {
+                Ok(gen_impl_trait_borsh_serialize(type_name, generics, maybe_store_as))
+            }
+            StringIrregularTrait::BorshDeserialize => Ok(gen_impl_trait_borsh_deserialize(
+                type_name,
+                generics,
+                inner_type.clone(),
+                maybe_error_type_name,
+            )),
+            StringIrregularTrait::RkyvArchive => {
+                gen_impl_trait_rkyv(type_name, generics, inner_type.clone(), maybe_error_type_name)
+            }
+            StringIrregularTrait::ClapValueParserFactory => {
+                Ok(gen_impl_trait_clap_value_parser_factory(
+                    type_name,
+                    generics,
+                    inner_type.clone(),
+                    maybe_error_type_name,
+                ))
+            }
         })
         .collect()
 }
 
 fn gen_impl_from_str(
     type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &StringInnerType,
     maybe_error_type_name: Option<&ErrorTypePath>,
 ) -> TokenStream {
+    let generics_without_bounds = strip_trait_bounds_on_generics(generics);
+
+    // A borrowed `&str` can not outlive `from_str()`, so `Cow` types take ownership of it.
+    // NOTE: `FromStr` is not allowed for `&'a str` inner type.
+    let raw_value = match inner_type {
+        StringInnerType::String | StringInnerType::Str { .. } => quote!(raw_string),
+        StringInnerType::Cow { .. } => quote!(String::from(raw_string)),
+    };
+
     if let Some(error_type_name) = maybe_error_type_name {
         quote! {
-            impl core::str::FromStr for #type_name {
+            impl #generics core::str::FromStr for #type_name #generics_without_bounds {
                 type Err = #error_type_name;
 
                 #[inline]
                 fn from_str(raw_string: &str) -> ::core::result::Result<Self, Self::Err> {
-                    #type_name::try_new(raw_string)
+                    #type_name::try_new(#raw_value)
                 }
             }
         }
     } else {
         quote! {
-            impl core::str::FromStr for #type_name {
+            impl #generics core::str::FromStr for #type_name #generics_without_bounds {
                 type Err = ::core::convert::Infallible;
 
                 #[inline]
                 fn from_str(raw_string: &str) -> ::core::result::Result<Self, Self::Err> {
-                    Ok(#type_name::new(raw_string))
+                    Ok(#type_name::new(#raw_value))
                 }
             }
         }
     }
 }
 
-fn gen_impl_from_str_and_string(type_name: &TypeName) -> TokenStream {
-    let generics = Generics::default();
-    let impl_from_string = gen_impl_trait_from(type_name, &generics, quote!(String));
-    let impl_from_str = gen_impl_trait_from(type_name, &generics, quote!(&str));
-
-    quote! {
-        #impl_from_string
-        #impl_from_str
+/// Types that a string based newtype can be created from:
+/// * `String` and `&str` for `String`
+/// * `&'a str` for `&'a str`
+/// * `Cow<'a, str>`, `String` and `&'a str` for `Cow<'a, str>`
+fn source_types(inner_type: &StringInnerType) -> Vec<TokenStream> {
+    match inner_type {
+        StringInnerType::String => vec![quote!(String), quote!(&str)],
+        StringInnerType::Str { lifetime } => vec![quote!(&#lifetime str)],
+        StringInnerType::Cow { lifetime, .. } => {
+            vec![quote!(#inner_type), quote!(String), quote!(&#lifetime str)]
+        }
     }
 }
 
+fn gen_impl_from_str_and_string(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &StringInnerType,
+) -> TokenStream {
+    source_types(inner_type)
+        .into_iter()
+        .map(|source_type| gen_impl_trait_from(type_name, generics, source_type))
+        .collect()
+}
+
 fn gen_impl_try_from(
     type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &StringInnerType,
     maybe_error_type_name: Option<&ErrorTypePath>,
 ) -> TokenStream {
-    let generics = Generics::default();
-    let impl_try_from_string =
-        gen_impl_trait_try_from(type_name, &generics, quote!(String), maybe_error_type_name);
-    let impl_try_from_str =
-        gen_impl_trait_try_from(type_name, &generics, quote!(&str), maybe_error_type_name);
-
-    quote! {
-        #impl_try_from_string
-        #impl_try_from_str
-    }
+    source_types(inner_type)
+        .into_iter()
+        .map(|source_type| {
+            gen_impl_trait_try_from(type_name, generics, source_type, maybe_error_type_name)
+        })
+        .collect()
 }
 
-fn gen_impl_borrow_str_and_string(type_name: &TypeName) -> TokenStream {
-    let generics = Generics::default();
-    let impl_borrow_string = gen_impl_trait_borrow(type_name, &generics, quote!(String));
-    let impl_borrow_str = gen_impl_trait_borrow(type_name, &generics, quote!(str));
+fn gen_impl_borrow_str_and_string(
+    type_name: &TypeName,
+    generics: &Generics,
+    inner_type: &StringInnerType,
+) -> TokenStream {
+    let impl_borrow_str = gen_impl_trait_borrow(type_name, generics, quote!(str));
+    let impl_borrow_inner = match inner_type {
+        StringInnerType::String | StringInnerType::Cow { .. } => {
+            gen_impl_trait_borrow(type_name, generics, inner_type)
+        }
+        // Borrow<&'a str> would not be of much use
+        StringInnerType::Str { .. } => quote!(),
+    };
 
     quote! {
-        #impl_borrow_string
+        #impl_borrow_inner
         #impl_borrow_str
     }
 }
@@ -0,0 +1,58 @@
+use quote::quote;
+
+use crate::{common::gen::traits::SerdeBorrow, string::models::StringInnerType};
+
+/// Build the borrowing deserialization for `&'a str` and `Cow<'a, str>` inner types.
+///
+/// `Cow` implementation of `Deserialize` in serde always allocates, so a borrowed `Cow` is obtained
+/// with a helper type, that takes the borrowed string if the deserializer can provide one.
+pub fn gen_serde_borrow(inner_type: &StringInnerType) -> Option<SerdeBorrow> {
+    match inner_type {
+        StringInnerType::String => None,
+        StringInnerType::Str { lifetime } => Some(SerdeBorrow {
+            lifetime: lifetime.clone(),
+            raw_type: quote!(&#lifetime str),
+            helpers: quote!(),
+            into_inner: quote!(raw_value),
+        }),
+        StringInnerType::Cow { path, lifetime } => {
+            let helpers = quote! {
+                struct __BorrowedStr<#lifetime>(#path<#lifetime, str>);
+
+                impl<'de: #lifetime, #lifetime> ::serde::Deserialize<'de> for __BorrowedStr<#lifetime> {
+                    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                        struct __StrVisitor<#lifetime>(::core::marker::PhantomData<&#lifetime ()>);
+
+                        impl<'de: #lifetime, #lifetime> ::serde::de::Visitor<'de> for __StrVisitor<#lifetime> {
+                            type Value = __BorrowedStr<#lifetime>;
+
+                            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                                formatter.write_str("a string")
+                            }
+
+                            fn visit_borrowed_str<E: ::serde::de::Error>(self, value: &'de str) -> ::core::result::Result<Self::Value, E> {
+                                Ok(__BorrowedStr(#path::Borrowed(value)))
+                            }
+
+                            fn visit_str<E: ::serde::de::Error>(self, value: &str) -> ::core::result::Result<Self::Value, E> {
+                                Ok(__BorrowedStr(#path::Owned(value.into())))
+                            }
+
+                            fn visit_string<E: ::serde::de::Error>(self, value: String) -> ::core::result::Result<Self::Value, E> {
+                                Ok(__BorrowedStr(#path::Owned(value)))
+                            }
+                        }
+
+                        deserializer.deserialize_str(__StrVisitor(::core::marker::PhantomData))
+                    }
+                }
+            };
+            Some(SerdeBorrow {
+                lifetime: lifetime.clone(),
+                raw_type: quote!(__BorrowedStr<#lifetime>),
+                helpers,
+                into_inner: quote!(raw_value.0),
+            })
+        }
+    }
+}
@@ -5,9 +5,15 @@ pub mod validate;
 
 use std::collections::HashSet;
 
-use crate::common::{
-    gen::GenerateNewtype,
-    models::{Attributes, GenerateParams, Newtype, SpannedDeriveTrait, TypeName},
+use crate::{
+    any::models::AnyInnerType,
+    common::{
+        gen::{optional::gen_optional_nutype, GenerateNewtype},
+        models::{
+            Attributes, DeriveOptions, DeriveTrait, GenerateParams, Guard, Newtype,
+            SpannedDeriveTrait, TypeName,
+        },
+    },
 };
 
 use models::{StringDeriveTrait, StringSanitizer, StringValidator};
@@ -29,15 +35,18 @@ impl Newtype for StringNewtype {
     fn parse_attributes(
         attrs: TokenStream,
         type_name: &TypeName,
+        inner_type: &StringInnerType,
     ) -> Result<Attributes<StringGuard, SpannedDeriveTrait>, syn::Error> {
-        parse::parse_attributes(attrs, type_name)
+        parse::parse_attributes(attrs, type_name, inner_type)
     }
 
     fn validate(
         guard: &StringGuard,
         derive_traits: Vec<SpannedDeriveTrait>,
+        inner_type: &StringInnerType,
+        derive_options: &DeriveOptions,
     ) -> Result<HashSet<Self::TypedTrait>, syn::Error> {
-        validate_string_derive_traits(guard, derive_traits)
+        validate_string_derive_traits(guard, derive_traits, inner_type, derive_options)
     }
 
     fn generate(
@@ -45,4 +54,16 @@ impl Newtype for StringNewtype {
     ) -> Result<TokenStream, syn::Error> {
         StringNewtype::gen_nutype(params)
     }
+
+    fn generate_optional(
+        params: GenerateParams<
+            StringInnerType,
+            DeriveTrait,
+            Guard<Self::Sanitizer, Self::Validator>,
+        >,
+        optional_type: &AnyInnerType,
+        is_required: bool,
+    ) -> Result<TokenStream, syn::Error> {
+        gen_optional_nutype::<StringNewtype>(params, optional_type, is_required)
+    }
 }
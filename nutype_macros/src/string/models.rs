@@ -3,20 +3,29 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use crate::common::models::{
-    Guard, RawGuard, SpannedItem, TypeTrait, TypedCustomFunction, ValueOrExpr,
+    Guard, RawGuard, SpannedItem, TrySanitizer, TypeTrait, TypedCustomFunction, ValueOrExpr,
 };
 
 // Sanitizer
 
 pub type SpannedStringSanitizer = SpannedItem<StringSanitizer>;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Kinded)]
 #[kinded(display = "snake_case")]
 pub enum StringSanitizer {
     Trim,
     Lowercase,
     Uppercase,
+    CanonicalizeNumber,
+    #[cfg_attr(not(feature = "unicode"), allow(dead_code))]
+    SnakeCase,
+    #[cfg_attr(not(feature = "unicode"), allow(dead_code))]
+    KebabCase,
+    #[cfg_attr(not(feature = "unicode"), allow(dead_code))]
+    Slugify,
     With(TypedCustomFunction),
+    TryWith(TrySanitizer),
 }
 
 // Validator
@@ -29,7 +38,26 @@ pub type SpannedStringValidator = SpannedItem<StringValidator>;
 pub enum StringValidator {
     LenCharMin(ValueOrExpr<usize>),
     LenCharMax(ValueOrExpr<usize>),
+    LenByteMin(ValueOrExpr<usize>),
+    LenByteMax(ValueOrExpr<usize>),
+    #[cfg_attr(not(feature = "unicode-segmentation"), allow(dead_code))]
+    LenGraphemeMin(ValueOrExpr<usize>),
+    #[cfg_attr(not(feature = "unicode-segmentation"), allow(dead_code))]
+    LenGraphemeMax(ValueOrExpr<usize>),
     NotEmpty,
+    Lowercase,
+    Uppercase,
+    Alphanumeric,
+    Ascii,
+    NoWhitespace,
+    Hostname,
+    StartsWith(syn::Expr),
+    NotStartsWith(syn::Expr),
+    EndsWith(syn::Expr),
+    NotEndsWith(syn::Expr),
+    Contains(syn::Expr),
+    NotContains(syn::Expr),
+    In(Vec<syn::Expr>),
     Predicate(TypedCustomFunction),
     #[cfg_attr(not(feature = "regex"), allow(dead_code))]
     Regex(RegexDef),
@@ -54,6 +82,7 @@ pub enum StringDeriveTrait {
     // Standard
     Debug,
     Clone,
+    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -75,22 +104,63 @@ pub enum StringDeriveTrait {
     SerdeDeserialize,
     SchemarsJsonSchema,
     ArbitraryArbitrary,
+    ValidatorValidate,
+    BorshSerialize,
+    BorshDeserialize,
+    RkyvArchive,
+    ClapValueParserFactory,
 }
 
 impl TypeTrait for StringDeriveTrait {
     fn is_from_str(&self) -> bool {
         self == &Self::FromStr
     }
+
+    fn is_debug(&self) -> bool {
+        self == &Self::Debug
+    }
+
+    fn is_display(&self) -> bool {
+        self == &Self::Display
+    }
+
+    fn is_json_schema(&self) -> bool {
+        self == &Self::SchemarsJsonSchema
+    }
+
+    fn is_rkyv_archive(&self) -> bool {
+        self == &Self::RkyvArchive
+    }
 }
 
 pub type StringRawGuard = RawGuard<SpannedStringSanitizer, SpannedStringValidator>;
 pub type StringGuard = Guard<StringSanitizer, StringValidator>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct StringInnerType;
+/// The inner type of a string based newtype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringInnerType {
+    /// `String`
+    String,
+
+    /// `&'a str`
+    Str { lifetime: syn::Lifetime },
+
+    /// `Cow<'a, str>`.
+    /// The path is kept as it's written by the user (e.g. `Cow` or `std::borrow::Cow`), but
+    /// without generic arguments, so it can be used to refer to the enum variants.
+    Cow {
+        path: syn::Path,
+        lifetime: syn::Lifetime,
+    },
+}
 
 impl ToTokens for StringInnerType {
     fn to_tokens(&self, token_stream: &mut TokenStream) {
-        quote!(String).to_tokens(token_stream);
+        match self {
+            Self::String => quote!(String),
+            Self::Str { lifetime } => quote!(&#lifetime str),
+            Self::Cow { path, lifetime } => quote!(#path<#lifetime, str>),
+        }
+        .to_tokens(token_stream);
     }
 }
@@ -1,19 +1,25 @@
 use crate::{
     common::{
-        models::{Attributes, SpannedDeriveTrait, SpannedItem, TypeName},
+        models::{Attributes, CustomFunction, SpannedDeriveTrait, SpannedItem, StoreAs, TypeName},
         parse::{
-            parse_number_or_expr, parse_sanitizer_kind, parse_typed_custom_function_raw,
-            parse_validator_kind, ParseableAttributes,
+            parse_allowed_values, parse_len_or_expr, parse_sanitizer_kind, parse_try_sanitizer,
+            parse_typed_custom_function_raw, parse_validator_kind, ParseableAttributes,
+            ValidatorPresets,
         },
+        validate::{validate_as_variants, validate_nohash},
+    },
+    string::models::{
+        StringGuard, StringInnerType, StringRawGuard, StringSanitizer, StringValidator,
     },
-    string::models::{StringGuard, StringRawGuard, StringSanitizer, StringValidator},
 };
 use cfg_if::cfg_if;
 use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
+    parse_quote,
     spanned::Spanned,
-    LitStr, Path, Token,
+    GenericArgument, LitStr, Path, PathArguments, Token,
 };
 
 use super::{
@@ -21,12 +27,13 @@ use super::{
         RegexDef, SpannedStringSanitizer, SpannedStringValidator, StringSanitizerKind,
         StringValidatorKind,
     },
-    validate::validate_string_guard,
+    validate::{validate_string_default_value, validate_string_guard},
 };
 
 pub fn parse_attributes(
     input: TokenStream,
     type_name: &TypeName,
+    inner_type: &StringInnerType,
 ) -> Result<Attributes<StringGuard, SpannedDeriveTrait>, syn::Error> {
     let attrs: ParseableAttributes<SpannedStringSanitizer, SpannedStringValidator> =
         syn::parse2(input)?;
@@ -34,23 +41,277 @@ pub fn parse_attributes(
     let ParseableAttributes {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
         new_unchecked,
         default,
         derive_traits,
+        parse_money,
+        store_as,
+        storage,
+        as_variants,
+        bounded,
+        required,
+        trace,
+        secret,
+        arena,
+        guarantee,
+        derive_options,
     } = attrs;
+    validate_nohash(&derive_options, false)?;
+    if let Some(guarantee) = guarantee {
+        let msg = "`guarantee` is supported only by integer based types.";
+        return Err(syn::Error::new(guarantee, msg));
+    }
+    if let Some(bounded) = bounded {
+        let msg = "`bounded` is supported only by integer based types.";
+        return Err(syn::Error::new(bounded, msg));
+    }
+    if let Some(parse_money) = parse_money {
+        let msg = "`parse_money` is supported only by float based types and custom types (e.g. `Decimal`) that implement `FromStr`.";
+        return Err(syn::Error::new(parse_money.span(), msg));
+    }
+    if let Some(ffi) = derive_options.ffi {
+        if *inner_type != StringInnerType::String {
+            let msg = "`ffi` is supported only by `String` based string types.";
+            return Err(syn::Error::new(ffi, msg));
+        }
+    }
+    if let Some(wasm) = derive_options.wasm {
+        if *inner_type != StringInnerType::String {
+            let msg = "`wasm` is supported only by `String` based string types.";
+            return Err(syn::Error::new(wasm, msg));
+        }
+    }
+    if let Some(encoding) = &derive_options.encoding {
+        let msg = "`encoding` is supported only by `[u8; N]` based types.";
+        return Err(syn::Error::new(encoding.span(), msg));
+    }
+    if let Some(total_order) = derive_options.total_order {
+        let msg = "`total_order` is supported only by float based types.";
+        return Err(syn::Error::new(total_order, msg));
+    }
+    if let Some(bytes) = derive_options.bytes {
+        if *inner_type != StringInnerType::String {
+            let msg = "`bytes` is supported only by `String` based string types.";
+            return Err(syn::Error::new(bytes, msg));
+        }
+    }
+    if let Some(arena) = arena {
+        if !matches!(inner_type, StringInnerType::Str { .. }) {
+            let msg = "`arena` is supported only by `&'a str` based types.";
+            return Err(syn::Error::new(arena, msg));
+        }
+    }
+    if let Some(ref store_as) = store_as {
+        if *inner_type != StringInnerType::String {
+            let msg = "`store_as` is supported only by `String` based types.";
+            return Err(syn::Error::new(store_as.span(), msg));
+        }
+    }
+    if let (Some(_), Some(storage)) = (&store_as, &storage) {
+        let msg = "`storage` cannot be used together with `store_as`.";
+        return Err(syn::Error::new(storage.span(), msg));
+    }
     let raw_guard = StringRawGuard {
         sanitizers,
         validation,
+        legacy,
+        duplicates,
+    };
+    let guard = validate_string_guard(raw_guard, type_name, inner_type)?;
+    let store_as = match (store_as, storage) {
+        (Some(store_as), _) => Some(store_as.item),
+        (None, Some(storage)) => Some(gen_storage_store_as(storage, inner_type, &guard)?),
+        (None, None) => None,
     };
-    let guard = validate_string_guard(raw_guard, type_name)?;
+    if let Some(ref default_value) = default {
+        validate_string_default_value(&guard, default_value)?;
+    }
+    let as_variants = as_variants
+        .map(|span| {
+            let maybe_allowed_values = guard.standard_validators().and_then(|validators| {
+                validators.iter().find_map(|validator| match validator {
+                    StringValidator::In(allowed_values) => Some(allowed_values.as_slice()),
+                    _ => None,
+                })
+            });
+            validate_as_variants(span, maybe_allowed_values)
+        })
+        .transpose()?;
     Ok(Attributes {
         new_unchecked,
         guard,
         default,
         derive_traits,
+        parse_money: None,
+        store_as,
+        as_variants,
+        required,
+        trace: trace.is_some(),
+        secret,
+        arena: arena.is_some(),
+        guarantee: false,
+        derive_options,
     })
 }
 
+/// With `storage = Box<str>` or `storage = Arc<str>` the sanitized and validated `String` is
+/// converted into the storage type, which keeps no spare capacity.
+/// With `storage = heapless::String` or `storage = arrayvec::ArrayString` the value is kept inline,
+/// in a buffer sized by the declared maximal length. The constructors still take a `String`
+/// and the value is decoded into a new `String`.
+fn gen_storage_store_as(
+    storage: SpannedItem<syn::Type>,
+    inner_type: &StringInnerType,
+    guard: &StringGuard,
+) -> Result<StoreAs, syn::Error> {
+    if *inner_type != StringInnerType::String {
+        let msg = "`storage` is supported only by `String` based types.";
+        return Err(syn::Error::new(storage.span(), msg));
+    }
+    if is_str_pointer(&storage.item) {
+        return Ok(StoreAs {
+            storage_type: storage.item,
+            encode: CustomFunction::Path(parse_quote!(::core::convert::From::from)),
+            decode: CustomFunction::Path(parse_quote!(str::to_owned)),
+            derefs_to_str: true,
+        });
+    }
+    let Some(inline_storage) = InlineStorage::detect(&storage.item) else {
+        let msg = "`storage` supports only `Box<str>`, `Arc<str>`, `heapless::String` and `arrayvec::ArrayString`.";
+        return Err(syn::Error::new(storage.span(), msg));
+    };
+    if !inline_storage.is_enabled() {
+        let msg = format!(
+            "To use `storage = {}`, the feature `{}` of the crate `nutype` must be enabled.",
+            inline_storage.name(),
+            inline_storage.feature()
+        );
+        return Err(syn::Error::new(storage.span(), msg));
+    }
+    let syn::Type::Path(mut storage_type) = storage.item else {
+        unreachable!("inline storage is always a path");
+    };
+    let last_segment = storage_type
+        .path
+        .segments
+        .last_mut()
+        .expect("path is not empty");
+    if !last_segment.arguments.is_empty() {
+        let msg = format!(
+            "The capacity of `storage = {}` is derived from `len_byte_max` or `len_char_max`.\nRemove the generic arguments.",
+            inline_storage.name()
+        );
+        return Err(syn::Error::new(last_segment.arguments.span(), msg));
+    }
+
+    // A char takes up to 4 bytes in UTF-8, so `len_byte_max` gives a tighter capacity.
+    let validators = guard.standard_validators().map_or(&[][..], Vec::as_slice);
+    let maybe_len_byte_max = validators.iter().find_map(|validator| match validator {
+        StringValidator::LenByteMax(max_len) => Some(quote!(#max_len)),
+        _ => None,
+    });
+    let maybe_len_char_max = validators.iter().find_map(|validator| match validator {
+        StringValidator::LenCharMax(max_len) => Some(quote!(4 * #max_len)),
+        _ => None,
+    });
+    let Some(capacity) = maybe_len_byte_max.or(maybe_len_char_max) else {
+        let msg = format!(
+            "`storage = {}` requires `len_byte_max` or `len_char_max` validator, that limits the capacity.",
+            inline_storage.name()
+        );
+        return Err(syn::Error::new(storage_type.span(), msg));
+    };
+    last_segment.arguments = PathArguments::AngleBracketed(parse_quote!(<{ #capacity }>));
+
+    // The value is encoded only after validation, so it has at most `len_byte_max` bytes or
+    // `len_char_max` chars of at most 4 bytes each, which is exactly the capacity: the conversion
+    // can not fail.
+    let encode: syn::ExprClosure = parse_quote!(
+        |value: String| -> #storage_type {
+            ::core::convert::TryFrom::try_from(value.as_str()).unwrap_or_else(|_| {
+                unreachable!("The length of a valid value never exceeds the capacity of the storage")
+            })
+        }
+    );
+    Ok(StoreAs {
+        storage_type: syn::Type::Path(storage_type),
+        encode: CustomFunction::Closure(encode),
+        decode: CustomFunction::Path(parse_quote!(str::to_owned)),
+        derefs_to_str: true,
+    })
+}
+
+/// Fixed-capacity string types, that keep the value inline.
+#[derive(Debug, Clone, Copy)]
+enum InlineStorage {
+    /// `heapless::String<N>`
+    Heapless,
+    /// `arrayvec::ArrayString<N>`
+    ArrayVec,
+}
+
+impl InlineStorage {
+    /// Recognize `heapless::String` and `arrayvec::ArrayString` (or just `ArrayString`).
+    fn detect(tp: &syn::Type) -> Option<Self> {
+        let syn::Type::Path(type_path) = tp else {
+            return None;
+        };
+        let segments = &type_path.path.segments;
+        let last = segments.last()?;
+        let is_heapless = segments.len() >= 2 && segments[segments.len() - 2].ident == "heapless";
+        if last.ident == "String" && is_heapless {
+            Some(Self::Heapless)
+        } else if last.ident == "ArrayString" {
+            Some(Self::ArrayVec)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Heapless => "heapless::String",
+            Self::ArrayVec => "arrayvec::ArrayString",
+        }
+    }
+
+    fn feature(self) -> &'static str {
+        match self {
+            Self::Heapless => "heapless",
+            Self::ArrayVec => "arrayvec",
+        }
+    }
+
+    fn is_enabled(self) -> bool {
+        match self {
+            Self::Heapless => cfg!(feature = "heapless"),
+            Self::ArrayVec => cfg!(feature = "arrayvec"),
+        }
+    }
+}
+
+/// Is it `Box<str>` or `Arc<str>` (with any path, e.g. `std::sync::Arc<str>`)?
+fn is_str_pointer(tp: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = tp else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != "Box" && segment.ident != "Arc" {
+        return false;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => matches!(
+            &args.args[0],
+            GenericArgument::Type(syn::Type::Path(arg)) if arg.path.is_ident("str")
+        ),
+        _ => false,
+    }
+}
+
 impl Parse for SpannedStringSanitizer {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let (kind, ident) = parse_sanitizer_kind(input)?;
@@ -68,6 +329,32 @@ impl Parse for SpannedStringSanitizer {
                 item: StringSanitizer::Uppercase,
                 span: ident.span(),
             }),
+            StringSanitizerKind::CanonicalizeNumber => Ok(SpannedStringSanitizer {
+                item: StringSanitizer::CanonicalizeNumber,
+                span: ident.span(),
+            }),
+            StringSanitizerKind::SnakeCase
+            | StringSanitizerKind::KebabCase
+            | StringSanitizerKind::Slugify => {
+                cfg_if! {
+                    if #[cfg(feature = "unicode")] {
+                        let item = match kind {
+                            StringSanitizerKind::SnakeCase => StringSanitizer::SnakeCase,
+                            StringSanitizerKind::KebabCase => StringSanitizer::KebabCase,
+                            _ => StringSanitizer::Slugify,
+                        };
+                        Ok(SpannedStringSanitizer {
+                            item,
+                            span: ident.span(),
+                        })
+                    } else {
+                        let msg = format!(
+                            "To use `{kind}` sanitizer, the feature `unicode` of the crate `nutype` must be enabled."
+                        );
+                        Err(syn::Error::new(ident.span(), msg))
+                    }
+                }
+            }
             StringSanitizerKind::With => {
                 let _eq: Token![=] = input.parse()?;
                 let (typed_custom_function, span) =
@@ -77,10 +364,19 @@ impl Parse for SpannedStringSanitizer {
                     span,
                 })
             }
+            StringSanitizerKind::TryWith => {
+                let (try_sanitizer, span) = parse_try_sanitizer(input)?;
+                Ok(SpannedStringSanitizer {
+                    item: StringSanitizer::TryWith(try_sanitizer),
+                    span,
+                })
+            }
         }
     }
 }
 
+impl ValidatorPresets for SpannedStringValidator {}
+
 impl Parse for SpannedStringValidator {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let (kind, ident) = parse_validator_kind(input)?;
@@ -88,7 +384,7 @@ impl Parse for SpannedStringValidator {
         match kind {
             StringValidatorKind::LenCharMin => {
                 let _: Token![=] = input.parse()?;
-                let (min_len, span) = parse_number_or_expr::<usize>(input)?;
+                let (min_len, span) = parse_len_or_expr(input)?;
                 Ok(SpannedStringValidator {
                     item: StringValidator::LenCharMin(min_len),
                     span,
@@ -96,16 +392,118 @@ impl Parse for SpannedStringValidator {
             }
             StringValidatorKind::LenCharMax => {
                 let _: Token![=] = input.parse()?;
-                let (max_len, span) = parse_number_or_expr::<usize>(input)?;
+                let (max_len, span) = parse_len_or_expr(input)?;
                 Ok(SpannedStringValidator {
                     item: StringValidator::LenCharMax(max_len),
                     span,
                 })
             }
+            StringValidatorKind::LenByteMin => {
+                let _: Token![=] = input.parse()?;
+                let (min_len, span) = parse_len_or_expr(input)?;
+                Ok(SpannedStringValidator {
+                    item: StringValidator::LenByteMin(min_len),
+                    span,
+                })
+            }
+            StringValidatorKind::LenByteMax => {
+                let _: Token![=] = input.parse()?;
+                let (max_len, span) = parse_len_or_expr(input)?;
+                Ok(SpannedStringValidator {
+                    item: StringValidator::LenByteMax(max_len),
+                    span,
+                })
+            }
+            StringValidatorKind::LenGraphemeMin | StringValidatorKind::LenGraphemeMax => {
+                cfg_if! {
+                    if #[cfg(feature = "unicode-segmentation")] {
+                        let _: Token![=] = input.parse()?;
+                        let (len, span) = parse_len_or_expr(input)?;
+                        let item = if kind == StringValidatorKind::LenGraphemeMin {
+                            StringValidator::LenGraphemeMin(len)
+                        } else {
+                            StringValidator::LenGraphemeMax(len)
+                        };
+                        Ok(SpannedStringValidator { item, span })
+                    } else {
+                        let msg = format!(
+                            concat!(
+                                "To validate length of strings in graphemes with `{}`, the feature `unicode-segmentation` of the crate `nutype` must be enabled.\n",
+                                "IMPORTANT: Make sure that your crate EXPLICITLY depends on the `unicode-segmentation` crate.",
+                            ),
+                            kind,
+                        );
+                        Err(syn::Error::new(ident.span(), msg))
+                    }
+                }
+            }
             StringValidatorKind::NotEmpty => Ok(SpannedStringValidator {
                 item: StringValidator::NotEmpty,
                 span: ident.span(),
             }),
+            StringValidatorKind::Lowercase => Ok(SpannedStringValidator {
+                item: StringValidator::Lowercase,
+                span: ident.span(),
+            }),
+            StringValidatorKind::Uppercase => Ok(SpannedStringValidator {
+                item: StringValidator::Uppercase,
+                span: ident.span(),
+            }),
+            StringValidatorKind::Alphanumeric => Ok(SpannedStringValidator {
+                item: StringValidator::Alphanumeric,
+                span: ident.span(),
+            }),
+            StringValidatorKind::Ascii => Ok(SpannedStringValidator {
+                item: StringValidator::Ascii,
+                span: ident.span(),
+            }),
+            StringValidatorKind::NoWhitespace => Ok(SpannedStringValidator {
+                item: StringValidator::NoWhitespace,
+                span: ident.span(),
+            }),
+            StringValidatorKind::Hostname => Ok(SpannedStringValidator {
+                item: StringValidator::Hostname,
+                span: ident.span(),
+            }),
+            StringValidatorKind::StartsWith
+            | StringValidatorKind::NotStartsWith
+            | StringValidatorKind::EndsWith
+            | StringValidatorKind::NotEndsWith
+            | StringValidatorKind::Contains
+            | StringValidatorKind::NotContains => {
+                let _eq: Token![=] = input.parse()?;
+                let fragment: syn::Expr = input.parse()?;
+                let span = fragment.span();
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) = &fragment
+                {
+                    if lit_str.value().is_empty() {
+                        let msg = format!(
+                            "`{kind}` requires a non-empty fragment, e.g. `{kind} = \"+\"`."
+                        );
+                        return Err(syn::Error::new(span, msg));
+                    }
+                }
+                let item = match kind {
+                    StringValidatorKind::StartsWith => StringValidator::StartsWith(fragment),
+                    StringValidatorKind::NotStartsWith => StringValidator::NotStartsWith(fragment),
+                    StringValidatorKind::EndsWith => StringValidator::EndsWith(fragment),
+                    StringValidatorKind::NotEndsWith => StringValidator::NotEndsWith(fragment),
+                    StringValidatorKind::Contains => StringValidator::Contains(fragment),
+                    _ => StringValidator::NotContains(fragment),
+                };
+                Ok(SpannedStringValidator { item, span })
+            }
+            StringValidatorKind::In => {
+                let _eq: Token![=] = input.parse()?;
+                let (allowed_values, span) = parse_allowed_values(input)?;
+                Ok(SpannedStringValidator {
+                    item: StringValidator::In(allowed_values),
+                    span,
+                })
+            }
             StringValidatorKind::Predicate => {
                 let _eq: Token![=] = input.parse()?;
                 let (typed_custom_function, span) = parse_typed_custom_function_raw(input, "&str")?;
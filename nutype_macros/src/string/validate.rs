@@ -5,26 +5,171 @@ use proc_macro2::Span;
 
 use crate::{
     common::{
-        models::{DeriveTrait, SpannedDeriveTrait, TypeName, ValueOrExpr},
-        validate::{validate_duplicates, validate_guard, validate_traits_from_xor_try_from},
+        models::{DeriveOptions, DeriveTrait, SpannedDeriveTrait, TypeName, ValueOrExpr},
+        validate::{
+            invalid_default_value_error, unsupported_collection_trait, validate_duplicates,
+            validate_guard, validate_sanitizer_duplicates, validate_serde_borrow,
+            validate_traits_from_xor_try_from,
+        },
+    },
+    string::models::{
+        StringGuard, StringInnerType, StringRawGuard, StringSanitizer, StringValidator,
     },
-    string::models::{StringGuard, StringRawGuard, StringSanitizer, StringValidator},
 };
 
 use super::models::{
     SpannedStringSanitizer, SpannedStringValidator, StringDeriveTrait, StringSanitizerKind,
+    StringValidatorKind,
 };
 
 pub fn validate_string_guard(
     raw_guard: StringRawGuard,
     type_name: &TypeName,
+    inner_type: &StringInnerType,
 ) -> Result<StringGuard, syn::Error> {
-    validate_guard(
-        raw_guard,
-        type_name,
-        validate_validators,
-        validate_sanitizers,
-    )
+    validate_guard(raw_guard, type_name, validate_validators, |sanitizers| {
+        validate_sanitizers(sanitizers, inner_type)
+    })
+}
+
+/// Check a string literal default value against the validators at compile time.
+/// Standard sanitizers are applied to the literal first. The check is skipped for custom sanitizers,
+/// and validators that can not be evaluated by the macro (e.g. `predicate` or `regex`) are ignored.
+pub fn validate_string_default_value(
+    guard: &StringGuard,
+    default_value: &syn::Expr,
+) -> Result<(), syn::Error> {
+    let Some(validators) = guard.standard_validators() else {
+        return Ok(());
+    };
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit_str),
+        ..
+    }) = default_value
+    else {
+        return Ok(());
+    };
+
+    let mut value = lit_str.value();
+    for sanitizer in guard.sanitizers() {
+        value = match sanitizer {
+            StringSanitizer::Trim => value.trim().to_string(),
+            StringSanitizer::Lowercase => value.to_lowercase(),
+            StringSanitizer::Uppercase => value.to_uppercase(),
+            StringSanitizer::CanonicalizeNumber
+            | StringSanitizer::SnakeCase
+            | StringSanitizer::KebabCase
+            | StringSanitizer::Slugify
+            | StringSanitizer::With(_)
+            | StringSanitizer::TryWith(_) => return Ok(()),
+        };
+    }
+
+    let len_char = value.chars().count();
+    let len_byte = value.len();
+    for validator in validators {
+        let violated_validator = match validator {
+            StringValidator::LenCharMin(ValueOrExpr::Value(min)) if len_char < *min => {
+                "len_char_min"
+            }
+            StringValidator::LenCharMax(ValueOrExpr::Value(max)) if len_char > *max => {
+                "len_char_max"
+            }
+            StringValidator::LenByteMin(ValueOrExpr::Value(min)) if len_byte < *min => {
+                "len_byte_min"
+            }
+            StringValidator::LenByteMax(ValueOrExpr::Value(max)) if len_byte > *max => {
+                "len_byte_max"
+            }
+            StringValidator::NotEmpty if value.is_empty() => "not_empty",
+            StringValidator::Lowercase if value.chars().any(char::is_uppercase) => "lowercase",
+            StringValidator::Uppercase if value.chars().any(char::is_lowercase) => "uppercase",
+            StringValidator::Alphanumeric if !value.chars().all(char::is_alphanumeric) => {
+                "alphanumeric"
+            }
+            StringValidator::Ascii if !value.is_ascii() => "ascii",
+            StringValidator::NoWhitespace if value.chars().any(char::is_whitespace) => {
+                "no_whitespace"
+            }
+            StringValidator::Hostname if !is_hostname(&value) => "hostname",
+            StringValidator::StartsWith(fragment)
+                if str_literal(fragment).is_some_and(|fragment| !value.starts_with(&fragment)) =>
+            {
+                "starts_with"
+            }
+            StringValidator::NotStartsWith(fragment)
+                if str_literal(fragment).is_some_and(|fragment| value.starts_with(&fragment)) =>
+            {
+                "not_starts_with"
+            }
+            StringValidator::EndsWith(fragment)
+                if str_literal(fragment).is_some_and(|fragment| !value.ends_with(&fragment)) =>
+            {
+                "ends_with"
+            }
+            StringValidator::NotEndsWith(fragment)
+                if str_literal(fragment).is_some_and(|fragment| value.ends_with(&fragment)) =>
+            {
+                "not_ends_with"
+            }
+            StringValidator::Contains(fragment)
+                if str_literal(fragment).is_some_and(|fragment| !value.contains(&fragment)) =>
+            {
+                "contains"
+            }
+            StringValidator::NotContains(fragment)
+                if str_literal(fragment).is_some_and(|fragment| value.contains(&fragment)) =>
+            {
+                "not_contains"
+            }
+            StringValidator::In(allowed_values) if !is_allowed_value(&value, allowed_values) => {
+                "in"
+            }
+            _ => continue,
+        };
+        return Err(invalid_default_value_error(
+            default_value,
+            &format!("{:?}", lit_str.value()),
+            violated_validator,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns `true` unless all the allowed values are string literals, and none of them is equal
+/// to the given value.
+fn is_allowed_value(value: &str, allowed_values: &[syn::Expr]) -> bool {
+    let literals: Option<Vec<String>> = allowed_values.iter().map(str_literal).collect();
+    match literals {
+        Some(literals) => literals.iter().any(|literal| literal == value),
+        None => true,
+    }
+}
+
+/// A hostname as defined by RFC 1123: at most 253 characters, dot separated labels of 1 to 63
+/// ASCII letters, digits and `-`, that neither start nor end with `-`.
+fn is_hostname(value: &str) -> bool {
+    value.len() <= 253
+        && value.split('.').all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// The value of a string literal, or `None` for other expressions (e.g. constants).
+fn str_literal(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => Some(lit_str.value()),
+        _ => None,
+    }
 }
 
 fn validate_validators(
@@ -36,30 +181,35 @@ fn validate_validators(
         format!("Duplicated validators `{kind}`.\nDon't worry, you still remain ingenious!")
     })?;
 
-    // len_char_max VS len_char_min
+    // len_char_max VS len_char_min, and the same for bytes and graphemes
+    //
+    validate_len_boundaries(
+        &validators,
+        StringValidatorKind::LenCharMin,
+        StringValidatorKind::LenCharMax,
+    )?;
+    validate_len_boundaries(
+        &validators,
+        StringValidatorKind::LenByteMin,
+        StringValidatorKind::LenByteMax,
+    )?;
+    validate_len_boundaries(
+        &validators,
+        StringValidatorKind::LenGraphemeMin,
+        StringValidatorKind::LenGraphemeMax,
+    )?;
+
+    // lowercase VS uppercase
     //
-    let maybe_len_char_min = validators
+    let lowercase = validators
         .iter()
-        .flat_map(|v| match v.item {
-            StringValidator::LenCharMin(ValueOrExpr::Value(len)) => Some((v.span, len)),
-            _ => None,
-        })
-        .next();
-    let maybe_len_char_max = validators
+        .find(|v| v.kind() == StringValidatorKind::Lowercase);
+    let uppercase = validators
         .iter()
-        .flat_map(|v| match v.item {
-            StringValidator::LenCharMax(ValueOrExpr::Value(len)) => Some((v.span, len)),
-            _ => None,
-        })
-        .next();
-    if let (Some((_, len_char_min)), Some((len_char_max_span, len_char_max))) =
-        (maybe_len_char_min, maybe_len_char_max)
-    {
-        if len_char_min > len_char_max {
-            let msg = "`len_char_min` cannot be greater than `len_char_max`.\nDon't you find this obvious?";
-            let err = syn::Error::new(len_char_max_span, msg);
-            return Err(err);
-        }
+        .find(|v| v.kind() == StringValidatorKind::Uppercase);
+    if let (Some(_), Some(uppercase)) = (lowercase, uppercase) {
+        let msg = "Validators `lowercase` and `uppercase` cannot be used together, because only strings without cased letters would pass both.";
+        return Err(syn::Error::new(uppercase.span, msg));
     }
 
     // Validate regex
@@ -75,10 +225,44 @@ fn validate_validators(
     Ok(validators)
 }
 
+/// Ensure that the lower length boundary is not greater than the upper one,
+/// if both are set with literals.
+fn validate_len_boundaries(
+    validators: &[SpannedStringValidator],
+    min_kind: StringValidatorKind,
+    max_kind: StringValidatorKind,
+) -> Result<(), syn::Error> {
+    let find_len = |kind: StringValidatorKind| {
+        validators
+            .iter()
+            .filter(|v| v.kind() == kind)
+            .flat_map(|v| match v.item {
+                StringValidator::LenCharMin(ValueOrExpr::Value(len))
+                | StringValidator::LenCharMax(ValueOrExpr::Value(len))
+                | StringValidator::LenByteMin(ValueOrExpr::Value(len))
+                | StringValidator::LenByteMax(ValueOrExpr::Value(len))
+                | StringValidator::LenGraphemeMin(ValueOrExpr::Value(len))
+                | StringValidator::LenGraphemeMax(ValueOrExpr::Value(len)) => Some((v.span, len)),
+                _ => None,
+            })
+            .next()
+    };
+    if let (Some((_, min)), Some((max_span, max))) = (find_len(min_kind), find_len(max_kind)) {
+        if min > max {
+            let msg = format!(
+                "`{min_kind}` cannot be greater than `{max_kind}`.\nDon't you find this obvious?"
+            );
+            return Err(syn::Error::new(max_span, msg));
+        }
+    }
+    Ok(())
+}
+
 fn validate_sanitizers(
     sanitizers: Vec<SpannedStringSanitizer>,
+    inner_type: &StringInnerType,
 ) -> Result<Vec<StringSanitizer>, syn::Error> {
-    validate_duplicates(&sanitizers, |kind| {
+    validate_sanitizer_duplicates(&sanitizers, |kind| {
         format!(
             "Duplicated sanitizer `{kind}`.\nYou're doing well, it's not that bad unless you forgot to call your mom!"
         )
@@ -102,6 +286,20 @@ fn validate_sanitizers(
         return Err(err);
     }
 
+    // A borrowed `&'a str` can only be narrowed down, but not replaced with a new string.
+    if let StringInnerType::Str { .. } = inner_type {
+        if let Some(sanitizer) = sanitizers
+            .iter()
+            .find(|s| s.kind() != StringSanitizerKind::Trim)
+        {
+            let msg = format!(
+                "Sanitizer `{}` produces a new string, so it cannot be used with `&str` inner type.\nConsider using `Cow<'a, str>` or `String` instead.",
+                sanitizer.kind()
+            );
+            return Err(syn::Error::new(sanitizer.span, msg));
+        }
+    }
+
     let sanitizers: Vec<StringSanitizer> = sanitizers.into_iter().map(|s| s.item).collect();
     Ok(sanitizers)
 }
@@ -109,15 +307,25 @@ fn validate_sanitizers(
 pub fn validate_string_derive_traits(
     guard: &StringGuard,
     spanned_derive_traits: Vec<SpannedDeriveTrait>,
+    inner_type: &StringInnerType,
+    derive_options: &DeriveOptions,
 ) -> Result<HashSet<StringDeriveTrait>, syn::Error> {
     validate_traits_from_xor_try_from(&spanned_derive_traits)?;
+    let is_borrowed = !matches!(inner_type, StringInnerType::String);
+    validate_serde_borrow(derive_options, is_borrowed)?;
+    let is_serde_borrow = derive_options.serde_borrow.is_some();
 
     let mut traits = HashSet::with_capacity(24);
     let has_validation = guard.has_validation();
 
     for spanned_trait in spanned_derive_traits {
-        let string_derive_trait =
-            to_string_derive_trait(spanned_trait.item, has_validation, spanned_trait.span)?;
+        let string_derive_trait = to_string_derive_trait(
+            spanned_trait.item,
+            has_validation,
+            inner_type,
+            is_serde_borrow,
+            spanned_trait.span,
+        )?;
         traits.insert(string_derive_trait);
     }
 
@@ -127,8 +335,13 @@ pub fn validate_string_derive_traits(
 fn to_string_derive_trait(
     tr: DeriveTrait,
     has_validation: bool,
+    inner_type: &StringInnerType,
+    is_serde_borrow: bool,
     span: Span,
 ) -> Result<StringDeriveTrait, syn::Error> {
+    let is_str = matches!(inner_type, StringInnerType::Str { .. });
+    let is_borrowed = !matches!(inner_type, StringInnerType::String);
+
     match tr {
         DeriveTrait::Debug => Ok(StringDeriveTrait::Debug),
         DeriveTrait::Display => Ok(StringDeriveTrait::Display),
@@ -138,19 +351,46 @@ fn to_string_derive_trait(
         DeriveTrait::Eq => Ok(StringDeriveTrait::Eq),
         DeriveTrait::PartialOrd => Ok(StringDeriveTrait::PartialOrd),
         DeriveTrait::Ord => Ok(StringDeriveTrait::Ord),
-        DeriveTrait::FromStr => Ok(StringDeriveTrait::FromStr),
+        DeriveTrait::FromStr => {
+            if is_str {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `FromStr` for `&str` inner type, because the parsed string does not live long enough.\nConsider using `Cow<'a, str>` instead.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::FromStr)
+            }
+        }
         DeriveTrait::AsRef => Ok(StringDeriveTrait::AsRef),
         DeriveTrait::Deref => Ok(StringDeriveTrait::Deref),
         DeriveTrait::Hash => Ok(StringDeriveTrait::Hash),
         DeriveTrait::Borrow => Ok(StringDeriveTrait::Borrow),
+        DeriveTrait::IntoIterator | DeriveTrait::Extend => {
+            Err(unsupported_collection_trait(tr, span))
+        }
         DeriveTrait::Into => Ok(StringDeriveTrait::Into),
         DeriveTrait::SerdeSerialize => Ok(StringDeriveTrait::SerdeSerialize),
-        DeriveTrait::SerdeDeserialize => Ok(StringDeriveTrait::SerdeDeserialize),
+        DeriveTrait::SerdeDeserialize => {
+            if is_str && !is_serde_borrow {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `Deserialize` for `&str` inner type without `serde(borrow)`.\nConsider using `serde(borrow)` or `Cow<'a, str>` instead.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::SerdeDeserialize)
+            }
+        }
         DeriveTrait::SchemarsJsonSchema => Ok(StringDeriveTrait::SchemarsJsonSchema),
-        DeriveTrait::Copy => Err(syn::Error::new(
-            span,
-            "Copy trait cannot be derived for a String based type",
-        )),
+        DeriveTrait::Copy => {
+            if is_str {
+                Ok(StringDeriveTrait::Copy)
+            } else {
+                Err(syn::Error::new(
+                    span,
+                    "Copy trait cannot be derived for a String based type",
+                ))
+            }
+        }
         DeriveTrait::From => {
             if has_validation {
                 Err(syn::Error::new(
@@ -162,7 +402,48 @@ fn to_string_derive_trait(
             }
         }
         DeriveTrait::TryFrom => Ok(StringDeriveTrait::TryFrom),
-        DeriveTrait::ArbitraryArbitrary => Ok(StringDeriveTrait::ArbitraryArbitrary),
+        DeriveTrait::ArbitraryArbitrary => {
+            if is_borrowed {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `Arbitrary` for a borrowed string type.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::ArbitraryArbitrary)
+            }
+        }
+        DeriveTrait::ValidatorValidate => Ok(StringDeriveTrait::ValidatorValidate),
+        DeriveTrait::BorshSerialize => Ok(StringDeriveTrait::BorshSerialize),
+        DeriveTrait::BorshDeserialize => {
+            if is_str {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `BorshDeserialize` for `&str` inner type, because the read string does not live long enough.\nConsider using `Cow<'a, str>` instead.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::BorshDeserialize)
+            }
+        }
+        DeriveTrait::RkyvArchive => {
+            if is_borrowed {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `Archive` for a borrowed string type.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::RkyvArchive)
+            }
+        }
+        DeriveTrait::ClapValueParserFactory => {
+            if is_borrowed {
+                Err(syn::Error::new(
+                    span,
+                    "#[nutype] cannot derive `ValueParserFactory` for a borrowed string type, because the parsed arguments must be `'static`.",
+                ))
+            } else {
+                Ok(StringDeriveTrait::ClapValueParserFactory)
+            }
+        }
     }
 }
 
@@ -0,0 +1,227 @@
+//! `#[derive(NutypeWrap)]` generates a copy of a struct with raw fields, where the fields marked
+//! with `#[wrap(Newtype)]` have newtype types, and the conversions between the two structs.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Data, DeriveInput, Fields, Path, Token,
+};
+
+use crate::common::{
+    gen::error::gen_impl_error_trait, models::ErrorTypePath, parse::is_doc_attribute,
+    validate::to_upper_camel_case,
+};
+
+/// Struct level `#[wrap(name = ValidUser, derive(Debug, Clone))]`.
+#[derive(Default)]
+struct WrapOptions {
+    name: Option<Ident>,
+    derive: Vec<Path>,
+}
+
+impl Parse for WrapOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = WrapOptions::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident == "name" {
+                let _eq: Token![=] = input.parse()?;
+                options.name = Some(input.parse()?);
+            } else if ident == "derive" {
+                let content;
+                syn::parenthesized!(content in input);
+                let traits = content.parse_terminated(Path::parse, Token![,])?;
+                options.derive = traits.into_iter().collect();
+            } else {
+                let msg = format!("Unknown `wrap` attribute `{ident}`.\nPossible attributes are `name` and `derive`.");
+                return Err(syn::Error::new(ident.span(), msg));
+            }
+            if input.is_empty() {
+                break;
+            }
+            let _comma: Token![,] = input.parse()?;
+        }
+        Ok(options)
+    }
+}
+
+/// A field of the raw struct.
+struct WrapField {
+    ident: Ident,
+    doc_attrs: Vec<Attribute>,
+    vis: syn::Visibility,
+    ty: syn::Type,
+    /// The newtype set with `#[wrap(Newtype)]`
+    maybe_newtype: Option<Path>,
+}
+
+pub fn expand_nutype_wrap(input: TokenStream) -> Result<TokenStream, syn::Error> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let DeriveInput {
+        attrs,
+        vis,
+        ident: raw_name,
+        generics,
+        data,
+    } = input;
+
+    let named_fields = match data {
+        Data::Struct(syn::DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => fields.named,
+        _ => {
+            let msg = "`NutypeWrap` can be derived only for structs with named fields.";
+            return Err(syn::Error::new(raw_name.span(), msg));
+        }
+    };
+
+    let mut options = WrapOptions::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("wrap")) {
+        options = attr.parse_args()?;
+    }
+    let name = options
+        .name
+        .unwrap_or_else(|| format_ident!("{raw_name}Wrapped"));
+    let error_name = format_ident!("{name}Error");
+
+    let fields = named_fields
+        .into_iter()
+        .map(|field| {
+            let mut maybe_newtype = None;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("wrap"))
+            {
+                if maybe_newtype.is_some() {
+                    let msg = "Duplicated `wrap` attribute.";
+                    return Err(syn::Error::new_spanned(attr, msg));
+                }
+                maybe_newtype = Some(attr.parse_args::<Path>()?);
+            }
+            Ok(WrapField {
+                // Fields of a struct with named fields always have names.
+                ident: field.ident.expect("named field"),
+                doc_attrs: field.attrs.into_iter().filter(is_doc_attribute).collect(),
+                vis: field.vis,
+                ty: field.ty,
+                maybe_newtype,
+            })
+        })
+        .collect::<Result<Vec<_>, syn::Error>>()?;
+
+    if fields.iter().all(|field| field.maybe_newtype.is_none()) {
+        let msg = "`NutypeWrap` requires at least one field marked with `#[wrap(Newtype)]`.";
+        return Err(syn::Error::new(raw_name.span(), msg));
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let derive = &options.derive;
+
+    let wrapped_fields = fields.iter().map(|field| {
+        let WrapField {
+            ident,
+            doc_attrs,
+            vis,
+            ty,
+            maybe_newtype,
+        } = field;
+        match maybe_newtype {
+            Some(newtype) => quote!(#(#doc_attrs)* #vis #ident: #newtype),
+            None => quote!(#(#doc_attrs)* #vis #ident: #ty),
+        }
+    });
+
+    let wrapped_fields_with_variants: Vec<(&WrapField, &Path, Ident)> = fields
+        .iter()
+        .filter_map(|field| {
+            let newtype = field.maybe_newtype.as_ref()?;
+            let variant = format_ident!("{}", to_upper_camel_case(&field.ident.to_string()));
+            Some((field, newtype, variant))
+        })
+        .collect();
+    let error_variants = wrapped_fields_with_variants
+        .iter()
+        .map(|(field, newtype, variant)| {
+            let doc = format!("The value of `{}` is invalid.", field.ident);
+            quote! {
+                #[doc = #doc]
+                #variant(<#newtype as ::nutype::TryNewtype>::Error)
+            }
+        });
+    let display_arms = wrapped_fields_with_variants
+        .iter()
+        .map(|(field, _, variant)| {
+            let field_name = field.ident.to_string();
+            quote!(Self::#variant(err) => write!(f, "{}: {}", #field_name, err))
+        });
+
+    let try_new_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let wrapped_field = wrapped_fields_with_variants
+            .iter()
+            .find(|(wrapped_field, _, _)| wrapped_field.ident == *ident);
+        match wrapped_field {
+            Some((_, newtype, variant)) => quote! {
+                #ident: <#newtype as ::nutype::TryNewtype>::try_new(raw.#ident)
+                    .map_err(#error_name::#variant)?
+            },
+            None => quote!(#ident: raw.#ident),
+        }
+    });
+    let into_inner_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match field.maybe_newtype {
+            Some(_) => quote!(#ident: ::nutype::Newtype::into_inner(wrapped.#ident)),
+            None => quote!(#ident: wrapped.#ident),
+        }
+    });
+
+    let doc = format!("[`{raw_name}`] with the fields wrapped into newtypes.");
+    let error_doc = format!("An error returned when [`{raw_name}`] is converted into [`{name}`].");
+    let impl_error_trait = gen_impl_error_trait(&ErrorTypePath::new(error_name.clone()));
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(#(#derive),*)]
+        #vis struct #name #generics #where_clause {
+            #(#wrapped_fields),*
+        }
+
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq)]
+        #vis enum #error_name {
+            #(#error_variants),*
+        }
+
+        impl ::core::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#display_arms),*
+                }
+            }
+        }
+
+        #impl_error_trait
+
+        impl #impl_generics ::core::convert::TryFrom<#raw_name #type_generics> for #name #type_generics #where_clause {
+            type Error = #error_name;
+
+            fn try_from(raw: #raw_name #type_generics) -> ::core::result::Result<Self, Self::Error> {
+                Ok(Self {
+                    #(#try_new_fields),*
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #type_generics> for #raw_name #type_generics #where_clause {
+            fn from(wrapped: #name #type_generics) -> Self {
+                Self {
+                    #(#into_inner_fields),*
+                }
+            }
+        }
+    })
+}
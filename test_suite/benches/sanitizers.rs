@@ -0,0 +1,92 @@
+//! Compares newtypes generated by nutype with equivalent hand-written ones.
+//!
+//! Run with `cargo bench -p test_suite --bench sanitizers`.
+use core::hint::black_box;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use nutype::nutype;
+
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(not_empty, len_char_max = 32),
+    derive(Debug)
+)]
+struct Username(String);
+
+/// Same rules as `Username`: `lowercase` is Unicode aware, so `str::to_lowercase()` is used.
+#[derive(Debug)]
+struct HandWrittenUsername(String);
+
+impl HandWrittenUsername {
+    fn try_new(raw: String) -> Result<Self, &'static str> {
+        let value = raw.trim().to_lowercase();
+        if value.is_empty() {
+            return Err("empty");
+        }
+        if value.chars().count() > 32 {
+            return Err("too long");
+        }
+        Ok(Self(value))
+    }
+
+    fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+#[nutype(validate(greater_or_equal = 1, less_or_equal = 65535), derive(Debug))]
+struct Port(u32);
+
+#[derive(Debug)]
+struct HandWrittenPort(u32);
+
+impl HandWrittenPort {
+    fn try_new(raw: u32) -> Result<Self, &'static str> {
+        if !(1..=65535).contains(&raw) {
+            return Err("out of range");
+        }
+        Ok(Self(raw))
+    }
+
+    fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+fn bench_string(c: &mut Criterion) {
+    for (case, input) in [("ascii", "  Nutype_Fan  "), ("unicode", "  Ünïcödé_Fän  ")] {
+        let mut group = c.benchmark_group(format!("string/{case}"));
+        // The input is allocated in the setup, so only the construction is measured.
+        group.bench_function("nutype", |b| {
+            b.iter_batched(
+                || input.to_string(),
+                |raw| Username::try_new(black_box(raw)).map(Username::into_inner),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function("hand-written", |b| {
+            b.iter_batched(
+                || input.to_string(),
+                |raw| {
+                    HandWrittenUsername::try_new(black_box(raw))
+                        .map(HandWrittenUsername::into_inner)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+        group.finish();
+    }
+}
+
+fn bench_integer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("integer");
+    group.bench_function("nutype", |b| {
+        b.iter(|| Port::try_new(black_box(8080)).map(Port::into_inner))
+    });
+    group.bench_function("hand-written", |b| {
+        b.iter(|| HandWrittenPort::try_new(black_box(8080)).map(HandWrittenPort::into_inner))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_string, bench_integer);
+criterion_main!(benches);
@@ -34,6 +34,12 @@ impl core::fmt::Display for Point {
     }
 }
 
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
 impl core::str::FromStr for Point {
     type Err = &'static str;
 
@@ -249,6 +255,22 @@ mod traits {
                 assert_eq!(pos.into_inner(), Point::new(3, 2));
             }
         }
+
+        #[test]
+        fn test_with_listed_types() {
+            #[nutype(
+                derive(Debug, TryFrom),
+                try_from(Point, (i32, i32)),
+                validate(predicate = |p: &Point| p.x > p.y)
+            )]
+            pub struct Position(Point);
+
+            let pos = Position::try_from((3, 2)).unwrap();
+            assert_eq!(pos.into_inner(), Point::new(3, 2));
+
+            let err = Position::try_from(Point::new(2, 2)).unwrap_err();
+            assert_eq!(err, PositionError::PredicateViolated);
+        }
     }
 
     #[test]
@@ -546,6 +568,15 @@ mod with_generics {
         assert_eq!(b.to_string(), "true");
     }
 
+    #[test]
+    fn test_generic_boundaries_display_with_format() {
+        #[nutype(derive(Debug, Display), display = "<{}>")]
+        struct Tagged<T>(T);
+
+        let tagged = Tagged::new(5);
+        assert_eq!(tagged.to_string(), "<5>");
+    }
+
     #[test]
     fn test_generic_boundaries_clone() {
         #[nutype(derive(Clone))]
@@ -713,6 +744,24 @@ mod with_generics {
         assert_eq!(sorted.into_inner(), vec![1, 2, 3]);
     }
 
+    #[test]
+    fn test_generic_with_where_clause() {
+        #[nutype(
+            validate(predicate = |v| v.windows(2).all(|pair| pair[0] <= pair[1])),
+            derive(Debug, Clone, PartialEq, AsRef, TryFrom)
+        )]
+        struct Ascending<T>(Vec<T>)
+        where
+            T: PartialOrd + Clone;
+
+        let ascending = Ascending::try_new(vec![1.5, 2.0, 2.0]).unwrap();
+        assert_eq!(ascending.clone().into_inner(), vec![1.5, 2.0, 2.0]);
+        assert_eq!(
+            Ascending::try_from(vec!["b", "a"]),
+            Err(AscendingError::PredicateViolated)
+        );
+    }
+
     #[test]
     fn test_generic_from_str_without_validation() {
         // Note: the code generate for FromStr relies on "associated type bounds" feature, which is
@@ -983,3 +1032,892 @@ mod custom_error {
         assert_eq!(collection.as_ref(), &[1, 2, 3]);
     }
 }
+
+#[cfg(test)]
+mod parse_money {
+    use super::*;
+
+    /// A tiny fixed-point decimal with 2 digits after the decimal point.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Cents(i64);
+
+    impl core::str::FromStr for Cents {
+        type Err = &'static str;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (units, fraction) = s.split_once('.').unwrap_or((s, "0"));
+            let units: i64 = units.parse().map_err(|_| "Invalid units")?;
+            let fraction: i64 = format!("{fraction:0<2}")
+                .parse()
+                .map_err(|_| "Invalid fraction")?;
+            let sign = if s.starts_with('-') { -1 } else { 1 };
+            Ok(Cents(units * 100 + sign * fraction))
+        }
+    }
+
+    #[test]
+    fn test_parse_money_for_custom_decimal() {
+        #[nutype(
+            parse_money(scale = 2),
+            validate(predicate = |amount| amount.0 > 0),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Price(Cents);
+
+        assert_eq!(
+            Price::parse_money("$1,234.5").unwrap().into_inner(),
+            Cents(123450)
+        );
+        assert_eq!(
+            Price::parse_money("12,34 €").unwrap().into_inner(),
+            Cents(1234)
+        );
+        assert!(matches!(
+            Price::parse_money("0.001"),
+            Err(PriceParseMoneyError::ScaleViolated)
+        ));
+        assert!(matches!(
+            Price::parse_money("-1.00"),
+            Err(PriceParseMoneyError::Validate(
+                PriceError::PredicateViolated
+            ))
+        ));
+        assert_eq!(
+            Price::parse_money("n/a").unwrap_err().to_string(),
+            "Failed to parse Price: invalid amount"
+        );
+    }
+}
+
+#[cfg(test)]
+mod temporal {
+    use super::*;
+    use core::time::Duration;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    // 2024-01-15T00:00:00Z, Monday
+    const MONDAY: u64 = 1_705_276_800;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct UnixSeconds(i64);
+
+    impl nutype::Temporal for UnixSeconds {
+        fn unix_timestamp_nanos(&self) -> i128 {
+            i128::from(self.0) * 1_000_000_000
+        }
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_after_and_before() {
+        #[nutype(
+            validate(after = "2000-01-01", before = "2100-01-01T00:00:00Z"),
+            derive(Debug)
+        )]
+        struct EventTime(SystemTime);
+
+        assert_eq!(
+            EventTime::try_new(at(946_684_800)).unwrap_err(),
+            EventTimeError::AfterViolated
+        );
+        assert_eq!(
+            EventTime::try_new(at(946_684_801)).unwrap().into_inner(),
+            at(946_684_801)
+        );
+        assert_eq!(
+            EventTime::try_new(at(4_102_444_800)).unwrap_err(),
+            EventTimeError::BeforeViolated
+        );
+        assert_eq!(
+            EventTime::try_new(at(1)).unwrap_err().to_string(),
+            "EventTime is too early. The value must be after 2000-01-01."
+        );
+    }
+
+    #[test]
+    fn test_not_before_and_not_after() {
+        #[nutype(
+            validate(not_before = "1970-01-01", not_after = "2024-01-15"),
+            derive(Debug)
+        )]
+        struct CreatedAt(SystemTime);
+
+        assert_eq!(
+            CreatedAt::try_new(UNIX_EPOCH).unwrap().into_inner(),
+            UNIX_EPOCH
+        );
+        assert!(CreatedAt::try_new(at(MONDAY)).is_ok());
+        assert_eq!(
+            CreatedAt::try_new(UNIX_EPOCH - Duration::from_nanos(1)).unwrap_err(),
+            CreatedAtError::NotBeforeViolated
+        );
+        assert_eq!(
+            CreatedAt::try_new(at(MONDAY + 1)).unwrap_err(),
+            CreatedAtError::NotAfterViolated
+        );
+    }
+
+    #[test]
+    fn test_weekday_only() {
+        #[nutype(validate(weekday_only), derive(Debug))]
+        struct BusinessDay(SystemTime);
+
+        let day = 86_400;
+        for weekday in 0..5 {
+            assert_eq!(
+                BusinessDay::try_new(at(MONDAY + weekday * day))
+                    .unwrap()
+                    .into_inner(),
+                at(MONDAY + weekday * day)
+            );
+        }
+        assert_eq!(
+            BusinessDay::try_new(at(MONDAY + 5 * day)).unwrap_err(),
+            BusinessDayError::WeekdayOnlyViolated
+        );
+        assert_eq!(
+            BusinessDay::try_new(at(MONDAY - 1)).unwrap_err(),
+            BusinessDayError::WeekdayOnlyViolated
+        );
+        // 1969-12-31 was Wednesday
+        assert!(BusinessDay::try_new(UNIX_EPOCH - Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_granularity() {
+        #[nutype(validate(granularity = minutes), derive(Debug))]
+        struct SlotStart(SystemTime);
+
+        assert_eq!(
+            SlotStart::try_new(at(MONDAY + 120)).unwrap().into_inner(),
+            at(MONDAY + 120)
+        );
+        assert_eq!(
+            SlotStart::try_new(at(MONDAY + 30)).unwrap_err(),
+            SlotStartError::GranularityViolated
+        );
+        assert_eq!(
+            SlotStart::try_new(at(MONDAY) + Duration::from_nanos(1))
+                .unwrap_err()
+                .to_string(),
+            "SlotStart is too precise. The value must be in whole minutes."
+        );
+    }
+
+    // Pins "now" to 2024-01-15T00:00:00Z
+    struct FixedClock;
+
+    impl nutype::Clock for FixedClock {
+        fn now_unix_nanos() -> i128 {
+            i128::from(MONDAY) * 1_000_000_000
+        }
+    }
+
+    #[test]
+    fn test_within_last() {
+        #[nutype(validate(within_last = "30d", clock = FixedClock), derive(Debug))]
+        struct RecentLogin(SystemTime);
+
+        let day = 86_400;
+        assert_eq!(
+            RecentLogin::try_new(at(MONDAY - 30 * day))
+                .unwrap()
+                .into_inner(),
+            at(MONDAY - 30 * day)
+        );
+        assert!(RecentLogin::try_new(at(MONDAY)).is_ok());
+        assert_eq!(
+            RecentLogin::try_new(at(MONDAY - 30 * day - 1)).unwrap_err(),
+            RecentLoginError::WithinLastViolated
+        );
+        assert_eq!(
+            RecentLogin::try_new(at(MONDAY + 1)).unwrap_err(),
+            RecentLoginError::WithinLastViolated
+        );
+        assert_eq!(
+            RecentLogin::try_new(at(0)).unwrap_err().to_string(),
+            "RecentLogin must be within the last 30d."
+        );
+    }
+
+    #[test]
+    fn test_within_next() {
+        #[nutype(validate(within_next = "1y", clock = FixedClock), derive(Debug))]
+        struct Deadline(SystemTime);
+
+        let year = 365 * 86_400;
+        assert_eq!(
+            Deadline::try_new(at(MONDAY + year)).unwrap().into_inner(),
+            at(MONDAY + year)
+        );
+        assert_eq!(
+            Deadline::try_new(at(MONDAY + year + 1)).unwrap_err(),
+            DeadlineError::WithinNextViolated
+        );
+        assert_eq!(
+            Deadline::try_new(at(MONDAY - 1)).unwrap_err(),
+            DeadlineError::WithinNextViolated
+        );
+    }
+
+    #[test]
+    fn test_try_new_at() {
+        #[nutype(
+            sanitize(with = |t| t - Duration::from_secs(60)),
+            validate(within_last = "1d", not_before = "2024-01-01"),
+            derive(Debug)
+        )]
+        struct RecentLogin(SystemTime);
+
+        let day = 86_400;
+        assert_eq!(
+            RecentLogin::try_new_at(at(MONDAY), at(MONDAY + 3600))
+                .unwrap()
+                .into_inner(),
+            at(MONDAY - 60)
+        );
+        assert_eq!(
+            RecentLogin::try_new_at(at(MONDAY), at(MONDAY + 2 * day)).unwrap_err(),
+            RecentLoginError::WithinLastViolated
+        );
+        assert_eq!(
+            RecentLogin::try_new_at(at(0), at(60)).unwrap_err(),
+            RecentLoginError::NotBeforeViolated
+        );
+        // `try_new()` keeps using the system clock
+        assert_eq!(
+            RecentLogin::try_new(at(MONDAY)).unwrap_err(),
+            RecentLoginError::WithinLastViolated
+        );
+    }
+
+    static REJECTED_LOGINS: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+
+    #[test]
+    fn test_try_new_at_reports_rejected_value() {
+        #[nutype(
+            validate(within_last = "1d"),
+            on_invalid = |_err, _value| {
+                REJECTED_LOGINS.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+            },
+            derive(Debug)
+        )]
+        struct RecentLogin(SystemTime);
+
+        assert_eq!(
+            RecentLogin::try_new_at(at(MONDAY), at(MONDAY + 60))
+                .unwrap()
+                .into_inner(),
+            at(MONDAY)
+        );
+        assert_eq!(
+            RecentLogin::try_new_at(at(MONDAY), at(MONDAY + 2 * 86_400)).unwrap_err(),
+            RecentLoginError::WithinLastViolated
+        );
+        assert_eq!(
+            REJECTED_LOGINS.load(core::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[test]
+    fn test_within_last_with_system_clock() {
+        #[nutype(validate(within_last = "1h"), derive(Debug))]
+        struct JustNow(SystemTime);
+
+        let now = SystemTime::now();
+        assert_eq!(JustNow::try_new(now).unwrap().into_inner(), now);
+        assert_eq!(
+            JustNow::try_new(now - Duration::from_secs(7200)).unwrap_err(),
+            JustNowError::WithinLastViolated
+        );
+    }
+
+    #[test]
+    fn test_custom_temporal_type() {
+        #[nutype(validate(not_before = "1970-01-01", granularity = hours), derive(Debug))]
+        struct Hour(UnixSeconds);
+
+        assert_eq!(
+            Hour::try_new(UnixSeconds(3600)).unwrap().into_inner(),
+            UnixSeconds(3600)
+        );
+        assert_eq!(
+            Hour::try_new(UnixSeconds(-3600)).unwrap_err(),
+            HourError::NotBeforeViolated
+        );
+        assert_eq!(
+            Hour::try_new(UnixSeconds(60)).unwrap_err(),
+            HourError::GranularityViolated
+        );
+    }
+
+    #[test]
+    fn test_not_in_future_and_not_in_past() {
+        #[nutype(validate(not_in_future, clock = FixedClock), derive(Debug))]
+        struct BirthDate(SystemTime);
+
+        #[nutype(validate(not_in_past, clock = FixedClock), derive(Debug))]
+        struct ExpiresAt(SystemTime);
+
+        assert_eq!(
+            BirthDate::try_new(at(MONDAY)).unwrap().into_inner(),
+            at(MONDAY)
+        );
+        assert!(BirthDate::try_new(at(0)).is_ok());
+        assert_eq!(
+            BirthDate::try_new(at(MONDAY + 1)).unwrap_err(),
+            BirthDateError::NotInFutureViolated
+        );
+        assert_eq!(
+            BirthDate::try_new(at(MONDAY + 1)).unwrap_err().to_string(),
+            "BirthDate must not be in the future."
+        );
+
+        assert_eq!(
+            ExpiresAt::try_new(at(MONDAY)).unwrap().into_inner(),
+            at(MONDAY)
+        );
+        assert!(ExpiresAt::try_new(at(MONDAY + 1)).is_ok());
+        assert_eq!(
+            ExpiresAt::try_new(at(MONDAY - 1)).unwrap_err(),
+            ExpiresAtError::NotInPastViolated
+        );
+    }
+
+    #[test]
+    fn test_duration_min_and_max() {
+        #[nutype(validate(min = "100ms", max = "30s"), derive(Debug))]
+        struct Timeout(Duration);
+
+        assert_eq!(
+            Timeout::try_new(Duration::from_millis(100))
+                .unwrap()
+                .into_inner(),
+            Duration::from_millis(100)
+        );
+        assert!(Timeout::try_new(Duration::from_secs(30)).is_ok());
+        assert_eq!(
+            Timeout::try_new(Duration::from_millis(99)).unwrap_err(),
+            TimeoutError::MinViolated
+        );
+        assert_eq!(
+            Timeout::try_new(Duration::from_secs(30) + Duration::from_nanos(1)).unwrap_err(),
+            TimeoutError::MaxViolated
+        );
+        assert_eq!(
+            Timeout::try_new(Duration::ZERO).unwrap_err().to_string(),
+            "Timeout is too short. The value must be between 100ms and 30s."
+        );
+        assert_eq!(
+            Timeout::try_new(Duration::from_secs(60))
+                .unwrap_err()
+                .to_string(),
+            "Timeout is too long. The value must be between 100ms and 30s."
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "chrono")]
+mod chrono_types {
+    use super::*;
+    use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta, Utc};
+
+    #[test]
+    fn test_naive_date() {
+        #[nutype(
+            validate(not_before = "2000-01-01", before = "2100-01-01"),
+            derive(Debug)
+        )]
+        struct BirthDate(NaiveDate);
+
+        let date = NaiveDate::from_ymd_opt(1990, 5, 17).unwrap();
+        assert_eq!(
+            BirthDate::try_new(date).unwrap_err(),
+            BirthDateError::NotBeforeViolated
+        );
+        let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        assert_eq!(BirthDate::try_new(date).unwrap().into_inner(), date);
+    }
+
+    #[test]
+    fn test_date_time() {
+        #[nutype(validate(after = "2024-01-01T00:00:00Z"), derive(Debug))]
+        struct PublishedAt(DateTime<Utc>);
+
+        let time = DateTime::from_timestamp(1_704_067_201, 0).unwrap();
+        assert_eq!(PublishedAt::try_new(time).unwrap().into_inner(), time);
+        assert_eq!(
+            PublishedAt::try_new(DateTime::UNIX_EPOCH).unwrap_err(),
+            PublishedAtError::AfterViolated
+        );
+    }
+
+    #[test]
+    fn test_weekday_only_and_granularity_in_local_time() {
+        #[nutype(validate(weekday_only, granularity = hours), derive(Debug))]
+        struct Meeting(DateTime<FixedOffset>);
+
+        // Monday in Sydney, but still Sunday in UTC
+        let time = DateTime::parse_from_rfc3339("2024-01-15T08:00:00+10:00").unwrap();
+        assert_eq!(Meeting::try_new(time).unwrap().into_inner(), time);
+        // A whole hour in India, but not in UTC
+        let time = DateTime::parse_from_rfc3339("2024-01-15T10:00:00+05:30").unwrap();
+        assert_eq!(Meeting::try_new(time).unwrap().into_inner(), time);
+        // Saturday in Honolulu, but already Monday in UTC
+        let time = DateTime::parse_from_rfc3339("2024-01-13T23:00:00-10:00").unwrap();
+        assert_eq!(
+            Meeting::try_new(time).unwrap_err(),
+            MeetingError::WeekdayOnlyViolated
+        );
+        // A whole hour in UTC, but not in India
+        let time = DateTime::parse_from_rfc3339("2024-01-15T10:30:00+05:30").unwrap();
+        assert_eq!(
+            Meeting::try_new(time).unwrap_err(),
+            MeetingError::GranularityViolated
+        );
+    }
+
+    #[test]
+    fn test_time_delta() {
+        #[nutype(validate(min = "1s", max = "1h"), derive(Debug))]
+        struct Interval(TimeDelta);
+
+        assert_eq!(
+            Interval::try_new(TimeDelta::minutes(5))
+                .unwrap()
+                .into_inner(),
+            TimeDelta::minutes(5)
+        );
+        assert_eq!(
+            Interval::try_new(TimeDelta::seconds(-5)).unwrap_err(),
+            IntervalError::MinViolated
+        );
+        assert_eq!(
+            Interval::try_new(TimeDelta::hours(2)).unwrap_err(),
+            IntervalError::MaxViolated
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "time")]
+mod time_types {
+    use super::*;
+    use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+    #[test]
+    fn test_offset_date_time() {
+        #[nutype(validate(not_before = "2024-01-01", not_in_future), derive(Debug))]
+        struct CreatedAt(OffsetDateTime);
+
+        let time = Date::from_calendar_date(2024, Month::March, 1)
+            .unwrap()
+            .midnight()
+            .assume_utc();
+        assert_eq!(CreatedAt::try_new(time).unwrap().into_inner(), time);
+        assert_eq!(
+            CreatedAt::try_new(OffsetDateTime::UNIX_EPOCH).unwrap_err(),
+            CreatedAtError::NotBeforeViolated
+        );
+        let far_future = Date::from_calendar_date(9999, Month::January, 1)
+            .unwrap()
+            .midnight()
+            .assume_utc();
+        assert_eq!(
+            CreatedAt::try_new(far_future).unwrap_err(),
+            CreatedAtError::NotInFutureViolated
+        );
+    }
+
+    #[test]
+    fn test_weekday_only_and_granularity_in_local_time() {
+        #[nutype(validate(weekday_only, granularity = hours), derive(Debug))]
+        struct Meeting(OffsetDateTime);
+
+        let monday = Date::from_calendar_date(2024, Month::January, 15).unwrap();
+        let at = |hour, minute, offset| {
+            monday
+                .with_hms(hour, minute, 0)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(offset, 0, 0).unwrap())
+        };
+
+        // Monday in Sydney, but still Sunday in UTC
+        assert_eq!(
+            Meeting::try_new(at(8, 0, 10)).unwrap().into_inner(),
+            at(8, 0, 10)
+        );
+        // Monday 2024-01-15T23:00:00-10:00 is Tuesday in UTC, still a weekday
+        assert!(Meeting::try_new(at(23, 0, -10)).is_ok());
+        let india = UtcOffset::from_hms(5, 30, 0).unwrap();
+        let whole_local_hour = monday.with_hms(10, 0, 0).unwrap().assume_offset(india);
+        assert!(Meeting::try_new(whole_local_hour).is_ok());
+        let whole_utc_hour = monday.with_hms(10, 30, 0).unwrap().assume_offset(india);
+        assert_eq!(
+            Meeting::try_new(whole_utc_hour).unwrap_err(),
+            MeetingError::GranularityViolated
+        );
+        let sunday = Date::from_calendar_date(2024, Month::January, 14)
+            .unwrap()
+            .with_hms(20, 0, 0)
+            .unwrap()
+            .assume_offset(UtcOffset::from_hms(-10, 0, 0).unwrap());
+        // Monday in UTC, but Sunday in Honolulu
+        assert_eq!(
+            Meeting::try_new(sunday).unwrap_err(),
+            MeetingError::WeekdayOnlyViolated
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        #[nutype(validate(max = "1d"), derive(Debug))]
+        struct Retention(time::Duration);
+
+        assert_eq!(
+            Retention::try_new(time::Duration::hours(24))
+                .unwrap()
+                .into_inner(),
+            time::Duration::hours(24)
+        );
+        assert_eq!(
+            Retention::try_new(time::Duration::hours(25)).unwrap_err(),
+            RetentionError::MaxViolated
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "uuid")]
+mod uuid_validators {
+    use super::*;
+    use uuid::Uuid;
+
+    const V4: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    const V7: &str = "01932c07-209c-7b3a-8f3e-5c5e0b8f1a2d";
+
+    #[test]
+    fn test_uuid_version() {
+        #[nutype(
+            validate(uuid_version = 4),
+            derive(Debug, Clone, Copy, PartialEq, Eq, Hash)
+        )]
+        struct UserId(Uuid);
+
+        let v4 = Uuid::parse_str(V4).unwrap();
+        let v7 = Uuid::parse_str(V7).unwrap();
+        assert_eq!(UserId::try_new(v4).unwrap().into_inner(), v4);
+        assert_eq!(
+            UserId::try_new(v7).unwrap_err(),
+            UserIdError::UuidVersionViolated
+        );
+        assert_eq!(
+            UserId::try_new(Uuid::nil()).unwrap_err().to_string(),
+            "UserId must be a UUID of version 4."
+        );
+    }
+
+    #[test]
+    fn test_uuid_version_7() {
+        #[nutype(validate(uuid_version = 7), derive(Debug))]
+        struct EventId(Uuid);
+
+        let v7 = Uuid::parse_str(V7).unwrap();
+        assert_eq!(EventId::try_new(v7).unwrap().into_inner(), v7);
+        assert_eq!(
+            EventId::try_new(Uuid::parse_str(V4).unwrap()).unwrap_err(),
+            EventIdError::UuidVersionViolated
+        );
+    }
+
+    #[test]
+    fn test_not_nil() {
+        #[nutype(validate(not_nil), derive(Debug))]
+        struct OrderId(Uuid);
+
+        let id = Uuid::parse_str(V7).unwrap();
+        assert_eq!(OrderId::try_new(id).unwrap().into_inner(), id);
+        assert_eq!(
+            OrderId::try_new(Uuid::nil()).unwrap_err(),
+            OrderIdError::NotNilViolated
+        );
+        assert_eq!(
+            OrderId::try_new(Uuid::nil()).unwrap_err().to_string(),
+            "OrderId must not be the nil UUID."
+        );
+    }
+
+    #[test]
+    fn test_from_str_and_display() {
+        #[nutype(
+            validate(uuid_version = 4, not_nil),
+            derive(Debug, PartialEq, FromStr, Display)
+        )]
+        struct UserId(Uuid);
+
+        let id: UserId = V4.parse().unwrap();
+        assert_eq!(id.to_string(), V4);
+        assert!(matches!(
+            V7.parse::<UserId>().unwrap_err(),
+            UserIdParseError::Validate(UserIdError::UuidVersionViolated)
+        ));
+        assert!(matches!(
+            "not-a-uuid".parse::<UserId>().unwrap_err(),
+            UserIdParseError::Parse(_)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod ip_validators {
+    use super::*;
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_ip_addr() {
+        #[nutype(
+            validate(not_loopback, not_unspecified, not_multicast),
+            derive(Debug, Clone, Copy, PartialEq, FromStr)
+        )]
+        struct ServerIp(IpAddr);
+
+        let ip: ServerIp = "192.168.0.10".parse().unwrap();
+        assert_eq!(ip.into_inner(), IpAddr::V4(Ipv4Addr::new(192, 168, 0, 10)));
+        assert_eq!(
+            ServerIp::try_new(IpAddr::V6(Ipv6Addr::LOCALHOST)).unwrap_err(),
+            ServerIpError::NotLoopbackViolated
+        );
+        assert_eq!(
+            ServerIp::try_new(IpAddr::V4(Ipv4Addr::UNSPECIFIED)).unwrap_err(),
+            ServerIpError::NotUnspecifiedViolated
+        );
+        assert_eq!(
+            ServerIp::try_new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 1)))
+                .unwrap_err()
+                .to_string(),
+            "ServerIp must not be a multicast address."
+        );
+    }
+
+    #[test]
+    fn test_ipv4_and_ipv6() {
+        #[nutype(validate(not_loopback), derive(Debug))]
+        struct PublicIpv4(Ipv4Addr);
+
+        #[nutype(validate(not_unspecified), derive(Debug))]
+        struct PeerIpv6(Ipv6Addr);
+
+        assert_eq!(
+            PublicIpv4::try_new(Ipv4Addr::LOCALHOST).unwrap_err(),
+            PublicIpv4Error::NotLoopbackViolated
+        );
+        assert_eq!(
+            PublicIpv4::try_new(Ipv4Addr::new(8, 8, 8, 8))
+                .unwrap()
+                .into_inner(),
+            Ipv4Addr::new(8, 8, 8, 8)
+        );
+        assert_eq!(
+            PeerIpv6::try_new(Ipv6Addr::UNSPECIFIED).unwrap_err(),
+            PeerIpv6Error::NotUnspecifiedViolated
+        );
+        assert_eq!(
+            PeerIpv6::try_new(Ipv6Addr::LOCALHOST).unwrap().into_inner(),
+            Ipv6Addr::LOCALHOST
+        );
+    }
+}
+
+#[cfg(test)]
+mod new_into {
+    use super::*;
+
+    #[nutype(
+        sanitize(with = |mut v| { v.sort(); v }),
+        validate(predicate = |v| !v.is_empty()),
+        derive(Debug)
+    )]
+    pub struct Scores(Vec<u32>);
+
+    #[test]
+    fn test_try_new_into_reuses_buffer() {
+        let mut buffer: Vec<u32> = Vec::with_capacity(16);
+        let scores = Scores::try_new_into(&[3, 1, 2], &mut buffer).unwrap();
+        let scores = scores.into_inner();
+        assert_eq!(scores, vec![1, 2, 3]);
+        assert_eq!(scores.capacity(), 16);
+
+        let mut buffer = scores;
+        assert!(matches!(
+            Scores::try_new_into(&[], &mut buffer),
+            Err(ScoresError::PredicateViolated)
+        ));
+        assert_eq!(buffer.capacity(), 16);
+    }
+
+    #[test]
+    fn test_generic_vec() {
+        #[nutype(derive(Debug))]
+        pub struct Batch<T>(Vec<T>);
+
+        let mut buffer = Vec::with_capacity(8);
+        let batch = Batch::new_into(&["a", "b"], &mut buffer);
+        assert_eq!(batch.into_inner(), vec!["a", "b"]);
+    }
+}
+
+#[cfg(test)]
+mod collection_traits {
+    use super::*;
+    use alloc::collections::BTreeSet;
+
+    #[test]
+    fn test_into_iterator() {
+        #[nutype(
+            validate(predicate = |v| !v.is_empty()),
+            derive(Debug, IntoIterator)
+        )]
+        struct NonEmptyVec<T>(Vec<T>);
+
+        let vec = NonEmptyVec::try_new(vec![1, 2, 3]).unwrap();
+        assert_eq!(vec.len(), 3);
+        assert!(!vec.is_empty());
+
+        let mut sum = 0;
+        for item in &vec {
+            sum += item;
+        }
+        assert_eq!(sum, 6);
+
+        let doubled: Vec<i32> = vec.into_iter().map(|item| item * 2).collect();
+        assert_eq!(doubled, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_extend_sanitizes_again() {
+        #[nutype(
+            sanitize(with = |mut v| { v.sort(); v.dedup(); v }),
+            derive(Debug, IntoIterator, Extend)
+        )]
+        struct SortedVec(Vec<u32>);
+
+        let mut vec = SortedVec::new(vec![5, 1]);
+        vec.extend([3, 1, 4]);
+        assert_eq!(vec.into_inner(), vec![1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_try_extend_validates_again() {
+        #[nutype(
+            validate(predicate = |set| set.len() <= 3),
+            derive(Debug, PartialEq, IntoIterator, Extend)
+        )]
+        struct Tags(BTreeSet<&'static str>);
+
+        let mut tags = Tags::try_new(BTreeSet::from(["a"])).unwrap();
+        assert_eq!(tags.try_extend(["b", "a", "c"]), Ok(()));
+        assert_eq!(tags.len(), 3);
+
+        // The value stays unchanged, including the valid items before the invalid one.
+        assert_eq!(
+            tags.try_extend(["a", "d", "e"]),
+            Err(TagsError::PredicateViolated)
+        );
+        assert_eq!(tags.into_inner(), BTreeSet::from(["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_try_extend_sanitizes_before_validation() {
+        #[nutype(
+            sanitize(with = |mut v| { v.sort(); v.dedup(); v }),
+            validate(predicate = |v| v.len() <= 3),
+            derive(Debug, IntoIterator, Extend)
+        )]
+        struct Podium(Vec<u32>);
+
+        let mut podium = Podium::try_new(vec![30]).unwrap();
+        assert_eq!(podium.try_extend([10, 30, 20]), Ok(()));
+        assert_eq!(podium.try_extend([40]), Err(PodiumError::PredicateViolated));
+        assert_eq!(podium.into_inner(), vec![10, 20, 30]);
+    }
+}
+
+mod byte_array {
+    use super::*;
+
+    #[nutype(
+        validate(predicate = |key| key.iter().any(|byte| *byte != 0)),
+        derive(Debug, Clone, Copy, PartialEq, Eq, Hash, AsRef)
+    )]
+    pub struct Key([u8; 4]);
+
+    #[test]
+    fn test_byte_array() {
+        let key = Key::try_new([1, 2, 3, 4]).unwrap();
+        assert_eq!(key.as_ref(), &[1, 2, 3, 4]);
+        assert_eq!(key.into_inner(), [1, 2, 3, 4]);
+        assert_eq!(Key::try_new([0; 4]), Err(KeyError::PredicateViolated));
+    }
+
+    #[test]
+    fn test_sanitize_byte_array() {
+        #[nutype(
+            sanitize(with = |mut mac: [u8; 6]| { mac[0] &= 0b1111_1110; mac }),
+            derive(Debug)
+        )]
+        pub struct MacAddress([u8; 6]);
+
+        let mac = MacAddress::new([0xff, 1, 2, 3, 4, 5]);
+        assert_eq!(mac.into_inner(), [0xfe, 1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "hex")]
+    #[test]
+    fn test_hex_encoding() {
+        #[nutype(
+            validate(predicate = |hash| hash != &[0; 4]),
+            encoding = "hex",
+            derive(Debug, FromStr, Display)
+        )]
+        pub struct Checksum([u8; 4]);
+
+        let checksum: Checksum = "DEADbeef".parse().unwrap();
+        assert_eq!(checksum.to_string(), "deadbeef");
+        assert!(matches!(
+            "00000000".parse::<Checksum>(),
+            Err(ChecksumParseError::Validate(
+                ChecksumError::PredicateViolated
+            ))
+        ));
+        assert!(matches!(
+            "dead".parse::<Checksum>(),
+            Err(ChecksumParseError::Parse(
+                nutype::DecodeError::InvalidLength { expected: 4 }
+            ))
+        ));
+        assert!(matches!(
+            "deadbeeg".parse::<Checksum>(),
+            Err(ChecksumParseError::Parse(
+                nutype::DecodeError::InvalidCharacter { index: 7 }
+            ))
+        ));
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn test_base64_encoding() {
+        #[nutype(encoding = "base64", derive(Debug, FromStr, Display))]
+        pub struct Nonce([u8; 5]);
+
+        let nonce: Nonce = "aGVsbG8=".parse().unwrap();
+        assert_eq!(nonce.to_string(), "aGVsbG8=");
+        assert_eq!(&nonce.into_inner(), b"hello");
+        assert!("aGVsbG8".parse::<Nonce>().is_err());
+        assert!("aGVsbG=8".parse::<Nonce>().is_err());
+    }
+}
@@ -4,3 +4,92 @@ fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/**/*.rs");
 }
+
+// The cases below depend on features of `nutype`, so they live outside of `tests/ui`
+// and run only with the matching set of features.
+
+#[cfg(all(feature = "ui", feature = "serde"))]
+#[test]
+fn ui_serde() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_features/serde/*.rs");
+}
+
+#[cfg(all(feature = "ui", feature = "zeroize"))]
+#[test]
+fn ui_zeroize() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_features/zeroize/*.rs");
+}
+
+#[cfg(all(feature = "ui", feature = "bumpalo"))]
+#[test]
+fn ui_bumpalo() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_features/bumpalo/*.rs");
+}
+
+#[cfg(all(feature = "ui", feature = "from_schema"))]
+#[test]
+fn ui_from_schema() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_features/from_schema/*.rs");
+}
+
+#[cfg(all(feature = "ui", feature = "nohash-hasher"))]
+#[test]
+fn ui_nohash_hasher() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_features/nohash-hasher/*.rs");
+}
+
+// The cases below check the errors reported when a feature of `nutype` is not enabled.
+
+#[cfg(all(feature = "ui", not(feature = "wasm-bindgen")))]
+#[test]
+fn ui_without_wasm_bindgen() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/wasm-bindgen/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "hex")))]
+#[test]
+fn ui_without_hex() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/hex/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "borsh")))]
+#[test]
+fn ui_without_borsh() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/borsh/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "rkyv")))]
+#[test]
+fn ui_without_rkyv() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/rkyv/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "heapless")))]
+#[test]
+fn ui_without_heapless() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/heapless/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "clap")))]
+#[test]
+fn ui_without_clap() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/clap/*.rs");
+}
+
+#[cfg(all(feature = "ui", not(feature = "unicode")))]
+#[test]
+fn ui_without_unicode() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui_without_features/unicode/*.rs");
+}
@@ -1,4 +1,5 @@
 use nutype::nutype;
+extern crate alloc;
 
 #[cfg(test)]
 mod sanitizers {
@@ -109,6 +110,33 @@ mod validators {
         assert_eq!(Age::try_new(25.0).unwrap().into_inner(), 25.0);
     }
 
+    #[test]
+    fn test_bound_consts() {
+        #[nutype(validate(greater_or_equal = -273.15, less_or_equal = 1000.0), derive(Debug))]
+        struct Temperature(f64);
+
+        assert_eq!(Temperature::MIN, -273.15);
+        assert_eq!(Temperature::MAX, 1000.0);
+    }
+
+    #[test]
+    fn test_bounds_with_exponent() {
+        #[nutype(validate(greater_or_equal = -1e-3, less_or_equal = 1.5e3), derive(Debug))]
+        struct Altitude(f64);
+
+        assert_eq!(Altitude::MIN, -0.001);
+        assert_eq!(Altitude::MAX, 1500.0);
+        assert_eq!(
+            Altitude::try_new(-0.01).unwrap_err(),
+            AltitudeError::GreaterOrEqualViolated
+        );
+        assert_eq!(
+            Altitude::try_new(1500.5).unwrap_err(),
+            AltitudeError::LessOrEqualViolated
+        );
+        assert_eq!(Altitude::try_new(1500.0).unwrap().into_inner(), 1500.0);
+    }
+
     #[test]
     fn test_finite_f64() {
         #[nutype(validate(finite), derive(Debug, PartialEq))]
@@ -123,7 +151,7 @@ mod validators {
         assert_eq!(Dist::try_new(f64::NAN), Err(DistError::FiniteViolated));
         assert_eq!(Dist::try_new(-1.0 / 0.0), Err(DistError::FiniteViolated));
         assert_eq!(Dist::try_new(1.0 / 0.0), Err(DistError::FiniteViolated));
-        assert_eq!(Dist::try_new(0.0 / 0.0), Err(DistError::FiniteViolated));
+        assert_eq!(Dist::try_new(f64::NAN), Err(DistError::FiniteViolated));
 
         // valid
         assert_eq!(Dist::try_new(12.345).unwrap().into_inner(), 12.345);
@@ -141,13 +169,100 @@ mod validators {
         // invalid
         assert_eq!(Dist::try_new(-1.0 / 0.0), Err(DistError::FiniteViolated));
         assert_eq!(Dist::try_new(1.0 / 0.0), Err(DistError::FiniteViolated));
-        assert_eq!(Dist::try_new(0.0 / 0.0), Err(DistError::FiniteViolated));
+        assert_eq!(Dist::try_new(f32::NAN), Err(DistError::FiniteViolated));
 
         // valid
         assert_eq!(Dist::try_new(12.345).unwrap().into_inner(), 12.345);
         assert_eq!(Dist::try_new(-999.12).unwrap().into_inner(), -999.12);
     }
 
+    #[test]
+    fn test_not_nan() {
+        #[nutype(validate(not_nan), derive(Debug, PartialEq))]
+        struct Dist(f64);
+
+        // invalid
+        assert_eq!(Dist::try_new(f64::NAN), Err(DistError::NotNanViolated));
+        assert_eq!(DistError::NotNanViolated.to_string(), "Dist is NaN.");
+
+        // valid
+        assert_eq!(Dist::try_new(12.345).unwrap().into_inner(), 12.345);
+        assert_eq!(
+            Dist::try_new(f64::INFINITY).unwrap().into_inner(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            Dist::try_new(f64::NEG_INFINITY).unwrap().into_inner(),
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn test_unit_interval() {
+        #[nutype(validate(unit_interval), derive(Debug))]
+        struct Probability(f64);
+
+        assert_eq!(
+            Probability::try_new(-0.1).unwrap_err(),
+            ProbabilityError::GreaterOrEqualViolated
+        );
+        assert_eq!(
+            Probability::try_new(1.01).unwrap_err(),
+            ProbabilityError::LessOrEqualViolated
+        );
+        assert_eq!(Probability::try_new(0.0).unwrap().into_inner(), 0.0);
+        assert_eq!(Probability::try_new(1.0).unwrap().into_inner(), 1.0);
+        assert_eq!(
+            Probability::try_new(f64::NAN).unwrap_err(),
+            ProbabilityError::NotNanViolated
+        );
+    }
+
+    #[test]
+    fn test_unit_interval_rejects_nan() {
+        #[nutype(validate(unit_interval), derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+        struct Unit(f32);
+
+        assert_eq!(
+            Unit::try_new(f32::NAN).unwrap_err(),
+            UnitError::NotNanViolated
+        );
+        let (low, high) = (Unit::try_new(0.25).unwrap(), Unit::try_new(0.75).unwrap());
+        assert_eq!(low.cmp(&high), core::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn test_percentage() {
+        #[nutype(validate(percentage, finite), derive(Debug))]
+        struct Humidity(f32);
+
+        assert_eq!(
+            Humidity::try_new(-1.0).unwrap_err(),
+            HumidityError::GreaterOrEqualViolated
+        );
+        assert_eq!(
+            Humidity::try_new(100.5).unwrap_err(),
+            HumidityError::LessOrEqualViolated
+        );
+        assert_eq!(Humidity::try_new(42.5).unwrap().into_inner(), 42.5);
+        assert_eq!(
+            Humidity::try_new(f32::NAN).unwrap_err(),
+            HumidityError::NotNanViolated
+        );
+    }
+
+    #[test]
+    fn test_in() {
+        #[nutype(validate(in = [0.25, 0.5, 1.0]), derive(Debug))]
+        struct Scale(f64);
+
+        assert_eq!(
+            Scale::try_new(0.75).unwrap_err(),
+            ScaleError::InViolated(0.75)
+        );
+        assert_eq!(Scale::try_new(0.5).unwrap().into_inner(), 0.5);
+    }
+
     mod when_boundaries_defined_as_constants {
         use super::*;
 
@@ -267,7 +382,7 @@ mod validators {
 
             assert_eq!(
                 err.to_string(),
-                "Percentage is too small. The value must be greater or equal to 0.0."
+                "Percentage is too small. The value must be at least 0.0."
             );
         }
     }
@@ -455,6 +570,26 @@ mod traits {
         assert_eq!(error, DistError::LessOrEqualViolated);
     }
 
+    #[test]
+    fn test_trait_try_from_str() {
+        #[nutype(
+            validate(greater_or_equal = 0.0, less_or_equal = 1.0),
+            derive(Debug, TryFrom),
+            try_from(&str)
+        )]
+        pub struct Ratio(f64);
+
+        assert_eq!(Ratio::try_from(" 0.25 ").unwrap().into_inner(), 0.25);
+        assert!(matches!(
+            Ratio::try_from("half"),
+            Err(RatioParseError::Parse(_))
+        ));
+        assert!(matches!(
+            Ratio::try_from("1.5"),
+            Err(RatioParseError::Validate(RatioError::LessOrEqualViolated))
+        ));
+    }
+
     #[test]
     fn test_trait_from_str_without_validation() {
         #[nutype(derive(Debug, FromStr))]
@@ -489,7 +624,7 @@ mod traits {
         let err: DistParseError = "12.35".parse::<Dist>().unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Failed to parse Dist: Dist is too big. The value must be less than 12.34."
+            "Failed to parse Dist: Dist is too big. The value must be at most 12.34."
         );
     }
 
@@ -502,6 +637,15 @@ mod traits {
         assert_eq!(size.to_string(), "35.7");
     }
 
+    #[test]
+    fn test_trait_display_with_format() {
+        #[nutype(derive(Display), display = "{:.2} kg")]
+        pub struct Weight(f64);
+
+        let weight = Weight::new(12.3456);
+        assert_eq!(weight.to_string(), "12.35 kg");
+    }
+
     #[test]
     fn test_trait_eq() {
         #[nutype(validate(finite), derive(PartialEq, Eq, Debug))]
@@ -562,6 +706,130 @@ mod traits {
             assert_eq!(sorted_raw_sizes, vec![2.0, 3.5, 5.5, 15.0, 44.5],);
         }
 
+        #[test]
+        fn test_trait_ord_with_not_nan() {
+            #[nutype(validate(not_nan), derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+            pub struct Size(f64);
+
+            let mut sizes: Vec<Size> = [3.5, f64::INFINITY, -0.0, f64::NEG_INFINITY, 0.0]
+                .into_iter()
+                .map(|s| Size::try_new(s).unwrap())
+                .collect();
+            sizes.sort();
+            let sorted_raw_sizes: Vec<f64> = sizes.into_iter().map(Size::into_inner).collect();
+            assert_eq!(
+                sorted_raw_sizes,
+                vec![f64::NEG_INFINITY, 0.0, 0.0, 3.5, f64::INFINITY]
+            );
+        }
+
+        #[test]
+        fn test_trait_ord_with_total_order() {
+            #[nutype(total_order, derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+            pub struct Reading(f64);
+
+            let mut readings: Vec<Reading> = [3.5, f64::NAN, 0.0, f64::NEG_INFINITY, -0.0]
+                .into_iter()
+                .map(Reading::new)
+                .collect();
+            readings.sort();
+            let sorted_raw_readings: Vec<f64> =
+                readings.into_iter().map(Reading::into_inner).collect();
+            assert_eq!(
+                sorted_raw_readings[..4],
+                [f64::NEG_INFINITY, -0.0, 0.0, 3.5]
+            );
+            assert!(sorted_raw_readings[1].is_sign_negative());
+            assert!(sorted_raw_readings[4].is_nan());
+
+            // Comparison traits agree with `cmp()`, so NaN is equal to itself
+            // and `-0.0` is not equal to `0.0`.
+            assert_eq!(Reading::new(f64::NAN), Reading::new(f64::NAN));
+            assert_ne!(Reading::new(-0.0), Reading::new(0.0));
+            assert_eq!(
+                Reading::new(-0.0).partial_cmp(&Reading::new(0.0)),
+                Some(Ordering::Less)
+            );
+        }
+
+        #[test]
+        fn test_signed_zeros_with_and_without_total_order() {
+            #[nutype(validate(finite), derive(Debug, PartialEq, Eq, PartialOrd, Ord))]
+            pub struct Numeric(f64);
+
+            assert_eq!(Numeric::try_new(-0.0), Numeric::try_new(0.0));
+            assert_eq!(
+                Numeric::try_new(-0.0)
+                    .unwrap()
+                    .cmp(&Numeric::try_new(0.0).unwrap()),
+                Ordering::Equal
+            );
+
+            // `total_order` is an explicit choice, so it's respected even with validation.
+            #[nutype(
+                total_order,
+                validate(finite),
+                derive(Debug, PartialEq, Eq, PartialOrd, Ord)
+            )]
+            pub struct Total(f64);
+
+            assert_ne!(Total::try_new(-0.0), Total::try_new(0.0));
+            assert_eq!(
+                Total::try_new(-0.0)
+                    .unwrap()
+                    .cmp(&Total::try_new(0.0).unwrap()),
+                Ordering::Less
+            );
+            assert_eq!(Total::try_new(0.0), Total::try_new(0.0));
+            assert_eq!(Total::try_new(-0.0), Total::try_new(-0.0));
+        }
+
+        #[test]
+        fn test_btree_map_key_with_total_order() {
+            use alloc::collections::BTreeMap;
+
+            #[nutype(
+                total_order,
+                validate(greater_or_equal = 0.0),
+                derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+            )]
+            pub struct Distance(f32);
+
+            let mut map = BTreeMap::new();
+            map.insert(Distance::try_new(2.5).unwrap(), "far");
+            map.insert(Distance::try_new(0.5).unwrap(), "near");
+            assert_eq!(map.get(&Distance::try_new(0.5).unwrap()), Some(&"near"));
+            assert_eq!(map.values().copied().collect::<Vec<_>>(), ["near", "far"]);
+        }
+
+        #[test]
+        fn test_trait_hash() {
+            use alloc::collections::BTreeMap;
+            use std::collections::HashSet;
+
+            #[nutype(
+                validate(finite),
+                derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)
+            )]
+            pub struct Size(f64);
+
+            should_implement_hash::<Size>();
+
+            let zero = Size::try_new(0.0).unwrap();
+            let negative_zero = Size::try_new(-0.0).unwrap();
+            let one = Size::try_new(1.0).unwrap();
+
+            let set: HashSet<Size> = [zero, negative_zero, one].into_iter().collect();
+            assert_eq!(set.len(), 2);
+            assert!(set.contains(&negative_zero));
+
+            let mut map = BTreeMap::new();
+            map.insert(one, "one");
+            map.insert(zero, "zero");
+            assert_eq!(map.get(&negative_zero), Some(&"zero"));
+            assert_eq!(map.keys().next(), Some(&zero));
+        }
+
         #[cfg(test)]
         mod prop_tests {
             use super::*;
@@ -718,7 +986,8 @@ mod traits {
         #[test]
         #[should_panic(expected = "Default value for type `Number` is invalid")]
         fn test_default_with_validation_when_invalid() {
-            #[nutype(validate(less_or_equal = 20.0), default = 20.1, derive(Default))]
+            // Invalid literals are rejected at compile time, so an expression is used here.
+            #[nutype(validate(less_or_equal = 20.0), default = 20.0 + 0.1, derive(Default))]
             pub struct Number(f64);
 
             Number::default();
@@ -803,3 +1072,426 @@ mod custom_error {
         assert_eq!(oh_my_float.into_inner(), 99.0);
     }
 }
+
+#[cfg(test)]
+mod parse_money {
+    use super::*;
+
+    #[test]
+    fn test_parse_money_without_validation() {
+        #[nutype(parse_money, derive(Debug, PartialEq))]
+        pub struct Amount(f64);
+
+        assert_eq!(
+            Amount::parse_money("$1,234.50").unwrap(),
+            Amount::new(1234.5)
+        );
+        assert_eq!(
+            Amount::parse_money("1 234,50 EUR").unwrap(),
+            Amount::new(1234.5)
+        );
+        assert_eq!(Amount::parse_money("-€12").unwrap(), Amount::new(-12.0));
+        assert_eq!(Amount::parse_money("€-12").unwrap(), Amount::new(-12.0));
+        assert_eq!(Amount::parse_money("(12.00)").unwrap(), Amount::new(-12.0));
+        assert_eq!(Amount::parse_money("0.999").unwrap(), Amount::new(0.999));
+
+        assert!(matches!(
+            Amount::parse_money("USD"),
+            Err(AmountParseMoneyError::InvalidAmount)
+        ));
+        assert!(matches!(
+            Amount::parse_money("12 dollars 50"),
+            Err(AmountParseMoneyError::InvalidAmount)
+        ));
+        assert!(matches!(
+            Amount::parse_money("1.2.3"),
+            Err(AmountParseMoneyError::InvalidAmount)
+        ));
+    }
+
+    #[test]
+    fn test_parse_money_sign() {
+        #[nutype(parse_money, derive(Debug, PartialEq))]
+        pub struct Amount(f64);
+
+        assert_eq!(Amount::parse_money("-5").unwrap(), Amount::new(-5.0));
+        assert_eq!(Amount::parse_money("$ -5").unwrap(), Amount::new(-5.0));
+        assert_eq!(Amount::parse_money("($5.00)").unwrap(), Amount::new(-5.0));
+        assert_eq!(Amount::parse_money("(5 USD)").unwrap(), Amount::new(-5.0));
+
+        for input in [
+            "--5", "5 USD-", "5-", "US-$5", "-(5)", "(-5)", "(5", "5)", "((5))", ")5(",
+        ] {
+            assert!(
+                matches!(
+                    Amount::parse_money(input),
+                    Err(AmountParseMoneyError::InvalidAmount)
+                ),
+                "{input:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_money_with_scale_and_validation() {
+        #[nutype(
+            parse_money(scale = 2),
+            validate(greater_or_equal = 0.0, less = 1_000_000.0),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Price(f64);
+
+        assert_eq!(Price::parse_money("$ 9.99").unwrap().into_inner(), 9.99);
+        assert!(matches!(
+            Price::parse_money("1.000.000,5 €"),
+            Err(PriceParseMoneyError::Validate(PriceError::LessViolated))
+        ));
+        assert!(matches!(
+            Price::parse_money("0.999"),
+            Err(PriceParseMoneyError::ScaleViolated)
+        ));
+        assert!(matches!(
+            Price::parse_money("-$5"),
+            Err(PriceParseMoneyError::Validate(
+                PriceError::GreaterOrEqualViolated
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_money_rejects_ambiguous_separator() {
+        #[nutype(parse_money, derive(Debug, PartialEq))]
+        pub struct Amount(f64);
+
+        assert!(matches!(
+            Amount::parse_money("1,234"),
+            Err(AmountParseMoneyError::AmbiguousSeparator)
+        ));
+        assert!(matches!(
+            Amount::parse_money("$1.234"),
+            Err(AmountParseMoneyError::AmbiguousSeparator)
+        ));
+        assert_eq!(
+            Amount::parse_money("1,234").unwrap_err().to_string(),
+            "Failed to parse Amount: ambiguous decimal separator"
+        );
+
+        // Not ambiguous: a leading zero, a different number of digits or both separators.
+        assert_eq!(Amount::parse_money("0,125").unwrap(), Amount::new(0.125));
+        assert_eq!(
+            Amount::parse_money("1234,567").unwrap(),
+            Amount::new(1234.567)
+        );
+        assert_eq!(Amount::parse_money("1,23").unwrap(), Amount::new(1.23));
+        assert_eq!(Amount::parse_money("1,234.5").unwrap(), Amount::new(1234.5));
+    }
+
+    #[test]
+    fn test_parse_money_with_explicit_decimal_separator() {
+        #[nutype(parse_money(decimal_separator = '.'), derive(Debug, PartialEq))]
+        pub struct Dollars(f64);
+
+        assert_eq!(
+            Dollars::parse_money("$1,234").unwrap(),
+            Dollars::new(1234.0)
+        );
+        assert_eq!(Dollars::parse_money("1.234").unwrap(), Dollars::new(1.234));
+        assert_eq!(
+            Dollars::parse_money("1,234,567.89").unwrap(),
+            Dollars::new(1234567.89)
+        );
+        assert!(matches!(
+            Dollars::parse_money("1.234.567"),
+            Err(DollarsParseMoneyError::InvalidAmount)
+        ));
+
+        #[nutype(
+            parse_money(scale = 2, decimal_separator = ','),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Euros(f64);
+
+        assert!(matches!(
+            Euros::parse_money("1,234 €"),
+            Err(EurosParseMoneyError::ScaleViolated)
+        ));
+        assert_eq!(Euros::parse_money("1.234 €").unwrap(), Euros::new(1234.0));
+        assert_eq!(
+            Euros::parse_money("1.234.567,5").unwrap(),
+            Euros::new(1234567.5)
+        );
+    }
+}
+
+mod eq_raw {
+    use super::*;
+
+    #[test]
+    fn test_eq_raw_applies_sanitizers() {
+        #[nutype(sanitize(with = |n: f64| n.abs()))]
+        struct Distance(f64);
+
+        let distance = Distance::new(2.5);
+        assert!(distance.eq_raw(-2.5));
+        assert!(!distance.eq_raw(2.0));
+    }
+}
+
+#[cfg(test)]
+mod optional {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_and_validate_some() {
+        #[nutype(
+            sanitize(with = |n| n.clamp(0.0, 10.0)),
+            validate(finite),
+            derive(Debug, Clone, Copy, PartialEq)
+        )]
+        struct Rating(Option<f64>);
+
+        assert_eq!(Rating::try_new(None).unwrap().into_inner(), None);
+        assert_eq!(
+            Rating::try_new(Some(12.0)).unwrap().into_inner(),
+            Some(10.0)
+        );
+        assert_eq!(
+            Rating::try_new(Some(f64::NAN)),
+            Err(RatingError::FiniteViolated)
+        );
+    }
+
+    #[test]
+    fn test_required() {
+        #[nutype(required, derive(Debug))]
+        struct Weight(Option<f32>);
+
+        assert_eq!(
+            Weight::try_new(None).unwrap_err(),
+            WeightError::RequiredViolated
+        );
+        assert_eq!(Weight::try_new(Some(1.5)).unwrap().into_inner(), Some(1.5));
+    }
+}
+
+#[cfg(test)]
+mod error_codes {
+    use super::*;
+
+    #[test]
+    fn test_code_and_bound() {
+        #[nutype(validate(finite, greater = 0.0, less_or_equal = 1.0), derive(Debug))]
+        pub struct Ratio(f64);
+
+        let err = Ratio::try_new(f64::NAN).unwrap_err();
+        assert_eq!(err.code(), "finite");
+        assert_eq!(err.bound(), None);
+
+        let err = Ratio::try_new(1.5).unwrap_err();
+        assert_eq!(err.code(), "less_or_equal");
+        assert_eq!(err.bound(), Some(1.0));
+    }
+}
+
+#[cfg(test)]
+mod ffi {
+    use super::*;
+
+    #[test]
+    fn test_try_new_and_get() {
+        #[nutype(ffi, validate(finite), derive(Debug))]
+        pub struct Ratio(f64);
+
+        let mut ratio = core::mem::MaybeUninit::<Ratio>::uninit();
+        // SAFETY: `ratio` is valid for writes.
+        assert!(unsafe { Ratio::ffi_try_new(0.5, ratio.as_mut_ptr()) });
+        // SAFETY: `ffi_try_new()` returned `true`, so the value is initialized.
+        assert_eq!(unsafe { ratio.assume_init_ref() }.ffi_get(), 0.5);
+        // SAFETY: `ratio` is valid for writes.
+        assert!(!unsafe { Ratio::ffi_try_new(f64::NAN, ratio.as_mut_ptr()) });
+    }
+}
+
+#[cfg(feature = "from_schema")]
+mod from_schema {
+    use super::*;
+    use nutype::nutype_from_schema;
+
+    #[test]
+    fn test_rules_from_schema() {
+        #[nutype_from_schema(r#"{ "type": "number", "exclusiveMinimum": 0, "maximum": 1 }"#)]
+        #[nutype(validate(finite), derive(Debug, PartialEq))]
+        pub struct Probability(f64);
+
+        assert_eq!(Probability::try_new(0.5).unwrap().into_inner(), 0.5);
+        assert_eq!(
+            Probability::try_new(0.0),
+            Err(ProbabilityError::GreaterViolated)
+        );
+        assert_eq!(
+            Probability::try_new(1.5),
+            Err(ProbabilityError::LessOrEqualViolated)
+        );
+        assert_eq!(
+            Probability::try_new(f64::NAN),
+            Err(ProbabilityError::FiniteViolated)
+        );
+    }
+}
+
+#[cfg(test)]
+mod combinators {
+    use super::*;
+
+    #[test]
+    fn test_any_of() {
+        #[nutype(
+            validate(finite, any_of(less_or_equal = -1.0, greater_or_equal = 1.0)),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Magnitude(f64);
+
+        assert!(Magnitude::try_new(-2.5).is_ok());
+        assert!(Magnitude::try_new(1.0).is_ok());
+        assert_eq!(Magnitude::try_new(0.5), Err(MagnitudeError::AnyOfViolated));
+    }
+}
+
+#[cfg(test)]
+mod decimal_places {
+    use super::*;
+
+    #[test]
+    fn test_round_dp() {
+        #[nutype(sanitize(round_dp = 2), derive(Debug))]
+        pub struct Price(f64);
+
+        assert_eq!(Price::new(19.994).into_inner(), 19.99);
+        assert_eq!(Price::new(19.995).into_inner(), 20.0);
+        assert_eq!(Price::new(-0.125).into_inner(), -0.13);
+        assert_eq!(Price::new(1e300).into_inner(), 1e300);
+        assert!(Price::new(f64::NAN).into_inner().is_nan());
+    }
+
+    #[test]
+    fn test_round_dp_f32() {
+        #[nutype(sanitize(round_dp = 1), derive(Debug))]
+        pub struct Weight(f32);
+
+        assert_eq!(Weight::new(2.46).into_inner(), 2.5);
+        assert_eq!(Weight::new(f32::INFINITY).into_inner(), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_round_dp_rounds_the_decimal_representation() {
+        #[nutype(sanitize(round_dp = 2), derive(Debug))]
+        pub struct Price(f64);
+
+        // Stored as 1.00499999..., 2.67499999... and -1.55499999..., but rounded as written.
+        assert_eq!(Price::new(1.005).into_inner(), 1.01);
+        assert_eq!(Price::new(2.675).into_inner(), 2.68);
+        assert_eq!(Price::new(-1.555).into_inner(), -1.56);
+        assert_eq!(Price::new(0.005).into_inner(), 0.01);
+        assert_eq!(Price::new(0.004).into_inner(), 0.0);
+        assert_eq!(Price::new(1e-300).into_inner(), 0.0);
+
+        #[nutype(sanitize(round_dp = 2), derive(Debug))]
+        pub struct Weight(f32);
+
+        assert_eq!(Weight::new(1.005).into_inner(), 1.01);
+        assert_eq!(Weight::new(-1.555).into_inner(), -1.56);
+    }
+
+    #[test]
+    fn test_round_dp_of_large_magnitudes() {
+        #[nutype(sanitize(round_dp = 2), derive(Debug))]
+        pub struct Price(f64);
+
+        assert_eq!(Price::new(123_456_789.125).into_inner(), 123_456_789.13);
+        assert_eq!(Price::new(-9.2e18).into_inner(), -9.2e18);
+        assert_eq!(Price::new(1.5e19).into_inner(), 1.5e19);
+        assert_eq!(Price::new(f64::MAX).into_inner(), f64::MAX);
+        assert_eq!(Price::new(f64::MIN).into_inner(), f64::MIN);
+
+        #[nutype(sanitize(round_dp = 0), derive(Debug))]
+        pub struct Units(f64);
+
+        assert_eq!(
+            Units::new(4_503_599_627_370_495.5).into_inner(),
+            4_503_599_627_370_496.0
+        );
+        assert_eq!(Units::new(-2.5).into_inner(), -3.0);
+
+        #[nutype(sanitize(round_dp = 3), derive(Debug))]
+        pub struct Weight(f32);
+
+        assert_eq!(Weight::new(f32::MAX).into_inner(), f32::MAX);
+        assert_eq!(Weight::new(16_777_217.0).into_inner(), 16_777_216.0);
+    }
+
+    #[test]
+    fn test_max_dp() {
+        #[nutype(validate(max_dp = 2), derive(Debug, PartialEq, Eq))]
+        pub struct Amount(f64);
+
+        assert!(Amount::try_new(10.0).is_ok());
+        assert!(Amount::try_new(19.99).is_ok());
+        assert_eq!(Amount::try_new(0.1 + 0.2), Err(AmountError::MaxDpViolated));
+        assert_eq!(Amount::try_new(f64::NAN), Err(AmountError::MaxDpViolated));
+
+        let err = Amount::try_new(1.001).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Amount has too many decimal places. The value must have at most 2 decimal places."
+        );
+        assert_eq!(err.code(), "max_dp");
+    }
+
+    #[test]
+    fn test_max_dp_checks_the_decimal_representation() {
+        #[nutype(validate(max_dp = 2), derive(Debug, PartialEq, Eq))]
+        pub struct Amount(f64);
+
+        assert!(Amount::try_new(1.01).is_ok());
+        assert!(Amount::try_new(2.67).is_ok());
+        assert!(Amount::try_new(1e300).is_ok());
+        assert!(Amount::try_new(f64::MAX).is_ok());
+        assert_eq!(Amount::try_new(1.005), Err(AmountError::MaxDpViolated));
+        assert_eq!(Amount::try_new(1e-300), Err(AmountError::MaxDpViolated));
+    }
+
+    #[test]
+    fn test_round_dp_then_max_dp() {
+        #[nutype(
+            sanitize(round_dp = 2),
+            validate(max_dp = 2, greater_or_equal = 0.0),
+            derive(Debug)
+        )]
+        pub struct Balance(f64);
+
+        assert_eq!(Balance::try_new(0.1 + 0.2).unwrap().into_inner(), 0.3);
+    }
+}
+
+mod metadata {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        #[nutype(validate(finite, greater = 0.0, less_or_equal = 1.5), derive(Debug))]
+        pub struct Ratio(f32);
+
+        let bounds: Vec<(&str, Option<f64>)> = Ratio::METADATA
+            .validators()
+            .map(|validator| (validator.name, validator.bound))
+            .collect();
+        assert_eq!(
+            bounds,
+            [
+                ("finite", None),
+                ("greater", Some(0.0)),
+                ("less_or_equal", Some(1.5))
+            ]
+        );
+    }
+}
@@ -36,6 +36,15 @@ mod sanitizers {
 
             assert_eq!(Cent::new(222).into_inner(), 100);
         }
+
+        #[test]
+        fn test_many_with_sanitizers() {
+            #[nutype(sanitize(with = |n: i32| n.abs(), with = sanitize_cent, with = core::convert::identity::<i32>))]
+            pub struct Cent(i32);
+
+            assert_eq!(Cent::new(-42).into_inner(), 42);
+            assert_eq!(Cent::new(-222).into_inner(), 100);
+        }
     }
 
     #[test]
@@ -112,6 +121,90 @@ mod validators {
         assert_eq!(Age::try_new(25).unwrap().into_inner(), 25);
     }
 
+    #[test]
+    fn test_bound_consts() {
+        #[nutype(validate(greater_or_equal = 18, less = 100), derive(Debug))]
+        struct Age(u8);
+
+        assert_eq!(Age::MIN, 18);
+        assert_eq!(Age::MAX_EXCLUSIVE, 100);
+
+        #[nutype(validate(greater = -10, less_or_equal = 10), derive(Debug))]
+        struct Offset(i32);
+
+        assert_eq!(Offset::MIN_EXCLUSIVE, -10);
+        assert_eq!(Offset::MAX, 10);
+    }
+
+    #[test]
+    fn test_percentage() {
+        #[nutype(validate(percentage), derive(Debug))]
+        struct Discount(i32);
+
+        assert_eq!(
+            Discount::try_new(-1).unwrap_err(),
+            DiscountError::GreaterOrEqualViolated
+        );
+        assert_eq!(
+            Discount::try_new(101).unwrap_err(),
+            DiscountError::LessOrEqualViolated
+        );
+        assert_eq!(Discount::try_new(0).unwrap().into_inner(), 0);
+        assert_eq!(Discount::try_new(100).unwrap().into_inner(), 100);
+    }
+
+    #[test]
+    fn test_port() {
+        #[nutype(validate(port), derive(Debug))]
+        struct Port(u16);
+
+        assert_eq!(
+            Port::try_new(0).unwrap_err(),
+            PortError::GreaterOrEqualViolated
+        );
+        assert_eq!(Port::try_new(1).unwrap().into_inner(), 1);
+        assert_eq!(Port::try_new(65535).unwrap().into_inner(), 65535);
+
+        #[nutype(validate(not_reserved), derive(Debug))]
+        struct ListenPort(u32);
+
+        assert_eq!(
+            ListenPort::try_new(80).unwrap_err(),
+            ListenPortError::GreaterOrEqualViolated
+        );
+        assert_eq!(
+            ListenPort::try_new(65536).unwrap_err(),
+            ListenPortError::LessOrEqualViolated
+        );
+        assert_eq!(ListenPort::try_new(8080).unwrap().into_inner(), 8080);
+    }
+
+    #[test]
+    fn test_in() {
+        #[nutype(validate(in = [1, 2, 4, 8]), derive(Debug))]
+        struct Lanes(u8);
+
+        assert_eq!(Lanes::try_new(3).unwrap_err(), LanesError::InViolated(3));
+        assert_eq!(Lanes::try_new(4).unwrap().into_inner(), 4);
+        assert_eq!(
+            LanesError::InViolated(3).to_string(),
+            "Lanes is not allowed. The value must be one of [1, 2, 4, 8], got 3."
+        );
+    }
+
+    #[test]
+    fn test_number_literals() {
+        #[nutype(validate(greater_or_equal = -1_000i32, less_or_equal = 0xFF), derive(Debug))]
+        struct Delta(i32);
+
+        assert_eq!(Delta::MIN, -1000);
+        assert_eq!(Delta::MAX, 255);
+        assert_eq!(
+            Delta::try_new(256).unwrap_err(),
+            DeltaError::LessOrEqualViolated
+        );
+    }
+
     mod when_boundaries_defined_as_constants {
         use super::*;
 
@@ -146,6 +239,29 @@ mod validators {
             assert_eq!(Hour::try_new(25).unwrap_err(), HourError::LessViolated);
             assert_eq!(Hour::try_new(24).unwrap().into_inner(), 24);
         }
+
+        const OFFSET_LIMIT: i64 = 12;
+
+        #[nutype(
+            validate(greater_or_equal = -OFFSET_LIMIT, less_or_equal = u16::MAX as i64 * 2),
+            derive(Debug)
+        )]
+        struct Offset(i64);
+
+        #[test]
+        fn test_boundaries_defined_as_const_expressions() {
+            assert_eq!(Offset::MIN, -12);
+            assert_eq!(Offset::MAX, 131070);
+            assert_eq!(
+                Offset::try_new(-13).unwrap_err(),
+                OffsetError::GreaterOrEqualViolated
+            );
+            assert_eq!(Offset::try_new(-12).unwrap().into_inner(), -12);
+            assert_eq!(
+                Offset::try_new(131071).unwrap_err(),
+                OffsetError::LessOrEqualViolated
+            );
+        }
     }
 
     #[cfg(test)]
@@ -234,7 +350,7 @@ mod validators {
 
             assert_eq!(
                 err.to_string(),
-                "Age is too small. The value must be greater or equal to 18."
+                "Age is too small. The value must be at least 18."
             );
         }
     }
@@ -578,6 +694,21 @@ mod traits {
         assert_eq!(age, 32);
     }
 
+    #[test]
+    fn test_trait_into_listed_types() {
+        #[nutype(derive(Into), into(u16, i64, f64))]
+        pub struct Age(u8);
+
+        let age: u16 = Age::new(32).into();
+        assert_eq!(age, 32);
+
+        let age: i64 = Age::new(33).into();
+        assert_eq!(age, 33);
+
+        let age: f64 = Age::new(34).into();
+        assert_eq!(age, 34.0);
+    }
+
     #[test]
     fn test_trait_from() {
         #[nutype(derive(From))]
@@ -630,6 +761,107 @@ mod traits {
         assert_eq!(error, AmountError::LessOrEqualViolated);
     }
 
+    #[test]
+    fn test_trait_try_from_listed_types() {
+        #[nutype(
+            validate(less_or_equal = 1000),
+            derive(Debug, TryFrom),
+            try_from(u8, u16, i32)
+        )]
+        pub struct Amount(i64);
+
+        let amount = Amount::try_from(200u8).unwrap();
+        assert_eq!(amount.into_inner(), 200);
+
+        let amount = Amount::try_from(1000u16).unwrap();
+        assert_eq!(amount.into_inner(), 1000);
+
+        let error = Amount::try_from(1001i32).unwrap_err();
+        assert_eq!(error, AmountError::LessOrEqualViolated);
+    }
+
+    #[test]
+    fn test_trait_try_from_narrowing_types() {
+        #[nutype(
+            validate(greater_or_equal = 1),
+            derive(Debug, TryFrom),
+            try_from(u8, i64, usize)
+        )]
+        pub struct Quantity(u16);
+
+        // Lossless conversions keep the regular error type
+        let error: QuantityError = Quantity::try_from(0u8).unwrap_err();
+        assert_eq!(error, QuantityError::GreaterOrEqualViolated);
+
+        assert_eq!(Quantity::try_from(500i64).unwrap().into_inner(), 500);
+        assert_eq!(Quantity::try_from(7usize).unwrap().into_inner(), 7);
+        assert!(matches!(
+            Quantity::try_from(-1i64),
+            Err(QuantityTryFromError::OutOfRange(_))
+        ));
+        assert!(matches!(
+            Quantity::try_from(0i64),
+            Err(QuantityTryFromError::Validate(
+                QuantityError::GreaterOrEqualViolated
+            ))
+        ));
+
+        let error = Quantity::try_from(70_000usize).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Failed to convert into Quantity: out of range integral type conversion attempted"
+        );
+    }
+
+    #[test]
+    fn test_trait_try_from_narrowing_types_without_validation() {
+        #[nutype(derive(Debug, TryFrom), try_from(i32))]
+        pub struct Level(u8);
+
+        assert_eq!(Level::try_from(255).unwrap().into_inner(), 255);
+        assert!(matches!(
+            Level::try_from(256),
+            Err(LevelTryFromError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_trait_try_from_str() {
+        #[nutype(
+            validate(greater_or_equal = 1, less_or_equal = 65535),
+            derive(Debug, TryFrom),
+            try_from(&str, u16)
+        )]
+        pub struct Port(u32);
+
+        assert_eq!(Port::try_from(" 8080\n").unwrap().into_inner(), 8080);
+        assert_eq!(Port::try_from(443u16).unwrap().into_inner(), 443);
+        assert!(matches!(
+            Port::try_from("http"),
+            Err(PortParseError::Parse(_))
+        ));
+        assert!(matches!(
+            Port::try_from("70000"),
+            Err(PortParseError::Validate(PortError::LessOrEqualViolated))
+        ));
+        assert_eq!(
+            Port::try_from("0").unwrap_err().to_string(),
+            "Failed to parse Port: Port is too small. The value must be between 1 and 65535."
+        );
+    }
+
+    #[test]
+    fn test_trait_try_from_str_with_from_str() {
+        #[nutype(derive(Debug, FromStr, TryFrom), try_from(&str))]
+        pub struct Workers(u8);
+
+        assert_eq!(Workers::try_from(" 4 ").unwrap().into_inner(), 4);
+        // `FromStr` does not trim the input
+        assert!(" 4 ".parse::<Workers>().is_err());
+        let err: WorkersParseError = Workers::try_from("many").unwrap_err();
+        assert!(matches!(err, WorkersParseError::Parse(_)));
+    }
+
     #[test]
     fn test_trait_from_str_without_validation() {
         #[nutype(derive(Debug, FromStr))]
@@ -664,7 +896,7 @@ mod traits {
         let err: AgeParseError = "101".parse::<Age>().unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Failed to parse Age: Age is too big. The value must be less or equal to 99."
+            "Failed to parse Age: Age is too big. The value must be at most 99."
         );
     }
 
@@ -677,6 +909,15 @@ mod traits {
         assert_eq!(age.to_string(), "35");
     }
 
+    #[test]
+    fn test_trait_display_with_format() {
+        #[nutype(derive(Display), display = "Order #{:06}")]
+        pub struct OrderId(u32);
+
+        let order_id = OrderId::new(42);
+        assert_eq!(order_id.to_string(), "Order #000042");
+    }
+
     #[cfg(feature = "serde")]
     mod serialization {
         use super::*;
@@ -733,6 +974,42 @@ mod traits {
                     assert_eq!(offset.into_inner(), 13);
                 }
             }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn test_serde_field_mode() {
+                #[nutype(
+                    validate(greater_or_equal = 13),
+                    derive(Serialize, Deserialize, Debug),
+                    serde(field = "value")
+                )]
+                pub struct Offset(i64);
+
+                let offset_json = serde_json::to_string(&Offset::try_new(42).unwrap()).unwrap();
+                assert_eq!(offset_json, r#"{"value":42}"#);
+
+                let offset: Offset = serde_json::from_str(r#"{"unit":"px","value":15}"#).unwrap();
+                assert_eq!(offset.into_inner(), 15);
+
+                let err = serde_json::from_str::<Offset>(r#"{"value":12}"#).unwrap_err();
+                assert!(err.to_string().contains("Expected valid Offset"));
+
+                let err = serde_json::from_str::<Offset>(r#"{"unit":"px"}"#).unwrap_err();
+                assert!(err.to_string().contains("missing field `value`"));
+
+                let err = serde_json::from_str::<Offset>("42").unwrap_err();
+                assert!(err.to_string().contains("struct Offset with field `value`"));
+            }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn test_serde_transparent_mode() {
+                #[nutype(derive(Serialize, Deserialize), serde(transparent))]
+                pub struct Offset(i64);
+
+                let offset: Offset = serde_json::from_str("-259").unwrap();
+                assert_eq!(serde_json::to_string(&offset).unwrap(), "-259");
+            }
         }
 
         mod ron_format {
@@ -767,6 +1044,26 @@ mod traits {
 
                 assert_eq!(deserialized, weight);
             }
+
+            #[test]
+            fn test_rmp_roundtrip_with_serde_field_mode() {
+                #[nutype(
+                    derive(Serialize, Deserialize, PartialEq, Debug),
+                    serde(field = "weight")
+                )]
+                pub struct Weight(u8);
+
+                let weight = Weight::new(102);
+
+                // MessagePack encodes structs as arrays by default
+                let bytes = rmp_serde::to_vec(&weight).unwrap();
+                let deserialized: Weight = rmp_serde::from_slice(&bytes).unwrap();
+                assert_eq!(deserialized, weight);
+
+                let bytes = rmp_serde::to_vec_named(&weight).unwrap();
+                let deserialized: Weight = rmp_serde::from_slice(&bytes).unwrap();
+                assert_eq!(deserialized, weight);
+            }
         }
     }
 
@@ -793,7 +1090,8 @@ mod traits {
         #[test]
         #[should_panic(expected = "Default value for type `Number` is invalid")]
         fn test_default_with_validation_when_invalid() {
-            #[nutype(validate(less_or_equal = 20), default = 21, derive(Default))]
+            // Invalid literals are rejected at compile time, so an expression is used here.
+            #[nutype(validate(less_or_equal = 20), default = 20 + 1, derive(Default))]
             pub struct Number(i16);
 
             Number::default();
@@ -831,6 +1129,23 @@ mod derive_schemars_json_schema {
         // Make sure it compiles
         let _schema = schema_for!(CustomerId);
     }
+
+    #[test]
+    fn test_json_schema_with_metadata() {
+        #[nutype(derive(JsonSchema), schema(format = "int32", example = -40, example = 451))]
+        pub struct Temperature(i32);
+
+        let schema = schema_for!(Temperature).schema;
+        assert_eq!(schema.format.as_deref(), Some("int32"));
+        let examples = schema.metadata.unwrap().examples;
+        assert_eq!(
+            examples,
+            vec![
+                schemars::_serde_json::json!(-40),
+                schemars::_serde_json::json!(451)
+            ]
+        );
+    }
 }
 
 mod custom_error {
@@ -880,3 +1195,1133 @@ mod custom_error {
         assert_eq!(podd.into_inner(), 3);
     }
 }
+
+mod legacy {
+    use super::*;
+
+    #[test]
+    fn test_try_migrate() {
+        #[nutype(
+            sanitize(with = |n| n.clamp(0, 100)),
+            validate(greater_or_equal = 0, less_or_equal = 100),
+            legacy(validate(greater_or_equal = -1000, less_or_equal = 1000)),
+            derive(Debug, PartialEq)
+        )]
+        struct Score(i32);
+
+        assert_eq!(Score::try_migrate(250).unwrap().into_inner(), 100);
+        assert_eq!(Score::try_migrate(-5).unwrap().into_inner(), 0);
+        assert_eq!(
+            Score::try_migrate(1001),
+            Err(ScoreMigrateError::Legacy(
+                ScoreLegacyError::LessOrEqualViolated
+            ))
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct UnluckyError;
+
+    fn validate_lucky(value: &u32) -> Result<(), UnluckyError> {
+        if *value != 13 {
+            Ok(())
+        } else {
+            Err(UnluckyError)
+        }
+    }
+
+    #[nutype(
+        validate(with = validate_lucky, error = UnluckyError),
+        legacy(validate(less = 100)),
+        derive(Debug)
+    )]
+    struct Lucky(u32);
+
+    #[test]
+    fn test_try_migrate_with_custom_current_error() {
+        assert_eq!(Lucky::try_migrate(42).unwrap().into_inner(), 42);
+        assert!(matches!(
+            Lucky::try_migrate(13),
+            Err(LuckyMigrateError::Current(UnluckyError))
+        ));
+        assert!(matches!(
+            Lucky::try_migrate(200),
+            Err(LuckyMigrateError::Legacy(LuckyLegacyError::LessViolated))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod as_variants {
+    use super::*;
+
+    #[nutype(validate(in = [-1, 0, 1]), as_variants, derive(Debug))]
+    struct Sign(i8);
+
+    #[test]
+    fn test_as_variant() {
+        assert_eq!(
+            Sign::try_new(-1).unwrap().as_variant(),
+            SignVariant::ValueMinus1
+        );
+        assert_eq!(Sign::try_new(0).unwrap().as_variant(), SignVariant::Value0);
+        assert_eq!(Sign::try_new(1).unwrap().as_variant(), SignVariant::Value1);
+    }
+
+    #[test]
+    fn test_variant_display_and_from_str() {
+        assert_eq!(SignVariant::ValueMinus1.to_string(), "-1");
+        assert_eq!("-1".parse::<SignVariant>(), Ok(SignVariant::ValueMinus1));
+        assert_eq!("1".parse::<SignVariant>(), Ok(SignVariant::Value1));
+        assert_eq!("2".parse::<SignVariant>(), Err(SignVariantParseError));
+        assert_eq!("one".parse::<SignVariant>(), Err(SignVariantParseError));
+    }
+}
+
+mod eq_raw {
+    use super::*;
+
+    #[test]
+    fn test_eq_raw_applies_sanitizers() {
+        #[nutype(sanitize(with = |n: i32| n.clamp(0, 100)), validate(greater_or_equal = 0))]
+        struct Percentage(i32);
+
+        let full = Percentage::try_new(100).unwrap();
+        assert!(full.eq_raw(100));
+        assert!(full.eq_raw(250));
+        assert!(!full.eq_raw(99));
+    }
+}
+
+mod bounded {
+    use super::*;
+    use nutype::Bounded;
+
+    #[nutype(
+        bounded,
+        validate(greater_or_equal = 1, less_or_equal = 12),
+        derive(
+            Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, AsRef, Deref, Into
+        )
+    )]
+    struct Month(u8);
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(Month::try_new(0), Err(MonthError::GreaterOrEqualViolated));
+        assert_eq!(Month::try_new(13), Err(MonthError::LessOrEqualViolated));
+        assert_eq!(Month::try_new(12).unwrap().into_inner(), 12);
+    }
+
+    #[test]
+    fn test_traits() {
+        let month = Month::try_new(7).unwrap();
+        assert_eq!(format!("{month:?}"), "Month(7)");
+        assert_eq!(month.to_string(), "7");
+        assert_eq!(*month.as_ref(), 7);
+        assert_eq!(*month, 7);
+        assert!(month < Month::try_new(8).unwrap());
+        assert_eq!(u8::from(month), 7);
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(core::mem::size_of::<Month>(), 1);
+    }
+
+    #[test]
+    fn test_exclusive_and_missing_bounds() {
+        #[nutype(bounded, validate(greater = -1), derive(Debug, PartialEq))]
+        struct NonNegative(i64);
+
+        assert_eq!(
+            NonNegative::try_new(-1),
+            Err(NonNegativeError::GreaterViolated)
+        );
+        assert_eq!(NonNegative::try_new(0).unwrap().into_inner(), 0);
+        assert_eq!(
+            NonNegative::try_new(i64::MAX).unwrap().into_inner(),
+            i64::MAX
+        );
+    }
+
+    const LEVEL_LIMIT: u128 = 101;
+
+    #[test]
+    fn test_bounds_from_expressions_and_edges() {
+        #[nutype(bounded, validate(greater = 0, less = LEVEL_LIMIT), derive(Debug, PartialEq))]
+        struct Level(u128);
+
+        assert_eq!(Level::try_new(0), Err(LevelError::GreaterViolated));
+        assert_eq!(Level::try_new(101), Err(LevelError::LessViolated));
+        assert_eq!(Level::try_new(100).unwrap().into_inner(), 100);
+
+        #[nutype(
+            bounded,
+            validate(less_or_equal = 170141183460469231731687303715884105727),
+            derive(Debug, PartialEq)
+        )]
+        struct Huge(u128);
+
+        assert_eq!(
+            Huge::try_new(i128::MAX as u128).unwrap().into_inner(),
+            i128::MAX as u128
+        );
+        assert_eq!(
+            Huge::try_new(i128::MAX as u128 + 1),
+            Err(HugeError::LessOrEqualViolated)
+        );
+
+        #[nutype(bounded, validate(greater = i128::MIN, less = i128::MAX), derive(Debug, PartialEq))]
+        struct Inner(i128);
+
+        assert_eq!(Inner::try_new(i128::MIN), Err(InnerError::GreaterViolated));
+        assert_eq!(Inner::try_new(i128::MAX), Err(InnerError::LessViolated));
+        assert_eq!(Inner::try_new(0).unwrap().into_inner(), 0);
+    }
+
+    #[test]
+    fn test_bounded_runtime_type() {
+        type Percent = Bounded<0, 100, u8>;
+
+        assert_eq!(Percent::try_new(100).unwrap().get(), 100);
+        assert_eq!(Percent::try_new(101), Err(nutype::BoundedError::AboveMax));
+        assert_eq!(
+            Bounded::<0, { i128::MAX }, u128>::try_new(u128::MAX),
+            Err(nutype::BoundedError::AboveMax)
+        );
+        assert_eq!(
+            Bounded::<-5, 5, i32>::try_new(-6),
+            Err(nutype::BoundedError::BelowMin)
+        );
+    }
+}
+
+#[cfg(test)]
+mod optional {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_and_validate_some() {
+        #[nutype(
+            sanitize(with = |n| n.clamp(0, 200)),
+            validate(greater_or_equal = 18),
+            derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)
+        )]
+        struct Age(Option<u8>);
+
+        assert_eq!(Age::try_new(None).unwrap().into_inner(), None);
+        assert_eq!(Age::try_new(Some(250)).unwrap().into_inner(), Some(200));
+        assert_eq!(
+            Age::try_new(Some(17)),
+            Err(AgeError::GreaterOrEqualViolated)
+        );
+        assert!(Age::try_new(None).unwrap() < Age::try_new(Some(18)).unwrap());
+    }
+
+    #[test]
+    fn test_required() {
+        #[nutype(required, validate(less = 100), derive(Debug))]
+        struct Percent(Option<i32>);
+
+        assert_eq!(
+            Percent::try_new(None).unwrap_err(),
+            PercentError::RequiredViolated
+        );
+        assert_eq!(
+            Percent::try_new(Some(100)).unwrap_err(),
+            PercentError::LessViolated
+        );
+        assert_eq!(Percent::try_new(Some(5)).unwrap().as_inner(), Some(&5));
+    }
+}
+
+#[cfg(test)]
+mod newtype_traits {
+    use super::*;
+    use nutype::{Newtype, TryNewtype};
+
+    fn try_new_all<T: TryNewtype>(values: Vec<T::Inner>) -> Result<Vec<T>, T::Error> {
+        values.into_iter().map(T::try_new).collect()
+    }
+
+    fn sum<T: Newtype<Inner = u32>>(values: &[T]) -> u32 {
+        values.iter().map(|value| *value.get()).sum()
+    }
+
+    #[test]
+    fn test_with_validation() {
+        #[nutype(validate(less_or_equal = 100), derive(Debug, PartialEq))]
+        struct Percentage(u32);
+
+        let values = try_new_all::<Percentage>(vec![10, 20]).unwrap();
+        assert_eq!(sum(&values), 30);
+        assert_eq!(
+            try_new_all::<Percentage>(vec![10, 200]),
+            Err(PercentageError::LessOrEqualViolated)
+        );
+        assert_eq!(Newtype::into_inner(Percentage::try_new(7).unwrap()), 7);
+    }
+
+    #[test]
+    fn test_without_validation() {
+        #[nutype(sanitize(with = |n| n.min(10)), derive(Debug))]
+        struct Small(u32);
+
+        let values: Result<Vec<Small>, core::convert::Infallible> =
+            try_new_all::<Small>(vec![5, 50]);
+        assert_eq!(sum(&values.unwrap()), 15);
+    }
+
+    #[test]
+    fn test_optional() {
+        #[nutype(validate(greater = 0), derive(Debug))]
+        struct Quantity(Option<u32>);
+
+        let quantity = <Quantity as TryNewtype>::try_new(Some(3)).unwrap();
+        assert_eq!(quantity.get(), &Some(3));
+        assert_eq!(
+            <Quantity as TryNewtype>::try_new(Some(0)).unwrap_err(),
+            QuantityError::GreaterViolated
+        );
+    }
+}
+
+#[cfg(test)]
+mod secret {
+    use super::*;
+
+    #[test]
+    fn test_debug_is_redacted() {
+        #[nutype(secret, validate(less_or_equal = 9999), derive(Debug, Clone, Copy))]
+        pub struct PinCode(u16);
+
+        let pin = PinCode::try_new(1234).unwrap();
+        assert_eq!(format!("{pin:?}"), "PinCode(***)");
+        assert_eq!(pin.into_inner(), 1234);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_on_drop() {
+        use nutype::Newtype;
+
+        #[nutype(secret(zeroize), derive(Debug))]
+        pub struct Seed(u64);
+
+        assert!(core::mem::needs_drop::<Seed>());
+        assert_eq!(Newtype::into_inner(Seed::new(42)), 42);
+    }
+}
+
+#[cfg(test)]
+mod profile {
+    use super::*;
+
+    #[nutype(
+        profile = "strict",
+        validate(greater_or_equal = 10, predicate = |n| n.is_multiple_of(2)),
+        derive(Debug)
+    )]
+    pub struct TeamSize(u32);
+
+    #[test]
+    fn test_strict_validate_all() {
+        let errors: Vec<TeamSizeError> = TeamSize::validate_all(3).collect();
+        assert_eq!(
+            errors,
+            vec![
+                TeamSizeError::GreaterOrEqualViolated,
+                TeamSizeError::PredicateViolated
+            ]
+        );
+        assert_eq!(TeamSize::validate_all(12).count(), 0);
+        assert_eq!(
+            format!("{:?}", TeamSize::try_new(12).unwrap()),
+            "TeamSize(***)"
+        );
+    }
+}
+
+#[cfg(test)]
+mod with_context {
+    use super::*;
+
+    pub struct Limits {
+        max_workers: u16,
+    }
+
+    #[nutype(
+        validate(greater = 0, with_context = |workers, limits: &Limits| *workers <= limits.max_workers, context = Limits),
+        derive(Debug, Clone, Copy, PartialEq)
+    )]
+    pub struct Workers(u16);
+
+    #[test]
+    fn test_try_from_with() {
+        let limits = Limits { max_workers: 8 };
+        assert_eq!(Workers::try_from_with(&limits, 4).unwrap().into_inner(), 4);
+        assert_eq!(
+            Workers::try_from_with(&limits, 16),
+            Err(WorkersError::WithContextViolated)
+        );
+        assert_eq!(
+            Workers::try_from_with(&limits, 0),
+            Err(WorkersError::GreaterViolated)
+        );
+    }
+}
+
+#[cfg(test)]
+mod error_codes {
+    use super::*;
+
+    #[test]
+    fn test_code_and_bound() {
+        #[nutype(validate(greater_or_equal = 18, less = 150), derive(Debug))]
+        pub struct Age(u8);
+
+        let err = Age::try_new(16).unwrap_err();
+        assert_eq!(err.code(), "greater_or_equal");
+        assert_eq!(err.bound(), Some(18));
+
+        let err = Age::try_new(200).unwrap_err();
+        assert_eq!(err.code(), "less");
+        assert_eq!(err.bound(), Some(150));
+    }
+}
+
+#[cfg(feature = "from_schema")]
+mod from_schema {
+    use super::*;
+    use nutype::nutype_from_schema;
+
+    #[test]
+    fn test_rules_from_schema() {
+        #[nutype_from_schema(r#"{ "type": "integer", "minimum": -10, "exclusiveMaximum": 100 }"#)]
+        #[nutype(derive(Debug, PartialEq))]
+        pub struct Offset(i32);
+
+        assert_eq!(Offset::try_new(-10).unwrap().into_inner(), -10);
+        assert_eq!(
+            Offset::try_new(-11),
+            Err(OffsetError::GreaterOrEqualViolated)
+        );
+        assert_eq!(Offset::try_new(100), Err(OffsetError::LessViolated));
+    }
+}
+
+#[cfg(test)]
+mod guarantee {
+    use super::*;
+
+    #[test]
+    fn test_infallible_new() {
+        #[nutype(
+            sanitize(clamp(0, 100)),
+            validate(greater_or_equal = 0, less_or_equal = 100),
+            guarantee,
+            derive(Debug, PartialEq)
+        )]
+        pub struct Percent(u8);
+
+        assert_eq!(Percent::new(150).into_inner(), 100);
+        assert_eq!(Percent::new(42).into_inner(), 42);
+        assert_eq!(Percent::try_new(150), Ok(Percent::new(100)));
+    }
+
+    #[test]
+    fn test_clamp_within_strict_bounds() {
+        #[nutype(
+            sanitize(with = |n| n * 2, clamp(-9, 9)),
+            validate(greater = -10, less = 10),
+            guarantee,
+            derive(Debug)
+        )]
+        pub struct Offset(i32);
+
+        assert_eq!(Offset::new(-100).into_inner(), -9);
+        assert_eq!(Offset::new(3).into_inner(), 6);
+    }
+
+    #[test]
+    fn test_clamp_without_guarantee() {
+        #[nutype(sanitize(clamp(1, 5)), derive(Debug))]
+        pub struct Stars(u8);
+
+        assert_eq!(Stars::new(0).into_inner(), 1);
+        assert_eq!(Stars::new(9).into_inner(), 5);
+    }
+}
+
+#[cfg(test)]
+mod hash_with {
+    use super::*;
+    use core::hash::{Hash, Hasher};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash_of(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn fold_halves(id: &u64) -> u64 {
+        (id >> 32) ^ (id & 0xFFFF_FFFF)
+    }
+
+    #[test]
+    fn test_hash_with_function() {
+        #[nutype(hash_with = fold_halves, derive(PartialEq, Eq, Hash))]
+        pub struct UserId(u64);
+
+        let id = (7 << 32) | 5;
+        assert_eq!(hash_of(UserId::new(id)), hash_of(2_u64));
+    }
+
+    #[test]
+    fn test_hash_with_closure() {
+        #[nutype(hash_with = |id| *id as u64 * 31, derive(PartialEq, Eq, Hash))]
+        pub struct TenantId(u32);
+
+        assert_eq!(hash_of(TenantId::new(2)), hash_of(62_u64));
+    }
+}
+
+#[cfg(feature = "nohash-hasher")]
+mod nohash {
+    use super::*;
+    use nohash_hasher::IntMap;
+
+    #[test]
+    fn test_int_map_key() {
+        #[nutype(nohash, derive(Debug, PartialEq, Eq, Hash))]
+        pub struct OrderId(u64);
+
+        let mut orders: IntMap<OrderId, &str> = IntMap::default();
+        orders.insert(OrderId::new(1), "pending");
+        orders.insert(OrderId::new(2), "shipped");
+        assert_eq!(orders.get(&OrderId::new(2)), Some(&"shipped"));
+    }
+
+    #[test]
+    fn test_with_hash_with() {
+        #[nutype(nohash, hash_with = |id| *id as u64, derive(PartialEq, Eq, Hash))]
+        pub struct Counter(u128);
+
+        let mut counters: IntMap<Counter, u32> = IntMap::default();
+        counters.insert(Counter::new(u128::from(u64::MAX) + 1), 1);
+        assert_eq!(counters.len(), 1);
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod derive_borsh {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        #[nutype(
+            validate(greater_or_equal = 1, less_or_equal = 12),
+            derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)
+        )]
+        pub struct Month(u8);
+
+        let bytes = borsh::to_vec(&Month::try_new(3).unwrap()).unwrap();
+        assert_eq!(bytes, [3]);
+        assert_eq!(
+            borsh::from_slice::<Month>(&bytes).unwrap(),
+            Month::try_new(3).unwrap()
+        );
+
+        let err = borsh::from_slice::<Month>(&[13]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Month is too big. The value must be between 1 and 12. Expected valid Month"
+        );
+    }
+
+    #[test]
+    fn test_without_validation() {
+        #[nutype(sanitize(with = |n: i64| n.abs()), derive(Debug, PartialEq, BorshSerialize, BorshDeserialize))]
+        pub struct Distance(i64);
+
+        let bytes = borsh::to_vec(&-7_i64).unwrap();
+        assert_eq!(
+            borsh::from_slice::<Distance>(&bytes).unwrap(),
+            Distance::new(7)
+        );
+    }
+}
+
+#[cfg(feature = "clap")]
+mod derive_clap {
+    use super::*;
+    use clap::Parser;
+
+    #[nutype(
+        validate(greater_or_equal = 1, less_or_equal = 65535),
+        derive(Debug, Clone, Copy, PartialEq, ValueParserFactory)
+    )]
+    pub struct Port(u32);
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[arg(long, default_value = "8080")]
+        port: Port,
+    }
+
+    #[test]
+    fn test_parse_argument() {
+        let cli = Cli::try_parse_from(["app", "--port", "443"]).unwrap();
+        assert_eq!(cli.port, Port::try_new(443).unwrap());
+        let cli = Cli::try_parse_from(["app"]).unwrap();
+        assert_eq!(cli.port.into_inner(), 8080);
+    }
+
+    #[test]
+    fn test_invalid_argument() {
+        let err = Cli::try_parse_from(["app", "--port", "70000"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("Port is too big."));
+
+        let err = Cli::try_parse_from(["app", "--port", "http"]).unwrap_err();
+        assert!(err.to_string().contains("invalid digit found in string"));
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod derive_rkyv {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn test_roundtrip() {
+        #[nutype(
+            validate(greater_or_equal = 1, less_or_equal = 12),
+            derive(Debug, PartialEq, Archive)
+        )]
+        pub struct Month(u32);
+
+        let bytes = rkyv::to_bytes::<Error>(&Month::try_new(3).unwrap()).unwrap();
+        let archived = rkyv::access::<ArchivedMonth, Error>(&bytes).unwrap();
+        assert_eq!(archived.to_native(), 3);
+        assert_eq!(
+            rkyv::from_bytes::<Month, Error>(&bytes).unwrap(),
+            Month::try_new(3).unwrap()
+        );
+
+        let bytes = rkyv::to_bytes::<Error>(&13_u32).unwrap();
+        assert!(rkyv::from_bytes::<Month, Error>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_without_validation() {
+        #[nutype(sanitize(with = |n: i64| n.abs()), derive(Debug, PartialEq, Archive))]
+        pub struct Distance(i64);
+
+        let bytes = rkyv::to_bytes::<Error>(&-7_i64).unwrap();
+        assert_eq!(
+            rkyv::from_bytes::<Distance, Error>(&bytes).unwrap(),
+            Distance::new(-7)
+        );
+    }
+}
+
+#[cfg(test)]
+mod ffi {
+    use super::*;
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn test_try_new_and_get() {
+        #[nutype(ffi, validate(less_or_equal = 150), derive(Debug))]
+        pub struct Age(u8);
+
+        let mut age = MaybeUninit::<Age>::uninit();
+        // SAFETY: `age` is valid for writes.
+        assert!(unsafe { Age::ffi_try_new(42, age.as_mut_ptr()) });
+        // SAFETY: `ffi_try_new()` returned `true`, so the value is initialized.
+        let age = unsafe { age.assume_init() };
+        assert_eq!(age.ffi_get(), 42);
+
+        let mut invalid = MaybeUninit::<Age>::uninit();
+        // SAFETY: `invalid` is valid for writes.
+        assert!(!unsafe { Age::ffi_try_new(200, invalid.as_mut_ptr()) });
+    }
+
+    #[test]
+    fn test_new_and_layout() {
+        #[nutype(ffi, sanitize(with = |n: i64| n.abs()), derive(Debug))]
+        pub struct Distance(i64);
+
+        let distance = Distance::ffi_new(-7);
+        assert_eq!(distance.ffi_get(), 7);
+        assert_eq!(
+            core::mem::size_of::<Distance>(),
+            core::mem::size_of::<i64>()
+        );
+        assert_eq!(
+            core::mem::align_of::<Distance>(),
+            core::mem::align_of::<i64>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod error_messages {
+    use super::*;
+
+    #[test]
+    fn test_default_messages_mention_both_bounds() {
+        #[nutype(validate(greater = 0, less_or_equal = 100), derive(Debug))]
+        pub struct Score(u8);
+
+        assert_eq!(
+            Score::try_new(0).unwrap_err().to_string(),
+            "Score is too small. The value must be greater than 0 and at most 100."
+        );
+        assert_eq!(
+            Score::try_new(101).unwrap_err().to_string(),
+            "Score is too big. The value must be greater than 0 and at most 100."
+        );
+    }
+
+    #[test]
+    fn test_messages() {
+        #[nutype(
+            validate(
+                greater_or_equal = 18,
+                less_or_equal = 99,
+                messages(greater_or_equal = "{type} must be at least {limit} years old.")
+            ),
+            derive(Debug)
+        )]
+        pub struct Age(u8);
+
+        assert_eq!(
+            Age::try_new(17).unwrap_err().to_string(),
+            "Age must be at least 18 years old."
+        );
+        assert_eq!(
+            Age::try_new(100).unwrap_err().to_string(),
+            "Age is too big. The value must be between 18 and 99."
+        );
+    }
+}
+
+#[cfg(test)]
+mod combinators {
+    use super::*;
+
+    #[test]
+    fn test_any_of() {
+        #[nutype(
+            validate(any_of(less_or_equal = 10, greater_or_equal = 100)),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Port(u16);
+
+        assert!(Port::try_new(10).is_ok());
+        assert!(Port::try_new(100).is_ok());
+        assert_eq!(Port::try_new(50), Err(PortError::AnyOfViolated));
+    }
+
+    #[test]
+    fn test_not_all() {
+        #[nutype(
+            validate(not(all(greater_or_equal = 1000, less_or_equal = 1999))),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Port(u16);
+
+        assert!(Port::try_new(80).is_ok());
+        assert!(Port::try_new(2000).is_ok());
+        assert_eq!(Port::try_new(1500), Err(PortError::NotViolated));
+    }
+
+    #[test]
+    fn test_not() {
+        #[nutype(
+            validate(greater = 0, not(predicate = |n| n % 13 == 0)),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Floor(i32);
+
+        assert_eq!(Floor::try_new(12).unwrap().into_inner(), 12);
+        assert_eq!(Floor::try_new(0), Err(FloorError::GreaterViolated));
+        assert_eq!(Floor::try_new(13), Err(FloorError::NotViolated));
+    }
+}
+
+#[cfg(test)]
+mod attribute_syntax {
+    use super::*;
+
+    #[test]
+    fn test_nested_lists_and_expressions() {
+        #[nutype(
+            validate(greater = -1, less_or_equal = i32::MAX / 2, in = [0, 1, { 2 + 1 }],),
+            default = if cfg!(test) { 1 } else { 0 },
+            derive(Debug, PartialEq, Default),
+        )]
+        pub struct Level(i32);
+
+        assert_eq!(Level::default().into_inner(), 1);
+        assert_eq!(Level::try_new(3).unwrap().into_inner(), 3);
+        assert_eq!(Level::try_new(2), Err(LevelError::InViolated(2)));
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+mod wasm {
+    use super::*;
+
+    #[test]
+    fn test_js_new_and_value() {
+        #[nutype(wasm, validate(less_or_equal = 150), derive(Debug, Clone, Copy))]
+        pub struct Age(u8);
+
+        let age = Age::js_new(42).unwrap();
+        assert_eq!(age.js_value(), 42);
+    }
+}
+
+mod duplicates {
+    use super::*;
+
+    #[test]
+    fn test_last_wins() {
+        #[nutype(
+            duplicates = "last_wins",
+            sanitize(with = |n: i32| n.clamp(0, 200)),
+            validate(less_or_equal = 99, greater_or_equal = 1, less_or_equal = 150),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Age(i32);
+
+        assert_eq!(Age::MAX, 150);
+        assert!(Age::try_new(120).is_ok());
+        assert_eq!(Age::try_new(151), Err(AgeError::LessOrEqualViolated));
+    }
+}
+
+mod cfg {
+    use super::*;
+
+    #[nutype(validate(greater = 0), derive(Debug))]
+    #[cfg(not(test))]
+    pub struct Port(u16);
+
+    #[nutype(validate(greater = 1023), derive(Debug, Into))]
+    #[cfg(test)]
+    pub struct Port(u16);
+
+    #[test]
+    fn test_cfg_enabled_type() {
+        assert_eq!(Port::MIN_EXCLUSIVE, 1023);
+        assert_eq!(u16::from(Port::try_new(8080).unwrap()), 8080);
+    }
+}
+
+mod repr_transparent {
+    use super::*;
+
+    #[nutype(repr_transparent, validate(less_or_equal = 150), derive(Debug))]
+    pub struct Age(u8);
+
+    #[test]
+    fn test_as_inner_slice() {
+        let ages = vec![Age::try_new(7).unwrap(), Age::try_new(42).unwrap()];
+        assert_eq!(Age::as_inner_slice(&ages), &[7, 42]);
+        assert_eq!(core::mem::size_of::<Age>(), core::mem::size_of::<u8>());
+    }
+
+    #[cfg(feature = "new_unchecked")]
+    #[test]
+    fn test_from_inner_slice_unchecked() {
+        #[nutype(
+            new_unchecked,
+            repr_transparent,
+            validate(greater_or_equal = 1),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Quantity(u32);
+
+        let raw = [1, 2, 3];
+        let quantities = unsafe { Quantity::from_inner_slice_unchecked(&raw) };
+        assert_eq!(quantities.len(), 3);
+        assert_eq!(quantities[1], Quantity::try_new(2).unwrap());
+    }
+}
+
+mod try_from_iter {
+    use super::*;
+    use nutype::BatchError;
+
+    #[nutype(validate(less_or_equal = 150), derive(Debug, PartialEq, Into))]
+    pub struct Age(u8);
+
+    #[test]
+    fn test_try_from_iter() {
+        let ages: Vec<Age> = Age::try_from_iter([7, 42]).unwrap();
+        assert_eq!(ages.into_iter().map(u8::from).collect::<Vec<_>>(), [7, 42]);
+
+        let result: Result<Vec<Age>, _> = Age::try_from_iter([7, 151, 200]);
+        assert_eq!(
+            result.unwrap_err(),
+            BatchError {
+                index: 1,
+                error: AgeError::LessOrEqualViolated
+            }
+        );
+    }
+}
+
+mod named_field {
+    use super::*;
+
+    #[nutype(
+        validate(greater_or_equal = 1, less_or_equal = 65535),
+        getter = into_port,
+        derive(Debug, Clone, Copy)
+    )]
+    pub struct Port {
+        number: u32,
+    }
+
+    #[test]
+    fn test_named_field() {
+        let port = Port::try_new(8080).unwrap();
+        assert_eq!(*port.number(), 8080);
+        assert_eq!(port.into_port(), 8080);
+        assert_eq!(
+            Port::try_new(0).unwrap_err(),
+            PortError::GreaterOrEqualViolated
+        );
+    }
+}
+
+#[allow(deprecated)]
+mod renamed_from {
+    use super::*;
+
+    #[nutype(
+        renamed_from = "Years",
+        validate(less_or_equal = 150),
+        derive(Debug, PartialEq, FromStr)
+    )]
+    pub struct Age(u8);
+
+    #[test]
+    fn test_old_names_keep_working() {
+        assert_eq!(Years::try_new(30).unwrap(), Age::try_new(30).unwrap());
+
+        let err: YearsError = Years::try_new(151).unwrap_err();
+        assert_eq!(err, AgeError::LessOrEqualViolated);
+
+        let err: YearsParseError = "151".parse::<Years>().unwrap_err();
+        assert!(matches!(err, AgeParseError::Validate(_)));
+    }
+}
+
+mod map {
+    use super::*;
+
+    #[test]
+    fn test_try_map() {
+        #[nutype(validate(less_or_equal = 100), derive(Debug, PartialEq))]
+        pub struct Percent(u8);
+
+        let percent = Percent::try_new(40).unwrap();
+        assert_eq!(percent.try_map(|v| v + 60).unwrap().into_inner(), 100);
+        assert_eq!(
+            Percent::try_new(50).unwrap().try_map(|v| v * 3),
+            Err(PercentError::LessOrEqualViolated)
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        #[nutype(sanitize(with = |v| v.clamp(0, 10)), derive(Debug))]
+        pub struct Level(i32);
+
+        assert_eq!(Level::new(7).map(|v| v + 5).into_inner(), 10);
+    }
+}
+
+mod on_invalid {
+    use super::*;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static REJECTED_SUM: AtomicU32 = AtomicU32::new(0);
+
+    #[nutype(
+        validate(less_or_equal = 150),
+        on_invalid = |_err, value| {
+            REJECTED_SUM.fetch_add(u32::from(*value), Ordering::SeqCst);
+        },
+        derive(Debug, FromStr)
+    )]
+    pub struct Age(u8);
+
+    #[test]
+    fn test_closure_gets_rejected_value() {
+        assert!(Age::try_new(30).is_ok());
+        assert!(Age::try_new(200).is_err());
+        assert!("151".parse::<Age>().is_err());
+        assert_eq!(REJECTED_SUM.load(Ordering::SeqCst), 351);
+    }
+}
+
+mod try_with {
+    use super::*;
+    use core::num::TryFromIntError;
+
+    #[nutype(
+        sanitize(try_with(|dollars: i64| i64::try_from(i128::from(dollars) * 100), error = TryFromIntError)),
+        validate(greater_or_equal = 0),
+        derive(Debug, PartialEq, FromStr)
+    )]
+    pub struct Cents(i64);
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Cents::try_new(12).unwrap().into_inner(), 1200);
+        assert!(matches!(
+            Cents::try_new(i64::MAX),
+            Err(CentsError::TryWithViolated(_))
+        ));
+        assert_eq!(Cents::try_new(-1), Err(CentsError::GreaterOrEqualViolated));
+        assert!("92233720368547759".parse::<Cents>().is_err());
+    }
+
+    #[test]
+    fn test_eq_raw() {
+        let cents = Cents::try_new(3).unwrap();
+        assert!(cents.eq_raw(3));
+        assert!(!cents.eq_raw(i64::MAX));
+    }
+}
+
+mod lossy {
+    use super::*;
+    use nutype::SanitizeWarning;
+
+    #[nutype(
+        lossy,
+        sanitize(clamp(0, 100)),
+        validate(greater = 0),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Percentage(i32);
+
+    #[test]
+    fn test_try_from_lossy() {
+        let (percentage, warnings) = Percentage::try_from_lossy(42).unwrap();
+        assert_eq!(percentage.into_inner(), 42);
+        assert!(warnings.is_empty());
+
+        let (percentage, warnings) = Percentage::try_from_lossy(250).unwrap();
+        assert_eq!(percentage.into_inner(), 100);
+        assert_eq!(
+            warnings,
+            vec![SanitizeWarning {
+                sanitizer: "clamp",
+                index: 0
+            }]
+        );
+
+        assert_eq!(
+            Percentage::try_from_lossy(-5),
+            Err(PercentageError::GreaterViolated)
+        );
+    }
+}
+
+mod metadata {
+    use super::*;
+    use nutype::{RuleMetadata, RuleStage};
+
+    const LIMIT: i64 = 250;
+
+    #[test]
+    fn test_metadata() {
+        #[nutype(
+            sanitize(clamp(-1000, 1000)),
+            validate(greater_or_equal = -LIMIT, less = LIMIT, predicate = |v| v % 5 == 0),
+            derive(Debug)
+        )]
+        pub struct Step(i64);
+
+        let metadata = Step::METADATA;
+        assert_eq!(metadata.name, "Step");
+        assert_eq!(metadata.inner_type, "i64");
+        assert!(metadata.sanitizers().map(|rule| rule.name).eq(["clamp"]));
+        assert!(metadata
+            .validators()
+            .map(|rule| (rule.name, rule.bound))
+            .eq([
+                ("greater_or_equal", Some(-250.0)),
+                ("less", Some(250.0)),
+                ("predicate", None),
+            ]));
+        assert!(metadata
+            .guarantees()
+            .any(|guarantee| guarantee == "satisfies the predicate"));
+    }
+
+    #[test]
+    fn test_metadata_without_validation() {
+        #[nutype(derive(Debug))]
+        pub struct Counter(u32);
+
+        assert_eq!(Counter::METADATA.inner_type, "u32");
+        assert!(Counter::METADATA.rules.is_empty());
+        assert_eq!(Counter::METADATA.guarantees().count(), 0);
+    }
+
+    #[test]
+    fn test_metadata_rules() {
+        #[nutype(
+            sanitize(clamp(0, 100)),
+            validate(less = 90, any_of(greater = 50, less = 10)),
+            derive(Debug)
+        )]
+        pub struct Level(u8);
+
+        assert_eq!(
+            Level::METADATA.rules,
+            [
+                RuleMetadata {
+                    stage: RuleStage::Sanitizer,
+                    name: "clamp",
+                    bound: None,
+                    description: Some("clamped into 0..=100"),
+                },
+                RuleMetadata {
+                    stage: RuleStage::Validator,
+                    name: "less",
+                    bound: Some(90.0),
+                    description: Some("less than 90"),
+                },
+                RuleMetadata {
+                    stage: RuleStage::Validator,
+                    name: "any_of",
+                    bound: None,
+                    description: Some("any of: greater than 50 or less than 10"),
+                },
+            ]
+        );
+
+        assert!(Level::METADATA
+            .validators()
+            .map(|rule| rule.name)
+            .eq(["less", "any_of"]));
+        assert!(Level::METADATA.guarantees().eq([
+            "clamped into 0..=100",
+            "less than 90",
+            "any of: greater than 50 or less than 10"
+        ]));
+    }
+}
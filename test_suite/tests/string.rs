@@ -1,4 +1,5 @@
 use nutype::nutype;
+extern crate alloc;
 
 #[cfg(test)]
 mod sanitizers {
@@ -15,6 +16,25 @@ mod sanitizers {
         assert_eq!(Name::new(" foo \n bar ").into_inner(), "foo \n bar");
     }
 
+    #[test]
+    fn test_trim_and_change_case_of_non_ascii() {
+        #[nutype(sanitize(trim, lowercase))]
+        pub struct Lower(String);
+
+        #[nutype(sanitize(uppercase, trim))]
+        pub struct Upper(String);
+
+        assert_eq!(
+            Lower::new("\u{3000} ÄBC Straße \n").into_inner(),
+            "äbc straße"
+        );
+        assert_eq!(
+            Upper::new("\u{3000} äbc straße \n").into_inner(),
+            "ÄBC STRASSE"
+        );
+        assert_eq!(Lower::new(" ABC ").into_inner(), "abc");
+    }
+
     #[test]
     fn test_lowercase() {
         #[nutype(sanitize(lowercase))]
@@ -33,6 +53,208 @@ mod sanitizers {
         assert_eq!(Name::new("Hello THERE").into_inner(), "HELLO THERE");
     }
 
+    #[test]
+    fn test_canonicalize_number() {
+        #[nutype(sanitize(canonicalize_number))]
+        pub struct Amount(String);
+
+        assert_eq!(Amount::new("").into_inner(), "");
+        assert_eq!(Amount::new("  ").into_inner(), "");
+        assert_eq!(Amount::new("0042").into_inner(), "42");
+        assert_eq!(Amount::new("000").into_inner(), "0");
+        assert_eq!(Amount::new(" +12 ").into_inner(), "12");
+        assert_eq!(Amount::new("-0012,50").into_inner(), "-12.50");
+        assert_eq!(Amount::new(".5").into_inner(), "0.5");
+        assert_eq!(Amount::new("1 234 567").into_inner(), "1234567");
+        assert_eq!(Amount::new("1_000_000").into_inner(), "1000000");
+        assert_eq!(Amount::new("1'234.5").into_inner(), "1234.5");
+        assert_eq!(Amount::new("1,234,567").into_inner(), "1234567");
+        assert_eq!(Amount::new("1.234.567").into_inner(), "1234567");
+        assert_eq!(Amount::new("1,234.56").into_inner(), "1234.56");
+        assert_eq!(Amount::new("1.234,56").into_inner(), "1234.56");
+        assert_eq!(Amount::new("1\u{a0}234,56").into_inner(), "1234.56");
+        assert_eq!(Amount::new("12a").into_inner(), "12a");
+        assert_eq!(Amount::new("abc").into_inner(), "abc");
+    }
+
+    #[test]
+    fn test_canonicalize_number_trailing_separator() {
+        #[nutype(sanitize(canonicalize_number))]
+        pub struct Amount(String);
+
+        assert_eq!(Amount::new("12.").into_inner(), "12");
+        assert_eq!(Amount::new("-0012,").into_inner(), "-12");
+        assert_eq!(Amount::new("1.234,").into_inner(), "1234");
+    }
+
+    #[test]
+    fn test_canonicalize_number_keeps_ambiguous_separator() {
+        #[nutype(sanitize(canonicalize_number))]
+        pub struct Amount(String);
+
+        // `1,234` is either 1234 or 1.234
+        assert_eq!(Amount::new(" 1,234 ").into_inner(), "1,234");
+        assert_eq!(Amount::new("-999.000").into_inner(), "-999.000");
+
+        // Not ambiguous
+        assert_eq!(Amount::new("0,234").into_inner(), "0.234");
+        assert_eq!(Amount::new("1,23").into_inner(), "1.23");
+        assert_eq!(Amount::new("1234,567").into_inner(), "1234.567");
+        assert_eq!(Amount::new("1,234,567").into_inner(), "1234567");
+        assert_eq!(Amount::new("1,234.5").into_inner(), "1234.5");
+    }
+
+    #[test]
+    fn test_canonicalize_number_keeps_irregular_groups() {
+        #[nutype(sanitize(canonicalize_number))]
+        pub struct Amount(String);
+
+        assert_eq!(Amount::new(" 1.2.3 ").into_inner(), "1.2.3");
+        assert_eq!(Amount::new("12,34,567").into_inner(), "12,34,567");
+        assert_eq!(Amount::new("1,2345.6").into_inner(), "1,2345.6");
+        assert_eq!(Amount::new("1.234,5.6").into_inner(), "1.234,5.6");
+
+        assert_eq!(Amount::new("12,345,678.9").into_inner(), "12345678.9");
+    }
+
+    #[test]
+    fn test_canonicalize_number_before_validation() {
+        #[nutype(
+            sanitize(canonicalize_number),
+            validate(predicate = |s| s.parse::<f64>().is_ok()),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Amount(String);
+
+        assert_eq!(Amount::try_new("00 100,25").unwrap().into_inner(), "100.25");
+        assert_eq!(Amount::try_new("12a"), Err(AmountError::PredicateViolated));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_snake_case() {
+        #[nutype(sanitize(snake_case))]
+        pub struct Column(String);
+
+        assert_eq!(Column::new("").into_inner(), "");
+        assert_eq!(Column::new("id").into_inner(), "id");
+        assert_eq!(Column::new("createdAt").into_inner(), "created_at");
+        assert_eq!(Column::new("CreatedAt").into_inner(), "created_at");
+        assert_eq!(Column::new("HTTPServer").into_inner(), "http_server");
+        assert_eq!(Column::new("userID").into_inner(), "user_id");
+        assert_eq!(Column::new("version2Name").into_inner(), "version2_name");
+        assert_eq!(Column::new("  first name  ").into_inner(), "first_name");
+        assert_eq!(
+            Column::new("kebab-case--value").into_inner(),
+            "kebab_case_value"
+        );
+        assert_eq!(
+            Column::new("already_snake_case").into_inner(),
+            "already_snake_case"
+        );
+        // Combining marks neither split words nor remain after lowercasing `İ`.
+        assert_eq!(Column::new("İstanbul").into_inner(), "istanbul");
+        assert_eq!(Column::new("cafe\u{301}Menu").into_inner(), "cafe_menu");
+        assert_eq!(Column::new("CaféMenu").into_inner(), "café_menu");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_kebab_case() {
+        #[nutype(sanitize(kebab_case))]
+        pub struct CssClass(String);
+
+        assert_eq!(
+            CssClass::new("backgroundColor").into_inner(),
+            "background-color"
+        );
+        assert_eq!(
+            CssClass::new("XMLHttpRequest").into_inner(),
+            "xml-http-request"
+        );
+        assert_eq!(
+            CssClass::new("snake_case_value").into_inner(),
+            "snake-case-value"
+        );
+        assert_eq!(
+            CssClass::new("Title Case Words").into_inner(),
+            "title-case-words"
+        );
+        assert_eq!(
+            CssClass::new("İstanbul Bazaar").into_inner(),
+            "istanbul-bazaar"
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_slugify() {
+        #[nutype(sanitize(slugify), validate(not_empty), derive(Debug, PartialEq))]
+        pub struct Slug(String);
+
+        assert_eq!(
+            Slug::try_new(" Hello, World! ").unwrap().into_inner(),
+            "hello-world"
+        );
+        assert_eq!(
+            Slug::try_new("Rust 2024 Edition").unwrap().into_inner(),
+            "rust-2024-edition"
+        );
+        assert_eq!(Slug::try_new("a--b__c").unwrap().into_inner(), "a-b-c");
+        assert_eq!(Slug::try_new("Grüße").unwrap().into_inner(), "grusse");
+        assert_eq!(
+            Slug::try_new("Crème Brûlée à la Łódź")
+                .unwrap()
+                .into_inner(),
+            "creme-brulee-a-la-lodz"
+        );
+        assert_eq!(
+            Slug::try_new("Ærø Œuvre").unwrap().into_inner(),
+            "aero-oeuvre"
+        );
+        assert_eq!(
+            Slug::try_new("Привет, мир 2024").unwrap().into_inner(),
+            "2024"
+        );
+        assert_eq!(Slug::try_new("日本語"), Err(SlugError::NotEmptyViolated));
+        assert_eq!(
+            Slug::try_new("helloWorld").unwrap().into_inner(),
+            "hello-world"
+        );
+        assert_eq!(
+            Slug::try_new("XMLHttpRequest").unwrap().into_inner(),
+            "xml-http-request"
+        );
+        assert_eq!(Slug::try_new("CaféMenu").unwrap().into_inner(), "cafe-menu");
+        assert_eq!(Slug::try_new("ÉCOLE").unwrap().into_inner(), "ecole");
+        assert_eq!(Slug::try_new("?!"), Err(SlugError::NotEmptyViolated));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_slugify_decomposes_letters() {
+        #[nutype(sanitize(slugify), derive(Debug, PartialEq))]
+        pub struct Slug(String);
+
+        // Titlecase digraphs
+        assert_eq!(Slug::new("ǅemal").into_inner(), "dzemal");
+        assert_eq!(Slug::new("ǈubljana").into_inner(), "ljubljana");
+        assert_eq!(Slug::new("ǋegoš").into_inner(), "njegos");
+        assert_eq!(Slug::new("ǲeko").into_inner(), "dzeko");
+        assert_eq!(Slug::new("ǄEMAL").into_inner(), "dzemal");
+
+        // Combining marks, precomposed or not
+        assert_eq!(Slug::new("İstanbul").into_inner(), "istanbul");
+        assert_eq!(
+            Slug::new("cafe\u{301} au lait").into_inner(),
+            "cafe-au-lait"
+        );
+        assert_eq!(Slug::new("Ṩpeçiål Ĺetters").into_inner(), "special-letters");
+
+        // Compatibility characters
+        assert_eq!(Slug::new("ﬁle №１").into_inner(), "file-no1");
+    }
+
     #[cfg(test)]
     mod with {
         use super::*;
@@ -64,6 +286,26 @@ mod sanitizers {
 
             assert_eq!(Name::new(" Anton\n\n").into_inner(), "ANTON");
         }
+
+        #[test]
+        fn test_closures_with_commas_and_turbofish() {
+            #[nutype(sanitize(
+                with = |s: String| s.replace(",", ""),
+                trim,
+                with = |s: String| s.split(' ').map(str::to_string).collect::<Vec<_>>().join("-"),
+            ))]
+            pub struct Slug(String);
+
+            assert_eq!(Slug::new(" a,b c, d ").into_inner(), "ab-c-d");
+        }
+
+        #[test]
+        fn test_many_with_sanitizers() {
+            #[nutype(sanitize(with = sanitize_name, with = |s: String| format!("<{s}>")))]
+            pub struct Name(String);
+
+            assert_eq!(Name::new(" Anton\n").into_inner(), "<ANTON>");
+        }
     }
 
     #[test]
@@ -117,6 +359,89 @@ mod validators {
         assert_eq!(Name::try_new("Антон"), Err(NameError::LenCharMinViolated));
     }
 
+    #[test]
+    fn test_len_bound_consts() {
+        #[nutype(validate(len_char_min = 3, len_char_max = 16), derive(Debug))]
+        pub struct Username(String);
+
+        assert_eq!(Username::MIN_LEN, 3);
+        assert_eq!(Username::MAX_LEN, 16);
+    }
+
+    #[test]
+    fn test_max_accept_bytes() {
+        #[nutype(validate(len_char_max = 16), derive(Debug))]
+        pub struct Username(String);
+
+        assert_eq!(Username::MAX_ACCEPT_BYTES, 64);
+        assert!(!Username::exceeds_limit(64));
+        assert!(Username::exceeds_limit(65));
+
+        #[nutype(
+            sanitize(lowercase),
+            validate(len_byte_max = 8, len_char_max = 4),
+            derive(Debug)
+        )]
+        pub struct Code(String);
+
+        // `len_byte_max` is ignored, because lowercase may shorten the value in bytes.
+        assert_eq!(Code::MAX_ACCEPT_BYTES, 16);
+
+        #[nutype(validate(len_byte_max = 8, len_char_max = 4), derive(Debug))]
+        pub struct Tag(String);
+
+        assert_eq!(Tag::MAX_ACCEPT_BYTES, 8);
+        assert!(Tag::exceeds_limit("ааааа".len()));
+    }
+
+    #[test]
+    fn test_len_byte_min_and_max() {
+        #[nutype(validate(len_byte_min = 4, len_byte_max = 8), derive(Debug, PartialEq))]
+        pub struct Name(String);
+
+        assert_eq!(Name::try_new("Ann"), Err(NameError::LenByteMinViolated));
+        assert_eq!(Name::try_new("Anton").unwrap().into_inner(), "Anton");
+        assert_eq!(
+            Name::try_new("Alexandra"),
+            Err(NameError::LenByteMaxViolated)
+        );
+
+        // Ukrainian, Cyrillic. Every char is 2 bytes.
+        assert_eq!(Name::try_new("Ян").unwrap().into_inner(), "Ян");
+        assert_eq!(Name::try_new("Антон"), Err(NameError::LenByteMaxViolated));
+
+        assert_eq!(Name::MIN_LEN_BYTES, 4);
+        assert_eq!(Name::MAX_LEN_BYTES, 8);
+        assert_eq!(
+            NameError::LenByteMaxViolated.to_string(),
+            "Name is too long. The value length must be between 4 and 8 bytes."
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_len_grapheme_min_and_max() {
+        #[nutype(
+            validate(len_grapheme_min = 2, len_grapheme_max = 3),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Reaction(String);
+
+        // A thumbs up with a skin tone modifier is 2 chars, but a single grapheme
+        assert_eq!(
+            Reaction::try_new("👍🏽"),
+            Err(ReactionError::LenGraphemeMinViolated)
+        );
+        assert_eq!(Reaction::try_new("👍🏽👍🏽👍🏽").unwrap().into_inner(), "👍🏽👍🏽👍🏽");
+        assert_eq!(
+            Reaction::try_new("👍🏽👍🏽👍🏽👍🏽"),
+            Err(ReactionError::LenGraphemeMaxViolated)
+        );
+
+        assert_eq!(Reaction::MIN_LEN_GRAPHEMES, 2);
+        assert_eq!(Reaction::MAX_LEN_GRAPHEMES, 3);
+    }
+
     #[test]
     fn test_not_empty() {
         #[nutype(validate(not_empty), derive(Debug, PartialEq))]
@@ -127,6 +452,130 @@ mod validators {
         assert_eq!(Name::try_new("Julia").unwrap().into_inner(), "Julia");
     }
 
+    #[test]
+    fn test_in() {
+        #[nutype(
+            sanitize(trim, lowercase),
+            validate(in = ["red", "green", "blue"]),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Color(String);
+
+        assert_eq!(
+            Color::try_new("yellow"),
+            Err(ColorError::InViolated("yellow".to_string()))
+        );
+        assert_eq!(Color::try_new(" Green ").unwrap().into_inner(), "green");
+        assert_eq!(
+            ColorError::InViolated("yellow".to_string()).to_string(),
+            r#"Color is not allowed. The value must be one of ["red", "green", "blue"], got "yellow"."#
+        );
+    }
+
+    #[test]
+    fn test_starts_with_and_ends_with() {
+        #[nutype(
+            validate(starts_with = "+49", not_ends_with = "0"),
+            derive(Debug, PartialEq)
+        )]
+        pub struct GermanPhone(String);
+
+        assert!(GermanPhone::try_new("+49301234567").is_ok());
+        assert_eq!(
+            GermanPhone::try_new("+33123456789"),
+            Err(GermanPhoneError::StartsWithViolated("+49"))
+        );
+        assert_eq!(
+            GermanPhone::try_new("+49301234560"),
+            Err(GermanPhoneError::NotEndsWithViolated("0"))
+        );
+        assert_eq!(
+            GermanPhoneError::StartsWithViolated("+49").to_string(),
+            r#"GermanPhone must start with "+49"."#
+        );
+    }
+
+    const EXTENSION: &str = ".rs";
+
+    #[test]
+    fn test_fragment_as_constant() {
+        #[nutype(
+            validate(ends_with = EXTENSION, not_starts_with = ".", not_contains = "/"),
+            derive(Debug, PartialEq)
+        )]
+        pub struct SourceFile(String);
+
+        assert!(SourceFile::try_new("main.rs").is_ok());
+        assert_eq!(
+            SourceFile::try_new("main.py"),
+            Err(SourceFileError::EndsWithViolated(".rs"))
+        );
+        assert_eq!(
+            SourceFile::try_new(".hidden.rs"),
+            Err(SourceFileError::NotStartsWithViolated("."))
+        );
+        assert_eq!(
+            SourceFile::try_new("src/main.rs"),
+            Err(SourceFileError::NotContainsViolated("/"))
+        );
+        assert_eq!(
+            SourceFileError::NotContainsViolated("/").to_string(),
+            r#"SourceFile must not contain "/"."#
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        #[nutype(validate(contains = "@"), derive(Debug, PartialEq))]
+        pub struct Email(String);
+
+        assert!(Email::try_new("user@example.com").is_ok());
+        assert_eq!(
+            Email::try_new("user"),
+            Err(EmailError::ContainsViolated("@"))
+        );
+    }
+
+    #[test]
+    fn test_hostname() {
+        #[nutype(validate(hostname), derive(Debug, PartialEq))]
+        pub struct Host(String);
+
+        assert!(Host::try_new("localhost").is_ok());
+        assert!(Host::try_new("api-2.example.com").is_ok());
+        assert!(Host::try_new("a".repeat(63)).is_ok());
+
+        assert_eq!(Host::try_new(""), Err(HostError::HostnameViolated));
+        assert_eq!(
+            Host::try_new("-api.example.com"),
+            Err(HostError::HostnameViolated)
+        );
+        assert_eq!(
+            Host::try_new("api-.example.com"),
+            Err(HostError::HostnameViolated)
+        );
+        assert_eq!(
+            Host::try_new("example..com"),
+            Err(HostError::HostnameViolated)
+        );
+        assert_eq!(
+            Host::try_new("exa mple.com"),
+            Err(HostError::HostnameViolated)
+        );
+        assert_eq!(Host::try_new("bücher.de"), Err(HostError::HostnameViolated));
+        assert_eq!(
+            Host::try_new("a".repeat(64)),
+            Err(HostError::HostnameViolated)
+        );
+        let too_long = vec!["a".repeat(63); 4].join(".");
+        assert_eq!(too_long.len(), 255);
+        assert_eq!(Host::try_new(too_long), Err(HostError::HostnameViolated));
+        assert_eq!(
+            Host::try_new("local_host").unwrap_err().to_string(),
+            "Host is not a valid hostname."
+        );
+    }
+
     #[test]
     fn test_many_validators() {
         #[nutype(validate(len_char_min = 3, len_char_max = 6), derive(Debug, PartialEq))]
@@ -140,6 +589,55 @@ mod validators {
         assert_eq!(Name::try_new("Julia").unwrap().into_inner(), "Julia");
     }
 
+    #[test]
+    fn test_case_validators() {
+        #[nutype(validate(lowercase, no_whitespace), derive(Debug))]
+        pub struct Slug(String);
+
+        assert_eq!(
+            Slug::try_new("hello-world-2").unwrap().into_inner(),
+            "hello-world-2"
+        );
+        assert_eq!(
+            Slug::try_new("Hello").unwrap_err(),
+            SlugError::LowercaseViolated
+        );
+        assert_eq!(
+            Slug::try_new("hello world").unwrap_err(),
+            SlugError::NoWhitespaceViolated
+        );
+
+        #[nutype(validate(uppercase, ascii), derive(Debug))]
+        pub struct CountryCode(String);
+
+        assert!(CountryCode::try_new("UA").is_ok());
+        assert_eq!(
+            CountryCode::try_new("Ua").unwrap_err(),
+            CountryCodeError::UppercaseViolated
+        );
+        assert_eq!(
+            CountryCode::try_new("ÜA").unwrap_err(),
+            CountryCodeError::AsciiViolated
+        );
+        assert_eq!(
+            CountryCodeError::AsciiViolated.to_string(),
+            "CountryCode contains non-ASCII characters."
+        );
+    }
+
+    #[test]
+    fn test_alphanumeric() {
+        #[nutype(validate(alphanumeric), derive(Debug))]
+        pub struct Code(String);
+
+        assert!(Code::try_new("abc123").is_ok());
+        assert!(Code::try_new("Київ1").is_ok());
+        assert_eq!(
+            Code::try_new("abc-123").unwrap_err(),
+            CodeError::AlphanumericViolated
+        );
+    }
+
     #[cfg(test)]
     mod with {
         use super::*;
@@ -229,6 +727,16 @@ mod validators {
         assert_eq!(EmailError::NotEmptyViolated.to_string(), "Email is empty.");
     }
 
+    #[test]
+    fn test_number_literals() {
+        #[nutype(validate(len_char_min = 0x2, len_char_max = 1_000usize), derive(Debug))]
+        struct Bio(String);
+
+        assert_eq!(Bio::MIN_LEN, 2);
+        assert_eq!(Bio::MAX_LEN, 1000);
+        assert_eq!(Bio::try_new("a").unwrap_err(), BioError::LenCharMinViolated);
+    }
+
     mod when_boundaries_defined_as_constants {
         use super::*;
 
@@ -258,6 +766,20 @@ mod validators {
                 "abcdefghij".to_string(),
             );
         }
+
+        #[nutype(validate(len_char_min = MIN_LEN - 1, len_char_max = MAX_LEN * 2), derive(Debug))]
+        struct Nickname(String);
+
+        #[test]
+        fn test_boundaries_defined_as_const_expressions() {
+            assert_eq!(Nickname::MIN_LEN, 2);
+            assert_eq!(Nickname::MAX_LEN, 20);
+            assert_eq!(
+                Nickname::try_new("a").unwrap_err(),
+                NicknameError::LenCharMinViolated,
+            );
+            assert_eq!(Nickname::try_new("ab").unwrap().into_inner(), "ab");
+        }
     }
 }
 
@@ -349,6 +871,18 @@ mod derives {
         assert_eq!(name, "Anna")
     }
 
+    #[test]
+    fn test_trait_into_listed_types() {
+        #[nutype(sanitize(trim), derive(Into), into(String, alloc::boxed::Box<str>))]
+        pub struct Name(String);
+
+        let name: String = Name::new("  Anna").into();
+        assert_eq!(name, "Anna");
+
+        let name: alloc::boxed::Box<str> = Name::new("Bob  ").into();
+        assert_eq!(&*name, "Bob");
+    }
+
     #[test]
     fn test_trait_from_str() {
         #[nutype(derive(From))]
@@ -437,6 +971,26 @@ mod derives {
         assert_eq!(error, NameError::NotEmptyViolated);
     }
 
+    #[test]
+    fn test_trait_try_from_listed_types() {
+        #[nutype(
+            validate(not_empty),
+            derive(Debug, TryFrom),
+            try_from(&str, alloc::boxed::Box<str>)
+        )]
+        pub struct Name(String);
+
+        let name = Name::try_from("Anna").unwrap();
+        assert_eq!(name.into_inner(), "Anna");
+
+        let boxed: alloc::boxed::Box<str> = "Bob".into();
+        let name = Name::try_from(boxed).unwrap();
+        assert_eq!(name.into_inner(), "Bob");
+
+        let error = Name::try_from("").unwrap_err();
+        assert_eq!(error, NameError::NotEmptyViolated);
+    }
+
     #[test]
     fn test_trait_display() {
         #[nutype(derive(Display))]
@@ -446,6 +1000,15 @@ mod derives {
         assert_eq!(name.to_string(), "Serhii");
     }
 
+    #[test]
+    fn test_trait_display_with_format() {
+        #[nutype(derive(Display), display = "@{}")]
+        pub struct Handle(String);
+
+        let handle = Handle::new("serhii");
+        assert_eq!(handle.to_string(), "@serhii");
+    }
+
     #[cfg(test)]
     mod trait_default {
         use super::*;
@@ -469,7 +1032,8 @@ mod derives {
         #[test]
         #[should_panic(expected = "Default value for type `Name` is invalid")]
         fn test_default_with_validation_when_invalid() {
-            #[nutype(validate(len_char_min = 5), default = "Nope", derive(Default))]
+            // Invalid literals are rejected at compile time, so an expression is used here.
+            #[nutype(validate(len_char_min = 5), default = String::from("Nope"), derive(Default))]
             pub struct Name(String);
 
             Name::default();
@@ -522,22 +1086,67 @@ mod derives {
                     assert_eq!(email.into_inner(), "foo@bar.com");
                 }
             }
-        }
-
-        mod ron_format {
-            use super::*;
 
             #[test]
-            fn test_ron_roundtrip() {
-                #[nutype(derive(Serialize, Deserialize, PartialEq, Debug))]
-                pub struct Email(String);
+            fn test_trait_deserialize_borrowed_str() {
+                #[nutype(validate(not_empty), derive(Debug, Deserialize), serde(borrow))]
+                pub struct Tag<'a>(&'a str);
 
-                let email = Email::new("me@example.com");
+                let input = String::from("\"rust\"");
+                let tag: Tag = serde_json::from_str(&input).unwrap();
+                assert_eq!(tag.into_inner(), "rust");
 
-                let serialized = ron::to_string(&email).unwrap();
-                let deserialized: Email = ron::from_str(&serialized).unwrap();
+                let res: Result<Tag, _> = serde_json::from_str("\"\"");
+                assert!(res.is_err());
 
-                assert_eq!(deserialized, email);
+                // Escaped strings can not be borrowed
+                let res: Result<Tag, _> = serde_json::from_str("\"ru\\u0073t\"");
+                assert!(res.is_err());
+            }
+
+            #[test]
+            fn test_trait_deserialize_borrowed_cow() {
+                #[nutype(sanitize(trim), derive(Debug, Deserialize), serde(borrow))]
+                pub struct Tag<'a>(alloc::borrow::Cow<'a, str>);
+
+                let tag: Tag = serde_json::from_str("\" rust \"").unwrap();
+                assert!(matches!(
+                    tag.into_inner(),
+                    alloc::borrow::Cow::Borrowed("rust")
+                ));
+
+                // Escaped strings can not be borrowed, so they are owned
+                let tag: Tag = serde_json::from_str("\"ru\\u0073t\"").unwrap();
+                assert!(matches!(tag.into_inner(), alloc::borrow::Cow::Owned(s) if s == "rust"));
+            }
+
+            #[test]
+            fn test_trait_deserialize_borrowed_cow_with_field() {
+                #[nutype(derive(Debug, Deserialize), serde(field = "name", borrow))]
+                pub struct Name<'a>(alloc::borrow::Cow<'a, str>);
+
+                let name: Name = serde_json::from_str(r#"{"name": "Anton"}"#).unwrap();
+                assert!(matches!(
+                    name.into_inner(),
+                    alloc::borrow::Cow::Borrowed("Anton")
+                ));
+            }
+        }
+
+        mod ron_format {
+            use super::*;
+
+            #[test]
+            fn test_ron_roundtrip() {
+                #[nutype(derive(Serialize, Deserialize, PartialEq, Debug))]
+                pub struct Email(String);
+
+                let email = Email::new("me@example.com");
+
+                let serialized = ron::to_string(&email).unwrap();
+                let deserialized: Email = ron::from_str(&serialized).unwrap();
+
+                assert_eq!(deserialized, email);
             }
         }
 
@@ -574,6 +1183,363 @@ mod new_unchecked {
     }
 }
 
+#[cfg(test)]
+#[cfg(feature = "validator")]
+mod derive_validator_validate {
+    use super::*;
+    use validator::{Validate, ValidateEmail};
+
+    #[nutype(
+        sanitize(trim),
+        validate(predicate = |email| email.validate_email()),
+        derive(Debug, Validate)
+    )]
+    pub struct Email(String);
+
+    #[derive(Validate)]
+    struct SignUpForm {
+        #[validate(nested)]
+        email: Email,
+
+        #[validate(length(min = 8))]
+        password: String,
+    }
+
+    #[test]
+    fn test_nested_newtype_is_valid() {
+        let form = SignUpForm {
+            email: Email::try_new(" bob@example.com ").unwrap(),
+            password: "short".to_string(),
+        };
+        let errors = form.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("password"));
+        assert!(!errors.errors().contains_key("email"));
+    }
+
+    #[test]
+    fn test_predicate_reuses_validator_function() {
+        assert_eq!(
+            Email::try_new("bob.example.com").unwrap_err(),
+            EmailError::PredicateViolated
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "borsh")]
+mod derive_borsh {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 20),
+        derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)
+    )]
+    pub struct Name(String);
+
+    #[test]
+    fn test_roundtrip() {
+        let name = Name::try_new(" Anna ").unwrap();
+        let bytes = borsh::to_vec(&name).unwrap();
+        assert_eq!(bytes, borsh::to_vec("Anna").unwrap());
+        assert_eq!(borsh::from_slice::<Name>(&bytes).unwrap(), name);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_value() {
+        let bytes = borsh::to_vec("").unwrap();
+        let err = borsh::from_slice::<Name>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "Name is empty. Expected valid Name");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "clap")]
+mod derive_clap {
+    use super::*;
+    use clap::Parser;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 10),
+        derive(Debug, Clone, PartialEq, ValueParserFactory)
+    )]
+    pub struct Username(String);
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[arg(long)]
+        user: Username,
+    }
+
+    #[test]
+    fn test_parse_argument() {
+        let cli = Cli::try_parse_from(["app", "--user", " alice "]).unwrap();
+        assert_eq!(cli.user, Username::try_new("alice").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_argument() {
+        let err = Cli::try_parse_from(["app", "--user", "  "]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("Username is empty."));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "rkyv")]
+mod derive_rkyv {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 20),
+        derive(Debug, PartialEq, Archive)
+    )]
+    pub struct Name(String);
+
+    #[test]
+    fn test_roundtrip() {
+        let name = Name::try_new(" Anna ").unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&name).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Name>, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_str(), "Anna");
+        assert_eq!(rkyv::from_bytes::<Name, Error>(&bytes).unwrap(), name);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_value() {
+        let bytes = rkyv::to_bytes::<Error>(&String::new()).unwrap();
+        let err = rkyv::from_bytes::<Name, Error>(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), "Name is empty.");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tracing")]
+mod tracing_events {
+    use super::*;
+    use alloc::sync::Arc;
+    use core::fmt;
+    use std::sync::Mutex;
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    /// Collects fields of every event as `name=value` strings.
+    #[derive(Clone, Default)]
+    struct RecordingSubscriber {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct FieldsVisitor(String);
+
+    impl Visit for FieldsVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push_str(&format!("{}={:?};", field.name(), value));
+        }
+    }
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = FieldsVisitor(format!("{}:", event.metadata().target()));
+            event.record(&mut visitor);
+            self.events.lock().unwrap().push(visitor.0);
+        }
+        fn enter(&self, _span: &span::Id) {}
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    #[nutype(validate(not_empty, len_char_max = 5), derive(Debug))]
+    pub struct Nickname(String);
+
+    #[test]
+    fn test_event_on_validation_failure() {
+        let subscriber = RecordingSubscriber::default();
+        let events = subscriber.events.clone();
+
+        tracing::subscriber::with_default(subscriber, || {
+            Nickname::try_new("Bob").unwrap();
+            Nickname::try_new("Frederick").unwrap_err();
+        });
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "nutype:message=validation failed;type_name=\"Nickname\";rule=\"len_char_max\";"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[nutype(secret, validate(in = ["alpha", "beta"]), derive(Debug))]
+    pub struct ApiKey(String);
+
+    #[test]
+    fn test_event_does_not_contain_secret_value() {
+        let subscriber = RecordingSubscriber::default();
+        let events = subscriber.events.clone();
+
+        tracing::subscriber::with_default(subscriber, || {
+            ApiKey::try_new("sk-live-1234").unwrap_err();
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec!["nutype:message=validation failed;type_name=\"ApiKey\";rule=\"in\";".to_string()]
+        );
+        assert!(events.iter().all(|event| !event.contains("sk-live-1234")));
+    }
+
+    #[nutype(
+        trace,
+        sanitize(trim, lowercase),
+        validate(not_empty, len_char_max = 5)
+    )]
+    pub struct Tag(String);
+
+    #[test]
+    fn test_trace_sanitizers_and_validators() {
+        let subscriber = RecordingSubscriber::default();
+        let events = subscriber.events.clone();
+
+        tracing::subscriber::with_default(subscriber, || {
+            Tag::try_new(" Rust ").unwrap();
+        });
+
+        let type_name = core::any::type_name::<Tag>();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                format!("nutype:message=sanitizer applied;type_name={type_name:?};sanitizer=\"trim\";output=\"Rust\";"),
+                format!("nutype:message=sanitizer applied;type_name={type_name:?};sanitizer=\"lowercase\";output=\"rust\";"),
+                format!("nutype:message=validator checked;type_name={type_name:?};validator=\"not_empty\";passed=true;"),
+                format!("nutype:message=validator checked;type_name={type_name:?};validator=\"len_char_max\";passed=true;"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_stops_at_first_failed_validator() {
+        let subscriber = RecordingSubscriber::default();
+        let events = subscriber.events.clone();
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(Tag::try_new("   ").is_err());
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        assert!(events[2].contains("validator=\"not_empty\";passed=false;"));
+        assert!(events[3].contains("message=validation failed;"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "metrics")]
+mod metrics_counters {
+    use super::*;
+    use alloc::sync::Arc;
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::Mutex;
+
+    /// Records every registered counter as `name{label=value,...}`.
+    /// `counter!()` registers the counter on each call, so this is a list of increments.
+    #[derive(Default)]
+    struct RecordingRecorder {
+        counters: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder for RecordingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            let entry = format!("{}{{{}}}", key.name(), labels.join(","));
+            self.counters.lock().unwrap().push(entry);
+            Counter::noop()
+        }
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[nutype(validate(not_empty, in = ["red", "green"]), derive(Debug))]
+    pub struct Color(String);
+
+    #[derive(Debug, PartialEq)]
+    pub struct SecretError(String);
+
+    fn validate_secret(value: &str) -> Result<(), SecretError> {
+        Err(SecretError(value.to_owned()))
+    }
+
+    #[nutype(validate(with = validate_secret, error = SecretError), derive(Debug))]
+    pub struct Secret(String);
+
+    #[test]
+    fn test_counter_on_validation_failure() {
+        let recorder = RecordingRecorder::default();
+
+        metrics::with_local_recorder(&recorder, || {
+            Color::try_new("red").unwrap();
+            Color::try_new("").unwrap_err();
+            Color::try_new("blue").unwrap_err();
+        });
+
+        assert_eq!(
+            *recorder.counters.lock().unwrap(),
+            vec![
+                "nutype_validation_failures_total{type=Color,rule=not_empty}".to_string(),
+                "nutype_validation_failures_total{type=Color,rule=in}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counter_label_does_not_contain_value() {
+        #[nutype(validate(in = [1, 2, 3]), derive(Debug, PartialEq))]
+        pub struct Lane(u8);
+
+        let recorder = RecordingRecorder::default();
+
+        metrics::with_local_recorder(&recorder, || {
+            assert_eq!(Lane::try_new(42).unwrap_err(), LaneError::InViolated(42));
+            Secret::try_new("hunter2").unwrap_err();
+        });
+
+        assert_eq!(
+            *recorder.counters.lock().unwrap(),
+            vec![
+                "nutype_validation_failures_total{type=Lane,rule=in}".to_string(),
+                "nutype_validation_failures_total{type=Secret,rule=with}".to_string(),
+            ]
+        );
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "schemars08")]
 mod derive_schemars_json_schema {
@@ -589,6 +1555,47 @@ mod derive_schemars_json_schema {
         // Make sure it compiles
         let _schema = schema_for!(CustomerIdentifier);
     }
+
+    #[test]
+    fn test_json_schema_with_metadata() {
+        #[nutype(
+            validate(not_empty),
+            derive(JsonSchema),
+            schema(
+                format = "email",
+                example = "alice@example.com",
+                description = "Email of a customer"
+            )
+        )]
+        pub struct Email(String);
+
+        assert_eq!(Email::schema_name(), "Email");
+        let schema = schema_for!(Email).schema;
+        assert_eq!(schema.format.as_deref(), Some("email"));
+        let metadata = schema.metadata.unwrap();
+        assert_eq!(metadata.description.as_deref(), Some("Email of a customer"));
+        assert_eq!(
+            metadata.examples,
+            vec![schemars::_serde_json::json!("alice@example.com")]
+        );
+    }
+
+    #[test]
+    fn test_json_schema_description_from_doc_comments() {
+        /// Name of a customer
+        /// as printed on the card.
+        #[nutype(derive(JsonSchema), schema(example = "Alice", example = "Bob"))]
+        pub struct Name(String);
+
+        let schema = schema_for!(Name).schema;
+        assert_eq!(schema.format, None);
+        let metadata = schema.metadata.unwrap();
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Name of a customer\nas printed on the card.")
+        );
+        assert_eq!(metadata.examples.len(), 2);
+    }
 }
 
 #[cfg(test)]
@@ -718,3 +1725,2137 @@ mod custom_error {
         assert_eq!(name.as_ref(), "John");
     }
 }
+
+mod borrowed_str {
+    use super::*;
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn test_str_with_validation() {
+        #[nutype(
+            sanitize(trim),
+            validate(not_empty, len_char_max = 5),
+            derive(Debug, Clone, Copy, PartialEq, AsRef, Deref, Display, TryFrom, Into)
+        )]
+        struct Name<'a>(&'a str);
+
+        let input = String::from("  Anton ");
+        let name = Name::try_new(input.as_str()).unwrap();
+        assert_eq!(name.into_inner(), "Anton");
+        assert_eq!(name.as_ref(), "Anton");
+        assert_eq!(name.len(), 5);
+        assert_eq!(name.to_string(), "Anton");
+
+        // The inner value points to the original string, nothing is allocated.
+        assert_eq!(name.into_inner().as_ptr(), input[2..].as_ptr());
+
+        let inner: &str = name.into();
+        assert_eq!(inner, "Anton");
+
+        assert_eq!(Name::try_new("   "), Err(NameError::NotEmptyViolated));
+        assert_eq!(Name::try_from("Serhii"), Err(NameError::LenCharMaxViolated));
+    }
+
+    #[test]
+    fn test_str_without_validation() {
+        #[nutype(derive(Debug, PartialEq, From, Borrow))]
+        struct Name<'a>(&'a str);
+
+        let name = Name::from("Anton");
+        assert_eq!(name, Name::new("Anton"));
+        let borrowed: &str = core::borrow::Borrow::borrow(&name);
+        assert_eq!(borrowed, "Anton");
+    }
+
+    #[test]
+    fn test_cow_trim_does_not_allocate() {
+        #[nutype(sanitize(trim), validate(len_char_min = 3), derive(Debug, PartialEq))]
+        struct Name<'a>(Cow<'a, str>);
+
+        let name = Name::try_new(" Anton ").unwrap();
+        assert!(matches!(name.into_inner(), Cow::Borrowed("Anton")));
+
+        let name = Name::try_new(String::from("Anton ")).unwrap();
+        assert!(matches!(name.into_inner(), Cow::Owned(s) if s == "Anton"));
+
+        assert_eq!(Name::try_new(" Jo "), Err(NameError::LenCharMinViolated));
+    }
+
+    #[test]
+    fn test_cow_with_allocating_sanitizers() {
+        #[nutype(
+            sanitize(trim, lowercase),
+            derive(Debug, PartialEq, FromStr, From, AsRef)
+        )]
+        struct Email<'a>(alloc::borrow::Cow<'a, str>);
+
+        let email = Email::new(" Anton@Example.com");
+        assert_eq!(email.as_ref(), "anton@example.com");
+
+        let email: Email<'static> = "BOB@example.com".parse().unwrap();
+        assert_eq!(email, Email::from(Cow::Borrowed("bob@example.com")));
+    }
+}
+
+mod legacy {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim),
+        validate(len_char_max = 5),
+        legacy(validate(len_char_max = 10)),
+        derive(Debug, PartialEq)
+    )]
+    struct Code(String);
+
+    #[test]
+    fn test_try_migrate() {
+        assert_eq!(Code::try_migrate(" abc ").unwrap().into_inner(), "abc");
+        assert_eq!(
+            Code::try_migrate("abcdefgh"),
+            Err(CodeMigrateError::Current(CodeError::LenCharMaxViolated))
+        );
+        assert_eq!(
+            Code::try_migrate("abcdefghijkl"),
+            Err(CodeMigrateError::Legacy(
+                CodeLegacyError::LenCharMaxViolated
+            ))
+        );
+    }
+
+    #[test]
+    fn test_try_new_ignores_legacy_rules() {
+        assert_eq!(
+            Code::try_new("abcdefgh"),
+            Err(CodeError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_migrate_error_display() {
+        let err = Code::try_migrate("abcdefghijkl").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Code cannot be migrated: Code is too long. The value length must be at most 10 characters."
+        );
+    }
+}
+
+#[cfg(test)]
+mod store_as {
+    use super::*;
+
+    fn encode_color(color: String) -> u8 {
+        match color.as_str() {
+            "red" => 0,
+            "green" => 1,
+            _ => 2,
+        }
+    }
+
+    fn decode_color(code: &u8) -> String {
+        match code {
+            0 => "red",
+            1 => "green",
+            _ => "blue",
+        }
+        .to_string()
+    }
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(in = ["red", "green", "blue"]),
+        store_as(u8, encode = encode_color, decode = decode_color),
+        derive(Debug, Display, Clone, PartialEq, Eq, Hash, FromStr, Into, TryFrom)
+    )]
+    struct Color(String);
+
+    #[test]
+    fn test_compact_representation() {
+        assert_eq!(core::mem::size_of::<Color>(), 1);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let color = Color::try_new(" Green ").unwrap();
+        assert_eq!(color.clone().into_inner(), "green");
+        assert_eq!(color, "green".parse().unwrap());
+        assert_eq!(
+            Color::try_new("purple"),
+            Err(ColorError::InViolated("purple".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_traits_use_inner_value() {
+        let color = Color::try_from("blue").unwrap();
+        assert_eq!(format!("{color:?}"), r#"Color("blue")"#);
+        assert_eq!(color.to_string(), "blue");
+        let inner: String = color.into();
+        assert_eq!(inner, "blue");
+    }
+
+    #[test]
+    fn test_closures() {
+        #[nutype(
+            store_as(Box<str>, encode = |s| s.into_boxed_str(), decode = |s| s.to_string()),
+            derive(Debug)
+        )]
+        struct Name(String);
+
+        let name = Name::new("Anna");
+        assert_eq!(format!("{name:?}"), r#"Name("Anna")"#);
+        assert_eq!(name.into_inner(), "Anna");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[nutype(
+            validate(in = ["red", "green", "blue"]),
+            store_as(u8, encode = encode_color, decode = decode_color),
+            derive(Debug, PartialEq, Serialize, Deserialize)
+        )]
+        struct SerdeColor(String);
+
+        let color = SerdeColor::try_new("green").unwrap();
+        assert_eq!(serde_json::to_string(&color).unwrap(), r#""green""#);
+        let parsed: SerdeColor = serde_json::from_str(r#""green""#).unwrap();
+        assert_eq!(parsed, color);
+        assert!(serde_json::from_str::<SerdeColor>(r#""pink""#).is_err());
+    }
+}
+
+#[cfg(test)]
+mod storage {
+    use super::*;
+    use alloc::sync::Arc;
+    use std::collections::HashSet;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 20),
+        storage = Box<str>,
+        derive(Debug, Display, Clone, PartialEq, Eq, Hash, AsRef, Deref, Borrow, Into)
+    )]
+    struct Username(String);
+
+    #[test]
+    fn test_box_str() {
+        assert_eq!(
+            core::mem::size_of::<Username>(),
+            core::mem::size_of::<Box<str>>()
+        );
+
+        let username = Username::try_new("  alice ").unwrap();
+        assert_eq!(username.as_ref(), "alice");
+        assert_eq!(username.len(), 5);
+        assert_eq!(format!("{username:?}"), r#"Username("alice")"#);
+        assert_eq!(username.to_string(), "alice");
+        assert_eq!(
+            Username::try_new("  "),
+            Err(UsernameError::NotEmptyViolated)
+        );
+
+        let usernames: HashSet<Username> = [username.clone()].into();
+        assert!(usernames.contains("alice"));
+
+        let inner: String = username.into();
+        assert_eq!(inner, "alice");
+    }
+
+    #[test]
+    fn test_arc_str() {
+        #[nutype(
+            sanitize(lowercase),
+            storage = Arc<str>,
+            derive(Debug, Clone, PartialEq, PartialOrd, AsRef)
+        )]
+        struct Tag(String);
+
+        let tag = Tag::new("Rust");
+        let shared = tag.clone();
+        assert_eq!(shared.as_ref(), "rust");
+        assert!(Tag::new("async") < tag);
+        assert_eq!(tag.into_inner(), "rust");
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_heapless_string() {
+        #[nutype(
+            sanitize(trim),
+            validate(not_empty, len_byte_max = 8),
+            storage = heapless::String,
+            derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, Deref)
+        )]
+        struct DeviceName(String);
+
+        assert_eq!(
+            core::mem::size_of::<DeviceName>(),
+            core::mem::size_of::<heapless::String<8>>()
+        );
+        let name = DeviceName::try_new(" sensor-1 ").unwrap();
+        assert_eq!(name.as_ref(), "sensor-1");
+        assert_eq!(format!("{name:?}"), r#"DeviceName("sensor-1")"#);
+        assert_eq!(
+            DeviceName::try_new("sensor-10"),
+            Err(DeviceNameError::LenByteMaxViolated)
+        );
+        assert_eq!(name.into_inner(), "sensor-1");
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn test_array_string() {
+        #[nutype(
+            validate(len_char_min = 2, len_char_max = 3),
+            storage = arrayvec::ArrayString,
+            derive(Debug, Clone, PartialEq, PartialOrd, AsRef, Borrow)
+        )]
+        struct Code(String);
+
+        assert_eq!(Code::MAX_LEN, 3);
+        assert_eq!(
+            core::mem::size_of::<Code>(),
+            core::mem::size_of::<arrayvec::ArrayString<12>>()
+        );
+        let code = Code::try_new("ÄÖÜ").unwrap();
+        assert_eq!(code.as_ref(), "ÄÖÜ");
+        assert!(Code::try_new("AB").unwrap() < code);
+        assert_eq!(Code::try_new("ABCD"), Err(CodeError::LenCharMaxViolated));
+    }
+}
+
+#[cfg(test)]
+mod normalize_for_eq {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty),
+        normalize_for_eq = |email| email.to_lowercase(),
+        derive(Debug, Display, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)
+    )]
+    struct Email(String);
+
+    #[test]
+    fn test_eq_and_hash_use_normalized_value() {
+        let alice = Email::try_new("Alice@Example.com").unwrap();
+        let same_alice = Email::try_new(" alice@example.COM ").unwrap();
+        assert_eq!(alice, same_alice);
+        assert_ne!(alice, Email::try_new("bob@example.com").unwrap());
+
+        let emails: HashSet<Email> = [alice.clone(), same_alice].into();
+        assert_eq!(emails.len(), 1);
+        assert!(Email::try_new("Bob@example.com").unwrap() > alice);
+    }
+
+    #[test]
+    fn test_original_value_is_preserved() {
+        let email = Email::try_new("Alice@Example.com").unwrap();
+        assert_eq!(email.to_string(), "Alice@Example.com");
+        assert_eq!(email.into_inner(), "Alice@Example.com");
+    }
+
+    fn without_dashes(isbn: &str) -> String {
+        isbn.replace('-', "")
+    }
+
+    #[test]
+    fn test_function_path() {
+        #[nutype(normalize_for_eq = without_dashes, derive(Debug, PartialEq))]
+        struct Isbn(String);
+
+        assert_eq!(Isbn::new("978-3-16-148410-0"), Isbn::new("9783161484100"));
+    }
+}
+
+#[cfg(test)]
+mod as_variants {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(in = ["red", "green", "dark-blue"]),
+        as_variants,
+        derive(Debug)
+    )]
+    struct Color(String);
+
+    #[test]
+    fn test_as_variant() {
+        let color = Color::try_new(" Dark-Blue ").unwrap();
+        assert_eq!(color.as_variant(), ColorVariant::DarkBlue);
+
+        let description = match Color::try_new("red").unwrap().as_variant() {
+            ColorVariant::Red => "warm",
+            ColorVariant::Green | ColorVariant::DarkBlue => "cold",
+        };
+        assert_eq!(description, "warm");
+    }
+
+    #[test]
+    fn test_variant_display_and_from_str_round_trip() {
+        for variant in [
+            ColorVariant::Red,
+            ColorVariant::Green,
+            ColorVariant::DarkBlue,
+        ] {
+            let literal = variant.to_string();
+            assert_eq!(literal.parse::<ColorVariant>(), Ok(variant));
+        }
+        assert_eq!(ColorVariant::DarkBlue.to_string(), "dark-blue");
+    }
+
+    #[test]
+    fn test_variant_from_str_accepts_only_canonical_literals() {
+        assert_eq!("Red".parse::<ColorVariant>(), Err(ColorVariantParseError));
+        assert_eq!(" red".parse::<ColorVariant>(), Err(ColorVariantParseError));
+        assert_eq!(
+            ColorVariantParseError.to_string(),
+            r#"Failed to parse ColorVariant: expected one of ["red", "green", "dark-blue"]."#
+        );
+    }
+
+    #[test]
+    fn test_variant_to_newtype_and_back() {
+        let color = Color::try_new(ColorVariant::Green.to_string()).unwrap();
+        assert_eq!(ColorVariant::from(&color), ColorVariant::Green);
+    }
+
+    const ADMIN: &str = "admin";
+    const GUEST: &str = "guest";
+
+    #[nutype(validate(in = [ADMIN, GUEST]), as_variants)]
+    struct Role<'a>(&'a str);
+
+    #[test]
+    fn test_borrowed_str_with_constants() {
+        assert_eq!(
+            Role::try_new("guest").unwrap().as_variant(),
+            RoleVariant::Guest
+        );
+    }
+
+    #[test]
+    fn test_variant_from_str_with_constants() {
+        assert_eq!("admin".parse::<RoleVariant>(), Ok(RoleVariant::Admin));
+        assert_eq!(RoleVariant::Guest.to_string(), GUEST);
+    }
+
+    #[nutype(
+        validate(in = ["on", "off"]),
+        as_variants,
+        store_as(bool, encode = |s| s == "on", decode = |b| if *b { "on" } else { "off" }.to_string()),
+    )]
+    struct Switch(String);
+
+    #[test]
+    fn test_with_store_as() {
+        assert_eq!(
+            Switch::try_new("off").unwrap().as_variant(),
+            SwitchVariant::Off
+        );
+    }
+}
+
+mod eq_raw {
+    use super::*;
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn test_eq_raw_applies_sanitizers() {
+        #[nutype(sanitize(trim, lowercase), validate(not_empty))]
+        struct Email(String);
+
+        let email = Email::try_new("bob@example.com").unwrap();
+        assert!(email.eq_raw("  Bob@Example.COM "));
+        assert!(!email.eq_raw("alice@example.com"));
+    }
+
+    #[test]
+    fn test_eq_raw_without_sanitizers() {
+        #[nutype(derive(Debug))]
+        struct Name(String);
+
+        let name = Name::new("Bob");
+        assert!(name.eq_raw("Bob"));
+        assert!(!name.eq_raw(" Bob"));
+    }
+
+    #[test]
+    fn test_eq_raw_with_borrowed_inner_types() {
+        #[nutype(sanitize(trim))]
+        struct Word<'a>(&'a str);
+
+        #[nutype(sanitize(trim, uppercase))]
+        struct Code<'a>(Cow<'a, str>);
+
+        assert!(Word::new("hello").eq_raw(" hello "));
+        assert!(Code::new("ab1").eq_raw("Ab1 "));
+    }
+
+    #[test]
+    fn test_eq_raw_with_store_as() {
+        #[nutype(
+            sanitize(trim),
+            store_as(bool, encode = |s| s == "on", decode = |b| if *b { "on" } else { "off" }.to_string()),
+        )]
+        struct Switch(String);
+
+        assert!(Switch::new("on").eq_raw(" on"));
+        assert!(!Switch::new("on").eq_raw("off"));
+    }
+}
+
+#[cfg(test)]
+mod optional {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_and_validate_some() {
+        #[nutype(
+            sanitize(trim),
+            validate(not_empty, len_char_max = 10),
+            derive(Debug, PartialEq)
+        )]
+        struct MiddleName(Option<String>);
+
+        assert_eq!(MiddleName::try_new(None).unwrap().into_inner(), None);
+        assert_eq!(
+            MiddleName::try_new(Some("  Lee ".to_string()))
+                .unwrap()
+                .into_inner(),
+            Some("Lee".to_string())
+        );
+        assert_eq!(
+            MiddleName::try_new(Some("   ".to_string())),
+            Err(MiddleNameError::NotEmptyViolated)
+        );
+        assert_eq!(
+            MiddleName::try_new(Some("Bartholomew".to_string())),
+            Err(MiddleNameError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_without_validation() {
+        #[nutype(sanitize(trim, lowercase), derive(Debug, Clone, PartialEq))]
+        struct Nickname(Option<String>);
+
+        assert_eq!(
+            Nickname::new(Some(" BOB ".to_string())).as_inner(),
+            Some(&"bob".to_string())
+        );
+        assert_eq!(Nickname::new(None).as_inner(), None);
+    }
+
+    #[test]
+    fn test_required() {
+        #[nutype(required, validate(not_empty), derive(Debug, PartialEq))]
+        struct MiddleName(Option<String>);
+
+        assert_eq!(
+            MiddleName::try_new(None),
+            Err(MiddleNameError::RequiredViolated)
+        );
+        assert_eq!(
+            MiddleName::try_new(None).unwrap_err().to_string(),
+            "MiddleName is required."
+        );
+        assert!(MiddleName::try_new(Some("Lee".to_string())).is_ok());
+    }
+
+    #[test]
+    fn test_required_without_validation() {
+        #[nutype(required, derive(Debug))]
+        struct MiddleName(Option<String>);
+
+        assert_eq!(
+            MiddleName::try_new(None).unwrap_err(),
+            MiddleNameError::RequiredViolated
+        );
+        assert_eq!(
+            MiddleName::try_new(Some(String::new()))
+                .unwrap()
+                .into_inner(),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn test_traits() {
+        #[nutype(
+            validate(not_empty),
+            derive(Debug, Clone, PartialEq, Eq, Hash, Into, TryFrom, Default),
+            default = None
+        )]
+        struct MiddleName(Option<String>);
+
+        assert_eq!(MiddleName::default().into_inner(), None);
+
+        let name = MiddleName::try_from(Some("Lee".to_string())).unwrap();
+        let inner: Option<String> = name.clone().into();
+        assert_eq!(inner, Some("Lee".to_string()));
+        assert!(MiddleName::try_from(Some(String::new())).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[nutype(validate(not_empty), derive(Debug, PartialEq, Serialize, Deserialize))]
+        struct MiddleName(Option<String>);
+
+        let name = MiddleName::try_new(Some("Lee".to_string())).unwrap();
+        assert_eq!(serde_json::to_string(&name).unwrap(), "\"Lee\"");
+        assert_eq!(
+            serde_json::to_string(&MiddleName::try_new(None).unwrap()).unwrap(),
+            "null"
+        );
+
+        let name: MiddleName = serde_json::from_str("null").unwrap();
+        assert_eq!(name.into_inner(), None);
+        let name: MiddleName = serde_json::from_str("\"Lee\"").unwrap();
+        assert_eq!(name.into_inner(), Some("Lee".to_string()));
+        assert!(serde_json::from_str::<MiddleName>("\"\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod newtype_traits {
+    use super::*;
+    use nutype::{Newtype, TryNewtype};
+
+    fn field_value<T: Newtype<Inner = String>>(value: &T) -> &str {
+        value.get()
+    }
+
+    #[test]
+    fn test_generic_over_newtypes() {
+        #[nutype(sanitize(trim), validate(not_empty), derive(Debug))]
+        struct Name(String);
+
+        #[nutype(sanitize(lowercase))]
+        struct Tag(String);
+
+        let name = <Name as TryNewtype>::try_new(" Bob ".to_string()).unwrap();
+        let tag = <Tag as TryNewtype>::try_new("RUST".to_string()).unwrap();
+        assert_eq!(field_value(&name), "Bob");
+        assert_eq!(field_value(&tag), "rust");
+        assert_eq!(
+            <Name as TryNewtype>::try_new(String::new()).unwrap_err(),
+            NameError::NotEmptyViolated
+        );
+    }
+}
+
+#[cfg(test)]
+mod secret {
+    use super::*;
+
+    #[nutype(
+        secret,
+        sanitize(trim),
+        validate(not_empty),
+        derive(Debug, Display, Clone, PartialEq, AsRef)
+    )]
+    pub struct ApiKey(String);
+
+    #[test]
+    fn test_debug_and_display_are_redacted() {
+        let key = ApiKey::try_new(" sk-12345 ").unwrap();
+        assert_eq!(format!("{key:?}"), "ApiKey(***)");
+        assert_eq!(key.to_string(), "ApiKey(***)");
+        assert_eq!(format!("{:?}", Some(&key)), "Some(ApiKey(***))");
+    }
+
+    #[test]
+    fn test_value_is_accessible() {
+        let key = ApiKey::try_new(" sk-12345 ").unwrap();
+        assert_eq!(key.as_ref(), "sk-12345");
+        assert_eq!(key.clone().into_inner(), "sk-12345");
+        assert_eq!(ApiKey::try_new("  "), Err(ApiKeyError::NotEmptyViolated));
+    }
+
+    #[test]
+    fn test_optional_secret() {
+        #[nutype(secret, derive(Debug))]
+        pub struct Password(Option<String>);
+
+        let password = Password::new(Some("hunter2".to_string()));
+        assert_eq!(format!("{password:?}"), "Password(***)");
+        assert_eq!(password.into_inner(), Some("hunter2".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_allowed_explicitly() {
+        #[nutype(secret(serialize), derive(Debug, Serialize))]
+        pub struct Token(String);
+
+        let token = Token::new("abc");
+        assert_eq!(format!("{token:?}"), "Token(***)");
+        assert_eq!(serde_json::to_string(&token).unwrap(), "\"abc\"");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_on_drop() {
+        #[nutype(secret(zeroize), validate(len_char_min = 4), derive(Debug, Clone))]
+        pub struct Pin(String);
+
+        assert!(core::mem::needs_drop::<Pin>());
+        let pin = Pin::try_new("1234").unwrap();
+        assert_eq!(format!("{pin:?}"), "Pin(***)");
+        assert_eq!(pin.into_inner(), "1234");
+    }
+}
+
+#[cfg(test)]
+mod profile {
+    use super::*;
+
+    #[nutype(
+        profile = "strict",
+        sanitize(trim),
+        validate(len_char_max = 5, predicate = |s| s.chars().all(char::is_lowercase)),
+        derive(Debug, Display, PartialEq)
+    )]
+    pub struct Username(String);
+
+    #[test]
+    fn test_strict_debug_is_redacted() {
+        let name = Username::try_new(" alice ").unwrap();
+        assert_eq!(format!("{name:?}"), "Username(***)");
+        assert_eq!(name.to_string(), "alice");
+    }
+
+    #[test]
+    fn test_strict_validate_all() {
+        let errors: Vec<UsernameError> = Username::validate_all("ALICE BOB").collect();
+        assert_eq!(
+            errors,
+            vec![
+                UsernameError::LenCharMaxViolated,
+                UsernameError::PredicateViolated
+            ]
+        );
+        assert_eq!(Username::validate_all(" alice ").count(), 0);
+        assert_eq!(
+            Username::try_new("ALICE BOB"),
+            Err(UsernameError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_default_profile() {
+        #[nutype(profile = "default", derive(Debug))]
+        pub struct Nickname(String);
+
+        assert_eq!(format!("{:?}", Nickname::new("bob")), "Nickname(\"bob\")");
+    }
+
+    #[test]
+    fn test_strict_optional() {
+        #[nutype(profile = "strict", derive(Debug))]
+        pub struct MiddleName(Option<String>);
+
+        assert_eq!(
+            format!("{:?}", MiddleName::new(Some("Lee".to_string()))),
+            "MiddleName(***)"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_strict_denies_unknown_fields() {
+        #[nutype(profile = "strict", derive(Debug, Deserialize), serde(field = "name"))]
+        pub struct Login(String);
+
+        let login: Login = serde_json::from_str(r#"{"name": "alice"}"#).unwrap();
+        assert_eq!(login.into_inner(), "alice");
+
+        let err = serde_json::from_str::<Login>(r#"{"name": "alice", "admin": true}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("unknown field `admin`, expected `name`"),
+            "{err}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod refines {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(len_char_min = 3, len_char_max = 20),
+        derive(Debug, PartialEq, AsRef)
+    )]
+    pub struct Username(String);
+
+    #[nutype(
+        refines = Username,
+        validate(len_char_max = 8, predicate = |name| name.chars().all(char::is_alphabetic)),
+        derive(Debug, PartialEq, AsRef)
+    )]
+    pub struct PremiumUsername(String);
+
+    #[nutype(refines = Username, derive(Debug, AsRef))]
+    pub struct DisplayName(String);
+
+    #[test]
+    fn test_try_from_refined_type() {
+        let name = Username::try_new("  Alice ").unwrap();
+        let premium = PremiumUsername::try_from(name).unwrap();
+        assert_eq!(premium.as_ref(), "alice");
+
+        let long_name = Username::try_new("alexander_the_great").unwrap();
+        assert_eq!(
+            PremiumUsername::try_from(long_name),
+            Err(PremiumUsernameError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_from_refined_type_without_validation() {
+        let name = Username::try_new("bob").unwrap();
+        let display_name = DisplayName::from(name);
+        assert_eq!(display_name.as_ref(), "bob");
+    }
+}
+
+#[cfg(test)]
+mod nutype_wrap {
+    use super::*;
+    use nutype::NutypeWrap;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty),
+        derive(Debug, Clone, PartialEq, AsRef)
+    )]
+    pub struct UserName(String);
+
+    #[nutype(validate(less_or_equal = 150), derive(Debug, Clone, Copy, PartialEq))]
+    pub struct Age(u8);
+
+    #[derive(Debug, Clone, PartialEq, NutypeWrap)]
+    #[wrap(name = ValidUser, derive(Debug, Clone, PartialEq))]
+    pub struct User {
+        #[wrap(UserName)]
+        pub name: String,
+        #[wrap(Age)]
+        pub age: u8,
+        pub is_admin: bool,
+    }
+
+    #[test]
+    fn test_try_from_raw_struct() {
+        let raw = User {
+            name: " Alice ".to_string(),
+            age: 30,
+            is_admin: false,
+        };
+        let user = ValidUser::try_from(raw).unwrap();
+        assert_eq!(user.name.as_ref(), "Alice");
+        assert_eq!(user.age.into_inner(), 30);
+        assert!(!user.is_admin);
+
+        let raw = User {
+            name: "Bob".to_string(),
+            age: 200,
+            is_admin: true,
+        };
+        let err = ValidUser::try_from(raw).unwrap_err();
+        assert_eq!(err, ValidUserError::Age(AgeError::LessOrEqualViolated));
+        assert_eq!(
+            err.to_string(),
+            format!("age: {}", AgeError::LessOrEqualViolated)
+        );
+    }
+
+    #[test]
+    fn test_from_wrapped_struct() {
+        let user = ValidUser {
+            name: UserName::try_new("Carol").unwrap(),
+            age: Age::try_new(40).unwrap(),
+            is_admin: true,
+        };
+        let raw = User::from(user);
+        assert_eq!(
+            raw,
+            User {
+                name: "Carol".to_string(),
+                age: 40,
+                is_admin: true,
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod new_into {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(not_empty),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Tag(String);
+
+    #[test]
+    fn test_try_new_into_reuses_buffer() {
+        let mut buffer = String::with_capacity(64);
+        let tag = Tag::try_new_into(" Rust ", &mut buffer).unwrap();
+        assert_eq!(tag.into_inner().capacity(), 64);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_try_new_into_returns_buffer_on_failure() {
+        let mut buffer = String::with_capacity(64);
+        buffer.push_str("leftover");
+        assert_eq!(
+            Tag::try_new_into("   ", &mut buffer),
+            Err(TagError::NotEmptyViolated)
+        );
+        assert_eq!(buffer.capacity(), 64);
+
+        let tag = Tag::try_new_into("Nutype", &mut buffer).unwrap();
+        assert_eq!(tag.into_inner(), "nutype");
+    }
+
+    #[test]
+    fn test_new_into() {
+        #[nutype(sanitize(trim))]
+        pub struct Line(String);
+
+        let mut buffer = String::with_capacity(32);
+        let line = Line::new_into("  hello ", &mut buffer);
+        assert_eq!(line.into_inner(), "hello");
+    }
+}
+
+mod with_context {
+    use super::*;
+
+    pub struct Settings {
+        max_username_len: usize,
+    }
+
+    fn fits_settings(username: &str, settings: &Settings) -> bool {
+        username.len() <= settings.max_username_len
+    }
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, with_context = fits_settings, context = Settings),
+        derive(Debug, PartialEq, AsRef)
+    )]
+    pub struct Username(String);
+
+    #[test]
+    fn test_try_from_with() {
+        let settings = Settings {
+            max_username_len: 5,
+        };
+        let username = Username::try_from_with(&settings, "  alice ").unwrap();
+        assert_eq!(username.as_ref(), "alice");
+
+        assert_eq!(
+            Username::try_from_with(&settings, "bob the builder"),
+            Err(UsernameError::WithContextViolated)
+        );
+        assert_eq!(
+            Username::try_from_with(&settings, "   "),
+            Err(UsernameError::NotEmptyViolated)
+        );
+    }
+
+    #[test]
+    fn test_with_context_closure() {
+        #[nutype(
+            validate(with_context = |name, banned: &Vec<String>| !banned.iter().any(|b| b == name), context = Vec<String>),
+            derive(Debug)
+        )]
+        pub struct Nickname(String);
+
+        let banned = vec!["admin".to_string()];
+        assert!(Nickname::try_from_with(&banned, "ferris").is_ok());
+        assert_eq!(
+            Nickname::try_from_with(&banned, "admin").unwrap_err(),
+            NicknameError::WithContextViolated
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let settings = Settings {
+            max_username_len: 1,
+        };
+        let err = Username::try_from_with(&settings, "alice").unwrap_err();
+        assert_eq!(err.to_string(), "Username failed the context validation.");
+    }
+}
+
+mod error_codes {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 20, predicate = |s| s.chars().all(|c| c.is_ascii_lowercase())),
+        derive(Debug)
+    )]
+    pub struct Login(String);
+
+    #[test]
+    fn test_code_and_bound() {
+        let err = Login::try_new("   ").unwrap_err();
+        assert_eq!(err.code(), "not_empty");
+        assert_eq!(err.bound(), None);
+
+        let err = Login::try_new("a".repeat(21)).unwrap_err();
+        assert_eq!(err.code(), "len_char_max");
+        assert_eq!(err.bound(), Some(20));
+
+        assert_eq!(Login::try_new("Ferris").unwrap_err().code(), "predicate");
+    }
+
+    #[test]
+    fn test_code_of_required() {
+        #[nutype(required, validate(len_char_min = 3), derive(Debug))]
+        pub struct Nickname(Option<String>);
+
+        let err = Nickname::try_new(None).unwrap_err();
+        assert_eq!(err.code(), "required");
+        assert_eq!(err.bound(), None);
+        assert_eq!(
+            Nickname::try_new(Some("ab".to_string()))
+                .unwrap_err()
+                .bound(),
+            Some(3)
+        );
+    }
+}
+
+#[cfg(feature = "from_schema")]
+mod from_schema {
+    use super::*;
+    use nutype::nutype_from_schema;
+
+    #[nutype_from_schema(
+        r#"{
+        "type": "string",
+        "description": "Login of a user",
+        "minLength": 3,
+        "maxLength": 8,
+        "enum": ["alice", "bob", "charlie", "ferris", "administrator"]
+    }"#
+    )]
+    #[nutype(sanitize(trim), validate(not_empty), derive(Debug, PartialEq))]
+    pub struct Login(String);
+
+    #[test]
+    fn test_rules_from_schema() {
+        assert_eq!(Login::try_new(" alice ").unwrap().into_inner(), "alice");
+        assert_eq!(Login::try_new("   "), Err(LoginError::NotEmptyViolated));
+        assert_eq!(Login::try_new("bo"), Err(LoginError::LenCharMinViolated));
+        assert_eq!(
+            Login::try_new("administrator"),
+            Err(LoginError::LenCharMaxViolated)
+        );
+        assert_eq!(
+            Login::try_new("mallory"),
+            Err(LoginError::InViolated("mallory".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_without_nutype_attribute() {
+        #[nutype_from_schema(r#"{ "maxLength": 2 }"#)]
+        pub struct Code(String);
+
+        assert!(Code::try_new("ab").is_ok());
+        assert!(Code::try_new("abc").is_err());
+    }
+}
+
+#[cfg(feature = "inventory")]
+mod inventory {
+    use super::*;
+
+    #[nutype(sanitize(trim, lowercase), validate(not_empty, len_char_max = 64))]
+    pub struct Email(String);
+
+    #[nutype(validate(less_or_equal = 150))]
+    pub struct Age(Option<u8>);
+
+    fn find(name: &str) -> &'static nutype::NewtypeInfo {
+        nutype::registered_newtypes()
+            .find(|info| info.metadata.name == name && info.module_path == module_path!())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_registered_newtypes() {
+        let email = find("Email").metadata;
+        assert_eq!(email.inner_type, "String");
+        assert!(email
+            .sanitizers()
+            .map(|rule| rule.name)
+            .eq(["trim", "lowercase"]));
+        assert!(email
+            .validators()
+            .map(|rule| rule.name)
+            .eq(["not_empty", "len_char_max"]));
+        assert!(email.guarantees().eq([
+            "trimmed",
+            "lowercase",
+            "not empty",
+            "at most 64 characters"
+        ]));
+
+        let age = find("Age").metadata;
+        assert_eq!(age.inner_type, "Option < u8 >");
+        assert_eq!(age.sanitizers().count(), 0);
+        assert!(age.validators().map(|rule| rule.name).eq(["less_or_equal"]));
+        assert!(age.guarantees().eq(["at most 150"]));
+    }
+
+    #[test]
+    fn test_registered_metadata_matches_constant() {
+        assert_eq!(find("Email").metadata, Email::METADATA);
+        assert_eq!(find("Age").metadata, Age::METADATA);
+        assert_eq!(
+            find("Age").metadata.rules,
+            [nutype::RuleMetadata {
+                stage: nutype::RuleStage::Validator,
+                name: "less_or_equal",
+                bound: Some(150.0),
+                description: Some("at most 150"),
+            }]
+        );
+    }
+}
+
+#[cfg(test)]
+mod method_names {
+    use super::*;
+
+    #[test]
+    fn test_constructor_and_getter() {
+        #[nutype(
+            constructor = parse,
+            getter = value,
+            sanitize(trim, lowercase),
+            validate(not_empty),
+            derive(Debug, PartialEq, TryFrom, FromStr)
+        )]
+        pub struct Email(String);
+
+        let email = Email::parse("  Bob@Example.com ").unwrap();
+        assert_eq!(email.value(), "bob@example.com");
+        assert_eq!(Email::parse(" "), Err(EmailError::NotEmptyViolated));
+
+        // The traits keep working with the renamed methods.
+        assert_eq!(
+            Email::try_from("bob@example.com"),
+            Email::parse("bob@example.com")
+        );
+        assert_eq!(
+            "bob@example.com".parse::<Email>(),
+            Email::parse("bob@example.com")
+        );
+    }
+
+    #[test]
+    fn test_constructor_without_validation() {
+        #[nutype(constructor = from_raw, sanitize(trim), derive(Debug, AsRef))]
+        pub struct Title(String);
+
+        assert_eq!(Title::from_raw("  Hello ").as_ref(), "Hello");
+    }
+
+    mod repository {
+        use nutype::nutype;
+
+        #[nutype(
+            constructor_visibility = pub(self),
+            sanitize(trim),
+            validate(not_empty),
+            derive(Debug, Clone, PartialEq)
+        )]
+        pub struct UserName(String);
+
+        pub fn load(raw: &str) -> Result<UserName, UserNameError> {
+            UserName::try_new(raw)
+        }
+
+        pub mod legacy {
+            use nutype::nutype;
+
+            #[nutype(
+                constructor = from_raw,
+                constructor_visibility = pub(super),
+                sanitize(trim),
+                derive(Debug)
+            )]
+            pub struct Nickname(String);
+        }
+
+        pub fn load_nickname(raw: &str) -> legacy::Nickname {
+            legacy::Nickname::from_raw(raw)
+        }
+    }
+
+    #[test]
+    fn test_constructor_visibility() {
+        // The type is public, but only `repository` can construct it.
+        let name = repository::load(" alice ").unwrap();
+        assert_eq!(name.clone().into_inner(), "alice");
+        assert_eq!(
+            repository::load(" "),
+            Err(repository::UserNameError::NotEmptyViolated)
+        );
+
+        let nickname = repository::load_nickname(" Al ");
+        assert_eq!(nickname.into_inner(), "Al");
+    }
+}
+
+#[cfg(test)]
+mod ffi {
+    use super::*;
+    use core::ffi::c_char;
+
+    #[test]
+    fn test_try_from_c_string() {
+        #[nutype(ffi, sanitize(trim), validate(not_empty), derive(Debug))]
+        pub struct Username(String);
+
+        let raw = c"  alice ";
+        let username = Username::try_from(raw.as_ptr()).unwrap();
+        assert_eq!(username.into_inner(), "alice");
+
+        assert!(matches!(
+            Username::try_from(c" ".as_ptr()),
+            Err(UsernameFfiError::Validate(UsernameError::NotEmptyViolated))
+        ));
+        assert!(matches!(
+            Username::try_from(core::ptr::null::<c_char>()),
+            Err(UsernameFfiError::NullPointer)
+        ));
+        let invalid_utf8 = c"\xff";
+        assert_eq!(
+            Username::try_from(invalid_utf8.as_ptr())
+                .unwrap_err()
+                .to_string(),
+            "Failed to convert into Username: invalid utf-8 sequence of 1 bytes from index 0"
+        );
+    }
+
+    #[test]
+    fn test_accessors() {
+        #[nutype(ffi, derive(Debug))]
+        pub struct Title(String);
+
+        let title = Title::try_from(c"Hello".as_ptr()).unwrap();
+        // SAFETY: the pointer and the length describe the bytes of the title.
+        let bytes = unsafe { core::slice::from_raw_parts(title.ffi_as_ptr(), title.ffi_len()) };
+        assert_eq!(bytes, b"Hello");
+        assert_eq!(
+            core::mem::size_of::<Title>(),
+            core::mem::size_of::<String>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod error_messages {
+    use super::*;
+
+    #[test]
+    fn test_default_messages_mention_both_bounds() {
+        #[nutype(validate(len_char_min = 3, len_char_max = 50), derive(Debug))]
+        pub struct Username(String);
+
+        assert_eq!(
+            Username::try_new("ab").unwrap_err().to_string(),
+            "Username is too short. The value length must be between 3 and 50 characters."
+        );
+
+        #[nutype(validate(len_byte_max = 5), derive(Debug))]
+        pub struct Nickname(String);
+
+        assert_eq!(
+            Nickname::try_new("abcdef").unwrap_err().to_string(),
+            "Nickname is too long. The value length must be at most 5 bytes."
+        );
+    }
+
+    #[test]
+    fn test_messages() {
+        #[nutype(
+            validate(
+                not_empty,
+                len_char_max = 20,
+                messages(
+                    not_empty = "Please enter a {type}.",
+                    len_char_max = "{type} can have at most {limit} characters."
+                )
+            ),
+            derive(Debug)
+        )]
+        pub struct Title(String);
+
+        assert_eq!(
+            Title::try_new("").unwrap_err().to_string(),
+            "Please enter a Title."
+        );
+        assert_eq!(
+            Title::try_new("a".repeat(21)).unwrap_err().to_string(),
+            "Title can have at most 20 characters."
+        );
+    }
+}
+
+#[cfg(test)]
+mod combinators {
+    use super::*;
+
+    #[test]
+    fn test_any_of() {
+        #[nutype(
+            validate(not_empty, any_of(len_char_max = 3, len_char_min = 8)),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Code(String);
+
+        assert!(Code::try_new("abc").is_ok());
+        assert!(Code::try_new("abcdefgh").is_ok());
+        assert_eq!(Code::try_new(""), Err(CodeError::NotEmptyViolated));
+        assert_eq!(Code::try_new("abcde"), Err(CodeError::AnyOfViolated));
+        assert_eq!(
+            CodeError::AnyOfViolated.to_string(),
+            "Code does not match any of the alternatives."
+        );
+    }
+
+    #[test]
+    fn test_not() {
+        #[nutype(
+            sanitize(trim, lowercase),
+            validate(not_empty, not(any_of(predicate = |s| s.starts_with("admin"), predicate = |s| s == "root"))),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Login(String);
+
+        assert_eq!(Login::try_new(" Alice ").unwrap().into_inner(), "alice");
+        assert_eq!(
+            Login::try_new("Administrator"),
+            Err(LoginError::NotViolated)
+        );
+        assert_eq!(Login::try_new("root"), Err(LoginError::NotViolated));
+        assert_eq!(
+            LoginError::NotViolated.to_string(),
+            "Login matches a rule, that it must not match."
+        );
+    }
+
+    #[test]
+    fn test_any_and_all() {
+        #[nutype(
+            validate(
+                not(predicate = |s| s.contains(' ')),
+                any(
+                    all(predicate = |s| s.starts_with('+'), len_char_min = 9),
+                    all(predicate = |s| s.starts_with("00"), len_char_min = 10),
+                ),
+            ),
+            derive(Debug, PartialEq)
+        )]
+        pub struct IntlPhone(String);
+
+        assert!(IntlPhone::try_new("+49301234").is_ok());
+        assert!(IntlPhone::try_new("0049301234").is_ok());
+        assert_eq!(
+            IntlPhone::try_new("+4930"),
+            Err(IntlPhoneError::AnyOfViolated)
+        );
+        assert_eq!(
+            IntlPhone::try_new("030123456"),
+            Err(IntlPhoneError::AnyOfViolated)
+        );
+        assert_eq!(
+            IntlPhone::try_new("+49 301234"),
+            Err(IntlPhoneError::NotViolated)
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        #[nutype(
+            validate(not_empty, all(len_char_max = 8, predicate = |s| s.is_ascii())),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Code(String);
+
+        assert!(Code::try_new("abc").is_ok());
+        assert_eq!(Code::try_new(""), Err(CodeError::NotEmptyViolated));
+        assert_eq!(Code::try_new("abcdefghi"), Err(CodeError::AllViolated));
+        assert_eq!(Code::try_new("äbc"), Err(CodeError::AllViolated));
+        assert_eq!(
+            CodeError::AllViolated.to_string(),
+            "Code does not meet all the rules."
+        );
+    }
+
+    #[test]
+    fn test_when() {
+        #[nutype(
+            sanitize(with = |s: String| s.replace(' ', "")),
+            validate(
+                predicate = |s| s.trim_start_matches('+').chars().all(|c| c.is_ascii_digit()),
+                when(predicate = |s| s.starts_with('+'), then(len_char_min = 9, len_char_max = 16)),
+                when(not(predicate = |s| s.starts_with('+')), then(len_char_max = 10)),
+            ),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Phone(String);
+
+        assert_eq!(
+            Phone::try_new("+49 30 1234567").unwrap().into_inner(),
+            "+49301234567"
+        );
+        assert_eq!(
+            Phone::try_new("030 123456").unwrap().into_inner(),
+            "030123456"
+        );
+        assert_eq!(Phone::try_new("+4930"), Err(PhoneError::WhenViolated));
+        assert_eq!(
+            Phone::try_new("030 1234 5678"),
+            Err(PhoneError::WhenViolated)
+        );
+        assert_eq!(
+            Phone::try_new("+49 30 abc"),
+            Err(PhoneError::PredicateViolated)
+        );
+        assert_eq!(
+            PhoneError::WhenViolated.to_string(),
+            "Phone does not meet the rules required by its format."
+        );
+    }
+
+    #[test]
+    fn test_validate_all() {
+        #[nutype(
+            profile = "strict",
+            validate(len_char_max = 10, not(predicate = |s| s.contains(' '))),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Tag(String);
+
+        let errors: Vec<TagError> = Tag::validate_all("too long tag").collect();
+        assert_eq!(
+            errors,
+            vec![TagError::LenCharMaxViolated, TagError::NotViolated]
+        );
+    }
+}
+
+#[cfg(test)]
+mod attribute_syntax {
+    use super::*;
+
+    const MAX_LEN: usize = 5;
+
+    #[test]
+    fn test_trailing_commas() {
+        #[nutype(
+            sanitize(trim,),
+            validate(not_empty, len_char_max = 5,),
+            derive(Debug, PartialEq,)
+        )]
+        pub struct Name(String);
+
+        assert_eq!(Name::try_new(" abc ").unwrap().into_inner(), "abc");
+        assert_eq!(Name::try_new("abcdef"), Err(NameError::LenCharMaxViolated));
+    }
+
+    #[test]
+    fn test_complex_expressions() {
+        #[nutype(
+            sanitize(with = |s: String| s.split(',').map(str::trim).collect::<Vec<_>>().join(",")),
+            validate(
+                len_char_max = MAX_LEN * 2 + 1,
+                predicate = |s| !matches!(s, "a,b" | "b,a"),
+            ),
+            derive(Debug, PartialEq)
+        )]
+        pub struct Pair(String);
+
+        assert_eq!(Pair::try_new("x , y").unwrap().into_inner(), "x,y");
+        assert_eq!(Pair::try_new("a, b"), Err(PairError::PredicateViolated));
+        assert_eq!(
+            Pair::try_new("abcdef,ghijkl"),
+            Err(PairError::LenCharMaxViolated)
+        );
+    }
+}
+
+#[cfg(test)]
+mod bytes {
+    use super::*;
+
+    #[nutype(
+        bytes,
+        sanitize(trim),
+        validate(not_empty, len_char_max = 8),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Token(String);
+
+    #[test]
+    fn test_validate_bytes() {
+        assert!(Token::validate_bytes(b" abc ").is_ok());
+        assert!(matches!(
+            Token::validate_bytes(b"   "),
+            Err(TokenBytesError::Validate(TokenError::NotEmptyViolated))
+        ));
+        assert!(matches!(
+            Token::validate_bytes(&[0xff, 0xfe]),
+            Err(TokenBytesError::Utf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_utf8() {
+        let token = Token::try_from_utf8(b" abc ".to_vec()).unwrap();
+        assert_eq!(token.into_inner(), "abc");
+
+        let err = Token::try_from_utf8(b"too long value".to_vec()).unwrap_err();
+        assert!(matches!(
+            err,
+            TokenBytesError::Validate(TokenError::LenCharMaxViolated)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Failed to convert into Token: Token is too long. The value length must be at most 8 characters."
+        );
+    }
+
+    #[test]
+    fn test_allocating_sanitizer() {
+        #[nutype(bytes, sanitize(lowercase), validate(predicate = |s| s != "admin"), derive(Debug))]
+        pub struct Login(String);
+
+        assert!(matches!(
+            Login::validate_bytes(b"ADMIN"),
+            Err(LoginBytesError::Validate(LoginError::PredicateViolated))
+        ));
+        assert_eq!(
+            Login::try_from_utf8(b"Alice".to_vec())
+                .unwrap()
+                .into_inner(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn test_without_validation() {
+        #[nutype(bytes, sanitize(trim), derive(Debug))]
+        pub struct Note(String);
+
+        assert_eq!(
+            Note::try_from_utf8(b" hi ".to_vec()).unwrap().into_inner(),
+            "hi"
+        );
+        assert!(matches!(
+            Note::try_from_utf8(vec![0xff]),
+            Err(NoteBytesError::Utf8(_))
+        ));
+    }
+}
+
+#[cfg(feature = "wasm-bindgen")]
+mod wasm {
+    use super::*;
+
+    // Only the successful paths are tested, because `JsValue` can not be created outside of wasm.
+    #[test]
+    fn test_js_new_and_value() {
+        #[nutype(wasm, sanitize(trim), validate(not_empty), derive(Debug))]
+        pub struct Username(String);
+
+        let username = Username::js_new("  alice ".to_string()).unwrap();
+        assert_eq!(username.js_value(), "alice");
+    }
+
+    #[test]
+    fn test_js_new_without_validation() {
+        #[nutype(wasm, sanitize(lowercase), derive(Debug))]
+        pub struct Tag(String);
+
+        let tag = Tag::js_new("RUST".to_string());
+        assert_eq!(tag.js_value(), "rust");
+    }
+}
+
+mod duplicates {
+    use super::*;
+
+    #[test]
+    fn test_last_wins() {
+        #[nutype(
+            duplicates = "last_wins",
+            sanitize(trim, lowercase, trim),
+            validate(not_empty, len_char_max = 3, len_char_max = 5),
+            derive(Debug, PartialEq, AsRef)
+        )]
+        pub struct Nickname(String);
+
+        assert_eq!(Nickname::MAX_LEN, 5);
+        assert_eq!(Nickname::try_new("  ABCDE ").unwrap().as_ref(), "abcde");
+        assert_eq!(
+            Nickname::try_new("abcdef"),
+            Err(NicknameError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_repeated_with_sanitizers_are_kept() {
+        #[nutype(
+            duplicates = "last_wins",
+            sanitize(with = |s: String| format!("{s}!"), with = |s: String| format!("{s}?")),
+            derive(Debug, AsRef)
+        )]
+        pub struct Exclamation(String);
+
+        assert_eq!(Exclamation::new("hey").as_ref(), "hey!?");
+    }
+}
+
+mod eq_str {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_eq_str_and_borrow() {
+        #[nutype(
+            eq_str,
+            sanitize(trim),
+            validate(not_empty),
+            derive(Debug, PartialEq, Eq, Hash, Borrow)
+        )]
+        pub struct Username(String);
+
+        let alice = Username::try_new(" alice ").unwrap();
+        assert!(alice == "alice");
+        assert!(alice == *"alice");
+        assert!("alice" == alice);
+        assert!(*"alice" == alice);
+        assert!(alice != " alice ");
+
+        let mut scores: HashMap<Username, u32> = HashMap::new();
+        scores.insert(alice, 42);
+        assert_eq!(scores.get("alice"), Some(&42));
+        assert_eq!(scores.get("bob"), None);
+    }
+
+    #[test]
+    fn test_eq_str_with_borrowed_inner_type() {
+        #[nutype(eq_str, validate(not_empty), derive(Debug))]
+        pub struct Name<'a>(&'a str);
+
+        let name = Name::try_new("bob").unwrap();
+        assert!(name == "bob");
+        assert!("bob" == name);
+    }
+
+    #[test]
+    fn test_eq_str_with_store_as() {
+        #[nutype(eq_str, storage = Box<str>, derive(Debug))]
+        pub struct Tag(String);
+
+        let tag = Tag::new("rust");
+        assert!(tag == "rust");
+        assert!("go" != tag);
+    }
+}
+
+mod cfg {
+    use super::*;
+
+    // `cfg` and `cfg_attr` are evaluated before `#[nutype]` is expanded, so a disabled type
+    // generates nothing, that could clash with the enabled type of the same name.
+    #[nutype(validate(not_empty), derive(Debug))]
+    #[cfg(not(test))]
+    pub struct Platform(String);
+
+    #[nutype(sanitize(trim), validate(not_empty), derive(Debug, AsRef))]
+    #[cfg(test)]
+    #[cfg_attr(test, doc = "Name of the target platform.")]
+    pub struct Platform(String);
+
+    #[test]
+    fn test_cfg_enabled_type() {
+        assert_eq!(Platform::try_new(" linux ").unwrap().as_ref(), "linux");
+        assert_eq!(
+            Platform::try_new("").unwrap_err(),
+            PlatformError::NotEmptyViolated
+        );
+    }
+}
+
+mod repr_transparent {
+    use super::*;
+
+    #[test]
+    fn test_as_inner_slice() {
+        #[nutype(repr_transparent, sanitize(trim), validate(not_empty), derive(Debug))]
+        pub struct Tag(String);
+
+        let tags = [
+            Tag::try_new(" rust ").unwrap(),
+            Tag::try_new("ffi").unwrap(),
+        ];
+        assert_eq!(Tag::as_inner_slice(&tags), ["rust", "ffi"]);
+    }
+}
+
+mod try_from_iter {
+    use super::*;
+    use alloc::collections::BTreeSet;
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(not_empty),
+        derive(Debug, PartialEq, Eq, PartialOrd, Ord, AsRef)
+    )]
+    pub struct Tag(String);
+
+    #[test]
+    fn test_try_from_iter() {
+        let tags: BTreeSet<Tag> = Tag::try_from_iter(["Rust", " rust ", "FFI"]).unwrap();
+        let tags: Vec<&str> = tags.iter().map(AsRef::as_ref).collect();
+        assert_eq!(tags, ["ffi", "rust"]);
+
+        let err =
+            Tag::try_from_iter::<Vec<Tag>, _>(vec!["a".to_string(), "  ".to_string()]).unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.error, TagError::NotEmptyViolated);
+        assert_eq!(err.to_string(), "Item 1 is invalid: Tag is empty.");
+    }
+}
+
+mod named_field {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim, lowercase),
+        validate(not_empty),
+        derive(Debug, Clone, PartialEq, AsRef)
+    )]
+    pub struct Email {
+        value: String,
+    }
+
+    #[test]
+    fn test_named_field() {
+        let email = Email::try_new(" Bob@Example.com ").unwrap();
+        assert_eq!(email.value(), "bob@example.com");
+        assert_eq!(email.as_ref(), "bob@example.com");
+        assert_eq!(email.clone().into_inner(), "bob@example.com");
+        assert_eq!(Email::try_new("  "), Err(EmailError::NotEmptyViolated));
+    }
+
+    #[nutype(sanitize(trim), derive(Debug))]
+    pub struct Nickname {
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_optional_named_field() {
+        let nickname = Nickname::new(Some(" neo ".to_string()));
+        assert_eq!(nickname.name().as_deref(), Some("neo"));
+    }
+}
+
+mod map {
+    use super::*;
+
+    #[test]
+    fn test_try_map() {
+        #[nutype(
+            sanitize(trim),
+            validate(len_char_max = 5),
+            derive(Debug, PartialEq, AsRef)
+        )]
+        pub struct Code(String);
+
+        let code = Code::try_new("ab").unwrap();
+        let code = code.try_map(|s| format!(" {s}cd ")).unwrap();
+        assert_eq!(code.as_ref(), "abcd");
+        assert_eq!(
+            code.try_map(|s| s.repeat(2)),
+            Err(CodeError::LenCharMaxViolated)
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        #[nutype(sanitize(trim, lowercase), derive(Debug, AsRef))]
+        pub struct Slug(String);
+
+        let slug = Slug::new("hello").map(|s| format!("{s} World "));
+        assert_eq!(slug.as_ref(), "hello world");
+    }
+}
+
+#[allow(deprecated)]
+mod renamed_from {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 10),
+        renamed_from = "Nickname",
+        since = "2.0.0",
+        derive(Debug, PartialEq, FromStr)
+    )]
+    pub struct Username(String);
+
+    #[test]
+    fn test_old_names_keep_working() {
+        let nickname: Nickname = Nickname::try_new(" alice ").unwrap();
+        assert_eq!(nickname, Username::try_new("alice").unwrap());
+
+        let err: NicknameError = Nickname::try_new("").unwrap_err();
+        assert_eq!(err, UsernameError::NotEmptyViolated);
+
+        let err: NicknameError = " ".parse::<Nickname>().unwrap_err();
+        assert_eq!(err, UsernameError::NotEmptyViolated);
+    }
+
+    #[test]
+    fn test_optional_type() {
+        #[nutype(renamed_from = "MaybeNote", validate(len_char_max = 3), derive(Debug))]
+        pub struct Note(Option<String>);
+
+        assert!(MaybeNote::try_new(Some("abcd".to_string())).is_err());
+    }
+}
+
+mod test_examples {
+    use super::*;
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 10, predicate = |s| s.contains('@')),
+        test_examples(valid = ["a@b.c", " x@y "], invalid = ["", "x", "long@example.com"]),
+        derive(Debug)
+    )]
+    pub struct Email(String);
+
+    #[test]
+    fn test_generated_tests_compile() {
+        assert!(Email::try_new("a@b.c").is_ok());
+    }
+}
+
+mod on_invalid {
+    use super::*;
+    use core::cell::RefCell;
+
+    thread_local! {
+        static REJECTED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn take_rejected() -> Vec<String> {
+        REJECTED.with(|rejected| rejected.take())
+    }
+
+    fn record_username(err: &UsernameError, value: &str) {
+        REJECTED.with(|rejected| rejected.borrow_mut().push(format!("{err:?}: {value}")));
+    }
+
+    #[nutype(
+        sanitize(trim),
+        validate(not_empty, len_char_max = 5),
+        on_invalid = record_username,
+        derive(Debug)
+    )]
+    pub struct Username(String);
+
+    #[test]
+    fn test_callback_gets_error_and_sanitized_value() {
+        take_rejected();
+        assert!(Username::try_new(" alice ").is_ok());
+        assert!(Username::try_new("   ").is_err());
+        assert!(Username::try_new(" alexander ").is_err());
+        assert_eq!(
+            take_rejected(),
+            vec![
+                "NotEmptyViolated: ".to_string(),
+                "LenCharMaxViolated: alexander".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_new_into() {
+        take_rejected();
+        let mut buffer = String::new();
+        assert!(Username::try_new_into("alexander", &mut buffer).is_err());
+        assert_eq!(take_rejected(), vec!["LenCharMaxViolated: alexander"]);
+    }
+
+    fn record_password(err: &PasswordError) {
+        REJECTED.with(|rejected| rejected.borrow_mut().push(format!("{err:?}")));
+    }
+
+    #[nutype(
+        validate(len_char_min = 8),
+        secret,
+        on_invalid = record_password,
+        derive(Debug)
+    )]
+    pub struct Password(String);
+
+    #[test]
+    fn test_secret_gets_only_error() {
+        take_rejected();
+        assert!(Password::try_new("hunter2").is_err());
+        assert_eq!(take_rejected(), vec!["LenCharMinViolated"]);
+    }
+
+    fn record_note(err: &NoteError, value: &Option<String>) {
+        REJECTED.with(|rejected| rejected.borrow_mut().push(format!("{err:?}: {value:?}")));
+    }
+
+    #[nutype(required, on_invalid = record_note, derive(Debug))]
+    pub struct Note(Option<String>);
+
+    #[test]
+    fn test_optional_type() {
+        take_rejected();
+        assert!(Note::try_new(None).is_err());
+        assert_eq!(take_rejected(), vec!["RequiredViolated: None"]);
+    }
+}
+
+mod try_with {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct NonAsciiError {
+        position: usize,
+    }
+
+    impl core::fmt::Display for NonAsciiError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "non-ASCII character at {}", self.position)
+        }
+    }
+
+    fn to_ascii_lowercase(value: String) -> Result<String, NonAsciiError> {
+        match value.find(|c: char| !c.is_ascii()) {
+            Some(position) => Err(NonAsciiError { position }),
+            None => Ok(value.to_ascii_lowercase()),
+        }
+    }
+
+    #[nutype(
+        sanitize(trim, try_with(to_ascii_lowercase, error = NonAsciiError)),
+        validate(not_empty, len_char_max = 10),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Hostname(String);
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            Hostname::try_new(" Example ").unwrap().into_inner(),
+            "example"
+        );
+        assert_eq!(
+            Hostname::try_new(" bücher "),
+            Err(HostnameError::TryWithViolated(NonAsciiError {
+                position: 1
+            }))
+        );
+        assert_eq!(
+            Hostname::try_new("   "),
+            Err(HostnameError::NotEmptyViolated)
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let err = Hostname::try_new("bücher").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Hostname could not be sanitized: non-ASCII character at 1"
+        );
+        assert_eq!(err.code(), "try_with");
+    }
+
+    #[test]
+    fn test_eq_raw() {
+        let hostname = Hostname::try_new("example").unwrap();
+        assert!(hostname.eq_raw(" EXAMPLE "));
+        assert!(!hostname.eq_raw("bücher"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum SlugError {
+        NonAscii,
+        Empty,
+    }
+
+    impl From<NonAsciiError> for SlugError {
+        fn from(_: NonAsciiError) -> Self {
+            SlugError::NonAscii
+        }
+    }
+
+    fn validate_slug(slug: &str) -> Result<(), SlugError> {
+        if slug.is_empty() {
+            return Err(SlugError::Empty);
+        }
+        Ok(())
+    }
+
+    #[nutype(
+        sanitize(try_with(to_ascii_lowercase, error = NonAsciiError)),
+        validate(with = validate_slug, error = SlugError),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Slug(String);
+
+    #[test]
+    fn test_custom_error_is_converted_with_from() {
+        assert_eq!(Slug::try_new("Rust").unwrap().into_inner(), "rust");
+        assert_eq!(Slug::try_new("straße"), Err(SlugError::NonAscii));
+        assert_eq!(Slug::try_new(""), Err(SlugError::Empty));
+    }
+
+    #[nutype(
+        sanitize(try_with(to_ascii_lowercase, error = NonAsciiError)),
+        validate(not_empty),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Tag(Option<String>);
+
+    #[test]
+    fn test_optional() {
+        assert_eq!(
+            Tag::try_new(Some("Rust".to_string())).unwrap().into_inner(),
+            Some("rust".to_string())
+        );
+        assert_eq!(Tag::try_new(None).unwrap().into_inner(), None);
+        assert_eq!(
+            Tag::try_new(Some("ß".to_string())),
+            Err(TagError::TryWithViolated(NonAsciiError { position: 0 }))
+        );
+    }
+
+    #[nutype(
+        profile = "strict",
+        sanitize(try_with(to_ascii_lowercase, error = NonAsciiError)),
+        validate(not_empty, len_char_max = 3),
+        derive(Debug)
+    )]
+    pub struct Code(String);
+
+    #[test]
+    fn test_validate_all() {
+        let errors: Vec<CodeError> = Code::validate_all("åbcd").collect();
+        assert_eq!(
+            errors,
+            vec![CodeError::TryWithViolated(NonAsciiError { position: 0 })]
+        );
+        let errors: Vec<CodeError> = Code::validate_all("ABCD").collect();
+        assert_eq!(errors, vec![CodeError::LenCharMaxViolated]);
+    }
+}
+
+mod no_clone {
+    use super::*;
+
+    #[nutype(validate(len_char_min = 16), no_clone, derive(Debug, PartialEq, AsRef))]
+    pub struct ResetToken(String);
+
+    fn redeem(token: ResetToken) -> String {
+        token.into_inner()
+    }
+
+    #[test]
+    fn test_move_only_value() {
+        let token = ResetToken::try_new("0123456789abcdef").unwrap();
+        assert_eq!(token.as_ref(), "0123456789abcdef");
+        assert_eq!(redeem(token), "0123456789abcdef");
+    }
+}
+
+mod lossy {
+    use super::*;
+    use nutype::SanitizeWarning;
+
+    #[nutype(
+        lossy,
+        sanitize(trim, lowercase),
+        validate(not_empty, len_char_max = 20),
+        derive(Debug, PartialEq)
+    )]
+    pub struct Username(String);
+
+    #[test]
+    fn test_try_from_lossy() {
+        let (username, warnings) = Username::try_from_lossy("bob").unwrap();
+        assert_eq!(username, Username::try_new("bob").unwrap());
+        assert!(warnings.is_empty());
+
+        let (username, warnings) = Username::try_from_lossy(" Bob ").unwrap();
+        assert_eq!(username.into_inner(), "bob");
+        assert_eq!(
+            warnings,
+            vec![
+                SanitizeWarning {
+                    sanitizer: "trim",
+                    index: 0
+                },
+                SanitizeWarning {
+                    sanitizer: "lowercase",
+                    index: 1
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Sanitizer `trim` changed the value"
+        );
+
+        assert_eq!(
+            Username::try_from_lossy("   "),
+            Err(UsernameError::NotEmptyViolated)
+        );
+    }
+
+    #[test]
+    fn test_from_lossy() {
+        #[nutype(lossy, sanitize(trim), derive(Debug))]
+        pub struct Comment(String);
+
+        let (comment, warnings) = Comment::from_lossy("nice\n");
+        assert_eq!(comment.into_inner(), "nice");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].sanitizer, "trim");
+    }
+}
@@ -0,0 +1,25 @@
+mod repository {
+    use nutype::nutype;
+    use std::time::SystemTime;
+
+    // `pub(crate)` can't hide anything in a single crate, so the constructors are restricted to
+    // a crate relative path instead.
+    #[nutype(
+        constructor_visibility = pub(in crate::repository),
+        validate(not_in_future),
+        derive(Debug)
+    )]
+    pub struct CreatedAt(SystemTime);
+
+    pub fn now() -> CreatedAt {
+        CreatedAt::try_new_at(SystemTime::now(), SystemTime::now()).unwrap()
+    }
+}
+
+fn main() {
+    let _created_at = repository::now();
+    let _forged = repository::CreatedAt::try_new_at(
+        std::time::SystemTime::now(),
+        std::time::SystemTime::now(),
+    );
+}
@@ -0,0 +1,12 @@
+use nutype::nutype;
+
+#[derive(Debug)]
+pub struct Coordinates {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[nutype(ffi, derive(Debug))]
+pub struct Point(Coordinates);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+struct MyClock;
+
+#[nutype(validate(after = "2000-01-01", clock = MyClock))]
+pub struct EventTime(std::time::SystemTime);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(min = "1h", max = "30m"))]
+pub struct Timeout(std::time::Duration);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(within_last = "30 days"))]
+pub struct RecentEvent(std::time::SystemTime);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(after = "2024-02-30"))]
+pub struct EventTime(std::time::SystemTime);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(
+    validate(within_last = "7d"),
+    legacy(validate(within_last = "30d")),
+)]
+pub struct RecentEvent(std::time::SystemTime);
+
+fn main() {}
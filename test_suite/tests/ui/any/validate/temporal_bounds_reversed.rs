@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(after = "2024-01-01", before = "2023-01-01"))]
+pub struct EventTime(std::time::SystemTime);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use nutype::nutype;
+
+#[nutype(
+    validate(predicate = |v| !v.is_empty()),
+    derive(Debug)
+)]
+struct Names<T>(Vec<T>)
+where
+    Vec<T>: Clone;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(all(len_char_max = 10)))]
+pub struct Name(String);
+
+fn main() {}
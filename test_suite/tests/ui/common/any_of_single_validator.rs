@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(any_of(len_char_max = 10)))]
+pub struct Name(String);
+
+fn main() {}
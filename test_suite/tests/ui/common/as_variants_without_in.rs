@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), as_variants)]
+pub struct Color(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not(predicate = |c| c.is_ascii())))]
+pub struct Letter(char);
+
+fn main() {}
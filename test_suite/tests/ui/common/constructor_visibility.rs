@@ -0,0 +1,20 @@
+mod repository {
+    use nutype::nutype;
+
+    #[nutype(
+        constructor_visibility = pub(self),
+        sanitize(trim),
+        validate(not_empty),
+        derive(Debug)
+    )]
+    pub struct UserId(String);
+
+    pub fn load(raw: &str) -> UserId {
+        UserId::try_new(raw).unwrap()
+    }
+}
+
+fn main() {
+    let _id = repository::load("42");
+    let _forged = repository::UserId::try_new("42");
+}
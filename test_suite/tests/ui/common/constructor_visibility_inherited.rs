@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(constructor_visibility = , validate(not_empty))]
+pub struct UserId(String);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use nutype::nutype;
+
+#[nutype(
+    validate(with = validate_num, error = NumError, error = NumError)
+)]
+pub struct Num(i32);
+
+fn validate_num(_val: &i32) -> Result<(), NumError> {
+    Ok(())
+}
+
+#[derive(Debug)]
+enum NumError {
+    Invalid,
+}
+
+fn main () {}
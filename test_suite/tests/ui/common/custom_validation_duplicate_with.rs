@@ -0,0 +1,13 @@
+use nutype::nutype;
+
+#[nutype(
+    validate(with = |n: &i32| *n > 0, with = |n: &i32| *n < 100, error = NumError)
+)]
+pub struct Num(i32);
+
+#[derive(Debug)]
+enum NumError {
+    Invalid,
+}
+
+fn main () {}
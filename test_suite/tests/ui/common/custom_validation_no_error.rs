@@ -0,0 +1,16 @@
+use nutype::nutype;
+
+#[nutype(
+    validate(with = validate_num,)
+)]
+pub struct Num(i32);
+
+fn validate_num(val: &i32) -> Result<(), ()> {
+    if *val > 100 {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+fn main () {}
@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(
+    sanitize(trim),
+    derive(Debug, Default)
+)]
+pub struct Name(String);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive)]
+pub struct Amount(i32);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Display), display = 42)]
+pub struct Amount(i32);
+
+fn main () {}
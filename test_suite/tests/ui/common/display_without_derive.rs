@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), display = "Order #{}")]
+pub struct OrderId(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty))]
+pub struct Email {
+    value: String,
+    domain: String,
+}
+
+fn main () {}
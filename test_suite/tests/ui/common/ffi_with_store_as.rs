@@ -0,0 +1,10 @@
+use nutype::nutype;
+
+#[nutype(
+    ffi,
+    store_as(Box<str>, encode = |s| s.into_boxed_str(), decode = |s| s.to_string()),
+    derive(Debug)
+)]
+pub struct Name(String);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), getter = value)]
+pub struct Email {
+    value: String,
+}
+
+fn main () {}
@@ -0,0 +1,10 @@
+use nutype::nutype;
+
+#[nutype(
+    normalize_for_eq = |email| email.to_lowercase(),
+    hash_with = |email| email.len() as u64,
+    derive(PartialEq, Eq, Hash)
+)]
+pub struct Email(String);
+
+fn main() {}
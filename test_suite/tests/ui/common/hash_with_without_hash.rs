@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(hash_with = |id| *id, derive(PartialEq, Eq))]
+pub struct UserId(u64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(in = []))]
+pub struct Color(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), into(i64))]
+pub struct Amount(u32);
+
+fn main() {}
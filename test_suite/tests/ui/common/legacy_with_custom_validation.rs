@@ -0,0 +1,16 @@
+use nutype::nutype;
+
+fn validate_comment(_: &str) -> Result<(), CommentError> {
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct CommentError;
+
+#[nutype(
+    validate(len_char_max = 50),
+    legacy(validate(with = validate_comment, error = CommentError)),
+)]
+pub struct Comment(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(legacy(validate(len_char_max = 100)))]
+pub struct Comment(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty, messages(not_empty = "{type} must have at least {limit} characters.")))]
+pub struct Title(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty, messages(len_char_max = "{type} is too long.")))]
+pub struct Title(String);
+
+fn main() {}
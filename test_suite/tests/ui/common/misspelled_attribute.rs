@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(sanitise(trim), derive(Debug))]
+pub struct Name(String);
+
+fn main () {}
@@ -0,0 +1,12 @@
+use nutype::nutype;
+
+#[nutype(no_clone, derive(Debug))]
+pub struct Nonce(u64);
+
+impl Clone for Nonce {
+    fn clone(&self) -> Self {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(no_clone, derive(Debug, Clone))]
+pub struct Nonce(u64);
+
+fn main() {}
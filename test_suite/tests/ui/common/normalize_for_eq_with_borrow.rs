@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(
+    normalize_for_eq = |email| email.to_lowercase(),
+    derive(Debug, PartialEq, Eq, Hash, Borrow)
+)]
+pub struct Email(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not(less = 0, greater = 100)))]
+pub struct Score(i32);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::NutypeWrap;
+
+#[derive(NutypeWrap)]
+pub struct User(String);
+
+fn main() {}
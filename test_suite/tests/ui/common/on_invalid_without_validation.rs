@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+fn report(_err: &str) {}
+
+#[nutype(sanitize(trim), on_invalid = report)]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), derive(Debug, FromStr))]
+pub struct MiddleName(Option<String>);
+
+fn main() {}
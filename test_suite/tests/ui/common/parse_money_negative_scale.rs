@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(parse_money(scale = -1))]
+pub struct Price(f64);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(parse_money(precision = 2))]
+pub struct Price(f64);
+
+fn main () {}
@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty))]
+pub struct Username(String);
+
+#[nutype(refines = Username, sanitize(trim), validate(len_char_max = 8))]
+pub struct PremiumUsername(String);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+#[nutype(constructor = parse, validate(not_empty))]
+pub struct Username(String);
+
+fn main() {
+    let _ = Username::try_new("bob");
+}
@@ -0,0 +1,10 @@
+#![deny(deprecated)]
+
+use nutype::nutype;
+
+#[nutype(validate(not_empty), renamed_from = "Nickname", since = "2.0.0")]
+pub struct Username(String);
+
+fn main() {
+    let _ = Nickname::try_new("alice");
+}
@@ -0,0 +1,13 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), derive(Debug))]
+pub struct Name(String);
+
+fn main() {
+    // Even code in the same module cannot construct the type directly
+    let _name = Name(String::new());
+
+    // ... or mutate the inner value
+    let mut name = Name::try_new("Anna").unwrap();
+    name.0 = String::new();
+}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), schema(format = "email"))]
+pub struct Email(String);
+
+fn main() {}
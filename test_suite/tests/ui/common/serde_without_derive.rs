@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), serde(field = "value"))]
+pub struct Amount(u32);
+
+fn main() {}
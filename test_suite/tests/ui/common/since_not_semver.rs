@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), renamed_from = "Nickname", since = "v2")]
+pub struct Username(String);
+
+fn main() {}
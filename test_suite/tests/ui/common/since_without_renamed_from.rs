@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(not_empty), since = "2.0.0")]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(store_as(u8, encode = |s| s.len() as u8))]
+pub struct Name(String);
+
+fn main() {}
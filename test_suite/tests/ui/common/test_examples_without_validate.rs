@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(sanitize(trim), test_examples(valid = ["a"]))]
+pub struct Name(String);
+
+fn main () {}
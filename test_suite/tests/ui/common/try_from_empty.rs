@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(TryFrom), try_from())]
+pub struct Amount(i32);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), try_from(u8, u16))]
+pub struct Amount(u32);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use nutype::nutype;
+
+fn to_ascii(value: String) -> Result<String, String> {
+    Ok(value)
+}
+
+fn to_lowercase(value: String) -> Result<String, core::fmt::Error> {
+    Ok(value.to_lowercase())
+}
+
+#[nutype(
+    sanitize(try_with(to_ascii, error = String), try_with(to_lowercase, error = core::fmt::Error)),
+    validate(not_empty)
+)]
+pub struct Hostname(String);
+
+fn main() {}
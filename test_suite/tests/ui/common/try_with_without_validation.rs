@@ -0,0 +1,10 @@
+use nutype::nutype;
+
+fn to_ascii(value: String) -> Result<String, String> {
+    Ok(value)
+}
+
+#[nutype(sanitize(try_with(to_ascii, error = String)))]
+pub struct Hostname(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug), validation(greater = 0))]
+pub struct Amount(i32);
+
+fn main () {}
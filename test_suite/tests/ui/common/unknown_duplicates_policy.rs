@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(duplicates = "first_wins", validate(not_empty), derive(Debug))]
+pub struct Name(String);
+
+fn main() {}
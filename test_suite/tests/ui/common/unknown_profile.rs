@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(profile = "paranoid", derive(Debug))]
+pub struct Name(String);
+
+fn main() {}
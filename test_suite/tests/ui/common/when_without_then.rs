@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(when(predicate = |s| s.starts_with('+'), len_char_max = 16)))]
+pub struct Phone(String);
+
+fn main() {}
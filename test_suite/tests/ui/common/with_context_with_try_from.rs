@@ -0,0 +1,13 @@
+use nutype::nutype;
+
+pub struct Settings {
+    max_len: usize,
+}
+
+#[nutype(
+    validate(with_context = |name: &str, settings: &Settings| name.len() <= settings.max_len, context = Settings),
+    derive(Debug, TryFrom)
+)]
+pub struct Username(String);
+
+fn main() {}
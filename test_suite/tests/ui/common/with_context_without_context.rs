@@ -0,0 +1,10 @@
+use nutype::nutype;
+
+fn is_short(name: &str, max_len: &usize) -> bool {
+    name.len() <= *max_len
+}
+
+#[nutype(validate(not_empty, with_context = is_short), derive(Debug))]
+pub struct Username(String);
+
+fn main() {}
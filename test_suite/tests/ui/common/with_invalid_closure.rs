@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(sanitize(with = |s String| s.trim().to_string()))]
+pub struct Name(String);
+
+fn main() {}
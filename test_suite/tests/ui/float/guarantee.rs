@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(greater_or_equal = 0.0), guarantee)]
+pub struct Distance(f64);
+
+fn main() {}
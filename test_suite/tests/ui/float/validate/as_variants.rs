@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(in = [0.5, 1.0]), as_variants)]
+pub struct Ratio(f64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(greater = 0.0), default = -0.5, derive(Default))]
+pub struct Amount(f64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(max_dp = 16))]
+pub struct Amount(f64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(bounded, validate(greater = 170141183460469231731687303715884105727))]
+pub struct Nothing(i128);
+
+fn main() {}
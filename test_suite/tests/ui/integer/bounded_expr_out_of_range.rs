@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+const LIMIT: u128 = u128::MAX;
+
+#[nutype(bounded, validate(less = LIMIT))]
+pub struct Huge(u128);
+
+fn main() {}
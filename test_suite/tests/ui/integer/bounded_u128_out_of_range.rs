@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(bounded, validate(less_or_equal = 170141183460469231731687303715884105728))]
+pub struct Huge(u128);
+
+fn main() {}
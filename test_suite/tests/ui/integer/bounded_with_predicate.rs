@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(bounded, validate(greater_or_equal = 0, predicate = |n| n % 2 == 0))]
+pub struct Even(i32);
+
+fn main() {}
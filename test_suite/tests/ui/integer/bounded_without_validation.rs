@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(bounded, sanitize(with = |n| n.clamp(0, 10)))]
+pub struct Level(i32);
+
+fn main() {}
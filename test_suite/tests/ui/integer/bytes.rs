@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(bytes, validate(greater = 0))]
+pub struct Count(u32);
+
+fn main() {}
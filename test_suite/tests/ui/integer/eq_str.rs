@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(eq_str, derive(Debug, PartialEq))]
+pub struct Amount(i32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use nutype::nutype;
+
+#[nutype(
+    sanitize(clamp(0, 200)),
+    validate(less_or_equal = 100),
+    guarantee
+)]
+pub struct Percent(u8);
+
+fn main() {}
@@ -2,8 +2,8 @@ use nutype::nutype;
 
 #[nutype(
     sanitize(
-        with = |n| n,
-        with = |n| n,
+        clamp(0, 100),
+        clamp(0, 10),
     )
 )]
 pub struct Amount(i32);
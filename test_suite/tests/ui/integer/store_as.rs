@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(store_as(u8, encode = |n| n as u8, decode = |n| *n as u32))]
+pub struct Count(u32);
+
+fn main() {}
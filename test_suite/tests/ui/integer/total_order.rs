@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(total_order, derive(PartialEq, Eq))]
+pub struct Amount(i32);
+
+fn main() {}
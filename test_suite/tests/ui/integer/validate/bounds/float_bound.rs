@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(less_or_equal = 1.5e3))]
+pub struct Distance(i32);
+
+fn main () {}
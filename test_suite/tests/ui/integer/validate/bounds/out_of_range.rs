@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(less_or_equal = 300))]
+pub struct Level(u8);
+
+fn main() {}
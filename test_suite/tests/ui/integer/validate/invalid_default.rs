@@ -0,0 +1,9 @@
+use nutype::nutype;
+
+#[nutype(validate(greater_or_equal = -10, less = 0), default = -10, derive(Default))]
+pub struct Valid(i32);
+
+#[nutype(validate(greater_or_equal = -10, less = 0), default = 0, derive(Default))]
+pub struct Invalid(i32);
+
+fn main() {}
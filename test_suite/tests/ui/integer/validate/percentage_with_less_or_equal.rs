@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(percentage, less_or_equal = 50))]
+pub struct Discount(u8);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(unit_interval))]
+pub struct Ratio(u8);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use nutype::nutype;
+
+pub struct Settings {
+    pub banned: Vec<String>,
+}
+
+#[nutype(
+    bytes,
+    validate(
+        with_context = |s: &str, settings: &Settings| !settings.banned.iter().any(|b| b == s),
+        context = Settings,
+    )
+)]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug, FromStr))]
+pub struct Name<'a>(&'a str);
+
+fn main() {}
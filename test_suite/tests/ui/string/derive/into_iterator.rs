@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Debug, IntoIterator))]
+pub struct Name(String);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use nutype::nutype;
+
+fn encode(value: String) -> u8 {
+    value.len() as u8
+}
+
+fn decode(code: &u8) -> String {
+    "x".repeat(*code as usize)
+}
+
+#[nutype(
+    store_as(u8, encode = encode, decode = decode),
+    derive(Debug, AsRef)
+)]
+pub struct Xs(String);
+
+fn main() {}
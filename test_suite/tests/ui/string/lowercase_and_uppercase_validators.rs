@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(lowercase, uppercase))]
+pub struct Code(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(parse_money)]
+pub struct Amount(String);
+
+fn main() {}
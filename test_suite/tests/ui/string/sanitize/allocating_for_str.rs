@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(sanitize(trim, lowercase))]
+pub struct Email<'a>(&'a str);
+
+fn main() {}
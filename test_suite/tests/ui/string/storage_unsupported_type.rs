@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(storage = Box<String>, derive(Debug))]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(in = ["dark-blue", "dark_blue"]), as_variants)]
+pub struct Color(String);
+
+fn main() {}
@@ -0,0 +1,19 @@
+use nutype::nutype;
+
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(in = ["guest", "admin"]),
+    default = " Guest ",
+    derive(Default)
+)]
+pub struct Valid(String);
+
+#[nutype(
+    sanitize(trim),
+    validate(len_char_min = 3, len_char_max = 10),
+    default = "  x  ",
+    derive(Default)
+)]
+pub struct Invalid(String);
+
+fn main() {}
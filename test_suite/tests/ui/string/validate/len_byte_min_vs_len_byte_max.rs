@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(len_byte_min = 127, len_byte_max = 63))]
+pub struct Email(String);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(starts_with = ""))]
+pub struct Phone(String);
+
+fn main() {}
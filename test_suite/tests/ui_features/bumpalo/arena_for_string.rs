@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(arena, validate(not_empty))]
+pub struct Name(String);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use nutype::nutype_from_schema;
+
+#[nutype_from_schema(r#"{ "type": "string", "format": "email" }"#)]
+#[nutype::nutype(derive(Debug))]
+pub struct Email(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(nohash, derive(PartialEq, Eq, Hash))]
+pub struct Username(String);
+
+fn main() {}
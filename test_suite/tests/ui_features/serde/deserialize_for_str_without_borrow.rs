@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Deserialize))]
+pub struct Tag<'a>(&'a str);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(secret, derive(Debug, Serialize))]
+pub struct ApiKey(String);
+
+fn main() {}
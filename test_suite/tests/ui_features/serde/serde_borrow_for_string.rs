@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Deserialize), serde(borrow))]
+pub struct Tag(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Serialize), serde(borrow))]
+pub struct Tag<'a>(&'a str);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Serialize), serde(map))]
+pub struct Amount(u32);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(secret(zeroize), derive(Debug, Clone, Copy))]
+pub struct Pin(u32);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(BorshSerialize, BorshDeserialize))]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Clone, ValueParserFactory))]
+pub struct Username(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(validate(len_byte_max = 16), storage = heapless::String)]
+pub struct DeviceName(String);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(encoding = "hex", derive(Debug, FromStr, Display))]
+pub struct Checksum([u8; 4]);
+
+fn main () {}
@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(derive(Archive))]
+pub struct Username(String);
+
+fn main() {}
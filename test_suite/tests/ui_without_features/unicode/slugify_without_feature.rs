@@ -0,0 +1,6 @@
+use nutype::nutype;
+
+#[nutype(sanitize(trim, slugify), derive(Debug))]
+pub struct Slug(String);
+
+fn main() {}